@@ -5,13 +5,15 @@
 //! that can be caught by user-defined error hooks.
 
 use std::{
+    any::{Any, TypeId},
     cell::RefCell,
     error,
     fmt::{self, Display},
     future::Future,
     mem, ops,
     pin::Pin,
-    sync::Arc,
+    str::FromStr,
+    sync::{Arc, RwLock},
     task::{Context, Poll},
 };
 
@@ -23,14 +25,93 @@ use std::{
 pub type Result<T, E = Error> = core::result::Result<T, E>;
 
 /// A generic wrapper for any error.
-#[derive(Debug, Clone)]
-#[repr(transparent)]
-pub struct Error(Arc<dyn error::Error + Send + Sync>);
+///
+/// The wrapper keeps track of the concrete type of the error, so that you can branch on it with
+/// [`downcast_ref`](Error::downcast_ref) or [`downcast`](Error::downcast):
+///
+/// ```
+/// # use throw_error::Error;
+/// #[derive(Debug)]
+/// struct AuthError;
+///
+/// impl std::fmt::Display for AuthError {
+///     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+///         f.write_str("not logged in")
+///     }
+/// }
+///
+/// impl std::error::Error for AuthError {}
+///
+/// let error = Error::from(AuthError);
+/// assert!(error.downcast_ref::<AuthError>().is_some());
+/// assert!(error.downcast_ref::<std::io::Error>().is_none());
+/// ```
+///
+/// An error that has been serialized, for example to send it from the server to the client,
+/// only keeps its type if the type has been registered with [`register_error_type`].
+#[derive(Clone)]
+pub struct Error {
+    error: Arc<dyn error::Error + Send + Sync>,
+    // the same allocation as `error`, which allows downcasting to the concrete type
+    any: Arc<dyn Any + Send + Sync>,
+}
 
 impl Error {
     /// Converts the wrapper into the inner reference-counted error.
     pub fn into_inner(self) -> Arc<dyn error::Error + Send + Sync> {
-        Arc::clone(&self.0)
+        Arc::clone(&self.error)
+    }
+
+    /// Returns `true` if the inner error is of type `T`.
+    pub fn is<T>(&self) -> bool
+    where
+        T: error::Error + Send + Sync + 'static,
+    {
+        self.any.is::<T>()
+    }
+
+    /// Returns a reference to the inner error if it is of type `T`.
+    pub fn downcast_ref<T>(&self) -> Option<&T>
+    where
+        T: error::Error + Send + Sync + 'static,
+    {
+        self.any.downcast_ref()
+    }
+
+    /// Converts the wrapper into the inner error if it is of type `T`, or returns it unchanged
+    /// otherwise.
+    pub fn downcast<T>(self) -> Result<Arc<T>, Self>
+    where
+        T: error::Error + Send + Sync + 'static,
+    {
+        Arc::clone(&self.any).downcast().map_err(|_| self)
+    }
+
+    /// Serializes the error, returning the tag its type was registered with using
+    /// [`register_error_type`], if any, along with the serialized data.
+    ///
+    /// Errors of types that have not been registered are serialized using their
+    /// [`Display`] implementation.
+    pub fn serialize(&self) -> (Option<&'static str>, String) {
+        let type_id = (*self.any).type_id();
+        ERROR_TYPES
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .find(|ty| ty.type_id == type_id)
+            .and_then(|ty| Some((Some(ty.tag), (ty.ser)(self)?)))
+            .unwrap_or_else(|| (None, self.to_string()))
+    }
+
+    /// Restores an error serialized with [`Error::serialize`], if its tag belongs to a type
+    /// that has been registered with [`register_error_type`].
+    pub fn deserialize(tag: &str, data: &str) -> Option<Self> {
+        ERROR_TYPES
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .find(|ty| ty.tag == tag)
+            .and_then(|ty| (ty.de)(data))
     }
 }
 
@@ -38,13 +119,19 @@ impl ops::Deref for Error {
     type Target = Arc<dyn error::Error + Send + Sync>;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.error
+    }
+}
+
+impl fmt::Debug for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Error").field(&self.error).finish()
     }
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.0)
+        write!(f, "{}", self.error)
     }
 }
 
@@ -53,10 +140,61 @@ where
     T: error::Error + Send + Sync + 'static,
 {
     fn from(value: T) -> Self {
-        Error(Arc::new(value))
+        let value = Arc::new(value);
+        Error {
+            error: Arc::clone(&value) as _,
+            any: value,
+        }
     }
 }
 
+/* Serialization */
+
+type SerializeFn = Box<dyn Fn(&Error) -> Option<String> + Send + Sync>;
+type DeserializeFn = Box<dyn Fn(&str) -> Option<Error> + Send + Sync>;
+
+struct ErrorType {
+    tag: &'static str,
+    type_id: TypeId,
+    ser: SerializeFn,
+    de: DeserializeFn,
+}
+
+static ERROR_TYPES: RwLock<Vec<ErrorType>> = RwLock::new(Vec::new());
+
+/// Registers an error type under the given tag, so that it keeps its type when it is serialized
+/// with [`Error::serialize`] and restored with [`Error::deserialize`].
+///
+/// This uses the type's [`Display`] and [`FromStr`] implementations. To use some other format,
+/// see [`register_error_type_with`].
+pub fn register_error_type<T>(tag: &'static str)
+where
+    T: error::Error + FromStr + Send + Sync + 'static,
+{
+    register_error_type_with::<T>(tag, T::to_string, |data| data.parse().ok())
+}
+
+/// Registers an error type under the given tag, using the given functions to serialize and
+/// deserialize it.
+///
+/// Registering a type or tag a second time replaces the earlier registration.
+pub fn register_error_type_with<T>(
+    tag: &'static str,
+    ser: fn(&T) -> String,
+    de: fn(&str) -> Option<T>,
+) where
+    T: error::Error + Send + Sync + 'static,
+{
+    let mut types = ERROR_TYPES.write().unwrap_or_else(|e| e.into_inner());
+    types.retain(|ty| ty.tag != tag && ty.type_id != TypeId::of::<T>());
+    types.push(ErrorType {
+        tag,
+        type_id: TypeId::of::<T>(),
+        ser: Box::new(move |error| error.downcast_ref::<T>().map(ser)),
+        de: Box::new(move |data| de(data).map(Error::from)),
+    });
+}
+
 /// Implements behavior that allows for global or scoped error handling.
 ///
 /// This allows for both "throwing" errors to register them, and "clearing" errors when they are no
@@ -69,6 +207,16 @@ pub trait ErrorHook: Send + Sync {
 
     /// Clears the error associated with the given identifier.
     fn clear(&self, id: &ErrorId);
+
+    /// Handles an error that replaces the one previously thrown with the given identifier,
+    /// returning the identifier under which it is now registered.
+    ///
+    /// By default, this clears the old error and throws the new one. Hooks that want an error
+    /// to keep its identity across re-renders can reuse `id` instead.
+    fn rethrow(&self, id: &ErrorId, error: Error) -> ErrorId {
+        self.clear(id);
+        self.throw(error)
+    }
 }
 
 /// A unique identifier for an error. This is returned when you call [`throw`], which calls a
@@ -120,6 +268,16 @@ pub fn throw(error: impl Into<Error>) -> ErrorId {
         .unwrap_or_default()
 }
 
+/// Replaces the error previously thrown with the given identifier, using the error hook set by
+/// [`set_error_hook`].
+pub fn rethrow(id: &ErrorId, error: impl Into<Error>) -> ErrorId {
+    ERROR_HOOK
+        .with_borrow(|hook| {
+            hook.as_ref().map(|hook| hook.rethrow(id, error.into()))
+        })
+        .unwrap_or_default()
+}
+
 /// Clears the given error from the current error hook.
 pub fn clear(id: &ErrorId) {
     ERROR_HOOK
@@ -24,19 +24,48 @@
 //! Executor::spawn_local(async { /* ... */ });
 //! # }
 //! ```
+//!
+//! ## Testing
+//!
+//! Tests that need spawned tasks to have run before they check their results can use
+//! [`Executor::init_manual`], which only runs tasks when [`Executor::flush`] is called. Instead
+//! of waiting for an executor to get around to them, a test can update some state, flush, and
+//! then assert:
+//!
+//! ```rust
+//! use any_spawner::Executor;
+//! use std::{cell::Cell, rc::Rc};
+//!
+//! _ = Executor::init_manual();
+//!
+//! let ran = Rc::new(Cell::new(false));
+//! Executor::spawn_local({
+//!     let ran = Rc::clone(&ran);
+//!     async move { ran.set(true) }
+//! });
+//! assert!(!ran.get());
+//!
+//! Executor::flush();
+//! assert!(ran.get());
+//! ```
 
 #![forbid(unsafe_code)]
 #![deny(missing_docs)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
-use std::{future::Future, pin::Pin, sync::OnceLock};
+use std::{future::Future, pin::Pin, sync::OnceLock, time::Duration};
 use thiserror::Error;
 
+mod manual;
+#[cfg(feature = "futures-executor")]
+mod timer;
+
 pub(crate) type PinnedFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
 pub(crate) type PinnedLocalFuture<T> = Pin<Box<dyn Future<Output = T>>>;
 
 static SPAWN: OnceLock<fn(PinnedFuture<()>)> = OnceLock::new();
 static SPAWN_LOCAL: OnceLock<fn(PinnedLocalFuture<()>)> = OnceLock::new();
+static SLEEP: OnceLock<fn(Duration) -> PinnedLocalFuture<()>> = OnceLock::new();
 
 /// Errors that can occur when using the executor.
 #[derive(Error, Debug)]
@@ -46,6 +75,16 @@ pub enum ExecutorError {
     AlreadySet,
 }
 
+/// Spawns futures that cannot be sent across threads, in place of the global executor.
+///
+/// This lets part of an application run its tasks on a scheduler of its own, like a game loop
+/// that polls them at a specific point in each frame, while the rest of the application uses
+/// the global [`Executor`].
+pub trait CustomSpawner: Send + Sync {
+    /// Spawns a [`Future`] that cannot be sent across threads.
+    fn spawn_local(&self, fut: Pin<Box<dyn Future<Output = ()>>>);
+}
+
 /// A global async executor that can spawn tasks.
 pub struct Executor;
 
@@ -115,9 +154,89 @@ impl Executor {
         });
         _ = rx.await;
     }
+
+    /// Waits until the given amount of time has passed, using the timer of the current async
+    /// executor.
+    /// ```rust
+    /// use any_spawner::Executor;
+    /// use std::time::Duration;
+    /// # if false {
+    /// Executor::spawn_local(async {
+    ///     Executor::sleep(Duration::from_millis(250)).await;
+    ///     /* ... */
+    /// });
+    /// # }
+    /// ```
+    #[track_caller]
+    pub fn sleep(duration: Duration) -> impl Future<Output = ()> {
+        let sleep = SLEEP.get().map(|sleep| sleep(duration));
+        if sleep.is_none() {
+            #[cfg(all(debug_assertions, feature = "tracing"))]
+            tracing::error!(
+                "At {}, tried to wait with Executor::sleep() before the \
+                 Executor had been set.",
+                std::panic::Location::caller()
+            );
+            #[cfg(all(debug_assertions, not(feature = "tracing")))]
+            panic!(
+                "At {}, tried to wait with Executor::sleep() before the \
+                 Executor had been set.",
+                std::panic::Location::caller()
+            );
+        }
+        async move {
+            if let Some(sleep) = sleep {
+                sleep.await;
+            }
+        }
+    }
+
+    /// Moves the clock used by [`Executor::sleep`] forward, when using the executor set by
+    /// [`Executor::init_manual`]. Timers that are due are woken in the order they are due, and
+    /// the tasks they wake are [flushed](Executor::flush) before the clock moves on to the next
+    /// timer, so that tasks which start new timers see the time at which they were woken.
+    ///
+    /// With any other executor, this does nothing.
+    pub fn advance_time(duration: Duration) {
+        manual::advance_time(duration);
+    }
+
+    /// Runs the tasks spawned on this thread until all of them have either finished or are
+    /// waiting for something that hasn't happened yet, when using the executor set by
+    /// [`Executor::init_manual`]. This includes tasks spawned while flushing, and tasks that are
+    /// woken while flushing.
+    ///
+    /// With any other executor, this does nothing.
+    ///
+    /// # Panics
+    /// Panics if tasks keep waking one another (or themselves) so that they are polled more than
+    /// 100,000 times in one flush, which usually means there is a livelock.
+    pub fn flush() {
+        manual::flush();
+    }
 }
 
 impl Executor {
+    /// Globally sets an executor that only runs tasks when [`Executor::flush`] is called, which
+    /// makes it possible to test asynchronous code deterministically.
+    ///
+    /// Tasks are queued on the thread that spawns them, and are run by calling
+    /// [`Executor::flush`] on that thread. This includes thread-safe tasks spawned with
+    /// [`Executor::spawn`]. Time only passes for [`Executor::sleep`] when it is moved forward
+    /// with [`Executor::advance_time`].
+    ///
+    /// Returns `Err(_)` if an executor has already been set.
+    pub fn init_manual() -> Result<(), ExecutorError> {
+        SPAWN
+            .set(|fut| manual::spawn(fut))
+            .map_err(|_| ExecutorError::AlreadySet)?;
+        SPAWN_LOCAL
+            .set(manual::spawn)
+            .map_err(|_| ExecutorError::AlreadySet)?;
+        _ = SLEEP.set(|duration| Box::pin(manual::sleep(duration)));
+        Ok(())
+    }
+
     /// Globally sets the [`tokio`] runtime as the executor used to spawn tasks.
     ///
     /// Returns `Err(_)` if an executor has already been set.
@@ -136,6 +255,7 @@ impl Executor {
                 tokio::task::spawn_local(fut);
             })
             .map_err(|_| ExecutorError::AlreadySet)?;
+        _ = SLEEP.set(|duration| Box::pin(tokio::time::sleep(duration)));
         Ok(())
     }
 
@@ -157,6 +277,26 @@ impl Executor {
                 wasm_bindgen_futures::spawn_local(fut);
             })
             .map_err(|_| ExecutorError::AlreadySet)?;
+        _ = SLEEP.set(|duration| {
+            use wasm_bindgen::{JsCast, JsValue};
+
+            let promise = js_sys::Promise::new(&mut |resolve, _| {
+                let set_timeout = js_sys::Reflect::get(
+                    &js_sys::global(),
+                    &"setTimeout".into(),
+                )
+                .expect("setTimeout is not available")
+                .unchecked_into::<js_sys::Function>();
+                _ = set_timeout.call2(
+                    &JsValue::UNDEFINED,
+                    &resolve,
+                    &(duration.as_millis() as f64).into(),
+                );
+            });
+            Box::pin(async move {
+                _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+            })
+        });
         Ok(())
     }
 
@@ -180,6 +320,7 @@ impl Executor {
                 main_context.spawn_local(fut);
             })
             .map_err(|_| ExecutorError::AlreadySet)?;
+        _ = SLEEP.set(|duration| glib::timeout_future(duration));
         Ok(())
     }
 
@@ -224,6 +365,9 @@ impl Executor {
                 });
             })
             .map_err(|_| ExecutorError::AlreadySet)?;
+        // this executor doesn't have a timer, so sleeps share a single thread that waits for each
+        // deadline in turn
+        _ = SLEEP.set(|duration| Box::pin(timer::sleep(duration)));
         Ok(())
     }
 }
@@ -242,4 +386,80 @@ mod tests {
         });
         Executor::spawn(async {});
     }
+
+    #[cfg(feature = "futures-executor")]
+    #[test]
+    fn futures_executor_sleeps_finish_in_deadline_order() {
+        use crate::timer::sleep;
+        use futures::{executor::block_on, future::join_all};
+        use std::{
+            sync::{Arc, Mutex},
+            time::{Duration, Instant},
+        };
+
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let sleeps = [30, 10, 20].map(|ms| {
+            let log = Arc::clone(&log);
+            async move {
+                sleep(Duration::from_millis(ms)).await;
+                log.lock().unwrap().push(ms);
+            }
+        });
+        let start = Instant::now();
+        block_on(join_all(sleeps));
+        assert!(start.elapsed() >= Duration::from_millis(30));
+        assert_eq!(*log.lock().unwrap(), [10, 20, 30]);
+    }
+
+    #[cfg(not(feature = "futures-executor"))]
+    #[test]
+    fn flush_runs_tasks_until_they_wait() {
+        use crate::Executor;
+        use futures::channel::oneshot;
+        use std::{cell::Cell, rc::Rc};
+
+        _ = Executor::init_manual();
+        let steps = Rc::new(Cell::new(0));
+        let (tx, rx) = oneshot::channel::<()>();
+        Executor::spawn_local({
+            let steps = Rc::clone(&steps);
+            async move {
+                steps.set(1);
+                _ = rx.await;
+                steps.set(2);
+            }
+        });
+        assert_eq!(steps.get(), 0);
+
+        Executor::flush();
+        assert_eq!(steps.get(), 1);
+
+        // sending wakes the task, which finishes in the next flush
+        _ = tx.send(());
+        assert_eq!(steps.get(), 1);
+        Executor::flush();
+        assert_eq!(steps.get(), 2);
+    }
+
+    #[cfg(not(feature = "futures-executor"))]
+    #[test]
+    fn advance_time_wakes_timers_in_order() {
+        use crate::Executor;
+        use std::{cell::RefCell, rc::Rc, time::Duration};
+
+        _ = Executor::init_manual();
+        let log = Rc::new(RefCell::new(Vec::new()));
+        for ms in [30, 10, 20] {
+            let log = Rc::clone(&log);
+            Executor::spawn_local(async move {
+                Executor::sleep(Duration::from_millis(ms)).await;
+                log.borrow_mut().push(ms);
+            });
+        }
+
+        Executor::advance_time(Duration::from_millis(15));
+        assert_eq!(*log.borrow(), [10]);
+        Executor::advance_time(Duration::from_millis(15));
+        assert_eq!(*log.borrow(), [10, 20, 30]);
+    }
 }
@@ -0,0 +1,144 @@
+//! An executor that only runs tasks when [`Executor::flush`](crate::Executor::flush) is called.
+
+use crate::PinnedLocalFuture;
+use std::{
+    cell::{Cell, RefCell},
+    collections::{HashMap, VecDeque},
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Wake, Waker},
+    time::Duration,
+};
+
+/// The number of times tasks can be polled during one flush before it is treated as a livelock.
+pub(crate) const MAX_POLLS: usize = 100_000;
+
+thread_local! {
+    static TASKS: RefCell<HashMap<usize, PinnedLocalFuture<()>>> = RefCell::new(HashMap::new());
+    static NEXT_ID: Cell<usize> = const { Cell::new(0) };
+    // woken tasks are queued here, which can happen from any thread
+    static READY: Arc<Mutex<VecDeque<usize>>> = Default::default();
+    // the time since the clock started, which only moves in `advance_time`
+    static NOW: Cell<Duration> = const { Cell::new(Duration::ZERO) };
+    static TIMERS: RefCell<Vec<(Duration, Waker)>> = const { RefCell::new(Vec::new()) };
+}
+
+struct TaskWaker {
+    id: usize,
+    ready: Arc<Mutex<VecDeque<usize>>>,
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        let mut ready = self.ready.lock().unwrap_or_else(|e| e.into_inner());
+        if !ready.contains(&self.id) {
+            ready.push_back(self.id);
+        }
+    }
+}
+
+pub(crate) fn spawn(fut: PinnedLocalFuture<()>) {
+    let id = NEXT_ID.get();
+    NEXT_ID.set(id + 1);
+    TASKS.with_borrow_mut(|tasks| tasks.insert(id, fut));
+    READY.with(|ready| {
+        ready
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push_back(id)
+    });
+}
+
+pub(crate) fn flush() {
+    let ready = READY.with(Arc::clone);
+    let mut polls = 0;
+    loop {
+        let next = ready.lock().unwrap_or_else(|e| e.into_inner()).pop_front();
+        let Some(id) = next else {
+            break;
+        };
+        // the task is taken out while it is polled, so that it can spawn other tasks
+        let Some(mut task) = TASKS.with_borrow_mut(|tasks| tasks.remove(&id))
+        else {
+            continue;
+        };
+
+        polls += 1;
+        if polls > MAX_POLLS {
+            panic!(
+                "Executor::flush() polled tasks {MAX_POLLS} times without \
+                 them all finishing or waiting; a task is probably waking \
+                 itself in a loop."
+            );
+        }
+
+        let waker = Waker::from(Arc::new(TaskWaker {
+            id,
+            ready: Arc::clone(&ready),
+        }));
+        let mut cx = Context::from_waker(&waker);
+        if task.as_mut().poll(&mut cx).is_pending() {
+            TASKS.with_borrow_mut(|tasks| tasks.insert(id, task));
+        }
+    }
+}
+
+pub(crate) fn sleep(duration: Duration) -> Sleep {
+    Sleep {
+        deadline: NOW.get() + duration,
+    }
+}
+
+pub(crate) struct Sleep {
+    deadline: Duration,
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if NOW.get() >= self.deadline {
+            Poll::Ready(())
+        } else {
+            TIMERS.with_borrow_mut(|timers| {
+                timers.push((self.deadline, cx.waker().clone()))
+            });
+            Poll::Pending
+        }
+    }
+}
+
+pub(crate) fn advance_time(duration: Duration) {
+    let until = NOW.get() + duration;
+    flush();
+    loop {
+        let next = TIMERS.with_borrow(|timers| {
+            timers
+                .iter()
+                .map(|(deadline, _)| *deadline)
+                .filter(|deadline| *deadline <= until)
+                .min()
+        });
+        let Some(deadline) = next else {
+            break;
+        };
+        NOW.set(NOW.get().max(deadline));
+        let due = TIMERS.with_borrow_mut(|timers| {
+            let (due, waiting) = timers
+                .drain(..)
+                .partition::<Vec<_>, _>(|(deadline, _)| *deadline <= NOW.get());
+            *timers = waiting;
+            due
+        });
+        for (_, waker) in due {
+            waker.wake();
+        }
+        flush();
+    }
+    NOW.set(until);
+}
@@ -0,0 +1,125 @@
+//! A timer for [`Executor::sleep`](crate::Executor::sleep) with the `futures` executor, which
+//! doesn't have one of its own.
+//!
+//! Every sleep is handled by a single thread, which waits for the earliest deadline in a heap.
+
+use futures::channel::oneshot;
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::BinaryHeap,
+    future::Future,
+    sync::{
+        atomic::{AtomicU64, Ordering as AtomicOrdering},
+        Condvar, Mutex, OnceLock,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+struct Timer {
+    deadlines: Mutex<BinaryHeap<Reverse<Deadline>>>,
+    // notified when a deadline earlier than the one the thread is waiting for is added
+    changed: Condvar,
+}
+
+struct Deadline {
+    at: Instant,
+    // breaks ties, so that sleeps with the same deadline finish in the order they started
+    id: u64,
+    done: oneshot::Sender<()>,
+}
+
+impl PartialEq for Deadline {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Deadline {}
+
+impl PartialOrd for Deadline {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Deadline {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.at, self.id).cmp(&(other.at, other.id))
+    }
+}
+
+fn timer() -> &'static Timer {
+    static TIMER: OnceLock<Timer> = OnceLock::new();
+    static THREAD: OnceLock<()> = OnceLock::new();
+
+    let timer = TIMER.get_or_init(|| Timer {
+        deadlines: Mutex::new(BinaryHeap::new()),
+        changed: Condvar::new(),
+    });
+    THREAD.get_or_init(|| {
+        thread::Builder::new()
+            .name("any_spawner timer".into())
+            .spawn(move || run(timer))
+            .expect("could not start the timer thread");
+    });
+    timer
+}
+
+fn run(timer: &Timer) {
+    let mut deadlines =
+        timer.deadlines.lock().unwrap_or_else(|e| e.into_inner());
+    loop {
+        let now = Instant::now();
+        while deadlines
+            .peek()
+            .is_some_and(|Reverse(deadline)| deadline.at <= now)
+        {
+            let Reverse(deadline) = deadlines.pop().unwrap();
+            // the sleep may have been dropped already
+            _ = deadline.done.send(());
+        }
+        deadlines = match deadlines.peek() {
+            Some(Reverse(next)) => {
+                let wait = next.at - now;
+                timer
+                    .changed
+                    .wait_timeout(deadlines, wait)
+                    .unwrap_or_else(|e| e.into_inner())
+                    .0
+            }
+            None => timer
+                .changed
+                .wait(deadlines)
+                .unwrap_or_else(|e| e.into_inner()),
+        };
+    }
+}
+
+/// Returns a `Future` that finishes once `duration` has passed since it was created.
+pub(crate) fn sleep(duration: Duration) -> impl Future<Output = ()> {
+    static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+    let (done, finished) = oneshot::channel();
+    let deadline = Deadline {
+        at: Instant::now() + duration,
+        id: NEXT_ID.fetch_add(1, AtomicOrdering::Relaxed),
+        done,
+    };
+
+    let timer = timer();
+    let mut deadlines =
+        timer.deadlines.lock().unwrap_or_else(|e| e.into_inner());
+    let earliest = match deadlines.peek() {
+        Some(Reverse(next)) => deadline < *next,
+        None => true,
+    };
+    deadlines.push(Reverse(deadline));
+    if earliest {
+        timer.changed.notify_one();
+    }
+
+    async move {
+        _ = finished.await;
+    }
+}
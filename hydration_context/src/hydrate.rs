@@ -30,14 +30,20 @@ fn serialized_errors() -> Vec<(SerializedDataId, ErrorId, Error)> {
                     let error_boundary_id =
                         value.get(0).as_f64().unwrap() as usize;
                     let error_id = value.get(1).as_f64().unwrap() as usize;
-                    let value = value
+                    let data = value
                         .get(2)
                         .as_string()
-                        .expect("Expected a [number, string] tuple");
+                        .expect("Expected a [number, number, string] tuple");
+                    // errors of registered types are restored with their original type
+                    let error = value
+                        .get(3)
+                        .as_string()
+                        .and_then(|tag| Error::deserialize(&tag, &data))
+                        .unwrap_or_else(|| Error::from(SerializedError(data)));
                     (
                         SerializedDataId(error_boundary_id),
                         ErrorId::from(error_id),
-                        Error::from(SerializedError(value)),
+                        error,
                     )
                 })
             })
@@ -8,7 +8,7 @@ use futures::{
 use or_poisoned::OrPoisoned;
 use std::{
     collections::HashSet,
-    fmt::{Debug, Write},
+    fmt::{Debug, Display, Write},
     mem,
     pin::Pin,
     sync::{
@@ -161,12 +161,14 @@ impl SharedContext for SsrSharedContext {
 
         initial_chunk.push_str("__SERIALIZED_ERRORS=[");
         for error in mem::take(&mut *self.errors.write().or_poisoned()) {
+            let (tag, data) = error.2.serialize();
             _ = write!(
                 initial_chunk,
-                "[{}, {}, {:?}],",
+                "[{}, {}, {:?}, {}],",
                 error.0 .0,
                 error.1,
-                error.2.to_string()
+                data,
+                SerializedTag(tag)
             );
         }
         initial_chunk.push_str("];");
@@ -272,12 +274,14 @@ impl Stream for AsyncDataStream {
         let sealed = self.sealed_error_boundaries.read().or_poisoned();
         for error in mem::take(&mut *self.errors.write().or_poisoned()) {
             if !sealed.contains(&error.0) {
+                let (tag, data) = error.2.serialize();
                 _ = write!(
                     resolved,
-                    "__SERIALIZED_ERRORS.push([{}, {}, {:?}]);",
+                    "__SERIALIZED_ERRORS.push([{}, {}, {:?}, {}]);",
                     error.0 .0,
                     error.1,
-                    error.2.to_string()
+                    data,
+                    SerializedTag(tag)
                 );
             }
         }
@@ -304,3 +308,15 @@ impl ResolvedData {
         write!(buf, "{}: {:?}", id.0, ser).unwrap();
     }
 }
+
+/// The type tag of a serialized error, written as a JavaScript string or `null`.
+struct SerializedTag(Option<&'static str>);
+
+impl Display for SerializedTag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            Some(tag) => write!(f, "{tag:?}"),
+            None => f.write_str("null"),
+        }
+    }
+}
@@ -674,9 +674,13 @@ where
 {
     _ = replace_blocks; // TODO
     handle_response(method, additional_context, app_fn, |app, chunks| {
+        let nonce = leptos::nonce::use_nonce();
         Box::pin(async move {
-            Box::pin(app.to_html_stream_out_of_order().chain(chunks()))
-                as PinnedStream<String>
+            let mut app = app.to_html_stream_out_of_order();
+            if let Some(nonce) = nonce {
+                app = app.with_nonce(&*nonce);
+            }
+            Box::pin(app.chain(chunks())) as PinnedStream<String>
         })
     })
 }
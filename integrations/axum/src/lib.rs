@@ -372,9 +372,11 @@ async fn handle_server_fns_inner(
     let path = req.uri().path().to_string();
     let (req, parts) = generate_request_and_parts(req);
 
-    if let Some(mut service) =
-        server_fn::axum::get_server_fn_service(&path, method)
-    {
+    if let Some(mut service) = server_fn::axum::get_server_fn_service_with(
+        &path,
+        method,
+        ProvidePartsLayer,
+    ) {
         let owner = Owner::new();
         owner
             .with(|| {
@@ -432,6 +434,42 @@ async fn handle_server_fns_inner(
     .expect("could not build Response")
 }
 
+/// Provides the request [`Parts`] again once the server function's own middleware has run, so
+/// that [`extract`] sees any changes it made, like added extensions.
+struct ProvidePartsLayer;
+
+impl server_fn::middleware::Layer<Request<Body>, Response<Body>>
+    for ProvidePartsLayer
+{
+    fn layer(
+        &self,
+        inner: server_fn::middleware::BoxedService<
+            Request<Body>,
+            Response<Body>,
+        >,
+    ) -> server_fn::middleware::BoxedService<Request<Body>, Response<Body>>
+    {
+        server_fn::middleware::BoxedService::new(ProvideParts(inner))
+    }
+}
+
+struct ProvideParts(
+    server_fn::middleware::BoxedService<Request<Body>, Response<Body>>,
+);
+
+impl server_fn::middleware::Service<Request<Body>, Response<Body>>
+    for ProvideParts
+{
+    fn run(
+        &mut self,
+        req: Request<Body>,
+    ) -> Pin<Box<dyn Future<Output = Response<Body>> + Send>> {
+        let (req, parts) = generate_request_and_parts(req);
+        provide_context(parts);
+        self.0.run(req)
+    }
+}
+
 pub type PinnedHtmlStream =
     Pin<Box<dyn Stream<Item = io::Result<Bytes>> + Send>>;
 
@@ -783,12 +821,16 @@ where
 {
     _ = replace_blocks; // TODO
     handle_response(additional_context, app_fn, |app, chunks| {
+        let nonce = leptos::nonce::use_nonce();
         Box::pin(async move {
-            let app = if cfg!(feature = "dont-use-islands-router") {
+            let mut app = if cfg!(feature = "dont-use-islands-router") {
                 app.to_html_stream_out_of_order_branching()
             } else {
                 app.to_html_stream_out_of_order()
             };
+            if let Some(nonce) = nonce {
+                app = app.with_nonce(&*nonce);
+            }
             Box::pin(app.chain(chunks())) as PinnedStream<String>
         })
     })
@@ -1928,6 +1970,9 @@ where
 ///
 /// Any error that occurs during extraction is converted to a [`ServerFnError`].
 ///
+/// The request includes any changes made by the server function's own `#[middleware]`, so this
+/// can also be used to read an [`Extension`](axum::Extension) that it added.
+///
 /// ```rust
 /// use leptos::prelude::*;
 ///
@@ -0,0 +1,219 @@
+use axum::{
+    body::{Body, Bytes},
+    http::{header::CACHE_CONTROL, Request, StatusCode},
+    routing::get,
+    Router,
+};
+use futures::Stream;
+use leptos::{
+    prelude::*,
+    server_fn::{
+        client::Client, codec::GetUrl, request::ClientReq, response::ClientRes,
+        ServerFn,
+    },
+};
+use std::{
+    future::Future,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use tower::ServiceExt;
+
+/// A `GET` request that is answered by the mock transport, instead of being sent over the network.
+pub struct MockRequest {
+    url: String,
+}
+
+impl<CustErr> ClientReq<CustErr> for MockRequest {
+    type FormData = ();
+
+    fn try_new_get(
+        path: &str,
+        _accepts: &str,
+        _content_type: &str,
+        query: &str,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        Ok(MockRequest {
+            url: format!("{path}?{query}"),
+        })
+    }
+
+    fn try_new_post(
+        _path: &str,
+        _accepts: &str,
+        _content_type: &str,
+        _body: String,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        unimplemented!()
+    }
+
+    fn try_new_post_bytes(
+        _path: &str,
+        _accepts: &str,
+        _content_type: &str,
+        _body: Bytes,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        unimplemented!()
+    }
+
+    fn try_new_post_form_data(
+        _path: &str,
+        _accepts: &str,
+        _content_type: &str,
+        _body: Self::FormData,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        unimplemented!()
+    }
+
+    fn try_new_multipart(
+        _path: &str,
+        _accepts: &str,
+        _body: Self::FormData,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        unimplemented!()
+    }
+
+    fn try_new_streaming(
+        _path: &str,
+        _accepts: &str,
+        _content_type: &str,
+        _body: impl Stream<Item = Bytes> + Send + 'static,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        unimplemented!()
+    }
+
+    fn try_add_header(
+        &mut self,
+        _name: &str,
+        _value: &str,
+    ) -> Result<(), ServerFnError<CustErr>> {
+        Ok(())
+    }
+
+    fn try_set_credentials(
+        self,
+        _credentials: leptos::server_fn::client::Credentials,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        Ok(self)
+    }
+
+    fn url(&self) -> String {
+        self.url.clone()
+    }
+}
+
+pub struct MockResponse(String);
+
+impl<CustErr> ClientRes<CustErr> for MockResponse {
+    async fn try_into_string(self) -> Result<String, ServerFnError<CustErr>> {
+        Ok(self.0)
+    }
+
+    async fn try_into_bytes(self) -> Result<Bytes, ServerFnError<CustErr>> {
+        Ok(self.0.into())
+    }
+
+    fn try_into_stream(
+        self,
+    ) -> Result<
+        impl Stream<Item = Result<Bytes, ServerFnError>> + Send + Sync + 'static,
+        ServerFnError<CustErr>,
+    > {
+        Ok(futures::stream::once(async { Ok(Bytes::from(self.0)) }))
+    }
+
+    fn status(&self) -> u16 {
+        200
+    }
+
+    fn status_text(&self) -> String {
+        String::new()
+    }
+
+    fn location(&self) -> String {
+        String::new()
+    }
+
+    fn has_redirect(&self) -> bool {
+        false
+    }
+}
+
+static SENT: AtomicUsize = AtomicUsize::new(0);
+
+/// Counts every request it sends, and answers each with the number of requests sent so far.
+pub struct CountingClient;
+
+impl<CustErr> Client<CustErr> for CountingClient {
+    type Request = MockRequest;
+    type Response = MockResponse;
+
+    fn send(
+        _req: Self::Request,
+    ) -> impl Future<Output = Result<Self::Response, ServerFnError<CustErr>>> + Send
+    {
+        let count = SENT.fetch_add(1, Ordering::SeqCst) + 1;
+        async move {
+            // stay in flight long enough for concurrent calls to find this request
+            tokio::task::yield_now().await;
+            Ok(MockResponse(count.to_string()))
+        }
+    }
+}
+
+#[server(
+    input = GetUrl,
+    cache = "max-age=60, public",
+    client = CountingClient,
+    endpoint = "post_count"
+)]
+pub async fn post_count(user: String) -> Result<usize, ServerFnError> {
+    Ok(user.len())
+}
+
+#[tokio::test]
+async fn cache_control_header_is_sent_with_get_responses() {
+    let app = Router::new()
+        .route(PostCount::PATH, get(leptos_axum::handle_server_fns));
+    let res = app
+        .oneshot(
+            Request::get(format!("{}?user=alice", PostCount::PATH))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.headers()[CACHE_CONTROL], "max-age=60, public");
+}
+
+#[tokio::test]
+async fn concurrent_identical_calls_share_one_request() {
+    let call = |user: &str, force| {
+        PostCount { user: user.into() }.run_on_client_deduped(force)
+    };
+
+    // two resources reading the same data at the same time send one request
+    let (a, b) = futures::join!(call("alice", false), call("alice", false));
+    assert_eq!((a, b), (Ok(1), Ok(1)));
+    assert_eq!(SENT.load(Ordering::SeqCst), 1);
+
+    // once the response has arrived, the next call sends a new request
+    assert_eq!(call("alice", false).await, Ok(2));
+
+    // different arguments, or forcing the call, are never shared
+    let (a, b, c) = futures::join!(
+        call("alice", false),
+        call("bob", false),
+        call("alice", true)
+    );
+    assert_eq!((a, b, c), (Ok(3), Ok(4), Ok(5)));
+    assert_eq!(SENT.load(Ordering::SeqCst), 5);
+}
+
+#[tokio::test]
+async fn cached_functions_have_a_forced_variant() {
+    // on the server, both run the function body; on the client, the forced one is never shared
+    assert_eq!(post_count("alice".into()).await, Ok(5));
+    assert_eq!(post_count_forced("alice".into()).await, Ok(5));
+}
@@ -0,0 +1,259 @@
+use axum::body::Bytes;
+use futures::Stream;
+use leptos::{
+    prelude::*,
+    server_fn::{
+        client::{
+            get_server_url, set_server_fn_client, Client, Credentials,
+            ServerFnClient,
+        },
+        request::ClientReq,
+        response::ClientRes,
+        ServerFn,
+    },
+};
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+};
+
+/// A request recorded by the mock transport, instead of being sent over the network.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MockRequest {
+    url: String,
+    headers: Vec<(String, String)>,
+    credentials: Option<Credentials>,
+    body: String,
+}
+
+impl<CustErr> ClientReq<CustErr> for MockRequest {
+    type FormData = ();
+
+    fn try_new_get(
+        _path: &str,
+        _accepts: &str,
+        _content_type: &str,
+        _query: &str,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        unimplemented!()
+    }
+
+    fn try_new_post(
+        path: &str,
+        _accepts: &str,
+        _content_type: &str,
+        body: String,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        Ok(MockRequest {
+            url: format!("{}{}", get_server_url(), path),
+            body,
+            ..Default::default()
+        })
+    }
+
+    fn try_new_post_bytes(
+        _path: &str,
+        _accepts: &str,
+        _content_type: &str,
+        _body: Bytes,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        unimplemented!()
+    }
+
+    fn try_new_post_form_data(
+        _path: &str,
+        _accepts: &str,
+        _content_type: &str,
+        _body: Self::FormData,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        unimplemented!()
+    }
+
+    fn try_new_multipart(
+        _path: &str,
+        _accepts: &str,
+        _body: Self::FormData,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        unimplemented!()
+    }
+
+    fn try_new_streaming(
+        _path: &str,
+        _accepts: &str,
+        _content_type: &str,
+        _body: impl Stream<Item = Bytes> + Send + 'static,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        unimplemented!()
+    }
+
+    fn try_add_header(
+        &mut self,
+        name: &str,
+        value: &str,
+    ) -> Result<(), ServerFnError<CustErr>> {
+        self.headers.push((name.to_string(), value.to_string()));
+        Ok(())
+    }
+
+    fn try_set_credentials(
+        mut self,
+        credentials: Credentials,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        self.credentials = Some(credentials);
+        Ok(self)
+    }
+
+    fn url(&self) -> String {
+        self.url.clone()
+    }
+}
+
+pub struct MockResponse {
+    status: u16,
+    body: String,
+}
+
+impl<CustErr> ClientRes<CustErr> for MockResponse {
+    async fn try_into_string(self) -> Result<String, ServerFnError<CustErr>> {
+        Ok(self.body)
+    }
+
+    async fn try_into_bytes(self) -> Result<Bytes, ServerFnError<CustErr>> {
+        Ok(self.body.into())
+    }
+
+    fn try_into_stream(
+        self,
+    ) -> Result<
+        impl Stream<Item = Result<Bytes, ServerFnError>> + Send + Sync + 'static,
+        ServerFnError<CustErr>,
+    > {
+        Ok(futures::stream::once(async { Ok(Bytes::from(self.body)) }))
+    }
+
+    fn status(&self) -> u16 {
+        self.status
+    }
+
+    fn status_text(&self) -> String {
+        String::new()
+    }
+
+    fn location(&self) -> String {
+        String::new()
+    }
+
+    fn has_redirect(&self) -> bool {
+        false
+    }
+}
+
+static SENT: Mutex<Vec<MockRequest>> = Mutex::new(Vec::new());
+
+/// Records every request, and answers it with a `200` response that echoes its URL.
+pub struct MockClient;
+
+impl<CustErr> Client<CustErr> for MockClient {
+    type Request = MockRequest;
+    type Response = MockResponse;
+
+    fn send(
+        req: Self::Request,
+    ) -> impl Future<Output = Result<Self::Response, ServerFnError<CustErr>>> + Send
+    {
+        let body = serde_json::to_string(&req.url).unwrap();
+        SENT.lock().unwrap().push(req);
+        async move { Ok(MockResponse { status: 200, body }) }
+    }
+}
+
+#[server(client = MockClient, endpoint = "echo_url")]
+pub async fn echo_url(message: String) -> Result<String, ServerFnError> {
+    Ok(message)
+}
+
+#[tokio::test]
+async fn client_configuration_applies_to_every_call() {
+    let token = ArcRwSignal::new(Some("first".to_string()));
+    let intercepted = std::sync::Arc::new(AtomicUsize::new(0));
+    set_server_fn_client(
+        ServerFnClient::new()
+            .base_url("https://api.example.com")
+            .header("X-Client", "test")
+            .header_with("Authorization", {
+                let token = token.clone();
+                move || token.get_untracked().map(|t| format!("Bearer {t}"))
+            })
+            .credentials(Credentials::Include)
+            .on_request(|mut req: MockRequest| {
+                req.body.push_str("&intercepted=true");
+                req
+            })
+            .on_response({
+                let intercepted = intercepted.clone();
+                move |res: MockResponse| {
+                    intercepted.fetch_add(1, Ordering::SeqCst);
+                    Ok(res)
+                }
+            }),
+    );
+
+    let url = EchoUrl::run_on_client(EchoUrl {
+        message: "hi".into(),
+    })
+    .await
+    .unwrap();
+    assert_eq!(url, "https://api.example.com/api/echo_url");
+
+    token.set(None);
+    EchoUrl::run_on_client(EchoUrl {
+        message: "again".into(),
+    })
+    .await
+    .unwrap();
+
+    let sent = SENT.lock().unwrap().clone();
+    assert_eq!(
+        sent,
+        vec![
+            MockRequest {
+                url: "https://api.example.com/api/echo_url".into(),
+                headers: vec![
+                    ("X-Client".into(), "test".into()),
+                    ("Authorization".into(), "Bearer first".into()),
+                ],
+                credentials: Some(Credentials::Include),
+                body: "message=hi&intercepted=true".into(),
+            },
+            MockRequest {
+                url: "https://api.example.com/api/echo_url".into(),
+                headers: vec![("X-Client".into(), "test".into())],
+                credentials: Some(Credentials::Include),
+                body: "message=again&intercepted=true".into(),
+            },
+        ]
+    );
+    assert_eq!(intercepted.load(Ordering::SeqCst), 2);
+
+    set_server_fn_client(
+        ServerFnClient::new()
+            .base_url("https://forbidden.example.com")
+            .on_response(|res: MockResponse| {
+                if res.body.contains("forbidden") {
+                    Err(ServerFnError::new("blocked by interceptor"))
+                } else {
+                    Ok(res)
+                }
+            }),
+    );
+    assert_eq!(
+        EchoUrl::run_on_client(EchoUrl {
+            message: "hi".into(),
+        })
+        .await,
+        Err(ServerFnError::new("blocked by interceptor"))
+    );
+}
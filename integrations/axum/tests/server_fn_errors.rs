@@ -0,0 +1,123 @@
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::post,
+    Router,
+};
+use leptos::{prelude::*, server_fn::ServerFn};
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+use tower::ServiceExt;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ItemError {
+    NotFound { id: u32 },
+    Forbidden { id: u32, owner: String },
+}
+
+impl fmt::Display for ItemError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ItemError::NotFound { id } => write!(f, "item {id} not found"),
+            ItemError::Forbidden { id, owner } => {
+                write!(f, "item {id} belongs to {owner}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ItemError {}
+
+// custom error types always implement `FromStr`, but this one is sent as JSON rather than as
+// text, because it is declared with `error = ItemError`
+impl FromStr for ItemError {
+    type Err = serde_json::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s)
+    }
+}
+
+impl From<&ItemError> for StatusCode {
+    fn from(value: &ItemError) -> Self {
+        match value {
+            ItemError::NotFound { .. } => StatusCode::NOT_FOUND,
+            ItemError::Forbidden { .. } => StatusCode::FORBIDDEN,
+        }
+    }
+}
+
+#[server(error = ItemError)]
+pub async fn get_item(id: u32) -> Result<String, ServerFnError<ItemError>> {
+    match id {
+        0 => Ok("the first item".to_string()),
+        1 => Err(ItemError::NotFound { id }.into()),
+        _ => Err(ItemError::Forbidden {
+            id,
+            owner: "admin".to_string(),
+        }
+        .into()),
+    }
+}
+
+/// Calls `GetItem` through the Axum integration, returning the status code and the result as it
+/// is decoded by the client.
+async fn call(
+    args: &str,
+) -> (StatusCode, Result<String, ServerFnError<ItemError>>) {
+    let app = Router::new()
+        .route(GetItem::PATH, post(leptos_axum::handle_server_fns));
+    let req = Request::post(GetItem::PATH)
+        .header("content-type", "application/x-www-form-urlencoded")
+        .body(Body::from(args.to_string()))
+        .unwrap();
+    let res = app.oneshot(req).await.unwrap();
+    let status = res.status();
+    let body = axum::body::to_bytes(res.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body = String::from_utf8(body.to_vec()).unwrap();
+    let result = if status.is_success() {
+        Ok(serde_json::from_str(&body).unwrap())
+    } else {
+        Err(GetItem::de_error(&body))
+    };
+    (status, result)
+}
+
+#[tokio::test]
+async fn custom_errors_keep_their_type_and_status() {
+    assert_eq!(
+        call("id=0").await,
+        (StatusCode::OK, Ok("the first item".into()))
+    );
+
+    assert_eq!(
+        call("id=1").await,
+        (
+            StatusCode::NOT_FOUND,
+            Err(ServerFnError::WrappedServerError(ItemError::NotFound {
+                id: 1
+            }))
+        )
+    );
+
+    let (status, result) = call("id=2").await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let error = Error::from(result.unwrap_err());
+    assert_eq!(error.to_string(), "item 2 belongs to admin");
+    assert_eq!(
+        error.downcast_ref::<ServerFnError<ItemError>>(),
+        Some(&ServerFnError::WrappedServerError(ItemError::Forbidden {
+            id: 2,
+            owner: "admin".into()
+        }))
+    );
+}
+
+#[tokio::test]
+async fn other_errors_still_use_the_default_status() {
+    let (status, result) = call("id=not-a-number").await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert!(matches!(result, Err(ServerFnError::Args(_))), "{result:?}");
+}
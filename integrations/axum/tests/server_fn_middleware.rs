@@ -0,0 +1,126 @@
+use axum::{
+    body::Body,
+    extract::Extension,
+    http::{header::COOKIE, Request, Response, StatusCode},
+    routing::post,
+    Router,
+};
+use leptos::{prelude::*, server_fn::ServerFn};
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::atomic::{AtomicUsize, Ordering},
+    task::{Context, Poll},
+};
+use tower::{Layer, Service, ServiceExt};
+
+#[derive(Debug, Clone)]
+struct Session {
+    user: String,
+}
+
+/// Rejects requests without a `session` cookie, and adds the [`Session`] to the request
+/// otherwise.
+struct RequireSession;
+
+impl<S> Layer<S> for RequireSession {
+    type Service = RequireSessionService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequireSessionService { inner }
+    }
+}
+
+struct RequireSessionService<S> {
+    inner: S,
+}
+
+impl<S> Service<Request<Body>> for RequireSessionService<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>, Error = ServerFnError>,
+    S::Future: Send + 'static,
+{
+    type Response = Response<Body>;
+    type Error = ServerFnError;
+    type Future = Pin<
+        Box<dyn Future<Output = Result<Response<Body>, ServerFnError>> + Send>,
+    >;
+
+    fn poll_ready(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<Body>) -> Self::Future {
+        let user = req
+            .headers()
+            .get(COOKIE)
+            .and_then(|cookie| cookie.to_str().ok())
+            .and_then(|cookie| cookie.strip_prefix("session="))
+            .map(ToString::to_string);
+        match user {
+            Some(user) => {
+                req.extensions_mut().insert(Session { user });
+                Box::pin(self.inner.call(req))
+            }
+            None => Box::pin(async {
+                Err(ServerFnError::MissingArg("session cookie".into()))
+            }),
+        }
+    }
+}
+
+static RUNS: AtomicUsize = AtomicUsize::new(0);
+
+#[server]
+#[middleware(RequireSession)]
+pub async fn whoami() -> Result<String, ServerFnError> {
+    RUNS.fetch_add(1, Ordering::SeqCst);
+    let Extension(session) =
+        leptos_axum::extract::<Extension<Session>>().await?;
+    Ok(session.user)
+}
+
+/// Calls `Whoami` through the Axum integration, returning the status code and the result as it
+/// is decoded by the client.
+async fn call(
+    cookie: Option<&str>,
+) -> (StatusCode, Result<String, ServerFnError>) {
+    let app =
+        Router::new().route(Whoami::PATH, post(leptos_axum::handle_server_fns));
+    let mut req = Request::post(Whoami::PATH)
+        .header("content-type", "application/x-www-form-urlencoded");
+    if let Some(cookie) = cookie {
+        req = req.header(COOKIE, cookie);
+    }
+    let res = app.oneshot(req.body(Body::empty()).unwrap()).await.unwrap();
+    let status = res.status();
+    let body = axum::body::to_bytes(res.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body = String::from_utf8(body.to_vec()).unwrap();
+    let result = if status.is_success() {
+        Ok(serde_json::from_str(&body).unwrap())
+    } else {
+        Err(Whoami::de_error(&body))
+    };
+    (status, result)
+}
+
+#[tokio::test]
+async fn middleware_can_reject_requests_or_add_extensions() {
+    let (status, result) = call(None).await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(
+        result,
+        Err(ServerFnError::MissingArg("session cookie".into()))
+    );
+    assert_eq!(RUNS.load(Ordering::SeqCst), 0);
+
+    let (status, result) = call(Some("session=alice")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(result, Ok("alice".to_string()));
+    assert_eq!(RUNS.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,92 @@
+use axum::{body::Body, http::StatusCode, routing::post, Router};
+use leptos::{
+    prelude::*,
+    server_fn::{
+        codec::{MultipartData, MultipartFormData, MultipartLimits},
+        ServerFn,
+    },
+};
+use tower::ServiceExt;
+
+#[server(input = MultipartFormData)]
+pub async fn upload_files(
+    data: MultipartData,
+) -> Result<Vec<(String, String, usize)>, ServerFnError> {
+    let limits = MultipartLimits::new()
+        .per_field(16)
+        .whole_stream(32)
+        .allowed_fields(["file"]);
+    let mut data = data.into_limited(limits).unwrap();
+    let mut files = Vec::new();
+    while let Some(field) = data.next_field().await? {
+        let name = field.file_name().unwrap_or_default().to_string();
+        let content_type = field.content_type().unwrap_or_default().to_string();
+        let bytes = field.bytes().await?;
+        files.push((name, content_type, bytes.len()));
+    }
+    Ok(files)
+}
+
+const BOUNDARY: &str = "leptos-test-boundary";
+
+fn multipart_body(fields: &[(&str, &str, &str)]) -> String {
+    let mut body = String::new();
+    for (name, file_name, contents) in fields {
+        body.push_str(&format!(
+            "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"{name}\"; \
+             filename=\"{file_name}\"\r\nContent-Type: text/plain\r\n\r\n\
+             {contents}\r\n"
+        ));
+    }
+    body.push_str(&format!("--{BOUNDARY}--\r\n"));
+    body
+}
+
+async fn call(fields: &[(&str, &str, &str)]) -> (StatusCode, String) {
+    let app = Router::new()
+        .route(UploadFiles::PATH, post(leptos_axum::handle_server_fns));
+    let req = axum::http::Request::post(UploadFiles::PATH)
+        .header(
+            "content-type",
+            format!("multipart/form-data; boundary={BOUNDARY}"),
+        )
+        .body(Body::from(multipart_body(fields)))
+        .unwrap();
+    let res = app.oneshot(req).await.unwrap();
+    let status = res.status();
+    let body = axum::body::to_bytes(res.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn reads_uploaded_files() {
+    let (status, body) =
+        call(&[("file", "a.txt", "hello"), ("file", "b.txt", "world!")]).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body,
+        r#"[["a.txt","text/plain",5],["b.txt","text/plain",6]]"#
+    );
+}
+
+#[tokio::test]
+async fn rejects_uploads_that_exceed_the_limits() {
+    let (status, body) = call(&[("file", "big.txt", &"x".repeat(17))]).await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert!(body.contains("is too large"), "{body}");
+
+    let (status, body) = call(&[
+        ("file", "a.txt", &"x".repeat(16)),
+        ("file", "b.txt", &"x".repeat(16)),
+        ("file", "c.txt", "x"),
+    ])
+    .await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert!(body.contains("multipart data is too large"), "{body}");
+
+    let (status, body) = call(&[("avatar", "a.txt", "x")]).await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert!(body.contains("unexpected multipart field"), "{body}");
+}
@@ -0,0 +1,172 @@
+use axum::{
+    body::{Body, Bytes},
+    routing::post,
+    Router,
+};
+use futures::{channel::mpsc, SinkExt, Stream, StreamExt};
+use leptos::{
+    prelude::*,
+    server_fn::{
+        codec::{EventStream, FromRes, StreamingEvents},
+        error::NoCustomError,
+        response::ClientRes,
+        ServerFn,
+    },
+};
+use std::{
+    future::Future,
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+use tower::ServiceExt;
+
+#[server(output = StreamingEvents)]
+pub async fn count_to(
+    n: u32,
+    fail_at: Option<u32>,
+) -> Result<EventStream<u32>, ServerFnError> {
+    Ok(EventStream::new(futures::stream::iter((0..n).map(
+        move |i| {
+            if Some(i) == fail_at {
+                Err(ServerFnError::new(format!("failed at {i}")))
+            } else {
+                Ok(i)
+            }
+        },
+    ))))
+}
+
+static TICKER_DROPPED: AtomicBool = AtomicBool::new(false);
+
+struct SetOnDrop;
+
+impl Drop for SetOnDrop {
+    fn drop(&mut self) {
+        TICKER_DROPPED.store(true, Ordering::SeqCst);
+    }
+}
+
+#[server(output = StreamingEvents)]
+pub async fn ticker() -> Result<EventStream<u32>, ServerFnError> {
+    let guard = SetOnDrop;
+    Ok(futures::stream::iter(0..)
+        .map(move |i| {
+            let _guard = &guard;
+            i
+        })
+        .into())
+}
+
+/// The response to a server function call, as received by the client.
+struct TestResponse(mpsc::Receiver<Result<Bytes, ServerFnError>>);
+
+impl ClientRes<NoCustomError> for TestResponse {
+    async fn try_into_string(self) -> Result<String, ServerFnError> {
+        unimplemented!()
+    }
+
+    async fn try_into_bytes(self) -> Result<Bytes, ServerFnError> {
+        unimplemented!()
+    }
+
+    fn try_into_stream(
+        self,
+    ) -> Result<
+        impl Stream<Item = Result<Bytes, ServerFnError>> + Send + Sync + 'static,
+        ServerFnError,
+    > {
+        Ok(self.0)
+    }
+
+    fn status(&self) -> u16 {
+        200
+    }
+
+    fn status_text(&self) -> String {
+        "OK".into()
+    }
+
+    fn location(&self) -> String {
+        String::new()
+    }
+
+    fn has_redirect(&self) -> bool {
+        false
+    }
+}
+
+/// Calls a server function through the Axum integration, returning the stream the client sees.
+async fn call<F>(args: &str) -> EventStream<u32>
+where
+    F: ServerFn,
+{
+    let app =
+        Router::new().route(F::PATH, post(leptos_axum::handle_server_fns));
+    let req = axum::http::Request::post(F::PATH)
+        .header("content-type", "application/x-www-form-urlencoded")
+        .body(Body::from(args.to_string()))
+        .unwrap();
+    let res = app.oneshot(req).await.unwrap();
+    assert_eq!(res.headers()["content-type"], "text/event-stream");
+
+    // forward the body the way the network would, in small chunks, and stop reading when the
+    // client hangs up
+    let (mut tx, rx) = mpsc::channel(1);
+    tokio::spawn(async move {
+        let mut body = res.into_body().into_data_stream();
+        while let Some(chunk) = body.next().await {
+            let chunk = chunk.unwrap();
+            for piece in chunk.chunks(3) {
+                if tx.send(Ok(Bytes::copy_from_slice(piece))).await.is_err() {
+                    return;
+                }
+            }
+        }
+    });
+    EventStream::from_res(TestResponse(rx)).await.unwrap()
+}
+
+async fn with_timeout<T>(fut: impl Future<Output = T>) -> T {
+    tokio::time::timeout(Duration::from_secs(5), fut)
+        .await
+        .expect("timed out")
+}
+
+#[tokio::test]
+async fn streams_items_and_mid_stream_errors() {
+    let items: Vec<_> =
+        with_timeout(call::<CountTo>("n=4&fail_at=2").await.collect()).await;
+    assert_eq!(
+        items,
+        vec![
+            Ok(0),
+            Ok(1),
+            Err(ServerFnError::ServerError("failed at 2".into())),
+            Ok(3),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn dropping_the_signal_cancels_the_stream_on_the_server() {
+    _ = any_spawner::Executor::init_tokio();
+    let stream = call::<Ticker>("").await;
+
+    let owner = Owner::new();
+    let latest = owner.with(|| stream.into_signal());
+    with_timeout(async {
+        while !matches!(latest.get_untracked(), Some(Ok(n)) if n >= 2) {
+            tokio::task::yield_now().await;
+        }
+    })
+    .await;
+    assert!(!TICKER_DROPPED.load(Ordering::SeqCst));
+
+    drop(owner);
+    with_timeout(async {
+        while !TICKER_DROPPED.load(Ordering::SeqCst) {
+            tokio::task::yield_now().await;
+        }
+    })
+    .await;
+}
@@ -0,0 +1,138 @@
+use axum::{routing::get, Router};
+use futures::{SinkExt, StreamExt};
+use leptos::{
+    prelude::*,
+    server_fn::{
+        client::set_server_url,
+        websocket::{
+            tungstenite::TungsteniteClient, Backoff, BoxedStream, Websocket,
+            WebsocketServerFn,
+        },
+    },
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::{
+    net::{TcpListener, TcpStream},
+    task::AbortHandle,
+    time::timeout,
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Shout {
+    text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Echoed {
+    text: String,
+    len: usize,
+}
+
+#[server(protocol = Websocket, client = TungsteniteClient)]
+pub async fn echo(
+    input: BoxedStream<Shout>,
+) -> Result<BoxedStream<Echoed>, ServerFnError> {
+    Ok(BoxedStream::new(input.map(|msg| {
+        msg.map(|Shout { text }| Echoed {
+            len: text.len(),
+            text: text.to_uppercase(),
+        })
+    })))
+}
+
+/// Forwards connections to the server, and can drop every open connection to simulate a
+/// network failure.
+#[derive(Clone, Default)]
+struct Proxy {
+    connections: Arc<Mutex<Vec<AbortHandle>>>,
+}
+
+impl Proxy {
+    async fn start(self, upstream: SocketAddr) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            loop {
+                let (mut client, _) = listener.accept().await.unwrap();
+                let handle = tokio::spawn(async move {
+                    let mut server =
+                        TcpStream::connect(upstream).await.unwrap();
+                    let _ =
+                        tokio::io::copy_bidirectional(&mut client, &mut server)
+                            .await;
+                });
+                self.connections.lock().unwrap().push(handle.abort_handle());
+            }
+        });
+        addr
+    }
+
+    fn drop_connections(&self) {
+        for handle in self.connections.lock().unwrap().drain(..) {
+            handle.abort();
+        }
+    }
+}
+
+async fn next(stream: &mut BoxedStream<Echoed>) -> Option<Echoed> {
+    timeout(Duration::from_secs(5), stream.next())
+        .await
+        .expect("timed out waiting for a message")
+        .map(|msg| msg.unwrap())
+}
+
+fn shout(text: &str) -> Shout {
+    Shout { text: text.into() }
+}
+
+fn echoed(text: &str) -> Echoed {
+    Echoed {
+        text: text.to_uppercase(),
+        len: text.len(),
+    }
+}
+
+#[tokio::test]
+async fn websocket_server_fn_streams_typed_messages_and_reconnects() {
+    let app =
+        Router::new().route(Echo::PATH, get(leptos_axum::handle_server_fns));
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let server_addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    let proxy = Proxy::default();
+    let proxy_addr = proxy.clone().start(server_addr).await;
+    set_server_url(format!("http://{proxy_addr}").leak());
+
+    // an input stream is sent message by message, and the output stream ends with it
+    let mut output = echo(BoxedStream::new(futures::stream::iter(vec![
+        Ok(shout("hello")),
+        Ok(shout("websocket")),
+    ])))
+    .await
+    .unwrap();
+    assert_eq!(next(&mut output).await, Some(echoed("hello")));
+    assert_eq!(next(&mut output).await, Some(echoed("websocket")));
+    assert_eq!(next(&mut output).await, None);
+
+    // a dropped connection is reopened, and messages sent meanwhile are delivered
+    let (mut tx, mut output) =
+        Echo::connect_with(Backoff::new().initial(Duration::from_millis(10)));
+    tx.send(shout("before")).await.unwrap();
+    assert_eq!(next(&mut output).await, Some(echoed("before")));
+
+    // messages written to a connection before the client notices that it was dropped are lost,
+    // so wait for the reset to arrive
+    proxy.drop_connections();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    tx.send(shout("after")).await.unwrap();
+    assert_eq!(next(&mut output).await, Some(echoed("after")));
+
+    drop(tx);
+    assert_eq!(next(&mut output).await, None);
+}
@@ -1,32 +1,53 @@
 use crate::{children::TypedChildren, IntoView};
+use any_spawner::Executor;
 use hydration_context::{SerializedDataId, SharedContext};
 use leptos_macro::component;
+use or_poisoned::OrPoisoned;
 use reactive_graph::{
     computed::ArcMemo,
     effect::RenderEffect,
     owner::{provide_context, Owner},
     signal::ArcRwSignal,
-    traits::{Get, Update, With, WithUntracked},
+    traits::{Get, GetUntracked, Set, Update, With, WithUntracked},
+    wrappers::read::{ArcSignal, Signal},
+};
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::{
+    fmt::Debug,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
 };
-use rustc_hash::FxHashMap;
-use std::{fmt::Debug, sync::Arc};
 use tachys::{
     html::attribute::Attribute,
     hydration::Cursor,
     reactive_graph::OwnedView,
-    ssr::StreamBuilder,
+    ssr::{BranchMarks, StreamBuilder, StreamChunk},
     view::{
         add_attr::AddAnyAttr, Mountable, Position, PositionState, Render,
         RenderHtml,
     },
 };
-use throw_error::{Error, ErrorHook, ErrorId};
+use throw_error::{Error, ErrorHook, ErrorHookFuture, ErrorId};
 
 /// When you render a `Result<_, _>` in your view, in the `Err` case it will
 /// render nothing, and search up through the view tree for an `<ErrorBoundary/>`.
 /// This component lets you define a fallback that should be rendered in that
 /// error case, allowing you to handle errors within a section of the interface.
 ///
+/// This includes errors returned later by a `Suspend` inside the boundary. The
+/// error is cleared again when the `Suspend` is rebuilt with a successful result.
+/// When streaming from the server, a boundary whose children are still loading
+/// waits for them (in-order streaming) or replaces them with the fallback once
+/// they fail (out-of-order streaming), and the errors are sent to the client.
+///
+/// Both the children and the fallback can access an [`ErrorBoundaryHandle`] via
+/// `use_context::<ErrorBoundaryHandle>()`. Its [`retry_with`](ErrorBoundaryHandle::retry_with)
+/// method clears the current errors and keeps retrying with a backoff for as long as the same
+/// errors are thrown again, up to `max_attempts` times.
+///
 /// ```
 /// # use leptos::prelude::*;
 /// #[component]
@@ -46,6 +67,45 @@ use throw_error::{Error, ErrorHook, ErrorId};
 /// }
 /// ```
 ///
+/// ## Handling Different Kinds of Errors
+/// Errors are stored as a type-erased [`Error`], but keep their concrete type, so the fallback
+/// can use [`Error::downcast_ref`] to handle some errors differently from others:
+///
+/// ```
+/// # use leptos::prelude::*;
+/// # #[derive(Debug)] struct AuthError;
+/// # impl std::fmt::Display for AuthError {
+/// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { f.write_str("auth") }
+/// # }
+/// # impl std::error::Error for AuthError {}
+/// # #[component] fn Dashboard() -> impl IntoView {}
+/// #[component]
+/// pub fn Account() -> impl IntoView {
+///   view! {
+///     <ErrorBoundary fallback=|errors| {
+///       errors
+///         .get()
+///         .into_iter()
+///         .map(|(_, error)| {
+///           if error.is::<AuthError>() {
+///             view! { <a href="/login">"Log in"</a> }.into_any()
+///           } else {
+///             view! { <p>{error.to_string()}</p> }.into_any()
+///           }
+///         })
+///         .collect::<Vec<_>>()
+///     }>
+///       <Dashboard/>
+///     </ErrorBoundary>
+///   }
+/// }
+/// ```
+///
+/// Errors caught while rendering on the server are sent to the client as text, and only keep
+/// their type if it has been registered on both sides with
+/// [`register_error_type`](throw_error::register_error_type), or with
+/// [`server_fn::error::register_error_type`] for server function errors.
+///
 /// ## Beginner's Tip: ErrorBoundary Requires Your Error To Implement std::error::Error.
 /// `ErrorBoundary` requires your `Result<T,E>` to implement [IntoView](https://docs.rs/leptos/latest/leptos/trait.IntoView.html).
 /// `Result<T,E>` only implements `IntoView` if `E` implements [std::error::Error](https://doc.rust-lang.org/std/error/trait.Error.html).
@@ -73,6 +133,9 @@ pub fn ErrorBoundary<FalFn, Fal, Chil>(
     children: TypedChildren<Chil>,
     /// A fallback that will be shown if an error occurs.
     fallback: FalFn,
+    /// The maximum number of attempts made by [`ErrorBoundaryHandle::retry_with`]. Defaults to 3.
+    #[prop(optional)]
+    max_attempts: Option<u32>,
 ) -> impl IntoView
 where
     FalFn: FnMut(ArcRwSignal<Errors>) -> Fal + Send + 'static,
@@ -87,8 +150,10 @@ where
     let hook = Arc::new(ErrorBoundaryErrorHook::new(
         boundary_id.clone(),
         initial_errors,
+        max_attempts.unwrap_or(DEFAULT_MAX_ATTEMPTS),
     ));
-    let errors = hook.errors.clone();
+    let handle = hook.handle.clone();
+    let errors = handle.errors.clone();
     let errors_empty = ArcMemo::new({
         let errors = errors.clone();
         move |_| errors.with(|map| map.is_empty())
@@ -99,7 +164,13 @@ where
 
     let owner = Owner::new();
     let children = owner.with(|| {
+        // a retry that is still waiting must not clear errors once the boundary is gone
+        Owner::on_cleanup({
+            let handle = handle.clone();
+            move || handle.cancel_retry()
+        });
         provide_context(Arc::clone(&hook));
+        provide_context(handle);
         children.into_inner()()
     });
 
@@ -300,7 +371,7 @@ where
         buf: &mut String,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) {
         // first, attempt to serialize the children to HTML, then check for errors
         let _hook = throw_error::set_error_hook(self.hook);
@@ -332,13 +403,15 @@ where
         buf: &mut StreamBuilder,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) where
         Self: Sized,
     {
         let _hook = throw_error::set_error_hook(self.hook);
         // first, attempt to serialize the children to HTML, then check for errors
-        let mut new_buf = StreamBuilder::new(buf.clone_id());
+        // the children get IDs of their own, so that the boundary can be replaced out of order
+        buf.next_id();
+        let mut new_buf = StreamBuilder::new(buf.child_id());
         let mut new_pos = *position;
         self.children.to_html_async_with_buf::<OUT_OF_ORDER>(
             &mut new_buf,
@@ -346,11 +419,10 @@ where
             escape,
             mark_branches,
         );
+        let mut new_buf = new_buf.finish();
 
-        // any thrown errors would've been caught here
-        if self.errors.with_untracked(|map| map.is_empty()) {
-            buf.append(new_buf);
-        } else {
+        // any errors thrown synchronously would've been caught here
+        if !self.errors.with_untracked(|map| map.is_empty()) {
             // otherwise, serialize the fallback instead
             let mut fallback = String::with_capacity(Fal::MIN_LENGTH);
             (self.fallback)(self.errors).to_html_with_buf(
@@ -360,6 +432,76 @@ where
                 mark_branches,
             );
             buf.push_sync(&fallback);
+            return;
+        }
+
+        let chunks = new_buf.take_chunks();
+        if chunks
+            .iter()
+            .all(|chunk| matches!(chunk, StreamChunk::Sync(_)))
+        {
+            for chunk in chunks {
+                if let StreamChunk::Sync(html) = chunk {
+                    buf.push_sync(&html);
+                }
+            }
+            return;
+        }
+
+        // some children are still pending, and may throw errors once they have loaded
+        let owner = Owner::current().unwrap_or_default();
+        let ErrorBoundaryView {
+            mut fallback,
+            errors,
+            ..
+        } = self;
+        if OUT_OF_ORDER {
+            // stream the children as usual, and replace them with the fallback if they throw
+            let (chunks, ready) = StreamChunk::with_error_hook(chunks);
+            buf.write_chunk_marker(true);
+            buf.push_chunks(chunks);
+            buf.write_chunk_marker(false);
+            let mark_branches = mark_branches.clone();
+            buf.push_async_out_of_order_with(
+                async move {
+                    ready.await;
+                    (!errors.with_untracked(|map| map.is_empty()))
+                        .then_some(errors)
+                },
+                position,
+                move |errors, builder, position| {
+                    owner.with(|| {
+                        fallback(errors).to_html_async_with_buf::<true>(
+                            builder,
+                            position,
+                            escape,
+                            &mark_branches,
+                        )
+                    })
+                },
+            );
+        } else {
+            // an in-order stream can't take back what it has sent, so wait for the children
+            let id = buf.clone_id();
+            let mut position = *position;
+            let mark_branches = mark_branches.clone();
+            buf.push_async(ErrorHookFuture::new(async move {
+                let chunks = StreamChunk::resolve_all(chunks).await;
+                if errors.with_untracked(|map| map.is_empty()) {
+                    chunks
+                } else {
+                    let mut builder = StreamBuilder::new(id);
+                    owner.with(|| {
+                        fallback(errors).to_html_async_with_buf::<false>(
+                            &mut builder,
+                            &mut position,
+                            escape,
+                            &mark_branches,
+                        )
+                    });
+                    builder.finish().take_chunks()
+                }
+            }));
         }
     }
 
@@ -425,36 +567,40 @@ where
     }
 }
 
+const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+
 #[derive(Debug)]
 struct ErrorBoundaryErrorHook {
-    errors: ArcRwSignal<Errors>,
+    handle: ErrorBoundaryHandle,
     id: SerializedDataId,
     shared_context: Option<Arc<dyn SharedContext + Send + Sync>>,
+    next_local_id: AtomicUsize,
 }
 
 impl ErrorBoundaryErrorHook {
     pub fn new(
         id: SerializedDataId,
         initial_errors: impl IntoIterator<Item = (ErrorId, Error)>,
+        max_attempts: u32,
     ) -> Self {
+        let attempt = ArcRwSignal::new(0);
         Self {
-            errors: ArcRwSignal::new(Errors(
-                initial_errors.into_iter().collect(),
-            )),
+            handle: ErrorBoundaryHandle {
+                errors: ArcRwSignal::new(Errors(
+                    initial_errors.into_iter().collect(),
+                )),
+                attempt_signal: ArcSignal::from(attempt.clone()).into(),
+                attempt,
+                max_attempts,
+                retry: Default::default(),
+            },
             id,
             shared_context: Owner::current_shared_context(),
+            next_local_id: AtomicUsize::new(0),
         }
     }
-}
-
-impl ErrorHook for ErrorBoundaryErrorHook {
-    fn throw(&self, error: Error) -> ErrorId {
-        // generate a unique ID
-        let key: ErrorId = Owner::current_shared_context()
-            .map(|sc| sc.next_id())
-            .unwrap_or_default()
-            .into();
 
+    fn register(&self, key: &ErrorId, error: Error) {
         // register it with the shared context, so that it can be serialized from server to client
         // as needed
         if let Some(sc) = &self.shared_context {
@@ -462,20 +608,166 @@ impl ErrorHook for ErrorBoundaryErrorHook {
         }
 
         // add it to the reactive map of errors
-        self.errors.update(|map| {
+        self.handle.errors.update(|map| {
             map.insert(key.clone(), error);
         });
+    }
+}
+
+impl ErrorHook for ErrorBoundaryErrorHook {
+    fn throw(&self, error: Error) -> ErrorId {
+        // generate a unique ID
+        let key: ErrorId = match Owner::current_shared_context() {
+            Some(sc) => sc.next_id().into(),
+            None => self.next_local_id.fetch_add(1, Ordering::Relaxed).into(),
+        };
+
+        self.register(&key, error);
 
         // return the key, which will be owned by the Result being rendered and can be used to
         // unregister this error if it is rebuilt
         key
     }
 
+    fn rethrow(&self, id: &ErrorId, error: Error) -> ErrorId {
+        // keep the same key, so that a retry can tell that this error has come back
+        self.register(id, error);
+        self.handle.schedule_retry(id);
+        id.clone()
+    }
+
     fn clear(&self, id: &throw_error::ErrorId) {
+        self.handle.clear_err(id);
+    }
+}
+
+/// Allows the children or fallback of an [`ErrorBoundary`] to inspect its errors and to retry.
+///
+/// This is provided as context within the boundary.
+#[derive(Debug, Clone)]
+pub struct ErrorBoundaryHandle {
+    errors: ArcRwSignal<Errors>,
+    attempt: ArcRwSignal<u32>,
+    attempt_signal: Signal<u32>,
+    max_attempts: u32,
+    retry: Arc<Mutex<RetryState>>,
+}
+
+#[derive(Default)]
+struct RetryState {
+    generation: usize,
+    active: Option<Retry>,
+}
+
+struct Retry {
+    ids: FxHashSet<ErrorId>,
+    backoff: Arc<dyn Fn(u32) -> Duration + Send + Sync>,
+    scheduled: bool,
+}
+
+impl Debug for RetryState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RetryState")
+            .field("generation", &self.generation)
+            .field("active", &self.active.as_ref().map(|retry| &retry.ids))
+            .finish()
+    }
+}
+
+impl ErrorBoundaryHandle {
+    /// The reactive map of errors currently caught by the boundary.
+    pub fn errors(&self) -> ArcRwSignal<Errors> {
+        self.errors.clone()
+    }
+
+    /// Clears a single error. The boundary shows its children again once no errors are left.
+    pub fn clear_err(&self, id: &ErrorId) {
         self.errors.update(|map| {
             map.remove(id);
         });
     }
+
+    /// The current retry attempt, starting at `1` for the first call to
+    /// [`retry_with`](Self::retry_with) and `0` before any retry.
+    ///
+    /// Resources inside the boundary can track this to refetch when a retry is made.
+    pub fn attempt(&self) -> Signal<u32> {
+        self.attempt_signal
+    }
+
+    /// Clears all current errors and retries.
+    ///
+    /// If any of these errors is thrown again during an attempt, it is cleared again after
+    /// waiting for `backoff(attempt)`, until the boundary's maximum number of attempts is
+    /// reached. Errors that were not present when this was called do not cause a retry.
+    pub fn retry_with(
+        &self,
+        backoff: impl Fn(u32) -> Duration + Send + Sync + 'static,
+    ) {
+        let ids = self
+            .errors
+            .with_untracked(|errors| errors.0.keys().cloned().collect());
+        {
+            let mut retry = self.retry.lock().or_poisoned();
+            retry.generation += 1;
+            retry.active = Some(Retry {
+                ids,
+                backoff: Arc::new(backoff),
+                scheduled: false,
+            });
+        }
+        self.errors.update(|errors| errors.0.clear());
+        self.attempt.set(1);
+    }
+
+    fn schedule_retry(&self, id: &ErrorId) {
+        let mut retry = self.retry.lock().or_poisoned();
+        let generation = retry.generation;
+        let Some(active) = &mut retry.active else {
+            return;
+        };
+        // several errors coming back in the same attempt only schedule a single retry
+        if !active.ids.contains(id) || active.scheduled {
+            return;
+        }
+        let attempt = self.attempt.get_untracked();
+        if attempt >= self.max_attempts {
+            retry.active = None;
+            return;
+        }
+        active.scheduled = true;
+        let delay = (active.backoff)(attempt);
+        let this = self.clone();
+        reactive_graph::spawn_local(async move {
+            Executor::sleep(delay).await;
+            let ids = {
+                let mut retry = this.retry.lock().or_poisoned();
+                // retry_with was called again in the meantime
+                if retry.generation != generation {
+                    return;
+                }
+                match &mut retry.active {
+                    Some(active) => {
+                        active.scheduled = false;
+                        active.ids.clone()
+                    }
+                    None => return,
+                }
+            };
+            this.errors.update(|errors| {
+                for id in &ids {
+                    errors.remove(id);
+                }
+            });
+            this.attempt.update(|n| *n += 1);
+        });
+    }
+
+    fn cancel_retry(&self) {
+        let mut retry = self.retry.lock().or_poisoned();
+        retry.generation += 1;
+        retry.active = None;
+    }
 }
 
 /// A struct to hold all the possible errors that could be provided by child Views
@@ -557,3 +849,157 @@ impl<'a> Iterator for Iter<'a> {
         self.0.next()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use any_spawner::Executor;
+
+    #[test]
+    fn error_boundary_retries_errors_that_come_back() {
+        use reactive_graph::computed::ArcAsyncDerived;
+        use std::{
+            sync::{
+                atomic::{AtomicUsize, Ordering},
+                Arc, Mutex,
+            },
+            time::Duration,
+        };
+        use throw_error::ErrorId;
+
+        _ = Executor::init_manual();
+        let owner = Owner::new();
+        owner.set();
+
+        let boundary = Arc::new(Mutex::new(None));
+        let _view = {
+            let boundary = Arc::clone(&boundary);
+            view! {
+                <ErrorBoundary fallback=|_| "failed">
+                    {
+                        *boundary.lock().unwrap() = Some((
+                            expect_context::<ErrorBoundaryHandle>(),
+                            throw_error::get_error_hook().unwrap(),
+                        ));
+                    }
+                </ErrorBoundary>
+            }
+        };
+        let (handle, hook) = boundary.lock().unwrap().take().unwrap();
+        let errors = handle.errors();
+        let attempt = handle.attempt();
+
+        // a resource that fails twice, then succeeds
+        let calls = Arc::new(AtomicUsize::new(0));
+        let loaded = ArcAsyncDerived::new({
+            let calls = Arc::clone(&calls);
+            move || {
+                attempt.track();
+                let call = calls.fetch_add(1, Ordering::SeqCst);
+                async move { call >= 2 }
+            }
+        });
+        // rebuilds the resource's view the way a `Result` does: an error that comes back is
+        // rethrown with the id it had before
+        let mut error = None::<ErrorId>;
+        let mut rebuild = move || {
+            let _hook = throw_error::set_error_hook(Arc::clone(&hook));
+            let offline = || std::io::Error::other("offline");
+            error = match (loaded.get_untracked(), error.take()) {
+                (Some(false), Some(id)) => {
+                    Some(throw_error::rethrow(&id, offline()))
+                }
+                (Some(false), None) => Some(throw_error::throw(offline())),
+                (Some(true), Some(id)) => {
+                    throw_error::clear(&id);
+                    None
+                }
+                (_, error) => error,
+            };
+        };
+
+        Executor::flush();
+        rebuild();
+        let id = errors
+            .with_untracked(|errors| {
+                errors.iter().next().map(|(id, _)| id.clone())
+            })
+            .unwrap();
+
+        handle
+            .retry_with(|attempt| Duration::from_millis(100 * attempt as u64));
+        assert!(errors.with_untracked(Errors::is_empty));
+        assert_eq!(attempt.get_untracked(), 1);
+
+        // the second call fails again, with the same error id
+        Executor::flush();
+        rebuild();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert!(errors.with_untracked(|errors| errors
+            .iter()
+            .map(|(id, _)| id.clone())
+            .eq([id.clone()])));
+
+        // after the backoff, the boundary retries on its own and the third call succeeds
+        Executor::advance_time(Duration::from_millis(99));
+        assert_eq!(attempt.get_untracked(), 1);
+        Executor::advance_time(Duration::from_millis(1));
+        assert_eq!(attempt.get_untracked(), 2);
+        assert!(errors.with_untracked(Errors::is_empty));
+        Executor::flush();
+        rebuild();
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        assert!(errors.with_untracked(Errors::is_empty));
+    }
+
+    #[test]
+    fn error_boundary_disposed_before_retry_does_not_retry() {
+        use std::{
+            sync::{Arc, Mutex},
+            time::Duration,
+        };
+
+        _ = Executor::init_manual();
+        let owner = Owner::new();
+        owner.set();
+
+        let boundary = Arc::new(Mutex::new(None));
+        let view = {
+            let boundary = Arc::clone(&boundary);
+            view! {
+                <ErrorBoundary fallback=|_| "failed">
+                    {
+                        *boundary.lock().unwrap() = Some((
+                            expect_context::<ErrorBoundaryHandle>(),
+                            throw_error::get_error_hook().unwrap(),
+                        ));
+                    }
+                </ErrorBoundary>
+            }
+        };
+        let (handle, hook) = boundary.lock().unwrap().take().unwrap();
+        let errors = handle.errors();
+        let attempt = handle.attempt();
+        let offline = || std::io::Error::other("offline");
+
+        let id = {
+            let _hook = throw_error::set_error_hook(Arc::clone(&hook));
+            throw_error::throw(offline())
+        };
+        handle.retry_with(|_| Duration::from_millis(100));
+        // the error comes back, which schedules a retry
+        {
+            let _hook = throw_error::set_error_hook(Arc::clone(&hook));
+            throw_error::rethrow(&id, offline());
+        }
+        assert_eq!(attempt.get_untracked(), 1);
+
+        drop(view);
+        Executor::advance_time(Duration::from_millis(100));
+        assert_eq!(attempt.get_untracked(), 1);
+        assert!(errors.with_untracked(|errors| errors
+            .iter()
+            .map(|(id, _)| id.clone())
+            .eq([id.clone()])));
+    }
+}
@@ -0,0 +1,57 @@
+((hydrate_root) => {
+	const types = ["click", "dblclick", "input", "change", "submit", "keydown", "keyup", "focusin", "focusout"];
+	const queue = (window.__LEPTOS_EVENTS = window.__LEPTOS_EVENTS || []);
+	function rootNode() {
+		return (hydrate_root && document.querySelector(hydrate_root)) || document.body;
+	}
+	function pathTo(node) {
+		const root = rootNode();
+		const path = [];
+		while (node && node !== root) {
+			const parent = node.parentNode;
+			if (!parent) {
+				return null;
+			}
+			path.unshift(Array.prototype.indexOf.call(parent.childNodes, node));
+			node = parent;
+		}
+		return node === root ? path : null;
+	}
+	function record(ev) {
+		const target = ev.target;
+		const path = pathTo(target);
+		if (!path) {
+			return;
+		}
+		const entry = { type: ev.type, path };
+		if (target.type === "file") {
+			entry.file = true;
+		} else if (typeof target.value === "string") {
+			entry.value = target.value;
+		}
+		if (target.type === "checkbox" || target.type === "radio") {
+			entry.checked = target.checked;
+		}
+		if (typeof ev.key === "string") {
+			entry.key = ev.key;
+		}
+		// the form can't be submitted until there is something to handle it
+		if (ev.type === "submit") {
+			ev.preventDefault();
+		}
+		queue.push(entry);
+		const load = window.__LEPTOS_LOAD;
+		if (load) {
+			window.__LEPTOS_LOAD = null;
+			load();
+		}
+	}
+	for (const type of types) {
+		document.addEventListener(type, record, true);
+	}
+	window.__LEPTOS_STOP_RECORDING = () => {
+		for (const type of types) {
+			document.removeEventListener(type, record, true);
+		}
+	};
+})
@@ -1,8 +1,16 @@
-(function (root, pkg_path, output_name, wasm_output_name) {
-	import(`${root}/${pkg_path}/${output_name}.js`)
-		.then(mod => {
-			mod.default(`${root}/${pkg_path}/${wasm_output_name}.wasm`).then(() => {
-				mod.hydrate();
-			});
-		})
+(function (root, pkg_path, output_name, wasm_output_name, hydrate_root, defer) {
+	function load() {
+		import(`${root}/${pkg_path}/${output_name}.js`)
+			.then(mod => {
+				mod.default(`${root}/${pkg_path}/${wasm_output_name}.wasm`).then(() => {
+					mod.hydrate();
+				});
+			})
+	}
+	// wait for the first event, unless one has already been recorded
+	if (defer && !(window.__LEPTOS_EVENTS && window.__LEPTOS_EVENTS.length)) {
+		window.__LEPTOS_LOAD = load;
+	} else {
+		load();
+	}
 })
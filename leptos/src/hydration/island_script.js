@@ -1,4 +1,4 @@
-((root, pkg_path, output_name, wasm_output_name) => {
+((root, pkg_path, output_name, wasm_output_name, hydrate_root) => {
 	function idle(c) {
 		if ("requestIdleCallback" in window) {
 			window.requestIdleCallback(c);
@@ -54,7 +54,8 @@
 			.then(mod => {
 				mod.default(`${root}/${pkg_path}/${wasm_output_name}.wasm`).then(() => {
 					mod.hydrate();
-					hydrateIslands(islandTree(document.body, null), mod);
+					const rootNode = (hydrate_root && document.querySelector(hydrate_root)) || document.body;
+					hydrateIslands(islandTree(rootNode, null), mod);
 				});
 			})
 	});
@@ -4,6 +4,11 @@ use crate::prelude::*;
 use leptos_config::LeptosOptions;
 use leptos_macro::{component, view};
 
+#[cfg(feature = "hydrate")]
+mod replay;
+#[cfg(feature = "hydrate")]
+pub use replay::*;
+
 #[component]
 pub fn AutoReload(
     #[prop(optional)] disable_watch: bool,
@@ -41,6 +46,16 @@ pub fn HydrationScripts(
     /// A base url, not including a trailing slash
     #[prop(optional, into)]
     root: Option<String>,
+    /// A CSS selector for the element the app is hydrated from, if it is not the whole
+    /// `<body>`. In islands mode, only islands inside this element are hydrated.
+    #[prop(optional, into)]
+    hydrate_root: Option<String>,
+    /// Waits for the first interaction with the page before loading the WASM, and records the
+    /// events that happen in the meantime, so that they can be replayed once the app has been
+    /// hydrated with [`hydrate_body_with_replay`](crate::mount::hydrate_body_with_replay).
+    /// This has no effect in islands mode.
+    #[prop(optional)]
+    replay_events: bool,
 ) -> impl IntoView {
     let mut js_file_name = options.output_name.to_string();
     let mut wasm_file_name = options.output_name.to_string();
@@ -87,17 +102,29 @@ pub fn HydrationScripts(
     };
 
     let root = root.unwrap_or_default();
+    let hydrate_root = hydrate_root.unwrap_or_default();
+    let replay_events = replay_events && !islands;
+    let recorder = replay_events.then(|| {
+        let recorder = include_str!("./event_replay.js");
+        view! { <script nonce=nonce.clone()>{format!("{recorder}({hydrate_root:?})")}</script> }
+    });
+    let preload = (!replay_events).then(|| {
+        view! {
+            <link rel="modulepreload" href=format!("{root}/{pkg_path}/{js_file_name}.js") nonce=nonce.clone()/>
+            <link
+                rel="preload"
+                href=format!("{root}/{pkg_path}/{wasm_file_name}.wasm")
+                r#as="fetch"
+                r#type="application/wasm"
+                crossorigin=nonce.clone().unwrap_or_default()
+            />
+        }
+    });
     view! {
-        <link rel="modulepreload" href=format!("{root}/{pkg_path}/{js_file_name}.js") nonce=nonce.clone()/>
-        <link
-            rel="preload"
-            href=format!("{root}/{pkg_path}/{wasm_file_name}.wasm")
-            r#as="fetch"
-            r#type="application/wasm"
-            crossorigin=nonce.clone().unwrap_or_default()
-        />
+        {preload}
+        {recorder}
         <script type="module" nonce=nonce>
-            {format!("{script}({root:?}, {pkg_path:?}, {js_file_name:?}, {wasm_file_name:?})")}
+            {format!("{script}({root:?}, {pkg_path:?}, {js_file_name:?}, {wasm_file_name:?}, {hydrate_root:?}, {replay_events})")}
         </script>
     }
 }
@@ -0,0 +1,180 @@
+use crate::logging;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{
+    Event, EventInit, HtmlFormElement, KeyboardEvent, KeyboardEventInit,
+    MouseEvent, MouseEventInit, Node,
+};
+
+const QUEUE: &str = "__LEPTOS_EVENTS";
+const STOP_RECORDING: &str = "__LEPTOS_STOP_RECORDING";
+
+/// An event that happened before the app was hydrated.
+///
+/// These are recorded by the script that [`HydrationScripts`](super::HydrationScripts) adds to
+/// the page when `replay_events` is set.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RecordedEvent {
+    /// The type of the event, like `click` or `input`.
+    pub event_type: String,
+    /// The path to the target of the event, as the index of each node among its parent's child
+    /// nodes, starting from the root element the app is hydrated from.
+    pub path: Vec<u32>,
+    /// The `value` of the target when the event happened, if it has one.
+    pub value: Option<String>,
+    /// Whether the target was checked, if it is a checkbox or radio button.
+    pub checked: Option<bool>,
+    /// The `key` of a keyboard event.
+    pub key: Option<String>,
+    /// Whether the target is a file input. Files can't be set on an input from code, so these
+    /// events are never replayed.
+    pub file: bool,
+}
+
+/// Events that happened before the app was hydrated, in the order they happened.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventQueue(pub Vec<RecordedEvent>);
+
+impl EventQueue {
+    /// Creates a queue of events.
+    pub fn new(events: Vec<RecordedEvent>) -> Self {
+        Self(events)
+    }
+
+    /// Stops recording events, and takes the ones that have been recorded so far.
+    ///
+    /// Returns an empty queue if the recording script is not on the page.
+    pub fn take_recorded() -> Self {
+        let window = JsValue::from(window());
+        if let Ok(stop) = js_sys::Reflect::get(&window, &STOP_RECORDING.into())
+        {
+            if let Some(stop) = stop.dyn_ref::<js_sys::Function>() {
+                _ = stop.call0(&JsValue::NULL);
+            }
+        }
+        let events = js_sys::Reflect::get(&window, &QUEUE.into())
+            .ok()
+            .and_then(|queue| queue.dyn_into::<js_sys::Array>().ok())
+            .map(|queue| queue.iter().filter_map(read_event).collect())
+            .unwrap_or_default();
+        _ = js_sys::Reflect::set(&window, &QUEUE.into(), &JsValue::UNDEFINED);
+        Self(events)
+    }
+
+    /// Dispatches each event to its target under `root`, in order.
+    ///
+    /// Before each event is dispatched, the value of its target is restored to what it was when
+    /// the event happened. Form submissions go through `requestSubmit()`, so that the form is
+    /// only submitted if no handler prevents it. Events whose target can't be found, or is a file
+    /// input, are skipped with a warning.
+    pub fn replay(self, root: &web_sys::Element) {
+        for event in self.0 {
+            if event.file {
+                logging::warn!(
+                    "not replaying `{}` event on a file input, since its \
+                     files can't be restored",
+                    event.event_type
+                );
+                continue;
+            }
+            let Some(target) = resolve_path(root, &event.path) else {
+                logging::warn!(
+                    "not replaying `{}` event, since its target at {:?} was \
+                     not found",
+                    event.event_type,
+                    event.path
+                );
+                continue;
+            };
+            event.dispatch(&target);
+        }
+    }
+}
+
+impl RecordedEvent {
+    fn dispatch(&self, target: &Node) {
+        if let Some(value) = &self.value {
+            _ = js_sys::Reflect::set(target, &"value".into(), &value.into());
+        }
+        if let Some(checked) = self.checked {
+            // a click toggles a checkbox again as it is dispatched
+            let checked = if self.event_type == "click" {
+                !checked
+            } else {
+                checked
+            };
+            _ = js_sys::Reflect::set(
+                target,
+                &"checked".into(),
+                &checked.into(),
+            );
+        }
+
+        if self.event_type == "submit" {
+            if let Some(form) = target.dyn_ref::<HtmlFormElement>() {
+                _ = form.request_submit();
+                return;
+            }
+        }
+
+        let ty = self.event_type.as_str();
+        let event: Option<Event> = match ty {
+            "click" | "dblclick" => {
+                let init = MouseEventInit::new();
+                init.set_bubbles(true);
+                init.set_cancelable(true);
+                MouseEvent::new_with_mouse_event_init_dict(ty, &init)
+                    .ok()
+                    .map(Into::into)
+            }
+            "keydown" | "keyup" => {
+                let init = KeyboardEventInit::new();
+                init.set_bubbles(true);
+                init.set_cancelable(true);
+                if let Some(key) = &self.key {
+                    init.set_key(key);
+                }
+                KeyboardEvent::new_with_keyboard_event_init_dict(ty, &init)
+                    .ok()
+                    .map(Into::into)
+            }
+            _ => {
+                let init = EventInit::new();
+                init.set_bubbles(true);
+                init.set_cancelable(true);
+                Event::new_with_event_init_dict(ty, &init).ok()
+            }
+        };
+        if let Some(event) = event {
+            _ = target.dispatch_event(&event);
+        }
+    }
+}
+
+fn window() -> web_sys::Window {
+    web_sys::window().expect("no window")
+}
+
+fn resolve_path(root: &web_sys::Element, path: &[u32]) -> Option<Node> {
+    let mut node: Node = root.clone().into();
+    for index in path {
+        node = node.child_nodes().item(*index)?;
+    }
+    Some(node)
+}
+
+fn read_event(entry: JsValue) -> Option<RecordedEvent> {
+    let get = |key: &str| js_sys::Reflect::get(&entry, &key.into()).ok();
+    Some(RecordedEvent {
+        event_type: get("type")?.as_string()?,
+        path: get("path")?
+            .dyn_into::<js_sys::Array>()
+            .ok()?
+            .iter()
+            .map(|index| index.as_f64().map(|index| index as u32))
+            .collect::<Option<_>>()?,
+        value: get("value").and_then(|value| value.as_string()),
+        checked: get("checked").and_then(|checked| checked.as_bool()),
+        key: get("key").and_then(|key| key.as_string()),
+        file: get("file").and_then(|file| file.as_bool()).unwrap_or(false),
+    })
+}
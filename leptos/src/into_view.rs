@@ -2,7 +2,7 @@ use std::borrow::Cow;
 use tachys::{
     html::attribute::Attribute,
     hydration::Cursor,
-    ssr::StreamBuilder,
+    ssr::{BranchMarks, StreamBuilder},
     view::{
         add_attr::AddAnyAttr, Position, PositionState, Render, RenderHtml,
         ToTemplate,
@@ -97,7 +97,7 @@ impl<T: RenderHtml> RenderHtml for View<T> {
         buf: &mut String,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) {
         #[cfg(debug_assertions)]
         let vm = self.view_marker.to_owned();
@@ -120,7 +120,7 @@ impl<T: RenderHtml> RenderHtml for View<T> {
         buf: &mut StreamBuilder,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) where
         Self: Sized,
     {
@@ -90,6 +90,14 @@
 //! - **`rkyv`** In SSR/hydrate mode, uses [`rkyv`](https://docs.rs/rkyv/latest/rkyv/) to serialize resources and send them
 //!   from the server to the client.
 //! - **`tracing`** Adds support for [`tracing`](https://docs.rs/tracing/latest/tracing/).
+//! - **`persist`** Adds signals that are saved to storage, like `localStorage`, in `reactive::persist`.
+//! - **`reactive_stores`** Allows fields of a [`reactive_stores`](https://docs.rs/reactive_stores/latest/reactive_stores/)
+//!   store to be used directly as views and attribute values.
+//! - **`panic-isolation`** Catches panics in effects, so that the effect stops running and the hook set with
+//!   `reactive::effect::on_effect_panic` is called, instead of the panic unwinding through the reactive system.
+//!   This only works on targets that unwind: `wasm32-unknown-unknown` aborts on panic, so it has no effect in the browser.
+//! - **`graph-introspection`** Records the signals, memos, and effects in the reactive graph, including
+//!   the ones that update the DOM, so that they can be inspected with `reactive::introspect::snapshot`.
 //!
 //! **Important Note:** You must enable one of `csr`, `hydrate`, or `ssr` to tell Leptos
 //! which mode your app is operating in. You should only enable one of these per build target,
@@ -168,12 +176,25 @@ pub mod prelude {
         pub use leptos_server::*;
         pub use oco_ref::*;
         pub use reactive_graph::{
-            actions::*, computed::*, effect::*, graph::untrack, owner::*,
-            signal::*, wrappers::read::*,
+            actions::*,
+            batch,
+            computed::*,
+            diagnostics::allow_untracked_read,
+            effect::*,
+            graph::{untrack, untrack_with_diagnostics},
+            owner::*,
+            signal::*,
+            wrappers::{
+                read::*,
+                write::{Model, SignalSetter},
+            },
         };
         pub use server_fn::{self, ServerFnError};
         pub use tachys::{
-            reactive_graph::{bind::BindAttribute, node_ref::*, Suspend},
+            reactive_graph::{
+                bind::BindAttribute, node_ref::*, on_trigger, StreamResolution,
+                Suspend, SuspendStream,
+            },
             view::template::ViewTemplate,
         };
     }
@@ -258,7 +279,10 @@ pub use reactive_graph as reactive;
 /// Provide and access data along the reactive graph, sharing data without directly passing arguments.
 pub mod context {
     pub use crate::provider::*;
-    pub use reactive_graph::owner::{provide_context, use_context};
+    pub use reactive_graph::owner::{
+        provide_context, provide_context_in, use_context,
+    };
+    pub use tachys::reactive_graph::provide_context_view;
 }
 
 #[doc(inline)]
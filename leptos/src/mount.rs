@@ -1,17 +1,20 @@
+#[cfg(feature = "hydrate")]
+use crate::hydration::EventQueue;
 #[cfg(debug_assertions)]
 use crate::logging;
 use crate::IntoView;
-use any_spawner::Executor;
+use any_spawner::{CustomSpawner, Executor};
 use reactive_graph::owner::Owner;
 #[cfg(debug_assertions)]
 use std::cell::Cell;
+use std::sync::Arc;
 use tachys::{
     dom::body,
     view::{Mountable, Render},
 };
 #[cfg(feature = "hydrate")]
 use tachys::{
-    hydration::Cursor,
+    hydration::{Cursor, LenientHydration},
     view::{PositionState, RenderHtml},
 };
 #[cfg(feature = "hydrate")]
@@ -29,20 +32,105 @@ where
     owner.forget();
 }
 
+#[cfg(feature = "hydrate")]
+/// Hydrates the app described by the provided function, starting at `<body>`, skipping over
+/// nodes that were added to the page after it was rendered on the server.
+///
+/// This tolerates elements inserted by browser extensions; see [`LenientHydration`].
+pub fn hydrate_body_lenient<F, N>(f: F)
+where
+    F: FnOnce() -> N + 'static,
+    N: IntoView,
+{
+    let owner = hydrate_from_lenient(body(), LenientHydration::default(), f);
+    owner.forget();
+}
+
+#[cfg(feature = "hydrate")]
+/// Hydrates the app described by the provided function, starting at `<body>`, and then replays
+/// the events that happened before it was hydrated.
+///
+/// This is meant to be used with the `replay_events` prop of
+/// [`HydrationScripts`](crate::hydration::HydrationScripts), which waits for the first
+/// interaction with the page before loading the WASM.
+pub fn hydrate_body_with_replay<F, N>(f: F)
+where
+    F: FnOnce() -> N + 'static,
+    N: IntoView,
+{
+    let body = body();
+    let owner = hydrate_from(body.clone(), f);
+    EventQueue::take_recorded().replay(&body);
+    owner.forget();
+}
+
 #[cfg(debug_assertions)]
 thread_local! {
     static FIRST_CALL: Cell<bool> = const { Cell::new(true) };
 }
 
 #[cfg(feature = "hydrate")]
-/// Runs the provided closure and mounts the result to the provided element.
+/// Runs the provided closure and hydrates the result, starting at the provided element.
+///
+/// The element does not need to be `<body>`: if only part of the page was rendered by Leptos,
+/// for example because the rest of the document comes from another templating system, pass the
+/// element that contains the server-rendered view, and its children are hydrated as if they were
+/// the whole app. Event listeners are delegated to the window, so they work no matter where the
+/// element is in the page. In islands mode, set the `hydrate_root` prop of
+/// [`HydrationScripts`](crate::hydration::HydrationScripts) so that only the islands inside it
+/// are hydrated.
 pub fn hydrate_from<F, N>(parent: HtmlElement, f: F) -> UnmountHandle<N::State>
+where
+    F: FnOnce() -> N + 'static,
+    N: IntoView,
+{
+    hydrate_with_cursor(Cursor::new(parent.unchecked_into()), f)
+}
+
+#[cfg(feature = "hydrate")]
+/// Runs the provided closure and mounts the result to the provided element, skipping over nodes
+/// that were added to the page after it was rendered on the server.
+pub fn hydrate_from_lenient<F, N>(
+    parent: HtmlElement,
+    lenient: LenientHydration,
+    f: F,
+) -> UnmountHandle<N::State>
+where
+    F: FnOnce() -> N + 'static,
+    N: IntoView,
+{
+    hydrate_with_cursor(
+        Cursor::new(parent.unchecked_into()).with_lenient(lenient),
+        f,
+    )
+}
+
+#[cfg(feature = "hydrate")]
+/// Runs the provided closure and hydrates the result, starting at the provided element, and then
+/// dispatches each of the events in the queue to the now-hydrated view.
+///
+/// See [`EventQueue::replay`] for how the events are replayed.
+pub fn hydrate_with_replay<F, N>(
+    parent: HtmlElement,
+    queue: EventQueue,
+    f: F,
+) -> UnmountHandle<N::State>
+where
+    F: FnOnce() -> N + 'static,
+    N: IntoView,
+{
+    let handle = hydrate_from(parent.clone(), f);
+    queue.replay(&parent);
+    handle
+}
+
+#[cfg(feature = "hydrate")]
+fn hydrate_with_cursor<F, N>(cursor: Cursor, f: F) -> UnmountHandle<N::State>
 where
     F: FnOnce() -> N + 'static,
     N: IntoView,
 {
     use hydration_context::HydrateSharedContext;
-    use std::sync::Arc;
 
     // use wasm-bindgen-futures to drive the reactive system
     // we ignore the return value because an Err here just means the wasm-bindgen executor is
@@ -68,10 +156,7 @@ where
     let owner = Owner::new_root(Some(Arc::new(HydrateSharedContext::new())));
     let mountable = owner.with(move || {
         let view = f().into_view();
-        view.hydrate::<true>(
-            &Cursor::new(parent.unchecked_into()),
-            &PositionState::default(),
-        )
+        view.hydrate::<true>(&cursor, &PositionState::default())
     });
 
     if let Some(sc) = Owner::current_shared_context() {
@@ -95,6 +180,35 @@ where
 
 /// Runs the provided closure and mounts the result to the provided element.
 pub fn mount_to<F, N>(parent: HtmlElement, f: F) -> UnmountHandle<N::State>
+where
+    F: FnOnce() -> N + 'static,
+    N: IntoView,
+{
+    mount_to_inner(parent, None, f)
+}
+
+/// Runs the provided closure and mounts the result to the provided element, spawning the view's
+/// tasks (like effects and suspended views) with `spawner` rather than the global
+/// [`Executor`].
+///
+/// This is useful to embed a view in an application that has its own scheduler.
+pub fn mount_to_with_spawner<F, N>(
+    parent: HtmlElement,
+    spawner: Arc<dyn CustomSpawner>,
+    f: F,
+) -> UnmountHandle<N::State>
+where
+    F: FnOnce() -> N + 'static,
+    N: IntoView,
+{
+    mount_to_inner(parent, Some(spawner), f)
+}
+
+fn mount_to_inner<F, N>(
+    parent: HtmlElement,
+    spawner: Option<Arc<dyn CustomSpawner>>,
+    f: F,
+) -> UnmountHandle<N::State>
 where
     F: FnOnce() -> N + 'static,
     N: IntoView,
@@ -122,6 +236,9 @@ where
 
     // create a new reactive owner and use it as the root node to run the app
     let owner = Owner::new();
+    if let Some(spawner) = spawner {
+        owner.set_spawner(spawner);
+    }
     let mountable = owner.with(move || {
         let view = f().into_view();
         let mut mountable = view.build();
@@ -166,7 +283,6 @@ where
 #[cfg(feature = "hydrate")]
 pub fn hydrate_islands() {
     use hydration_context::{HydrateSharedContext, SharedContext};
-    use std::sync::Arc;
 
     // use wasm-bindgen-futures to drive the reactive system
     // we ignore the return value because an Err here just means the wasm-bindgen executor is
@@ -187,6 +303,11 @@ pub fn hydrate_islands() {
 /// On drop, this will clean up the reactive [`Owner`] and unmount the view created by
 /// [`mount_to`].
 ///
+/// Each handle owns an independent root, so several views can be mounted into different parts of
+/// the same page (for example, to embed them in a page that isn't built with Leptos), and each can
+/// be unmounted without affecting the others. Delegated event listeners are shared by every root
+/// on the page, and keep working for the remaining roots when one is unmounted.
+///
 /// If you are using it to create the root of an application, you should use
 /// [`UnmountHandle::forget`] to leak it.
 #[must_use = "Dropping an `UnmountHandle` will unmount the view and cancel the \
@@ -197,7 +318,6 @@ pub struct UnmountHandle<M>
 where
     M: Mountable,
 {
-    #[allow(dead_code)]
     owner: Owner,
     mountable: M,
 }
@@ -212,6 +332,17 @@ where
     pub fn forget(self) {
         std::mem::forget(self);
     }
+
+    /// Unmounts the view and cleans up the reactive system. This is the same as dropping the
+    /// handle.
+    pub fn unmount(self) {
+        drop(self);
+    }
+
+    /// The reactive owner of the view, which is cleaned up when the view is unmounted.
+    pub fn owner(&self) -> &Owner {
+        &self.owner
+    }
 }
 
 impl<M> Drop for UnmountHandle<M>
@@ -220,5 +351,8 @@ where
 {
     fn drop(&mut self) {
         self.mountable.unmount();
+        // the owner may have been cloned (for example, into a context), so it is cleaned up here
+        // rather than when its last reference is dropped
+        self.owner.cleanup();
     }
 }
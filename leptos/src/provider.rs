@@ -1,10 +1,10 @@
 use crate::{children::TypedChildren, component, IntoView};
-use reactive_graph::owner::{provide_context, Owner};
-use tachys::reactive_graph::OwnedView;
+use tachys::reactive_graph::provide_context_view;
 
 #[component]
-/// Uses the context API to [`provide_context`] to its children and descendants,
-/// without overwriting any contexts of the same type in its own reactive scope.
+/// Uses the context API to [`provide_context`](crate::context::provide_context) to its
+/// children and descendants, without overwriting any contexts of the same type in its own
+/// reactive scope.
 ///
 /// This prevents issues related to “context shadowing.”
 ///
@@ -37,13 +37,5 @@ where
     T: Send + Sync + 'static,
     Chil: IntoView + 'static,
 {
-    let owner = Owner::current()
-        .expect("no current reactive Owner found")
-        .child();
-    let children = children.into_inner();
-    let children = owner.with(|| {
-        provide_context(value);
-        children()
-    });
-    OwnedView::new_with_owner(children, owner)
+    provide_context_view(value, children.into_inner())
 }
@@ -1,4 +1,5 @@
 use crate::{
+    callback::{Callable, Callback},
     children::{TypedChildren, ViewFnOnce},
     IntoView,
 };
@@ -10,10 +11,11 @@ use reactive_graph::{
         suspense::{LocalResourceNotifier, SuspenseContext},
         ArcMemo, ScopedFuture,
     },
-    effect::RenderEffect,
+    effect::{Effect, RenderEffect},
     owner::{provide_context, use_context, Owner},
     signal::ArcRwSignal,
-    traits::{Dispose, Get, Read, Track, With},
+    traits::{Dispose, Get, Read, Set, Track, With},
+    wrappers::{read::Signal, write::SignalSetter},
 };
 use slotmap::{DefaultKey, SlotMap};
 use tachys::{
@@ -21,7 +23,7 @@ use tachys::{
     html::attribute::Attribute,
     hydration::Cursor,
     reactive_graph::{OwnedView, OwnedViewState},
-    ssr::StreamBuilder,
+    ssr::{BranchMarks, StreamBuilder},
     view::{
         add_attr::AddAnyAttr,
         either::{EitherKeepAlive, EitherKeepAliveState},
@@ -43,6 +45,14 @@ use throw_error::ErrorHookFuture;
 /// `Some` value in `children`. However, you can read resources asynchronously by using
 /// [Suspend](crate::prelude::Suspend).
 ///
+/// Whether the boundary is waiting for anything is available to its `children` as
+/// [`SuspensePending`] context, and can be passed out with `set_pending`.
+///
+/// A [`LocalResource`](crate::prelude::LocalResource) never loads on the server. If one is read
+/// in the `children`, the server renders the `fallback` and marks this boundary as incomplete.
+/// The client then hydrates the `fallback` and renders the `children` once the resource has
+/// loaded in the browser.
+///
 /// ```
 /// # use leptos::prelude::*;
 /// # if false { // don't run in doctests
@@ -87,7 +97,16 @@ use throw_error::ErrorHookFuture;
 /// ```
 #[component]
 pub fn Suspense<Chil>(
-    #[prop(optional, into)] fallback: ViewFnOnce,
+    /// Will be displayed while resources are pending. By default this is the empty view.
+    #[prop(optional, into)]
+    fallback: ViewFnOnce,
+    /// Called with `true` when the boundary starts waiting for a resource, and with `false`
+    /// once everything it waits for has loaded.
+    #[prop(optional, into)]
+    set_pending: Option<SignalSetter<bool>>,
+    /// Called each time everything the boundary was waiting for has loaded.
+    #[prop(optional, into)]
+    on_resolved: Option<Callback<()>>,
     children: TypedChildren<Chil>,
 ) -> impl IntoView
 where
@@ -104,7 +123,6 @@ where
                 .unwrap_or_else(|| (false, Default::default()))
         };
         let fallback = fallback.run();
-        let children = children.into_inner()();
         let tasks = ArcRwSignal::new(SlotMap::<DefaultKey, ()>::new());
         provide_context(SuspenseContext {
             tasks: tasks.clone(),
@@ -117,6 +135,8 @@ where
                 tasks.with(SlotMap::is_empty)
             }
         });
+        provide_pending(&none_pending, set_pending, on_resolved);
+        let children = children.into_inner()();
 
         OwnedView::new(SuspenseBoundary::<false, _, _> {
             id,
@@ -127,6 +147,58 @@ where
     })
 }
 
+/// Whether the nearest [`Suspense`] or [`Transition`](crate::Transition) is waiting for any of
+/// the resources read inside it.
+///
+/// This is provided as context to the children of every boundary, so that other parts of the
+/// boundary can react to it, for example by disabling a button while data are reloading.
+///
+/// On the client, a boundary that the server could not finish rendering (because it waits for a
+/// [`LocalResource`](crate::prelude::LocalResource)) starts out pending, so that hydration
+/// matches the fallback the server sent.
+///
+/// ```
+/// # use leptos::prelude::*;
+/// #[component]
+/// fn SaveButton() -> impl IntoView {
+///     let SuspensePending(pending) = expect_context::<SuspensePending>();
+///     view! { <button disabled=pending>"Save"</button> }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct SuspensePending(pub Signal<bool>);
+
+/// Provides [`SuspensePending`] for a boundary, and calls the boundary's callbacks whenever it
+/// changes.
+pub(crate) fn provide_pending(
+    none_pending: &ArcMemo<bool>,
+    set_pending: Option<SignalSetter<bool>>,
+    on_resolved: Option<Callback<()>>,
+) {
+    provide_context(SuspensePending(Signal::derive({
+        let none_pending = none_pending.clone();
+        move || !none_pending.get()
+    })));
+
+    if set_pending.is_some() || on_resolved.is_some() {
+        Effect::new_isomorphic({
+            let none_pending = none_pending.clone();
+            move |was_pending: Option<bool>| {
+                let pending = !none_pending.get();
+                if let Some(set_pending) = set_pending {
+                    set_pending.set(pending);
+                }
+                if let Some(on_resolved) = on_resolved {
+                    if was_pending == Some(true) && !pending {
+                        on_resolved.run(());
+                    }
+                }
+                pending
+            }
+        });
+    }
+}
+
 pub(crate) struct SuspenseBoundary<const TRANSITION: bool, Fal, Chil> {
     pub id: SerializedDataId,
     pub none_pending: ArcMemo<bool>,
@@ -140,9 +212,7 @@ where
     Fal: Render + Send + 'static,
     Chil: Render + Send + 'static,
 {
-    type State = RenderEffect<
-        OwnedViewState<EitherKeepAliveState<Chil::State, Fal::State>>,
-    >;
+    type State = RenderEffect<OwnedViewState<EitherKeepAliveState<Chil, Fal>>>;
 
     fn build(self) -> Self::State {
         let mut children = Some(self.children);
@@ -243,7 +313,7 @@ where
         buf: &mut String,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) {
         self.fallback
             .to_html_with_buf(buf, position, escape, mark_branches);
@@ -254,7 +324,7 @@ where
         buf: &mut StreamBuilder,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) where
         Self: Sized,
     {
@@ -376,6 +446,7 @@ where
                     );
                     buf.push_async_out_of_order(fut, position, mark_branches);
                 } else {
+                    let mark_branches = mark_branches.clone();
                     buf.push_async({
                         let mut position = *position;
                         async move {
@@ -383,12 +454,15 @@ where
                                 None => Either::Left(self.fallback),
                                 Some(value) => Either::Right(value),
                             };
-                            let mut builder = StreamBuilder::new(id);
+                            let mut builder = StreamBuilder::with_capacity(
+                                value.html_len(),
+                                id,
+                            );
                             value.to_html_async_with_buf::<OUT_OF_ORDER>(
                                 &mut builder,
                                 &mut position,
                                 escape,
-                                mark_branches,
+                                &mark_branches,
                             );
                             builder.finish().take_chunks()
                         }
@@ -504,7 +578,7 @@ where
         buf: &mut String,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) {
         (self.0)().to_html_with_buf(buf, position, escape, mark_branches);
     }
@@ -514,7 +588,7 @@ where
         buf: &mut StreamBuilder,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) where
         Self: Sized,
     {
@@ -534,3 +608,86 @@ where
         (self.0)().hydrate::<FROM_SERVER>(cursor, position)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use any_spawner::Executor;
+
+    #[test]
+    fn suspense_pending_follows_resource_refetch() {
+        use futures::channel::oneshot;
+        use std::{
+            collections::VecDeque,
+            sync::{Arc, Mutex},
+        };
+
+        #[component]
+        fn Reader(resource: Resource<i32>) -> impl IntoView {
+            // reading the resource registers it with the boundary
+            _ = resource.get_untracked();
+        }
+
+        #[component]
+        fn PendingLog(log: Arc<Mutex<Vec<bool>>>) -> impl IntoView {
+            let SuspensePending(pending) = expect_context::<SuspensePending>();
+            std::mem::forget(RenderEffect::new_isomorphic(move |_| {
+                log.lock().unwrap().push(pending.get())
+            }));
+        }
+
+        _ = Executor::init_manual();
+        let owner = Owner::new();
+        owner.set();
+
+        let senders = Arc::new(Mutex::new(VecDeque::new()));
+        let resource = Resource::new(|| (), {
+            let senders = Arc::clone(&senders);
+            move |_| {
+                let (tx, rx) = oneshot::channel();
+                senders.lock().unwrap().push_back(tx);
+                async move { rx.await.unwrap() }
+            }
+        });
+        let resolve = |value: i32| {
+            let tx = senders.lock().unwrap().pop_front().unwrap();
+            tx.send(value).unwrap();
+            Executor::flush();
+        };
+
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let is_pending = RwSignal::new(false);
+        let resolved = Arc::new(Mutex::new(0));
+        let pending_log = Arc::clone(&log);
+        let _view = view! {
+            <Suspense
+                set_pending=is_pending
+                on_resolved={
+                    let resolved = Arc::clone(&resolved);
+                    move || *resolved.lock().unwrap() += 1
+                }
+            >
+                <Reader resource/>
+                <PendingLog log=pending_log/>
+            </Suspense>
+        };
+        Executor::flush();
+        assert_eq!(*log.lock().unwrap(), [true]);
+        assert!(is_pending.get_untracked());
+
+        resolve(1);
+        assert_eq!(*log.lock().unwrap(), [true, false]);
+        assert!(!is_pending.get_untracked());
+        assert_eq!(*resolved.lock().unwrap(), 1);
+
+        resource.refetch();
+        Executor::flush();
+        assert_eq!(*log.lock().unwrap(), [true, false, true]);
+        assert!(is_pending.get_untracked());
+
+        resolve(2);
+        assert_eq!(*log.lock().unwrap(), [true, false, true, false]);
+        assert!(!is_pending.get_untracked());
+        assert_eq!(*resolved.lock().unwrap(), 2);
+    }
+}
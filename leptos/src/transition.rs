@@ -1,15 +1,19 @@
 use crate::{
+    callback::Callback,
     children::{TypedChildren, ViewFnOnce},
-    suspense_component::SuspenseBoundary,
+    suspense_component::{provide_pending, SuspenseBoundary},
     IntoView,
 };
 use leptos_macro::component;
 use reactive_graph::{
-    computed::{suspense::SuspenseContext, ArcMemo},
+    computed::{
+        suspense::{SuspenseContext, TaskHandle},
+        ArcMemo,
+    },
     effect::Effect,
-    owner::{provide_context, Owner},
+    owner::{provide_context, use_context, Owner},
     signal::ArcRwSignal,
-    traits::{Get, Set, Track, With},
+    traits::{Get, Track, With},
     wrappers::write::SignalSetter,
 };
 use slotmap::{DefaultKey, SlotMap};
@@ -22,6 +26,10 @@ use tachys::reactive_graph::OwnedView;
 /// Unlike [`Suspense`](crate::Suspense), this will not fall
 /// back to the `fallback` state if there are further changes after the initial load.
 ///
+/// Each resource only counts toward the innermost boundary it is read in, so when a nested
+/// `Transition` reloads, the boundaries around it keep showing their children and are not
+/// pending. Set `propagate_pending` to make the enclosing boundary wait along with this one.
+///
 /// Note that the `children` will be rendered initially (in order to capture the fact that
 /// those resources are read under the suspense), so you cannot assume that resources read
 /// synchronously have
@@ -80,46 +88,151 @@ pub fn Transition<Chil>(
     /// or not pending (`false`).
     #[prop(optional, into)]
     set_pending: Option<SignalSetter<bool>>,
+    /// Called each time everything the boundary was waiting for has loaded.
+    #[prop(optional, into)]
+    on_resolved: Option<Callback<()>>,
+    /// Whether the nearest `<Suspense/>` or `<Transition/>` around this one should also count as
+    /// pending while this one is. By default, a nested boundary handles its own resources, so
+    /// an enclosing boundary keeps showing its children while this one reloads.
+    #[prop(optional)]
+    propagate_pending: bool,
     children: TypedChildren<Chil>,
 ) -> impl IntoView
 where
     Chil: IntoView + Send + 'static,
 {
-    let (starts_local, id) = {
-        Owner::current_shared_context()
-            .map(|sc| {
-                let id = sc.next_id();
-                (sc.get_incomplete_chunk(&id), id)
-            })
-            .unwrap_or_else(|| (false, Default::default()))
-    };
-    let fallback = fallback.run();
-    let children = children.into_inner()();
-    let tasks = ArcRwSignal::new(SlotMap::<DefaultKey, ()>::new());
-    provide_context(SuspenseContext {
-        tasks: tasks.clone(),
-    });
-    let none_pending = ArcMemo::new(move |prev: Option<&bool>| {
-        tasks.track();
-        if prev.is_none() && starts_local {
-            false
-        } else {
-            tasks.with(SlotMap::is_empty)
-        }
-    });
-    if let Some(set_pending) = set_pending {
-        Effect::new_isomorphic({
-            let none_pending = none_pending.clone();
-            move |_| {
-                set_pending.set(!none_pending.get());
+    let parent = use_context::<SuspenseContext>();
+    let owner = Owner::new();
+    owner.with(|| {
+        let (starts_local, id) = {
+            Owner::current_shared_context()
+                .map(|sc| {
+                    let id = sc.next_id();
+                    (sc.get_incomplete_chunk(&id), id)
+                })
+                .unwrap_or_else(|| (false, Default::default()))
+        };
+        let fallback = fallback.run();
+        let tasks = ArcRwSignal::new(SlotMap::<DefaultKey, ()>::new());
+        provide_context(SuspenseContext {
+            tasks: tasks.clone(),
+        });
+        let none_pending = ArcMemo::new(move |prev: Option<&bool>| {
+            tasks.track();
+            if prev.is_none() && starts_local {
+                false
+            } else {
+                tasks.with(SlotMap::is_empty)
             }
         });
-    }
+        provide_pending(&none_pending, set_pending, on_resolved);
+        if let Some(parent) = parent.filter(|_| propagate_pending) {
+            // holds a task in the parent for as long as this is pending
+            Effect::new_isomorphic({
+                let none_pending = none_pending.clone();
+                move |handle: Option<Option<TaskHandle>>| {
+                    if none_pending.get() {
+                        None
+                    } else {
+                        handle.flatten().or_else(|| Some(parent.task_id()))
+                    }
+                }
+            });
+        }
+        let children = children.into_inner()();
 
-    OwnedView::new(SuspenseBoundary::<true, _, _> {
-        id,
-        none_pending,
-        fallback,
-        children,
+        OwnedView::new(SuspenseBoundary::<true, _, _> {
+            id,
+            none_pending,
+            fallback,
+            children,
+        })
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use any_spawner::Executor;
+
+    #[test]
+    fn nested_transitions_are_pending_independently() {
+        use futures::channel::oneshot;
+        use std::{
+            collections::VecDeque,
+            sync::{Arc, Mutex},
+        };
+
+        type Senders = Arc<Mutex<VecDeque<oneshot::Sender<i32>>>>;
+
+        fn controlled() -> (Resource<i32>, Senders) {
+            let senders = Senders::default();
+            let resource = Resource::new(|| (), {
+                let senders = Arc::clone(&senders);
+                move |_| {
+                    let (tx, rx) = oneshot::channel();
+                    senders.lock().unwrap().push_back(tx);
+                    async move { rx.await.unwrap() }
+                }
+            });
+            (resource, senders)
+        }
+
+        fn resolve(senders: &Senders, value: i32) {
+            let tx = senders.lock().unwrap().pop_front().unwrap();
+            tx.send(value).unwrap();
+            Executor::flush();
+        }
+
+        #[component]
+        fn Reader(
+            resource: Resource<i32>,
+            runs: RwSignal<usize>,
+        ) -> impl IntoView {
+            *runs.write() += 1;
+            _ = resource.get_untracked();
+        }
+
+        for propagate_pending in [false, true] {
+            _ = Executor::init_manual();
+            let owner = Owner::new();
+            owner.set();
+
+            let (outer, outer_senders) = controlled();
+            let (inner, inner_senders) = controlled();
+            let outer_pending = RwSignal::new(false);
+            let inner_pending = RwSignal::new(false);
+            let outer_runs = RwSignal::new(0);
+            let inner_runs = RwSignal::new(0);
+            let _view = view! {
+                <Transition set_pending=outer_pending>
+                    <Transition set_pending=inner_pending propagate_pending>
+                        <Reader resource=inner runs=inner_runs/>
+                    </Transition>
+                    // read after the inner boundary has been created
+                    <Reader resource=outer runs=outer_runs/>
+                </Transition>
+            };
+            Executor::flush();
+            assert!(outer_pending.get_untracked());
+            assert!(inner_pending.get_untracked());
+
+            resolve(&inner_senders, 1);
+            assert!(!inner_pending.get_untracked());
+            assert!(outer_pending.get_untracked());
+            resolve(&outer_senders, 1);
+            assert!(!outer_pending.get_untracked());
+
+            inner.refetch();
+            Executor::flush();
+            assert!(inner_pending.get_untracked());
+            assert_eq!(outer_pending.get_untracked(), propagate_pending);
+
+            resolve(&inner_senders, 2);
+            assert!(!inner_pending.get_untracked());
+            assert!(!outer_pending.get_untracked());
+            assert_eq!(outer_runs.get_untracked(), 1);
+            assert_eq!(inner_runs.get_untracked(), 1);
+        }
+    }
+}
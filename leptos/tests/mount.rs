@@ -0,0 +1,81 @@
+//! Browser tests for mounting several independent roots on the same page.
+//!
+//! ```sh
+//! wasm-pack test --headless --firefox leptos --features csr
+//! ```
+#![cfg(all(target_arch = "wasm32", feature = "csr"))]
+
+use leptos::{mount::mount_to, prelude::*, task::tick};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn section() -> web_sys::HtmlElement {
+    let section = document().create_element("section").unwrap();
+    document().body().unwrap().append_child(&section).unwrap();
+    section.unchecked_into()
+}
+
+fn counter(
+    count: RwSignal<i32>,
+    runs: Arc<AtomicUsize>,
+) -> impl IntoView + 'static {
+    Effect::new(move |_| {
+        count.track();
+        runs.fetch_add(1, Ordering::Relaxed);
+    });
+    view! {
+        <button on:click=move |_| *count.write() += 1>{count}</button>
+    }
+}
+
+#[wasm_bindgen_test]
+async fn unmounting_one_root_leaves_the_other_working() {
+    let (first_el, second_el) = (section(), section());
+    let (first_count, second_count) = (RwSignal::new(0), RwSignal::new(0));
+    let (first_runs, second_runs) =
+        (Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0)));
+
+    let first = mount_to(first_el.clone(), {
+        let runs = Arc::clone(&first_runs);
+        move || counter(first_count, runs)
+    });
+    let _second = mount_to(second_el.clone(), {
+        let runs = Arc::clone(&second_runs);
+        move || counter(second_count, runs)
+    });
+    tick().await;
+
+    first_count.set(1);
+    second_count.set(1);
+    tick().await;
+    assert_eq!(first_el.inner_html(), "<button>1</button>");
+    assert_eq!(second_el.inner_html(), "<button>1</button>");
+    let first_runs_before = first_runs.load(Ordering::Relaxed);
+
+    first.unmount();
+    assert_eq!(first_el.inner_html(), "");
+
+    // the first root's effects are disposed
+    first_count.try_set(2);
+    tick().await;
+    assert_eq!(first_runs.load(Ordering::Relaxed), first_runs_before);
+
+    // and the second root still updates, and still handles delegated events
+    second_count.set(2);
+    tick().await;
+    assert_eq!(second_el.inner_html(), "<button>2</button>");
+    second_el
+        .first_element_child()
+        .unwrap()
+        .unchecked_into::<web_sys::HtmlElement>()
+        .click();
+    tick().await;
+    assert_eq!(second_el.inner_html(), "<button>3</button>");
+    assert!(second_runs.load(Ordering::Relaxed) >= 3);
+}
@@ -135,3 +135,1869 @@ fn ssr_option() {
 
     assert_eq!(rendered.to_html(), "<option></option>");
 }
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_optional_signal_attribute() {
+    use leptos::prelude::*;
+
+    let id = RwSignal::new(None::<String>);
+    let disabled = RwSignal::new(Some(true));
+    let view = move || {
+        let id: Signal<Option<String>> = id.into();
+        let disabled: Memo<Option<bool>> = Memo::new(move |_| disabled.get());
+        view! { <button data-id=id disabled=disabled></button> }
+    };
+
+    assert_eq!(view().to_html(), "<button disabled></button>");
+
+    id.set(Some("x".to_string()));
+    disabled.set(None);
+    assert_eq!(view().to_html(), "<button data-id=\"x\"></button>");
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_sanitized_inner_html() {
+    use leptos::{
+        html::div, prelude::*, tachys::reactive_graph::inner_html_sanitized,
+    };
+
+    let html = RwSignal::new("<script>alert(1)</script><b>hi</b>".to_string());
+    let rendered = div().add_any_attr(inner_html_sanitized(
+        move || html.get(),
+        |value: &str| value.replace("<script>", "").replace("</script>", ""),
+    ));
+
+    assert_eq!(rendered.to_html(), "<div>alert(1)<b>hi</b></div>");
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_batched_attributes() {
+    use leptos::{
+        html::div,
+        prelude::*,
+        tachys::{
+            html::attribute::{id, title},
+            reactive_graph::batch_attributes,
+        },
+    };
+
+    let count = RwSignal::new(1);
+    let rendered = div().add_any_attr(batch_attributes(move || {
+        (
+            id(format!("row-{}", count.get())),
+            title(count.get().to_string()),
+        )
+    }));
+
+    assert_eq!(rendered.to_html(), "<div id=\"row-1\" title=\"1\"></div>");
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_read_guard_attribute() {
+    use leptos::{html::div, prelude::*, tachys::html::attribute::title};
+    use send_wrapper::SendWrapper;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    #[derive(Debug)]
+    struct Counted(String, Arc<AtomicUsize>);
+
+    impl Clone for Counted {
+        fn clone(&self) -> Self {
+            self.1.fetch_add(1, Ordering::Relaxed);
+            Self(self.0.clone(), Arc::clone(&self.1))
+        }
+    }
+
+    impl AsRef<str> for Counted {
+        fn as_ref(&self) -> &str {
+            &self.0
+        }
+    }
+
+    let clones = Arc::new(AtomicUsize::new(0));
+    let text = RwSignal::new(Counted("first".to_string(), Arc::clone(&clones)));
+    let render = move || {
+        div()
+            .add_any_attr(title(move || SendWrapper::new(text.read())))
+            .to_html()
+    };
+
+    assert_eq!(render(), "<div title=\"first\"></div>");
+    text.update(|text| text.0 = "second".to_string());
+    assert_eq!(render(), "<div title=\"second\"></div>");
+    assert_eq!(clones.load(Ordering::Relaxed), 0);
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_owned_view_reattached_under_new_owner() {
+    use leptos::{
+        prelude::*, reactive::owner::Owner, tachys::reactive_graph::OwnedView,
+    };
+
+    // the tab's owner is not a child of any parent, so it can outlive them
+    let tab_owner = Owner::new();
+    let count = tab_owner.with(|| RwSignal::new(0));
+
+    let first_parent = Owner::new();
+    let tab = first_parent.with(|| {
+        OwnedView::new_with_owner(move || count.get().to_string(), tab_owner)
+    });
+    assert_eq!(tab.clone().to_html(), "0");
+
+    // detach the subtree, then dispose of its first parent
+    let (owner, view) = tab.into_inner();
+    first_parent.cleanup();
+    drop(first_parent);
+
+    // reattach it under a different parent
+    let second_parent = Owner::new();
+    let tab = second_parent.with(|| OwnedView::new_with_owner(view, owner));
+
+    count.set(1);
+    assert_eq!(tab.to_html(), "1");
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_memoized() {
+    use leptos::{prelude::*, tachys::reactive_graph::memoized};
+
+    let count = RwSignal::new(0);
+    let parity = move || {
+        memoized(move || if count.get() % 2 == 0 { "even" } else { "odd" })
+    };
+
+    assert_eq!(view! { <p>{parity()}</p> }.to_html(), "<p>even</p>");
+    count.set(3);
+    assert_eq!(view! { <p>{parity()}</p> }.to_html(), "<p>odd</p>");
+}
+
+#[cfg(all(feature = "ssr", feature = "reactive_stores"))]
+#[test]
+fn ssr_store_fields() {
+    use leptos::prelude::*;
+    use reactive_stores::{Field, Store};
+    use reactive_stores_macro::Store;
+
+    #[derive(Store)]
+    struct Profile {
+        name: String,
+        bio: String,
+    }
+
+    let store = Store::new(Profile {
+        name: "Alice".to_string(),
+        bio: "Hello".to_string(),
+    });
+    let bio: Field<String> = store.bio().into();
+    let render = move || {
+        view! { <p title=store.name()>{store.name()}": "{bio}</p> }.to_html()
+    };
+
+    assert_eq!(render(), "<p title=\"Alice\">Alice<!>: <!>Hello</p>");
+    store.name().set("Bob".to_string());
+    assert_eq!(render(), "<p title=\"Bob\">Bob<!>: <!>Hello</p>");
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_reactive_keyed() {
+    use leptos::{
+        html::{li, ul},
+        prelude::*,
+        tachys::reactive_graph::keyed,
+    };
+
+    let rows = RwSignal::new(vec![(1, "a"), (2, "b"), (3, "c")]);
+    let render = move || {
+        ul().child(keyed(
+            move || rows.get(),
+            |row| row.0,
+            |row| li().child(row.1),
+        ))
+        .to_html()
+    };
+
+    assert_eq!(render(), "<ul><li>a</li><li>b</li><li>c</li><!></ul>");
+    rows.update(|rows| rows.reverse());
+    assert_eq!(render(), "<ul><li>c</li><li>b</li><li>a</li><!></ul>");
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_optional_dynamic_class() {
+    use leptos::{html::div, prelude::*, tachys::html::class::class};
+
+    let status = RwSignal::new(Some("warn bold"));
+    let render = move || {
+        div()
+            .add_any_attr(class("card"))
+            .add_any_attr(class(move || status.get()))
+            .to_html()
+    };
+
+    assert_eq!(render(), "<div class=\"card warn bold\"></div>");
+    status.set(None);
+    assert_eq!(render(), "<div class=\"card\"></div>");
+    status.set(Some(""));
+    assert_eq!(render(), "<div class=\"card\"></div>");
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_optional_style_property() {
+    use leptos::{prelude::*, tachys::html::style::Px};
+
+    let width = RwSignal::new(Some(Px(100)));
+    let render = move || {
+        view! {
+            <div
+                style:width=move || width.get()
+                style:opacity=move || 0.5
+                style:z-index=move || 5
+            ></div>
+        }
+        .to_html()
+    };
+
+    assert_eq!(
+        render(),
+        "<div style=\"width:100px;opacity:0.5;z-index:5;\"></div>"
+    );
+    width.set(None);
+    assert_eq!(render(), "<div style=\"opacity:0.5;z-index:5;\"></div>");
+    width.set(Some(Px(50)));
+    assert_eq!(
+        render(),
+        "<div style=\"width:50px;opacity:0.5;z-index:5;\"></div>"
+    );
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_reactive_boolean_and_numeric_attributes() {
+    use leptos::prelude::*;
+
+    let disabled = RwSignal::new(true);
+    let tabindex = RwSignal::new(-1);
+    let render = move || {
+        view! {
+            <button
+                disabled=move || disabled.get()
+                tabindex=move || tabindex.get()
+            ></button>
+        }
+        .to_html()
+    };
+
+    assert_eq!(render(), "<button disabled tabindex=\"-1\"></button>");
+    disabled.set(false);
+    tabindex.set(3);
+    assert_eq!(render(), "<button tabindex=\"3\"></button>");
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_spread_class_and_style_merge() {
+    use leptos::{
+        prelude::*,
+        tachys::html::{class::class, style::style},
+    };
+
+    let active = RwSignal::new(true);
+    let render = move || {
+        view! {
+            <div class="a" class:c=move || active.get() style="color: red"></div>
+        }
+        .add_any_attr(class("b"))
+        .add_any_attr(style("margin: 0"))
+        .to_html()
+    };
+
+    assert_eq!(
+        render(),
+        "<div class=\"a c b\" style=\"color: red;margin: 0;\"></div>"
+    );
+    active.set(false);
+    assert_eq!(
+        render(),
+        "<div class=\"a  b\" style=\"color: red;margin: 0;\"></div>"
+    );
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_spread_runtime_attributes() {
+    use leptos::{
+        ev,
+        html::button,
+        prelude::*,
+        tachys::html::{
+            attribute::{any_attribute::AnyAttribute, title},
+            class::class,
+            event::on,
+        },
+    };
+
+    let dirty = RwSignal::new(false);
+    let render = move |names: &[&str]| {
+        let attrs = names
+            .iter()
+            .map(|name| match *name {
+                "title" => title("Save").into_any_attr(),
+                "class" => {
+                    class(move || if dirty.get() { "dirty" } else { "clean" })
+                        .into_any_attr()
+                }
+                _ => on(ev::click, |_| {}).into_any_attr(),
+            })
+            .collect::<Vec<AnyAttribute>>();
+        button().add_any_attr(attrs).to_html()
+    };
+
+    assert_eq!(
+        render(&["title", "class", "click"]),
+        "<button title=\"Save\" class=\"clean\"></button>"
+    );
+    dirty.set(true);
+    assert_eq!(
+        render(&["click", "class", "title"]),
+        "<button title=\"Save\" class=\"dirty\"></button>"
+    );
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_duplicate_attributes_last_wins() {
+    use leptos::{prelude::*, tachys::html::attribute::title};
+
+    let label = RwSignal::new("from signal");
+    let render = move || {
+        view! { <div title="static" id="a" hidden=true></div> }
+            .add_any_attr(title(move || label.get()))
+            .to_html()
+    };
+
+    assert_eq!(
+        render(),
+        "<div id=\"a\" hidden title=\"from signal\"></div>"
+    );
+    label.set("updated \"quoted\"");
+    assert_eq!(
+        render(),
+        "<div id=\"a\" hidden title=\"updated &quot;quoted&quot;\"></div>"
+    );
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_namespaced_attributes() {
+    use leptos::{
+        prelude::*, svg::svg, tachys::html::attribute::custom::custom_attribute,
+    };
+
+    let icon = RwSignal::new("#star");
+    let render = move || {
+        svg()
+            .add_any_attr(custom_attribute("xml:lang", "en"))
+            .child(
+                leptos::svg::r#use()
+                    .add_any_attr(custom_attribute("xlink:href", move || {
+                        icon.get()
+                    })),
+            )
+            .to_html()
+    };
+
+    assert_eq!(
+        render(),
+        "<svg xml:lang=\"en\"><use xlink:href=\"#star\"></use></svg>"
+    );
+    icon.set("#moon");
+    assert_eq!(
+        render(),
+        "<svg xml:lang=\"en\"><use xlink:href=\"#moon\"></use></svg>"
+    );
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_style_collections() {
+    use leptos::{
+        html::div,
+        prelude::*,
+        tachys::html::style::{style, Px},
+    };
+    use std::collections::HashMap;
+
+    let left = RwSignal::new(Px(10));
+    let top = RwSignal::new(Px(20));
+    let render = move || {
+        div()
+            .add_any_attr(style(vec![("left", left), ("top", top)]))
+            .to_html()
+    };
+    assert_eq!(render(), "<div style=\"left:10px;top:20px;\"></div>");
+    left.set(Px(15));
+    assert_eq!(render(), "<div style=\"left:15px;top:20px;\"></div>");
+
+    let map = HashMap::from([
+        ("z-index", None),
+        ("width", Some("100%")),
+        ("color", Some("red")),
+    ]);
+    assert_eq!(
+        div().add_any_attr(style(map)).to_html(),
+        "<div style=\"color:red;width:100%;\"></div>"
+    );
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_unescaped_attribute() {
+    use leptos::{
+        html::div,
+        prelude::*,
+        tachys::html::attribute::{custom::custom_attribute, UnescapedAttr},
+    };
+
+    let json = r#"{'ids':[1,2,3]}"#;
+    let escaped = div()
+        .add_any_attr(custom_attribute("data-config", r#"{"ids":[1,2,3]}"#))
+        .to_html();
+    let unescaped = div()
+        .add_any_attr(custom_attribute(
+            "data-config",
+            UnescapedAttr::dangerously_unescaped(json),
+        ))
+        .to_html();
+
+    assert_eq!(
+        escaped,
+        "<div data-config=\"{&quot;ids&quot;:[1,2,3]}\"></div>"
+    );
+    assert_eq!(unescaped, "<div data-config=\"{'ids':[1,2,3]}\"></div>");
+    assert!(unescaped.len() < escaped.len());
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_spread_event_handlers() {
+    use leptos::{ev, prelude::*, tachys::html::event::on};
+
+    let rendered =
+        view! { <button on:click:undelegated=|_| {}>"Save"</button> }
+            .add_any_attr(on(ev::click, |_| {}).undelegated())
+            .add_any_attr(on(ev::click, |_| {}))
+            .to_html();
+
+    assert_eq!(rendered, "<button>Save</button>");
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_shared_string_attributes() {
+    use leptos::{
+        html::a, oco::Oco, prelude::*, tachys::html::attribute::href,
+    };
+    use std::{borrow::Cow, sync::Arc};
+
+    let route: Arc<str> = Arc::from("/users/1");
+    let path = RwSignal::new(Arc::clone(&route));
+    let rendered = a().href(move || path.get()).to_html();
+    assert_eq!(rendered, "<a href=\"/users/1\"></a>");
+
+    let cow: Cow<'static, str> = Cow::Owned("/cow?a=1&b=2".to_string());
+    assert_eq!(
+        a().add_any_attr(href(cow)).to_html(),
+        "<a href=\"/cow?a=1&amp;b=2\"></a>"
+    );
+    assert_eq!(
+        a().add_any_attr(href(Oco::from("/oco"))).to_html(),
+        "<a href=\"/oco\"></a>"
+    );
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_spread_onto_closure_child() {
+    use leptos::{
+        html::div,
+        prelude::*,
+        tachys::html::{attribute::title, class::class},
+    };
+
+    let count = RwSignal::new(1);
+    let child = move || div().child(count.get());
+    let html = child
+        .add_any_attr(title("counter"))
+        .add_any_attr(class("count"))
+        .to_html();
+    assert_eq!(html, "<div title=\"counter\" class=\"count\">1</div>");
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_many_siblings() {
+    use leptos::{
+        html::{li, ul},
+        prelude::*,
+        tachys::view::fragment::Fragment,
+    };
+
+    let item = |n: usize| li().child(n);
+    let tuple = (
+        item(0),
+        item(1),
+        item(2),
+        item(3),
+        item(4),
+        item(5),
+        item(6),
+        item(7),
+        item(8),
+        item(9),
+        item(10),
+        item(11),
+        item(12),
+        item(13),
+        item(14),
+        item(15),
+        item(16),
+        item(17),
+        item(18),
+        item(19),
+        item(20),
+        item(21),
+        item(22),
+        item(23),
+        item(24),
+        item(25),
+        item(26),
+        item(27),
+        item(28),
+        item(29),
+        item(30),
+        item(31),
+    );
+    let expected = (0..32).map(|n| format!("<li>{n}</li>")).collect::<String>();
+    assert_eq!(ul().child(tuple).to_html(), format!("<ul>{expected}</ul>"));
+
+    let count = RwSignal::new(0);
+    let fragment = (0..40)
+        .map(|n| {
+            if n % 2 == 0 {
+                item(n).into_any()
+            } else {
+                li().child(move || count.get() + n).into_any()
+            }
+        })
+        .collect::<Fragment>();
+    let expected = (0..40).map(|n| format!("<li>{n}</li>")).collect::<String>();
+    assert_eq!(
+        ul().child(fragment).to_html(),
+        format!("<ul>{expected}<!></ul>")
+    );
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_either_keep_alive_renders_active_branch() {
+    use leptos::{
+        html::{div, p, span},
+        prelude::*,
+        tachys::view::either::EitherKeepAlive,
+    };
+
+    let tabs = |show_b| {
+        div()
+            .child(EitherKeepAlive {
+                a: Some(p().child("tab a")),
+                b: Some(span().child("tab b")),
+                show_b,
+            })
+            .to_html()
+    };
+    assert_eq!(tabs(false), "<div><p>tab a</p></div>");
+    assert_eq!(tabs(true), "<div><span>tab b</span></div>");
+
+    // the inactive branch does not need to be provided up front
+    let html = EitherKeepAlive::<(), _> {
+        a: None,
+        b: Some(span().child("only b")),
+        show_b: true,
+    }
+    .to_html();
+    assert_eq!(html, "<span>only b</span>");
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_static_view() {
+    use leptos::{
+        html::{span, td, tr},
+        prelude::*,
+    };
+
+    let label = String::from("Total");
+    let html = tr()
+        .child((
+            td().child(label.into_static()),
+            td().child(span().child("€").into_static()),
+        ))
+        .to_html();
+    assert_eq!(html, "<tr><td>Total</td><td><span>€</span></td></tr>");
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_custom_view_positions() {
+    use leptos::{
+        attr::Attribute,
+        html::div,
+        prelude::*,
+        tachys::{
+            hydration::Cursor,
+            renderer::{types::Placeholder, Rndr},
+            ssr::BranchMarks,
+            view::{testing::position_transitions, Position, PositionState},
+        },
+    };
+
+    // a custom view that only renders a marker, as a virtualized list might before and after its
+    // visible rows
+    struct Marker;
+
+    impl Render for Marker {
+        type State = Placeholder;
+
+        fn build(self) -> Self::State {
+            Rndr::create_placeholder()
+        }
+
+        fn rebuild(self, _state: &mut Self::State) {}
+    }
+
+    impl AddAnyAttr for Marker {
+        type Output<SomeNewAttr: Attribute> = Marker;
+
+        fn add_any_attr<NewAttr: Attribute>(
+            self,
+            _attr: NewAttr,
+        ) -> Self::Output<NewAttr> {
+            self
+        }
+    }
+
+    impl RenderHtml for Marker {
+        type AsyncOutput = Self;
+
+        const MIN_LENGTH: usize = 3;
+
+        fn dry_resolve(&mut self) {}
+
+        async fn resolve(self) -> Self::AsyncOutput {
+            self
+        }
+
+        fn to_html_with_buf(
+            self,
+            buf: &mut String,
+            position: &mut Position,
+            _escape: bool,
+            _mark_branches: &BranchMarks,
+        ) {
+            buf.push_str("<!>");
+            *position = Position::NextChild;
+        }
+
+        fn hydrate<const FROM_SERVER: bool>(
+            self,
+            cursor: &Cursor,
+            position: &PositionState,
+        ) -> Self::State {
+            cursor.next_placeholder(position)
+        }
+    }
+
+    let (html, transitions) = position_transitions(|log| {
+        div().child((log.trace(Marker), "a", log.trace(Marker), "b", "c"))
+    });
+    assert_eq!(html, "<div><!>a<!>b<!>c</div>");
+    assert_eq!(
+        transitions,
+        [
+            (Position::FirstChild, Position::NextChild),
+            (Position::NextChildAfterText, Position::NextChild),
+        ]
+    );
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_boxed_any_view_function() {
+    use leptos::{
+        html::{div, p, span},
+        prelude::*,
+        tachys::view::any_view::AnyView,
+    };
+
+    let mut wide = false;
+    let mut child: Box<dyn FnMut() -> AnyView + Send> = Box::new(move || {
+        wide = !wide;
+        if wide {
+            p().child("wide").into_any()
+        } else {
+            span().child("narrow").into_any()
+        }
+    });
+    assert_eq!(child().to_html(), "<p>wide</p>");
+    assert_eq!(
+        div().child(child).to_html(),
+        "<div><span>narrow</span></div>"
+    );
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_or_else_render() {
+    use leptos::{
+        html::{div, em, strong},
+        prelude::*,
+        tachys::reactive_graph::or_else_render,
+    };
+
+    let input = RwSignal::new("1");
+    let render = move || {
+        div()
+            .child((
+                strong().child("before"),
+                or_else_render(
+                    move || input.get().parse::<i32>().map_err(|_| input.get()),
+                    |err| format!("invalid: {err}"),
+                ),
+                em().child("after"),
+            ))
+            .to_html()
+    };
+
+    assert_eq!(
+        render(),
+        "<div><strong>before</strong>1<em>after</em></div>"
+    );
+    input.set("<b>");
+    assert_eq!(
+        render(),
+        "<div><strong>before</strong>invalid: &lt;b&gt;<em>after</em></div>"
+    );
+    input.set("2");
+    assert_eq!(
+        render(),
+        "<div><strong>before</strong>2<em>after</em></div>"
+    );
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_visible_when() {
+    use leptos::{
+        html::div,
+        prelude::*,
+        tachys::reactive_graph::{visible_when, visible_when_with, HideWith},
+    };
+
+    let open = RwSignal::new(false);
+    let panel = move || visible_when(move || open.get(), div().child("panel"));
+    assert_eq!(panel().to_html(), "<div hidden>panel</div>");
+    open.set(true);
+    assert_eq!(panel().to_html(), "<div>panel</div>");
+
+    let panel = move || {
+        visible_when_with(
+            move || open.get(),
+            div().child("panel"),
+            HideWith::DisplayNone,
+        )
+    };
+    assert_eq!(panel().to_html(), "<div>panel</div>");
+    open.set(false);
+    assert_eq!(
+        panel().to_html(),
+        "<div style=\"display:none;\">panel</div>"
+    );
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_stable_branch_ids() {
+    use either_of::Either;
+    use leptos::{
+        html::{div, p, span},
+        prelude::*,
+        tachys::ssr::{BranchMarks, HashedBranchPaths},
+    };
+    use std::sync::Arc;
+
+    let branch = || Either::<_, ()>::Left(Either::<(), _>::Right(p()));
+    let marks = BranchMarks::with_marker(Arc::new(HashedBranchPaths));
+    let alone = div().child(branch()).to_html_with_branch_marks(&marks);
+    let with_sibling = div()
+        .child((span(), branch()))
+        .to_html_with_branch_marks(&marks);
+
+    assert_eq!(branch_markers(&alone), branch_markers(&with_sibling));
+    // the inner branch is identified by its path, not just its own arm
+    let ids = branch_markers(&alone);
+    assert_eq!(ids.len(), 4);
+    assert_ne!(ids[0], ids[1]);
+    assert_eq!(ids[0][3..], ids[3][3..]);
+    assert_eq!(ids[1][3..], ids[2][3..]);
+}
+
+#[cfg(feature = "ssr")]
+fn branch_markers(html: &str) -> Vec<String> {
+    html.split("<!--")
+        .skip(1)
+        .map(|marker| marker.split("-->").next().unwrap().to_string())
+        .filter(|marker| marker.starts_with("bo-") || marker.starts_with("bc-"))
+        .collect()
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_stable_branch_ids_in_async_chunks() {
+    use either_of::Either;
+    use futures::StreamExt;
+    use leptos::{
+        html::{div, p},
+        prelude::*,
+        tachys::ssr::{
+            BranchMarks, HashedBranchPaths, StreamBuilder, StreamingStrategy,
+        },
+    };
+    use std::sync::Arc;
+
+    let owner = Owner::new();
+    owner.set();
+
+    let marks = BranchMarks::with_marker(Arc::new(HashedBranchPaths));
+    // the inner branch is rendered once the future resolves, but still knows the path of the
+    // branches around it
+    for strategy in [StreamingStrategy::InOrder, StreamingStrategy::OutOfOrder]
+    {
+        // an in-order stream renders a pending `Suspend`'s value as an `Option`
+        let inner = Either::<(), _>::Right(p());
+        let expected = match strategy {
+            StreamingStrategy::InOrder => div()
+                .child(Either::<_, ()>::Left(Some(inner)))
+                .to_html_with_branch_marks(&marks),
+            StreamingStrategy::OutOfOrder => div()
+                .child(Either::<_, ()>::Left(inner))
+                .to_html_with_branch_marks(&marks),
+        };
+        let view = div().child(Either::<_, ()>::Left(Suspend::new(async {
+            YieldOnce(false).await;
+            Either::<(), _>::Right(p())
+        })));
+        let html = futures::executor::block_on(
+            view.to_html_stream_with_builder(
+                StreamBuilder::for_strategy(strategy, 0),
+                &marks,
+            )
+            .collect::<Vec<_>>(),
+        )
+        .concat();
+        assert_eq!(branch_markers(&html), branch_markers(&expected));
+    }
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_stream_max_buffered_bytes() {
+    use futures::StreamExt;
+    use leptos::{
+        html::ul,
+        prelude::*,
+        tachys::ssr::{BranchMarks, StreamBuilder, StreamingStrategy},
+    };
+
+    const MAX: usize = 64 * 1024;
+
+    let owner = Owner::new();
+    owner.set();
+
+    // roughly 2MB of HTML
+    let page = || {
+        ul().child(view! {
+            <For each=|| 0..40_000 key=|n| *n let:n>
+                <li>{format!("row {n} ✓ {}", "x".repeat(32))}</li>
+            </For>
+        })
+    };
+    let expected = page().to_html();
+    assert!(expected.len() > 2_000_000);
+
+    for strategy in [StreamingStrategy::InOrder, StreamingStrategy::OutOfOrder]
+    {
+        let mut stream = page().to_html_stream_with_builder(
+            StreamBuilder::for_strategy(strategy, 0)
+                .with_max_buffered_bytes(MAX),
+            &BranchMarks::NONE,
+        );
+        // the whole page is not rendered up front, and no more than the limit (plus the row
+        // that reached it) is held at any point while it's streamed
+        let mut peak = stream.buffered_bytes();
+        let mut chunks = Vec::new();
+        while let Some(chunk) = futures::executor::block_on(stream.next()) {
+            peak = peak.max(stream.buffered_bytes() + chunk.len());
+            chunks.push(chunk);
+        }
+        assert!(peak <= MAX + 1024, "peak of {peak} bytes buffered");
+        assert!(chunks.len() > 1);
+        assert!(chunks.iter().all(|chunk| chunk.len() <= MAX));
+        assert_eq!(chunks.concat(), expected);
+    }
+}
+
+// resolves after being polled once, so that streams have to wait for it
+#[cfg(feature = "ssr")]
+struct YieldOnce(bool);
+
+#[cfg(feature = "ssr")]
+impl std::future::Future for YieldOnce {
+    type Output = ();
+
+    fn poll(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<()> {
+        if self.0 {
+            std::task::Poll::Ready(())
+        } else {
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            std::task::Poll::Pending
+        }
+    }
+}
+
+#[cfg(feature = "ssr")]
+fn stream_with_pending_chunk(
+    builder: tachys::ssr::StreamBuilder,
+) -> Vec<String> {
+    use futures::StreamExt;
+    use tachys::{ssr::BranchMarks, view::Position};
+
+    let mut builder = builder;
+    builder.push_fallback(
+        "loading",
+        &mut Position::FirstChild,
+        &BranchMarks::NONE,
+    );
+    builder.push_async_out_of_order(
+        async {
+            YieldOnce(false).await;
+            Some("loaded")
+        },
+        &mut Position::FirstChild,
+        &BranchMarks::NONE,
+    );
+    futures::executor::block_on(builder.finish().collect::<Vec<_>>())
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_stream_script_nonce() {
+    use tachys::ssr::StreamBuilder;
+
+    let html = stream_with_pending_chunk(
+        StreamBuilder::new(Some(vec![0])).with_nonce("abc"),
+    )
+    .concat();
+    assert!(html.contains("loaded"));
+    assert!(html.contains("<script"));
+    assert_eq!(
+        html.matches("<script").count(),
+        html.matches(r#"<script nonce="abc">"#).count()
+    );
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_stream_external_reorder_script() {
+    use tachys::ssr::{ReorderScript, StreamBuilder};
+
+    let html = stream_with_pending_chunk(
+        StreamBuilder::new(Some(vec![0]))
+            .with_nonce("abc")
+            .with_reorder_script(ReorderScript::External("/reorder.js".into())),
+    )
+    .concat();
+    assert!(html.contains("loaded"));
+    assert!(
+        html.contains(r#"<template id="0-f">loaded</template><!--s-0-r-->"#)
+    );
+    assert_eq!(html.matches("<script").count(), 1);
+    assert!(html.contains(r#"<script src="/reorder.js" nonce="abc"></script>"#));
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_in_order_stream_flushes_at_each_boundary() {
+    use futures::StreamExt;
+    use leptos::{
+        html::{main, p},
+        prelude::*,
+    };
+    use tachys::{reactive_graph::Suspend, ssr::StreamingStrategy};
+
+    let page = || {
+        main().child((
+            p().child("before"),
+            Suspend::new(async {
+                YieldOnce(false).await;
+                (
+                    p().child("outer"),
+                    Suspend::new(async {
+                        YieldOnce(false).await;
+                        p().child("inner")
+                    }),
+                    p().child("outer after"),
+                )
+            }),
+            p().child("after"),
+        ))
+    };
+
+    let chunks = futures::executor::block_on(
+        page()
+            .to_html_stream(StreamingStrategy::InOrder)
+            .collect::<Vec<_>>(),
+    );
+    assert_eq!(
+        chunks,
+        [
+            "<main><p>before</p>",
+            "<p>outer</p>",
+            "<p>inner</p><p>outer after</p><p>after</p></main>"
+        ]
+    );
+    assert!(chunks.iter().all(|chunk| !chunk.contains("<script")));
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_minify_collapses_text_whitespace() {
+    use leptos::{
+        html::{div, p, pre, textarea},
+        prelude::*,
+    };
+    use tachys::ssr::{ChunkContext, HtmlTransformer, Minify};
+
+    let html = div()
+        .class("a  b")
+        .child((
+            p().child("Hello,   \n  world!"),
+            pre().child("  keep \n  this  "),
+            textarea().child("and   this"),
+            Some("  "),
+        ))
+        .to_html_with_transformer(Minify::default());
+    assert_eq!(
+        html,
+        "<div class=\"a  b\"><p>Hello, world!</p><pre>  keep \n  this  \
+         </pre><textarea>and   this</textarea> </div>"
+    );
+
+    // chunks can split tags, and the scanner keeps its place between them
+    let mut minify = Minify::default();
+    let out = [
+        "<p data-preserve-whitespace>a   b</p><p>c",
+        "   d</p><scr",
+        "ipt>if (a <b) {  }</script>  <!-- x   y -->",
+    ]
+    .into_iter()
+    .enumerate()
+    .map(|(index, chunk)| {
+        let mut chunk = chunk.to_string();
+        minify.transform_chunk(&mut chunk, ChunkContext::new(index, true));
+        chunk
+    })
+    .collect::<String>();
+    assert_eq!(
+        out,
+        "<p data-preserve-whitespace>a   b</p><p>c d</p><script>if (a <b) \
+         {  }</script> <!-- x   y -->"
+    );
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_minify_keeps_hydration_markers() {
+    use either_of::Either;
+    use leptos::{
+        html::{div, span},
+        prelude::*,
+    };
+    use tachys::ssr::{ChunkContext, HtmlTransformer, Minify};
+
+    let view = || {
+        div().child((
+            "one",
+            "two",
+            Either::<_, &str>::Left(span().child("three")),
+            Some("four"),
+        ))
+    };
+    // tachys does not write whitespace of its own, so minifying leaves every text node and
+    // marker comment exactly where hydration expects it
+    let mut minify = Minify::default();
+    let html = view().to_html_branching();
+    let mut minified = html.clone();
+    minify.transform_chunk(&mut minified, ChunkContext::new(0, false));
+    assert!(html.contains("<!>"));
+    assert!(html.contains("<!--bo"));
+    assert_eq!(minified, html);
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_indent_stream() {
+    use futures::StreamExt;
+    use leptos::{
+        html::{br, li, pre, ul},
+        prelude::*,
+    };
+    use tachys::ssr::{Indent, StreamingStrategy};
+
+    let chunks = futures::executor::block_on(
+        ul().child((li().child(("a", br())), li().child(pre().child(" b\n"))))
+            .to_html_stream(StreamingStrategy::InOrder)
+            .with_transformer(Indent::default())
+            .collect::<Vec<_>>(),
+    );
+    assert_eq!(
+        chunks.concat(),
+        "<ul>\n  <li>a\n    <br>\n  </li>\n  <li>\n    <pre> b\n</pre>\n  \
+         </li>\n</ul>"
+    );
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_raw_html_island() {
+    use leptos::{
+        html::{article, p},
+        prelude::*,
+    };
+    use tachys::view::raw_html::RawHtmlIsland;
+
+    let markdown = "<h1>Title</h1>\n<ul><li><em>a &amp; b</em></li></ul>text";
+    let count = RwSignal::new(0);
+    let html = article()
+        .child((
+            move || count.get(),
+            RawHtmlIsland::new(markdown),
+            move || count.get() + 1,
+            p().child("after"),
+        ))
+        .to_html();
+    assert_eq!(
+        html,
+        format!(
+            "<article>0<!--raw-->{markdown}<!--/raw-->1<p>after</p></article>"
+        )
+    );
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn hydration_error_names_expected_and_found_nodes() {
+    use std::panic::Location;
+    use tachys::hydration::{ExpectedNode, HydrationError};
+
+    // a view expecting `<p><span>` that finds an extra whitespace text node before the `<span>`
+    let error = HydrationError {
+        expected: ExpectedNode::Element("span"),
+        found: Some(r#"#text "\n  ""#.to_string()),
+        path: vec![
+            "#document".to_string(),
+            "html[1]".to_string(),
+            "body[1]".to_string(),
+            "p[0]".to_string(),
+            r#"#text "\n  "[0]"#.to_string(),
+        ],
+        location: Location::caller(),
+    };
+    let message = error.to_string();
+    assert!(message.starts_with(
+        r#"hydration mismatch: expected a <span> element, but found #text "\n  "
+  at #document > html[1] > body[1] > p[0] > #text "\n  "[0]
+  while hydrating the view at "#
+    ));
+
+    let error = HydrationError {
+        expected: ExpectedNode::Text,
+        found: None,
+        ..error
+    };
+    assert!(error.to_string().starts_with(
+        "hydration mismatch: expected a text node, but found no more nodes"
+    ));
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_adjacent_text_with_empty_dynamic_text() {
+    use leptos::prelude::*;
+
+    // hydration relies on each piece of text being separated by a marker, and on empty text
+    // still rendering a text node, in order to split merged text nodes back apart
+    let s = RwSignal::new(String::new());
+    let render = move || view! { <p>"a"{move || s.get()}"b"</p> }.to_html();
+
+    assert_eq!(render(), "<p>a<!> <!>b</p>");
+    s.set("middle".to_string());
+    assert_eq!(render(), "<p>a<!>middle<!>b</p>");
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_hydration_scripts_hydrate_root() {
+    use leptos::{hydration::HydrationScripts, prelude::*};
+
+    let html = view! {
+        <HydrationScripts options=LeptosOptions::default() islands=true hydrate_root="#app"/>
+    }
+    .to_html();
+    assert!(html.contains(r##", "#app", false)</script>"##));
+
+    let html = view! { <HydrationScripts options=LeptosOptions::default()/> }
+        .to_html();
+    assert!(html.contains(r#", "", false)</script>"#));
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_hydration_scripts_replay_events() {
+    use leptos::{hydration::HydrationScripts, prelude::*};
+
+    let html = view! {
+        <HydrationScripts options=LeptosOptions::default() replay_events=true/>
+    }
+    .to_html();
+    // the WASM is not loaded until the first event
+    assert!(!html.contains("modulepreload"));
+    assert!(html.contains("__LEPTOS_STOP_RECORDING"));
+    assert!(html.contains(r#", "", true)</script>"#));
+
+    let html = view! {
+        <HydrationScripts options=LeptosOptions::default() islands=true replay_events=true/>
+    }
+    .to_html();
+    assert!(html.contains("modulepreload"));
+    assert!(!html.contains("__LEPTOS_STOP_RECORDING"));
+}
+
+// effects and spawned tasks run on the manual executor, which is flushed between polls
+#[cfg(feature = "ssr")]
+fn collect_flushing(
+    mut stream: impl futures::Stream<Item = String> + Unpin,
+) -> String {
+    use any_spawner::Executor;
+    use futures::{FutureExt, StreamExt};
+
+    let mut html = String::new();
+    for _ in 0..1000 {
+        Executor::flush();
+        match stream.next().now_or_never() {
+            Some(Some(chunk)) => html.push_str(&chunk),
+            Some(None) => return html,
+            None => {}
+        }
+    }
+    panic!("stream did not finish; rendered so far: {html}");
+}
+
+#[cfg(feature = "ssr")]
+fn render_with_local_resource<V: leptos::prelude::IntoView + 'static>(
+    view: impl FnOnce(leptos::prelude::LocalResource<i32>) -> V,
+    out_of_order: bool,
+) -> (String, String) {
+    use hydration_context::{SharedContext, SsrSharedContext};
+    use leptos::prelude::*;
+    use std::sync::Arc;
+
+    _ = any_spawner::Executor::init_manual();
+    let shared_context = Arc::new(SsrSharedContext::new());
+    let owner = Owner::new_root(Some(shared_context.clone()));
+    owner.with(|| {
+        let local = LocalResource::new(|| async { 42 });
+        let view = view(local);
+        let stream = if out_of_order {
+            view.to_html_stream_out_of_order()
+        } else {
+            view.to_html_stream_in_order()
+        };
+        let html = collect_flushing(stream);
+        let data = collect_flushing(shared_context.pending_data().unwrap());
+        (html, data)
+    })
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_local_resource_read_synchronously_renders_fallback() {
+    use leptos::prelude::*;
+
+    for out_of_order in [false, true] {
+        let (html, data) = render_with_local_resource(
+            |local| {
+                view! {
+                    <Suspense fallback=|| "loading">
+                        <p>{move || local.get().map(|n| *n)}</p>
+                    </Suspense>
+                }
+            },
+            out_of_order,
+        );
+        assert!(html.contains("loading"), "{html}");
+        assert!(!html.contains("<p>"), "{html}");
+        assert!(data.contains("__INCOMPLETE_CHUNKS=[0,];"), "{data}");
+    }
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_local_resource_read_after_await_renders_fallback() {
+    use leptos::prelude::*;
+
+    for out_of_order in [false, true] {
+        let (html, data) = render_with_local_resource(
+            |local| {
+                view! {
+                    <Suspense fallback=|| "loading">
+                        {move || Suspend::new(async move {
+                            YieldOnce(false).await;
+                            let value = local.await;
+                            view! { <p>{value}</p> }
+                        })}
+                    </Suspense>
+                }
+            },
+            out_of_order,
+        );
+        assert!(html.contains("loading"), "{html}");
+        assert!(!html.contains("<p>"), "{html}");
+        assert!(data.contains("__INCOMPLETE_CHUNKS=[0,];"), "{data}");
+    }
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_untrack_inside_suspense_renders_once() {
+    use any_spawner::Executor;
+    use futures::{FutureExt, StreamExt};
+    use hydration_context::SsrSharedContext;
+    use leptos::{prelude::*, tachys::reactive_graph::Untrack};
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    for out_of_order in [false, true] {
+        _ = Executor::init_manual();
+        let owner = Owner::new_root(Some(Arc::new(SsrSharedContext::new())));
+        owner.with(|| {
+            let draws = Arc::new(AtomicUsize::new(0));
+            let draw = {
+                let draws = Arc::clone(&draws);
+                move || {
+                    draws.fetch_add(1, Ordering::Relaxed);
+                    "drawn"
+                }
+            };
+            let view = view! {
+                <Suspense fallback=|| "loading">
+                    <p>{Untrack::new(draw)}</p>
+                </Suspense>
+            };
+            let stream = if out_of_order {
+                view.to_html_stream_out_of_order()
+            } else {
+                view.to_html_stream_in_order()
+            };
+            Executor::flush();
+            let html = stream
+                .collect::<String>()
+                .now_or_never()
+                .expect("the stream should be ready");
+            assert!(html.contains("<p>drawn</p>"), "{html}");
+            // the value created while walking the tree for resources is the one rendered
+            assert_eq!(draws.load(Ordering::Relaxed), 1);
+        });
+    }
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_zipped_resources_hold_suspense_until_both_load() {
+    use any_spawner::Executor;
+    use futures::{channel::oneshot, FutureExt, StreamExt};
+    use hydration_context::SsrSharedContext;
+    use leptos::prelude::*;
+    use std::sync::{Arc, Mutex};
+
+    fn controlled() -> (Resource<i32>, oneshot::Sender<i32>) {
+        let (tx, rx) = oneshot::channel();
+        let rx = Mutex::new(Some(rx));
+        let resource = Resource::new(
+            || (),
+            move |_| {
+                let rx = rx.lock().unwrap().take();
+                async move { rx.unwrap().await.unwrap() }
+            },
+        );
+        (resource, tx)
+    }
+
+    for out_of_order in [false, true] {
+        _ = Executor::init_manual();
+        let owner = Owner::new_root(Some(Arc::new(SsrSharedContext::new())));
+        owner.with(|| {
+            let (a, a_tx) = controlled();
+            let (b, b_tx) = controlled();
+            let both = a.zip(&b);
+            let view = view! {
+                <Suspense fallback=|| "loading">
+                    <p>{move || both.get().map(|(a, b)| format!("{a} and {b}"))}</p>
+                </Suspense>
+            };
+            let mut stream = if out_of_order {
+                view.to_html_stream_out_of_order()
+            } else {
+                view.to_html_stream_in_order()
+            };
+
+            let mut html = String::new();
+            let mut poll = |html: &mut String| {
+                for _ in 0..100 {
+                    Executor::flush();
+                    match stream.next().now_or_never() {
+                        Some(Some(chunk)) => html.push_str(&chunk),
+                        Some(None) => return true,
+                        None => {}
+                    }
+                }
+                false
+            };
+
+            assert!(!poll(&mut html));
+            a_tx.send(1).unwrap();
+            assert!(!poll(&mut html));
+            assert!(!html.contains("<p>"), "{html}");
+
+            b_tx.send(2).unwrap();
+            assert!(poll(&mut html));
+            assert!(html.contains("<p>1 and 2</p>"), "{html}");
+            assert_eq!(html.contains("loading"), out_of_order, "{html}");
+        });
+    }
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_provide_context_view_scopes_context_to_children() {
+    use leptos::{context::provide_context_view, prelude::*};
+
+    #[derive(Clone, Copy)]
+    struct RowContext(u8);
+
+    #[component]
+    fn Row() -> impl IntoView {
+        let RowContext(n) = expect_context::<RowContext>();
+        view! { <li>{n}</li> }
+    }
+
+    let owner = Owner::new();
+    owner.set();
+    provide_context(RowContext(0));
+
+    let rendered = view! {
+        <ul>
+            {provide_context_view(RowContext(1), || view! { <Row/> })}
+            {provide_context_view(RowContext(2), || {
+                // a grandchild resolves the nearest value
+                provide_context_view(RowContext(3), || view! { <Row/> })
+            })}
+            <Row/>
+        </ul>
+    };
+
+    assert_eq!(
+        rendered.to_html(),
+        "<ul><li>1</li><li>3</li><li>0</li></ul>"
+    );
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn suspend_stream_flushes_each_item() {
+    use any_spawner::Executor;
+    use futures::{channel::mpsc, FutureExt, StreamExt};
+    use hydration_context::SsrSharedContext;
+    use leptos::prelude::*;
+    use std::sync::Arc;
+
+    for resolve in [StreamResolution::FirstItem, StreamResolution::End] {
+        for out_of_order in [false, true] {
+            _ = Executor::init_manual();
+            let owner =
+                Owner::new_root(Some(Arc::new(SsrSharedContext::new())));
+            owner.with(|| {
+                let (tx, rx) = mpsc::unbounded::<i32>();
+                let items =
+                    SuspendStream::new(rx.map(|n| view! { <li>{n}</li> }))
+                        .resolve_on(resolve);
+                let view = view! {
+                    <Suspense fallback=|| "loading">
+                        <ul>{items}</ul>
+                    </Suspense>
+                };
+                let mut stream = if out_of_order {
+                    view.to_html_stream_out_of_order()
+                } else {
+                    view.to_html_stream_in_order()
+                };
+
+                let mut html = String::new();
+                let mut poll = |html: &mut String| {
+                    for _ in 0..100 {
+                        Executor::flush();
+                        match stream.next().now_or_never() {
+                            Some(Some(chunk)) => html.push_str(&chunk),
+                            Some(None) => return true,
+                            None => {}
+                        }
+                    }
+                    false
+                };
+
+                assert!(!poll(&mut html));
+                assert!(!html.contains("<li>"), "{html}");
+                tx.unbounded_send(1).unwrap();
+                assert!(!poll(&mut html));
+                assert_eq!(
+                    html.contains("<li>1</li>"),
+                    resolve == StreamResolution::FirstItem,
+                    "{html}"
+                );
+
+                tx.unbounded_send(2).unwrap();
+                assert!(!poll(&mut html));
+                assert_eq!(
+                    html.contains("<li>2</li>"),
+                    resolve == StreamResolution::FirstItem,
+                    "{html}"
+                );
+
+                drop(tx);
+                assert!(poll(&mut html));
+                let first = html.find("<li>1</li>").expect(&html);
+                let second = html.find("<li>2</li>").expect(&html);
+                assert!(first < second, "{html}");
+            });
+        }
+    }
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn suspended_error_renders_error_boundary_fallback() {
+    use any_spawner::Executor;
+    use hydration_context::{SharedContext, SsrSharedContext};
+    use leptos::prelude::*;
+    use std::sync::Arc;
+
+    for fail in [true, false] {
+        for out_of_order in [false, true] {
+            _ = Executor::init_manual();
+            let shared_context = Arc::new(SsrSharedContext::new());
+            let owner = Owner::new_root(Some(shared_context.clone()));
+            owner.with(|| {
+                let view = view! {
+                    <ErrorBoundary fallback=|_| view! { <p>"failed"</p> }>
+                        <Suspense fallback=|| "loading">
+                            {Suspend::new(async move {
+                                YieldOnce(false).await;
+                                if fail {
+                                    Err(std::io::Error::other("oops"))
+                                } else {
+                                    Ok(view! { <p>"loaded"</p> })
+                                }
+                            })}
+                        </Suspense>
+                    </ErrorBoundary>
+                };
+                let stream = if out_of_order {
+                    view.to_html_stream_out_of_order()
+                } else {
+                    view.to_html_stream_in_order()
+                };
+                let html = collect_flushing(stream);
+                let data =
+                    collect_flushing(shared_context.pending_data().unwrap());
+
+                assert_eq!(html.contains("<p>failed</p>"), fail, "{html}");
+                assert_eq!(html.contains("<p>loaded</p>"), !fail, "{html}");
+                // the client boundary starts out with the same errors
+                assert_eq!(data.contains(r#""oops""#), fail, "{data}");
+            });
+        }
+    }
+}
+
+#[cfg(feature = "ssr")]
+mod downcast_errors {
+    use std::{fmt, str::FromStr};
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct AuthError;
+
+    impl fmt::Display for AuthError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("session expired")
+        }
+    }
+
+    impl std::error::Error for AuthError {}
+
+    impl FromStr for AuthError {
+        type Err = ();
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            (s == "session expired").then_some(AuthError).ok_or(())
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct FetchError(pub u16);
+
+    impl fmt::Display for FetchError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "request failed with status {}", self.0)
+        }
+    }
+
+    impl std::error::Error for FetchError {}
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn error_boundary_fallback_branches_on_error_type() {
+    use downcast_errors::{AuthError, FetchError};
+    use leptos::prelude::*;
+
+    let owner = Owner::new();
+    owner.with(|| {
+        let view = view! {
+            <ErrorBoundary fallback=|errors| {
+                errors
+                    .get()
+                    .into_iter()
+                    .map(|(_, error)| {
+                        if error.is::<AuthError>() {
+                            view! { <a href="/login">"Log in"</a> }.into_any()
+                        } else if let Some(FetchError(status)) =
+                            error.downcast_ref::<FetchError>()
+                        {
+                            view! { <button>{format!("Retry {status}")}</button> }
+                                .into_any()
+                        } else {
+                            view! { <p>{error.to_string()}</p> }.into_any()
+                        }
+                    })
+                    .collect::<Vec<_>>()
+            }>
+                {Err::<(), _>(AuthError)}
+                {Err::<(), _>(FetchError(503))}
+            </ErrorBoundary>
+        };
+        let html = view.to_html();
+        assert!(html.contains(r#"<a href="/login">Log in</a>"#), "{html}");
+        assert!(html.contains("<button>Retry 503</button>"), "{html}");
+    });
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn serialized_server_fn_error_keeps_its_type() {
+    use downcast_errors::AuthError;
+    use hydration_context::{SharedContext, SsrSharedContext};
+    use leptos::prelude::*;
+    use std::sync::Arc;
+
+    server_fn::error::register_error_type::<AuthError>("auth");
+
+    let shared_context = Arc::new(SsrSharedContext::new());
+    let owner = Owner::new_root(Some(shared_context.clone()));
+    owner.with(|| {
+        let view = view! {
+            <ErrorBoundary fallback=|_| "failed">
+                {Err::<(), _>(ServerFnError::WrappedServerError(AuthError))}
+            </ErrorBoundary>
+        };
+        _ = view.to_html();
+    });
+    let data = collect_flushing(shared_context.pending_data().unwrap());
+
+    // the error is sent along with the tag of its type...
+    let error =
+        throw_error::Error::from(ServerFnError::WrappedServerError(AuthError));
+    let (tag, serialized) = error.serialize();
+    assert_eq!(tag, Some("auth"));
+    assert!(
+        data.contains(&format!("{serialized:?}, \"auth\"")),
+        "{data}"
+    );
+
+    // ...which restores the original type on the client
+    let error = throw_error::Error::deserialize("auth", &serialized).unwrap();
+    assert_eq!(
+        error.downcast_ref::<ServerFnError<AuthError>>(),
+        Some(&ServerFnError::WrappedServerError(AuthError))
+    );
+
+    // errors of unregistered types are sent as text
+    let (tag, serialized) =
+        throw_error::Error::from(std::io::Error::other("offline")).serialize();
+    assert_eq!((tag, serialized.as_str()), (None, "offline"));
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_streams_async_values_rendered_directly() {
+    use any_spawner::Executor;
+    use futures::{channel::oneshot, FutureExt, StreamExt};
+    use hydration_context::SsrSharedContext;
+    use leptos::prelude::*;
+    use std::sync::{Arc, Mutex};
+
+    for out_of_order in [false, true] {
+        _ = Executor::init_manual();
+        let owner = Owner::new_root(Some(Arc::new(SsrSharedContext::new())));
+        owner.with(|| {
+            let (tx, rx) = oneshot::channel();
+            let rx = Mutex::new(Some(rx));
+            let resource = Resource::new(
+                || (),
+                move |_| {
+                    let rx = rx.lock().unwrap().take();
+                    async move { rx.unwrap().await.unwrap() }
+                },
+            );
+            let arc_resource = ArcResource::new(|| (), |_| async { 2 });
+            let derived = AsyncDerived::new(|| async { 3 });
+            let view = view! {
+                <Suspense fallback=|| "loading">
+                    <p>{resource}", "{arc_resource}", "{derived}</p>
+                </Suspense>
+            };
+            let mut stream = if out_of_order {
+                view.to_html_stream_out_of_order()
+            } else {
+                view.to_html_stream_in_order()
+            };
+
+            let mut html = String::new();
+            let mut poll = |html: &mut String| {
+                for _ in 0..100 {
+                    Executor::flush();
+                    match stream.next().now_or_never() {
+                        Some(Some(chunk)) => html.push_str(&chunk),
+                        Some(None) => return true,
+                        None => {}
+                    }
+                }
+                false
+            };
+
+            assert!(!poll(&mut html));
+            assert!(!html.contains("<p>"), "{html}");
+
+            tx.send(1).unwrap();
+            assert!(poll(&mut html));
+            assert!(html.contains("<p>1<!>, <!>2<!>, <!>3</p>"), "{html}");
+            assert_eq!(html.contains("loading"), out_of_order, "{html}");
+        });
+    }
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn ssr_arc_memo_child_of_suspense_waits_for_resource() {
+    use any_spawner::Executor;
+    use futures::{channel::oneshot, FutureExt, StreamExt};
+    use hydration_context::SsrSharedContext;
+    use leptos::prelude::*;
+    use std::sync::{Arc, Mutex};
+
+    for out_of_order in [false, true] {
+        _ = Executor::init_manual();
+        let owner = Owner::new_root(Some(Arc::new(SsrSharedContext::new())));
+        owner.with(|| {
+            let (tx, rx) = oneshot::channel();
+            let rx = Mutex::new(Some(rx));
+            let resource = Resource::new(
+                || (),
+                move |_| {
+                    let rx = rx.lock().unwrap().take();
+                    async move { rx.unwrap().await.unwrap() }
+                },
+            );
+            // created under the Suspense, so that the resource read registers with it
+            let label = move || {
+                ArcMemo::new(move |_| {
+                    resource.get().map(|n: i32| format!("loaded {n}"))
+                })
+            };
+            let view = view! {
+                <Suspense fallback=|| "loading">{label()}</Suspense>
+            };
+            let mut stream = if out_of_order {
+                view.to_html_stream_out_of_order()
+            } else {
+                view.to_html_stream_in_order()
+            };
+
+            let mut html = String::new();
+            let mut poll = |html: &mut String| {
+                for _ in 0..100 {
+                    Executor::flush();
+                    match stream.next().now_or_never() {
+                        Some(Some(chunk)) => html.push_str(&chunk),
+                        Some(None) => return true,
+                        None => {}
+                    }
+                }
+                false
+            };
+
+            // the memo's read of the resource during the dry run holds the suspense open
+            assert!(!poll(&mut html));
+            assert!(!html.contains("loaded"), "{html}");
+
+            tx.send(1).unwrap();
+            assert!(poll(&mut html));
+            assert!(html.contains("loaded 1"), "{html}");
+        });
+    }
+}
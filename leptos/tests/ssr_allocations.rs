@@ -0,0 +1,83 @@
+// Counts how many times rendering a page to HTML has to grow an allocation, to check that
+// `html_len` is a good enough estimate to size the output buffer up front.
+#![cfg(feature = "ssr")]
+
+use leptos::{
+    html::{li, span, ul},
+    prelude::*,
+};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+struct CountReallocs;
+
+thread_local! {
+    static COUNTING: Cell<bool> = const { Cell::new(false) };
+    static REALLOCS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountReallocs {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(
+        &self,
+        ptr: *mut u8,
+        layout: Layout,
+        new_size: usize,
+    ) -> *mut u8 {
+        if COUNTING.try_with(Cell::get).unwrap_or(false) {
+            _ = REALLOCS.try_with(|n| n.set(n.get() + 1));
+        }
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountReallocs = CountReallocs;
+
+fn count_reallocs<T>(fun: impl FnOnce() -> T) -> (T, usize) {
+    REALLOCS.with(|n| n.set(0));
+    COUNTING.with(|c| c.set(true));
+    let value = fun();
+    COUNTING.with(|c| c.set(false));
+    (value, REALLOCS.with(Cell::get))
+}
+
+// a page of text with plenty of characters that need to be escaped
+fn page() -> impl RenderHtml {
+    ul().child(
+        (0..2_000)
+            .map(|n| {
+                li().child((
+                    span().child(format!("Row {n}: Fish & Chips")),
+                    " <R&D> ".to_string(),
+                ))
+            })
+            .collect::<Vec<_>>(),
+    )
+}
+
+#[test]
+fn ssr_to_html_preallocates_escaped_text() {
+    let (html, reallocs) = count_reallocs(|| page().to_html());
+    assert!(html.contains("Fish &amp; Chips"));
+    assert!(html.contains("&lt;R&amp;D&gt;"));
+    assert_eq!(reallocs, 0);
+}
+
+#[test]
+fn ssr_to_html_with_capacity_hint() {
+    let expected = page().to_html();
+    let (html, reallocs) =
+        count_reallocs(|| page().to_html_with_capacity_hint(expected.len()));
+    assert_eq!(html, expected);
+    assert_eq!(reallocs, 0);
+}
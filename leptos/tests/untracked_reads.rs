@@ -0,0 +1,50 @@
+#![cfg(all(feature = "ssr", debug_assertions))]
+
+use leptos::{prelude::*, reactive::diagnostics::on_untracked_read};
+use std::sync::{Arc, Mutex};
+
+#[component]
+fn DirectRead(count: RwSignal<i32>) -> impl IntoView {
+    view! { <p>{count.get()}</p> }
+}
+
+#[component]
+fn ClosureRead(count: RwSignal<i32>) -> impl IntoView {
+    view! { <p>{move || count.get()}</p> }
+}
+
+#[component]
+fn AllowedRead(count: RwSignal<i32>) -> impl IntoView {
+    let initial = untrack(|| count.get());
+    let doubled = {
+        let _guard = allow_untracked_read();
+        count.get() * 2
+    };
+    view! { <p>{initial + doubled}</p> }
+}
+
+// the hook is global, so all of the cases share one test
+#[test]
+fn reads_in_component_bodies_are_reported() {
+    let owner = Owner::new();
+    owner.set();
+    let reported = Arc::new(Mutex::new(Vec::new()));
+    on_untracked_read({
+        let reported = Arc::clone(&reported);
+        move |location| reported.lock().unwrap().push(location.file())
+    });
+    let count = RwSignal::new(1);
+
+    let html = view! { <DirectRead count/> }.to_html();
+    assert_eq!(html, "<p>1</p>");
+    assert_eq!(*reported.lock().unwrap(), [file!()]);
+    reported.lock().unwrap().clear();
+
+    let html = view! { <ClosureRead count/> }.to_html();
+    assert_eq!(html, "<p>1</p>");
+    assert!(reported.lock().unwrap().is_empty());
+
+    let html = view! { <AllowedRead count/> }.to_html();
+    assert_eq!(html, "<p>3</p>");
+    assert!(reported.lock().unwrap().is_empty());
+}
@@ -833,6 +833,12 @@ pub fn slot(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
 ///   to convert from the argument type to the server function type, and vice versa, allowing you to convert
 ///   between them easily. Setting `impl_from` to `false` disables this, which can be necessary for argument types
 ///   for which this would create a conflicting implementation. (defaults to `true`)
+/// - `error`: a custom error type that is sent to the client with the `output` encoding, rather
+///   than as text (see below)
+/// - `cache`: a `Cache-Control` header for successful responses, for server functions that use a
+///   `GET` input encoding (see below)
+/// - `protocol`: set to `Websocket` to communicate over a websocket rather than a single request,
+///   instead of `input` and `output` (see below)
 ///
 /// ```rust,ignore
 /// #[server(
@@ -876,6 +882,71 @@ pub fn slot(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
 /// pub async fn with_default_value(#[server(default)] values: Vec<u32>) /* etc. */
 /// ```
 ///
+/// ## Caching
+///
+/// A server function that uses a `GET` input encoding can declare how its responses may be cached
+/// with `cache`, which is sent as the `Cache-Control` header of each successful response. Using
+/// `cache` with one of the built-in `POST` input encodings is a compile error.
+///
+/// On the client, identical calls to that function (with the same arguments) that are made while
+/// a request is still in flight share its response, rather than sending another request. This
+/// requires the output and custom error types to be `Clone`. The macro also generates a
+/// `_forced` version of the function, which always sends a new request.
+/// ```rust,ignore
+/// #[server(input = GetUrl, cache = "max-age=60, public")]
+/// pub async fn list_posts(page: usize) -> Result<Vec<Post>, ServerFnError> {
+///     todo!()
+/// }
+///
+/// // bypasses any identical request that is already in flight
+/// let posts = list_posts_forced(1).await;
+/// ```
+///
+/// ## Websockets
+///
+/// With the `websocket` feature, a server function declared with `protocol = Websocket` takes a
+/// single stream of messages from the client, and returns a stream of messages for the client.
+/// Messages are JSON by default; `protocol = Websocket<Cbor>` uses CBOR instead. This is currently
+/// supported by the Axum integration only.
+///
+/// The body runs when the connection is opened, so it should return its output stream without
+/// waiting for any input. If the connection is dropped, the client reconnects with exponential
+/// backoff, which runs the body again.
+/// ```rust,ignore
+/// use server_fn::websocket::{BoxedStream, Websocket, WebsocketServerFn};
+///
+/// #[server(protocol = Websocket)]
+/// pub async fn chat(
+///     input: BoxedStream<ChatMessage>,
+/// ) -> Result<BoxedStream<ChatMessage>, ServerFnError> {
+///     Ok(BoxedStream::new(input.map(|msg| msg.map(moderate))))
+/// }
+///
+/// // on the client, either pass a stream of messages...
+/// let replies = chat(BoxedStream::new(messages)).await?;
+/// // ...or send them one at a time
+/// let (mut tx, replies) = Chat::connect();
+/// tx.send(ChatMessage::new("hello")).await?;
+/// ```
+///
+/// ## Middleware
+///
+/// A server function can be annotated with one or more `#[middleware(...)]` attributes, each of
+/// which takes an expression that creates a middleware layer (for Axum, a `tower::Layer`). The
+/// middleware is only applied to requests for this server function, and runs before its body.
+///
+/// If the middleware returns an error, the body does not run, and the error is sent to the client
+/// as a [`ServerFnError`](../server_fn/error/enum.ServerFnError.html). Any extensions that it adds
+/// to the request can be read in the body with `leptos_axum::extract`.
+/// ```rust,ignore
+/// #[server]
+/// #[middleware(RequireSession)]
+/// pub async fn whoami() -> Result<String, ServerFnError> {
+///     let Extension(session) = extract::<Extension<Session>>().await?;
+///     Ok(session.user)
+/// }
+/// ```
+///
 /// ## Important Notes
 /// - **Server functions must be `async`.** Even if the work being done inside the function body
 ///   can run synchronously on the server, from the client’s perspective it involves an asynchronous
@@ -886,6 +957,38 @@ pub fn slot(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
 ///     - [`ServerFnError`](../server_fn/error/enum.ServerFnError.html) can be generic over some custom error type. If so, that type should implement
 ///       [`FromStr`](std::str::FromStr) and [`Display`](std::fmt::Display), but does not need to implement [`Error`](std::error::Error). This is so the value
 ///       can be easily serialized and deserialized along with the result.
+///     - Alternatively, declare the custom error type with `#[server(error = MyError)]`. It is then sent with the same
+///       encoding as the result rather than as text (so it also needs `Serialize` and `Deserialize`, and the output
+///       encoding must be `Json` or `StreamingJson`), and `impl From<&MyError> for StatusCode` sets the HTTP status
+///       code of the response:
+///       ```rust,ignore
+///       #[derive(Debug, Clone, Serialize, Deserialize, thiserror::Error)]
+///       pub enum ItemError {
+///           #[error("item {id} not found")]
+///           NotFound { id: u32 },
+///       }
+///
+///       impl FromStr for ItemError {
+///           type Err = serde_json::Error;
+///
+///           fn from_str(s: &str) -> Result<Self, Self::Err> {
+///               serde_json::from_str(s)
+///           }
+///       }
+///
+///       impl From<&ItemError> for StatusCode {
+///           fn from(_: &ItemError) -> Self {
+///               StatusCode::NOT_FOUND
+///           }
+///       }
+///
+///       #[server(error = ItemError)]
+///       pub async fn get_item(id: u32) -> Result<Item, ServerFnError<ItemError>> {
+///           Err(ItemError::NotFound { id }.into())
+///       }
+///       ```
+///       On the client, the error is deserialized back into a `ServerFnError<ItemError>`, so an `ErrorBoundary`
+///       fallback can [downcast](../throw_error/struct.Error.html#method.downcast_ref) it.
 /// - **Server functions are part of the public API of your application.** A server function is an
 ///   ad hoc HTTP API endpoint, not a magic formula. Any server function can be accessed by any HTTP
 ///   client. You should take care to sanitize any data being returned from the function to ensure it
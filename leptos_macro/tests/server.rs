@@ -128,4 +128,18 @@ pub mod tests {
             TypeId::of::<codec::PostUrl>()
         );
     }
+
+    #[test]
+    fn server_cache() {
+        #[server(input = codec::GetUrl, cache = "max-age=60")]
+        pub async fn my_server_action() -> Result<(), ServerFnError> {
+            Ok(())
+        }
+        assert_eq!(
+            <MyServerAction as ServerFn>::CACHE_CONTROL,
+            Some("max-age=60")
+        );
+        // the variant that never shares an in-flight request
+        let _ = my_server_action_forced;
+    }
 }
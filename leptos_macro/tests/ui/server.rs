@@ -40,4 +40,15 @@ pub async fn encoding_not_found() -> Result<(), ServerFnError> {
     Ok(())
 }
 
+#[server(output = Cbor, error = MyError)]
+pub async fn error_encoding_not_supported() -> Result<(), ServerFnError<MyError>>
+{
+    Ok(())
+}
+
+#[server(input = Json, cache = "max-age=60")]
+pub async fn cache_not_supported() -> Result<(), ServerFnError> {
+    Ok(())
+}
+
 fn main() {}
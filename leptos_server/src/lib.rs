@@ -13,6 +13,8 @@ pub use once_resource::*;
 mod resource;
 pub use resource::*;
 mod shared;
+mod stream;
+pub use stream::*;
 ////! # Leptos Server Functions
 ////!
 ////! This package is based on a simple idea: sometimes it’s useful to write functions
@@ -183,14 +185,14 @@ impl FromEncodedStr for [u8] {
 
 #[cfg(feature = "tachys")]
 mod view_implementations {
-    use crate::Resource;
+    use crate::{ArcResource, Resource};
     use reactive_graph::traits::Read;
     use std::{future::Future, pin::Pin};
     use tachys::{
         html::attribute::Attribute,
         hydration::Cursor,
         reactive_graph::{RenderEffectState, Suspend, SuspendState},
-        ssr::StreamBuilder,
+        ssr::{BranchMarks, StreamBuilder},
         view::{
             add_attr::AddAnyAttr, Position, PositionState, Render, RenderHtml,
         },
@@ -238,7 +240,11 @@ mod view_implementations {
         where
             Self::Output<NewAttr>: RenderHtml,
         {
-            (move || Suspend::new(async move { self.await })).add_any_attr(attr)
+            let attr = attr.into_cloneable_owned();
+            Box::new(move || {
+                Suspend::new(async move { self.await })
+                    .add_any_attr(attr.clone())
+            })
         }
     }
 
@@ -264,7 +270,7 @@ mod view_implementations {
             buf: &mut String,
             position: &mut Position,
             escape: bool,
-            mark_branches: bool,
+            mark_branches: &BranchMarks,
         ) {
             (move || Suspend::new(async move { self.await })).to_html_with_buf(
                 buf,
@@ -279,7 +285,7 @@ mod view_implementations {
             buf: &mut StreamBuilder,
             position: &mut Position,
             escape: bool,
-            mark_branches: bool,
+            mark_branches: &BranchMarks,
         ) where
             Self: Sized,
         {
@@ -301,4 +307,133 @@ mod view_implementations {
                 .hydrate::<FROM_SERVER>(cursor, position)
         }
     }
+
+    impl<T, Ser> Render for ArcResource<T, Ser>
+    where
+        T: Render + Send + Sync + Clone + 'static,
+        Ser: Send + Sync + 'static,
+    {
+        type State = RenderEffectState<SuspendState<T>>;
+
+        fn build(self) -> Self::State {
+            (move || {
+                let this = self.clone();
+                Suspend::new(async move { this.await })
+            })
+            .build()
+        }
+
+        fn rebuild(self, state: &mut Self::State) {
+            (move || {
+                let this = self.clone();
+                Suspend::new(async move { this.await })
+            })
+            .rebuild(state)
+        }
+    }
+
+    impl<T, Ser> AddAnyAttr for ArcResource<T, Ser>
+    where
+        T: RenderHtml + Send + Sync + Clone + 'static,
+        Ser: Send + Sync + 'static,
+    {
+        type Output<SomeNewAttr: Attribute> = Box<
+            dyn FnMut() -> Suspend<
+                    Pin<
+                        Box<
+                            dyn Future<
+                                    Output = <T as AddAnyAttr>::Output<
+                                        <SomeNewAttr::CloneableOwned as Attribute>::CloneableOwned,
+                                    >,
+                                > + Send,
+                        >,
+                    >,
+                > + Send,
+        >;
+
+        fn add_any_attr<NewAttr: Attribute>(
+            self,
+            attr: NewAttr,
+        ) -> Self::Output<NewAttr>
+        where
+            Self::Output<NewAttr>: RenderHtml,
+        {
+            let attr = attr.into_cloneable_owned();
+            Box::new(move || {
+                let this = self.clone();
+                Suspend::new(async move { this.await })
+                    .add_any_attr(attr.clone())
+            })
+        }
+    }
+
+    impl<T, Ser> RenderHtml for ArcResource<T, Ser>
+    where
+        T: RenderHtml + Send + Sync + Clone + 'static,
+        Ser: Send + Sync + 'static,
+    {
+        type AsyncOutput = T;
+
+        const MIN_LENGTH: usize = 0;
+
+        fn dry_resolve(&mut self) {
+            self.read();
+        }
+
+        async fn resolve(self) -> Self::AsyncOutput {
+            self.await
+        }
+
+        fn to_html_with_buf(
+            self,
+            buf: &mut String,
+            position: &mut Position,
+            escape: bool,
+            mark_branches: &BranchMarks,
+        ) {
+            (move || {
+                let this = self.clone();
+                Suspend::new(async move { this.await })
+            })
+            .to_html_with_buf(
+                buf,
+                position,
+                escape,
+                mark_branches,
+            );
+        }
+
+        fn to_html_async_with_buf<const OUT_OF_ORDER: bool>(
+            self,
+            buf: &mut StreamBuilder,
+            position: &mut Position,
+            escape: bool,
+            mark_branches: &BranchMarks,
+        ) where
+            Self: Sized,
+        {
+            (move || {
+                let this = self.clone();
+                Suspend::new(async move { this.await })
+            })
+            .to_html_async_with_buf::<OUT_OF_ORDER>(
+                buf,
+                position,
+                escape,
+                mark_branches,
+            );
+        }
+
+        fn hydrate<const FROM_SERVER: bool>(
+            self,
+            cursor: &Cursor,
+            position: &PositionState,
+        ) -> Self::State {
+            (move || {
+                let this = self.clone();
+                Suspend::new(async move { this.await })
+            })
+            .hydrate::<FROM_SERVER>(cursor, position)
+        }
+    }
 }
@@ -17,6 +17,14 @@ use std::{
     panic::Location,
 };
 
+/// A resource that only loads its data locally on the client, and is never serialized.
+///
+/// During server rendering, a local resource never resolves. Reading it inside a
+/// [`Suspense`](https://docs.rs/leptos/latest/leptos/fn.Suspense.html) tells that `Suspense` to
+/// render its fallback into the response straight away, and to mark its boundary as incomplete.
+/// When the client hydrates an incomplete boundary, it hydrates the fallback, then loads the
+/// resource and renders the children once it is ready. Reading a local resource outside any
+/// `Suspense` during server rendering panics, because the response would never finish.
 pub struct ArcLocalResource<T> {
     data: ArcAsyncDerived<T>,
     #[cfg(debug_assertions)]
@@ -182,6 +190,9 @@ impl<T> Subscriber for ArcLocalResource<T> {
     }
 }
 
+/// An arena-allocated local resource, which only loads its data locally on the client.
+///
+/// See [`ArcLocalResource`] for how local resources are rendered on the server.
 pub struct LocalResource<T> {
     data: AsyncDerived<SendWrapper<T>>,
     #[cfg(debug_assertions)]
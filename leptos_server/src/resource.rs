@@ -12,8 +12,9 @@ use codee::{
     Decoder, Encoder,
 };
 use core::{fmt::Debug, marker::PhantomData};
-use futures::Future;
+use futures::{future::join, Future};
 use hydration_context::{SerializedDataId, SharedContext};
+use or_poisoned::OrPoisoned;
 use reactive_graph::{
     computed::{
         ArcAsyncDerived, ArcMemo, AsyncDerived, AsyncDerivedFuture,
@@ -26,12 +27,15 @@ use reactive_graph::{
 };
 use std::{
     future::{pending, IntoFuture},
+    mem,
     ops::Deref,
     panic::Location,
+    pin::Pin,
     sync::{
         atomic::{AtomicBool, Ordering},
-        Arc,
+        Arc, Mutex,
     },
+    task::{Context, Poll, Waker},
 };
 
 pub(crate) static IS_SUPPRESSING_RESOURCE_LOAD: AtomicBool =
@@ -58,9 +62,74 @@ impl Drop for SuppressResourceLoad {
     }
 }
 
+/// Tells a resource's fetcher that its result is no longer needed.
+///
+/// A resource stops waiting for a fetch when its source changes or it is refetched before the
+/// fetch has finished, and when the resource itself is dropped. The fetch's `Future` is dropped,
+/// and this signal is aborted, so that work running outside that `Future` (like a network request
+/// made through the browser's `AbortController`) can be canceled as well.
+///
+/// This is a `Future` that is ready once the fetch has been aborted.
+#[derive(Clone, Debug, Default)]
+pub struct ResourceAbortSignal {
+    inner: Arc<AbortInner>,
+}
+
+#[derive(Debug, Default)]
+struct AbortInner {
+    aborted: AtomicBool,
+    wakers: Mutex<Vec<Waker>>,
+}
+
+impl ResourceAbortSignal {
+    /// Creates a signal that is aborted when the current [`Owner`] is cleaned up.
+    fn new() -> Self {
+        let signal = Self::default();
+        Owner::on_cleanup({
+            let signal = signal.clone();
+            move || signal.abort()
+        });
+        signal
+    }
+
+    fn abort(&self) {
+        self.inner.aborted.store(true, Ordering::Relaxed);
+        for waker in mem::take(&mut *self.inner.wakers.lock().or_poisoned()) {
+            waker.wake();
+        }
+    }
+
+    /// Whether the fetch has been aborted.
+    pub fn aborted(&self) -> bool {
+        self.inner.aborted.load(Ordering::Relaxed)
+    }
+}
+
+impl Future for ResourceAbortSignal {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.aborted() {
+            return Poll::Ready(());
+        }
+        self.inner
+            .wakers
+            .lock()
+            .or_poisoned()
+            .push(cx.waker().clone());
+        // check again, in case it was aborted before the waker was registered
+        if self.aborted() {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
 pub struct ArcResource<T, Ser = JsonSerdeCodec> {
     ser: PhantomData<Ser>,
     refetch: ArcRwSignal<usize>,
+    fetching: ArcRwSignal<bool>,
     data: ArcAsyncDerived<T>,
     #[cfg(debug_assertions)]
     defined_at: &'static Location<'static>,
@@ -94,6 +163,7 @@ impl<T, Ser> Clone for ArcResource<T, Ser> {
         Self {
             ser: self.ser,
             refetch: self.refetch.clone(),
+            fetching: self.fetching.clone(),
             data: self.data.clone(),
             #[cfg(debug_assertions)]
             defined_at: self.defined_at,
@@ -165,6 +235,26 @@ where
     pub fn new_with_options<S, Fut>(
         source: impl Fn() -> S + Send + Sync + 'static,
         fetcher: impl Fn(S) -> Fut + Send + Sync + 'static,
+        blocking: bool,
+    ) -> ArcResource<T, Ser>
+    where
+        S: PartialEq + Clone + Send + Sync + 'static,
+        T: Send + Sync + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        ArcResource::new_abortable_with_options(
+            source,
+            move |source, _| fetcher(source),
+            blocking,
+        )
+    }
+
+    /// Creates a resource whose fetcher is given a [`ResourceAbortSignal`], which is aborted if
+    /// the result of that fetch is no longer needed.
+    #[track_caller]
+    pub fn new_abortable_with_options<S, Fut>(
+        source: impl Fn() -> S + Send + Sync + 'static,
+        fetcher: impl Fn(S, ResourceAbortSignal) -> Fut + Send + Sync + 'static,
         #[allow(unused)] // this is used with `feature = "ssr"`
         blocking: bool,
     ) -> ArcResource<T, Ser>
@@ -183,20 +273,30 @@ where
         let is_ready = initial.is_some();
 
         let refetch = ArcRwSignal::new(0);
+        let fetching = ArcRwSignal::new(false);
         let source = ArcMemo::new({
             let refetch = refetch.clone();
             move |_| (refetch.get(), source())
         });
         let fun = {
             let source = source.clone();
+            let fetching = fetching.clone();
             move || {
                 let (_, source) = source.get();
-                let fut = fetcher(source);
+                // this runs in the owner of the async derived, which is cleaned up when the next
+                // fetch starts, or when the resource is dropped
+                let fut = fetcher(source, ResourceAbortSignal::new());
+                if !fetching.get_untracked() {
+                    fetching.set(true);
+                }
+                let fetching = fetching.clone();
                 async move {
                     if IS_SUPPRESSING_RESOURCE_LOAD.load(Ordering::Relaxed) {
                         pending().await
                     } else {
-                        fut.await
+                        let value = fut.await;
+                        fetching.set(false);
+                        value
                     }
                 }
             }
@@ -240,6 +340,7 @@ where
             ser: PhantomData,
             data,
             refetch,
+            fetching,
             #[cfg(debug_assertions)]
             defined_at: Location::caller(),
         }
@@ -257,6 +358,15 @@ where
     pub fn refetch(&self) {
         *self.refetch.write() += 1;
     }
+
+    /// Whether the resource is holding the value of an earlier fetch while a newer one is
+    /// loading. This is reactive, so it can be used to show that the data is out of date.
+    pub fn is_stale(&self) -> bool
+    where
+        T: Send + Sync + 'static,
+    {
+        self.fetching.get() && self.data.with(Option::is_some)
+    }
 }
 
 #[inline(always)]
@@ -359,6 +469,21 @@ where
     {
         ArcResource::new_with_options(source, fetcher, true)
     }
+
+    /// Creates a resource whose fetcher is given a [`ResourceAbortSignal`], which is aborted if
+    /// the result of that fetch is no longer needed.
+    #[track_caller]
+    pub fn new_abortable<S, Fut>(
+        source: impl Fn() -> S + Send + Sync + 'static,
+        fetcher: impl Fn(S, ResourceAbortSignal) -> Fut + Send + Sync + 'static,
+    ) -> Self
+    where
+        S: PartialEq + Clone + Send + Sync + 'static,
+        T: Send + Sync + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        ArcResource::new_abortable_with_options(source, fetcher, false)
+    }
 }
 
 impl<T> ArcResource<T, FromToStringCodec>
@@ -562,6 +687,70 @@ where
     }
 }
 
+impl<T, Ser> ArcResource<T, Ser>
+where
+    T: Send + Sync + 'static,
+{
+    /// Creates an async derived signal that holds `fun` applied to the value of this resource.
+    ///
+    /// Like the resource, it is `None` until the first value has loaded, and reading it inside
+    /// a `Suspense` makes that `Suspense` wait for the resource.
+    #[track_caller]
+    pub fn derive_map<U>(
+        &self,
+        fun: impl Fn(&T) -> U + Send + Sync + 'static,
+    ) -> ArcAsyncDerived<U>
+    where
+        U: Send + Sync + 'static,
+    {
+        let data = self.data.clone();
+        let fun = Arc::new(fun);
+        ArcAsyncDerived::new(move || {
+            let value = data.by_ref();
+            let fun = Arc::clone(&fun);
+            async move { fun(&*value.await) }
+        })
+    }
+
+    /// Creates an async derived signal that holds the values of this resource and `other`, once
+    /// both have loaded.
+    ///
+    /// Reading it inside a `Suspense` makes that `Suspense` wait for both resources.
+    #[track_caller]
+    pub fn zip<U, Ser2>(
+        &self,
+        other: &ArcResource<U, Ser2>,
+    ) -> ArcAsyncDerived<(T, U)>
+    where
+        T: Clone,
+        U: Clone + Send + Sync + 'static,
+    {
+        let (this, other) = (self.data.clone(), other.data.clone());
+        ArcAsyncDerived::new(move || {
+            join(this.clone().into_future(), other.clone().into_future())
+        })
+    }
+}
+
+impl<T, E, Ser> ArcResource<Result<T, E>, Ser>
+where
+    T: Send + Sync + 'static,
+    E: Clone + Send + Sync + 'static,
+{
+    /// Creates an async derived signal that holds `fun` applied to the `Ok` value of this
+    /// resource, or the error if it failed. See [`derive_map`](Self::derive_map).
+    #[track_caller]
+    pub fn derive_and_then<U>(
+        &self,
+        fun: impl Fn(&T) -> U + Send + Sync + 'static,
+    ) -> ArcAsyncDerived<Result<U, E>>
+    where
+        U: Send + Sync + 'static,
+    {
+        self.derive_map(move |data| data.as_ref().map(&fun).map_err(E::clone))
+    }
+}
+
 pub struct Resource<T, Ser = JsonSerdeCodec>
 where
     T: Send + Sync + 'static,
@@ -569,6 +758,7 @@ where
     ser: PhantomData<Ser>,
     data: AsyncDerived<T>,
     refetch: RwSignal<usize>,
+    fetching: RwSignal<bool>,
     #[cfg(debug_assertions)]
     defined_at: &'static Location<'static>,
 }
@@ -736,6 +926,21 @@ where
     {
         Resource::new_with_options(source, fetcher, true)
     }
+
+    /// Creates a resource whose fetcher is given a [`ResourceAbortSignal`], which is aborted if
+    /// the result of that fetch is no longer needed.
+    #[track_caller]
+    pub fn new_abortable<S, Fut>(
+        source: impl Fn() -> S + Send + Sync + 'static,
+        fetcher: impl Fn(S, ResourceAbortSignal) -> Fut + Send + Sync + 'static,
+    ) -> Self
+    where
+        S: PartialEq + Clone + Send + Sync + 'static,
+        T: Send + Sync + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        Resource::new_abortable_with_options(source, fetcher, false)
+    }
 }
 
 #[cfg(feature = "serde-wasm-bindgen")]
@@ -892,12 +1097,38 @@ where
         T: Send + Sync + 'static,
         Fut: Future<Output = T> + Send + 'static,
     {
-        let ArcResource { data, refetch, .. }: ArcResource<T, Ser> =
-            ArcResource::new_with_options(source, fetcher, blocking);
+        Resource::new_abortable_with_options(
+            source,
+            move |source, _| fetcher(source),
+            blocking,
+        )
+    }
+
+    /// Creates a resource whose fetcher is given a [`ResourceAbortSignal`], which is aborted if
+    /// the result of that fetch is no longer needed.
+    #[track_caller]
+    pub fn new_abortable_with_options<S, Fut>(
+        source: impl Fn() -> S + Send + Sync + 'static,
+        fetcher: impl Fn(S, ResourceAbortSignal) -> Fut + Send + Sync + 'static,
+        blocking: bool,
+    ) -> Resource<T, Ser>
+    where
+        S: Send + Sync + Clone + PartialEq + 'static,
+        T: Send + Sync + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        let ArcResource {
+            data,
+            refetch,
+            fetching,
+            ..
+        }: ArcResource<T, Ser> =
+            ArcResource::new_abortable_with_options(source, fetcher, blocking);
         Resource {
             ser: PhantomData,
             data: data.into(),
             refetch: refetch.into(),
+            fetching: fetching.into(),
             #[cfg(debug_assertions)]
             defined_at: Location::caller(),
         }
@@ -913,6 +1144,12 @@ where
     pub fn refetch(&self) {
         self.refetch.try_update(|n| *n += 1);
     }
+
+    /// Whether the resource is holding the value of an earlier fetch while a newer one is
+    /// loading. This is reactive, so it can be used to show that the data is out of date.
+    pub fn is_stale(&self) -> bool {
+        self.fetching.get() && self.data.with(Option::is_some)
+    }
 }
 
 impl<T, E, Ser> Resource<Result<T, E>, Ser>
@@ -953,4 +1190,61 @@ where
     pub fn by_ref(&self) -> AsyncDerivedRefFuture<T> {
         self.data.by_ref()
     }
+
+    /// Creates an async derived signal that holds `fun` applied to the value of this resource.
+    ///
+    /// Like the resource, it is `None` until the first value has loaded, and reading it inside
+    /// a `Suspense` makes that `Suspense` wait for the resource.
+    #[track_caller]
+    pub fn derive_map<U>(
+        &self,
+        fun: impl Fn(&T) -> U + Send + Sync + 'static,
+    ) -> AsyncDerived<U>
+    where
+        U: Send + Sync + 'static,
+    {
+        let data = self.data;
+        let fun = Arc::new(fun);
+        AsyncDerived::new(move || {
+            let value = data.by_ref();
+            let fun = Arc::clone(&fun);
+            async move { fun(&*value.await) }
+        })
+    }
+
+    /// Creates an async derived signal that holds the values of this resource and `other`, once
+    /// both have loaded.
+    ///
+    /// Reading it inside a `Suspense` makes that `Suspense` wait for both resources.
+    #[track_caller]
+    pub fn zip<U, Ser2>(
+        &self,
+        other: &Resource<U, Ser2>,
+    ) -> AsyncDerived<(T, U)>
+    where
+        T: Clone,
+        U: Clone + Send + Sync + 'static,
+    {
+        let (this, other) = (self.data, other.data);
+        AsyncDerived::new(move || join(this.into_future(), other.into_future()))
+    }
+}
+
+impl<T, E, Ser> Resource<Result<T, E>, Ser>
+where
+    T: Send + Sync + 'static,
+    E: Clone + Send + Sync + 'static,
+{
+    /// Creates an async derived signal that holds `fun` applied to the `Ok` value of this
+    /// resource, or the error if it failed. See [`derive_map`](Self::derive_map).
+    #[track_caller]
+    pub fn derive_and_then<U>(
+        &self,
+        fun: impl Fn(&T) -> U + Send + Sync + 'static,
+    ) -> AsyncDerived<Result<U, E>>
+    where
+        U: Send + Sync + 'static,
+    {
+        self.derive_map(move |data| data.as_ref().map(&fun).map_err(E::clone))
+    }
 }
@@ -0,0 +1,42 @@
+use futures::{
+    stream::{AbortHandle, Abortable},
+    Stream, StreamExt,
+};
+use reactive_graph::{
+    owner::Owner,
+    signal::{arc_signal, ReadSignal},
+    traits::Set,
+};
+
+/// Reads the latest item of a [`Stream`] as a signal.
+///
+/// This is useful for the [`EventStream`](server_fn::codec::EventStream) returned by a server
+/// function with the [`StreamingEvents`](server_fn::codec::StreamingEvents) output encoding.
+pub trait StreamSignalExt: Stream + Sized {
+    /// Creates a signal that holds the most recent item of the stream, or `None` before the
+    /// first item arrives.
+    ///
+    /// The stream is dropped when the current reactive owner is cleaned up. For a server
+    /// function's stream, this cancels the request, which stops the stream on the server.
+    #[track_caller]
+    fn into_signal(self) -> ReadSignal<Option<Self::Item>>;
+}
+
+impl<S> StreamSignalExt for S
+where
+    S: Stream + Send + 'static,
+    S::Item: Send + Sync + 'static,
+{
+    fn into_signal(self) -> ReadSignal<Option<Self::Item>> {
+        let (read, write) = arc_signal(None);
+        let (abort, registration) = AbortHandle::new_pair();
+        Owner::on_cleanup(move || abort.abort());
+        let mut stream = Box::pin(Abortable::new(self, registration));
+        any_spawner::Executor::spawn(async move {
+            while let Some(item) = stream.next().await {
+                write.set(Some(item));
+            }
+        });
+        read.into()
+    }
+}
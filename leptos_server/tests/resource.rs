@@ -0,0 +1,116 @@
+use any_spawner::Executor;
+use futures::{channel::oneshot, FutureExt};
+use leptos_server::{ArcResource, ResourceAbortSignal};
+use reactive_graph::{owner::Owner, prelude::*, signal::RwSignal};
+use std::sync::{Arc, Mutex};
+
+type Fetches = Arc<Mutex<Vec<(oneshot::Sender<i32>, ResourceAbortSignal)>>>;
+
+fn controlled_resource(source: RwSignal<i32>) -> (ArcResource<i32>, Fetches) {
+    let fetches = Fetches::default();
+    let resource = ArcResource::new_abortable(move || source.get(), {
+        let fetches = Arc::clone(&fetches);
+        move |_, abort| {
+            let (tx, rx) = oneshot::channel();
+            fetches.lock().unwrap().push((tx, abort));
+            async move { rx.await.unwrap_or_default() }
+        }
+    });
+    (resource, fetches)
+}
+
+fn resolve(fetches: &Fetches, index: usize, value: i32) {
+    let (tx, _) = fetches.lock().unwrap().remove(index);
+    _ = tx.send(value);
+    Executor::flush();
+}
+
+#[test]
+fn older_fetch_does_not_overwrite_newer_one() {
+    _ = Executor::init_manual();
+    let owner = Owner::new();
+    owner.set();
+
+    let source = RwSignal::new(1);
+    let (resource, fetches) = controlled_resource(source);
+    Executor::flush();
+    assert_eq!(fetches.lock().unwrap().len(), 1);
+
+    source.set(2);
+    Executor::flush();
+    assert_eq!(fetches.lock().unwrap().len(), 2);
+    assert!(fetches.lock().unwrap()[0].1.aborted());
+    assert!(!fetches.lock().unwrap()[1].1.aborted());
+
+    // fetch #2 finishes first, then fetch #1
+    resolve(&fetches, 1, 20);
+    resolve(&fetches, 0, 10);
+
+    assert_eq!(resource.get_untracked(), Some(20));
+    assert!(!resource.is_stale());
+    assert!(resource.ready().now_or_never().is_some());
+}
+
+#[test]
+fn resource_is_stale_while_refetching() {
+    _ = Executor::init_manual();
+    let owner = Owner::new();
+    owner.set();
+
+    let source = RwSignal::new(1);
+    let (resource, fetches) = controlled_resource(source);
+    Executor::flush();
+    // nothing has loaded yet, so there is nothing out of date
+    assert!(!resource.is_stale());
+
+    resolve(&fetches, 0, 10);
+    assert!(!resource.is_stale());
+
+    resource.refetch();
+    Executor::flush();
+    assert!(resource.is_stale());
+    assert_eq!(resource.get_untracked(), Some(10));
+
+    resolve(&fetches, 0, 11);
+    assert!(!resource.is_stale());
+    assert_eq!(resource.get_untracked(), Some(11));
+}
+
+#[test]
+fn derive_map_follows_resource() {
+    _ = Executor::init_manual();
+    let owner = Owner::new();
+    owner.set();
+
+    let source = RwSignal::new(1);
+    let (resource, fetches) = controlled_resource(source);
+    let doubled = resource.derive_map(|n| n * 2);
+    Executor::flush();
+    assert_eq!(doubled.get_untracked(), None);
+
+    resolve(&fetches, 0, 10);
+    assert_eq!(doubled.get_untracked(), Some(20));
+
+    source.set(2);
+    Executor::flush();
+    resolve(&fetches, 0, 11);
+    assert_eq!(doubled.get_untracked(), Some(22));
+}
+
+#[test]
+fn zip_is_ready_once_both_resources_are() {
+    _ = Executor::init_manual();
+    let owner = Owner::new();
+    owner.set();
+
+    let (a, a_fetches) = controlled_resource(RwSignal::new(1));
+    let (b, b_fetches) = controlled_resource(RwSignal::new(2));
+    let both = a.zip(&b);
+    Executor::flush();
+
+    resolve(&b_fetches, 0, 20);
+    assert_eq!(both.get_untracked(), None);
+
+    resolve(&a_fetches, 0, 10);
+    assert_eq!(both.get_untracked(), Some((10, 20)));
+}
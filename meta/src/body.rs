@@ -7,6 +7,7 @@ use leptos::{
         dom::document,
         html::attribute::Attribute,
         hydration::Cursor,
+        ssr::BranchMarks,
         view::{
             add_attr::AddAnyAttr, Mountable, Position, PositionState, Render,
             RenderHtml,
@@ -121,7 +122,7 @@ where
         _buf: &mut String,
         _position: &mut Position,
         _escape: bool,
-        _mark_branches: bool,
+        _mark_branches: &BranchMarks,
     ) {
         if let Some(meta) = use_context::<ServerMetaContext>() {
             let mut buf = String::new();
@@ -60,6 +60,7 @@ use leptos::{
             element::{ElementType, HtmlElement},
         },
         hydration::Cursor,
+        ssr::BranchMarks,
         view::{
             add_attr::AddAnyAttr, Mountable, Position, PositionState, Render,
             RenderHtml,
@@ -337,7 +338,7 @@ where
             &mut buf,
             &mut Position::NextChild,
             false,
-            false,
+            &BranchMarks::NONE,
         );
         _ = cx.elements.send(buf); // fails only if the receiver is already dropped
     } else {
@@ -449,7 +450,7 @@ where
         _buf: &mut String,
         _position: &mut Position,
         _escape: bool,
-        _mark_branches: bool,
+        _mark_branches: &BranchMarks,
     ) {
         // meta tags are rendered into the buffer stored into the context
         // the value has already been taken out, when we're on the server
@@ -559,7 +560,7 @@ impl RenderHtml for MetaTagsView {
         buf: &mut String,
         _position: &mut Position,
         _escape: bool,
-        _mark_branches: bool,
+        _mark_branches: &BranchMarks,
     ) {
         buf.push_str("<!--HEAD-->");
     }
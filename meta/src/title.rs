@@ -10,6 +10,7 @@ use leptos::{
     tachys::{
         dom::document,
         hydration::Cursor,
+        ssr::BranchMarks,
         view::{
             add_attr::AddAnyAttr, Mountable, Position, PositionState, Render,
             RenderHtml,
@@ -248,7 +249,7 @@ impl RenderHtml for TitleView {
         _buf: &mut String,
         _position: &mut Position,
         _escape: bool,
-        _mark_branches: bool,
+        _mark_branches: &BranchMarks,
     ) {
         // meta tags are rendered into the buffer stored into the context
         // the value has already been taken out, when we're on the server
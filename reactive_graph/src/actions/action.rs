@@ -2,17 +2,22 @@ use crate::{
     computed::{ArcMemo, Memo},
     diagnostics::is_suppressing_resource_load,
     owner::{
-        ArcStoredValue, ArenaItem, FromLocal, LocalStorage, Storage,
+        ArcStoredValue, ArenaItem, FromLocal, LocalStorage, Owner, Storage,
         SyncStorage,
     },
     signal::{ArcRwSignal, RwSignal},
     traits::{DefinedAt, Dispose, Get, GetUntracked, GetValue, Update},
     unwrap_signal,
 };
-use any_spawner::Executor;
-use futures::{channel::oneshot, select, FutureExt};
+use futures::{channel::oneshot, future::FusedFuture, select, FutureExt};
+use or_poisoned::OrPoisoned;
 use send_wrapper::SendWrapper;
-use std::{future::Future, panic::Location, pin::Pin, sync::Arc};
+use std::{
+    future::Future,
+    panic::Location,
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
 
 /// An action runs some asynchronous code when you dispatch a new value to it, and gives you
 /// reactive access to the result.
@@ -97,6 +102,8 @@ pub struct ArcAction<I, O> {
     value: ArcRwSignal<Option<O>>,
     version: ArcRwSignal<usize>,
     dispatched: ArcStoredValue<usize>,
+    queued: ArcRwSignal<usize>,
+    queue: Option<Arc<SerialQueue>>,
     #[allow(clippy::complexity)]
     action_fn: Arc<
         dyn Fn(&I) -> Pin<Box<dyn Future<Output = O> + Send>> + Send + Sync,
@@ -113,6 +120,8 @@ impl<I, O> Clone for ArcAction<I, O> {
             value: self.value.clone(),
             version: self.version.clone(),
             dispatched: self.dispatched.clone(),
+            queued: self.queued.clone(),
+            queue: self.queue.clone(),
             action_fn: self.action_fn.clone(),
             #[cfg(debug_assertions)]
             defined_at: self.defined_at,
@@ -197,11 +206,175 @@ where
             value: ArcRwSignal::new(value),
             version: Default::default(),
             dispatched: Default::default(),
+            queued: Default::default(),
+            queue: None,
             action_fn: Arc::new(move |input| Box::pin(action_fn(input))),
             #[cfg(debug_assertions)]
             defined_at: Location::caller(),
         }
     }
+
+    /// Creates a new action that runs one dispatch at a time, in the order they were dispatched.
+    ///
+    /// When the action is dispatched while an earlier dispatch is still pending, the new input is
+    /// queued, and `action_fn` is not called for it until every earlier dispatch has finished or
+    /// been aborted. This means that [`value`](Self::value) always holds the result of the most
+    /// recent dispatch to have completed, in dispatch order, even if a later request would have
+    /// resolved sooner. [`input`](Self::input) holds the input that is currently running.
+    ///
+    /// Queued dispatches are canceled, without running, once every handle to the action has been
+    /// dropped (or, for an [`Action`], once it has been disposed).
+    ///
+    /// ```rust
+    /// # use reactive_graph::actions::*;
+    /// # use reactive_graph::prelude::*;
+    /// # tokio_test::block_on(async move {
+    /// # any_spawner::Executor::init_tokio(); let owner = reactive_graph::owner::Owner::new(); owner.set();
+    /// # let _guard = reactive_graph::diagnostics::SpecialNonReactiveZone::enter();
+    /// let save = ArcAction::new_serial(|n: &u8| {
+    ///     let n = n.to_owned();
+    ///     async move { n * 2 }
+    /// });
+    ///
+    /// save.dispatch(1);
+    /// save.dispatch(2);
+    /// save.dispatch(3);
+    /// assert_eq!(save.pending_count().get(), 3);
+    /// assert_eq!(save.queue_len().get(), 2);
+    ///
+    /// # tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    /// // after they have all resolved
+    /// assert_eq!(save.value().get(), Some(6));
+    /// assert_eq!(save.version().get(), 3);
+    /// # });
+    /// ```
+    #[track_caller]
+    pub fn new_serial<F, Fu>(action_fn: F) -> Self
+    where
+        F: Fn(&I) -> Fu + Send + Sync + 'static,
+        Fu: Future<Output = O> + Send + 'static,
+    {
+        Self {
+            queue: Some(Default::default()),
+            ..Self::new(action_fn)
+        }
+    }
+
+    /// Runs a dispatch to a serial action once every earlier dispatch has finished.
+    fn serial_task(
+        &self,
+        queue: &Arc<SerialQueue>,
+        input: I,
+        mut abort_rx: oneshot::Receiver<()>,
+    ) -> impl Future<Output = ()> + 'static {
+        // Update the state before waiting for our turn
+        self.in_flight.update(|n| *n += 1);
+        let (turn, done) = queue.enqueue();
+        if turn.is_some() {
+            self.queued.update(|n| *n += 1);
+        }
+
+        let owner = Owner::current();
+        let action_fn = Arc::clone(&self.action_fn);
+        let input_signal = self.input.clone();
+        let version = self.version.clone();
+        let value = self.value.clone();
+        let in_flight = self.in_flight.clone();
+        let queued = self.queued.clone();
+        async move {
+            // dropping this lets the next dispatch in the queue start
+            let _done = done;
+
+            let runs = match turn {
+                None => true,
+                Some(mut turn) => {
+                    let runs = select! {
+                        _ = abort_rx => None,
+                        runs = turn => Some(runs),
+                    };
+                    queued.update(|n| *n = n.saturating_sub(1));
+                    // an aborted dispatch still holds its place in the queue, so that the ones
+                    // after it don't start until the ones before it have finished
+                    match runs {
+                        Some(runs) => runs,
+                        None => {
+                            turn.await;
+                            false
+                        }
+                    }
+                }
+            };
+
+            if runs {
+                // the action function is only called once it is this input's turn, in the
+                // context of the owner that dispatched it
+                let mut fut = match &owner {
+                    Some(owner) => owner.with(|| action_fn(&input)),
+                    None => action_fn(&input),
+                }
+                .fuse();
+                input_signal.try_update(|inp| *inp = Some(input));
+
+                select! {
+                    // if the abort message has been sent, bail and do nothing
+                    _ = abort_rx => {},
+                    // otherwise, update the value
+                    result = fut => {
+                        version.update(|n| *n += 1);
+                        value.update(|n| *n = Some(result));
+                    }
+                }
+            }
+
+            in_flight.update(|n| *n = n.saturating_sub(1));
+            if in_flight.get_untracked() == 0 {
+                input_signal.update(|inp| *inp = None);
+            }
+        }
+    }
+}
+
+/// The queue of dispatches to an action created with [`ArcAction::new_serial`].
+#[derive(Debug, Default)]
+struct SerialQueue {
+    // resolves once the most recent dispatch has finished
+    last: Mutex<Option<oneshot::Receiver<()>>>,
+}
+
+impl SerialQueue {
+    /// Adds a dispatch to the end of the queue.
+    ///
+    /// If an earlier dispatch is still running, returns a future that resolves once every
+    /// earlier dispatch has finished, to `true` if the action still exists. Also returns a sender
+    /// that should be dropped once this dispatch has finished.
+    fn enqueue(
+        self: &Arc<Self>,
+    ) -> (
+        Option<impl FusedFuture<Output = bool> + Unpin>,
+        oneshot::Sender<()>,
+    ) {
+        let (done_tx, done_rx) = oneshot::channel();
+        let previous = self
+            .last
+            .lock()
+            .or_poisoned()
+            .replace(done_rx)
+            // the sender is dropped once that dispatch has finished
+            .and_then(|mut previous| {
+                matches!(previous.try_recv(), Ok(None)).then_some(previous)
+            });
+        let queue = Arc::downgrade(self);
+        let turn = previous.map(|previous| {
+            Box::pin(
+                async move {
+                    _ = previous.await;
+                    queue.upgrade().is_some()
+                }
+                .fuse(),
+            )
+        });
+        (turn, done_tx)
+    }
 }
 
 /// A handle that allows aborting an in-flight action. It is returned from [`Action::dispatch`] or
@@ -231,7 +404,11 @@ where
     #[track_caller]
     pub fn dispatch(&self, input: I) -> ActionAbortHandle {
         let (abort_tx, mut abort_rx) = oneshot::channel();
-        if !is_suppressing_resource_load() {
+        if let Some(queue) = &self.queue {
+            if !is_suppressing_resource_load() {
+                crate::spawn(self.serial_task(queue, input, abort_rx));
+            }
+        } else if !is_suppressing_resource_load() {
             let mut fut = (self.action_fn)(&input).fuse();
 
             // Update the state before loading
@@ -283,7 +460,11 @@ where
     #[track_caller]
     pub fn dispatch_local(&self, input: I) -> ActionAbortHandle {
         let (abort_tx, mut abort_rx) = oneshot::channel();
-        if !is_suppressing_resource_load() {
+        if let Some(queue) = &self.queue {
+            if !is_suppressing_resource_load() {
+                crate::spawn_local(self.serial_task(queue, input, abort_rx));
+            }
+        } else if !is_suppressing_resource_load() {
             let mut fut = (self.action_fn)(&input).fuse();
 
             // Update the state before loading
@@ -292,7 +473,7 @@ where
             self.input.try_update(|inp| *inp = Some(input));
 
             // Spawn the task
-            Executor::spawn_local({
+            crate::spawn_local({
                 let input = self.input.clone();
                 let version = self.version.clone();
                 let value = self.value.clone();
@@ -358,6 +539,8 @@ where
             value: ArcRwSignal::new(value),
             version: Default::default(),
             dispatched: Default::default(),
+            queued: Default::default(),
+            queue: None,
             action_fn: Arc::new(move |input| {
                 Box::pin(SendWrapper::new(action_fn(input)))
             }),
@@ -485,6 +668,22 @@ impl<I, O> ArcAction<I, O> {
         let in_flight = self.in_flight.clone();
         ArcMemo::new(move |_| in_flight.get() > 0)
     }
+
+    /// The number of dispatches that are currently pending, including any that are queued.
+    #[track_caller]
+    pub fn pending_count(&self) -> ArcMemo<usize> {
+        let in_flight = self.in_flight.clone();
+        ArcMemo::new(move |_| in_flight.get())
+    }
+
+    /// The number of dispatches that are waiting for an earlier dispatch to finish before they
+    /// start. This is always `0` unless the action was created with
+    /// [`new_serial`](ArcAction::new_serial).
+    #[track_caller]
+    pub fn queue_len(&self) -> ArcMemo<usize> {
+        let queued = self.queued.clone();
+        ArcMemo::new(move |_| queued.get())
+    }
 }
 
 impl<I, O> DefinedAt for ArcAction<I, O>
@@ -676,6 +875,23 @@ where
             defined_at: Location::caller(),
         }
     }
+
+    /// Creates a new action that runs one dispatch at a time, in the order they were dispatched.
+    ///
+    /// Queued dispatches are canceled, without running, when the action is disposed. See
+    /// [`ArcAction::new_serial`] for details.
+    #[track_caller]
+    pub fn new_serial<F, Fu>(action_fn: F) -> Self
+    where
+        F: Fn(&I) -> Fu + Send + Sync + 'static,
+        Fu: Future<Output = O> + Send + 'static,
+    {
+        Self {
+            inner: ArenaItem::new(ArcAction::new_serial(action_fn)),
+            #[cfg(debug_assertions)]
+            defined_at: Location::caller(),
+        }
+    }
 }
 
 impl<I, O> Action<I, O, LocalStorage>
@@ -784,6 +1000,28 @@ where
             .unwrap_or_else(unwrap_signal!(self));
         inner.into()
     }
+
+    /// The number of dispatches that are currently pending, including any that are queued.
+    #[track_caller]
+    pub fn pending_count(&self) -> Memo<usize> {
+        let inner = self
+            .inner
+            .try_with_value(|inner| inner.pending_count())
+            .unwrap_or_else(unwrap_signal!(self));
+        inner.into()
+    }
+
+    /// The number of dispatches that are waiting for an earlier dispatch to finish before they
+    /// start. This is always `0` unless the action was created with
+    /// [`new_serial`](Action::new_serial).
+    #[track_caller]
+    pub fn queue_len(&self) -> Memo<usize> {
+        let inner = self
+            .inner
+            .try_with_value(|inner| inner.queue_len())
+            .unwrap_or_else(unwrap_signal!(self));
+        inner.into()
+    }
 }
 
 impl<I, O, S> Action<I, O, S>
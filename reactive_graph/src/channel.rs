@@ -1,6 +1,7 @@
 use core::sync::atomic::Ordering::Relaxed;
 use futures::{task::AtomicWaker, Stream};
 use std::{
+    cell::{Cell, RefCell},
     fmt::Debug,
     hash::Hash,
     pin::Pin,
@@ -51,10 +52,43 @@ pub fn channel() -> (Sender, Receiver) {
 impl Sender {
     pub fn notify(&mut self) {
         self.0.set.store(true, Relaxed);
-        self.0.waker.wake();
+        if BATCH_DEPTH.get() > 0 {
+            // waking a receiver twice is harmless, so this doesn't check for duplicates
+            PENDING
+                .with_borrow_mut(|pending| pending.push(Arc::clone(&self.0)));
+        } else {
+            self.0.waker.wake();
+        }
     }
 }
 
+thread_local! {
+    static BATCH_DEPTH: Cell<usize> = const { Cell::new(0) };
+    // receivers that have been notified during the current batch, but not yet woken
+    static PENDING: RefCell<Vec<Arc<Inner>>> = const { RefCell::new(Vec::new()) };
+}
+
+struct BatchGuard;
+
+impl Drop for BatchGuard {
+    // this also runs if the batch unwinds, so that effects aren't left waiting
+    fn drop(&mut self) {
+        let depth = BATCH_DEPTH.get() - 1;
+        BATCH_DEPTH.set(depth);
+        if depth == 0 {
+            for inner in PENDING.take() {
+                inner.waker.wake();
+            }
+        }
+    }
+}
+
+pub(crate) fn batch<T>(fun: impl FnOnce() -> T) -> T {
+    BATCH_DEPTH.set(BATCH_DEPTH.get() + 1);
+    let _guard = BatchGuard;
+    fun()
+}
+
 impl Stream for Receiver {
     type Item = ();
 
@@ -7,7 +7,6 @@ mod memo;
 mod selector;
 use crate::{
     prelude::*,
-    signal::RwSignal,
     wrappers::{
         read::Signal,
         write::{IntoSignalSetter, SignalSetter},
@@ -19,7 +18,8 @@ pub(crate) use inner::MemoInner;
 pub use memo::*;
 pub use selector::*;
 
-/// Derives a reactive slice of an [`RwSignal`](crate::signal::RwSignal).
+/// Derives a reactive slice of an [`RwSignal`](crate::signal::RwSignal), or of any other
+/// signal that can be both read and updated, like a field of a store.
 ///
 /// Slices have the same guarantees as [`Memo`s](crate::computed::Memo):
 /// they only emit their value when it has actually been changed.
@@ -85,9 +85,12 @@ pub use selector::*;
 /// // setting name only causes name to log, not count
 /// set_name.set("Bob".into());
 /// ```
+///
+/// The slice is a [`Model`](crate::wrappers::write::Model), so it can be passed to anything that takes a readable and a
+/// writable half, like a two-way `bind:value` binding.
 #[track_caller]
 pub fn create_slice<T, O, S>(
-    signal: RwSignal<T>,
+    signal: impl With<Value = T> + Update<Value = T> + Copy + Send + Sync + 'static,
     getter: impl Fn(&T) -> O + Copy + Send + Sync + 'static,
     setter: impl Fn(&mut T, S) + Copy + Send + Sync + 'static,
 ) -> (Signal<O>, SignalSetter<S>)
@@ -105,7 +108,7 @@ where
 /// read-only half of [`create_slice`].
 #[track_caller]
 pub fn create_read_slice<T, O>(
-    signal: RwSignal<T>,
+    signal: impl With<Value = T> + Copy + Send + Sync + 'static,
     getter: impl Fn(&T) -> O + Copy + Send + Sync + 'static,
 ) -> Signal<O>
 where
@@ -119,7 +122,7 @@ where
 /// write-only half of [`create_slice`].
 #[track_caller]
 pub fn create_write_slice<T, O>(
-    signal: RwSignal<T>,
+    signal: impl Update<Value = T> + Copy + Send + Sync + 'static,
     setter: impl Fn(&mut T, O) + Copy + Send + Sync + 'static,
 ) -> SignalSetter<O>
 where
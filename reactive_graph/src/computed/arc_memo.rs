@@ -131,7 +131,7 @@ where
     )]
     pub fn new_with_compare(
         fun: impl Fn(Option<&T>) -> T + Send + Sync + 'static,
-        changed: fn(Option<&T>, Option<&T>) -> bool,
+        changed: impl Fn(Option<&T>, Option<&T>) -> bool + Send + Sync + 'static,
     ) -> Self {
         Self::new_owning(move |prev: Option<T>| {
             let new_value = fun(prev.as_ref());
@@ -163,6 +163,12 @@ where
 
             RwLock::new(MemoInner::new(Arc::new(fun), subscriber))
         });
+        #[cfg(feature = "graph-introspection")]
+        crate::introspect::register(
+            &inner,
+            crate::introspect::NodeKind::Memo,
+            Location::caller(),
+        );
         Self {
             #[cfg(debug_assertions)]
             defined_at: Location::caller(),
@@ -26,7 +26,11 @@ use crate::{
 use any_spawner::Executor;
 use async_lock::RwLock as AsyncRwLock;
 use core::fmt::Debug;
-use futures::{channel::oneshot, FutureExt, StreamExt};
+use futures::{
+    channel::oneshot,
+    future::{select, Either},
+    FutureExt, StreamExt,
+};
 use or_poisoned::OrPoisoned;
 use send_wrapper::SendWrapper;
 use std::{
@@ -306,15 +310,20 @@ macro_rules! spawn_derived {
                 let wakers = Arc::downgrade(&this.wakers);
                 let loading = Arc::downgrade(&this.loading);
                 let fut = async move {
-                    while rx.next().await.is_some() {
-                        let update_if_necessary = if $should_track {
-                            any_subscriber
-                                .with_observer(|| any_subscriber.update_if_necessary())
-                        } else {
-                            any_subscriber
-                                .with_observer_untracked(|| any_subscriber.update_if_necessary())
-                        };
-                        if update_if_necessary || first_run.is_some() {
+                    let update_if_necessary = || if $should_track {
+                        any_subscriber
+                            .with_observer(|| any_subscriber.update_if_necessary())
+                    } else {
+                        any_subscriber
+                            .with_observer_untracked(|| any_subscriber.update_if_necessary())
+                    };
+                    // set when a source changes while the previous Future is still running
+                    let mut rerun = false;
+                    // suspenses that were waiting on a Future that was canceled wait on the next one
+                    let mut suspense_ids;
+                    while rerun || rx.next().await.is_some() {
+                        rerun = false;
+                        if update_if_necessary() || first_run.is_some() {
                             match (value.upgrade(), inner.upgrade(), wakers.upgrade(), loading.upgrade()) {
                                 (Some(value), Some(inner), Some(wakers), Some(loading)) => {
                                     // generate new Future
@@ -346,20 +355,47 @@ macro_rules! spawn_derived {
                                     // generate and assign new value
                                     loading.store(true, Ordering::Relaxed);
 
-                                    let (this_version, suspense_ids) = {
+                                    let this_version = {
                                         let mut guard = inner.write().or_poisoned();
                                         guard.version += 1;
-                                        let version = guard.version;
-                                        let suspense_ids = mem::take(&mut guard.suspenses)
-                                            .into_iter()
-                                            .map(|sc| sc.task_id())
+                                        // the new tasks are registered before the previous
+                                        // Future's are dropped, so a boundary never looks ready
+                                        // in between
+                                        suspense_ids = guard
+                                            .live_suspenses()
+                                            .iter()
+                                            .map(SuspenseContext::task_id)
                                             .collect::<Vec<_>>();
-                                        (version, suspense_ids)
+                                        guard.version
+                                    };
+
+                                    // if a source changes before the Future is ready, it is dropped
+                                    // and a new one is started, so an older Future can never
+                                    // overwrite the value of a newer one
+                                    let mut fut = fut;
+                                    let new_value = loop {
+                                        match select(fut, rx.next()).await {
+                                            Either::Left((new_value, _)) => break Some(new_value),
+                                            Either::Right((Some(()), pending)) => {
+                                                if update_if_necessary() {
+                                                    rerun = true;
+                                                    break None;
+                                                }
+                                                fut = pending;
+                                            }
+                                            Either::Right((None, _)) => break None,
+                                        }
                                     };
 
-                                    let new_value = fut.await;
+                                    let Some(new_value) = new_value else {
+                                        if !rerun {
+                                            break;
+                                        }
+                                        first_run = Some(ready_tx);
+                                        continue;
+                                    };
 
-                                    drop(suspense_ids);
+                                    suspense_ids.clear();
 
                                     let latest_version = inner.read().or_poisoned().version;
 
@@ -521,7 +557,7 @@ impl<T: 'static> ArcAsyncDerived<T> {
         Fut: Future<Output = T> + 'static,
     {
         let (this, _) = spawn_derived!(
-            Executor::spawn_local,
+            crate::spawn_local,
             initial_value,
             fun,
             true,
@@ -559,7 +595,7 @@ impl<T: 'static> ArcAsyncDerived<SendWrapper<T>> {
             }
         };
         let (this, _) = spawn_derived!(
-            Executor::spawn_local,
+            crate::spawn_local,
             initial,
             fun,
             false,
@@ -586,8 +622,7 @@ impl<T: 'static> ReadUntracked for ArcAsyncDerived<T> {
                 self.inner
                     .write()
                     .or_poisoned()
-                    .suspenses
-                    .push(suspense_context);
+                    .add_suspense(&suspense_context);
             }
         }
         AsyncPlain::try_new(&self.value).map(ReadGuard::new)
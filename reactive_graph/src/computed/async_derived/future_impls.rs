@@ -130,8 +130,7 @@ where
             self.inner
                 .write()
                 .or_poisoned()
-                .suspenses
-                .push(suspense_context);
+                .add_suspense(&suspense_context);
         }
 
         pin_mut!(value);
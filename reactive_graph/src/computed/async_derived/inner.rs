@@ -1,6 +1,6 @@
 use crate::{
     channel::Sender,
-    computed::suspense::SuspenseContext,
+    computed::suspense::{SuspenseContext, WeakSuspenseContext},
     graph::{
         AnySource, AnySubscriber, ReactiveNode, Source, SourceSet, Subscriber,
         SubscriberSet,
@@ -21,7 +21,36 @@ pub(crate) struct ArcAsyncDerivedInner {
     pub notifier: Sender,
     pub state: AsyncDerivedState,
     pub version: usize,
-    pub suspenses: Vec<SuspenseContext>,
+    // every suspense boundary that has waited on this, so that it waits again when this reloads;
+    // they are held weakly, so that boundaries that have been dropped are not kept alive
+    pub suspenses: Vec<WeakSuspenseContext>,
+}
+
+impl ArcAsyncDerivedInner {
+    pub fn add_suspense(&mut self, suspense_context: &SuspenseContext) {
+        self.suspenses
+            .retain(|existing| existing.upgrade().is_some());
+        if !self
+            .suspenses
+            .iter()
+            .any(|existing| existing.is(suspense_context))
+        {
+            self.suspenses.push(suspense_context.downgrade());
+        }
+    }
+
+    /// The boundaries that have waited on this and still exist. Dropped boundaries are forgotten.
+    pub fn live_suspenses(&mut self) -> Vec<SuspenseContext> {
+        let mut live = Vec::with_capacity(self.suspenses.len());
+        self.suspenses.retain(|suspense| match suspense.upgrade() {
+            Some(suspense) => {
+                live.push(suspense);
+                true
+            }
+            None => false,
+        });
+        live
+    }
 }
 
 #[derive(Debug, PartialEq, Eq)]
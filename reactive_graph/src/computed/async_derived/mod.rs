@@ -57,13 +57,17 @@ impl<Fut: Future> Future for ScopedFuture<Fut> {
 /// Utilities used to track whether asynchronous computeds are currently loading.
 pub mod suspense {
     use crate::{
+        graph::SubscriberSet,
         signal::ArcRwSignal,
         traits::{Update, Write},
     };
     use futures::channel::oneshot::Sender;
     use or_poisoned::OrPoisoned;
     use slotmap::{DefaultKey, SlotMap};
-    use std::sync::{Arc, Mutex};
+    use std::{
+        panic::Location,
+        sync::{Arc, Mutex, RwLock, Weak},
+    };
 
     /// Sends a one-time notification that the resource being read from is "local only," i.e.,
     /// that it will only run on the client, not the server.
@@ -71,10 +75,12 @@ pub mod suspense {
     pub struct LocalResourceNotifier(Arc<Mutex<Option<Sender<()>>>>);
 
     impl LocalResourceNotifier {
-        /// Send the notification. If the inner channel has already been used, this does nothing.
+        /// Send the notification. If the inner channel has already been used, or nothing is
+        /// listening any more (for example, because the `Suspense` has already been rendered),
+        /// this does nothing.
         pub fn notify(&mut self) {
             if let Some(tx) = self.0.lock().or_poisoned().take() {
-                tx.send(()).unwrap();
+                _ = tx.send(());
             }
         }
     }
@@ -101,6 +107,61 @@ pub mod suspense {
                 key,
             }
         }
+
+        /// Registers a pending task with this context, returning a guard that removes the task
+        /// when it is dropped.
+        ///
+        /// This allows views that load data outside of `Suspend` or async resources to
+        /// participate in the nearest `Suspense`: while any guard is alive, the fallback is shown
+        /// on the client, and the server waits before rendering the children. The context can be
+        /// accessed with `use_context::<SuspenseContext>()`.
+        ///
+        /// During async server rendering, `Suspense` calls `dry_resolve` on its children, then
+        /// waits until no tasks are pending before calling `to_html_async_with_buf` and
+        /// `resolve`. A custom view should therefore take its guard no later than `dry_resolve`,
+        /// and drop it once its data are ready.
+        pub fn task_guard(&self) -> TaskHandle {
+            self.task_id()
+        }
+
+        /// Creates a handle that does not keep the boundary's tasks alive.
+        pub(crate) fn downgrade(&self) -> WeakSuspenseContext {
+            WeakSuspenseContext {
+                #[cfg(debug_assertions)]
+                defined_at: self.tasks.defined_at,
+                value: Arc::downgrade(&self.tasks.value),
+                inner: Arc::downgrade(&self.tasks.inner),
+            }
+        }
+    }
+
+    /// A [`SuspenseContext`] that does not keep its boundary alive, so that a resource read by
+    /// a boundary that has since been dropped does not hold on to it.
+    #[derive(Clone)]
+    pub(crate) struct WeakSuspenseContext {
+        #[cfg(debug_assertions)]
+        defined_at: &'static Location<'static>,
+        value: Weak<RwLock<SlotMap<DefaultKey, ()>>>,
+        inner: Weak<RwLock<SubscriberSet>>,
+    }
+
+    impl WeakSuspenseContext {
+        /// Returns the context, if its boundary still exists.
+        pub fn upgrade(&self) -> Option<SuspenseContext> {
+            Some(SuspenseContext {
+                tasks: ArcRwSignal {
+                    #[cfg(debug_assertions)]
+                    defined_at: self.defined_at,
+                    value: self.value.upgrade()?,
+                    inner: self.inner.upgrade()?,
+                },
+            })
+        }
+
+        /// Whether this is a handle to the given context.
+        pub fn is(&self, context: &SuspenseContext) -> bool {
+            Weak::as_ptr(&self.value) == Arc::as_ptr(&context.tasks.value)
+        }
     }
 
     /// A unique identifier that removes itself from the set of tasks when it is dropped.
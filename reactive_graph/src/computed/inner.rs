@@ -60,11 +60,15 @@ where
     S: Storage<T>,
 {
     fn mark_dirty(&self) {
+        #[cfg(feature = "graph-introspection")]
+        crate::introspect::notified(self as *const Self as usize);
         self.write().or_poisoned().state = ReactiveNodeState::Dirty;
         self.mark_subscribers_check();
     }
 
     fn mark_check(&self) {
+        #[cfg(feature = "graph-introspection")]
+        crate::introspect::notified(self as *const Self as usize);
         {
             let mut lock = self.write().or_poisoned();
             lock.state = ReactiveNodeState::Check;
@@ -1,11 +1,12 @@
 use super::{inner::MemoInner, ArcMemo};
 use crate::{
+    graph::{AnySource, ToAnySource},
     owner::{ArenaItem, FromLocal, LocalStorage, Storage, SyncStorage},
     signal::{
         guards::{Mapped, Plain, ReadGuard},
         ArcReadSignal,
     },
-    traits::{DefinedAt, Dispose, Get, ReadUntracked, Track},
+    traits::{DefinedAt, Dispose, Get, IsDisposed, ReadUntracked, Track},
     unwrap_signal,
 };
 use std::{fmt::Debug, hash::Hash, panic::Location};
@@ -192,9 +193,28 @@ where
     ///
     /// `changed` should be a function that returns `true` if the new value is different from the
     /// old value.
+    ///
+    /// ```
+    /// # use reactive_graph::prelude::*;
+    /// # use reactive_graph::computed::Memo;
+    /// # use reactive_graph::signal::signal;
+    /// # let owner = reactive_graph::owner::Owner::new(); owner.set();
+    /// let (value, set_value) = signal(0.0_f64);
+    ///
+    /// // only notifies subscribers when the value changes by more than 0.1
+    /// let epsilon = 0.1;
+    /// let approximate = Memo::new_with_compare(
+    ///     move |_| value.get(),
+    ///     move |prev, next| match (prev, next) {
+    ///         (Some(prev), Some(next)) => (prev - next).abs() > epsilon,
+    ///         _ => true,
+    ///     },
+    /// );
+    /// # assert_eq!(approximate.get(), 0.0);
+    /// ```
     pub fn new_with_compare(
         fun: impl Fn(Option<&T>) -> T + Send + Sync + 'static,
-        changed: fn(Option<&T>, Option<&T>) -> bool,
+        changed: impl Fn(Option<&T>, Option<&T>) -> bool + Send + Sync + 'static,
     ) -> Self {
         Self {
             #[cfg(debug_assertions)]
@@ -301,6 +321,29 @@ where
     }
 }
 
+impl<T, S> IsDisposed for Memo<T, S>
+where
+    T: 'static,
+    S: Storage<ArcMemo<T, S>> + Storage<T>,
+{
+    fn is_disposed(&self) -> bool {
+        self.inner.is_disposed()
+    }
+}
+
+impl<T, S> ToAnySource for Memo<T, S>
+where
+    T: 'static,
+    S: Storage<ArcMemo<T, S>> + Storage<T>,
+{
+    fn to_any_source(&self) -> AnySource {
+        self.inner
+            .try_get_value()
+            .map(|inner| inner.to_any_source())
+            .unwrap_or_else(unwrap_signal!(self))
+    }
+}
+
 impl<T, S> ReadUntracked for Memo<T, S>
 where
     T: 'static,
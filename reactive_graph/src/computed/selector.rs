@@ -1,13 +1,15 @@
 use crate::{
     effect::RenderEffect,
-    signal::ArcRwSignal,
-    traits::{Track, Update},
+    graph::Observer,
+    owner::Owner,
+    signal::ArcTrigger,
+    traits::{Notify, Track},
 };
 use or_poisoned::OrPoisoned;
 use rustc_hash::FxHashMap;
 use std::{
     hash::Hash,
-    sync::{Arc, RwLock},
+    sync::{Arc, RwLock, Weak},
 };
 
 /// A conditional signal that only notifies subscribers when a change
@@ -17,6 +19,12 @@ use std::{
 /// in certain situations (e.g., “set the class `selected` if `selected() == this_row_index`)
 /// because it reduces them from `O(n)` to `O(1)`.
 ///
+/// Each key that is read inside a reactive context gets its own trigger. When the
+/// source changes, only the subscribers of the previously-selected and newly-selected
+/// keys are notified. A key's trigger is dropped once no live reactive context depends
+/// on it anymore, either when the last subscriber is cleaned up or the next time the
+/// source changes, so rows that unmount do not leave entries behind.
+///
 /// ```
 /// # use reactive_graph::computed::*;
 /// # use reactive_graph::signal::*; let owner = reactive_graph::owner::Owner::new(); owner.set();
@@ -63,7 +71,7 @@ pub struct Selector<T>
 where
     T: PartialEq + Eq + Clone + Hash + 'static,
 {
-    subs: Arc<RwLock<FxHashMap<T, ArcRwSignal<bool>>>>,
+    subs: Arc<RwLock<FxHashMap<T, ArcTrigger>>>,
     v: Arc<RwLock<Option<T>>>,
     #[allow(clippy::type_complexity)]
     f: Arc<dyn Fn(&T, &T) -> bool + Send + Sync>,
@@ -87,8 +95,7 @@ where
         source: impl Fn() -> T + Clone + Send + Sync + 'static,
         f: impl Fn(&T, &T) -> bool + Send + Sync + Clone + 'static,
     ) -> Self {
-        let subs: Arc<RwLock<FxHashMap<T, ArcRwSignal<bool>>>> =
-            Default::default();
+        let subs: Arc<RwLock<FxHashMap<T, ArcTrigger>>> = Default::default();
         let v: Arc<RwLock<Option<T>>> = Default::default();
         let f = Arc::new(f) as Arc<dyn Fn(&T, &T) -> bool + Send + Sync>;
 
@@ -100,13 +107,20 @@ where
                 let next_value = source();
                 *v.write().or_poisoned() = Some(next_value.clone());
                 if prev.as_ref() != Some(&next_value) {
-                    for (key, signal) in &*subs.read().or_poisoned() {
+                    let mut changed = Vec::new();
+                    subs.write().or_poisoned().retain(|key, trigger| {
+                        if !has_live_subscribers(trigger) {
+                            return false;
+                        }
                         if f(key, &next_value)
-                            || (prev.is_some()
-                                && f(key, prev.as_ref().unwrap()))
+                            || prev.as_ref().is_some_and(|prev| f(key, prev))
                         {
-                            signal.update(|n| *n = true);
+                            changed.push(trigger.clone());
                         }
+                        true
+                    });
+                    for trigger in changed {
+                        trigger.notify();
                     }
                 }
                 next_value
@@ -117,17 +131,41 @@ where
     }
 
     /// Reactively checks whether the given key is selected.
+    ///
+    /// When called inside a reactive context, that context will only be notified when
+    /// this particular key becomes selected or stops being selected.
     pub fn selected(&self, key: T) -> bool {
-        let read = {
-            let mut subs = self.subs.write().or_poisoned();
-            subs.entry(key.clone())
-                .or_insert_with(|| ArcRwSignal::new(false))
-                .clone()
-        };
-        read.track();
+        if Observer::get().is_some() {
+            let trigger = self
+                .subs
+                .write()
+                .or_poisoned()
+                .entry(key.clone())
+                .or_default()
+                .clone();
+            trigger.track();
+
+            let subs = Arc::downgrade(&self.subs);
+            let key = key.clone();
+            Owner::on_cleanup(move || Self::unsubscribe(&subs, &key));
+        }
         (self.f)(&key, self.v.read().or_poisoned().as_ref().unwrap())
     }
 
+    fn unsubscribe(subs: &Weak<RwLock<FxHashMap<T, ArcTrigger>>>, key: &T) {
+        if let Some(subs) = subs.upgrade() {
+            let mut subs = subs.write().or_poisoned();
+            if subs.get(key).is_some_and(|t| !has_live_subscribers(t)) {
+                subs.remove(key);
+            }
+        }
+    }
+
+    /// Returns the number of keys that are currently tracked by this selector.
+    pub fn subscribed_keys(&self) -> usize {
+        self.subs.read().or_poisoned().len()
+    }
+
     /// Removes the listener for the given key.
     pub fn remove(&self, key: &T) {
         let mut subs = self.subs.write().or_poisoned();
@@ -140,3 +178,9 @@ where
         subs.clear();
     }
 }
+
+fn has_live_subscribers(trigger: &ArcTrigger) -> bool {
+    (&*trigger.inner.read().or_poisoned())
+        .into_iter()
+        .any(|subscriber| subscriber.1.strong_count() > 0)
+}
@@ -7,6 +7,12 @@
 //!
 //! This module provides utilities to suppress those warnings by entering a
 //! [`SpecialNonReactiveZone`].
+//!
+//! Code that builds a view without tracking it, like the body of a component, can run inside
+//! [`untrack_with_diagnostics`](crate::graph::untrack_with_diagnostics). Reading a signal there
+//! warns in debug mode even without the `effects` feature, because the value is only used once
+//! and the view will not update when it changes. Use [`allow_untracked_read`] to read a signal
+//! there on purpose.
 
 /// Marks an execution block that is known not to be reactive, and suppresses warnings.
 #[derive(Debug)]
@@ -16,16 +22,20 @@ pub struct SpecialNonReactiveZone;
 #[derive(Debug)]
 pub struct SpecialNonReactiveZoneGuard;
 
+use or_poisoned::OrPoisoned;
 use pin_project_lite::pin_project;
 use std::{
     cell::Cell,
     future::Future,
+    panic::Location,
     pin::Pin,
+    sync::{Arc, RwLock},
     task::{Context, Poll},
 };
 
 thread_local! {
     static IS_SPECIAL_ZONE: Cell<bool> = const { Cell::new(false) };
+    static IS_RENDERING_UNTRACKED: Cell<bool> = const { Cell::new(false) };
 }
 
 impl SpecialNonReactiveZone {
@@ -52,6 +62,104 @@ impl Drop for SpecialNonReactiveZoneGuard {
     }
 }
 
+/// Restores the previous diagnostics state when dropped.
+///
+/// This is returned by [`allow_untracked_read`].
+#[derive(Debug)]
+#[must_use = "reads are only allowed until the guard is dropped"]
+pub struct UntrackedReadGuard {
+    special_zone: bool,
+    rendering_untracked: bool,
+}
+
+impl UntrackedReadGuard {
+    fn enter(special_zone: bool, rendering_untracked: bool) -> Self {
+        Self {
+            special_zone: IS_SPECIAL_ZONE.replace(special_zone),
+            rendering_untracked: IS_RENDERING_UNTRACKED
+                .replace(rendering_untracked),
+        }
+    }
+
+    /// Starts building a view without tracking, until the guard is dropped.
+    pub(crate) fn rendering() -> Self {
+        Self::enter(false, true)
+    }
+}
+
+impl Drop for UntrackedReadGuard {
+    fn drop(&mut self) {
+        IS_SPECIAL_ZONE.set(self.special_zone);
+        IS_RENDERING_UNTRACKED.set(self.rendering_untracked);
+    }
+}
+
+/// Allows reading signals without tracking them, without any warning, until the returned guard
+/// is dropped.
+///
+/// ```rust
+/// # use reactive_graph::prelude::*;
+/// # use reactive_graph::signal::RwSignal;
+/// # use reactive_graph::graph::untrack_with_diagnostics;
+/// # use reactive_graph::diagnostics::allow_untracked_read;
+/// # let owner = reactive_graph::owner::Owner::new(); owner.set();
+/// let count = RwSignal::new(0);
+/// untrack_with_diagnostics(|| {
+///     // only used to pick the initial value, on purpose
+///     let _guard = allow_untracked_read();
+///     let initial = count.get();
+/// });
+/// ```
+pub fn allow_untracked_read() -> UntrackedReadGuard {
+    UntrackedReadGuard::enter(true, false)
+}
+
+type UntrackedReadHook = Arc<dyn Fn(&'static Location<'static>) + Send + Sync>;
+
+static UNTRACKED_READ_HOOK: RwLock<Option<UntrackedReadHook>> =
+    RwLock::new(None);
+
+/// Sets a function that is called with the location of every signal read that is reported
+/// because it happened inside [`untrack_with_diagnostics`](crate::graph::untrack_with_diagnostics),
+/// in addition to the warning. This replaces any function that was set before.
+///
+/// This only happens in debug mode.
+pub fn on_untracked_read(
+    hook: impl Fn(&'static Location<'static>) + Send + Sync + 'static,
+) {
+    *UNTRACKED_READ_HOOK.write().or_poisoned() = Some(Arc::new(hook));
+}
+
+/// Whether a signal that is read now, without an observer, should be reported.
+#[cfg(debug_assertions)]
+pub(crate) fn is_rendering_untracked() -> bool {
+    IS_RENDERING_UNTRACKED.get() && !IS_SPECIAL_ZONE.get()
+}
+
+#[cfg(debug_assertions)]
+pub(crate) fn report_untracked_read(
+    ty: &str,
+    called_at: &'static Location<'static>,
+    defined_at: Option<&'static Location<'static>>,
+) {
+    let defined_at = defined_at
+        .map(ToString::to_string)
+        .unwrap_or_else(|| String::from("{unknown}"));
+    crate::log_warning(format_args!(
+        "At {called_at}, you read a {ty} (defined at {defined_at}) while \
+         building a view, outside any reactive closure. The view will not \
+         update when it changes.\n\nIf this is inside a `view!` macro, wrap \
+         it in a closure:\n  ❌ NO  <p>{{x.get()}}</p>\n  ✅ YES <p>{{move \
+         || x.get()}}</p>\n\nIf you only want the current value, use \
+         `.get_untracked()` or `.with_untracked()`, or read it inside \
+         `untrack()` or while holding `allow_untracked_read()`."
+    ));
+    let hook = UNTRACKED_READ_HOOK.read().or_poisoned().clone();
+    if let Some(hook) = hook {
+        hook(called_at);
+    }
+}
+
 pin_project! {
     #[doc(hidden)]
     pub struct SpecialNonReactiveFuture<Fut> {
@@ -4,10 +4,13 @@
 mod effect;
 mod effect_function;
 mod inner;
+mod panic;
 mod render_effect;
 
 pub use effect::*;
 pub use effect_function::*;
+#[cfg(feature = "panic-isolation")]
+pub use panic::{on_effect_panic, PanicInfoLite};
 pub use render_effect::*;
 
 /// Creates a new render effect, which immediately runs `fun`.
@@ -1,18 +1,18 @@
 use crate::{
     channel::{channel, Receiver},
-    effect::{inner::EffectInner, EffectFunction},
+    effect::{inner::EffectInner, panic::isolate, EffectFunction},
     graph::{
-        AnySubscriber, ReactiveNode, SourceSet, Subscriber, ToAnySubscriber,
-        WithObserver,
+        untrack, AnySubscriber, ReactiveNode, SourceSet, Subscriber,
+        ToAnySubscriber, WithObserver,
     },
     owner::{ArenaItem, LocalStorage, Owner, Storage, SyncStorage},
     traits::Dispose,
 };
-use any_spawner::Executor;
 use futures::StreamExt;
 use or_poisoned::OrPoisoned;
 use std::{
     mem,
+    panic::Location,
     sync::{Arc, RwLock},
 };
 
@@ -76,12 +76,16 @@ use std::{
 ///    this with a web framework, this generally means that effects **do not run on the server**.
 ///    and you can call browser-specific APIs within the effect function without causing issues.
 ///    If you need an effect to run on the server, use [`Effect::new_isomorphic`].
+/// 3. The `panic-isolation` feature, which catches panics in effects, has no effect in the
+///    browser: `wasm32-unknown-unknown` aborts on panic rather than unwinding, so a panic in an
+///    effect still stops the whole application. It applies to native targets, like the server or
+///    tests.
 #[derive(Debug, Clone, Copy)]
 pub struct Effect<S> {
     inner: Option<ArenaItem<StoredEffect, S>>,
 }
 
-type StoredEffect = Option<Arc<RwLock<EffectInner>>>;
+pub(crate) type StoredEffect = Option<Arc<RwLock<EffectInner>>>;
 
 impl<S> Dispose for Effect<S> {
     fn dispose(self) {
@@ -91,7 +95,10 @@ impl<S> Dispose for Effect<S> {
     }
 }
 
-fn effect_base() -> (Receiver, Owner, Arc<RwLock<EffectInner>>) {
+fn effect_base(
+    #[allow(unused)] defined_at: &'static Location<'static>,
+    dirty: bool,
+) -> (Receiver, Owner, Arc<RwLock<EffectInner>>) {
     let (mut observer, rx) = channel();
 
     // spawn the effect asynchronously
@@ -101,10 +108,16 @@ fn effect_base() -> (Receiver, Owner, Arc<RwLock<EffectInner>>) {
 
     let owner = Owner::new();
     let inner = Arc::new(RwLock::new(EffectInner {
-        dirty: true,
+        dirty,
         observer,
         sources: SourceSet::new(),
     }));
+    #[cfg(feature = "graph-introspection")]
+    crate::introspect::register(
+        &inner,
+        crate::introspect::NodeKind::Effect,
+        defined_at,
+    );
 
     (rx, owner, inner)
 }
@@ -122,6 +135,11 @@ where
             drop(inner);
         }
     }
+
+    /// Returns a [`WatchHandle`] that stops this effect when it is dropped.
+    pub fn stop_on_drop(self) -> WatchHandle<S> {
+        WatchHandle { effect: self }
+    }
 }
 
 impl Effect<LocalStorage> {
@@ -129,18 +147,20 @@ impl Effect<LocalStorage> {
     /// that are read inside it change.
     ///
     /// This spawns a task on the local thread using
-    /// [`spawn_local`](any_spawner::Executor::spawn_local). For an effect that can be spawned on
+    /// [`spawn_local`](crate::spawn_local). For an effect that can be spawned on
     /// any thread, use [`new_sync`](Effect::new_sync).
+    #[track_caller]
     pub fn new<T, M>(mut fun: impl EffectFunction<T, M> + 'static) -> Self
     where
         T: 'static,
     {
+        let defined_at = Location::caller();
         let inner = cfg!(feature = "effects").then(|| {
-            let (mut rx, owner, inner) = effect_base();
+            let (mut rx, owner, inner) = effect_base(defined_at, true);
             let value = Arc::new(RwLock::new(None::<T>));
             let mut first_run = true;
 
-            Executor::spawn_local({
+            crate::spawn_local({
                 let value = Arc::clone(&value);
                 let subscriber = inner.to_any_subscriber();
 
@@ -157,9 +177,17 @@ impl Effect<LocalStorage> {
                             let old_value =
                                 mem::take(&mut *value.write().or_poisoned());
                             let new_value = owner.with_cleanup(|| {
-                                subscriber.with_observer(|| fun.run(old_value))
+                                subscriber.with_observer(|| {
+                                    isolate(defined_at, || fun.run(old_value))
+                                })
                             });
-                            *value.write().or_poisoned() = Some(new_value);
+                            match new_value {
+                                Some(new_value) => {
+                                    *value.write().or_poisoned() =
+                                        Some(new_value)
+                                }
+                                None => break,
+                            }
                         }
                     }
                 }
@@ -177,6 +205,11 @@ impl Effect<LocalStorage> {
     /// The return value of `dependency_fn` is passed into `handler` as an argument together with the previous value.
     /// Additionally, the last return value of `handler` is provided as a third argument, as is done in [`Effect::new`].
     ///
+    /// The watcher stops when the returned [`Effect`] is stopped, or when its [`Owner`] is
+    /// disposed. To stop it when a handle is dropped instead, use
+    /// [`stop_on_drop`](Effect::stop_on_drop). This returns an [`Effect`] rather than a
+    /// [`WatchHandle`] so that existing calls which discard the result keep the watcher running.
+    ///
     /// ## Usage
     ///
     /// ```
@@ -274,6 +307,13 @@ impl Effect<LocalStorage> {
     /// # }).await;
     /// # });
     /// ```
+    ///
+    /// ## Comparing Dependencies
+    ///
+    /// The `handler` runs whenever a signal read in `deps` changes, even if `deps` returns the
+    /// same value as before. To only run it when the value is different according to
+    /// [`PartialEq`], use [`Effect::watch_eq`].
+    #[track_caller]
     pub fn watch<D, T>(
         mut dependency_fn: impl FnMut() -> D + 'static,
         mut handler: impl FnMut(&D, Option<&D>, Option<T>) -> T + 'static,
@@ -283,13 +323,17 @@ impl Effect<LocalStorage> {
         D: 'static,
         T: 'static,
     {
+        let defined_at = Location::caller();
         let inner = cfg!(feature = "effects").then(|| {
-            let (mut rx, owner, inner) = effect_base();
+            // the first run is forced, rather than triggered by being dirty. starting clean means
+            // the first notification afterwards only reruns the handler if a source has changed
+            // (e.g., not for a memo that recomputed to the same value)
+            let (mut rx, owner, inner) = effect_base(defined_at, false);
             let mut first_run = true;
             let dep_value = Arc::new(RwLock::new(None::<D>));
             let watch_value = Arc::new(RwLock::new(None::<T>));
 
-            Executor::spawn_local({
+            crate::spawn_local({
                 let dep_value = Arc::clone(&dep_value);
                 let watch_value = Arc::clone(&watch_value);
                 let subscriber = inner.to_any_subscriber();
@@ -307,19 +351,32 @@ impl Effect<LocalStorage> {
                                 &mut *dep_value.write().or_poisoned(),
                             );
                             let new_dep_value = owner.with_cleanup(|| {
-                                subscriber.with_observer(&mut dependency_fn)
+                                subscriber.with_observer(|| {
+                                    isolate(defined_at, &mut dependency_fn)
+                                })
                             });
+                            let Some(new_dep_value) = new_dep_value else {
+                                break;
+                            };
 
                             let old_watch_value = mem::take(
                                 &mut *watch_value.write().or_poisoned(),
                             );
 
                             if immediate || !first_run {
-                                let new_watch_value = handler(
-                                    &new_dep_value,
-                                    old_dep_value.as_ref(),
-                                    old_watch_value,
-                                );
+                                let new_watch_value = untrack(|| {
+                                    isolate(defined_at, || {
+                                        handler(
+                                            &new_dep_value,
+                                            old_dep_value.as_ref(),
+                                            old_watch_value,
+                                        )
+                                    })
+                                });
+                                let Some(new_watch_value) = new_watch_value
+                                else {
+                                    break;
+                                };
 
                                 *watch_value.write().or_poisoned() =
                                     Some(new_watch_value);
@@ -339,6 +396,57 @@ impl Effect<LocalStorage> {
 
         Self { inner }
     }
+
+    /// Like [`Effect::watch`], but only runs `handler` when `dependency_fn` returns a value that
+    /// is different from the previous one, according to [`PartialEq`].
+    ///
+    /// ```
+    /// # use reactive_graph::effect::Effect;
+    /// # use reactive_graph::traits::*;
+    /// # use reactive_graph::signal::signal;
+    /// # tokio_test::block_on(async move {
+    /// # tokio::task::LocalSet::new().run_until(async move {
+    /// # any_spawner::Executor::init_tokio(); let owner = reactive_graph::owner::Owner::new(); owner.set();
+    /// #
+    /// let (num, set_num) = signal(0);
+    ///
+    /// Effect::watch_eq(
+    ///     move || num.get() % 2 == 0,
+    ///     move |is_even, _, _| {
+    ///         // log::debug!("Is even: {}", is_even);
+    ///     },
+    ///     false,
+    /// );
+    ///
+    /// set_num.set(2); // (nothing happens)
+    /// set_num.set(3); // > "Is even: false"
+    /// # assert_eq!(num.get(), 3);
+    /// # }).await;
+    /// # });
+    /// ```
+    #[track_caller]
+    pub fn watch_eq<D, T>(
+        dependency_fn: impl FnMut() -> D + 'static,
+        mut handler: impl FnMut(&D, Option<&D>, Option<T>) -> T + 'static,
+        immediate: bool,
+    ) -> Self
+    where
+        D: PartialEq + 'static,
+        T: 'static,
+    {
+        Self::watch(
+            dependency_fn,
+            move |value, prev_value, prev: Option<Option<T>>| {
+                let prev = prev.flatten();
+                if prev_value == Some(value) {
+                    prev
+                } else {
+                    Some(handler(value, prev_value, prev))
+                }
+            },
+            immediate,
+        )
+    }
 }
 
 impl Effect<SyncStorage> {
@@ -347,14 +455,16 @@ impl Effect<SyncStorage> {
     ///
     /// This spawns a task that can be run on any thread. For an effect that will be spawned on
     /// the current thread, use [`new`](Effect::new).
+    #[track_caller]
     pub fn new_sync<T, M>(
         mut fun: impl EffectFunction<T, M> + Send + Sync + 'static,
     ) -> Self
     where
         T: Send + Sync + 'static,
     {
+        let defined_at = Location::caller();
         let inner = cfg!(feature = "effects").then(|| {
-            let (mut rx, owner, inner) = effect_base();
+            let (mut rx, owner, inner) = effect_base(defined_at, true);
             let mut first_run = true;
             let value = Arc::new(RwLock::new(None::<T>));
 
@@ -375,9 +485,17 @@ impl Effect<SyncStorage> {
                             let old_value =
                                 mem::take(&mut *value.write().or_poisoned());
                             let new_value = owner.with_cleanup(|| {
-                                subscriber.with_observer(|| fun.run(old_value))
+                                subscriber.with_observer(|| {
+                                    isolate(defined_at, || fun.run(old_value))
+                                })
                             });
-                            *value.write().or_poisoned() = Some(new_value);
+                            match new_value {
+                                Some(new_value) => {
+                                    *value.write().or_poisoned() =
+                                        Some(new_value)
+                                }
+                                None => break,
+                            }
                         }
                     }
                 }
@@ -393,13 +511,15 @@ impl Effect<SyncStorage> {
     /// that are read inside it change.
     ///
     /// This will run whether the `effects` feature is enabled or not.
+    #[track_caller]
     pub fn new_isomorphic<T, M>(
         mut fun: impl EffectFunction<T, M> + Send + Sync + 'static,
     ) -> Self
     where
         T: Send + Sync + 'static,
     {
-        let (mut rx, owner, inner) = effect_base();
+        let defined_at = Location::caller();
+        let (mut rx, owner, inner) = effect_base(defined_at, true);
         let mut first_run = true;
         let value = Arc::new(RwLock::new(None::<T>));
 
@@ -419,9 +539,16 @@ impl Effect<SyncStorage> {
                         let old_value =
                             mem::take(&mut *value.write().or_poisoned());
                         let new_value = owner.with_cleanup(|| {
-                            subscriber.with_observer(|| fun.run(old_value))
+                            subscriber.with_observer(|| {
+                                isolate(defined_at, || fun.run(old_value))
+                            })
                         });
-                        *value.write().or_poisoned() = Some(new_value);
+                        match new_value {
+                            Some(new_value) => {
+                                *value.write().or_poisoned() = Some(new_value)
+                            }
+                            None => break,
+                        }
                     }
                 }
             }
@@ -435,6 +562,7 @@ impl Effect<SyncStorage> {
     }
 
     /// This is to [`Effect::watch`] what [`Effect::new_sync`] is to [`Effect::new`].
+    #[track_caller]
     pub fn watch_sync<D, T>(
         mut dependency_fn: impl FnMut() -> D + Send + Sync + 'static,
         mut handler: impl FnMut(&D, Option<&D>, Option<T>) -> T
@@ -447,7 +575,9 @@ impl Effect<SyncStorage> {
         D: Send + Sync + 'static,
         T: Send + Sync + 'static,
     {
-        let (mut rx, owner, inner) = effect_base();
+        let defined_at = Location::caller();
+        // starts clean, see `Effect::watch`
+        let (mut rx, owner, inner) = effect_base(defined_at, false);
         let mut first_run = true;
         let dep_value = Arc::new(RwLock::new(None::<D>));
         let watch_value = Arc::new(RwLock::new(None::<T>));
@@ -471,19 +601,32 @@ impl Effect<SyncStorage> {
                                 &mut *dep_value.write().or_poisoned(),
                             );
                             let new_dep_value = owner.with_cleanup(|| {
-                                subscriber.with_observer(&mut dependency_fn)
+                                subscriber.with_observer(|| {
+                                    isolate(defined_at, &mut dependency_fn)
+                                })
                             });
+                            let Some(new_dep_value) = new_dep_value else {
+                                break;
+                            };
 
                             let old_watch_value = mem::take(
                                 &mut *watch_value.write().or_poisoned(),
                             );
 
                             if immediate || !first_run {
-                                let new_watch_value = handler(
-                                    &new_dep_value,
-                                    old_dep_value.as_ref(),
-                                    old_watch_value,
-                                );
+                                let new_watch_value = untrack(|| {
+                                    isolate(defined_at, || {
+                                        handler(
+                                            &new_dep_value,
+                                            old_dep_value.as_ref(),
+                                            old_watch_value,
+                                        )
+                                    })
+                                });
+                                let Some(new_watch_value) = new_watch_value
+                                else {
+                                    break;
+                                };
 
                                 *watch_value.write().or_poisoned() =
                                     Some(new_watch_value);
@@ -503,6 +646,116 @@ impl Effect<SyncStorage> {
 
         Self { inner }
     }
+
+    /// This is to [`Effect::watch_eq`] what [`Effect::new_sync`] is to [`Effect::new`].
+    #[track_caller]
+    pub fn watch_sync_eq<D, T>(
+        dependency_fn: impl FnMut() -> D + Send + Sync + 'static,
+        mut handler: impl FnMut(&D, Option<&D>, Option<T>) -> T
+            + Send
+            + Sync
+            + 'static,
+        immediate: bool,
+    ) -> Self
+    where
+        D: PartialEq + Send + Sync + 'static,
+        T: Send + Sync + 'static,
+    {
+        Self::watch_sync(
+            dependency_fn,
+            move |value, prev_value, prev: Option<Option<T>>| {
+                let prev = prev.flatten();
+                if prev_value == Some(value) {
+                    prev
+                } else {
+                    Some(handler(value, prev_value, prev))
+                }
+            },
+            immediate,
+        )
+    }
+}
+
+/// Stops an effect when it is dropped, created with [`Effect::stop_on_drop`].
+///
+/// The effect also stops when its [`Owner`] is disposed. To keep it running until then without
+/// holding on to the handle, [`detach`](WatchHandle::detach) it. To keep it running for as long
+/// as the handle is alive instead, convert it into an [`ArcWatchHandle`].
+#[must_use = "An effect stops when its WatchHandle is dropped. Store the handle, or \
+              call `detach()` to keep it running until its owner is disposed."]
+#[derive(Debug)]
+pub struct WatchHandle<S>
+where
+    S: Storage<StoredEffect>,
+{
+    effect: Effect<S>,
+}
+
+impl<S> WatchHandle<S>
+where
+    S: Storage<StoredEffect>,
+{
+    /// Stops watching.
+    pub fn stop(self) {
+        drop(self);
+    }
+
+    /// Keeps watching until the owner is disposed, or the returned [`Effect`] is stopped, instead
+    /// of when this handle is dropped.
+    pub fn detach(self) -> Effect<S> {
+        // `Effect` is only `Copy` for `Copy` storage types, but its arena item always is
+        let effect = Effect {
+            inner: self.effect.inner,
+        };
+        mem::forget(self);
+        effect
+    }
+}
+
+impl<S> Drop for WatchHandle<S>
+where
+    S: Storage<StoredEffect>,
+{
+    fn drop(&mut self) {
+        Effect {
+            inner: self.effect.inner,
+        }
+        .stop();
+    }
+}
+
+/// A reference-counted handle to a watcher, which stops when the last clone is dropped or when
+/// any clone is stopped.
+///
+/// Unlike a [`WatchHandle`], this keeps watching after its [`Owner`] is disposed, as long as a
+/// clone of it is alive.
+#[derive(Debug, Clone)]
+pub struct ArcWatchHandle {
+    inner: Arc<RwLock<StoredEffect>>,
+}
+
+impl ArcWatchHandle {
+    /// Stops watching.
+    pub fn stop(&self) {
+        drop(self.inner.write().or_poisoned().take());
+    }
+}
+
+impl<S> From<WatchHandle<S>> for ArcWatchHandle
+where
+    S: Storage<StoredEffect>,
+{
+    fn from(value: WatchHandle<S>) -> Self {
+        // take the effect out of the arena, so it is no longer stopped with its owner
+        let inner = value
+            .detach()
+            .inner
+            .and_then(|inner| inner.try_update_value(|inner| inner.take()))
+            .flatten();
+        Self {
+            inner: Arc::new(RwLock::new(inner)),
+        }
+    }
 }
 
 impl<S> ToAnySubscriber for Effect<S>
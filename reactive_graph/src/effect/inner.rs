@@ -48,10 +48,14 @@ impl ReactiveNode for RwLock<EffectInner> {
     }
 
     fn mark_check(&self) {
+        #[cfg(feature = "graph-introspection")]
+        crate::introspect::notified(self as *const Self as usize);
         self.write().or_poisoned().observer.notify()
     }
 
     fn mark_dirty(&self) {
+        #[cfg(feature = "graph-introspection")]
+        crate::introspect::notified(self as *const Self as usize);
         let mut lock = self.write().or_poisoned();
         lock.dirty = true;
         lock.observer.notify()
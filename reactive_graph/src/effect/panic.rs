@@ -0,0 +1,101 @@
+//! Catching panics in effects, with the `panic-isolation` feature, on targets that unwind.
+
+use std::panic::Location;
+#[cfg(feature = "panic-isolation")]
+use std::{
+    any::Any,
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::{Arc, RwLock},
+};
+#[cfg(feature = "panic-isolation")]
+use {or_poisoned::OrPoisoned, std::fmt::Display};
+
+/// A description of a panic that was caught while running an effect.
+#[cfg(feature = "panic-isolation")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PanicInfoLite {
+    message: Option<String>,
+}
+
+#[cfg(feature = "panic-isolation")]
+impl PanicInfoLite {
+    fn new(payload: &(dyn Any + Send)) -> Self {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned());
+        Self { message }
+    }
+
+    /// The message the effect panicked with, if it was a string.
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+}
+
+#[cfg(feature = "panic-isolation")]
+impl Display for PanicInfoLite {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.message().unwrap_or("Box<dyn Any>"))
+    }
+}
+
+#[cfg(feature = "panic-isolation")]
+type PanicHook =
+    Arc<dyn Fn(&PanicInfoLite, &'static Location<'static>) + Send + Sync>;
+
+#[cfg(feature = "panic-isolation")]
+static PANIC_HOOK: RwLock<Option<PanicHook>> = RwLock::new(None);
+
+/// Sets the function that is called when an effect panics, replacing any that was set before.
+///
+/// With the `panic-isolation` feature, a panic in an [`Effect`](super::Effect) or
+/// [`RenderEffect`](super::RenderEffect) is caught rather than unwinding through the reactive
+/// system. The effect stops running, and `hook` is called with the panic and the location at which
+/// the effect was created, so that the application can show an error or report it. Other effects
+/// keep running.
+///
+/// The panic is still reported to the standard panic hook first, which prints it by default.
+///
+/// Panics can only be caught on targets that unwind. `wasm32-unknown-unknown`, which is used in
+/// the browser, always aborts on panic, so there a panic in an effect still stops the whole
+/// application and `hook` is never called.
+#[cfg(feature = "panic-isolation")]
+pub fn on_effect_panic(
+    hook: impl Fn(&PanicInfoLite, &'static Location<'static>)
+        + Send
+        + Sync
+        + 'static,
+) {
+    *PANIC_HOOK.write().or_poisoned() = Some(Arc::new(hook));
+}
+
+/// Runs one run of an effect, returning `None` if it panicked and the effect should stop.
+///
+/// Without the `panic-isolation` feature, panics are not caught.
+#[inline(always)]
+pub(crate) fn isolate<T>(
+    defined_at: &'static Location<'static>,
+    fun: impl FnOnce() -> T,
+) -> Option<T> {
+    #[cfg(feature = "panic-isolation")]
+    {
+        match catch_unwind(AssertUnwindSafe(fun)) {
+            Ok(value) => Some(value),
+            Err(payload) => {
+                let info = PanicInfoLite::new(&*payload);
+                // the hook is cloned so that it can set another hook
+                let hook = PANIC_HOOK.read().or_poisoned().clone();
+                if let Some(hook) = hook {
+                    hook(&info, defined_at);
+                }
+                None
+            }
+        }
+    }
+    #[cfg(not(feature = "panic-isolation"))]
+    {
+        _ = defined_at;
+        Some(fun())
+    }
+}
@@ -1,18 +1,18 @@
 use crate::{
     channel::channel,
-    effect::inner::EffectInner,
+    effect::{inner::EffectInner, panic::isolate},
     graph::{
         AnySubscriber, ReactiveNode, SourceSet, Subscriber, ToAnySubscriber,
         WithObserver,
     },
     owner::Owner,
 };
-use any_spawner::Executor;
 use futures::StreamExt;
 use or_poisoned::OrPoisoned;
 use std::{
     fmt::Debug,
     mem,
+    panic::Location,
     sync::{Arc, RwLock, Weak},
 };
 
@@ -28,6 +28,12 @@ use std::{
 ///
 /// Like an [`Effect`](super::Effect), a render effect runs only with the `effects` feature
 /// enabled.
+///
+/// With the `panic-isolation` feature, a panic while running the effect is caught, and the effect
+/// stops running. See `on_effect_panic`.
+///
+/// While the [`Owner`] it was created under is paused with [`Owner::pause`], the effect does not
+/// run; it runs once the owner is resumed instead, if its sources have changed.
 #[must_use = "A RenderEffect will be canceled when it is dropped. Creating a \
               RenderEffect that is not stored in some other data structure or \
               leaked will drop it immediately, and it will not react to \
@@ -53,11 +59,13 @@ where
     T: 'static,
 {
     /// Creates a new render effect, which immediately runs `fun`.
+    #[track_caller]
     pub fn new(fun: impl FnMut(Option<T>) -> T + 'static) -> Self {
         Self::new_with_value(fun, None)
     }
 
     /// Creates a new render effect with an initial value.
+    #[track_caller]
     pub fn new_with_value(
         fun: impl FnMut(Option<T>) -> T + 'static,
         initial_value: Option<T>,
@@ -65,6 +73,7 @@ where
         fn erased<T>(
             mut fun: Box<dyn FnMut(Option<T>) -> T + 'static>,
             initial_value: Option<T>,
+            defined_at: &'static Location<'static>,
         ) -> RenderEffect<T> {
             let (observer, mut rx) = channel();
             let value = Arc::new(RwLock::new(None::<T>));
@@ -74,23 +83,39 @@ where
                 observer,
                 sources: SourceSet::new(),
             }));
+            #[cfg(feature = "graph-introspection")]
+            {
+                crate::introspect::register(
+                    &inner,
+                    crate::introspect::NodeKind::RenderEffect,
+                    defined_at,
+                );
+                crate::introspect::will_run(Arc::as_ptr(&inner) as usize);
+            }
 
             let initial_value = cfg!(feature = "effects").then(|| {
                 owner.with(|| {
-                    inner
-                        .to_any_subscriber()
-                        .with_observer(|| fun(initial_value))
+                    inner.to_any_subscriber().with_observer(|| {
+                        isolate(defined_at, || fun(initial_value))
+                    })
                 })
             });
-            *value.write().or_poisoned() = initial_value;
+            // if the first run panicked, the effect never runs again
+            let poisoned = matches!(initial_value, Some(None));
+            *value.write().or_poisoned() = initial_value.flatten();
 
-            if cfg!(feature = "effects") {
-                Executor::spawn_local({
+            if cfg!(feature = "effects") && !poisoned {
+                crate::spawn_local({
                     let value = Arc::clone(&value);
                     let subscriber = inner.to_any_subscriber();
 
                     async move {
                         while rx.next().await.is_some() {
+                            // a paused effect runs once its owner is resumed
+                            if owner.paused() {
+                                owner.defer_until_resumed(subscriber.clone());
+                                continue;
+                            }
                             if subscriber.with_observer(|| {
                                 subscriber.update_if_necessary()
                             }) {
@@ -100,9 +125,17 @@ where
                                     &mut *value.write().or_poisoned(),
                                 );
                                 let new_value = owner.with_cleanup(|| {
-                                    subscriber.with_observer(|| fun(old_value))
+                                    subscriber.with_observer(|| {
+                                        isolate(defined_at, || fun(old_value))
+                                    })
                                 });
-                                *value.write().or_poisoned() = Some(new_value);
+                                match new_value {
+                                    Some(new_value) => {
+                                        *value.write().or_poisoned() =
+                                            Some(new_value)
+                                    }
+                                    None => break,
+                                }
                             }
                         }
                     }
@@ -112,7 +145,7 @@ where
             RenderEffect { value, inner }
         }
 
-        erased(Box::new(fun), initial_value)
+        erased(Box::new(fun), initial_value, Location::caller())
     }
 
     /// Mutably accesses the current value.
@@ -134,11 +167,13 @@ where
     T: Send + Sync + 'static,
 {
     /// Creates a render effect that will run whether the `effects` feature is enabled or not.
+    #[track_caller]
     pub fn new_isomorphic(
         fun: impl FnMut(Option<T>) -> T + Send + Sync + 'static,
     ) -> Self {
         fn erased<T: Send + Sync + 'static>(
             mut fun: Box<dyn FnMut(Option<T>) -> T + Send + Sync + 'static>,
+            defined_at: &'static Location<'static>,
         ) -> RenderEffect<T> {
             let (observer, mut rx) = channel();
             let value = Arc::new(RwLock::new(None::<T>));
@@ -148,17 +183,39 @@ where
                 observer,
                 sources: SourceSet::new(),
             }));
+            #[cfg(feature = "graph-introspection")]
+            {
+                crate::introspect::register(
+                    &inner,
+                    crate::introspect::NodeKind::RenderEffect,
+                    defined_at,
+                );
+                crate::introspect::will_run(Arc::as_ptr(&inner) as usize);
+            }
 
-            let initial_value = owner
-                .with(|| inner.to_any_subscriber().with_observer(|| fun(None)));
-            *value.write().or_poisoned() = Some(initial_value);
+            let initial_value = owner.with(|| {
+                inner
+                    .to_any_subscriber()
+                    .with_observer(|| isolate(defined_at, || fun(None)))
+            });
+            // if the first run panicked, the effect never runs again
+            let poisoned = initial_value.is_none();
+            *value.write().or_poisoned() = initial_value;
 
             crate::spawn({
                 let value = Arc::clone(&value);
                 let subscriber = inner.to_any_subscriber();
 
                 async move {
+                    if poisoned {
+                        return;
+                    }
                     while rx.next().await.is_some() {
+                        // a paused effect runs once its owner is resumed
+                        if owner.paused() {
+                            owner.defer_until_resumed(subscriber.clone());
+                            continue;
+                        }
                         if subscriber
                             .with_observer(|| subscriber.update_if_necessary())
                         {
@@ -167,9 +224,17 @@ where
                             let old_value =
                                 mem::take(&mut *value.write().or_poisoned());
                             let new_value = owner.with_cleanup(|| {
-                                subscriber.with_observer(|| fun(old_value))
+                                subscriber.with_observer(|| {
+                                    isolate(defined_at, || fun(old_value))
+                                })
                             });
-                            *value.write().or_poisoned() = Some(new_value);
+                            match new_value {
+                                Some(new_value) => {
+                                    *value.write().or_poisoned() =
+                                        Some(new_value)
+                                }
+                                None => break,
+                            }
                         }
                     }
                 }
@@ -178,7 +243,7 @@ where
             RenderEffect { value, inner }
         }
 
-        erased(Box::new(fun))
+        erased(Box::new(fun), Location::caller())
     }
 }
 
@@ -5,8 +5,11 @@ mod node;
 mod sets;
 mod source;
 mod subscriber;
+mod subscription;
 
 pub use node::*;
 pub(crate) use sets::*;
 pub use source::*;
 pub use subscriber::*;
+pub(crate) use subscription::notifying;
+pub use subscription::{Subscribe, Subscription};
@@ -36,6 +36,9 @@ impl SourceSet {
     }
 
     pub fn clear_sources(&mut self, subscriber: &AnySubscriber) {
+        // subscribers clear their sources right before they run again
+        #[cfg(feature = "graph-introspection")]
+        crate::introspect::will_run(subscriber.0);
         for source in self.take() {
             source.remove_subscriber(subscriber);
         }
@@ -135,6 +135,20 @@ pub fn untrack<T>(fun: impl FnOnce() -> T) -> T {
     fun()
 }
 
+/// Suspends reactive tracking while running the given function, like [`untrack`], but reports
+/// any signal that is read inside it.
+///
+/// This is meant for code that builds a view once, like the body of a component: a signal
+/// that is read there directly, rather than inside a closure, is only read once, and the view
+/// will not update when it changes. In debug mode, each such read logs a warning with its
+/// location. Reads inside [`untrack`], or while holding an
+/// [`allow_untracked_read`](crate::diagnostics::allow_untracked_read) guard, are not reported.
+pub fn untrack_with_diagnostics<T>(fun: impl FnOnce() -> T) -> T {
+    let _guard = crate::diagnostics::UntrackedReadGuard::rendering();
+    let _prev = Observer::take();
+    fun()
+}
+
 /// Converts a [`Subscriber`] to a type-erased [`AnySubscriber`].
 pub trait ToAnySubscriber {
     /// Converts this type to its type-erased equivalent.
@@ -0,0 +1,289 @@
+use super::{
+    AnySource, AnySubscriber, ReactiveNode, Source, Subscriber, ToAnySource,
+    ToAnySubscriber, WithObserver,
+};
+use crate::traits::WithUntracked;
+use send_wrapper::SendWrapper;
+use std::{
+    cell::{Cell, RefCell},
+    fmt::Debug,
+    sync::{
+        atomic::{AtomicBool, AtomicU8, Ordering},
+        Arc, Weak,
+    },
+};
+
+/// Calls a function whenever a signal changes, without creating an effect.
+pub trait Subscribe: WithUntracked {
+    /// Calls `callback` with the new value each time this signal changes, until the returned
+    /// [`Subscription`] is dropped.
+    ///
+    /// Unlike an effect, this doesn't need an [`Owner`](crate::owner::Owner), doesn't track
+    /// anything that the callback reads, and calls the callback synchronously, after the value
+    /// has been updated and before the function that updated it returns. It is not called for
+    /// the current value.
+    ///
+    /// The callback is given a clone of the new value, so the signal isn't locked while it runs.
+    /// If it updates a signal that has subscriptions (including this one), their callbacks are
+    /// called after this callback returns, rather than in the middle of it.
+    ///
+    /// The callback is always called on the thread that subscribed. If the signal is updated on
+    /// another thread, the callback is not called.
+    ///
+    /// ```rust
+    /// # use reactive_graph::prelude::*;
+    /// # use reactive_graph::signal::ArcRwSignal;
+    /// # use std::{cell::Cell, rc::Rc};
+    /// let count = ArcRwSignal::new(0);
+    /// let last_seen = Rc::new(Cell::new(0));
+    ///
+    /// let subscription = count.subscribe({
+    ///     let last_seen = Rc::clone(&last_seen);
+    ///     move |count| last_seen.set(*count)
+    /// });
+    /// count.set(1);
+    /// assert_eq!(last_seen.get(), 1);
+    ///
+    /// drop(subscription);
+    /// count.set(2);
+    /// assert_eq!(last_seen.get(), 1);
+    /// ```
+    #[must_use = "The subscription is canceled when the Subscription is dropped."]
+    fn subscribe(
+        &self,
+        callback: impl FnMut(&Self::Value) + 'static,
+    ) -> Subscription;
+}
+
+impl<S> Subscribe for S
+where
+    S: ToAnySource + WithUntracked + Clone + Send + Sync + 'static,
+    S::Value: Clone,
+{
+    fn subscribe(
+        &self,
+        callback: impl FnMut(&Self::Value) + 'static,
+    ) -> Subscription {
+        let source = self.to_any_source();
+        let reader = self.clone();
+        // the value is read once so that memos have run, and will be notified of changes
+        reader.with_untracked(|_| {});
+
+        let node = Arc::new_cyclic(|weak: &Weak<SubscriptionNode<S>>| {
+            SubscriptionNode {
+                source: source.clone(),
+                reader,
+                state: AtomicU8::new(CLEAN),
+                canceled: AtomicBool::new(false),
+                callback: SendWrapper::new(RefCell::new(Box::new(callback))),
+                this: weak.clone(),
+            }
+        });
+        let subscriber = node.to_any_subscriber();
+        source.add_subscriber(subscriber.clone());
+
+        Subscription {
+            source,
+            subscriber,
+            node: node as Arc<dyn PendingSubscription + Send + Sync>,
+        }
+    }
+}
+
+/// A callback that is called whenever a signal changes, created with [`Subscribe::subscribe`].
+///
+/// The callback is no longer called once this is dropped.
+pub struct Subscription {
+    source: AnySource,
+    subscriber: AnySubscriber,
+    node: Arc<dyn PendingSubscription + Send + Sync>,
+}
+
+impl Debug for Subscription {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Subscription")
+            .field("source", &self.source)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        self.node.cancel();
+        self.source.remove_subscriber(&self.subscriber);
+    }
+}
+
+const CLEAN: u8 = 0;
+const CHECK: u8 = 1;
+const DIRTY: u8 = 2;
+
+type Callback<T> = SendWrapper<RefCell<Box<dyn FnMut(&T)>>>;
+
+struct SubscriptionNode<S>
+where
+    S: WithUntracked,
+{
+    source: AnySource,
+    reader: S,
+    state: AtomicU8,
+    canceled: AtomicBool,
+    callback: Callback<S::Value>,
+    this: Weak<Self>,
+}
+
+trait PendingSubscription {
+    fn run(&self);
+
+    fn cancel(&self);
+}
+
+impl<S> PendingSubscription for SubscriptionNode<S>
+where
+    S: WithUntracked + Send + Sync + 'static,
+    S::Value: Clone,
+{
+    fn run(&self) {
+        if self.canceled.load(Ordering::Relaxed) {
+            return;
+        }
+        let subscriber = self.to_any_subscriber();
+        let state = self.state.swap(CLEAN, Ordering::Relaxed);
+        // memos only mark this to be checked, and may turn out not to have changed. this is the
+        // observer while they update, so that they don't mark it dirty again when they change
+        let changed = state == DIRTY
+            || (state == CHECK
+                && subscriber.with_observer_untracked(|| {
+                    self.source.update_if_necessary()
+                }));
+
+        // signals drop their subscribers when they notify them, so this subscribes again before
+        // the callback runs, in case it updates the same signal
+        self.source.add_subscriber(subscriber);
+
+        if changed && self.callback.valid() {
+            // the value is cloned so that the signal isn't locked while the callback runs
+            if let Some(value) = self.reader.try_with_untracked(S::Value::clone)
+            {
+                (self.callback.borrow_mut())(&value);
+            }
+        }
+    }
+
+    fn cancel(&self) {
+        self.canceled.store(true, Ordering::Relaxed);
+    }
+}
+
+impl<S> SubscriptionNode<S>
+where
+    S: WithUntracked + Send + Sync + 'static,
+    S::Value: Clone,
+{
+    fn mark(&self, state: u8) {
+        self.state.fetch_max(state, Ordering::Relaxed);
+        if let Some(this) = self.this.upgrade() {
+            PENDING.with_borrow_mut(|pending| pending.push(this));
+        }
+        // memos notify their subscribers while they are locked, so subscriptions only run once
+        // the signal that started the update has notified all of its subscribers
+        if NOTIFYING.get() == 0 {
+            run_pending();
+        }
+    }
+}
+
+impl<S> ReactiveNode for SubscriptionNode<S>
+where
+    S: WithUntracked + Send + Sync + 'static,
+    S::Value: Clone,
+{
+    fn mark_dirty(&self) {
+        self.mark(DIRTY);
+    }
+
+    fn mark_check(&self) {
+        self.mark(CHECK);
+    }
+
+    fn mark_subscribers_check(&self) {}
+
+    fn update_if_necessary(&self) -> bool {
+        false
+    }
+}
+
+impl<S> Subscriber for SubscriptionNode<S>
+where
+    S: WithUntracked + Send + Sync + 'static,
+    S::Value: Clone,
+{
+    fn add_source(&self, _source: AnySource) {}
+
+    fn clear_sources(&self, _subscriber: &AnySubscriber) {}
+}
+
+impl<S> ToAnySubscriber for SubscriptionNode<S>
+where
+    S: WithUntracked + Send + Sync + 'static,
+    S::Value: Clone,
+{
+    fn to_any_subscriber(&self) -> AnySubscriber {
+        AnySubscriber(
+            self.this.as_ptr() as usize,
+            self.this.clone() as Weak<dyn Subscriber + Send + Sync>,
+        )
+    }
+}
+
+thread_local! {
+    static NOTIFYING: Cell<usize> = const { Cell::new(0) };
+    static RUNNING: Cell<bool> = const { Cell::new(false) };
+    static PENDING: RefCell<Vec<Arc<dyn PendingSubscription + Send + Sync>>> =
+        const { RefCell::new(Vec::new()) };
+}
+
+/// Runs subscriptions that are notified during `fun` once it has finished.
+pub(crate) fn notifying(fun: impl FnOnce()) {
+    struct Guard;
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            let depth = NOTIFYING.get() - 1;
+            NOTIFYING.set(depth);
+            if depth == 0 {
+                run_pending();
+            }
+        }
+    }
+
+    NOTIFYING.set(NOTIFYING.get() + 1);
+    let _guard = Guard;
+    fun();
+}
+
+fn run_pending() {
+    // subscriptions that are notified by a callback are added to the queue, and run by the loop
+    // that is already running
+    if RUNNING.replace(true) {
+        return;
+    }
+    struct Guard;
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            RUNNING.set(false);
+        }
+    }
+
+    let _guard = Guard;
+    loop {
+        let next = PENDING.with_borrow_mut(|pending| {
+            (!pending.is_empty()).then(|| pending.remove(0))
+        });
+        let Some(next) = next else {
+            break;
+        };
+        next.run();
+    }
+}
@@ -0,0 +1,285 @@
+//! Inspecting the reactive graph, with the `graph-introspection` feature.
+//!
+//! With the feature enabled, every signal, trigger, memo, and effect records where it was
+//! created, an optional name given with [`Named::named`], and how often it was notified and
+//! run. [`snapshot`] returns all of the nodes that are still alive, along with the
+//! subscriptions between them, which makes it possible to build tools like a devtools overlay
+//! that answers “why did this run?”
+//!
+//! ```rust
+//! # use reactive_graph::prelude::*;
+//! # use reactive_graph::computed::Memo;
+//! # use reactive_graph::signal::RwSignal;
+//! # use reactive_graph::introspect::{snapshot, Named};
+//! # let owner = reactive_graph::owner::Owner::new(); owner.set();
+//! let price = RwSignal::new(10).named("price");
+//! let total = Memo::new(move |_| price.get() * 2).named("cart_total");
+//! # let _guard = reactive_graph::diagnostics::SpecialNonReactiveZone::enter();
+//! assert_eq!(total.get(), 20);
+//!
+//! let graph = snapshot();
+//! let price = graph.find("price").unwrap();
+//! let total = graph.find("cart_total").unwrap();
+//! assert!(graph.edges.contains(&(price.id, total.id).into()));
+//! ```
+
+use crate::{
+    effect::{Effect, RenderEffect, StoredEffect},
+    graph::{ToAnySource, ToAnySubscriber},
+    owner::Storage,
+};
+use or_poisoned::OrPoisoned;
+use rustc_hash::FxHashMap;
+use std::{
+    panic::Location,
+    sync::{Arc, OnceLock, RwLock, Weak},
+};
+
+/// The kind of a node in the reactive graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NodeKind {
+    /// A signal.
+    Signal,
+    /// A trigger, i.e., a signal without a value.
+    Trigger,
+    /// A memo.
+    Memo,
+    /// An effect.
+    Effect,
+    /// A render effect, like those used to update the DOM.
+    RenderEffect,
+}
+
+/// A node in a [`GraphSnapshot`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeSnapshot {
+    /// Identifies the node in [`GraphSnapshot::edges`]. This is only unique among the nodes
+    /// that are alive at the same time.
+    pub id: usize,
+    /// What kind of node this is.
+    pub kind: NodeKind,
+    /// The name given with [`Named::named`], if any.
+    pub name: Option<String>,
+    /// Where the node was created.
+    pub defined_at: &'static Location<'static>,
+    /// How many times the node has been notified: for a signal, that it changed, and for a
+    /// memo or an effect, that one of its sources may have changed.
+    pub times_notified: usize,
+    /// How many times a memo or effect has run. This is always `0` for signals.
+    pub times_run: usize,
+}
+
+/// A subscription between two nodes in a [`GraphSnapshot`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Edge {
+    /// The id of the node that is read.
+    pub source: usize,
+    /// The id of the memo or effect that reads it.
+    pub subscriber: usize,
+}
+
+impl From<(usize, usize)> for Edge {
+    fn from((source, subscriber): (usize, usize)) -> Self {
+        Self { source, subscriber }
+    }
+}
+
+/// The nodes of the reactive graph that were alive when [`snapshot`] was called, and the
+/// subscriptions between them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GraphSnapshot {
+    /// The nodes, in no particular order.
+    pub nodes: Vec<NodeSnapshot>,
+    /// The subscriptions between the nodes, from the last time each subscriber ran.
+    pub edges: Vec<Edge>,
+}
+
+impl GraphSnapshot {
+    /// Returns the node with the given id.
+    pub fn node(&self, id: usize) -> Option<&NodeSnapshot> {
+        self.nodes.iter().find(|node| node.id == id)
+    }
+
+    /// Returns the first node with the given name.
+    pub fn find(&self, name: &str) -> Option<&NodeSnapshot> {
+        self.nodes
+            .iter()
+            .find(|node| node.name.as_deref() == Some(name))
+    }
+
+    /// Returns the nodes that the given node reads.
+    pub fn sources(&self, id: usize) -> impl Iterator<Item = &NodeSnapshot> {
+        self.edges
+            .iter()
+            .filter(move |edge| edge.subscriber == id)
+            .filter_map(|edge| self.node(edge.source))
+    }
+
+    /// Returns the nodes that read the given node.
+    pub fn subscribers(
+        &self,
+        id: usize,
+    ) -> impl Iterator<Item = &NodeSnapshot> {
+        self.edges
+            .iter()
+            .filter(move |edge| edge.source == id)
+            .filter_map(|edge| self.node(edge.subscriber))
+    }
+}
+
+/// Returns the nodes of the reactive graph that are currently alive, and the subscriptions
+/// between them.
+///
+/// This only copies the information that has been recorded, so it is cheap enough to call
+/// regularly, for example once a second.
+pub fn snapshot() -> GraphSnapshot {
+    let mut registry = registry().write().or_poisoned();
+    registry.prune();
+
+    let nodes = registry
+        .nodes
+        .iter()
+        .map(|(id, node)| NodeSnapshot {
+            id: *id,
+            kind: node.kind,
+            name: node.name.clone(),
+            defined_at: node.defined_at,
+            times_notified: node.times_notified,
+            times_run: node.times_run,
+        })
+        .collect();
+    let edges = registry
+        .nodes
+        .iter()
+        .flat_map(|(id, node)| {
+            node.sources.iter().map(|source| Edge {
+                source: *source,
+                subscriber: *id,
+            })
+        })
+        .filter(|edge| registry.nodes.contains_key(&edge.source))
+        .collect();
+    GraphSnapshot { nodes, edges }
+}
+
+/// Gives a reactive node a name, which shows up in a [`snapshot`].
+pub trait Named {
+    /// Names this node, and returns it.
+    fn named(self, name: impl Into<String>) -> Self;
+}
+
+impl<T: ToAnySource> Named for T {
+    fn named(self, name: impl Into<String>) -> Self {
+        set_name(self.to_any_source().0, name.into());
+        self
+    }
+}
+
+impl<S> Named for Effect<S>
+where
+    S: Storage<StoredEffect>,
+{
+    fn named(self, name: impl Into<String>) -> Self {
+        set_name(self.to_any_subscriber().0, name.into());
+        self
+    }
+}
+
+impl<T> Named for RenderEffect<T> {
+    fn named(self, name: impl Into<String>) -> Self {
+        set_name(self.to_any_subscriber().0, name.into());
+        self
+    }
+}
+
+fn set_name(id: usize, name: String) {
+    if let Some(node) = registry().write().or_poisoned().nodes.get_mut(&id) {
+        node.name = Some(name);
+    }
+}
+
+struct NodeRecord {
+    kind: NodeKind,
+    name: Option<String>,
+    defined_at: &'static Location<'static>,
+    times_notified: usize,
+    times_run: usize,
+    sources: Vec<usize>,
+    alive: Box<dyn Fn() -> bool + Send + Sync>,
+}
+
+#[derive(Default)]
+struct Registry {
+    nodes: FxHashMap<usize, NodeRecord>,
+    // the number of nodes after the last time dead nodes were removed
+    live_after_prune: usize,
+}
+
+impl Registry {
+    fn prune(&mut self) {
+        self.nodes.retain(|_, node| (node.alive)());
+        self.live_after_prune = self.nodes.len();
+    }
+}
+
+static REGISTRY: OnceLock<RwLock<Registry>> = OnceLock::new();
+
+fn registry() -> &'static RwLock<Registry> {
+    REGISTRY.get_or_init(Default::default)
+}
+
+/// Records a new node, identified by the address of `node`, which is how the node is
+/// identified by [`AnySource`](crate::graph::AnySource) and
+/// [`AnySubscriber`](crate::graph::AnySubscriber).
+pub(crate) fn register<T>(
+    node: &Arc<T>,
+    kind: NodeKind,
+    defined_at: &'static Location<'static>,
+) where
+    T: Send + Sync + 'static,
+{
+    let weak = Arc::downgrade(node);
+    let record = NodeRecord {
+        kind,
+        name: None,
+        defined_at,
+        times_notified: 0,
+        times_run: 0,
+        sources: Vec::new(),
+        alive: Box::new(move || Weak::strong_count(&weak) > 0),
+    };
+
+    let mut registry = registry().write().or_poisoned();
+    // nodes are only removed from the registry when they are looked at, so remove the dead
+    // ones from time to time in case no one is looking
+    if registry.nodes.len() >= (registry.live_after_prune * 2).max(1024) {
+        registry.prune();
+    }
+    // a dead node with the same address is replaced
+    registry.nodes.insert(Arc::as_ptr(node) as usize, record);
+}
+
+pub(crate) fn notified(id: usize) {
+    if let Some(node) = registry().write().or_poisoned().nodes.get_mut(&id) {
+        node.times_notified += 1;
+    }
+}
+
+/// Records that a subscriber is about to run, which also means that it is about to track its
+/// sources again.
+pub(crate) fn will_run(id: usize) {
+    if let Some(node) = registry().write().or_poisoned().nodes.get_mut(&id) {
+        node.times_run += 1;
+        node.sources.clear();
+    }
+}
+
+pub(crate) fn tracked(source: usize, subscriber: usize) {
+    if let Some(node) =
+        registry().write().or_poisoned().nodes.get_mut(&subscriber)
+    {
+        if !node.sources.contains(&source) {
+            node.sources.push(source);
+        }
+    }
+}
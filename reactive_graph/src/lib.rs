@@ -80,10 +80,15 @@ pub mod computed;
 pub mod diagnostics;
 pub mod effect;
 pub mod graph;
+#[cfg(feature = "graph-introspection")]
+pub mod introspect;
 pub mod owner;
+#[cfg(feature = "persist")]
+pub mod persist;
 #[cfg(feature = "serde")]
 mod serde;
 pub mod signal;
+pub mod timing;
 pub mod traits;
 pub mod transition;
 pub mod wrappers;
@@ -95,7 +100,7 @@ mod nightly;
 
 /// Reexports frequently-used traits.
 pub mod prelude {
-    pub use crate::{owner::FromLocal, traits::*};
+    pub use crate::{graph::Subscribe, owner::FromLocal, traits::*};
 }
 
 // TODO remove this, it's just useful while developing
@@ -123,6 +128,32 @@ pub fn log_warning(text: Arguments) {
     }
 }
 
+/// Runs the given function, waiting until it has finished to notify any effects that depend on
+/// signals it updates.
+///
+/// Each effect then runs at most once, with the final values of the signals, rather than being
+/// notified after each update. Batches can be nested, in which case effects are notified when the
+/// outermost batch finishes. If the function panics, effects are still notified as it unwinds.
+///
+/// This only delays effects. Memos and derived signals read inside the batch see the latest
+/// values.
+///
+/// ```rust
+/// # use reactive_graph::prelude::*;
+/// # use reactive_graph::signal::RwSignal;
+/// # let owner = reactive_graph::owner::Owner::new(); owner.set();
+/// let first = RwSignal::new(String::new());
+/// let last = RwSignal::new(String::new());
+///
+/// reactive_graph::batch(|| {
+///     first.set("Ada".to_string());
+///     last.set("Lovelace".to_string());
+/// });
+/// ```
+pub fn batch<T>(fun: impl FnOnce() -> T) -> T {
+    channel::batch(fun)
+}
+
 /// Calls [`Executor::spawn`], but ensures that the task also runs in the current arena, if
 /// multithreaded arena sandboxing is enabled.
 pub fn spawn(task: impl Future<Output = ()> + Send + 'static) {
@@ -132,6 +163,18 @@ pub fn spawn(task: impl Future<Output = ()> + Send + 'static) {
     any_spawner::Executor::spawn(task);
 }
 
+/// Spawns a task that cannot be sent across threads, with the [`CustomSpawner`] set for the
+/// current [`Owner`] or one of its ancestors with [`Owner::set_spawner`], or with
+/// [`Executor::spawn_local`] if there is none.
+///
+/// [`CustomSpawner`]: any_spawner::CustomSpawner
+pub fn spawn_local(task: impl Future<Output = ()> + 'static) {
+    match owner::Owner::current().and_then(|owner| owner.spawner()) {
+        Some(spawner) => spawner.spawn_local(Box::pin(task)),
+        None => any_spawner::Executor::spawn_local(task),
+    }
+}
+
 /// Calls [`Executor::spawn_local`], but ensures that the task runs under the current reactive [`Owner`]
 /// and [`Observed`]. Does not cancel the task if the owner is cleaned up.
 pub fn spawn_local_scoped(task: impl Future<Output = ()> + 'static) {
@@ -140,7 +183,7 @@ pub fn spawn_local_scoped(task: impl Future<Output = ()> + 'static) {
     #[cfg(feature = "sandboxed-arenas")]
     let task = owner::Sandboxed::new(task);
 
-    any_spawner::Executor::spawn_local(task);
+    spawn_local(task);
 }
 
 /// Calls [`Executor::spawn_local`], but ensures that the task runs under the current reactive [`Owner`]
@@ -160,7 +203,7 @@ pub fn spawn_local_scoped_with_cancellation(
     #[cfg(feature = "sandboxed-arenas")]
     let task = owner::Sandboxed::new(task);
 
-    any_spawner::Executor::spawn_local(async move {
+    spawn_local(async move {
         _ = task.await;
     });
 }
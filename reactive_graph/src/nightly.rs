@@ -7,7 +7,10 @@ use crate::{
     },
     traits::{Get, Set},
     wrappers::{
-        read::{ArcSignal, MaybeProp, MaybeSignal, Signal},
+        read::{
+            ArcMappedSignal, ArcSignal, MappedSignal, MaybeProp, MaybeSignal,
+            Signal,
+        },
         write::SignalSetter,
     },
 };
@@ -77,10 +80,10 @@ macro_rules! impl_set_fn_traits_arena {
 }
 
 macro_rules! impl_get_fn_traits_get {
-    ($($ty:ident),*) => {
+    ($($ty:ident $(<$($param:ident),*>)?),*) => {
         $(
             #[cfg(feature = "nightly")]
-            impl<T> FnOnce<()> for $ty<T> where $ty<T>: Get {
+            impl<T $($(, $param: 'static)*)?> FnOnce<()> for $ty<T $($(, $param)*)?> where $ty<T $($(, $param)*)?>: Get {
                 type Output = <Self as Get>::Value;
 
                 #[inline(always)]
@@ -90,7 +93,7 @@ macro_rules! impl_get_fn_traits_get {
             }
 
             #[cfg(feature = "nightly")]
-            impl<T> FnMut<()> for $ty<T> where $ty<T>: Get {
+            impl<T $($(, $param: 'static)*)?> FnMut<()> for $ty<T $($(, $param)*)?> where $ty<T $($(, $param)*)?>: Get {
                 #[inline(always)]
                 extern "rust-call" fn call_mut(&mut self, _args: ()) -> Self::Output {
                     self.get()
@@ -98,7 +101,7 @@ macro_rules! impl_get_fn_traits_get {
             }
 
             #[cfg(feature = "nightly")]
-            impl<T> Fn<()> for $ty<T> where $ty<T>: Get {
+            impl<T $($(, $param: 'static)*)?> Fn<()> for $ty<T $($(, $param)*)?> where $ty<T $($(, $param)*)?>: Get {
                 #[inline(always)]
                 extern "rust-call" fn call(&self, _args: ()) -> Self::Output {
                     self.get()
@@ -109,10 +112,10 @@ macro_rules! impl_get_fn_traits_get {
 }
 
 macro_rules! impl_get_fn_traits_get_arena {
-    ($($ty:ident),*) => {
+    ($($ty:ident $(<$($param:ident),*>)?),*) => {
         $(
             #[cfg(feature = "nightly")]
-            impl<T, S> FnOnce<()> for $ty<T, S> where $ty<T, S>: Get, S: Storage<T> + Storage<Option<T>> {
+            impl<T, $($($param: 'static,)*)? S> FnOnce<()> for $ty<T, $($($param,)*)? S> where $ty<T, $($($param,)*)? S>: Get, S: Storage<T> + Storage<Option<T>> {
                 type Output = <Self as Get>::Value;
 
                 #[inline(always)]
@@ -122,7 +125,7 @@ macro_rules! impl_get_fn_traits_get_arena {
             }
 
             #[cfg(feature = "nightly")]
-            impl<T, S> FnMut<()> for $ty<T, S> where $ty<T, S>: Get, S: Storage<T> + Storage<Option<T>> {
+            impl<T, $($($param: 'static,)*)? S> FnMut<()> for $ty<T, $($($param,)*)? S> where $ty<T, $($($param,)*)? S>: Get, S: Storage<T> + Storage<Option<T>> {
                 #[inline(always)]
                 extern "rust-call" fn call_mut(&mut self, _args: ()) -> Self::Output {
                     self.get()
@@ -130,7 +133,7 @@ macro_rules! impl_get_fn_traits_get_arena {
             }
 
             #[cfg(feature = "nightly")]
-            impl<T, S> Fn<()> for $ty<T, S> where $ty<T, S>: Get, S: Storage<T> + Storage<Option<T>> {
+            impl<T, $($($param: 'static,)*)? S> Fn<()> for $ty<T, $($($param,)*)? S> where $ty<T, $($($param,)*)? S>: Get, S: Storage<T> + Storage<Option<T>> {
                 #[inline(always)]
                 extern "rust-call" fn call(&self, _args: ()) -> Self::Output {
                     self.get()
@@ -140,7 +143,7 @@ macro_rules! impl_get_fn_traits_get_arena {
     };
 }
 
-impl_get_fn_traits_get![ArcReadSignal, ArcRwSignal];
+impl_get_fn_traits_get![ArcReadSignal, ArcRwSignal, ArcMappedSignal<G>];
 impl_get_fn_traits_get_arena![
     ReadSignal,
     RwSignal,
@@ -149,7 +152,8 @@ impl_get_fn_traits_get_arena![
     Signal,
     MaybeSignal,
     Memo,
-    MaybeProp
+    MaybeProp,
+    MappedSignal<G>
 ];
 impl_set_fn_traits![ArcRwSignal, ArcWriteSignal];
 impl_set_fn_traits_arena![RwSignal, WriteSignal, SignalSetter];
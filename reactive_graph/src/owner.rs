@@ -1,5 +1,6 @@
 //! The reactive ownership model, which manages effect cancelation, cleanups, and arena allocation.
 
+use crate::graph::{AnySubscriber, ReactiveNode};
 #[cfg(feature = "hydration")]
 use hydration_context::SharedContext;
 use or_poisoned::OrPoisoned;
@@ -9,7 +10,10 @@ use std::{
     cell::RefCell,
     fmt::Debug,
     mem,
-    sync::{Arc, RwLock, Weak},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, RwLock, Weak,
+    },
 };
 
 mod arc_stored_value;
@@ -70,6 +74,10 @@ thread_local! {
     static OWNER: RefCell<Option<Owner>> = Default::default();
 }
 
+// the number of paused owners, so that checking whether an owner is paused doesn't need to walk
+// its ancestors when none are
+static PAUSED_OWNERS: AtomicUsize = AtomicUsize::new(0);
+
 impl Owner {
     /// Returns a unique identifier for this owner, which can be used to identify it for debugging
     /// purposes.
@@ -124,6 +132,8 @@ impl Owner {
                 contexts: Default::default(),
                 cleanups: Default::default(),
                 children: Default::default(),
+                paused: false,
+                paused_subscribers: Default::default(),
                 #[cfg(feature = "sandboxed-arenas")]
                 arena: parent
                     .as_ref()
@@ -161,6 +171,8 @@ impl Owner {
                 contexts: Default::default(),
                 cleanups: Default::default(),
                 children: Default::default(),
+                paused: false,
+                paused_subscribers: Default::default(),
                 #[cfg(feature = "sandboxed-arenas")]
                 arena: Default::default(),
             })),
@@ -183,6 +195,8 @@ impl Owner {
                 contexts: Default::default(),
                 cleanups: Default::default(),
                 children: Default::default(),
+                paused: false,
+                paused_subscribers: Default::default(),
                 #[cfg(feature = "sandboxed-arenas")]
                 arena,
             })),
@@ -197,6 +211,33 @@ impl Owner {
         child
     }
 
+    /// Creates a new child of this `Owner`, and runs the given function with it as the current
+    /// `Owner`.
+    ///
+    /// Returns the child along with the function's result. The child is cleaned up once the
+    /// returned handle (and any clones of it) are dropped, or when this `Owner` is cleaned up, so
+    /// it should be kept for as long as anything created in it is needed.
+    ///
+    /// This can be used to scope context to part of a tree without creating a component, by
+    /// calling [`provide_context`](crate::owner::provide_context) in the function.
+    ///
+    /// ```rust
+    /// # use reactive_graph::owner::*;
+    /// # let owner = Owner::new(); owner.set();
+    /// provide_context("parent");
+    /// let (_child, value) = owner.with_child(|| {
+    ///     provide_context("child");
+    ///     use_context::<&'static str>()
+    /// });
+    /// assert_eq!(value, Some("child"));
+    /// assert_eq!(use_context::<&'static str>(), Some("parent"));
+    /// ```
+    pub fn with_child<T>(&self, fun: impl FnOnce() -> T) -> (Owner, T) {
+        let child = self.child();
+        let value = child.with(fun);
+        (child, value)
+    }
+
     /// Sets this as the current `Owner`.
     pub fn set(&self) {
         OWNER.with_borrow_mut(|owner| *owner = Some(self.clone()));
@@ -250,6 +291,70 @@ impl Owner {
         }
     }
 
+    /// Pauses the [`RenderEffect`](crate::effect::RenderEffect)s created under this owner or any
+    /// of its descendants.
+    ///
+    /// A paused render effect does not run when its sources change. Instead, it runs once the owner
+    /// is [resumed](Owner::resume), if any of its sources changed in the meantime. This can be used
+    /// to keep a view that is not currently mounted, without updating it.
+    pub fn pause(&self) {
+        let mut lock = self.inner.write().or_poisoned();
+        if !lock.paused {
+            lock.paused = true;
+            PAUSED_OWNERS.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Resumes the render effects paused by [`Owner::pause`]. Any of them that were notified while
+    /// paused will run again if their sources changed.
+    pub fn resume(&self) {
+        let subscribers = {
+            let mut lock = self.inner.write().or_poisoned();
+            if !lock.paused {
+                return;
+            }
+            lock.paused = false;
+            PAUSED_OWNERS.fetch_sub(1, Ordering::Relaxed);
+            mem::take(&mut lock.paused_subscribers)
+        };
+        for subscriber in subscribers {
+            subscriber.mark_check();
+        }
+    }
+
+    /// Whether this owner or any of its ancestors has been paused with [`Owner::pause`].
+    pub fn paused(&self) -> bool {
+        self.paused_ancestor().is_some()
+    }
+
+    fn paused_ancestor(&self) -> Option<Arc<RwLock<OwnerInner>>> {
+        if PAUSED_OWNERS.load(Ordering::Relaxed) == 0 {
+            return None;
+        }
+        let mut current = Some(Arc::clone(&self.inner));
+        while let Some(owner) = current {
+            let lock = owner.read().or_poisoned();
+            if lock.paused {
+                drop(lock);
+                return Some(owner);
+            }
+            current = lock.parent.as_ref().and_then(|parent| parent.upgrade());
+        }
+        None
+    }
+
+    // runs the subscriber once the paused owner is resumed, rather than now
+    pub(crate) fn defer_until_resumed(&self, subscriber: AnySubscriber) {
+        match self.paused_ancestor() {
+            Some(paused) => paused
+                .write()
+                .or_poisoned()
+                .paused_subscribers
+                .push(subscriber),
+            None => subscriber.mark_check(),
+        }
+    }
+
     fn register(&self, node: NodeId) {
         self.inner.write().or_poisoned().nodes.push(node);
     }
@@ -353,6 +458,9 @@ pub(crate) struct OwnerInner {
     pub contexts: FxHashMap<TypeId, Box<dyn Any + Send + Sync>>,
     pub cleanups: Vec<Box<dyn FnOnce() + Send + Sync>>,
     pub children: Vec<Weak<RwLock<OwnerInner>>>,
+    paused: bool,
+    // render effects that were notified while this was paused
+    paused_subscribers: Vec<AnySubscriber>,
     #[cfg(feature = "sandboxed-arenas")]
     arena: Arc<RwLock<ArenaMap>>,
 }
@@ -364,12 +472,17 @@ impl Debug for OwnerInner {
             .field("nodes", &self.nodes)
             .field("contexts", &self.contexts)
             .field("cleanups", &self.cleanups.len())
+            .field("paused", &self.paused)
             .finish()
     }
 }
 
 impl Drop for OwnerInner {
     fn drop(&mut self) {
+        if self.paused {
+            PAUSED_OWNERS.fetch_sub(1, Ordering::Relaxed);
+        }
+
         for child in std::mem::take(&mut self.children) {
             if let Some(child) = child.upgrade() {
                 child.cleanup();
@@ -1,11 +1,41 @@
 use crate::owner::Owner;
+use any_spawner::CustomSpawner;
 use or_poisoned::OrPoisoned;
 use std::{
     any::{Any, TypeId},
     collections::VecDeque,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
 };
 
+// the spawner set with `Owner::set_spawner`, which is looked up like any other context
+#[derive(Clone)]
+struct ScopedSpawner(Arc<dyn CustomSpawner>);
+
+// most applications never set a spawner, so they can skip looking one up
+static HAS_SCOPED_SPAWNER: AtomicBool = AtomicBool::new(false);
+
 impl Owner {
+    /// Sets the [`CustomSpawner`] used to spawn tasks that cannot be sent across threads (like
+    /// effects and suspended views) for this owner and its descendants, instead of the global
+    /// [`Executor`](any_spawner::Executor).
+    pub fn set_spawner(&self, spawner: Arc<dyn CustomSpawner>) {
+        HAS_SCOPED_SPAWNER.store(true, Ordering::Relaxed);
+        self.provide_context(ScopedSpawner(spawner));
+    }
+
+    /// Returns the spawner set for this owner or its nearest ancestor with
+    /// [`Owner::set_spawner`], if any.
+    pub fn spawner(&self) -> Option<Arc<dyn CustomSpawner>> {
+        if !HAS_SCOPED_SPAWNER.load(Ordering::Relaxed) {
+            return None;
+        }
+        self.use_context::<ScopedSpawner>()
+            .map(|ScopedSpawner(spawner)| spawner)
+    }
+
     fn provide_context<T: Send + Sync + 'static>(&self, value: T) {
         self.inner
             .write()
@@ -168,6 +198,29 @@ pub fn provide_context<T: Send + Sync + 'static>(value: T) {
     }
 }
 
+/// Provides a context value of type `T` to the given [`Owner`] and all of its descendants,
+/// rather than to the current owner.
+///
+/// This is usually used with an owner created by [`Owner::child`] or [`Owner::with_child`], so
+/// that the value is only visible in that part of the tree. Providing a value of a type that the
+/// owner already has replaces it, and shadows any value of that type provided by its ancestors.
+///
+/// ```rust
+/// # use reactive_graph::owner::*;
+/// # let owner = Owner::new(); owner.set();
+/// let first = owner.child();
+/// let second = owner.child();
+/// provide_context_in(&first, 1u8);
+/// provide_context_in(&second, 2u8);
+///
+/// assert_eq!(first.with(use_context::<u8>), Some(1));
+/// assert_eq!(second.with(use_context::<u8>), Some(2));
+/// assert_eq!(use_context::<u8>(), None);
+/// ```
+pub fn provide_context_in<T: Send + Sync + 'static>(owner: &Owner, value: T) {
+    owner.provide_context(value);
+}
+
 /// Extracts a context value of type `T` from the reactive system by traversing
 /// it upwards, beginning from the current reactive [`Owner`] and iterating
 /// through its parents, if any. When the value is found, it is cloned.
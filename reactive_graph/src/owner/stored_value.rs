@@ -20,6 +20,13 @@ use std::{
 /// and [`RwSignal`](crate::signal::RwSignal)), it is `Copy` and `'static`. Unlike the signal
 /// types, it is not reactive; accessing it does not cause effects to subscribe, and
 /// updating it does not notify anything else.
+///
+/// The value is dropped when the [`Owner`](crate::owner::Owner) it was created in is disposed.
+/// Accessing it after that (for example, from an async callback that outlives its component)
+/// panics, but the `try_` accessors, like
+/// [`try_get_value`](crate::traits::GetValue::try_get_value), return `None` instead. To keep the
+/// value alive for as long as it is used, use an [`ArcStoredValue`], which is reference-counted;
+/// the two can be converted into one another.
 pub struct StoredValue<T, S = SyncStorage> {
     value: ArenaItem<ArcStoredValue<T>, S>,
     #[cfg(debug_assertions)]
@@ -176,6 +183,20 @@ where
     }
 }
 
+impl<T> FromLocal<ArcStoredValue<T>> for StoredValue<T, LocalStorage>
+where
+    T: 'static,
+{
+    #[track_caller]
+    fn from_local(value: ArcStoredValue<T>) -> Self {
+        StoredValue {
+            #[cfg(debug_assertions)]
+            defined_at: Location::caller(),
+            value: ArenaItem::new_with_storage(value),
+        }
+    }
+}
+
 impl<T, S> From<StoredValue<T, S>> for ArcStoredValue<T>
 where
     S: Storage<ArcStoredValue<T>>,
@@ -0,0 +1,313 @@
+//! Signals that are mirrored into some kind of persistent storage.
+//!
+//! A [`Persisted`] signal writes its value to a [`Storage`] whenever it changes, serialized as
+//! JSON. It always starts out with its default value, even if the storage already holds one, so
+//! that the server and the client render the same thing while hydrating. Call
+//! [`Persisted::sync`] once the app has been mounted (for example, in an effect) to update the
+//! signal from storage.
+//!
+//! ```rust
+//! # use reactive_graph::prelude::*;
+//! # use reactive_graph::persist::{persisted_signal, MemoryStorage, Storage};
+//! # _ = any_spawner::Executor::init_manual();
+//! # let owner = reactive_graph::owner::Owner::new(); owner.set();
+//! let storage = MemoryStorage::default();
+//! storage.store("theme", "\"dark\"");
+//!
+//! let theme = persisted_signal("theme", String::from("light"), storage);
+//! assert_eq!(theme.signal().get_untracked(), "light");
+//!
+//! // after mounting
+//! theme.sync();
+//! assert_eq!(theme.signal().get_untracked(), "dark");
+//! ```
+
+use crate::{
+    effect::Effect,
+    owner::Owner,
+    signal::RwSignal,
+    traits::{Set, With},
+};
+use any_spawner::Executor;
+use or_poisoned::OrPoisoned;
+use rustc_hash::FxHashMap;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    fmt::Debug,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+/// A place that a [`Persisted`] signal can load its value from and save it to.
+pub trait Storage: Send + Sync + 'static {
+    /// Loads the value saved for `key`, if any.
+    fn load(&self, key: &str) -> Option<String>;
+
+    /// Saves `value` for `key`.
+    fn store(&self, key: &str, value: &str);
+}
+
+/// Storage in the browser’s [`localStorage`](https://developer.mozilla.org/en-US/docs/Web/API/Window/localStorage).
+///
+/// (Not to be confused with [`owner::LocalStorage`](crate::owner::LocalStorage), which is about
+/// where the reactive system keeps a value.)
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LocalStorage;
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+impl LocalStorage {
+    fn storage() -> Option<web_sys::Storage> {
+        web_sys::window()?.local_storage().ok()?
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+impl Storage for LocalStorage {
+    fn load(&self, key: &str) -> Option<String> {
+        Self::storage()?.get_item(key).ok()?
+    }
+
+    fn store(&self, key: &str, value: &str) {
+        if let Some(storage) = Self::storage() {
+            _ = storage.set_item(key, value);
+        }
+    }
+}
+
+/// Storage that never holds anything, for example during server rendering.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoopStorage;
+
+impl Storage for NoopStorage {
+    fn load(&self, _key: &str) -> Option<String> {
+        None
+    }
+
+    fn store(&self, _key: &str, _value: &str) {}
+}
+
+/// Storage that keeps values in memory. Clones share the same values, which makes this useful
+/// for testing.
+#[derive(Debug, Default, Clone)]
+pub struct MemoryStorage(Arc<Mutex<FxHashMap<String, String>>>);
+
+impl Storage for MemoryStorage {
+    fn load(&self, key: &str) -> Option<String> {
+        self.0.lock().or_poisoned().get(key).cloned()
+    }
+
+    fn store(&self, key: &str, value: &str) {
+        self.0
+            .lock()
+            .or_poisoned()
+            .insert(key.to_string(), value.to_string());
+    }
+}
+
+/// Options for a [`Persisted`] signal.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PersistOptions {
+    debounce: Duration,
+}
+
+impl PersistOptions {
+    /// Only writes to storage once the signal has stopped changing for `delay`, rather than after
+    /// every change.
+    pub fn debounce(mut self, delay: Duration) -> Self {
+        self.debounce = delay;
+        self
+    }
+}
+
+struct PersistState {
+    key: String,
+    storage: Box<dyn Storage>,
+    // the serialized value that was last loaded from or written to storage
+    last: Option<String>,
+    pending: Option<String>,
+    // increases with every change, so that a timer knows whether it is still the latest one
+    generation: usize,
+}
+
+impl PersistState {
+    fn write(&mut self, value: String) {
+        if self.last.as_ref() != Some(&value) {
+            self.storage.store(&self.key, &value);
+            self.last = Some(value);
+        }
+    }
+
+    fn flush(&mut self) {
+        self.generation += 1;
+        if let Some(value) = self.pending.take() {
+            self.write(value);
+        }
+    }
+}
+
+/// A signal whose value is saved to a [`Storage`] whenever it changes.
+///
+/// See the [module documentation](self) for more.
+pub struct Persisted<T>
+where
+    T: 'static,
+{
+    signal: RwSignal<T>,
+    state: Arc<Mutex<PersistState>>,
+}
+
+impl<T> Clone for Persisted<T> {
+    fn clone(&self) -> Self {
+        Self {
+            signal: self.signal,
+            state: Arc::clone(&self.state),
+        }
+    }
+}
+
+impl<T> Debug for Persisted<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Persisted")
+            .field("signal", &self.signal)
+            .field("key", &self.state.lock().or_poisoned().key)
+            .finish()
+    }
+}
+
+impl<T> Persisted<T>
+where
+    T: Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+    /// The signal holding the value.
+    pub fn signal(&self) -> RwSignal<T> {
+        self.signal
+    }
+
+    /// Updates the signal to the value in storage, if there is one.
+    ///
+    /// Signals always start out with their default value, so that server rendering and
+    /// hydration agree. This should be called once the app has been mounted.
+    pub fn sync(&self) {
+        let value = {
+            let mut state = self.state.lock().or_poisoned();
+            let Some(stored) = state.storage.load(&state.key) else {
+                return;
+            };
+            match serde_json::from_str::<T>(&stored) {
+                Ok(value) => {
+                    // the stored value wins over any write that is still waiting
+                    state.pending = None;
+                    state.generation += 1;
+                    state.last = Some(stored);
+                    value
+                }
+                Err(e) => {
+                    crate::log_warning(format_args!(
+                        "Could not deserialize the value persisted for {:?}: \
+                         {e}",
+                        state.key
+                    ));
+                    return;
+                }
+            }
+        };
+        self.signal.set(value);
+    }
+
+    /// Immediately writes a value that is waiting to be written to storage.
+    pub fn flush(&self) {
+        self.state.lock().or_poisoned().flush();
+    }
+}
+
+impl<T> RwSignal<T>
+where
+    T: Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+    /// Creates a signal that is saved to `storage` under `key`. See [`persisted_signal`].
+    #[track_caller]
+    pub fn persisted(
+        key: impl Into<String>,
+        default: T,
+        storage: impl Storage,
+    ) -> Persisted<T> {
+        persisted_signal(key, default, storage)
+    }
+}
+
+/// Creates a signal that starts out as `default`, and is saved to `storage` under `key` after
+/// every change.
+#[track_caller]
+pub fn persisted_signal<T>(
+    key: impl Into<String>,
+    default: T,
+    storage: impl Storage,
+) -> Persisted<T>
+where
+    T: Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+    persisted_signal_with_options(key, default, storage, Default::default())
+}
+
+/// Creates a signal that starts out as `default`, and is saved to `storage` under `key` when it
+/// changes, as configured by `options`.
+///
+/// If the current [`Owner`] is disposed while a write is waiting, it is written right away.
+#[track_caller]
+pub fn persisted_signal_with_options<T>(
+    key: impl Into<String>,
+    default: T,
+    storage: impl Storage,
+    options: PersistOptions,
+) -> Persisted<T>
+where
+    T: Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+    let signal = RwSignal::new(default);
+    let state = Arc::new(Mutex::new(PersistState {
+        key: key.into(),
+        storage: Box::new(storage),
+        last: None,
+        pending: None,
+        generation: 0,
+    }));
+
+    Effect::watch(
+        move || signal.with(|value| serde_json::to_string(value).ok()),
+        {
+            let state = Arc::clone(&state);
+            move |value: &Option<String>, _, _: Option<()>| {
+                let Some(value) = value.clone() else {
+                    return;
+                };
+                let mut guard = state.lock().or_poisoned();
+                if options.debounce.is_zero() {
+                    guard.write(value);
+                    return;
+                }
+                guard.pending = Some(value);
+                guard.generation += 1;
+                let generation = guard.generation;
+                drop(guard);
+
+                let state = Arc::clone(&state);
+                crate::spawn_local(async move {
+                    Executor::sleep(options.debounce).await;
+                    let mut state = state.lock().or_poisoned();
+                    if state.generation == generation {
+                        state.flush();
+                    }
+                });
+            }
+        },
+        false,
+    );
+
+    Owner::on_cleanup({
+        let state = Arc::clone(&state);
+        move || state.lock().or_poisoned().flush()
+    });
+
+    Persisted { signal, state }
+}
@@ -153,11 +153,18 @@ impl<T> ArcRwSignal<T> {
     )]
     #[track_caller]
     pub fn new(value: T) -> Self {
+        let inner = Arc::new(RwLock::new(SubscriberSet::new()));
+        #[cfg(feature = "graph-introspection")]
+        crate::introspect::register(
+            &inner,
+            crate::introspect::NodeKind::Signal,
+            Location::caller(),
+        );
         Self {
             #[cfg(debug_assertions)]
             defined_at: Location::caller(),
             value: Arc::new(RwLock::new(value)),
-            inner: Arc::new(RwLock::new(SubscriberSet::new())),
+            inner,
         }
     }
 
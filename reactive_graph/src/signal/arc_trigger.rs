@@ -1,7 +1,7 @@
 use super::subscriber_traits::AsSubscriberSet;
 use crate::{
     graph::{ReactiveNode, SubscriberSet},
-    traits::{DefinedAt, IsDisposed, Notify},
+    traits::{DefinedAt, IsDisposed, Notify, ReadUntracked},
 };
 use std::{
     fmt::{Debug, Formatter, Result},
@@ -12,6 +12,32 @@ use std::{
 /// A trigger is a data-less signal with the sole purpose of notifying other reactive code of a change.
 ///
 /// This can be useful for when using external data not stored in signals, for example.
+///
+/// A trigger's value is `()`, so it can be used anywhere that a reactive value is expected: call
+/// [`track`](crate::traits::Track::track) to subscribe to it and
+/// [`notify`](crate::traits::Notify::notify) to notify its subscribers. To re-render part of a
+/// view when the trigger fires, track it inside the view:
+///
+/// ```rust
+/// # use reactive_graph::prelude::*;
+/// # use reactive_graph::signal::ArcTrigger;
+/// # let owner = reactive_graph::owner::Owner::new(); owner.set();
+/// # fn draw_view() {}
+/// let redraw = ArcTrigger::new();
+/// let view = {
+///     let redraw = redraw.clone();
+///     move || {
+///         redraw.track();
+///         draw_view()
+///     }
+/// };
+/// # let _guard = reactive_graph::diagnostics::SpecialNonReactiveZone::enter();
+/// // composes with anything that reads a reactive value
+/// let () = redraw.get();
+///
+/// // later: makes `view` run again
+/// redraw.notify();
+/// ```
 pub struct ArcTrigger {
     #[cfg(debug_assertions)]
     pub(crate) defined_at: &'static Location<'static>,
@@ -22,10 +48,17 @@ impl ArcTrigger {
     /// Creates a new trigger.
     #[track_caller]
     pub fn new() -> Self {
+        let inner = Default::default();
+        #[cfg(feature = "graph-introspection")]
+        crate::introspect::register(
+            &inner,
+            crate::introspect::NodeKind::Trigger,
+            Location::caller(),
+        );
         Self {
             #[cfg(debug_assertions)]
             defined_at: Location::caller(),
-            inner: Default::default(),
+            inner,
         }
     }
 }
@@ -88,3 +121,11 @@ impl Notify for ArcTrigger {
         self.inner.mark_dirty();
     }
 }
+
+impl ReadUntracked for ArcTrigger {
+    type Value = &'static ();
+
+    fn try_read_untracked(&self) -> Option<Self::Value> {
+        Some(&())
+    }
+}
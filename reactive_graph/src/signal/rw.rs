@@ -5,7 +5,7 @@ use super::{
 };
 use crate::{
     graph::{ReactiveNode, SubscriberSet},
-    owner::{ArenaItem, FromLocal, LocalStorage, Storage, SyncStorage},
+    owner::{ArenaItem, FromLocal, LocalStorage, Owner, Storage, SyncStorage},
     signal::guards::{UntrackedWriteGuard, WriteGuard},
     traits::{
         DefinedAt, Dispose, IsDisposed, Notify, ReadUntracked,
@@ -214,12 +214,66 @@ where
         + Storage<ArcReadSignal<T>>,
 {
     /// Splits the signal into its readable and writable halves.
+    ///
+    /// Each half is stored separately in the arena of the current [`Owner`], but they share the
+    /// signal's value, which is kept alive until the signal and both halves have been disposed.
+    /// To give the writable half to a different owner, use [`RwSignal::split_owned`].
     #[track_caller]
     #[inline(always)]
     pub fn split(&self) -> (ReadSignal<T, S>, WriteSignal<T, S>) {
         (self.read_only(), self.write_only())
     }
 
+    /// Splits the signal into its readable and writable halves, with the writable half belonging
+    /// to `write_owner` instead of the current [`Owner`]. This signal is disposed, so that only
+    /// the two halves remain.
+    ///
+    /// Each half is disposed along with its own owner, and the value is kept alive as long as
+    /// either half has not been disposed. For example, a signal created in a component can be
+    /// split so that its writable half is owned by an app-wide store: when the component is
+    /// unmounted, its readable half is disposed, but the store can still set the signal without
+    /// panicking.
+    ///
+    /// ```rust
+    /// # use reactive_graph::prelude::*;
+    /// # use reactive_graph::owner::Owner;
+    /// # use reactive_graph::signal::RwSignal;
+    /// let store = Owner::new();
+    /// let component = store.child();
+    ///
+    /// let (count, set_count) =
+    ///     component.with(|| RwSignal::new(0).split_owned(&store));
+    /// component.cleanup();
+    ///
+    /// assert!(count.try_get().is_none());
+    /// set_count.set(1);
+    /// ```
+    #[track_caller]
+    pub fn split_owned(
+        self,
+        write_owner: &Owner,
+    ) -> (ReadSignal<T, S>, WriteSignal<T, S>) {
+        #[cfg(debug_assertions)]
+        let defined_at = Location::caller();
+        let inner = self
+            .inner
+            .try_get_value()
+            .unwrap_or_else(unwrap_signal!(self));
+        self.dispose();
+
+        let read = ReadSignal {
+            #[cfg(debug_assertions)]
+            defined_at,
+            inner: ArenaItem::new_with_storage(inner.read_only()),
+        };
+        let write = write_owner.with(|| WriteSignal {
+            #[cfg(debug_assertions)]
+            defined_at,
+            inner: ArenaItem::new_with_storage(inner.write_only()),
+        });
+        (read, write)
+    }
+
     /// Reunites the two halves of a signal. Returns `None` if the two signals
     /// provided were not created from the same signal.
     #[track_caller]
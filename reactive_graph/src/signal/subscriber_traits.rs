@@ -10,8 +10,8 @@
 
 use crate::{
     graph::{
-        AnySource, AnySubscriber, ReactiveNode, Source, SubscriberSet,
-        ToAnySource,
+        notifying, AnySource, AnySubscriber, ReactiveNode, Source,
+        SubscriberSet, ToAnySource,
     },
     traits::{DefinedAt, IsDisposed},
     unwrap_signal,
@@ -55,10 +55,16 @@ impl<T: AsSubscriberSet + DefinedAt> ReactiveNode for T {
 
     fn mark_subscribers_check(&self) {
         if let Some(inner) = self.as_subscriber_set() {
+            #[cfg(feature = "graph-introspection")]
+            crate::introspect::notified(inner.borrow()
+                as *const RwLock<SubscriberSet>
+                as usize);
             let subs = inner.borrow().write().unwrap().take();
-            for sub in subs {
-                sub.mark_dirty();
-            }
+            notifying(|| {
+                for sub in subs {
+                    sub.mark_dirty();
+                }
+            });
         }
     }
 
@@ -121,6 +127,8 @@ impl ReactiveNode for RwLock<SubscriberSet> {
     fn mark_check(&self) {}
 
     fn mark_subscribers_check(&self) {
+        #[cfg(feature = "graph-introspection")]
+        crate::introspect::notified(self as *const Self as usize);
         let subs = self.write().unwrap().take();
         for sub in subs {
             sub.mark_dirty();
@@ -2,7 +2,7 @@ use super::{subscriber_traits::AsSubscriberSet, ArcTrigger};
 use crate::{
     graph::{ReactiveNode, SubscriberSet},
     owner::ArenaItem,
-    traits::{DefinedAt, Dispose, IsDisposed, Notify},
+    traits::{DefinedAt, Dispose, IsDisposed, Notify, ReadUntracked},
 };
 use std::{
     fmt::{Debug, Formatter, Result},
@@ -17,6 +17,8 @@ use std::{
 /// This is an arena-allocated Trigger, which is `Copy` and is disposed when its reactive
 /// [`Owner`](crate::owner::Owner) cleans up. For a reference-counted trigger that lives
 /// as long as a reference to it is alive, see [`ArcTrigger`].
+///
+/// Like [`ArcTrigger`], its value is `()`, and it can be tracked and notified.
 pub struct Trigger {
     #[cfg(debug_assertions)]
     pub(crate) defined_at: &'static Location<'static>,
@@ -101,3 +103,11 @@ impl Notify for Trigger {
         }
     }
 }
+
+impl ReadUntracked for Trigger {
+    type Value = &'static ();
+
+    fn try_read_untracked(&self) -> Option<Self::Value> {
+        (!self.inner.is_disposed()).then_some(&())
+    }
+}
@@ -0,0 +1,212 @@
+//! Signals that follow another signal, but limit how often they update.
+//!
+//! These wait using [`Executor::sleep`], so they work with any executor that provides a timer.
+//! Effects don't run without the `effects` feature (for example, during server rendering), so in
+//! that case the signals they return are the same as the signal they follow.
+//!
+//! ```rust
+//! # use reactive_graph::prelude::*;
+//! # use reactive_graph::signal::RwSignal;
+//! # use reactive_graph::wrappers::read::Signal;
+//! # use std::time::Duration;
+//! # _ = any_spawner::Executor::init_manual();
+//! # let owner = reactive_graph::owner::Owner::new(); owner.set();
+//! let search = RwSignal::new(String::new());
+//!
+//! // only updates once the user has stopped typing for 300ms
+//! let query = Signal::derive(move || search.get()).debounce(Duration::from_millis(300));
+//! # assert_eq!(query.get_untracked(), "");
+//! ```
+
+use crate::{
+    effect::Effect,
+    owner::Owner,
+    signal::ArcRwSignal,
+    traits::{Get, GetUntracked, Set},
+    wrappers::read::{ArcSignal, Signal},
+};
+use any_spawner::Executor;
+use or_poisoned::OrPoisoned;
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+impl<T> Signal<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    /// Creates a signal that updates to the value of this signal once it has stopped changing
+    /// for the given amount of time. See [`debounce`].
+    #[track_caller]
+    pub fn debounce(self, delay: Duration) -> Signal<T> {
+        debounce(self, delay)
+    }
+
+    /// Creates a signal that updates to the value of this signal at most once in the given
+    /// amount of time. See [`throttle`].
+    #[track_caller]
+    pub fn throttle(self, window: Duration) -> Signal<T> {
+        throttle(self, window)
+    }
+}
+
+struct Debounced<T> {
+    pending: Option<T>,
+    // increases with every change, so that a timer knows whether it is still the latest one
+    generation: usize,
+}
+
+/// Creates a signal that updates to the value of `source` once `source` has stopped changing
+/// for `delay` (i.e., on the trailing edge).
+///
+/// If the current [`Owner`] is disposed while a value is pending, the new signal is updated to
+/// that value right away, instead of after the owner has been disposed.
+#[track_caller]
+pub fn debounce<T>(source: Signal<T>, delay: Duration) -> Signal<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    if !cfg!(feature = "effects") {
+        return source;
+    }
+
+    let output = ArcRwSignal::new(source.get_untracked());
+    let state = Arc::new(Mutex::new(Debounced {
+        pending: None,
+        generation: 0,
+    }));
+
+    Effect::watch(
+        move || source.get(),
+        {
+            let output = output.clone();
+            let state = Arc::clone(&state);
+            move |value: &T, _, _: Option<()>| {
+                let generation = {
+                    let mut state = state.lock().or_poisoned();
+                    state.pending = Some(value.clone());
+                    state.generation += 1;
+                    state.generation
+                };
+                let output = output.clone();
+                let state = Arc::clone(&state);
+                crate::spawn_local(async move {
+                    Executor::sleep(delay).await;
+                    let value = {
+                        let mut state = state.lock().or_poisoned();
+                        if state.generation != generation {
+                            return;
+                        }
+                        state.pending.take()
+                    };
+                    if let Some(value) = value {
+                        output.set(value);
+                    }
+                });
+            }
+        },
+        false,
+    );
+
+    Owner::on_cleanup({
+        let output = output.clone();
+        move || {
+            let value = {
+                let mut state = state.lock().or_poisoned();
+                state.generation += 1;
+                state.pending.take()
+            };
+            if let Some(value) = value {
+                output.set(value);
+            }
+        }
+    });
+
+    ArcSignal::from(output).into()
+}
+
+struct Throttled<T> {
+    pending: Option<T>,
+    window_open: bool,
+    disposed: bool,
+}
+
+/// Creates a signal that updates to the value of `source` as soon as it changes (i.e., on the
+/// leading edge), and then ignores further changes for `window`.
+///
+/// If `source` changed during the window, the new signal is updated to its latest value once the
+/// window closes, which starts another window.
+#[track_caller]
+pub fn throttle<T>(source: Signal<T>, window: Duration) -> Signal<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    if !cfg!(feature = "effects") {
+        return source;
+    }
+
+    let output = ArcRwSignal::new(source.get_untracked());
+    let state = Arc::new(Mutex::new(Throttled {
+        pending: None,
+        window_open: false,
+        disposed: false,
+    }));
+
+    Effect::watch(
+        move || source.get(),
+        {
+            let output = output.clone();
+            let state = Arc::clone(&state);
+            move |value: &T, _, _: Option<()>| {
+                {
+                    let mut state = state.lock().or_poisoned();
+                    if state.window_open {
+                        state.pending = Some(value.clone());
+                        return;
+                    }
+                    state.window_open = true;
+                }
+                output.set(value.clone());
+
+                let output = output.clone();
+                let state = Arc::clone(&state);
+                crate::spawn_local(async move {
+                    loop {
+                        Executor::sleep(window).await;
+                        let value = {
+                            let mut state = state.lock().or_poisoned();
+                            if state.disposed {
+                                return;
+                            }
+                            let value = state.pending.take();
+                            state.window_open = value.is_some();
+                            value
+                        };
+                        match value {
+                            Some(value) => output.set(value),
+                            None => break,
+                        }
+                    }
+                });
+            }
+        },
+        false,
+    );
+
+    Owner::on_cleanup({
+        let output = output.clone();
+        move || {
+            let value = {
+                let mut state = state.lock().or_poisoned();
+                state.disposed = true;
+                state.pending.take()
+            };
+            if let Some(value) = value {
+                output.set(value);
+            }
+        }
+    });
+
+    ArcSignal::from(output).into()
+}
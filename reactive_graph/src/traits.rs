@@ -29,6 +29,7 @@
 //! | [`With`]          | `fn(&T) -> U` | [`ReadUntracked`] + [`Track`]      | Applies closure to the current value of the signal and returns result, with reactive tracking.
 //! | [`GetUntracked`]  | `T`           | [`WithUntracked`] + [`Clone`] | Clones the current value of the signal.
 //! | [`Get`]           | `T`           | [`GetUntracked`] + [`Track`]  | Clones the current value of the signal, with reactive tracking.
+//! | [`MapRef`]        | `fn(&T) -> &U` | [`Read`] + [`ReadUntracked`] | Creates a [`MappedSignal`](crate::wrappers::read::MappedSignal) that reads one part of the value of the signal.
 //!
 //! ### Update
 //! | Trait               | Mode          | Composition                       | Description
@@ -53,8 +54,8 @@ use crate::{
     graph::{Observer, Source, Subscriber, ToAnySource},
     owner::Owner,
     signal::{arc_signal, guards::UntrackedWriteGuard, ArcReadSignal},
+    wrappers::read::{ArcMappedSignal, MappedSignal},
 };
-use any_spawner::Executor;
 use futures::{Stream, StreamExt};
 use std::{
     ops::{Deref, DerefMut},
@@ -113,9 +114,22 @@ impl<T: Source + ToAnySource + DefinedAt> Track for T {
         }
 
         if let Some(subscriber) = Observer::get() {
-            subscriber.add_source(self.to_any_source());
+            let source = self.to_any_source();
+            #[cfg(feature = "graph-introspection")]
+            crate::introspect::tracked(source.0, subscriber.0);
+            subscriber.add_source(source);
             self.add_subscriber(subscriber);
         } else {
+            #[cfg(debug_assertions)]
+            if crate::diagnostics::is_rendering_untracked() {
+                crate::diagnostics::report_untracked_read(
+                    std::any::type_name::<T>(),
+                    Location::caller(),
+                    self.defined_at(),
+                );
+                return;
+            }
+
             #[cfg(all(debug_assertions, feature = "effects"))]
             {
                 use crate::diagnostics::SpecialNonReactiveZone;
@@ -385,6 +399,81 @@ where
     }
 }
 
+/// Creates a signal that reads one part of the value of another signal.
+pub trait MapRef:
+    ReadUntracked
+    + Read<Value = <Self as ReadUntracked>::Value>
+    + Send
+    + Sync
+    + 'static
+where
+    <Self as ReadUntracked>::Value: 'static,
+{
+    /// Creates a [`MappedSignal`] that reads the part of this signal's value that `map` returns.
+    /// Only that part is cloned when the new signal is read, not the whole value.
+    ///
+    /// The new signal has no reactive node of its own: reading it subscribes to this signal, and
+    /// maps a reference to its value. It notifies its subscribers whenever this signal does. To
+    /// only notify them when that part of the value has changed, read it in a
+    /// [`Memo`](crate::computed::Memo).
+    ///
+    /// ```rust
+    /// # use reactive_graph::prelude::*;
+    /// # use reactive_graph::computed::Memo;
+    /// # use reactive_graph::signal::RwSignal;
+    /// # let owner = reactive_graph::owner::Owner::new(); owner.set();
+    /// struct User {
+    ///     name: String,
+    ///     friends: Vec<User>,
+    /// }
+    ///
+    /// let user = RwSignal::new(User {
+    ///     name: "Alice".to_string(),
+    ///     friends: Vec::new(),
+    /// });
+    /// let name = user.map_ref(|user| &user.name);
+    /// assert_eq!(name.get(), "Alice");
+    /// assert_eq!(name.read().len(), 5);
+    ///
+    /// // only changes when the name does
+    /// let name = Memo::new(move |_| name.get());
+    /// # assert_eq!(name.get(), "Alice");
+    /// ```
+    #[track_caller]
+    fn map_ref<U>(
+        self,
+        map: impl Fn(&<<Self as ReadUntracked>::Value as Deref>::Target) -> &U
+            + Send
+            + Sync
+            + 'static,
+    ) -> MappedSignal<U, <Self as ReadUntracked>::Value> {
+        MappedSignal::new(self, map)
+    }
+
+    /// Like [`map_ref`](MapRef::map_ref), but creates a reference-counted [`ArcMappedSignal`].
+    #[track_caller]
+    fn map_arc<U>(
+        self,
+        map: impl Fn(&<<Self as ReadUntracked>::Value as Deref>::Target) -> &U
+            + Send
+            + Sync
+            + 'static,
+    ) -> ArcMappedSignal<U, <Self as ReadUntracked>::Value> {
+        ArcMappedSignal::new(self, map)
+    }
+}
+
+impl<T> MapRef for T
+where
+    T: ReadUntracked
+        + Read<Value = <T as ReadUntracked>::Value>
+        + Send
+        + Sync
+        + 'static,
+    <T as ReadUntracked>::Value: 'static,
+{
+}
+
 /// Notifies subscribers of a change in this signal.
 pub trait Notify {
     /// Notifies subscribers of a change in this signal.
@@ -599,7 +688,7 @@ where
     fn from_stream_unsync(stream: impl Stream<Item = T> + 'static) -> Self {
         let (read, write) = arc_signal(None);
         let mut stream = Box::pin(stream);
-        Executor::spawn_local(async move {
+        crate::spawn_local(async move {
             while let Some(value) = stream.next().await {
                 write.set(Some(value));
             }
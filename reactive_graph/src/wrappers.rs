@@ -14,8 +14,8 @@ pub mod read {
             ArcReadSignal, ArcRwSignal, ReadSignal, RwSignal,
         },
         traits::{
-            DefinedAt, Dispose, Get, Read, ReadUntracked, ReadValue, Track,
-            With, WithValue,
+            DefinedAt, Dispose, Get, IsDisposed, Read, ReadUntracked,
+            ReadValue, Track, With, WithValue,
         },
         unwrap_signal,
     };
@@ -381,6 +381,15 @@ pub mod read {
         }
     }
 
+    impl<T, S> IsDisposed for Signal<T, S>
+    where
+        S: Storage<T>,
+    {
+        fn is_disposed(&self) -> bool {
+            self.inner.is_disposed()
+        }
+    }
+
     impl<T, S> Clone for Signal<T, S>
     where
         S: Storage<T>,
@@ -892,6 +901,18 @@ pub mod read {
         }
     }
 
+    impl<T, S> IsDisposed for MaybeSignal<T, S>
+    where
+        S: Storage<T>,
+    {
+        fn is_disposed(&self) -> bool {
+            match self {
+                Self::Static(_) => false,
+                Self::Dynamic(signal) => signal.is_disposed(),
+            }
+        }
+    }
+
     impl<T, S> DefinedAt for MaybeSignal<T, S>
     where
         S: Storage<T>,
@@ -1499,6 +1520,359 @@ pub mod read {
         }
     }
 
+    /// Reads a part of another signal's value, without an intermediate reactive node.
+    ///
+    /// A mapped signal subscribes its readers to the source signal directly, and maps a reference
+    /// to the source's value to a reference to the part it reads. Reading it only clones that part,
+    /// and does not run any closure that copies the value into a new signal.
+    ///
+    /// This is usually created with [`MapRef::map_arc`](crate::traits::MapRef::map_arc).
+    ///
+    /// `G` is the read guard of the source signal, which the mapped signal's own read guard holds.
+    pub struct ArcMappedSignal<T: 'static, G> {
+        #[cfg(debug_assertions)]
+        defined_at: &'static Location<'static>,
+        // reads the source, tracking it if `true`
+        #[allow(clippy::type_complexity)]
+        read: Arc<dyn Fn(bool) -> Option<G> + Send + Sync>,
+        #[allow(clippy::type_complexity)]
+        map: Arc<dyn Fn(&G) -> &T + Send + Sync>,
+    }
+
+    impl<T, G> ArcMappedSignal<T, G>
+    where
+        G: Deref,
+    {
+        /// Creates a signal that reads the part of `source`'s value returned by `map`.
+        #[track_caller]
+        pub fn new<Src>(
+            source: Src,
+            map: impl Fn(&G::Target) -> &T + Send + Sync + 'static,
+        ) -> Self
+        where
+            Src: ReadUntracked<Value = G>
+                + Read<Value = G>
+                + Send
+                + Sync
+                + 'static,
+        {
+            Self {
+                #[cfg(debug_assertions)]
+                defined_at: Location::caller(),
+                read: Arc::new(move |tracked| {
+                    if tracked {
+                        source.try_read()
+                    } else {
+                        source.try_read_untracked()
+                    }
+                }),
+                map: Arc::new(move |guard| map(guard)),
+            }
+        }
+    }
+
+    impl<T, G> ArcMappedSignal<T, G> {
+        fn try_read_mapped(
+            &self,
+            tracked: bool,
+        ) -> Option<MappedSignalGuard<T, G>> {
+            (self.read)(tracked).map(|guard| {
+                ReadGuard::new(MappedGuard {
+                    guard,
+                    map: Arc::clone(&self.map),
+                })
+            })
+        }
+    }
+
+    impl<T, G> Clone for ArcMappedSignal<T, G> {
+        fn clone(&self) -> Self {
+            Self {
+                #[cfg(debug_assertions)]
+                defined_at: self.defined_at,
+                read: Arc::clone(&self.read),
+                map: Arc::clone(&self.map),
+            }
+        }
+    }
+
+    impl<T, G> core::fmt::Debug for ArcMappedSignal<T, G> {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            let mut s = f.debug_struct("ArcMappedSignal");
+            s.field("read", &Arc::as_ptr(&self.read));
+            #[cfg(debug_assertions)]
+            s.field("defined_at", &self.defined_at);
+            s.finish()
+        }
+    }
+
+    impl<T, G> Eq for ArcMappedSignal<T, G> {}
+
+    impl<T, G> PartialEq for ArcMappedSignal<T, G> {
+        fn eq(&self, other: &Self) -> bool {
+            Arc::ptr_eq(&self.read, &other.read)
+        }
+    }
+
+    impl<T, G> DefinedAt for ArcMappedSignal<T, G> {
+        fn defined_at(&self) -> Option<&'static Location<'static>> {
+            #[cfg(debug_assertions)]
+            {
+                Some(self.defined_at)
+            }
+            #[cfg(not(debug_assertions))]
+            {
+                None
+            }
+        }
+    }
+
+    impl<T, G> With for ArcMappedSignal<T, G> {
+        type Value = T;
+
+        fn try_with<U>(
+            &self,
+            fun: impl FnOnce(&Self::Value) -> U,
+        ) -> Option<U> {
+            self.try_read().map(|value| fun(&value))
+        }
+    }
+
+    impl<T, G> ReadUntracked for ArcMappedSignal<T, G> {
+        type Value = MappedSignalGuard<T, G>;
+
+        fn try_read_untracked(&self) -> Option<Self::Value> {
+            self.try_read_mapped(false)
+        }
+    }
+
+    impl<T, G> Read for ArcMappedSignal<T, G> {
+        type Value = MappedSignalGuard<T, G>;
+
+        fn try_read(&self) -> Option<Self::Value> {
+            self.try_read_mapped(true)
+        }
+
+        fn read(&self) -> Self::Value {
+            self.try_read().unwrap_or_else(unwrap_signal!(self))
+        }
+    }
+
+    /// An arena-allocated [`ArcMappedSignal`]: reads a part of another signal's value, without an
+    /// intermediate reactive node.
+    ///
+    /// This is usually created with [`MapRef::map_ref`](crate::traits::MapRef::map_ref).
+    pub struct MappedSignal<T: 'static, G, S = SyncStorage> {
+        #[cfg(debug_assertions)]
+        defined_at: &'static Location<'static>,
+        inner: ArenaItem<ArcMappedSignal<T, G>, S>,
+    }
+
+    impl<T, G> MappedSignal<T, G>
+    where
+        G: Deref + 'static,
+    {
+        /// Creates a signal that reads the part of `source`'s value returned by `map`.
+        #[track_caller]
+        pub fn new<Src>(
+            source: Src,
+            map: impl Fn(&G::Target) -> &T + Send + Sync + 'static,
+        ) -> Self
+        where
+            Src: ReadUntracked<Value = G>
+                + Read<Value = G>
+                + Send
+                + Sync
+                + 'static,
+        {
+            ArcMappedSignal::new(source, map).into()
+        }
+    }
+
+    impl<T, G> From<ArcMappedSignal<T, G>> for MappedSignal<T, G>
+    where
+        G: 'static,
+    {
+        #[track_caller]
+        fn from(value: ArcMappedSignal<T, G>) -> Self {
+            Self {
+                #[cfg(debug_assertions)]
+                defined_at: Location::caller(),
+                inner: ArenaItem::new(value),
+            }
+        }
+    }
+
+    impl<T, G, S> From<MappedSignal<T, G, S>> for ArcMappedSignal<T, G>
+    where
+        S: Storage<ArcMappedSignal<T, G>>,
+    {
+        #[track_caller]
+        fn from(value: MappedSignal<T, G, S>) -> Self {
+            value
+                .inner
+                .try_get_value()
+                .unwrap_or_else(unwrap_signal!(value))
+        }
+    }
+
+    impl<T, G, S> Dispose for MappedSignal<T, G, S> {
+        fn dispose(self) {
+            self.inner.dispose()
+        }
+    }
+
+    impl<T, G, S> IsDisposed for MappedSignal<T, G, S>
+    where
+        S: Storage<ArcMappedSignal<T, G>>,
+    {
+        fn is_disposed(&self) -> bool {
+            self.inner.is_disposed()
+        }
+    }
+
+    impl<T, G, S> Clone for MappedSignal<T, G, S> {
+        fn clone(&self) -> Self {
+            *self
+        }
+    }
+
+    impl<T, G, S> Copy for MappedSignal<T, G, S> {}
+
+    impl<T, G, S> core::fmt::Debug for MappedSignal<T, G, S>
+    where
+        S: std::fmt::Debug,
+    {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            let mut s = f.debug_struct("MappedSignal");
+            s.field("inner", &self.inner);
+            #[cfg(debug_assertions)]
+            s.field("defined_at", &self.defined_at);
+            s.finish()
+        }
+    }
+
+    impl<T, G, S> Eq for MappedSignal<T, G, S> {}
+
+    impl<T, G, S> PartialEq for MappedSignal<T, G, S> {
+        fn eq(&self, other: &Self) -> bool {
+            self.inner == other.inner
+        }
+    }
+
+    impl<T, G, S> DefinedAt for MappedSignal<T, G, S> {
+        fn defined_at(&self) -> Option<&'static Location<'static>> {
+            #[cfg(debug_assertions)]
+            {
+                Some(self.defined_at)
+            }
+            #[cfg(not(debug_assertions))]
+            {
+                None
+            }
+        }
+    }
+
+    impl<T, G, S> With for MappedSignal<T, G, S>
+    where
+        S: Storage<ArcMappedSignal<T, G>>,
+    {
+        type Value = T;
+
+        fn try_with<U>(
+            &self,
+            fun: impl FnOnce(&Self::Value) -> U,
+        ) -> Option<U> {
+            self.try_read().map(|value| fun(&value))
+        }
+    }
+
+    impl<T, G, S> ReadUntracked for MappedSignal<T, G, S>
+    where
+        S: Storage<ArcMappedSignal<T, G>>,
+    {
+        type Value = MappedSignalGuard<T, G>;
+
+        fn try_read_untracked(&self) -> Option<Self::Value> {
+            self.inner
+                // clone the inner Arc type and release the lock
+                // prevents deadlocking if the source includes taking a lock on the arena
+                .try_with_value(Clone::clone)
+                .and_then(|inner| inner.try_read_untracked())
+        }
+    }
+
+    impl<T, G, S> Read for MappedSignal<T, G, S>
+    where
+        S: Storage<ArcMappedSignal<T, G>>,
+    {
+        type Value = MappedSignalGuard<T, G>;
+
+        fn try_read(&self) -> Option<Self::Value> {
+            self.inner
+                // clone the inner Arc type and release the lock
+                // prevents deadlocking if the source includes taking a lock on the arena
+                .try_with_value(Clone::clone)
+                .and_then(|inner| inner.try_read())
+        }
+
+        fn read(&self) -> Self::Value {
+            self.try_read().unwrap_or_else(unwrap_signal!(self))
+        }
+    }
+
+    /// The read guard of a [`MappedSignal`].
+    pub type MappedSignalGuard<T, G> = ReadGuard<T, MappedGuard<G, T>>;
+
+    /// Holds the source signal's read guard `G`, and maps it to a part of its value on each
+    /// dereference.
+    pub struct MappedGuard<G, T: 'static> {
+        guard: G,
+        #[allow(clippy::type_complexity)]
+        map: Arc<dyn Fn(&G) -> &T + Send + Sync>,
+    }
+
+    impl<G, T> core::fmt::Debug for MappedGuard<G, T>
+    where
+        T: core::fmt::Debug,
+    {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            f.debug_tuple("MappedGuard").field(&**self).finish()
+        }
+    }
+
+    impl<G, T> Deref for MappedGuard<G, T> {
+        type Target = T;
+
+        fn deref(&self) -> &Self::Target {
+            (self.map)(&self.guard)
+        }
+    }
+
+    impl<G, T> Borrow<T> for MappedGuard<G, T> {
+        fn borrow(&self) -> &T {
+            self.deref()
+        }
+    }
+
+    impl<G, T> PartialEq<T> for MappedGuard<G, T>
+    where
+        T: PartialEq,
+    {
+        fn eq(&self, other: &T) -> bool {
+            self.deref() == other
+        }
+    }
+
+    impl<G, T> Display for MappedGuard<G, T>
+    where
+        T: Display,
+    {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            Display::fmt(&**self, f)
+        }
+    }
+
     /// The content of a [`Signal`] wrapper read guard, variable depending on the signal type.
     #[derive(Debug)]
     pub enum SignalReadGuard<T: 'static, S: Storage<T>> {
@@ -1572,12 +1946,31 @@ pub mod read {
 
 /// Types that abstract over the ability to update a signal.
 pub mod write {
+    use super::read::Signal;
     use crate::{
         owner::{ArenaItem, Storage, SyncStorage},
         signal::{ArcRwSignal, ArcWriteSignal, RwSignal, WriteSignal},
         traits::Set,
     };
 
+    /// A readable half and a writable half of some value, for two-way bindings.
+    ///
+    /// Components that edit a value can take a `Model<T>` rather than separate getter and setter
+    /// props. Any [`RwSignal`] can be turned into one, as can a slice made with
+    /// [`create_slice`](crate::computed::create_slice).
+    ///
+    /// ```rust
+    /// # use reactive_graph::prelude::*;  let owner = reactive_graph::owner::Owner::new(); owner.set();
+    /// # use reactive_graph::wrappers::write::Model;
+    /// # use reactive_graph::signal::RwSignal;
+    /// let name = RwSignal::new(String::from("Alice"));
+    /// let (value, set_value): Model<String> = (name.into(), name.into());
+    ///
+    /// set_value.set("Bob".into());
+    /// assert_eq!(value.get_untracked(), "Bob");
+    /// ```
+    pub type Model<T, S = SyncStorage> = (Signal<T, S>, SignalSetter<T, S>);
+
     /// Helper trait for converting `Fn(T)` into [`SignalSetter<T>`].
     pub trait IntoSignalSetter<T, S>: Sized {
         /// Consumes `self`, returning [`SignalSetter<T>`].
@@ -1733,6 +2126,22 @@ pub mod write {
         }
     }
 
+    impl<T, S> From<Box<dyn Fn(T) + Send + Sync>> for SignalSetter<T, S>
+    where
+        S: Storage<Box<dyn Fn(T) + Send + Sync>>,
+    {
+        #[track_caller]
+        fn from(value: Box<dyn Fn(T) + Send + Sync>) -> Self {
+            Self {
+                inner: SignalSetterTypes::Mapped(ArenaItem::new_with_storage(
+                    value,
+                )),
+                #[cfg(debug_assertions)]
+                defined_at: std::panic::Location::caller(),
+            }
+        }
+    }
+
     enum SignalSetterTypes<T, S = SyncStorage>
     where
         T: 'static,
@@ -0,0 +1,157 @@
+use any_spawner::Executor;
+use futures::channel::oneshot;
+use reactive_graph::{
+    actions::{Action, ArcAction},
+    owner::Owner,
+    traits::{Dispose, GetUntracked},
+};
+use std::sync::{Arc, Mutex};
+
+type Log = Arc<Mutex<Vec<String>>>;
+
+/// Creates an action function whose futures resolve when the test sends on the returned senders,
+/// logging when each input starts and finishes.
+#[allow(clippy::type_complexity)]
+fn controlled(
+    count: usize,
+    log: &Log,
+) -> (
+    Vec<oneshot::Sender<usize>>,
+    impl Fn(&usize) -> futures::future::BoxFuture<'static, usize>
+        + Send
+        + Sync
+        + 'static,
+) {
+    let (senders, receivers): (Vec<_>, Vec<_>) =
+        (0..count).map(|_| oneshot::channel()).unzip();
+    let receivers = Arc::new(Mutex::new(
+        receivers.into_iter().map(Some).collect::<Vec<_>>(),
+    ));
+    let log = Arc::clone(log);
+    let action_fn = move |n: &usize| {
+        let n = *n;
+        log.lock().unwrap().push(format!("start {n}"));
+        let rx = receivers.lock().unwrap()[n].take().unwrap();
+        let log = Arc::clone(&log);
+        Box::pin(async move {
+            let value = rx.await.unwrap();
+            log.lock().unwrap().push(format!("end {n}"));
+            value
+        }) as futures::future::BoxFuture<'static, usize>
+    };
+    (senders, action_fn)
+}
+
+#[test]
+fn serial_action_runs_dispatches_in_order() {
+    _ = Executor::init_manual();
+    let owner = Owner::new();
+    owner.set();
+
+    let log = Log::default();
+    let (senders, action_fn) = controlled(3, &log);
+    let action = ArcAction::new_serial(action_fn);
+    let pending_count = action.pending_count();
+    let queue_len = action.queue_len();
+
+    action.dispatch(0);
+    action.dispatch(1);
+    action.dispatch(2);
+    Executor::flush();
+    assert_eq!(pending_count.get_untracked(), 3);
+    assert_eq!(queue_len.get_untracked(), 2);
+    assert_eq!(action.input().get_untracked(), Some(0));
+    assert_eq!(*log.lock().unwrap(), ["start 0"]);
+
+    // the futures resolve in a different order than they were dispatched
+    let mut senders = senders.into_iter().map(Some).collect::<Vec<_>>();
+    senders[2].take().unwrap().send(20).unwrap();
+    Executor::flush();
+    assert_eq!(*log.lock().unwrap(), ["start 0"]);
+    assert_eq!(action.value().get_untracked(), None);
+
+    senders[0].take().unwrap().send(0).unwrap();
+    Executor::flush();
+    assert_eq!(*log.lock().unwrap(), ["start 0", "end 0", "start 1"]);
+    assert_eq!(action.value().get_untracked(), Some(0));
+    assert_eq!(action.input().get_untracked(), Some(1));
+    assert_eq!(pending_count.get_untracked(), 2);
+    assert_eq!(queue_len.get_untracked(), 1);
+
+    senders[1].take().unwrap().send(10).unwrap();
+    Executor::flush();
+    assert_eq!(
+        *log.lock().unwrap(),
+        ["start 0", "end 0", "start 1", "end 1", "start 2", "end 2"]
+    );
+    assert_eq!(action.value().get_untracked(), Some(20));
+    assert_eq!(action.version().get_untracked(), 3);
+    assert_eq!(action.input().get_untracked(), None);
+    assert_eq!(pending_count.get_untracked(), 0);
+    assert_eq!(queue_len.get_untracked(), 0);
+}
+
+#[test]
+fn serial_action_skips_aborted_dispatches() {
+    _ = Executor::init_manual();
+    let owner = Owner::new();
+    owner.set();
+
+    let log = Log::default();
+    let (mut senders, action_fn) = controlled(3, &log);
+    let action = ArcAction::new_serial(action_fn);
+
+    action.dispatch(0);
+    action.dispatch(1).abort();
+    action.dispatch(2);
+    Executor::flush();
+    assert_eq!(action.queue_len().get_untracked(), 1);
+
+    senders.remove(0).send(0).unwrap();
+    Executor::flush();
+    assert_eq!(*log.lock().unwrap(), ["start 0", "end 0", "start 2"]);
+    assert_eq!(action.pending_count().get_untracked(), 1);
+}
+
+#[test]
+fn disposing_serial_action_cancels_queue() {
+    _ = Executor::init_manual();
+    let owner = Owner::new();
+    owner.set();
+
+    let log = Log::default();
+    let (mut senders, action_fn) = controlled(3, &log);
+    let action = Action::new_serial(action_fn);
+
+    action.dispatch(0);
+    action.dispatch(1);
+    action.dispatch(2);
+    Executor::flush();
+    action.dispose();
+
+    senders.remove(0).send(0).unwrap();
+    Executor::flush();
+    assert_eq!(*log.lock().unwrap(), ["start 0", "end 0"]);
+}
+
+#[test]
+fn pending_count_counts_concurrent_dispatches() {
+    _ = Executor::init_manual();
+    let owner = Owner::new();
+    owner.set();
+
+    let log = Log::default();
+    let (mut senders, action_fn) = controlled(2, &log);
+    let action = ArcAction::new(action_fn);
+
+    action.dispatch(0);
+    action.dispatch(1);
+    Executor::flush();
+    assert_eq!(action.pending_count().get_untracked(), 2);
+    assert_eq!(action.queue_len().get_untracked(), 0);
+    assert_eq!(*log.lock().unwrap(), ["start 0", "start 1"]);
+
+    senders.remove(1).send(10).unwrap();
+    Executor::flush();
+    assert_eq!(action.pending_count().get_untracked(), 1);
+}
@@ -137,3 +137,84 @@ async fn async_derived_with_initial() {
     signal2.set(1);
     assert_eq!(derived.await, 2);
 }
+
+#[tokio::test]
+async fn suspense_task_guard_holds_pending_until_dropped() {
+    use reactive_graph::{
+        computed::suspense::SuspenseContext,
+        owner::{provide_context, use_context},
+        signal::ArcRwSignal,
+    };
+    use slotmap::SlotMap;
+
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+
+    provide_context(SuspenseContext {
+        tasks: ArcRwSignal::new(SlotMap::new()),
+    });
+    let suspense = use_context::<SuspenseContext>().unwrap();
+    assert!(suspense.tasks.with_untracked(|tasks| tasks.is_empty()));
+
+    let (tx, rx) = futures::channel::oneshot::channel::<()>();
+    let guard = suspense.task_guard();
+    let handle = tokio::spawn(async move {
+        _ = rx.await;
+        drop(guard);
+    });
+    assert_eq!(suspense.tasks.with_untracked(|tasks| tasks.len()), 1);
+
+    tx.send(()).unwrap();
+    handle.await.unwrap();
+    assert!(suspense.tasks.with_untracked(|tasks| tasks.is_empty()));
+}
+
+#[tokio::test]
+async fn reloads_register_one_task_per_suspense_boundary() {
+    use reactive_graph::{
+        computed::suspense::SuspenseContext, owner::provide_context,
+        signal::ArcRwSignal,
+    };
+    use slotmap::SlotMap;
+    use std::time::Duration;
+
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+
+    let signal = RwSignal::new(0);
+    let derived = ArcAsyncDerived::new(move || {
+        let value = signal.get();
+        async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            value
+        }
+    });
+
+    let tasks = ArcRwSignal::new(SlotMap::new());
+    let boundary = owner.child();
+    boundary.with(|| {
+        provide_context(SuspenseContext {
+            tasks: tasks.clone(),
+        });
+        // the boundary reads the pending resource twice
+        _ = derived.get();
+        _ = derived.get();
+    });
+    assert_eq!(derived.clone().await, 0);
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    assert!(tasks.with_untracked(|tasks| tasks.is_empty()));
+
+    // each reload registers a single task with the boundary, even if it is restarted while
+    // the previous one is still pending
+    signal.set(1);
+    tokio::time::sleep(Duration::from_millis(1)).await;
+    assert_eq!(tasks.with_untracked(|tasks| tasks.len()), 1);
+    signal.set(2);
+    tokio::time::sleep(Duration::from_millis(1)).await;
+    assert_eq!(tasks.with_untracked(|tasks| tasks.len()), 1);
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    assert_eq!(derived.get(), Some(2));
+    assert!(tasks.with_untracked(|tasks| tasks.is_empty()));
+}
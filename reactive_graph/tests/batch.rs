@@ -0,0 +1,102 @@
+#[cfg(feature = "effects")]
+use any_spawner::Executor;
+#[cfg(feature = "effects")]
+use reactive_graph::{
+    batch, computed::Memo, effect::Effect, owner::Owner, prelude::*,
+    signal::RwSignal,
+};
+#[cfg(feature = "effects")]
+use std::sync::{Arc, RwLock};
+
+#[cfg(feature = "effects")]
+fn effect_runs(fun: impl Fn() + Send + Sync + 'static) -> Arc<RwLock<usize>> {
+    let runs = Arc::new(RwLock::new(0));
+    Effect::new({
+        let runs = Arc::clone(&runs);
+        move |_| {
+            fun();
+            *runs.write().unwrap() += 1;
+        }
+    });
+    Executor::flush();
+    runs
+}
+
+#[cfg(feature = "effects")]
+#[test]
+fn batch_runs_effects_once_with_final_values() {
+    _ = Executor::init_manual();
+    let owner = Owner::new();
+    owner.set();
+
+    let (a, b, c) = (RwSignal::new(0), RwSignal::new(0), RwSignal::new(0));
+    let computations = Arc::new(RwLock::new(0));
+    let sum = Memo::new({
+        let computations = Arc::clone(&computations);
+        move |_| {
+            *computations.write().unwrap() += 1;
+            a.get() + b.get() + c.get()
+        }
+    });
+    let seen = Arc::new(RwLock::new(Vec::new()));
+    let runs = effect_runs({
+        let seen = Arc::clone(&seen);
+        move || seen.write().unwrap().push(sum.get())
+    });
+
+    batch(|| {
+        a.set(1);
+        b.set(2);
+        c.set(3);
+    });
+    Executor::flush();
+    assert_eq!(*runs.read().unwrap(), 2);
+    assert_eq!(*computations.read().unwrap(), 2);
+    assert_eq!(*seen.read().unwrap(), [0, 6]);
+}
+
+#[cfg(feature = "effects")]
+#[test]
+fn nested_batches_notify_when_outermost_ends() {
+    _ = Executor::init_manual();
+    let owner = Owner::new();
+    owner.set();
+
+    let a = RwSignal::new(0);
+    let runs = effect_runs(move || a.track());
+
+    batch(|| {
+        batch(|| a.set(1));
+        Executor::flush();
+        assert_eq!(*runs.read().unwrap(), 1);
+        a.set(2);
+    });
+    Executor::flush();
+    assert_eq!(*runs.read().unwrap(), 2);
+}
+
+#[cfg(feature = "effects")]
+#[test]
+fn batch_notifies_effects_when_it_panics() {
+    _ = Executor::init_manual();
+    let owner = Owner::new();
+    owner.set();
+
+    let a = RwSignal::new(0);
+    let runs = effect_runs(move || a.track());
+
+    let result = std::panic::catch_unwind(|| {
+        batch(|| {
+            a.set(1);
+            panic!("failed in the middle of a batch");
+        })
+    });
+    assert!(result.is_err());
+    Executor::flush();
+    assert_eq!(*runs.read().unwrap(), 2);
+
+    // later updates aren't batched
+    a.set(2);
+    Executor::flush();
+    assert_eq!(*runs.read().unwrap(), 3);
+}
@@ -0,0 +1,45 @@
+use reactive_graph::owner::{
+    provide_context, provide_context_in, use_context, Owner,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct RowContext(usize);
+
+#[test]
+fn sibling_child_owners_get_their_own_context() {
+    let owner = Owner::new();
+    owner.set();
+    provide_context(RowContext(0));
+
+    let (first, _) = owner.with_child(|| provide_context(RowContext(1)));
+    let second = owner.child();
+    provide_context_in(&second, RowContext(2));
+
+    assert_eq!(first.with(use_context::<RowContext>), Some(RowContext(1)));
+    assert_eq!(second.with(use_context::<RowContext>), Some(RowContext(2)));
+    // the parent still sees its own value
+    assert_eq!(use_context::<RowContext>(), Some(RowContext(0)));
+}
+
+#[test]
+fn grandchild_uses_nearest_scoped_context() {
+    let owner = Owner::new();
+    owner.set();
+    provide_context(RowContext(0));
+
+    let (_child, (_grandchild, value)) = owner.with_child(|| {
+        provide_context(RowContext(1));
+        Owner::current()
+            .unwrap()
+            .with_child(use_context::<RowContext>)
+    });
+    assert_eq!(value, Some(RowContext(1)));
+
+    let (_other, value) = owner.with_child(|| {
+        Owner::current()
+            .unwrap()
+            .with_child(use_context::<RowContext>)
+            .1
+    });
+    assert_eq!(value, Some(RowContext(0)));
+}
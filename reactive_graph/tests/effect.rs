@@ -157,6 +157,62 @@ async fn dynamic_dependencies() {
         .await
 }
 
+#[cfg(feature = "effects")]
+#[tokio::test]
+async fn paused_render_effect_runs_once_resumed() {
+    use imports::*;
+
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+    task::LocalSet::new()
+        .run_until(async {
+            let a = RwSignal::new(0);
+            let values = Arc::new(RwLock::new(Vec::new()));
+
+            let child = owner.child();
+            let effect = child.with(|| {
+                RenderEffect::new({
+                    let values = Arc::clone(&values);
+                    move |_| values.write().unwrap().push(a.get())
+                })
+            });
+
+            child.pause();
+            assert!(child.paused());
+            a.set(1);
+            Executor::tick().await;
+            a.set(2);
+            Executor::tick().await;
+            assert_eq!(&*values.read().unwrap(), &[0]);
+
+            // runs once, with the latest value
+            child.resume();
+            Executor::tick().await;
+            assert_eq!(&*values.read().unwrap(), &[0, 2]);
+
+            // and keeps tracking its sources
+            a.set(3);
+            Executor::tick().await;
+            assert_eq!(&*values.read().unwrap(), &[0, 2, 3]);
+
+            // effects created under a paused owner are paused too
+            child.pause();
+            let nested = child.child();
+            assert!(nested.paused());
+            child.resume();
+            assert!(!nested.paused());
+
+            // a paused effect that wasn't notified doesn't run when resumed
+            child.pause();
+            child.resume();
+            Executor::tick().await;
+            assert_eq!(&*values.read().unwrap(), &[0, 2, 3]);
+            drop(effect);
+        })
+        .await;
+}
+
 #[cfg(feature = "effects")]
 #[tokio::test]
 async fn recursive_effect_runs_recursively() {
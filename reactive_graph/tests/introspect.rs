@@ -0,0 +1,111 @@
+#![cfg(all(feature = "effects", feature = "graph-introspection"))]
+
+use any_spawner::Executor;
+use reactive_graph::{
+    computed::Memo,
+    effect::{Effect, RenderEffect},
+    introspect::{snapshot, Edge, GraphSnapshot, Named, NodeKind},
+    owner::Owner,
+    prelude::*,
+    signal::{ArcTrigger, RwSignal},
+};
+
+fn id(graph: &GraphSnapshot, name: &str) -> usize {
+    graph.find(name).unwrap().id
+}
+
+fn sources(graph: &GraphSnapshot, name: &str) -> Vec<String> {
+    let mut sources = graph
+        .sources(id(graph, name))
+        .map(|node| node.name.clone().unwrap())
+        .collect::<Vec<_>>();
+    sources.sort();
+    sources
+}
+
+#[test]
+fn snapshot_has_nodes_and_edges() {
+    _ = Executor::init_manual();
+    let owner = Owner::new();
+    owner.set();
+
+    let a = RwSignal::new(1).named("snapshot_a");
+    let b = RwSignal::new(2).named("snapshot_b");
+    let sum = Memo::new(move |_| a.get() + b.get()).named("snapshot_sum");
+    Effect::new(move |_| {
+        _ = sum.get();
+        _ = a.get();
+    })
+    .named("snapshot_effect");
+    Executor::flush();
+
+    let graph = snapshot();
+    assert_eq!(graph.find("snapshot_a").unwrap().kind, NodeKind::Signal);
+    assert_eq!(graph.find("snapshot_sum").unwrap().kind, NodeKind::Memo);
+    assert_eq!(
+        graph.find("snapshot_effect").unwrap().kind,
+        NodeKind::Effect
+    );
+    assert_eq!(
+        sources(&graph, "snapshot_sum"),
+        ["snapshot_a", "snapshot_b"]
+    );
+    assert_eq!(
+        sources(&graph, "snapshot_effect"),
+        ["snapshot_a", "snapshot_sum"]
+    );
+    assert!(graph.edges.contains(&Edge::from((
+        id(&graph, "snapshot_sum"),
+        id(&graph, "snapshot_effect")
+    ))));
+    assert_eq!(graph.sources(id(&graph, "snapshot_a")).count(), 0);
+
+    b.set(3);
+    Executor::flush();
+    let graph = snapshot();
+    assert_eq!(graph.find("snapshot_b").unwrap().times_notified, 1);
+    assert_eq!(graph.find("snapshot_sum").unwrap().times_run, 2);
+    assert_eq!(graph.find("snapshot_effect").unwrap().times_run, 2);
+}
+
+#[test]
+fn edges_follow_dynamic_dependencies() {
+    _ = Executor::init_manual();
+    let owner = Owner::new();
+    owner.set();
+
+    let show = RwSignal::new(true).named("dynamic_show");
+    let trigger = ArcTrigger::new().named("dynamic_trigger");
+    let effect = RenderEffect::new({
+        let trigger = trigger.clone();
+        move |_| {
+            if show.get() {
+                trigger.track();
+            }
+        }
+    })
+    .named("dynamic_effect");
+
+    let graph = snapshot();
+    assert_eq!(
+        graph.find("dynamic_effect").unwrap().kind,
+        NodeKind::RenderEffect
+    );
+    assert_eq!(
+        sources(&graph, "dynamic_effect"),
+        ["dynamic_show", "dynamic_trigger"]
+    );
+
+    show.set(false);
+    Executor::flush();
+    let graph = snapshot();
+    assert_eq!(sources(&graph, "dynamic_effect"), ["dynamic_show"]);
+
+    // nodes that have been dropped are no longer part of the graph
+    drop(trigger);
+    drop(effect);
+    Executor::flush();
+    let graph = snapshot();
+    assert!(graph.find("dynamic_trigger").is_none());
+    assert!(graph.find("dynamic_effect").is_none());
+}
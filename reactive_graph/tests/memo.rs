@@ -443,3 +443,125 @@ fn unsync_derived_signal_and_memo() {
     assert_eq!(f.with(|n| *n), 6);
     assert_eq!(f.get_untracked(), 6);
 }
+
+#[cfg(feature = "effects")]
+#[tokio::test]
+async fn memo_with_compare_doesnt_rerun_effects_when_equal() {
+    use imports::*;
+
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+
+    #[derive(Debug, Clone)]
+    struct Row {
+        id: usize,
+        label: String,
+    }
+
+    task::LocalSet::new()
+        .run_until(async {
+            let label = RwSignal::new("a".to_string());
+            let id = RwSignal::new(0);
+            // only the `id` field counts as a change
+            let row = Memo::new_with_compare(
+                move |_| Row {
+                    id: id.get(),
+                    label: label.get(),
+                },
+                |prev, next| prev.map(|row| row.id) != next.map(|row| row.id),
+            );
+
+            let runs = Arc::new(RwLock::new(0));
+            mem::forget(RenderEffect::new({
+                let runs = Arc::clone(&runs);
+                move |_| {
+                    _ = row.get();
+                    *runs.write().unwrap() += 1;
+                }
+            }));
+
+            Executor::tick().await;
+            assert_eq!(*runs.read().unwrap(), 1);
+
+            label.set("b".to_string());
+            Executor::tick().await;
+            assert_eq!(*runs.read().unwrap(), 1);
+            // the new value is still stored, even though it isn't a change
+            assert_eq!(row.with_untracked(|row| row.label.clone()), "b");
+
+            id.set(1);
+            Executor::tick().await;
+            assert_eq!(*runs.read().unwrap(), 2);
+        })
+        .await
+}
+
+#[cfg(feature = "effects")]
+#[tokio::test]
+async fn memo_with_capturing_compare() {
+    use imports::*;
+
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+
+    task::LocalSet::new()
+        .run_until(async {
+            let value = RwSignal::new(0.0_f64);
+            let epsilon = 0.5;
+            let approximate = ArcMemo::new_with_compare(
+                move |_| value.get(),
+                move |prev, next| match (prev, next) {
+                    (Some(prev), Some(next)) => (prev - next).abs() > epsilon,
+                    _ => true,
+                },
+            );
+
+            let runs = Arc::new(RwLock::new(0));
+            mem::forget(RenderEffect::new({
+                let runs = Arc::clone(&runs);
+                let approximate = approximate.clone();
+                move |_| {
+                    _ = approximate.get();
+                    *runs.write().unwrap() += 1;
+                }
+            }));
+
+            Executor::tick().await;
+            value.set(0.2);
+            Executor::tick().await;
+            assert_eq!(*runs.read().unwrap(), 1);
+
+            value.set(1.0);
+            Executor::tick().await;
+            assert_eq!(*runs.read().unwrap(), 2);
+        })
+        .await
+}
+
+#[test]
+fn memo_owning_reuses_previous_value() {
+    let owner = Owner::new();
+    owner.set();
+
+    let count = RwSignal::new(1);
+    let allocations = Arc::new(RwLock::new(0));
+    let numbers = Memo::new_owning({
+        let allocations = Arc::clone(&allocations);
+        move |prev: Option<Vec<usize>>| {
+            let mut numbers = prev.unwrap_or_else(|| {
+                *allocations.write().unwrap() += 1;
+                Vec::with_capacity(8)
+            });
+            numbers.clear();
+            numbers.extend(0..count.get());
+            (numbers, true)
+        }
+    });
+
+    assert_eq!(numbers.get_untracked(), [0]);
+    count.set(3);
+    assert_eq!(numbers.get_untracked(), [0, 1, 2]);
+    assert_eq!(*allocations.read().unwrap(), 1);
+}
@@ -0,0 +1,154 @@
+#![cfg(all(feature = "effects", feature = "panic-isolation"))]
+
+use any_spawner::Executor;
+use reactive_graph::{
+    effect::{on_effect_panic, Effect, RenderEffect},
+    owner::Owner,
+    prelude::*,
+    signal::RwSignal,
+};
+use std::{
+    panic::Location,
+    sync::{Arc, Mutex},
+};
+
+// the hook is global, so the tests that check it share one and filter by message
+type Reports = Arc<Mutex<Vec<(String, &'static Location<'static>)>>>;
+
+fn reports() -> Reports {
+    static REPORTS: Mutex<Option<Reports>> = Mutex::new(None);
+    REPORTS
+        .lock()
+        .unwrap()
+        .get_or_insert_with(|| {
+            let reports = Reports::default();
+            on_effect_panic({
+                let reports = Arc::clone(&reports);
+                move |info, location| {
+                    reports.lock().unwrap().push((
+                        info.message().unwrap_or_default().to_string(),
+                        location,
+                    ))
+                }
+            });
+            reports
+        })
+        .clone()
+}
+
+fn reported(
+    reports: &Reports,
+    message: &str,
+) -> Vec<&'static Location<'static>> {
+    reports
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|(msg, _)| msg == message)
+        .map(|(_, location)| *location)
+        .collect()
+}
+
+#[test]
+fn panicking_render_effect_stops_and_siblings_keep_running() {
+    _ = Executor::init_manual();
+    let owner = Owner::new();
+    owner.set();
+    let reports = reports();
+
+    let count = RwSignal::new(0);
+    let line = line!() + 1;
+    let panicking = RenderEffect::new(move |_| {
+        let count = count.get();
+        if count == 1 {
+            panic!("render effect panicked at 1");
+        }
+        count
+    });
+    let sibling = RenderEffect::new(move |_| count.get());
+    assert_eq!(panicking.with_value_mut(|n| *n), Some(0));
+
+    count.set(1);
+    Executor::flush();
+    assert_eq!(panicking.with_value_mut(|n| *n), None);
+    assert_eq!(sibling.with_value_mut(|n| *n), Some(1));
+
+    let locations = reported(&reports, "render effect panicked at 1");
+    assert_eq!(locations.len(), 1);
+    assert_eq!(locations[0].file(), file!());
+    assert_eq!(locations[0].line(), line);
+
+    // the poisoned effect doesn't run again
+    count.set(2);
+    Executor::flush();
+    assert_eq!(panicking.with_value_mut(|n| *n), None);
+    assert_eq!(sibling.with_value_mut(|n| *n), Some(2));
+    assert_eq!(reported(&reports, "render effect panicked at 1").len(), 1);
+}
+
+#[test]
+fn render_effect_that_panics_on_first_run_is_isolated() {
+    _ = Executor::init_manual();
+    let owner = Owner::new();
+    owner.set();
+    let reports = reports();
+
+    let count = RwSignal::new(0);
+    let panicking = RenderEffect::new(move |_: Option<()>| {
+        count.track();
+        panic!("render effect panicked on first run");
+    });
+    assert_eq!(panicking.with_value_mut(|_| ()), None);
+
+    count.set(1);
+    Executor::flush();
+    assert_eq!(
+        reported(&reports, "render effect panicked on first run").len(),
+        1
+    );
+}
+
+#[test]
+fn panicking_effect_stops_and_siblings_keep_running() {
+    _ = Executor::init_manual();
+    let owner = Owner::new();
+    owner.set();
+    let reports = reports();
+
+    let count = RwSignal::new(0);
+    let runs = Arc::new(Mutex::new(Vec::new()));
+    let line = line!() + 1;
+    Effect::new({
+        let runs = Arc::clone(&runs);
+        move || {
+            let count = count.get();
+            if count == 1 {
+                panic!("effect panicked at 1");
+            }
+            runs.lock().unwrap().push(("panicking", count));
+        }
+    });
+    Effect::new({
+        let runs = Arc::clone(&runs);
+        move || runs.lock().unwrap().push(("sibling", count.get()))
+    });
+    Executor::flush();
+
+    count.set(1);
+    Executor::flush();
+    count.set(2);
+    Executor::flush();
+
+    assert_eq!(
+        *runs.lock().unwrap(),
+        [
+            ("panicking", 0),
+            ("sibling", 0),
+            ("sibling", 1),
+            ("sibling", 2)
+        ]
+    );
+    let locations = reported(&reports, "effect panicked at 1");
+    assert_eq!(locations.len(), 1);
+    assert_eq!(locations[0].line(), line);
+}
@@ -0,0 +1,128 @@
+#![cfg(all(feature = "effects", feature = "persist"))]
+
+use any_spawner::Executor;
+use reactive_graph::{
+    owner::Owner,
+    persist::{
+        persisted_signal, persisted_signal_with_options, MemoryStorage,
+        PersistOptions, Storage,
+    },
+    prelude::*,
+    signal::RwSignal,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+#[derive(Clone, Default)]
+struct CountingStorage {
+    inner: MemoryStorage,
+    writes: Arc<Mutex<Vec<String>>>,
+}
+
+impl Storage for CountingStorage {
+    fn load(&self, key: &str) -> Option<String> {
+        self.inner.load(key)
+    }
+
+    fn store(&self, key: &str, value: &str) {
+        self.writes.lock().unwrap().push(value.to_string());
+        self.inner.store(key, value);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Settings {
+    theme: String,
+    font_size: u8,
+}
+
+#[test]
+fn persisted_signal_round_trips() {
+    _ = Executor::init_manual();
+    let owner = Owner::new();
+    owner.set();
+
+    let storage = MemoryStorage::default();
+    let default = Settings {
+        theme: "light".into(),
+        font_size: 12,
+    };
+    let settings =
+        RwSignal::persisted("settings", default.clone(), storage.clone());
+    Executor::flush();
+    // the default isn't written until it changes
+    assert_eq!(storage.load("settings"), None);
+
+    settings.signal().update(|s| s.font_size = 16);
+    Executor::flush();
+
+    let restored = persisted_signal("settings", default, storage);
+    restored.sync();
+    assert_eq!(
+        restored.signal().get_untracked(),
+        Settings {
+            theme: "light".into(),
+            font_size: 16
+        }
+    );
+}
+
+#[test]
+fn persisted_signal_debounces_writes() {
+    _ = Executor::init_manual();
+    let owner = Owner::new();
+    owner.set();
+
+    let storage = CountingStorage::default();
+    let count = persisted_signal_with_options(
+        "count",
+        0,
+        storage.clone(),
+        PersistOptions::default().debounce(Duration::from_millis(100)),
+    );
+    Executor::flush();
+
+    for n in 1..=3 {
+        count.signal().set(n);
+        Executor::flush();
+        Executor::advance_time(Duration::from_millis(50));
+    }
+    assert!(storage.writes.lock().unwrap().is_empty());
+
+    Executor::advance_time(Duration::from_millis(50));
+    assert_eq!(*storage.writes.lock().unwrap(), ["3"]);
+
+    // disposing the owner writes a value that is still waiting
+    count.signal().set(4);
+    Executor::flush();
+    owner.cleanup();
+    assert_eq!(*storage.writes.lock().unwrap(), ["3", "4"]);
+}
+
+#[test]
+fn persisted_signal_starts_with_default_until_synced() {
+    _ = Executor::init_manual();
+    let owner = Owner::new();
+    owner.set();
+
+    let storage = CountingStorage::default();
+    storage.inner.store("name", "\"stored\"");
+
+    let name =
+        persisted_signal("name", String::from("default"), storage.clone());
+    Executor::flush();
+    assert_eq!(name.signal().get_untracked(), "default");
+
+    name.sync();
+    Executor::flush();
+    assert_eq!(name.signal().get_untracked(), "stored");
+    // loading the stored value doesn't write it back
+    assert!(storage.writes.lock().unwrap().is_empty());
+
+    name.signal().set("changed".into());
+    Executor::flush();
+    assert_eq!(*storage.writes.lock().unwrap(), ["\"changed\""]);
+}
@@ -0,0 +1,71 @@
+#![cfg(feature = "effects")]
+
+use any_spawner::Executor;
+use reactive_graph::{
+    computed::Selector,
+    effect::RenderEffect,
+    owner::Owner,
+    signal::RwSignal,
+    traits::{Get, Set},
+};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+#[test]
+fn selector_only_notifies_changed_keys() {
+    _ = Executor::init_manual();
+    let owner = Owner::new();
+    owner.set();
+
+    let selected = RwSignal::new(0);
+    let selector = Selector::new(move || selected.get());
+    let runs = Arc::new(AtomicUsize::new(0));
+    let effects = (0..10)
+        .map(|n| {
+            let selector = selector.clone();
+            let runs = Arc::clone(&runs);
+            RenderEffect::new(move |_| {
+                runs.fetch_add(1, Ordering::Relaxed);
+                selector.selected(n);
+            })
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(runs.load(Ordering::Relaxed), 10);
+
+    selected.set(3);
+    Executor::flush();
+    assert_eq!(runs.load(Ordering::Relaxed), 12);
+
+    selected.set(4);
+    Executor::flush();
+    assert_eq!(runs.load(Ordering::Relaxed), 14);
+    assert_eq!(selector.subscribed_keys(), 10);
+    drop(effects);
+}
+
+#[test]
+fn selector_drops_keys_of_dropped_subscribers() {
+    _ = Executor::init_manual();
+    let owner = Owner::new();
+    owner.set();
+
+    let selected = RwSignal::new(0);
+    let selector = Selector::new(move || selected.get());
+    let mut effects = (0..10)
+        .map(|n| {
+            let selector = selector.clone();
+            RenderEffect::new(move |_| selector.selected(n))
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(selector.subscribed_keys(), 10);
+
+    effects.truncate(4);
+    Executor::flush();
+    assert_eq!(selector.subscribed_keys(), 4);
+
+    // reading outside a reactive context does not subscribe
+    assert!(!selector.selected(7));
+    assert_eq!(selector.subscribed_keys(), 4);
+}
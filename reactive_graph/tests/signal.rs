@@ -1,10 +1,16 @@
 use reactive_graph::{
+    computed::{ArcMemo, Memo},
     owner::Owner,
-    signal::{arc_signal, signal, ArcRwSignal, RwSignal},
+    signal::{arc_signal, signal, ArcRwSignal, RwSignal, Trigger},
     traits::{
-        Get, GetUntracked, Read, Set, Update, UpdateUntracked, With,
-        WithUntracked, Write,
+        Dispose, Get, GetUntracked, MapRef, Notify, Read, ReadUntracked, Set,
+        Update, UpdateUntracked, With, WithUntracked, Write,
     },
+    wrappers::read::MappedSignal,
+};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
 };
 
 #[test]
@@ -108,3 +114,129 @@ fn update_signal() {
     set_a.set(4);
     assert_eq!(a.get(), 4);
 }
+
+#[test]
+fn map_ref_only_clones_the_mapped_part() {
+    let owner = Owner::new();
+    owner.set();
+
+    // isn't `Clone`, so only the name can be cloned
+    struct Large {
+        name: String,
+        rows: Vec<usize>,
+    }
+
+    let a = ArcRwSignal::new(Large {
+        name: "a".to_string(),
+        rows: vec![0; 1000],
+    });
+    let name = a.clone().map_ref(|large| &large.name);
+    assert_eq!(name.get(), "a");
+
+    a.update(|large| {
+        large.name = "b".to_string();
+        large.rows.clear();
+    });
+    assert_eq!(name.get(), "b");
+    assert_eq!(*name.read(), "b");
+}
+
+#[test]
+fn mapped_signal_reads_the_source_without_a_node_of_its_own() {
+    let owner = Owner::new();
+    owner.set();
+
+    let maps = Arc::new(AtomicUsize::new(0));
+    let a = RwSignal::new(("a".to_string(), 0));
+    let name = a.map_arc({
+        let maps = Arc::clone(&maps);
+        move |(name, _)| {
+            maps.fetch_add(1, Ordering::Relaxed);
+            name
+        }
+    });
+
+    // nothing runs until it is read, even when the source changes
+    a.update(|(_, n)| *n += 1);
+    assert_eq!(maps.load(Ordering::Relaxed), 0);
+
+    // every copy maps the source's current value
+    let copy = name.clone();
+    assert_eq!(copy.get_untracked(), "a");
+    a.update(|(name, _)| *name = "b".to_string());
+    assert_eq!(name.get_untracked(), "b");
+    assert_eq!(copy.read_untracked().len(), 1);
+    assert_eq!(maps.load(Ordering::Relaxed), 3);
+
+    // readers subscribe to the source itself
+    let derived = ArcMemo::new({
+        let name = name.clone();
+        move |_| name.get()
+    });
+    assert_eq!(derived.get_untracked(), "b");
+    a.update(|(name, _)| *name = "c".to_string());
+    assert_eq!(derived.get_untracked(), "c");
+
+    let arena: MappedSignal<String, _> = name.into();
+    assert_eq!(arena.get_untracked(), "c");
+    a.dispose();
+    assert_eq!(arena.try_get_untracked(), None);
+}
+
+#[test]
+fn split_owned_writer_outlives_reader_owner() {
+    let store = Owner::new();
+    let component = store.child();
+
+    let (count, set_count) =
+        component.with(|| RwSignal::new(0).split_owned(&store));
+    assert_eq!(count.get_untracked(), 0);
+    set_count.set(1);
+    assert_eq!(count.get_untracked(), 1);
+
+    // the component unmounts, but the store can still write
+    component.cleanup();
+    assert_eq!(count.try_get_untracked(), None);
+    set_count.set(2);
+    set_count.update(|n| *n += 1);
+}
+
+#[test]
+fn split_owned_reader_outlives_writer_owner() {
+    let owner = Owner::new();
+    let writer_owner = owner.child();
+
+    let (count, set_count) =
+        owner.with(|| RwSignal::new(0).split_owned(&writer_owner));
+    set_count.set(1);
+
+    writer_owner.cleanup();
+    // the value is handed back, since the writable half has been disposed
+    assert_eq!(set_count.try_set(2), Some(2));
+    assert_eq!(count.get_untracked(), 1);
+}
+
+#[test]
+fn trigger_can_be_read_as_unit() {
+    let owner = Owner::new();
+    owner.set();
+
+    let trigger = Trigger::new();
+    let runs = Arc::new(AtomicUsize::new(0));
+    let memo = Memo::new_with_compare(
+        {
+            let runs = Arc::clone(&runs);
+            move |_| {
+                trigger.get();
+                runs.fetch_add(1, Ordering::Relaxed)
+            }
+        },
+        |_, _| true,
+    );
+    assert_eq!(memo.get_untracked(), 0);
+    trigger.notify();
+    assert_eq!(memo.get_untracked(), 1);
+
+    trigger.dispose();
+    assert_eq!(trigger.try_get(), None);
+}
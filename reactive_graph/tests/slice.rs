@@ -0,0 +1,87 @@
+#![cfg(feature = "effects")]
+
+use any_spawner::Executor;
+use reactive_graph::{
+    computed::create_slice,
+    effect::RenderEffect,
+    owner::Owner,
+    prelude::*,
+    signal::RwSignal,
+    wrappers::write::{Model, SignalSetter},
+};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+#[derive(Debug, Clone, Default)]
+struct State {
+    count: u32,
+    name: String,
+}
+
+fn track_runs(
+    fun: impl Fn() + 'static,
+) -> (Arc<AtomicUsize>, RenderEffect<()>) {
+    let runs = Arc::new(AtomicUsize::new(0));
+    let effect = RenderEffect::new({
+        let runs = Arc::clone(&runs);
+        move |_| {
+            fun();
+            runs.fetch_add(1, Ordering::Relaxed);
+        }
+    });
+    (runs, effect)
+}
+
+#[test]
+fn slices_only_notify_when_their_part_changes() {
+    _ = Executor::init_manual();
+    let owner = Owner::new();
+    owner.set();
+
+    let state = RwSignal::new(State::default());
+    let (count, set_count) =
+        create_slice(state, |s| s.count, |s, n| s.count = n);
+    let (name, set_name) =
+        create_slice(state, |s| s.name.clone(), |s, n| s.name = n);
+
+    let (count_runs, _count_effect) = track_runs(move || _ = count.get());
+    let (name_runs, _name_effect) = track_runs(move || _ = name.get());
+
+    set_count.set(1);
+    Executor::flush();
+    assert_eq!(count.get_untracked(), 1);
+    assert_eq!(count_runs.load(Ordering::Relaxed), 2);
+    assert_eq!(name_runs.load(Ordering::Relaxed), 1);
+
+    set_name.set("Bob".into());
+    Executor::flush();
+    assert_eq!(count_runs.load(Ordering::Relaxed), 2);
+    assert_eq!(name_runs.load(Ordering::Relaxed), 2);
+
+    // updating the whole signal without touching a slice doesn't notify it
+    state.update(|s| s.count = 2);
+    Executor::flush();
+    assert_eq!(count_runs.load(Ordering::Relaxed), 3);
+    assert_eq!(name_runs.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn model_round_trips_through_signal() {
+    let owner = Owner::new();
+    owner.set();
+
+    let name = RwSignal::new(String::from("Alice"));
+    let (value, set_value): Model<String> = (name.into(), name.into());
+    set_value.set("Bob".into());
+    assert_eq!(name.get_untracked(), "Bob");
+    name.set("Carol".into());
+    assert_eq!(value.get_untracked(), "Carol");
+
+    let boxed: Box<dyn Fn(String) + Send + Sync> =
+        Box::new(move |new| name.set(new.to_uppercase()));
+    let set_upper: SignalSetter<String> = boxed.into();
+    set_upper.set("dave".into());
+    assert_eq!(value.get_untracked(), "DAVE");
+}
@@ -0,0 +1,55 @@
+use any_spawner::Executor;
+use reactive_graph::{
+    owner::{ArcStoredValue, Owner, StoredValue},
+    traits::{GetValue, SetValue, UpdateValue, WithValue},
+};
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn try_accessors_return_none_after_owner_is_disposed() {
+    _ = Executor::init_manual();
+    let owner = Owner::new();
+    owner.set();
+
+    let child = owner.child();
+    let value = child.with(|| StoredValue::new(1));
+    assert_eq!(value.try_get_value(), Some(1));
+
+    let results = Arc::new(Mutex::new(None));
+    reactive_graph::spawn({
+        let results = Arc::clone(&results);
+        async move {
+            *results.lock().unwrap() = Some((
+                value.try_get_value(),
+                value.try_with_value(|n| *n),
+                value.try_update_value(|n| *n += 1),
+                value.try_set_value(2),
+            ));
+        }
+    });
+
+    // the task runs after the component that owned the value is gone
+    child.cleanup();
+    Executor::flush();
+    assert_eq!(*results.lock().unwrap(), Some((None, None, None, Some(2))));
+}
+
+#[test]
+fn arc_stored_value_outlives_stored_value() {
+    let owner = Owner::new();
+    owner.set();
+
+    let child = owner.child();
+    let value = child.with(|| StoredValue::new(1));
+    let shared = ArcStoredValue::from(value);
+
+    child.cleanup();
+    assert_eq!(value.try_get_value(), None);
+    shared.update_value(|n| *n += 1);
+    assert_eq!(shared.get_value(), 2);
+
+    // and can be stored in the arena again
+    let value = StoredValue::from(shared.clone());
+    value.set_value(3);
+    assert_eq!(shared.get_value(), 3);
+}
@@ -0,0 +1,93 @@
+use reactive_graph::{
+    computed::Memo,
+    owner::Owner,
+    prelude::*,
+    signal::{ArcRwSignal, RwSignal},
+};
+use std::{cell::RefCell, rc::Rc};
+
+#[test]
+fn subscription_stops_when_dropped() {
+    let count = ArcRwSignal::new(0);
+    let seen = Rc::new(RefCell::new(Vec::new()));
+
+    let subscription = count.read_only().subscribe({
+        let seen = Rc::clone(&seen);
+        move |value| seen.borrow_mut().push(*value)
+    });
+    assert!(seen.borrow().is_empty());
+
+    count.set(1);
+    count.set(2);
+    assert_eq!(*seen.borrow(), [1, 2]);
+
+    drop(subscription);
+    count.set(3);
+    assert_eq!(*seen.borrow(), [1, 2]);
+}
+
+#[test]
+fn subscriptions_run_in_order() {
+    let owner = Owner::new();
+    owner.set();
+
+    let count = RwSignal::new(0);
+    let seen = Rc::new(RefCell::new(Vec::new()));
+
+    let _first = count.read_only().subscribe({
+        let seen = Rc::clone(&seen);
+        move |value| seen.borrow_mut().push(("first", *value))
+    });
+    let _second = count.read_only().subscribe({
+        let seen = Rc::clone(&seen);
+        move |value| seen.borrow_mut().push(("second", *value))
+    });
+
+    count.set(1);
+    count.set(2);
+    assert_eq!(
+        *seen.borrow(),
+        [("first", 1), ("second", 1), ("first", 2), ("second", 2)]
+    );
+}
+
+#[test]
+fn memo_subscription_skips_equal_values() {
+    let owner = Owner::new();
+    owner.set();
+
+    let count = RwSignal::new(1);
+    let is_even = Memo::new(move |_| count.get() % 2 == 0);
+    let seen = Rc::new(RefCell::new(Vec::new()));
+
+    let _subscription = is_even.subscribe({
+        let seen = Rc::clone(&seen);
+        move |value| seen.borrow_mut().push(*value)
+    });
+
+    count.set(2);
+    count.set(4);
+    count.set(5);
+    assert_eq!(*seen.borrow(), [true, false]);
+}
+
+#[test]
+fn updates_in_callback_run_afterwards() {
+    let count = ArcRwSignal::new(0);
+    let seen = Rc::new(RefCell::new(Vec::new()));
+
+    let _subscription = count.subscribe({
+        let count = count.clone();
+        let seen = Rc::clone(&seen);
+        move |value| {
+            seen.borrow_mut().push(*value);
+            if *value < 3 {
+                count.set(*value + 1);
+            }
+            seen.borrow_mut().push(-1);
+        }
+    });
+
+    count.set(1);
+    assert_eq!(*seen.borrow(), [1, -1, 2, -1, 3, -1]);
+}
@@ -0,0 +1,99 @@
+#[cfg(feature = "effects")]
+use any_spawner::Executor;
+#[cfg(feature = "effects")]
+use reactive_graph::{
+    owner::Owner,
+    prelude::*,
+    signal::RwSignal,
+    timing::{debounce, throttle},
+    wrappers::read::{ArcSignal, Signal},
+};
+#[cfg(feature = "effects")]
+use std::time::Duration;
+
+#[cfg(feature = "effects")]
+fn ms(ms: u64) -> Duration {
+    Duration::from_millis(ms)
+}
+
+#[cfg(feature = "effects")]
+#[test]
+fn debounce_updates_once_source_stops_changing() {
+    _ = Executor::init_manual();
+    let owner = Owner::new();
+    owner.set();
+
+    let source = RwSignal::new(0);
+    let debounced: Signal<i32> = debounce(source.into(), ms(100));
+    Executor::flush();
+    assert_eq!(debounced.get_untracked(), 0);
+
+    source.set(1);
+    Executor::advance_time(ms(60));
+    source.set(2);
+    Executor::advance_time(ms(60));
+    assert_eq!(debounced.get_untracked(), 0);
+
+    Executor::advance_time(ms(40));
+    assert_eq!(debounced.get_untracked(), 2);
+}
+
+#[cfg(feature = "effects")]
+#[test]
+fn debounce_flushes_pending_value_on_dispose() {
+    _ = Executor::init_manual();
+    let owner = Owner::new();
+    owner.set();
+
+    let child = Owner::new();
+    let source = RwSignal::new(0);
+    let debounced: ArcSignal<i32> =
+        child.with(|| debounce(source.into(), ms(100)).into());
+    Executor::flush();
+
+    source.set(1);
+    Executor::advance_time(ms(50));
+    assert_eq!(debounced.get_untracked(), 0);
+
+    child.cleanup();
+    assert_eq!(debounced.get_untracked(), 1);
+
+    // the timer that was running doesn't fire after the owner is gone
+    source.set(2);
+    Executor::advance_time(ms(200));
+    assert_eq!(debounced.get_untracked(), 1);
+}
+
+#[cfg(feature = "effects")]
+#[test]
+fn throttle_updates_on_leading_edge_and_after_window() {
+    _ = Executor::init_manual();
+    let owner = Owner::new();
+    owner.set();
+
+    let source = RwSignal::new(0);
+    let throttled: Signal<i32> = throttle(source.into(), ms(100));
+    Executor::flush();
+
+    source.set(1);
+    Executor::flush();
+    assert_eq!(throttled.get_untracked(), 1);
+
+    source.set(2);
+    Executor::advance_time(ms(30));
+    source.set(3);
+    Executor::advance_time(ms(30));
+    assert_eq!(throttled.get_untracked(), 1);
+
+    // the final value is emitted when the window closes
+    Executor::advance_time(ms(40));
+    assert_eq!(throttled.get_untracked(), 3);
+
+    // which starts another window, and nothing changes after it closes
+    Executor::advance_time(ms(100));
+    assert_eq!(throttled.get_untracked(), 3);
+
+    source.set(4);
+    Executor::flush();
+    assert_eq!(throttled.get_untracked(), 4);
+}
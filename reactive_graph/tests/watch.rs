@@ -3,7 +3,12 @@ use any_spawner::Executor;
 #[cfg(feature = "effects")]
 use reactive_graph::owner::Owner;
 #[cfg(feature = "effects")]
-use reactive_graph::{effect::Effect, prelude::*, signal::RwSignal};
+use reactive_graph::{
+    computed::Memo,
+    effect::{ArcWatchHandle, Effect},
+    prelude::*,
+    signal::RwSignal,
+};
 #[cfg(feature = "effects")]
 use std::sync::{Arc, RwLock};
 #[cfg(feature = "effects")]
@@ -178,6 +183,162 @@ async fn watch_ignores_callback() {
         .await
 }
 
+#[cfg(feature = "effects")]
+#[tokio::test]
+async fn watch_memo_skips_equal_values() {
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+
+    task::LocalSet::new()
+        .run_until(async {
+            let a = RwSignal::new(0);
+            let is_even = Memo::new(move |_| a.get() % 2 == 0);
+            let runs = Arc::new(RwLock::new(Vec::new()));
+
+            Effect::watch(
+                move || is_even.get(),
+                {
+                    let runs = runs.clone();
+                    move |is_even, prev, _| {
+                        runs.write().unwrap().push((*is_even, prev.copied()));
+                    }
+                },
+                false,
+            );
+
+            Executor::tick().await;
+            a.set(2);
+            Executor::tick().await;
+            assert_eq!(*runs.read().unwrap(), []);
+
+            a.set(3);
+            Executor::tick().await;
+            a.set(5);
+            Executor::tick().await;
+            assert_eq!(*runs.read().unwrap(), [(false, Some(true))]);
+        })
+        .await
+}
+
+#[cfg(feature = "effects")]
+#[tokio::test]
+async fn watch_eq_skips_equal_values() {
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+
+    task::LocalSet::new()
+        .run_until(async {
+            let a = RwSignal::new(0);
+            let runs = Arc::new(RwLock::new(Vec::new()));
+            let eq_runs = Arc::new(RwLock::new(Vec::new()));
+
+            Effect::watch(
+                move || a.get() % 2 == 0,
+                {
+                    let runs = runs.clone();
+                    move |is_even, prev, _| {
+                        runs.write().unwrap().push((*is_even, prev.copied()));
+                    }
+                },
+                false,
+            );
+            Effect::watch_eq(
+                move || a.get() % 2 == 0,
+                {
+                    let eq_runs = eq_runs.clone();
+                    move |is_even, prev, count: Option<usize>| {
+                        eq_runs.write().unwrap().push((
+                            *is_even,
+                            prev.copied(),
+                            count,
+                        ));
+                        count.unwrap_or(0) + 1
+                    }
+                },
+                true,
+            );
+
+            Executor::tick().await;
+            a.set(2);
+            Executor::tick().await;
+            // without a comparison, the handler always runs
+            assert_eq!(*runs.read().unwrap(), [(true, Some(true))]);
+            assert_eq!(*eq_runs.read().unwrap(), [(true, None, None)]);
+
+            a.set(3);
+            Executor::tick().await;
+            a.set(5);
+            Executor::tick().await;
+            // the previous return value is kept while the handler is skipped
+            assert_eq!(
+                *eq_runs.read().unwrap(),
+                [(true, None, None), (false, Some(true), Some(1))]
+            );
+        })
+        .await
+}
+
+#[cfg(feature = "effects")]
+#[tokio::test]
+async fn watch_stop_on_drop() {
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+
+    task::LocalSet::new()
+        .run_until(async {
+            let a = RwSignal::new(0);
+            let runs = Arc::new(RwLock::new(Vec::new()));
+            let watch = |name| {
+                let runs = runs.clone();
+                Effect::watch(
+                    move || a.get(),
+                    move |a, _, _| runs.write().unwrap().push((name, *a)),
+                    false,
+                )
+                .stop_on_drop()
+            };
+
+            let watchers = owner.child();
+            let (dropped, arc) = watchers.with(|| {
+                watch("detached").detach();
+                (watch("dropped"), ArcWatchHandle::from(watch("arc")))
+            });
+            let arc_clone = arc.clone();
+
+            Executor::tick().await;
+            a.set(1);
+            Executor::tick().await;
+            assert_eq!(
+                *runs.read().unwrap(),
+                [("detached", 1), ("dropped", 1), ("arc", 1)]
+            );
+
+            runs.write().unwrap().clear();
+            drop(dropped);
+            drop(arc);
+            a.set(2);
+            Executor::tick().await;
+            assert_eq!(*runs.read().unwrap(), [("detached", 2), ("arc", 2)]);
+
+            // disposing the owner only stops the watcher in the arena
+            runs.write().unwrap().clear();
+            watchers.cleanup();
+            a.set(3);
+            Executor::tick().await;
+            assert_eq!(*runs.read().unwrap(), [("arc", 3)]);
+
+            runs.write().unwrap().clear();
+            arc_clone.clone().stop();
+            a.set(4);
+            Executor::tick().await;
+            assert!(runs.read().unwrap().is_empty());
+        })
+        .await
+}
+
 #[cfg(feature = "effects")]
 #[tokio::test]
 async fn deprecated_watch_runs() {
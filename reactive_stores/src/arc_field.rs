@@ -5,6 +5,7 @@ use crate::{
 };
 use reactive_graph::traits::{
     DefinedAt, IsDisposed, Notify, ReadUntracked, Track, UntrackableGuard,
+    Write,
 };
 use std::{
     fmt::Debug,
@@ -253,6 +254,23 @@ impl<T> ReadUntracked for ArcField<T> {
     }
 }
 
+impl<T> Write for ArcField<T> {
+    type Value = T;
+
+    fn try_write(&self) -> Option<impl UntrackableGuard<Target = Self::Value>> {
+        (self.write)()
+    }
+
+    fn try_write_untracked(
+        &self,
+    ) -> Option<impl DerefMut<Target = Self::Value>> {
+        (self.write)().map(|mut writer| {
+            writer.untrack();
+            writer
+        })
+    }
+}
+
 impl<T> IsDisposed for ArcField<T> {
     fn is_disposed(&self) -> bool {
         false
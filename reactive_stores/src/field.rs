@@ -6,10 +6,19 @@ use crate::{
 };
 use reactive_graph::{
     owner::{ArenaItem, Storage, SyncStorage},
-    traits::{DefinedAt, IsDisposed, Notify, ReadUntracked, Track},
+    traits::{
+        DefinedAt, IsDisposed, Notify, ReadUntracked, Set, Track,
+        UntrackableGuard, Write,
+    },
     unwrap_signal,
+    wrappers::write::SignalSetter,
+};
+use std::{
+    fmt::Debug,
+    hash::Hash,
+    ops::{DerefMut, IndexMut},
+    panic::Location,
 };
-use std::{fmt::Debug, hash::Hash, ops::IndexMut, panic::Location};
 
 pub struct Field<T, S = SyncStorage>
 where
@@ -170,6 +179,36 @@ where
     }
 }
 
+impl<T, S> Write for Field<T, S>
+where
+    S: Storage<ArcField<T>>,
+{
+    type Value = T;
+
+    fn try_write(&self) -> Option<impl UntrackableGuard<Target = Self::Value>> {
+        self.writer()
+    }
+
+    fn try_write_untracked(
+        &self,
+    ) -> Option<impl DerefMut<Target = Self::Value>> {
+        self.writer().map(|mut writer| {
+            writer.untrack();
+            writer
+        })
+    }
+}
+
+impl<T> From<Field<T>> for SignalSetter<T>
+where
+    T: Send + Sync,
+{
+    #[track_caller]
+    fn from(value: Field<T>) -> Self {
+        SignalSetter::map(move |new_value| value.set(new_value))
+    }
+}
+
 impl<T, S> IsDisposed for Field<T, S> {
     fn is_disposed(&self) -> bool {
         self.inner.is_disposed()
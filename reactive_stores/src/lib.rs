@@ -425,10 +425,15 @@ where
 
 #[cfg(test)]
 mod tests {
-    use crate::{self as reactive_stores, Patch, Store, StoreFieldIterator};
+    use crate::{
+        self as reactive_stores, Field, Patch, Store, StoreFieldIterator,
+    };
     use reactive_graph::{
+        computed::create_slice,
         effect::Effect,
-        traits::{Read, ReadUntracked, Set, Update, Write},
+        owner::Owner,
+        traits::{GetUntracked, Read, ReadUntracked, Set, Update, Write},
+        wrappers::write::SignalSetter,
     };
     use reactive_stores_macro::{Patch, Store};
     use std::sync::{
@@ -690,6 +695,30 @@ mod tests {
         assert_eq!(combined_count.load(Ordering::Relaxed), 2);
     }
 
+    #[test]
+    fn store_fields_can_be_sliced_and_set() {
+        let owner = Owner::new();
+        owner.set();
+
+        let store = Store::new(data());
+        let set_user: SignalSetter<String> = store.user().into();
+        set_user.set("Carol".into());
+        assert_eq!(*store.user().read_untracked(), "Carol");
+
+        let todos: Field<Vec<Todo>> = store.todos().into();
+        let (count, add_todo) = create_slice(
+            todos,
+            |todos| todos.len(),
+            |todos, label: &str| todos.push(Todo::new(label)),
+        );
+        add_todo.set("Celebrate");
+        assert_eq!(count.get_untracked(), 4);
+
+        let set_todos: SignalSetter<Vec<Todo>> = todos.into();
+        set_todos.set(Vec::new());
+        assert_eq!(count.get_untracked(), 0);
+    }
+
     #[derive(Debug, Store)]
     pub struct StructWithOption {
         opt_field: Option<Todo>,
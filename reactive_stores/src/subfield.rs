@@ -9,9 +9,10 @@ use reactive_graph::{
         ArcTrigger,
     },
     traits::{
-        DefinedAt, IsDisposed, Notify, ReadUntracked, Track, UntrackableGuard,
-        Write,
+        DefinedAt, IsDisposed, Notify, ReadUntracked, Set, Track,
+        UntrackableGuard, Write,
     },
+    wrappers::write::SignalSetter,
 };
 use std::{iter, marker::PhantomData, ops::DerefMut, panic::Location};
 
@@ -189,3 +190,15 @@ where
         })
     }
 }
+
+impl<Inner, Prev, T> From<Subfield<Inner, Prev, T>> for SignalSetter<T>
+where
+    T: Send + Sync + 'static,
+    Inner: StoreField<Value = Prev> + IsDisposed + Send + Sync + 'static,
+    Prev: 'static,
+{
+    #[track_caller]
+    fn from(value: Subfield<Inner, Prev, T>) -> Self {
+        SignalSetter::map(move |new_value| value.set(new_value))
+    }
+}
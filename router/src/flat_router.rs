@@ -21,7 +21,7 @@ use std::{cell::RefCell, iter, mem, rc::Rc};
 use tachys::{
     hydration::Cursor,
     reactive_graph::OwnedView,
-    ssr::StreamBuilder,
+    ssr::{BranchMarks, StreamBuilder},
     view::{
         add_attr::AddAnyAttr, Mountable, Position, PositionState, Render,
         RenderHtml,
@@ -429,7 +429,7 @@ where
         buf: &mut String,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) {
         // if this is being run on the server for the first time, generating all possible routes
         if RouteList::is_generating() {
@@ -485,7 +485,7 @@ where
         buf: &mut StreamBuilder,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) where
         Self: Sized,
     {
@@ -31,7 +31,7 @@ use std::{
 use tachys::{
     hydration::Cursor,
     reactive_graph::{OwnedView, Suspend},
-    ssr::StreamBuilder,
+    ssr::{BranchMarks, StreamBuilder},
     view::{
         add_attr::AddAnyAttr,
         any_view::{AnyView, IntoAny},
@@ -236,7 +236,7 @@ where
         buf: &mut String,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) {
         // if this is being run on the server for the first time, generating all possible routes
         if RouteList::is_generating() {
@@ -325,7 +325,7 @@ where
         buf: &mut StreamBuilder,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) where
         Self: Sized,
     {
@@ -17,7 +17,7 @@ use tachydom::{
     hydration::Cursor,
     log,
     renderer::Renderer,
-    ssr::StreamBuilder,
+    ssr::{BranchMarks, StreamBuilder},
     view::{Mountable, Position, PositionState, Render, RenderHtml},
 };
 
@@ -129,7 +129,7 @@ where
         buf: &mut String,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) {
         // if this is being run on the server for the first time, generating all possible routes
         if RouteList::is_generating() {
@@ -155,7 +155,7 @@ where
         buf: &mut StreamBuilder,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) where
         Self: Sized,
     {
@@ -323,7 +323,7 @@ where
         buf: &mut String,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) {
         let MatchedRoute {
             search_params,
@@ -345,7 +345,7 @@ where
         buf: &mut StreamBuilder,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) where
         Self: Sized,
     {
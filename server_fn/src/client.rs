@@ -1,8 +1,28 @@
 use crate::{error::ServerFnError, request::ClientReq, response::ClientRes};
-use std::{future::Future, sync::OnceLock};
+use futures::{
+    future::{BoxFuture, Shared, WeakShared},
+    FutureExt,
+};
+use once_cell::sync::Lazy;
+use std::{
+    any::Any,
+    collections::HashMap,
+    fmt,
+    future::Future,
+    sync::{Arc, Mutex, OnceLock, RwLock},
+};
 
 static ROOT_URL: OnceLock<&'static str> = OnceLock::new();
 
+static SERVER_FN_CLIENT: RwLock<Option<Arc<ServerFnClient>>> =
+    RwLock::new(None);
+
+/// Requests that are currently in flight, keyed by URL, so that identical requests can share a
+/// response. Each entry is a [`WeakShared`] future, so an entry whose callers have all been
+/// dropped is simply replaced.
+static IN_FLIGHT: Lazy<Mutex<HashMap<String, Box<dyn Any + Send + Sync>>>> =
+    Lazy::new(Default::default);
+
 /// Set the root server URL that all server function paths are relative to for the client.
 ///
 /// If this is not set, it defaults to the origin.
@@ -11,8 +31,250 @@ pub fn set_server_url(url: &'static str) {
 }
 
 /// Returns the root server URL for all server functions.
+///
+/// This is the [`base_url`](ServerFnClient::base_url) of the [`ServerFnClient`], if one has been
+/// set, or the URL given to [`set_server_url`].
 pub fn get_server_url() -> &'static str {
-    ROOT_URL.get().copied().unwrap_or("")
+    server_fn_client()
+        .and_then(|client| client.base_url)
+        .or_else(|| ROOT_URL.get().copied())
+        .unwrap_or("")
+}
+
+/// Sets the [`ServerFnClient`] that configures every server function request made by this
+/// client, replacing any that was set before.
+pub fn set_server_fn_client(client: ServerFnClient) {
+    *SERVER_FN_CLIENT.write().unwrap_or_else(|e| e.into_inner()) =
+        Some(Arc::new(client));
+}
+
+/// Returns the [`ServerFnClient`] set with [`set_server_fn_client`], if any.
+pub fn server_fn_client() -> Option<Arc<ServerFnClient>> {
+    SERVER_FN_CLIENT
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+}
+
+/// The credentials mode of a request, which controls whether cookies and other credentials are
+/// sent to another origin.
+///
+/// This corresponds to the [`credentials`](https://developer.mozilla.org/en-US/docs/Web/API/Request/credentials)
+/// of a `fetch` request in the browser.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Credentials {
+    /// Never send credentials.
+    Omit,
+    /// Only send credentials to the same origin. This is the default in the browser.
+    SameOrigin,
+    /// Always send credentials, including to other origins.
+    Include,
+}
+
+type HeaderFn = Arc<dyn Fn() -> Option<String> + Send + Sync>;
+type OnRequest<Req> = Box<dyn Fn(Req) -> Req + Send + Sync>;
+type OnResponse<Res> =
+    Box<dyn Fn(Res) -> Result<Res, ServerFnError> + Send + Sync>;
+
+/// Configures how server function requests are sent by the client, for all server functions.
+///
+/// This is set with [`set_server_fn_client`].
+///
+/// ```rust,ignore
+/// let (token, set_token) = signal(None::<String>);
+/// set_server_fn_client(
+///     ServerFnClient::new()
+///         // send requests to the API on another origin
+///         .base_url("https://api.example.com")
+///         .credentials(Credentials::Include)
+///         // the header is read again for every request
+///         .header_with("Authorization", move || {
+///             token.get_untracked().map(|token| format!("Bearer {token}"))
+///         }),
+/// );
+/// ```
+#[derive(Clone, Default)]
+pub struct ServerFnClient {
+    base_url: Option<&'static str>,
+    headers: Vec<(String, HeaderFn)>,
+    credentials: Option<Credentials>,
+    on_request: Option<Arc<dyn Any + Send + Sync>>,
+    on_response: Option<Arc<dyn Any + Send + Sync>>,
+}
+
+impl fmt::Debug for ServerFnClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ServerFnClient")
+            .field("base_url", &self.base_url)
+            .field(
+                "headers",
+                &self
+                    .headers
+                    .iter()
+                    .map(|(name, _)| name)
+                    .collect::<Vec<_>>(),
+            )
+            .field("credentials", &self.credentials)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ServerFnClient {
+    /// Creates a new configuration, which does not change any requests yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the root URL that all server function paths are relative to, like
+    /// [`set_server_url`].
+    pub fn base_url(mut self, url: &'static str) -> Self {
+        self.base_url = Some(url);
+        self
+    }
+
+    /// Adds a header to every request.
+    pub fn header(
+        self,
+        name: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        let value = value.into();
+        self.header_with(name, move || Some(value.clone()))
+    }
+
+    /// Adds a header to every request, with a value that is computed again for each request.
+    ///
+    /// The header is left out if this returns `None`.
+    pub fn header_with(
+        mut self,
+        name: impl Into<String>,
+        value: impl Fn() -> Option<String> + Send + Sync + 'static,
+    ) -> Self {
+        self.headers.push((name.into(), Arc::new(value)));
+        self
+    }
+
+    /// Sets the [`Credentials`] mode of every request.
+    pub fn credentials(mut self, credentials: Credentials) -> Self {
+        self.credentials = Some(credentials);
+        self
+    }
+
+    /// Calls `on_request` with every request before it is sent, and sends the request it returns.
+    ///
+    /// `Req` is the request type of the server function's [`Client`], like
+    /// [`BrowserRequest`](crate::request::browser::BrowserRequest). Requests of other types are
+    /// sent unchanged.
+    pub fn on_request<Req: 'static>(
+        mut self,
+        on_request: impl Fn(Req) -> Req + Send + Sync + 'static,
+    ) -> Self {
+        let on_request: OnRequest<Req> = Box::new(on_request);
+        self.on_request = Some(Arc::new(on_request));
+        self
+    }
+
+    /// Calls `on_response` with every response before it is handled. If it returns an error,
+    /// the server function call fails with that error.
+    ///
+    /// `Res` is the response type of the server function's [`Client`], like
+    /// [`BrowserResponse`](crate::response::browser::BrowserResponse). Responses of other types
+    /// are handled unchanged.
+    pub fn on_response<Res: 'static>(
+        mut self,
+        on_response: impl Fn(Res) -> Result<Res, ServerFnError>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        let on_response: OnResponse<Res> = Box::new(on_response);
+        self.on_response = Some(Arc::new(on_response));
+        self
+    }
+
+    pub(crate) fn prepare_request<CustErr, Req>(
+        &self,
+        mut req: Req,
+    ) -> Result<Req, ServerFnError<CustErr>>
+    where
+        Req: ClientReq<CustErr> + 'static,
+    {
+        for (name, value) in &self.headers {
+            if let Some(value) = value() {
+                req.try_add_header(name, &value)?;
+            }
+        }
+        if let Some(credentials) = self.credentials {
+            req = req.try_set_credentials(credentials)?;
+        }
+        if let Some(on_request) = self
+            .on_request
+            .as_ref()
+            .and_then(|f| f.downcast_ref::<OnRequest<Req>>())
+        {
+            req = on_request(req);
+        }
+        Ok(req)
+    }
+
+    pub(crate) fn intercept_response<CustErr, Res>(
+        &self,
+        res: Res,
+    ) -> Result<Res, ServerFnError<CustErr>>
+    where
+        Res: 'static,
+    {
+        match self
+            .on_response
+            .as_ref()
+            .and_then(|f| f.downcast_ref::<OnResponse<Res>>())
+        {
+            Some(on_response) => {
+                on_response(res).map_err(ServerFnError::with_custom_error)
+            }
+            None => Ok(res),
+        }
+    }
+}
+
+/// Runs `fut`, unless a request with the same `key` is already in flight, in which case this
+/// waits for that request and returns a clone of its result instead.
+///
+/// The entry is removed as soon as the request completes, so this only deduplicates concurrent
+/// requests and never caches a response.
+pub(crate) fn dedupe<T>(
+    key: String,
+    fut: impl Future<Output = T> + Send + 'static,
+) -> Shared<BoxFuture<'static, T>>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    let mut in_flight = IN_FLIGHT.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(shared) = in_flight
+        .get(&key)
+        .and_then(|f| f.downcast_ref::<WeakShared<BoxFuture<'static, T>>>())
+        .and_then(WeakShared::upgrade)
+    {
+        return shared;
+    }
+
+    let shared = {
+        let key = key.clone();
+        async move {
+            let res = fut.await;
+            IN_FLIGHT
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .remove(&key);
+            res
+        }
+    }
+    .boxed()
+    .shared();
+    if let Some(weak) = shared.downgrade() {
+        in_flight.insert(key, Box::new(weak));
+    }
+    shared
 }
 
 /// A client defines a pair of request/response types and the logic to send
@@ -23,9 +285,9 @@ pub fn get_server_url() -> &'static str {
 /// yourself, unless you’re trying to use an alternative HTTP crate on the client side.
 pub trait Client<CustErr> {
     /// The type of a request sent by this client.
-    type Request: ClientReq<CustErr> + Send;
+    type Request: ClientReq<CustErr> + Send + 'static;
     /// The type of a response received by this client.
-    type Response: ClientRes<CustErr> + Send;
+    type Response: ClientRes<CustErr> + Send + 'static;
 
     /// Sends the request and receives a response.
     fn send(
@@ -61,7 +323,17 @@ pub mod browser {
                 let RequestInner {
                     request,
                     mut abort_ctrl,
+                    upload,
                 } = req;
+                if let Some(upload) = upload {
+                    return upload
+                        .send()
+                        .await
+                        .map(|res| {
+                            BrowserResponse(SendWrapper::new(res.into()))
+                        })
+                        .map_err(ServerFnError::Request);
+                }
                 let res = request
                     .send()
                     .await
@@ -0,0 +1,204 @@
+use super::{Encoding, FromRes, IntoRes};
+use crate::{
+    error::{NoCustomError, ServerFnError, ServerFnErrorSerde},
+    response::{ClientRes, Res},
+};
+use bytes::Bytes;
+use futures::{stream, Stream, StreamExt};
+use http::Method;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    fmt::{Display, Write},
+    pin::Pin,
+    str::FromStr,
+    task::{Context, Poll},
+};
+
+/// An encoding that sends a stream of JSON values as [server-sent events].
+///
+/// A server function that uses this as its output encoding should return [`EventStream`].
+///
+/// Each item is sent as a separate event, so the client receives exactly the items the server
+/// sent, however the response is split into chunks along the way. An error returned by the
+/// stream on the server is sent as an `error` event, and received by the client as an `Err`
+/// item, after which the stream continues with any further items.
+///
+/// The stream is dropped on the server when the client stops reading the response, so dropping
+/// the [`EventStream`] on the client cancels the stream on the server as well.
+///
+/// [server-sent events]: https://html.spec.whatwg.org/multipage/server-sent-events.html
+pub struct StreamingEvents;
+
+impl Encoding for StreamingEvents {
+    const CONTENT_TYPE: &'static str = "text/event-stream";
+    const METHOD: Method = Method::POST;
+}
+
+/// A stream of typed items, sent from the server to the client as server-sent events.
+///
+/// A server function can return this type if its output encoding is [`StreamingEvents`]. On the
+/// client, it is a [`Stream`] of the items sent by the server.
+pub struct EventStream<T, CustErr = NoCustomError>(
+    Pin<Box<dyn Stream<Item = Result<T, ServerFnError<CustErr>>> + Send>>,
+);
+
+impl<T, CustErr> std::fmt::Debug for EventStream<T, CustErr> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("EventStream").finish()
+    }
+}
+
+impl<T, CustErr> EventStream<T, CustErr> {
+    /// Creates a new `EventStream` from the given stream.
+    pub fn new(
+        value: impl Stream<Item = Result<T, ServerFnError<CustErr>>>
+            + Send
+            + 'static,
+    ) -> Self {
+        Self(Box::pin(value))
+    }
+
+    /// Consumes the wrapper, returning a stream of items.
+    pub fn into_inner(
+        self,
+    ) -> impl Stream<Item = Result<T, ServerFnError<CustErr>>> + Send {
+        self.0
+    }
+}
+
+impl<S, T: 'static, CustErr: 'static> From<S> for EventStream<T, CustErr>
+where
+    S: Stream<Item = T> + Send + 'static,
+{
+    fn from(value: S) -> Self {
+        Self(Box::pin(value.map(Ok)))
+    }
+}
+
+impl<T, CustErr> Stream for EventStream<T, CustErr> {
+    type Item = Result<T, ServerFnError<CustErr>>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        self.0.as_mut().poll_next(cx)
+    }
+}
+
+impl<CustErr, T, Response> IntoRes<StreamingEvents, Response, CustErr>
+    for EventStream<T, CustErr>
+where
+    Response: Res<CustErr>,
+    CustErr: FromStr + Display + 'static,
+    T: Serialize + 'static,
+{
+    async fn into_res(self) -> Result<Response, ServerFnError<CustErr>> {
+        Response::try_from_stream(
+            StreamingEvents::CONTENT_TYPE,
+            self.into_inner().map(|value| {
+                let event = match value {
+                    Ok(value) => {
+                        let data =
+                            serde_json::to_string(&value).map_err(|e| {
+                                ServerFnError::Serialization(e.to_string())
+                            })?;
+                        encode_event(None, &data)
+                    }
+                    Err(e) => encode_event(
+                        Some("error"),
+                        &e.ser().unwrap_or_else(|_| e.to_string()),
+                    ),
+                };
+                Ok(Bytes::from(event))
+            }),
+        )
+    }
+}
+
+impl<CustErr, T, Response> FromRes<StreamingEvents, Response, CustErr>
+    for EventStream<T>
+where
+    Response: ClientRes<CustErr> + Send,
+    T: DeserializeOwned + Send + 'static,
+{
+    async fn from_res(res: Response) -> Result<Self, ServerFnError<CustErr>> {
+        let stream = res.try_into_stream()?;
+        Ok(EventStream::new(decode_events(stream)))
+    }
+}
+
+fn encode_event(event: Option<&str>, data: &str) -> String {
+    let mut buf = String::new();
+    if let Some(event) = event {
+        _ = writeln!(buf, "event: {event}");
+    }
+    // data containing line breaks is split across several data fields
+    for line in data.lines() {
+        _ = writeln!(buf, "data: {line}");
+    }
+    buf.push('\n');
+    buf
+}
+
+fn decode_events<T>(
+    stream: impl Stream<Item = Result<Bytes, ServerFnError>> + Send + 'static,
+) -> impl Stream<Item = Result<T, ServerFnError>> + Send
+where
+    T: DeserializeOwned + Send + 'static,
+{
+    // the response may split an event across several chunks, or pack several events into one
+    let mut buf = Vec::new();
+    stream
+        .map(Some)
+        .chain(stream::once(async { None }))
+        .map(move |chunk| {
+            let mut events = Vec::new();
+            match chunk {
+                Some(Ok(bytes)) => {
+                    buf.extend_from_slice(&bytes);
+                    while let Some(end) =
+                        buf.windows(2).position(|window| window == b"\n\n")
+                    {
+                        let event = buf.drain(..end + 2).collect::<Vec<_>>();
+                        events.push(decode_event(&event[..end]));
+                    }
+                }
+                Some(Err(e)) => events.push(Err(e)),
+                None if !buf.iter().all(u8::is_ascii_whitespace) => {
+                    events.push(Err(ServerFnError::Deserialization(
+                        "the stream ended in the middle of an event".into(),
+                    )));
+                }
+                None => {}
+            }
+            stream::iter(events)
+        })
+        .flatten()
+}
+
+fn decode_event<T>(event: &[u8]) -> Result<T, ServerFnError>
+where
+    T: DeserializeOwned,
+{
+    let event = std::str::from_utf8(event).map_err(|e| {
+        ServerFnError::<NoCustomError>::Deserialization(e.to_string())
+    })?;
+    let mut kind = None;
+    let mut data = Vec::new();
+    for line in event.lines() {
+        let (field, value) = line.split_once(':').unwrap_or((line, ""));
+        let value = value.strip_prefix(' ').unwrap_or(value);
+        match field {
+            "event" => kind = Some(value),
+            "data" => data.push(value),
+            _ => {}
+        }
+    }
+    let data = data.join("\n");
+    match kind {
+        Some("error") => Err(ServerFnError::de(&data)),
+        _ => serde_json::from_str(&data)
+            .map_err(|e| ServerFnError::Deserialization(e.to_string())),
+    }
+}
@@ -1,13 +1,13 @@
 use super::{Encoding, FromReq, FromRes, Streaming};
 use crate::{
-    error::{NoCustomError, ServerFnError},
+    error::{ErrorEncoding, NoCustomError, ServerFnError, ServerFnErrorSerde},
     request::{ClientReq, Req},
     response::{ClientRes, Res},
     IntoReq, IntoRes,
 };
 use bytes::Bytes;
 use futures::{Stream, StreamExt};
-use http::Method;
+use http::{Method, StatusCode};
 use serde::{de::DeserializeOwned, Serialize};
 use std::pin::Pin;
 /// Pass arguments and receive responses as JSON in the body of a `POST` request.
@@ -70,6 +70,41 @@ where
     }
 }
 
+/// Sends the errors of a server function that uses `#[server(error = MyError)]` as JSON.
+///
+/// The HTTP status code of the response for a custom error is given by its
+/// `impl From<&MyError> for StatusCode`. All other errors use a `500` status code.
+impl<CustErr> ErrorEncoding<CustErr> for Json
+where
+    CustErr: Serialize + DeserializeOwned,
+    for<'a> StatusCode: From<&'a CustErr>,
+{
+    fn status(err: &ServerFnError<CustErr>) -> u16 {
+        match err {
+            ServerFnError::WrappedServerError(e) => StatusCode::from(e),
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+        .as_u16()
+    }
+
+    fn ser(err: &ServerFnError<CustErr>) -> String {
+        serde_json::to_string(err).unwrap_or_else(|e| {
+            serde_json::to_string(&ServerFnError::<CustErr>::Serialization(
+                e.to_string(),
+            ))
+            .unwrap_or_default()
+        })
+    }
+
+    fn de(data: &str) -> ServerFnError<CustErr> {
+        // errors that did not come from the server function itself, like those returned by
+        // middleware, are sent as text
+        serde_json::from_str(data).unwrap_or_else(|_| {
+            ServerFnError::<NoCustomError>::de(data).with_custom_error()
+        })
+    }
+}
+
 /// An encoding that represents a stream of JSON data.
 ///
 /// A server function that uses this as its output encoding should return [`StreamingJson`]
@@ -90,6 +125,25 @@ impl Encoding for StreamingJson {
     const METHOD: Method = Streaming::METHOD;
 }
 
+/// Sends the errors of a server function that uses `#[server(error = MyError)]` as JSON, like
+/// [`Json`].
+impl<CustErr> ErrorEncoding<CustErr> for StreamingJson
+where
+    Json: ErrorEncoding<CustErr>,
+{
+    fn status(err: &ServerFnError<CustErr>) -> u16 {
+        Json::status(err)
+    }
+
+    fn ser(err: &ServerFnError<CustErr>) -> String {
+        Json::ser(err)
+    }
+
+    fn de(data: &str) -> ServerFnError<CustErr> {
+        Json::de(data)
+    }
+}
+
 /// A stream of typed data encoded as JSON.
 ///
 /// A server function can return this type if its output encoding is [`StreamingJson`].
@@ -24,6 +24,11 @@ mod json;
 #[cfg(feature = "json")]
 pub use json::*;
 
+#[cfg(feature = "json")]
+mod event_stream;
+#[cfg(feature = "json")]
+pub use event_stream::*;
+
 #[cfg(feature = "serde-lite")]
 mod serde_lite;
 #[cfg(feature = "serde-lite")]
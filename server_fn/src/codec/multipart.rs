@@ -1,17 +1,47 @@
 use super::{Encoding, FromReq};
 use crate::{
-    error::ServerFnError,
+    error::{NoCustomError, ServerFnError},
     request::{browser::BrowserFormData, ClientReq, Req},
     IntoReq,
 };
+use bytes::{Bytes, BytesMut};
 use futures::StreamExt;
 use http::Method;
 use multer::Multipart;
-use web_sys::FormData;
+use web_sys::{FileList, FormData};
 
 /// Encodes multipart form data.
 ///
-/// You should primarily use this if you are trying to handle file uploads.
+/// You should primarily use this if you are trying to handle file uploads. The argument of the
+/// server function is a [`MultipartData`], which is created from a `FormData` or `FileList` on
+/// the client, and read as a stream of fields on the server.
+///
+/// ```rust,ignore
+/// #[server(input = MultipartFormData)]
+/// pub async fn upload(data: MultipartData) -> Result<usize, ServerFnError> {
+///     let limits = MultipartLimits::new().per_field(10 * 1024 * 1024);
+///     let mut data = data.into_limited(limits).unwrap();
+///     let mut size = 0;
+///     while let Some(mut field) = data.next_field().await? {
+///         // stop reading the upload early if it isn't an image
+///         if !field.content_type().is_some_and(|ty| ty.starts_with("image/")) {
+///             return Err(ServerFnError::Args("expected an image".into()));
+///         }
+///         while let Some(chunk) = field.chunk().await? {
+///             size += chunk.len();
+///         }
+///     }
+///     Ok(size)
+/// }
+///
+/// // on the client
+/// let (progress, set_progress) = signal(0.0);
+/// let data = MultipartData::from_files("images", &input.files().unwrap())?
+///     .on_progress(move |uploaded| set_progress.set(uploaded));
+/// spawn_local(async move {
+///     upload(data).await;
+/// });
+/// ```
 pub struct MultipartFormData;
 
 impl Encoding for MultipartFormData {
@@ -39,6 +69,21 @@ impl MultipartData {
         }
     }
 
+    /// Reads the multipart data on the server side, stopping with an error as soon as one of
+    /// the given limits is exceeded, without reading the rest of the request.
+    ///
+    /// On the server side, this always returns `Some(_)`. On the client side, always returns `None`.
+    pub fn into_limited(
+        self,
+        limits: MultipartLimits,
+    ) -> Option<LimitedMultipart> {
+        self.into_inner().map(|inner| LimitedMultipart {
+            inner,
+            limits,
+            read: 0,
+        })
+    }
+
     /// Extracts the inner form data on the client side.
     ///
     /// On the server side, this always returns `None`. On the client side, always returns `Some(_)`.
@@ -48,6 +93,176 @@ impl MultipartData {
             MultipartData::Server(_) => None,
         }
     }
+
+    /// Creates multipart data on the client side, with each of the given files as a field with
+    /// the given name.
+    pub fn from_files(
+        name: &str,
+        files: &FileList,
+    ) -> Result<Self, ServerFnError> {
+        let err = |e: wasm_bindgen::JsValue| {
+            ServerFnError::<NoCustomError>::Serialization(format!("{e:?}"))
+        };
+        let data = FormData::new().map_err(err)?;
+        for file in (0..files.length()).filter_map(|i| files.get(i)) {
+            data.append_with_blob_and_filename(name, &file, &file.name())
+                .map_err(err)?;
+        }
+        Ok(data.into())
+    }
+
+    /// On the client side, calls `on_progress` with the fraction of the data that has been
+    /// uploaded, from `0.0` to `1.0`, while it is being sent.
+    ///
+    /// This does nothing on the server side.
+    pub fn on_progress(self, on_progress: impl Fn(f64) + 'static) -> Self {
+        match self {
+            MultipartData::Client(data) => {
+                MultipartData::Client(data.on_progress(on_progress))
+            }
+            server => server,
+        }
+    }
+}
+
+/// Limits on the size of [`MultipartData`] read on the server with
+/// [`MultipartData::into_limited`].
+#[derive(Debug, Clone, Default)]
+pub struct MultipartLimits {
+    whole_stream: Option<usize>,
+    per_field: Option<usize>,
+    allowed_fields: Option<Vec<String>>,
+}
+
+impl MultipartLimits {
+    /// Creates a new set of limits, which does not limit anything yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of bytes read from all fields together.
+    pub fn whole_stream(mut self, bytes: usize) -> Self {
+        self.whole_stream = Some(bytes);
+        self
+    }
+
+    /// Sets the maximum number of bytes read from any single field.
+    pub fn per_field(mut self, bytes: usize) -> Self {
+        self.per_field = Some(bytes);
+        self
+    }
+
+    /// Only allows fields with the given names.
+    pub fn allowed_fields(
+        mut self,
+        names: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.allowed_fields = Some(names.into_iter().map(Into::into).collect());
+        self
+    }
+}
+
+/// Multipart data on the server, read with a set of [`MultipartLimits`].
+///
+/// Returning early, for example because a field has an unexpected name or content type, drops
+/// the rest of the request without reading it.
+#[derive(Debug)]
+pub struct LimitedMultipart {
+    inner: Multipart<'static>,
+    limits: MultipartLimits,
+    read: usize,
+}
+
+impl LimitedMultipart {
+    /// Returns the next field, or `None` if there are no more fields.
+    ///
+    /// The field must be read before asking for the next one.
+    pub async fn next_field(
+        &mut self,
+    ) -> Result<Option<LimitedField<'_>>, ServerFnError> {
+        let field =
+            self.inner.next_field().await.map_err(|e| {
+                ServerFnError::<NoCustomError>::Args(e.to_string())
+            })?;
+        let Some(field) = field else {
+            return Ok(None);
+        };
+        if let Some(allowed) = &self.limits.allowed_fields {
+            let name = field.name().unwrap_or_default();
+            if !allowed.iter().any(|allowed| allowed == name) {
+                return Err(ServerFnError::Args(format!(
+                    "unexpected multipart field {name:?}"
+                )));
+            }
+        }
+        Ok(Some(LimitedField {
+            inner: field,
+            multipart: self,
+            read: 0,
+        }))
+    }
+}
+
+/// A single field of [`LimitedMultipart`] data.
+#[derive(Debug)]
+pub struct LimitedField<'a> {
+    inner: multer::Field<'static>,
+    multipart: &'a mut LimitedMultipart,
+    read: usize,
+}
+
+impl LimitedField<'_> {
+    /// The name of the field.
+    pub fn name(&self) -> Option<&str> {
+        self.inner.name()
+    }
+
+    /// The name of the uploaded file, if this field is a file.
+    pub fn file_name(&self) -> Option<&str> {
+        self.inner.file_name()
+    }
+
+    /// The content type of the field.
+    pub fn content_type(&self) -> Option<&str> {
+        self.inner.content_type().map(AsRef::as_ref)
+    }
+
+    /// Reads the next chunk of the field, or `None` if the whole field has been read.
+    pub async fn chunk(&mut self) -> Result<Option<Bytes>, ServerFnError> {
+        let chunk =
+            self.inner.chunk().await.map_err(|e| {
+                ServerFnError::<NoCustomError>::Args(e.to_string())
+            })?;
+        if let Some(chunk) = &chunk {
+            self.read += chunk.len();
+            self.multipart.read += chunk.len();
+            let limits = &self.multipart.limits;
+            if limits.per_field.is_some_and(|max| self.read > max) {
+                return Err(ServerFnError::Args(format!(
+                    "multipart field {:?} is too large",
+                    self.name().unwrap_or_default()
+                )));
+            }
+            if limits
+                .whole_stream
+                .is_some_and(|max| self.multipart.read > max)
+            {
+                return Err(ServerFnError::Args(
+                    "multipart data is too large".into(),
+                ));
+            }
+        }
+        Ok(chunk)
+    }
+
+    /// Reads the rest of the field.
+    pub async fn bytes(mut self) -> Result<Bytes, ServerFnError> {
+        let mut buf = BytesMut::new();
+        while let Some(chunk) = self.chunk().await? {
+            buf.extend_from_slice(&chunk);
+        }
+        Ok(buf.freeze())
+    }
 }
 
 impl From<FormData> for MultipartData {
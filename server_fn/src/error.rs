@@ -298,6 +298,93 @@ where
     }
 }
 
+/// Describes how the errors returned by a server function are sent from the server to the
+/// client.
+///
+/// By default, server functions send their errors with [`TextErrors`]. A server function that
+/// uses `#[server(error = MyError)]` sends its errors with the same encoding as its output instead
+/// (see [`ServerFn::error_response`](crate::ServerFn::error_response)), so its custom error type
+/// also needs to implement the traits that encoding requires (for example, `Serialize` and
+/// `Deserialize` for [`Json`](crate::codec::Json)).
+///
+/// This is implemented for [`Json`](crate::codec::Json) and
+/// [`StreamingJson`](crate::codec::StreamingJson).
+pub trait ErrorEncoding<CustErr> {
+    /// The HTTP status code of the response for the given error.
+    fn status(err: &ServerFnError<CustErr>) -> u16;
+
+    /// Serializes the error into the body of the response.
+    fn ser(err: &ServerFnError<CustErr>) -> String;
+
+    /// Deserializes the error from the body of the response.
+    fn de(data: &str) -> ServerFnError<CustErr>;
+}
+
+/// The default [`ErrorEncoding`], which sends errors as text using [`ServerFnErrorSerde`], with a
+/// `500` status code.
+///
+/// This requires the custom error type to implement [`FromStr`] and [`Display`].
+#[derive(Debug, Clone, Copy)]
+pub struct TextErrors;
+
+impl<CustErr> ErrorEncoding<CustErr> for TextErrors
+where
+    CustErr: FromStr + Display,
+{
+    fn status(_err: &ServerFnError<CustErr>) -> u16 {
+        500
+    }
+
+    fn ser(err: &ServerFnError<CustErr>) -> String {
+        err.ser().unwrap_or_else(|_| err.to_string())
+    }
+
+    fn de(data: &str) -> ServerFnError<CustErr> {
+        ServerFnError::de(data)
+    }
+}
+
+impl ServerFnError<NoCustomError> {
+    /// Converts an error without a custom error type into a [`ServerFnError<CustErr>`] for any
+    /// custom error type.
+    pub(crate) fn with_custom_error<CustErr>(self) -> ServerFnError<CustErr> {
+        match self {
+            ServerFnError::WrappedServerError(_) => {
+                ServerFnError::Deserialization(
+                    "missing custom error value".to_string(),
+                )
+            }
+            ServerFnError::Registration(s) => ServerFnError::Registration(s),
+            ServerFnError::Request(s) => ServerFnError::Request(s),
+            ServerFnError::Response(s) => ServerFnError::Response(s),
+            ServerFnError::ServerError(s) => ServerFnError::ServerError(s),
+            ServerFnError::Deserialization(s) => {
+                ServerFnError::Deserialization(s)
+            }
+            ServerFnError::Serialization(s) => ServerFnError::Serialization(s),
+            ServerFnError::Args(s) => ServerFnError::Args(s),
+            ServerFnError::MissingArg(s) => ServerFnError::MissingArg(s),
+        }
+    }
+}
+
+/// Registers [`ServerFnError<CustErr>`] as a serializable error type with [`throw_error`].
+///
+/// A server function error that is caught by an error boundary while rendering on the server is
+/// sent to the client along with the rest of the page. Once it is registered under the same tag on
+/// both the server and the client, it keeps its type, so the boundary's fallback can
+/// [downcast](throw_error::Error::downcast_ref) it.
+pub fn register_error_type<CustErr>(tag: &'static str)
+where
+    CustErr: std::error::Error + FromStr + Send + Sync + 'static,
+{
+    throw_error::register_error_type_with::<ServerFnError<CustErr>>(
+        tag,
+        |error| error.ser().unwrap_or_default(),
+        |data| Some(ServerFnError::de(data)),
+    );
+}
+
 impl<E> std::error::Error for ServerFnError<E>
 where
     E: std::error::Error + 'static,
@@ -416,13 +503,19 @@ impl<CustErr> ServerFnUrlError<CustErr> {
     where
         CustErr: FromStr + Display,
     {
+        self.to_url_with(base, TextErrors::ser)
+    }
+
+    /// Adds a form of this server function error, serialized with `ser`, to the given base URL.
+    pub fn to_url_with(
+        &self,
+        base: &str,
+        ser: impl FnOnce(&ServerFnError<CustErr>) -> String,
+    ) -> Result<Url, url::ParseError> {
         let mut url = Url::parse(base)?;
         url.query_pairs_mut()
             .append_pair("__path", &self.path)
-            .append_pair(
-                "__err",
-                &ServerFnErrorSerde::ser(&self.error).unwrap_or_default(),
-            );
+            .append_pair("__err", &ser(&self.error));
         Ok(url)
     }
 
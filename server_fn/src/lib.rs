@@ -113,6 +113,8 @@ pub mod redirect;
 pub mod request;
 /// Types and traits for HTTP responses.
 pub mod response;
+#[cfg(feature = "websocket")]
+pub mod websocket;
 
 #[cfg(feature = "actix")]
 #[doc(hidden)]
@@ -126,14 +128,14 @@ use codec::{Encoding, FromReq, FromRes, IntoReq, IntoRes};
 pub use const_format;
 use dashmap::DashMap;
 pub use error::ServerFnError;
-use error::ServerFnErrorSerde;
 #[cfg(feature = "form-redirects")]
 use error::ServerFnUrlError;
+use error::{ErrorEncoding, TextErrors};
 use http::Method;
 use middleware::{Layer, Service};
 use once_cell::sync::Lazy;
 use redirect::RedirectHook;
-use request::Req;
+use request::{ClientReq, Req};
 use response::{ClientRes, Res};
 #[cfg(feature = "rkyv")]
 pub use rkyv;
@@ -220,6 +222,13 @@ where
     /// custom error type, this can be `NoCustomError` by default.)
     type Error: FromStr + Display;
 
+    /// The `Cache-Control` header sent with successful responses, if the server function uses
+    /// a `GET` input encoding. This is set with `#[server(cache = "...")]`.
+    ///
+    /// Functions that set this also deduplicate identical requests on the client, using
+    /// [`run_on_client_deduped`](ServerFn::run_on_client_deduped).
+    const CACHE_CONTROL: Option<&'static str> = None;
+
     /// Returns [`Self::PATH`].
     fn url() -> &'static str {
         Self::PATH
@@ -231,6 +240,28 @@ where
         Vec::new()
     }
 
+    /// Converts an error returned by the server function into the response sent to the client.
+    ///
+    /// By default, this is [`Res::error_response`], which sends the error as text with a `500`
+    /// status code. A server function that uses `#[server(error = MyError)]` sends it with the
+    /// [`ErrorEncoding`] of its output instead.
+    fn error_response(
+        err: &ServerFnError<Self::Error>,
+    ) -> Self::ServerResponse {
+        Self::ServerResponse::error_response(Self::PATH, err)
+    }
+
+    /// Serializes an error returned by the server function, in the same format as
+    /// [`ServerFn::error_response`]. This is the inverse of [`ServerFn::de_error`].
+    fn ser_error(err: &ServerFnError<Self::Error>) -> String {
+        TextErrors::ser(err)
+    }
+
+    /// Deserializes an error sent by the server function.
+    fn de_error(data: &str) -> ServerFnError<Self::Error> {
+        TextErrors::de(data)
+    }
+
     /// The body of the server function. This will only run on the server.
     fn run_body(
         self,
@@ -256,13 +287,15 @@ where
             // used in form redirects feature
             let (mut res, err) = Self::execute_on_server(req)
                 .await
-                .map(|res| (res, None))
-                .unwrap_or_else(|e| {
-                    (
-                        Self::ServerResponse::error_response(Self::PATH, &e),
-                        Some(e),
-                    )
-                });
+                .map(|mut res| {
+                    if let Some(cache_control) = Self::CACHE_CONTROL {
+                        if Self::InputEncoding::METHOD == Method::GET {
+                            res.set_header("Cache-Control", cache_control);
+                        }
+                    }
+                    (res, None)
+                })
+                .unwrap_or_else(|e| (Self::error_response(&e), Some(e)));
 
             // if it accepts HTML, we'll redirect to the Referer
             #[cfg(feature = "form-redirects")]
@@ -270,7 +303,10 @@ where
                 // if it had an error, encode that error in the URL
                 if let Some(err) = err {
                     if let Ok(url) = ServerFnUrlError::new(Self::PATH, err)
-                        .to_url(referer.as_deref().unwrap_or("/"))
+                        .to_url_with(
+                            referer.as_deref().unwrap_or("/"),
+                            Self::ser_error,
+                        )
                     {
                         referer = Some(url.to_string());
                     }
@@ -303,6 +339,42 @@ where
         }
     }
 
+    /// Calls the server function from the client, sharing one request between identical calls
+    /// that are in flight at the same time.
+    ///
+    /// This applies only to server functions with a [`CACHE_CONTROL`](ServerFn::CACHE_CONTROL)
+    /// and a `GET` input encoding. Calls with the same arguments wait for the same request,
+    /// until its response arrives. Setting `force` always sends a new request.
+    fn run_on_client_deduped(
+        self,
+        force: bool,
+    ) -> impl Future<Output = Result<Self::Output, ServerFnError<Self::Error>>> + Send
+    where
+        Self: 'static,
+        Self::Output: Clone + Sync + 'static,
+        Self::Error: Clone + Send + Sync + 'static,
+    {
+        async move {
+            if force
+                || Self::CACHE_CONTROL.is_none()
+                || Self::InputEncoding::METHOD != Method::GET
+            {
+                return self.run_on_client().await;
+            }
+            let req =
+                self.into_req(Self::PATH, Self::OutputEncoding::CONTENT_TYPE)?;
+            let key = ClientReq::<Self::Error>::url(&req);
+            client::dedupe(
+                key,
+                Self::run_on_client_with_req(
+                    req,
+                    redirect::REDIRECT_HOOK.get(),
+                ),
+            )
+            .await
+        }
+    }
+
     #[doc(hidden)]
     fn run_on_client_with_req(
         req: <Self::Client as Client<Self::Error>>::Request,
@@ -310,16 +382,25 @@ where
     ) -> impl Future<Output = Result<Self::Output, ServerFnError<Self::Error>>> + Send
     {
         async move {
+            let client = client::server_fn_client();
+            let req = match &client {
+                Some(client) => client.prepare_request(req)?,
+                None => req,
+            };
             let res = Self::Client::send(req).await?;
+            let res = match &client {
+                Some(client) => client.intercept_response(res)?,
+                None => res,
+            };
 
             let status = res.status();
             let location = res.location();
             let has_redirect_header = res.has_redirect();
 
-            // if it returns an error status, deserialize the error using FromStr
+            // if it returns an error status, deserialize the error using the error encoding
             let res = if (400..=599).contains(&status) {
                 let text = res.try_into_string().await?;
-                Err(ServerFnError::<Self::Error>::de(&text))
+                Err(Self::de_error(&text))
             } else {
                 // otherwise, deserialize the body as is
                 Ok(Self::Output::from_res(res).await)
@@ -461,7 +542,7 @@ impl<Req: 'static, Res: 'static> inventory::Collect
 #[cfg(feature = "axum-no-default")]
 pub mod axum {
     use crate::{
-        middleware::{BoxedService, Service},
+        middleware::{BoxedService, Layer, Service},
         Encoding, LazyServerFnMap, ServerFn, ServerFnTraitObj,
     };
     use axum::body::Body;
@@ -540,6 +621,27 @@ pub mod axum {
             service
         })
     }
+
+    /// Returns the server function at the given path as a service that can be modified, with
+    /// `inner` applied directly around the server function, inside any of its own middleware.
+    ///
+    /// This allows an integration to see the request after the server function's middleware
+    /// has run, for example to pick up extensions that the middleware has added.
+    pub fn get_server_fn_service_with(
+        path: &str,
+        method: Method,
+        inner: impl Layer<Request<Body>, Response<Body>>,
+    ) -> Option<BoxedService<Request<Body>, Response<Body>>> {
+        let key = (path.into(), method);
+        REGISTERED_SERVER_FUNCTIONS.get(&key).map(|server_fn| {
+            let middleware = (server_fn.middleware)();
+            let mut service = inner.layer(BoxedService::new(server_fn.clone()));
+            for middleware in middleware {
+                service = middleware.layer(service);
+            }
+            service
+        })
+    }
 }
 
 /// Actix integration.
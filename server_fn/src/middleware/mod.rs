@@ -52,7 +52,7 @@ mod axum {
             let inner = self.call(req);
             Box::pin(async move {
                 inner.await.unwrap_or_else(|e| {
-                    let err = ServerFnError::new(e);
+                    let err: ServerFnError = e.into();
                     Response::<Body>::error_response(&path, &err)
                 })
             })
@@ -130,7 +130,7 @@ mod actix {
             let inner = self.call(req);
             Box::pin(async move {
                 inner.await.unwrap_or_else(|e| {
-                    let err = ServerFnError::new(e);
+                    let err: ServerFnError = e.into();
                     ActixResponse::error_response(&path, &err).take()
                 })
             })
@@ -151,7 +151,7 @@ mod actix {
             let inner = self.call(req.0.take().0);
             Box::pin(async move {
                 ActixResponse::from(inner.await.unwrap_or_else(|e| {
-                    let err = ServerFnError::new(e);
+                    let err: ServerFnError = e.into();
                     ActixResponse::error_response(&path, &err).take()
                 }))
             })
@@ -1,16 +1,25 @@
 use super::ClientReq;
-use crate::{client::get_server_url, error::ServerFnError};
+use crate::{
+    client::{get_server_url, Credentials},
+    error::ServerFnError,
+};
 use bytes::Bytes;
 use futures::{Stream, StreamExt};
 pub use gloo_net::http::Request;
-use js_sys::{Reflect, Uint8Array};
+use js_sys::{Promise, Reflect, Uint8Array};
 use send_wrapper::SendWrapper;
-use std::ops::{Deref, DerefMut};
-use wasm_bindgen::JsValue;
+use std::{
+    fmt::{self, Debug},
+    ops::{Deref, DerefMut},
+    rc::Rc,
+};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
 use wasm_streams::ReadableStream;
 use web_sys::{
-    AbortController, AbortSignal, FormData, Headers, RequestInit,
-    UrlSearchParams,
+    AbortController, AbortSignal, FormData, Headers, ProgressEvent,
+    RequestCredentials, RequestInit, ResponseInit, UrlSearchParams,
+    XmlHttpRequest, XmlHttpRequestResponseType,
 };
 
 /// A `fetch` request made in the browser.
@@ -21,6 +30,7 @@ pub struct BrowserRequest(pub(crate) SendWrapper<RequestInner>);
 pub(crate) struct RequestInner {
     pub(crate) request: Request,
     pub(crate) abort_ctrl: Option<AbortOnDrop>,
+    pub(crate) upload: Option<XhrUpload>,
 }
 
 #[derive(Debug)]
@@ -67,13 +77,126 @@ impl DerefMut for BrowserRequest {
     }
 }
 
+type OnProgress = Rc<dyn Fn(f64)>;
+
 /// The `FormData` type available in the browser.
-#[derive(Debug)]
-pub struct BrowserFormData(pub(crate) SendWrapper<FormData>);
+pub struct BrowserFormData(
+    pub(crate) SendWrapper<FormData>,
+    pub(crate) Option<SendWrapper<OnProgress>>,
+);
+
+impl BrowserFormData {
+    /// Calls `on_progress` with the fraction of the data that has been uploaded, from `0.0` to
+    /// `1.0`, when it is sent as a multipart request.
+    ///
+    /// `fetch` does not report upload progress, so a request with a progress callback is sent
+    /// using `XMLHttpRequest` instead.
+    pub fn on_progress(mut self, on_progress: impl Fn(f64) + 'static) -> Self {
+        self.1 = Some(SendWrapper::new(Rc::new(on_progress)));
+        self
+    }
+}
+
+impl Debug for BrowserFormData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("BrowserFormData").field(&*self.0).finish()
+    }
+}
 
 impl From<FormData> for BrowserFormData {
     fn from(value: FormData) -> Self {
-        Self(SendWrapper::new(value))
+        Self(SendWrapper::new(value), None)
+    }
+}
+
+/// A multipart request that reports its upload progress, which is sent with `XMLHttpRequest`.
+pub(crate) struct XhrUpload {
+    url: String,
+    accepts: String,
+    headers: Vec<(String, String)>,
+    with_credentials: bool,
+    form: FormData,
+    on_progress: OnProgress,
+}
+
+impl Debug for XhrUpload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("XhrUpload")
+            .field("url", &self.url)
+            .field("accepts", &self.accepts)
+            .finish_non_exhaustive()
+    }
+}
+
+struct AbortXhrOnDrop(Option<XmlHttpRequest>);
+
+impl Drop for AbortXhrOnDrop {
+    fn drop(&mut self) {
+        if let Some(xhr) = self.0.take() {
+            _ = xhr.abort();
+        }
+    }
+}
+
+impl XhrUpload {
+    /// Sends the request, aborting it if the returned future is dropped before it completes.
+    pub(crate) async fn send(self) -> Result<web_sys::Response, String> {
+        fn js_err(e: JsValue) -> String {
+            e.as_string().unwrap_or_else(|| format!("{e:?}"))
+        }
+
+        let xhr = XmlHttpRequest::new().map_err(js_err)?;
+        xhr.open("POST", &self.url).map_err(js_err)?;
+        xhr.set_request_header("Accept", &self.accepts)
+            .map_err(js_err)?;
+        for (name, value) in &self.headers {
+            xhr.set_request_header(name, value).map_err(js_err)?;
+        }
+        xhr.set_with_credentials(self.with_credentials);
+        xhr.set_response_type(XmlHttpRequestResponseType::Arraybuffer);
+
+        let on_progress = Rc::clone(&self.on_progress);
+        let progress =
+            Closure::<dyn Fn(ProgressEvent)>::new(move |ev: ProgressEvent| {
+                if ev.length_computable() && ev.total() > 0.0 {
+                    on_progress(ev.loaded() / ev.total());
+                }
+            });
+        xhr.upload()
+            .map_err(js_err)?
+            .set_onprogress(Some(progress.as_ref().unchecked_ref()));
+        let done = Promise::new(&mut |resolve, reject| {
+            xhr.set_onload(Some(&resolve));
+            xhr.set_onerror(Some(&reject));
+            xhr.set_onabort(Some(&reject));
+        });
+
+        let mut abort = AbortXhrOnDrop(Some(xhr.clone()));
+        xhr.send_with_opt_form_data(Some(&self.form))
+            .map_err(js_err)?;
+        JsFuture::from(done)
+            .await
+            .map_err(|_| "the upload could not be completed".to_string())?;
+        abort.0.take();
+        (self.on_progress)(1.0);
+
+        // turn the XMLHttpRequest into a Response, so it can be handled like any other
+        let headers = Headers::new().map_err(js_err)?;
+        for line in xhr.get_all_response_headers().map_err(js_err)?.lines() {
+            if let Some((name, value)) = line.split_once(':') {
+                headers.append(name.trim(), value.trim()).map_err(js_err)?;
+            }
+        }
+        let init = ResponseInit::new();
+        init.set_status(xhr.status().map_err(js_err)?);
+        init.set_headers(&headers);
+        let body = Uint8Array::new(&xhr.response().map_err(js_err)?);
+        let body = (body.length() > 0).then_some(body);
+        web_sys::Response::new_with_opt_buffer_source_and_init(
+            body.as_ref().map(|body| body.unchecked_ref()),
+            &init,
+        )
+        .map_err(js_err)
     }
 }
 
@@ -109,6 +232,7 @@ impl<CustErr> ClientReq<CustErr> for BrowserRequest {
                 .build()
                 .map_err(|e| ServerFnError::Request(e.to_string()))?,
             abort_ctrl,
+            upload: None,
         })))
     }
 
@@ -131,6 +255,7 @@ impl<CustErr> ClientReq<CustErr> for BrowserRequest {
                 .body(body)
                 .map_err(|e| ServerFnError::Request(e.to_string()))?,
             abort_ctrl,
+            upload: None,
         })))
     }
 
@@ -155,6 +280,7 @@ impl<CustErr> ClientReq<CustErr> for BrowserRequest {
                 .body(body)
                 .map_err(|e| ServerFnError::Request(e.to_string()))?,
             abort_ctrl,
+            upload: None,
         })))
     }
 
@@ -168,13 +294,23 @@ impl<CustErr> ClientReq<CustErr> for BrowserRequest {
         let mut url = String::with_capacity(server_url.len() + path.len());
         url.push_str(server_url);
         url.push_str(path);
+        let form = body.0.take();
+        let upload = body.1.map(|on_progress| XhrUpload {
+            url: url.clone(),
+            accepts: accepts.to_string(),
+            headers: Vec::new(),
+            with_credentials: false,
+            form: form.clone(),
+            on_progress: on_progress.take(),
+        });
         Ok(Self(SendWrapper::new(RequestInner {
             request: Request::post(&url)
                 .header("Accept", accepts)
                 .abort_signal(abort_signal.as_ref())
-                .body(body.0.take())
+                .body(form)
                 .map_err(|e| ServerFnError::Request(e.to_string()))?,
             abort_ctrl,
+            upload,
         })))
     }
 
@@ -204,6 +340,7 @@ impl<CustErr> ClientReq<CustErr> for BrowserRequest {
                 .body(url_params)
                 .map_err(|e| ServerFnError::Request(e.to_string()))?,
             abort_ctrl,
+            upload: None,
         })))
     }
 
@@ -220,8 +357,61 @@ impl<CustErr> ClientReq<CustErr> for BrowserRequest {
         Ok(Self(SendWrapper::new(RequestInner {
             request,
             abort_ctrl,
+            upload: None,
         })))
     }
+
+    fn try_add_header(
+        &mut self,
+        name: &str,
+        value: &str,
+    ) -> Result<(), ServerFnError<CustErr>> {
+        let inner = &mut *self.0;
+        inner
+            .request
+            .headers()
+            .into_raw()
+            .append(name, value)
+            .map_err(|e| ServerFnError::Request(format!("{e:?}")))?;
+        if let Some(upload) = inner.upload.as_mut() {
+            upload.headers.push((name.to_string(), value.to_string()));
+        }
+        Ok(())
+    }
+
+    fn try_set_credentials(
+        self,
+        credentials: Credentials,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        let RequestInner {
+            request,
+            abort_ctrl,
+            mut upload,
+        } = self.0.take();
+        let init = RequestInit::new();
+        init.set_credentials(match credentials {
+            Credentials::Omit => RequestCredentials::Omit,
+            Credentials::SameOrigin => RequestCredentials::SameOrigin,
+            Credentials::Include => RequestCredentials::Include,
+        });
+        // the credentials mode can't be changed, so this creates a copy of the request with the
+        // new mode instead
+        let request =
+            web_sys::Request::new_with_request_and_init(&request.into(), &init)
+                .map_err(|e| ServerFnError::Request(format!("{e:?}")))?;
+        if let Some(upload) = upload.as_mut() {
+            upload.with_credentials = credentials == Credentials::Include;
+        }
+        Ok(Self(SendWrapper::new(RequestInner {
+            request: request.into(),
+            abort_ctrl,
+            upload,
+        })))
+    }
+
+    fn url(&self) -> String {
+        self.0.request.url()
+    }
 }
 
 fn streaming_request(
@@ -1,4 +1,4 @@
-use crate::error::ServerFnError;
+use crate::{client::Credentials, error::ServerFnError};
 use bytes::Bytes;
 use futures::Stream;
 use std::{borrow::Cow, future::Future};
@@ -70,6 +70,22 @@ where
         content_type: &str,
         body: impl Stream<Item = Bytes> + Send + 'static,
     ) -> Result<Self, ServerFnError<CustErr>>;
+
+    /// Attempts to add a header to the request.
+    fn try_add_header(
+        &mut self,
+        name: &str,
+        value: &str,
+    ) -> Result<(), ServerFnError<CustErr>>;
+
+    /// Attempts to set the [`Credentials`] mode of the request.
+    fn try_set_credentials(
+        self,
+        credentials: Credentials,
+    ) -> Result<Self, ServerFnError<CustErr>>;
+
+    /// The URL the request will be sent to, including its query string.
+    fn url(&self) -> String;
 }
 
 /// Represents the request as received by the server.
@@ -1,9 +1,12 @@
 use super::ClientReq;
-use crate::{client::get_server_url, error::ServerFnError};
+use crate::{
+    client::{get_server_url, Credentials},
+    error::ServerFnError,
+};
 use bytes::Bytes;
 use futures::Stream;
 use once_cell::sync::Lazy;
-use reqwest::header::{ACCEPT, CONTENT_TYPE};
+use reqwest::header::{HeaderName, HeaderValue, ACCEPT, CONTENT_TYPE};
 pub use reqwest::{multipart::Form, Client, Method, Request, Url};
 
 pub(crate) static CLIENT: Lazy<Client> = Lazy::new(Client::new);
@@ -115,4 +118,29 @@ impl<CustErr> ClientReq<CustErr> for Request {
                 .map_err(|e| ServerFnError::Request(e.to_string()))
         }*/
     }
+
+    fn try_add_header(
+        &mut self,
+        name: &str,
+        value: &str,
+    ) -> Result<(), ServerFnError<CustErr>> {
+        let name = HeaderName::from_bytes(name.as_bytes())
+            .map_err(|e| ServerFnError::Request(e.to_string()))?;
+        let value = HeaderValue::from_str(value)
+            .map_err(|e| ServerFnError::Request(e.to_string()))?;
+        self.headers_mut().append(name, value);
+        Ok(())
+    }
+
+    fn try_set_credentials(
+        self,
+        _credentials: Credentials,
+    ) -> Result<Self, ServerFnError<CustErr>> {
+        // outside the browser, there are no credentials that are sent automatically
+        Ok(self)
+    }
+
+    fn url(&self) -> String {
+        Request::url(self).to_string()
+    }
 }
@@ -5,7 +5,7 @@ use crate::error::{
 use actix_web::{
     http::{
         header,
-        header::{HeaderValue, LOCATION},
+        header::{HeaderName, HeaderValue, LOCATION},
         StatusCode,
     },
     HttpResponse,
@@ -88,10 +88,34 @@ where
         ))
     }
 
+    fn error_response_with_status(
+        path: &str,
+        status: u16,
+        err: String,
+    ) -> Self {
+        ActixResponse(SendWrapper::new(
+            HttpResponse::build(
+                StatusCode::from_u16(status)
+                    .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+            )
+            .append_header((SERVER_FN_ERROR_HEADER, path))
+            .body(err),
+        ))
+    }
+
     fn redirect(&mut self, path: &str) {
         if let Ok(path) = HeaderValue::from_str(path) {
             *self.0.status_mut() = StatusCode::FOUND;
             self.0.headers_mut().insert(LOCATION, path);
         }
     }
+
+    fn set_header(&mut self, name: &str, value: &str) {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(name.as_bytes()),
+            HeaderValue::from_str(value),
+        ) {
+            self.0.headers_mut().insert(name, value);
+        }
+    }
 }
@@ -5,7 +5,7 @@ use crate::error::{
 use axum::body::Body;
 use bytes::Bytes;
 use futures::{Stream, StreamExt};
-use http::{header, HeaderValue, Response, StatusCode};
+use http::{header, HeaderName, HeaderValue, Response, StatusCode};
 use std::{
     fmt::{Debug, Display},
     str::FromStr,
@@ -63,10 +63,34 @@ where
             .unwrap()
     }
 
+    fn error_response_with_status(
+        path: &str,
+        status: u16,
+        err: String,
+    ) -> Self {
+        Response::builder()
+            .status(
+                StatusCode::from_u16(status)
+                    .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+            )
+            .header(SERVER_FN_ERROR_HEADER, path)
+            .body(err.into())
+            .unwrap()
+    }
+
     fn redirect(&mut self, path: &str) {
         if let Ok(path) = HeaderValue::from_str(path) {
             self.headers_mut().insert(header::LOCATION, path);
             *self.status_mut() = StatusCode::FOUND;
         }
     }
+
+    fn set_header(&mut self, name: &str, value: &str) {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(name.as_bytes()),
+            HeaderValue::from_str(value),
+        ) {
+            self.headers_mut().insert(name, value);
+        }
+    }
 }
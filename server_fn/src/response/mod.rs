@@ -44,8 +44,28 @@ where
     /// Converts an error into a response, with a `500` status code and the error text as its body.
     fn error_response(path: &str, err: &ServerFnError<CustErr>) -> Self;
 
+    /// Converts an error that has already been serialized into a response with the given status
+    /// code.
+    ///
+    /// This is used for errors sent with an [`ErrorEncoding`](crate::error::ErrorEncoding) other
+    /// than text. By default, it falls back to [`Res::error_response`], which sends the serialized
+    /// error as a [`ServerFnError::ServerError`] with a `500` status code.
+    fn error_response_with_status(
+        path: &str,
+        _status: u16,
+        err: String,
+    ) -> Self {
+        Self::error_response(path, &ServerFnError::ServerError(err))
+    }
+
     /// Redirect the response by setting a 302 code and Location header.
     fn redirect(&mut self, path: &str);
+
+    /// Sets a header on the response, replacing any existing value.
+    ///
+    /// This is used to send the `Cache-Control` header of GET server functions. By default, it
+    /// does nothing, so response types that do not override it are sent without the header.
+    fn set_header(&mut self, _name: &str, _value: &str) {}
 }
 
 /// Represents the response as received by the client.
@@ -118,4 +138,8 @@ impl<CustErr> Res<CustErr> for BrowserMockRes {
     fn redirect(&mut self, _path: &str) {
         unreachable!()
     }
+
+    fn set_header(&mut self, _name: &str, _value: &str) {
+        unreachable!()
+    }
 }
@@ -0,0 +1,94 @@
+use super::{
+    serve_websocket, BoxedStream, Message, UpgradedWebsocket, WebsocketUpgrade,
+};
+use crate::error::ServerFnError;
+use axum::{
+    body::Body,
+    extract::{
+        ws::{self, CloseFrame, WebSocketUpgrade},
+        FromRequestParts,
+    },
+    response::IntoResponse,
+};
+use futures::{
+    channel::{mpsc, oneshot},
+    future, SinkExt, StreamExt,
+};
+use http::{Request, Response};
+use std::fmt::Display;
+
+impl WebsocketUpgrade<Response<Body>> for Request<Body> {
+    async fn try_into_websocket(
+        self,
+    ) -> Result<UpgradedWebsocket<Response<Body>>, ServerFnError> {
+        let (mut parts, _body) = self.into_parts();
+        let upgrade = WebSocketUpgrade::from_request_parts(&mut parts, &())
+            .await
+            .map_err(|e| request_err(e.body_text()))?;
+
+        let (incoming_tx, incoming_rx) = mpsc::unbounded();
+        let (outgoing_tx, outgoing_rx) = oneshot::channel();
+        let res = upgrade
+            .on_upgrade(|socket| async move {
+                let (socket_tx, socket_rx) = socket.split();
+                let socket_tx = socket_tx
+                    .sink_map_err(response_err)
+                    .with(|msg| future::ready(Ok(into_axum_message(msg))));
+                let socket_rx = socket_rx.filter_map(|msg| {
+                    future::ready(match msg {
+                        Ok(msg) => from_axum_message(msg).map(Ok),
+                        Err(e) => Some(Err(request_err(e))),
+                    })
+                });
+                serve_websocket(
+                    Box::pin(socket_rx),
+                    Box::pin(socket_tx),
+                    incoming_tx,
+                    outgoing_rx,
+                )
+                .await
+            })
+            .into_response();
+        Ok((BoxedStream::new(incoming_rx), outgoing_tx, res))
+    }
+}
+
+fn into_axum_message(msg: Message) -> ws::Message {
+    match msg {
+        Message::Text(text) => ws::Message::Text(text),
+        Message::Binary(bytes) => ws::Message::Binary(bytes.into()),
+        Message::Close { code, reason } => {
+            ws::Message::Close(Some(CloseFrame {
+                code,
+                reason: reason.into(),
+            }))
+        }
+    }
+}
+
+fn from_axum_message(msg: ws::Message) -> Option<Message> {
+    match msg {
+        ws::Message::Text(text) => Some(Message::Text(text)),
+        ws::Message::Binary(bytes) => Some(Message::Binary(bytes.into())),
+        ws::Message::Close(frame) => Some(match frame {
+            Some(frame) => Message::Close {
+                code: frame.code,
+                reason: frame.reason.into_owned(),
+            },
+            None => Message::Close {
+                code: super::NO_STATUS,
+                reason: String::new(),
+            },
+        }),
+        // pings are answered automatically
+        ws::Message::Ping(_) | ws::Message::Pong(_) => None,
+    }
+}
+
+fn request_err(e: impl Display) -> ServerFnError {
+    ServerFnError::Request(e.to_string())
+}
+
+fn response_err(e: impl Display) -> ServerFnError {
+    ServerFnError::Response(e.to_string())
+}
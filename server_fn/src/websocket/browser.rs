@@ -0,0 +1,123 @@
+use super::{BoxedStream, Message, MessageSink, WebsocketClient};
+use crate::error::ServerFnError;
+use futures::{stream::SplitSink, Sink, StreamExt};
+use gloo_net::websocket::{self, futures::WebSocket, WebSocketError};
+use send_wrapper::SendWrapper;
+use std::{
+    fmt::Display,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_futures::JsFuture;
+
+/// Implements [`WebsocketClient`] with a `WebSocket` in the browser.
+pub struct BrowserClient;
+
+impl WebsocketClient for BrowserClient {
+    fn connect(
+        url: &str,
+    ) -> impl Future<
+        Output = Result<(BoxedStream<Message>, MessageSink), ServerFnError>,
+    > + Send {
+        let url = absolute_url(url);
+        SendWrapper::new(async move {
+            let socket = WebSocket::open(&url?).map_err(request_err)?;
+            let (socket_tx, socket_rx) = socket.split();
+            let socket_rx = socket_rx.map(|msg| match msg {
+                Ok(websocket::Message::Text(text)) => Ok(Message::Text(text)),
+                Ok(websocket::Message::Bytes(bytes)) => {
+                    Ok(Message::Binary(bytes.into()))
+                }
+                Err(WebSocketError::ConnectionClose(event)) => {
+                    Ok(Message::Close {
+                        code: event.code,
+                        reason: event.reason,
+                    })
+                }
+                Err(e) => Err(request_err(e)),
+            });
+            let socket_tx: MessageSink =
+                Box::pin(BrowserSink(SendWrapper::new(socket_tx)));
+            Ok((BoxedStream::new(SendWrapper::new(socket_rx)), socket_tx))
+        })
+    }
+
+    fn sleep(duration: Duration) -> impl Future<Output = ()> + Send {
+        let promise = js_sys::Promise::new(&mut |resolve, _| {
+            if let Some(window) = web_sys::window() {
+                let _ = window
+                    .set_timeout_with_callback_and_timeout_and_arguments_0(
+                        &resolve,
+                        duration.as_millis().try_into().unwrap_or(i32::MAX),
+                    );
+            }
+        });
+        SendWrapper::new(async move {
+            let _ = JsFuture::from(promise).await;
+        })
+    }
+}
+
+/// Resolves a URL relative to the current page into a websocket URL.
+fn absolute_url(url: &str) -> Result<String, ServerFnError> {
+    if !url.starts_with('/') {
+        return Ok(url.to_string());
+    }
+    let location = web_sys::window()
+        .ok_or_else(|| request_err("could not find the window"))?
+        .location();
+    let js_err = |e: JsValue| request_err(format!("{e:?}"));
+    let scheme = match location.protocol().map_err(js_err)?.as_str() {
+        "https:" => "wss",
+        _ => "ws",
+    };
+    let host = location.host().map_err(js_err)?;
+    Ok(format!("{scheme}://{host}{url}"))
+}
+
+struct BrowserSink(SendWrapper<SplitSink<WebSocket, websocket::Message>>);
+
+impl Sink<Message> for BrowserSink {
+    type Error = ServerFnError;
+
+    fn poll_ready(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut *self.0).poll_ready(cx).map_err(request_err)
+    }
+
+    fn start_send(
+        mut self: Pin<&mut Self>,
+        item: Message,
+    ) -> Result<(), Self::Error> {
+        let item = match item {
+            Message::Text(text) => websocket::Message::Text(text),
+            Message::Binary(bytes) => websocket::Message::Bytes(bytes.into()),
+            // the connection is closed when the socket is dropped
+            Message::Close { .. } => return Ok(()),
+        };
+        Pin::new(&mut *self.0).start_send(item).map_err(request_err)
+    }
+
+    fn poll_flush(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut *self.0).poll_flush(cx).map_err(request_err)
+    }
+
+    fn poll_close(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut *self.0).poll_close(cx).map_err(request_err)
+    }
+}
+
+fn request_err(e: impl Display) -> ServerFnError {
+    ServerFnError::Request(e.to_string())
+}
@@ -0,0 +1,692 @@
+//! Server functions that communicate over a websocket.
+//!
+//! A server function declared with `#[server(protocol = Websocket)]` takes a single stream of
+//! messages from the client, and returns a stream of messages for the client. Each message is
+//! serialized with the protocol’s encoding ([`Json`] by default).
+//!
+//! ```rust,ignore
+//! use server_fn::websocket::{BoxedStream, Websocket};
+//!
+//! #[server(protocol = Websocket)]
+//! pub async fn echo(
+//!     input: BoxedStream<String>,
+//! ) -> Result<BoxedStream<String>, ServerFnError> {
+//!     Ok(BoxedStream::new(input.map(|msg| msg.map(|msg| msg.to_uppercase()))))
+//! }
+//! ```
+//!
+//! On the client, calling the function opens a websocket to the function’s URL, sends each
+//! message from the input stream, and returns the stream of messages from the server.
+//! [`WebsocketServerFn::connect`] returns a [`WebsocketSender`] to send messages with instead.
+//!
+//! If the connection is dropped, the client reconnects with exponential [`Backoff`], which starts
+//! the server function again. A connection that the server closes, either because its output
+//! stream ended or because it returned an error, is not reopened.
+//!
+//! Once the client’s input stream ends, it sends an empty message, which ends the input stream
+//! on the server. The server can keep sending messages until its own output stream ends.
+
+use crate::{
+    client::get_server_url,
+    codec::{Encoding, Json},
+    error::{NoCustomError, ServerFnError, ServerFnErrorSerde},
+    middleware::Layer,
+    response::Res,
+};
+use bytes::Bytes;
+use futures::{
+    channel::{mpsc, oneshot},
+    future, select, select_biased, stream, FutureExt, Sink, SinkExt, Stream,
+    StreamExt,
+};
+use http::Method;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    fmt::{self, Debug},
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+#[cfg(feature = "axum-no-default")]
+mod axum;
+/// A websocket client for the browser.
+#[cfg(feature = "browser")]
+pub mod browser;
+/// A websocket client for native targets, using `tokio-tungstenite`.
+#[cfg(feature = "tungstenite")]
+pub mod tungstenite;
+
+/// The close code for a connection that ended normally.
+const NORMAL_CLOSURE: u16 = 1000;
+/// The close code for a connection that was closed without a status code.
+const NO_STATUS: u16 = 1005;
+/// The close code for a connection that was closed because of a server error.
+const INTERNAL_ERROR: u16 = 1011;
+/// The maximum length of the reason for closing a connection, in bytes.
+const MAX_CLOSE_REASON: usize = 123;
+
+/// The protocol for a server function that communicates over a websocket, sending each message
+/// with the encoding `Enc`.
+pub struct Websocket<Enc = Json>(PhantomData<Enc>);
+
+impl<Enc: Encoding> Encoding for Websocket<Enc> {
+    const CONTENT_TYPE: &'static str = Enc::CONTENT_TYPE;
+    const METHOD: Method = Method::GET;
+}
+
+/// A single message sent over a websocket.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    /// A text message.
+    Text(String),
+    /// A binary message.
+    Binary(Bytes),
+    /// Closes the connection.
+    Close {
+        /// The [close code](https://developer.mozilla.org/en-US/docs/Web/API/CloseEvent/code).
+        code: u16,
+        /// The reason the connection was closed.
+        reason: String,
+    },
+}
+
+impl Message {
+    /// The empty message that the client sends once its input has ended. The server can still
+    /// send messages after this, unlike after a close frame.
+    fn end_of_input() -> Self {
+        Message::Text(String::new())
+    }
+
+    fn is_end_of_input(&self) -> bool {
+        match self {
+            Message::Text(text) => text.is_empty(),
+            Message::Binary(bytes) => bytes.is_empty(),
+            Message::Close { .. } => false,
+        }
+    }
+
+    fn close_with_error(err: &ServerFnError) -> Self {
+        let mut reason = err.ser().unwrap_or_else(|_| err.to_string());
+        if reason.len() > MAX_CLOSE_REASON {
+            let mut len = MAX_CLOSE_REASON;
+            while !reason.is_char_boundary(len) {
+                len -= 1;
+            }
+            reason.truncate(len);
+        }
+        Message::Close {
+            code: INTERNAL_ERROR,
+            reason,
+        }
+    }
+}
+
+/// Serializes values into websocket messages, and deserializes them again.
+pub trait MessageEncoding {
+    /// Serializes a value into a message.
+    ///
+    /// This should never return an empty message, which marks the end of the client’s input.
+    fn encode<T: Serialize>(value: &T) -> Result<Message, ServerFnError>;
+
+    /// Deserializes a value from a message.
+    fn decode<T: DeserializeOwned>(
+        message: Message,
+    ) -> Result<T, ServerFnError>;
+}
+
+impl MessageEncoding for Json {
+    fn encode<T: Serialize>(value: &T) -> Result<Message, ServerFnError> {
+        serde_json::to_string(value)
+            .map(Message::Text)
+            .map_err(|e| ServerFnError::Serialization(e.to_string()))
+    }
+
+    fn decode<T: DeserializeOwned>(
+        message: Message,
+    ) -> Result<T, ServerFnError> {
+        match message {
+            Message::Text(text) => serde_json::from_str(&text),
+            Message::Binary(bytes) => serde_json::from_slice(&bytes),
+            Message::Close { .. } => {
+                return Err(ServerFnError::Deserialization(
+                    "expected a message, found a close frame".into(),
+                ))
+            }
+        }
+        .map_err(|e| ServerFnError::Deserialization(e.to_string()))
+    }
+}
+
+#[cfg(feature = "cbor")]
+impl MessageEncoding for crate::codec::Cbor {
+    fn encode<T: Serialize>(value: &T) -> Result<Message, ServerFnError> {
+        let mut buffer: Vec<u8> = Vec::new();
+        match ciborium::ser::into_writer(value, &mut buffer) {
+            Ok(_) => Ok(Message::Binary(buffer.into())),
+            Err(e) => Err(ServerFnError::Serialization(e.to_string())),
+        }
+    }
+
+    fn decode<T: DeserializeOwned>(
+        message: Message,
+    ) -> Result<T, ServerFnError> {
+        match message {
+            Message::Binary(bytes) => ciborium::de::from_reader(bytes.as_ref())
+                .map_err(|e| ServerFnError::Deserialization(e.to_string())),
+            _ => Err(ServerFnError::Deserialization(
+                "expected a binary message".into(),
+            )),
+        }
+    }
+}
+
+impl<Enc: MessageEncoding> MessageEncoding for Websocket<Enc> {
+    fn encode<T: Serialize>(value: &T) -> Result<Message, ServerFnError> {
+        Enc::encode(value)
+    }
+
+    fn decode<T: DeserializeOwned>(
+        message: Message,
+    ) -> Result<T, ServerFnError> {
+        Enc::decode(message)
+    }
+}
+
+/// A stream of messages sent over a websocket.
+///
+/// This is both the argument and the return type of a websocket server function.
+pub struct BoxedStream<T>(
+    Pin<Box<dyn Stream<Item = Result<T, ServerFnError>> + Send>>,
+);
+
+impl<T> BoxedStream<T> {
+    /// Wraps a stream of messages.
+    pub fn new(
+        stream: impl Stream<Item = Result<T, ServerFnError>> + Send + 'static,
+    ) -> Self {
+        Self(Box::pin(stream))
+    }
+
+    /// Consumes the wrapper, returning the stream of messages.
+    pub fn into_inner(
+        self,
+    ) -> impl Stream<Item = Result<T, ServerFnError>> + Send {
+        self.0
+    }
+}
+
+impl<T> Debug for BoxedStream<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("BoxedStream").finish()
+    }
+}
+
+impl<T> Stream for BoxedStream<T> {
+    type Item = Result<T, ServerFnError>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        self.0.as_mut().poll_next(cx)
+    }
+}
+
+/// The sending half of a websocket connection, as returned by a [`WebsocketClient`].
+pub type MessageSink =
+    Pin<Box<dyn Sink<Message, Error = ServerFnError> + Send>>;
+
+/// An upgraded websocket connection, as returned by [`WebsocketUpgrade::try_into_websocket`]:
+/// 1. the stream of messages received from the client,
+/// 2. a channel to send the stream of messages for the client, and
+/// 3. the response that completes the upgrade.
+pub type UpgradedWebsocket<Res> = (
+    BoxedStream<Message>,
+    oneshot::Sender<BoxedStream<Message>>,
+    Res,
+);
+
+/// A server request that can be upgraded to a websocket connection.
+pub trait WebsocketUpgrade<Res>: Sized {
+    /// Upgrades the request to a websocket connection.
+    fn try_into_websocket(
+        self,
+    ) -> impl Future<Output = Result<UpgradedWebsocket<Res>, ServerFnError>> + Send;
+}
+
+impl WebsocketUpgrade<crate::response::BrowserMockRes>
+    for crate::request::BrowserMockReq
+{
+    async fn try_into_websocket(
+        self,
+    ) -> Result<UpgradedWebsocket<crate::response::BrowserMockRes>, ServerFnError>
+    {
+        unreachable!()
+    }
+}
+
+/// Forwards messages between an upgraded websocket and a server function, until either side
+/// closes the connection.
+#[allow(dead_code)] // used by server integrations
+async fn serve_websocket(
+    socket_rx: impl Stream<Item = Result<Message, ServerFnError>> + Unpin,
+    mut socket_tx: impl Sink<Message, Error = ServerFnError> + Unpin,
+    incoming: mpsc::UnboundedSender<Result<Message, ServerFnError>>,
+    outgoing: oneshot::Receiver<BoxedStream<Message>>,
+) {
+    // the server function has already returned its output before the upgrade completes
+    let Ok(outgoing) = outgoing.await else {
+        return;
+    };
+    let mut socket_rx = socket_rx.fuse();
+    let mut outgoing = outgoing.fuse();
+    loop {
+        select! {
+            msg = socket_rx.next() => match msg {
+                Some(Ok(Message::Close { .. })) | None => break,
+                Some(Ok(msg)) if msg.is_end_of_input() => incoming.close_channel(),
+                Some(msg) => {
+                    let _ = incoming.unbounded_send(msg);
+                }
+            },
+            msg = outgoing.next() => {
+                let msg = match msg {
+                    Some(Ok(msg)) => msg,
+                    Some(Err(e)) => Message::close_with_error(&e),
+                    None => Message::Close {
+                        code: NORMAL_CLOSURE,
+                        reason: String::new(),
+                    },
+                };
+                let is_close = matches!(msg, Message::Close { .. });
+                if socket_tx.send(msg).await.is_err() || is_close {
+                    break;
+                }
+            },
+        }
+    }
+}
+
+/// Opens and reopens a websocket connection for a [`WebsocketServerFn`].
+pub trait WebsocketClient {
+    /// Opens a websocket connection to the given URL, returning the stream of messages received
+    /// from the server and a sink to send messages to it.
+    ///
+    /// The stream should yield a [`Message::Close`] if the server closes the connection, and end
+    /// or return an error if it is dropped.
+    fn connect(
+        url: &str,
+    ) -> impl Future<
+        Output = Result<(BoxedStream<Message>, MessageSink), ServerFnError>,
+    > + Send;
+
+    /// Waits for the given duration, before reconnecting.
+    fn sleep(duration: Duration) -> impl Future<Output = ()> + Send;
+}
+
+/// How a websocket client reconnects after its connection is dropped.
+///
+/// The first attempt to reconnect waits for the initial delay, and each failed attempt doubles
+/// the delay, up to the maximum. Defaults to an initial delay of 100ms, a maximum of 10s, and 10
+/// attempts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Backoff {
+    initial: Duration,
+    max: Duration,
+    attempts: u32,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            initial: Duration::from_millis(100),
+            max: Duration::from_secs(10),
+            attempts: 10,
+        }
+    }
+}
+
+impl Backoff {
+    /// Creates the default backoff.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the delay before the first attempt to reconnect.
+    pub fn initial(mut self, delay: Duration) -> Self {
+        self.initial = delay;
+        self
+    }
+
+    /// Sets the maximum delay between attempts to reconnect.
+    pub fn max(mut self, delay: Duration) -> Self {
+        self.max = delay;
+        self
+    }
+
+    /// Sets the number of failed attempts in a row after which the client stops reconnecting.
+    ///
+    /// `0` never reconnects.
+    pub fn attempts(mut self, attempts: u32) -> Self {
+        self.attempts = attempts;
+        self
+    }
+
+    fn delay(&self, attempt: u32) -> Duration {
+        self.initial
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.max)
+    }
+}
+
+/// Sends messages to a websocket server function.
+///
+/// This is returned by [`WebsocketServerFn::connect`]. Messages that are sent while the client is
+/// reconnecting are sent once the connection is reopened. Dropping every sender ends the input
+/// stream of the server function.
+pub struct WebsocketSender<T>(mpsc::UnboundedSender<T>);
+
+impl<T> Clone for WebsocketSender<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> Debug for WebsocketSender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("WebsocketSender").finish()
+    }
+}
+
+impl<T> Sink<T> for WebsocketSender<T> {
+    type Error = ServerFnError;
+
+    fn poll_ready(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.0).poll_ready(cx).map_err(closed)
+    }
+
+    fn start_send(
+        mut self: Pin<&mut Self>,
+        item: T,
+    ) -> Result<(), Self::Error> {
+        Pin::new(&mut self.0).start_send(item).map_err(closed)
+    }
+
+    fn poll_flush(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.0).poll_flush(cx).map_err(closed)
+    }
+
+    fn poll_close(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), Self::Error>> {
+        Pin::new(&mut self.0).poll_close(cx).map_err(closed)
+    }
+}
+
+fn closed(_: mpsc::SendError) -> ServerFnError {
+    ServerFnError::Request("the websocket connection was closed".into())
+}
+
+/// A server function that communicates with the client over a websocket.
+///
+/// This is implemented by the `#[server]` macro for server functions declared with
+/// `protocol = Websocket`. The type is the argument to the function, which wraps the stream of
+/// messages from the client.
+pub trait WebsocketServerFn: Send + Sized + 'static {
+    /// A unique path for the server function’s API endpoint, relative to the host, including its prefix.
+    const PATH: &'static str;
+
+    /// The type of the messages sent by the client.
+    type ClientMsg: Serialize + DeserializeOwned + Send + 'static;
+
+    /// The type of the messages sent by the server.
+    type ServerMsg: Serialize + DeserializeOwned + Send + 'static;
+
+    /// The [`MessageEncoding`] used for messages in both directions.
+    type Encoding: MessageEncoding;
+
+    /// The websocket client that connects to the server function.
+    type Client: WebsocketClient;
+
+    /// The type of the HTTP request when received by the server function on the server side.
+    type ServerRequest: WebsocketUpgrade<Self::ServerResponse> + Send;
+
+    /// The type of the HTTP response returned by the server function on the server side.
+    type ServerResponse: Res<NoCustomError> + Send;
+
+    /// Returns [`Self::PATH`].
+    fn url() -> &'static str {
+        Self::PATH
+    }
+
+    /// Middleware that should be applied to this server function.
+    fn middlewares(
+    ) -> Vec<Arc<dyn Layer<Self::ServerRequest, Self::ServerResponse>>> {
+        Vec::new()
+    }
+
+    /// The body of the server function. This will only run on the server.
+    ///
+    /// This runs before the connection is upgraded, so it should return its output stream
+    /// without waiting for messages from the client.
+    fn run_body(
+        self,
+    ) -> impl Future<Output = Result<BoxedStream<Self::ServerMsg>, ServerFnError>>
+           + Send;
+
+    #[doc(hidden)]
+    fn run_on_server(
+        req: Self::ServerRequest,
+    ) -> impl Future<Output = Self::ServerResponse> + Send
+    where
+        Self: From<BoxedStream<Self::ClientMsg>>,
+    {
+        async move {
+            Self::execute_on_server(req).await.unwrap_or_else(|e| {
+                Self::ServerResponse::error_response(Self::PATH, &e)
+            })
+        }
+    }
+
+    /// Upgrades the request and runs the server function, bubbling up an `Err(_)` after any
+    /// stage.
+    #[doc(hidden)]
+    fn execute_on_server(
+        req: Self::ServerRequest,
+    ) -> impl Future<Output = Result<Self::ServerResponse, ServerFnError>> + Send
+    where
+        Self: From<BoxedStream<Self::ClientMsg>>,
+    {
+        async move {
+            let (incoming, outgoing, res) = req.try_into_websocket().await?;
+            let input = BoxedStream::new(incoming.map(|msg| {
+                msg.and_then(<Self::Encoding as MessageEncoding>::decode)
+            }));
+            let output = Self::from(input).run_body().await?;
+            let output = BoxedStream::new(output.map(|msg| {
+                msg.and_then(|msg| {
+                    <Self::Encoding as MessageEncoding>::encode(&msg)
+                })
+            }));
+            // the receiver is only dropped if the upgrade failed
+            let _ = outgoing.send(output);
+            Ok(res)
+        }
+    }
+
+    /// Connects to the server function, sending each message from the input stream, and
+    /// returns the stream of messages from the server.
+    #[doc(hidden)]
+    fn run_on_client(
+        self,
+    ) -> impl Future<Output = Result<BoxedStream<Self::ServerMsg>, ServerFnError>>
+           + Send
+    where
+        Self: Into<BoxedStream<Self::ClientMsg>>,
+    {
+        let input = self
+            .into()
+            .take_while(|msg| future::ready(msg.is_ok()))
+            .filter_map(|msg| future::ready(msg.ok()));
+        future::ready(Ok(connect_with_input::<Self>(input, Backoff::default())))
+    }
+
+    /// Connects to the server function, returning a sender for messages to the server and the
+    /// stream of messages from the server.
+    ///
+    /// If the connection is dropped, the client reconnects with the default [`Backoff`].
+    fn connect() -> (
+        WebsocketSender<Self::ClientMsg>,
+        BoxedStream<Self::ServerMsg>,
+    ) {
+        Self::connect_with(Backoff::default())
+    }
+
+    /// Connects to the server function like [`connect`](WebsocketServerFn::connect), reconnecting
+    /// with the given [`Backoff`] if the connection is dropped.
+    fn connect_with(
+        backoff: Backoff,
+    ) -> (
+        WebsocketSender<Self::ClientMsg>,
+        BoxedStream<Self::ServerMsg>,
+    ) {
+        let (tx, rx) = mpsc::unbounded();
+        (WebsocketSender(tx), connect_with_input::<Self>(rx, backoff))
+    }
+}
+
+/// Returns the websocket URL for the given server function path.
+fn websocket_url(path: &str) -> String {
+    let server_url = get_server_url();
+    let server_url = if let Some(rest) = server_url.strip_prefix("http") {
+        format!("ws{rest}")
+    } else {
+        server_url.to_string()
+    };
+    format!("{server_url}{path}")
+}
+
+/// Connects to a websocket server function, returning its output stream, which also drives the
+/// connection.
+fn connect_with_input<F: WebsocketServerFn>(
+    input: impl Stream<Item = F::ClientMsg> + Send + 'static,
+    backoff: Backoff,
+) -> BoxedStream<F::ServerMsg> {
+    let (tx, rx) = mpsc::unbounded();
+    let driver =
+        run_connection::<F>(websocket_url(F::PATH), input, backoff, tx)
+            .into_stream()
+            .filter_map(|_| future::ready(None));
+    BoxedStream::new(stream::select(driver, rx))
+}
+
+/// Sends messages from the input stream and forwards messages from the server to the output,
+/// reconnecting whenever the connection is dropped.
+async fn run_connection<F: WebsocketServerFn>(
+    url: String,
+    input: impl Stream<Item = F::ClientMsg> + Send + 'static,
+    backoff: Backoff,
+    output: mpsc::UnboundedSender<Result<F::ServerMsg, ServerFnError>>,
+) {
+    let mut input = Box::pin(input.fuse());
+    // a message that could not be sent before the connection was dropped
+    let mut pending: Option<Message> = None;
+    // once the input has ended, each new connection is told so right away
+    let mut input_ended = false;
+    let mut failures = 0;
+    loop {
+        let err = match F::Client::connect(&url).await {
+            Err(e) => e,
+            Ok((socket_rx, mut socket_tx)) => {
+                let mut socket_rx = socket_rx.fuse();
+                let resent = match pending.take() {
+                    Some(msg) => match socket_tx.send(msg.clone()).await {
+                        Ok(()) => Ok(()),
+                        Err(e) => {
+                            pending = Some(msg);
+                            Err(e)
+                        }
+                    },
+                    None => Ok(()),
+                };
+                let resent = match resent {
+                    Ok(()) if input_ended => {
+                        socket_tx.send(Message::end_of_input()).await
+                    }
+                    resent => resent,
+                };
+                match resent {
+                    Err(e) => e,
+                    Ok(()) => loop {
+                        // a dropped connection is noticed before sending more messages on it
+                        select_biased! {
+                            msg = socket_rx.next() => match msg {
+                                Some(Ok(Message::Close { code, reason })) => match code {
+                                    NORMAL_CLOSURE | NO_STATUS => return,
+                                    INTERNAL_ERROR => {
+                                        let _ = output.unbounded_send(Err(
+                                            ServerFnError::de(&reason),
+                                        ));
+                                        return;
+                                    }
+                                    _ => break ServerFnError::Request(format!(
+                                        "the websocket connection was closed with code {code}: {reason}"
+                                    )),
+                                },
+                                Some(Ok(msg)) => {
+                                    // once we've received a message, the connection is working
+                                    failures = 0;
+                                    let _ = output.unbounded_send(
+                                        <F::Encoding as MessageEncoding>::decode(msg),
+                                    );
+                                }
+                                Some(Err(e)) => break e,
+                                None => break ServerFnError::Request(
+                                    "the websocket connection was dropped".into(),
+                                ),
+                            },
+                            value = input.next() => {
+                                let Some(value) = value else {
+                                    input_ended = true;
+                                    match socket_tx.send(Message::end_of_input()).await {
+                                        Ok(()) => continue,
+                                        Err(e) => break e,
+                                    }
+                                };
+                                match <F::Encoding as MessageEncoding>::encode(&value) {
+                                    Ok(msg) => {
+                                        if let Err(e) = socket_tx.send(msg.clone()).await {
+                                            pending = Some(msg);
+                                            break e;
+                                        }
+                                    }
+                                    Err(e) => {
+                                        let _ = output.unbounded_send(Err(e));
+                                    }
+                                }
+                            },
+                        }
+                    },
+                }
+            }
+        };
+
+        failures += 1;
+        if failures > backoff.attempts {
+            let _ = output.unbounded_send(Err(err));
+            return;
+        }
+        F::Client::sleep(backoff.delay(failures)).await;
+    }
+}
@@ -0,0 +1,78 @@
+use super::{BoxedStream, Message, MessageSink, WebsocketClient};
+use crate::error::ServerFnError;
+use futures::{future, SinkExt, StreamExt};
+use std::{future::Future, time::Duration};
+use tokio_tungstenite::tungstenite::{
+    self,
+    protocol::{frame::coding::CloseCode, CloseFrame},
+};
+
+/// Implements [`WebsocketClient`] with `tokio-tungstenite`, for use outside the browser.
+///
+/// This requires a Tokio runtime.
+pub struct TungsteniteClient;
+
+impl WebsocketClient for TungsteniteClient {
+    async fn connect(
+        url: &str,
+    ) -> Result<(BoxedStream<Message>, MessageSink), ServerFnError> {
+        let (socket, _) = tokio_tungstenite::connect_async(url)
+            .await
+            .map_err(request_err)?;
+        let (socket_tx, socket_rx) = socket.split();
+        let socket_tx = socket_tx
+            .sink_map_err(request_err)
+            .with(|msg| future::ready(Ok(into_tungstenite_message(msg))));
+        let socket_rx = socket_rx.filter_map(|msg| {
+            future::ready(match msg {
+                Ok(msg) => from_tungstenite_message(msg).map(Ok),
+                Err(e) => Some(Err(request_err(e))),
+            })
+        });
+        Ok((BoxedStream::new(socket_rx), Box::pin(socket_tx)))
+    }
+
+    fn sleep(duration: Duration) -> impl Future<Output = ()> + Send {
+        tokio::time::sleep(duration)
+    }
+}
+
+fn into_tungstenite_message(msg: Message) -> tungstenite::Message {
+    match msg {
+        Message::Text(text) => tungstenite::Message::Text(text),
+        Message::Binary(bytes) => tungstenite::Message::Binary(bytes.into()),
+        Message::Close { code, reason } => {
+            tungstenite::Message::Close(Some(CloseFrame {
+                code: CloseCode::from(code),
+                reason: reason.into(),
+            }))
+        }
+    }
+}
+
+fn from_tungstenite_message(msg: tungstenite::Message) -> Option<Message> {
+    match msg {
+        tungstenite::Message::Text(text) => Some(Message::Text(text)),
+        tungstenite::Message::Binary(bytes) => {
+            Some(Message::Binary(bytes.into()))
+        }
+        tungstenite::Message::Close(frame) => Some(match frame {
+            Some(frame) => Message::Close {
+                code: frame.code.into(),
+                reason: frame.reason.into_owned(),
+            },
+            None => Message::Close {
+                code: super::NO_STATUS,
+                reason: String::new(),
+            },
+        }),
+        // pings are answered automatically
+        tungstenite::Message::Ping(_)
+        | tungstenite::Message::Pong(_)
+        | tungstenite::Message::Frame(_) => None,
+    }
+}
+
+fn request_err(e: tungstenite::Error) -> ServerFnError {
+    ServerFnError::Request(e.to_string())
+}
@@ -59,15 +59,14 @@ pub fn server_macro_impl(
         .inputs
         .iter_mut()
         .map(|f| {
-            let typed_arg = match f {
-                FnArg::Receiver(_) => {
-                    return Err(syn::Error::new(
+            let typed_arg =
+                match f {
+                    FnArg::Receiver(_) => return Err(syn::Error::new(
                         f.span(),
                         "cannot use receiver types in server function macro",
-                    ))
-                }
-                FnArg::Typed(t) => t,
-            };
+                    )),
+                    FnArg::Typed(t) => t,
+                };
 
             // strip `mut`, which is allowed in fn args but not in struct fields
             if let Pat::Ident(ident) = &mut *typed_arg.pat {
@@ -206,6 +205,9 @@ pub fn server_macro_impl(
         client,
         custom_wrapper,
         impl_from,
+        error,
+        cache,
+        protocol,
     } = args;
     let prefix = prefix.unwrap_or_else(|| Literal::string(default_path));
     let fn_path = fn_path.unwrap_or_else(|| Literal::string(""));
@@ -213,6 +215,7 @@ pub fn server_macro_impl(
         Some(Type::Path(path)) => {
             path.path.segments.last().map(|seg| seg.ident.to_string())
         }
+        None if protocol.is_some() => Some("Websocket".to_string()),
         None => Some("PostUrl".to_string()),
         _ => None,
     };
@@ -229,6 +232,13 @@ pub fn server_macro_impl(
                 #server_fn_path::codec::PostUrl
             }
         });
+    let output_ident = match &output {
+        Some(Type::Path(path)) => {
+            path.path.segments.last().map(|seg| seg.ident.to_string())
+        }
+        None => Some("Json".to_string()),
+        _ => None,
+    };
     let output = output
         .map(|n| {
             if builtin_encoding {
@@ -293,7 +303,9 @@ pub fn server_macro_impl(
         FnArg::Receiver(_) => None,
         FnArg::Typed(t) => Some((&t.pat, &t.ty)),
     });
-    let impl_from = impl_from.map(|v| v.value).unwrap_or(true);
+    // websocket server functions convert their input stream to and from the struct
+    let impl_from =
+        impl_from.map(|v| v.value).unwrap_or(true) || protocol.is_some();
     let from_impl = (body.inputs.len() == 1
         && first_field.is_some()
         && impl_from)
@@ -322,12 +334,99 @@ pub fn server_macro_impl(
 
     let output_ty = output_type(&return_ty)?;
     let error_ty = err_type(&return_ty)?;
-    let error_ty =
-        error_ty.map(ToTokens::to_token_stream).unwrap_or_else(|| {
+    // errors of a custom error type given with `error = MyError` are sent with the output
+    // encoding, rather than as text
+    let error_encoding = match &error {
+        Some(error) => {
+            // the built-in encodings other than JSON can't encode errors
+            if let Some(output_ident) =
+                output_ident.as_deref().filter(|ident| {
+                    matches!(
+                        *ident,
+                        "Cbor"
+                            | "MsgPack"
+                            | "Postcard"
+                            | "Rkyv"
+                            | "SerdeLite"
+                            | "Streaming"
+                            | "StreamingText"
+                            | "StreamingEvents"
+                    )
+                })
+            {
+                return Err(syn::Error::new(
+                    error.span(),
+                    format!(
+                        "`error = ...` sends errors with the output encoding, \
+                         but `{output_ident}` cannot encode errors; use the \
+                         `Json` or `StreamingJson` output encoding, or remove \
+                         `error` to send errors as text"
+                    ),
+                ));
+            }
+            Some(quote! {
+                fn error_response(
+                    err: &#server_fn_path::ServerFnError<Self::Error>,
+                ) -> Self::ServerResponse {
+                    <Self::ServerResponse as #server_fn_path::response::Res<Self::Error>>::error_response_with_status(
+                        Self::PATH,
+                        <#output as #server_fn_path::error::ErrorEncoding<Self::Error>>::status(err),
+                        <#output as #server_fn_path::error::ErrorEncoding<Self::Error>>::ser(err),
+                    )
+                }
+
+                fn ser_error(
+                    err: &#server_fn_path::ServerFnError<Self::Error>,
+                ) -> String {
+                    <#output as #server_fn_path::error::ErrorEncoding<Self::Error>>::ser(err)
+                }
+
+                fn de_error(
+                    data: &str,
+                ) -> #server_fn_path::ServerFnError<Self::Error> {
+                    <#output as #server_fn_path::error::ErrorEncoding<Self::Error>>::de(data)
+                }
+            })
+        }
+        None => None,
+    };
+    let error_ty = match error {
+        Some(error) => error.to_token_stream(),
+        None => error_ty.map(ToTokens::to_token_stream).unwrap_or_else(|| {
             quote! {
                 #server_fn_path::error::NoCustomError
             }
-        });
+        }),
+    };
+
+    // a websocket server function takes a single stream of messages from the client, and
+    // returns a stream of messages for the client
+    let websocket_msgs = match &protocol {
+        Some(_) => {
+            let client_msg = match (fn_args.as_slice(), &custom_wrapper) {
+                ([arg], None) => stream_item(&arg.ty)?,
+                _ => {
+                    return Err(syn::Error::new(
+                        body.inputs.span(),
+                        "websocket server functions should take a single \
+                         `BoxedStream<T>` argument",
+                    ))
+                }
+            };
+            let server_msg = match output_ty {
+                GenericArgument::Type(ty) => stream_item(ty)?,
+                _ => {
+                    return Err(syn::Error::new(
+                        output_ty.span(),
+                        "websocket server functions should return \
+                         `Result<BoxedStream<T>, ServerFnError>`",
+                    ))
+                }
+            };
+            Some((client_msg, server_msg))
+        }
+        None => None,
+    };
 
     // build server fn path
     let serde_path = server_fn_path.as_ref().map(|path| {
@@ -364,7 +463,23 @@ pub fn server_macro_impl(
         .collect::<TokenStream2>();
 
     // auto-registration with inventory
-    let inventory = if cfg!(feature = "ssr") {
+    let inventory = if let (true, Some(protocol)) =
+        (cfg!(feature = "ssr"), &protocol)
+    {
+        quote! {
+            #server_fn_path::inventory::submit! {{
+                use #server_fn_path::{websocket::WebsocketServerFn, codec::Encoding};
+                #server_fn_path::ServerFnTraitObj::new(
+                    <#struct_name as WebsocketServerFn>::PATH,
+                    <#protocol as Encoding>::METHOD,
+                    |req| {
+                        Box::pin(<#struct_name as WebsocketServerFn>::run_on_server(req))
+                    },
+                    <#struct_name as WebsocketServerFn>::middlewares
+                )
+            }}
+        }
+    } else if cfg!(feature = "ssr") {
         quote! {
             #server_fn_path::inventory::submit! {{
                 use #server_fn_path::{ServerFn, codec::Encoding};
@@ -434,14 +549,70 @@ pub fn server_macro_impl(
         }
     };
 
+    let server_fn_trait = if protocol.is_some() {
+        quote! { #server_fn_path::websocket::WebsocketServerFn }
+    } else {
+        quote! { #server_fn_path::ServerFn }
+    };
+    // the built-in encodings other than `GetUrl` send requests with `POST`, which is not cached
+    if let (Some(cache), Some(input_ident)) = (&cache, input_ident.as_deref()) {
+        if matches!(
+            input_ident,
+            "PostUrl"
+                | "Json"
+                | "Cbor"
+                | "MsgPack"
+                | "Postcard"
+                | "Rkyv"
+                | "SerdeLite"
+                | "MultipartFormData"
+                | "Streaming"
+                | "StreamingJson"
+                | "StreamingText"
+                | "StreamingEvents"
+        ) {
+            return Err(syn::Error::new(
+                cache.span(),
+                format!(
+                    "`cache = ...` only applies to `GET` requests, but \
+                     `{input_ident}` sends requests with `POST`; use the \
+                     `GetUrl` input encoding, or remove `cache`"
+                ),
+            ));
+        }
+    }
+    let cache_control = cache.as_ref().map(|cache| {
+        quote! {
+            const CACHE_CONTROL: Option<&'static str> = Some(#cache);
+        }
+    });
+
+    // functions with a cache directive also get a `_forced` variant, which never shares a request
+    let forced_fn_name = format_ident!("{fn_name}_forced");
+    let forced_docs = format!(
+        "Calls [`{fn_name_as_str}`], but always sends a new request, rather \
+         than sharing an identical one that is already in flight."
+    );
+
     // the actual function definition
     let func = if cfg!(feature = "ssr") {
+        let forced_func = cache.is_some().then(|| {
+            quote! {
+                #[doc = #forced_docs]
+                #(#attrs)*
+                #vis async fn #forced_fn_name(#(#fn_args),*) #output_arrow #return_ty {
+                    #dummy_name(#(#field_names),*).await
+                }
+            }
+        });
         quote! {
             #docs
             #(#attrs)*
             #vis async fn #fn_name(#(#fn_args),*) #output_arrow #return_ty {
                 #dummy_name(#(#field_names),*).await
             }
+
+            #forced_func
         }
     } else {
         let restructure = if let Some(custom_wrapper) = custom_wrapper.as_ref()
@@ -454,15 +625,36 @@ pub fn server_macro_impl(
                 let data = #struct_name { #(#field_names),* };
             }
         };
+        // identical in-flight requests are shared on the client
+        let (run_on_client, forced_func) = if cache.is_some() {
+            let forced_func = quote! {
+                #[doc = #forced_docs]
+                #(#attrs)*
+                #[allow(unused_variables)]
+                #vis async fn #forced_fn_name(#(#fn_args),*) #output_arrow #return_ty {
+                    use #server_fn_trait;
+                    #restructure
+                    data.run_on_client_deduped(true).await
+                }
+            };
+            (
+                quote! { data.run_on_client_deduped(false).await },
+                Some(forced_func),
+            )
+        } else {
+            (quote! { data.run_on_client().await }, None)
+        };
         quote! {
             #docs
             #(#attrs)*
             #[allow(unused_variables)]
             #vis async fn #fn_name(#(#fn_args),*) #output_arrow #return_ty {
-                use #server_fn_path::ServerFn;
+                use #server_fn_trait;
                 #restructure
-                data.run_on_client().await
+                #run_on_client
             }
+
+            #forced_func
         }
     };
 
@@ -481,7 +673,8 @@ pub fn server_macro_impl(
         ),
         Some("MultipartFormData")
         | Some("Streaming")
-        | Some("StreamingText") => (PathInfo::None, quote! {}),
+        | Some("StreamingText")
+        | Some("Websocket") => (PathInfo::None, quote! {}),
         Some("SerdeLite") => (
             PathInfo::Serde,
             quote! {
@@ -511,6 +704,14 @@ pub fn server_macro_impl(
 
     let client = if let Some(client) = client {
         client.to_token_stream()
+    } else if protocol.is_some() && cfg!(feature = "reqwest") {
+        quote! {
+            #server_fn_path::websocket::tungstenite::TungsteniteClient
+        }
+    } else if protocol.is_some() {
+        quote! {
+            #server_fn_path::websocket::browser::BrowserClient
+        }
     } else if cfg!(feature = "reqwest") {
         quote! {
             #server_fn_path::client::reqwest::ReqwestClient
@@ -620,6 +821,54 @@ pub fn server_macro_impl(
         quote! { vec![] }
     };
 
+    let server_fn_impl = if let (
+        Some(protocol),
+        Some((client_msg, server_msg)),
+    ) = (&protocol, &websocket_msgs)
+    {
+        quote! {
+            impl #server_fn_path::websocket::WebsocketServerFn for #wrapped_struct_name {
+                const PATH: &'static str = #path;
+
+                type ClientMsg = #client_msg;
+                type ServerMsg = #server_msg;
+                type Encoding = #protocol;
+                type Client = #client;
+                type ServerRequest = #req;
+                type ServerResponse = #res;
+
+                fn middlewares() -> Vec<std::sync::Arc<dyn #server_fn_path::middleware::Layer<#req, #res>>> {
+                    #middlewares
+                }
+
+                #run_body
+            }
+        }
+    } else {
+        quote! {
+            impl #server_fn_path::ServerFn for #wrapped_struct_name {
+                const PATH: &'static str = #path;
+                #cache_control
+
+                type Client = #client;
+                type ServerRequest = #req;
+                type ServerResponse = #res;
+                type Output = #output_ty;
+                type InputEncoding = #input;
+                type OutputEncoding = #output;
+                type Error = #error_ty;
+
+                fn middlewares() -> Vec<std::sync::Arc<dyn #server_fn_path::middleware::Layer<#req, #res>>> {
+                    #middlewares
+                }
+
+                #error_encoding
+
+                #run_body
+            }
+        }
+    };
+
     Ok(quote::quote! {
         #args_docs
         #docs
@@ -631,23 +880,7 @@ pub fn server_macro_impl(
 
         #from_impl
 
-        impl #server_fn_path::ServerFn for #wrapped_struct_name {
-            const PATH: &'static str = #path;
-
-            type Client = #client;
-            type ServerRequest = #req;
-            type ServerResponse = #res;
-            type Output = #output_ty;
-            type InputEncoding = #input;
-            type OutputEncoding = #output;
-            type Error = #error_ty;
-
-            fn middlewares() -> Vec<std::sync::Arc<dyn #server_fn_path::middleware::Layer<#req, #res>>> {
-                #middlewares
-            }
-
-            #run_body
-        }
+        #server_fn_impl
 
         #inventory
 
@@ -713,6 +946,26 @@ fn output_type(return_ty: &Type) -> Result<&GenericArgument> {
     ))
 }
 
+fn stream_item(ty: &Type) -> Result<&Type> {
+    if let Type::Path(pat) = ty {
+        if let Some(segment) = pat.path.segments.last() {
+            if segment.ident == "BoxedStream" {
+                if let PathArguments::AngleBracketed(args) = &segment.arguments
+                {
+                    if let Some(GenericArgument::Type(ty)) = args.args.first() {
+                        return Ok(ty);
+                    }
+                }
+            }
+        }
+    }
+
+    Err(syn::Error::new(
+        ty.span(),
+        "websocket server functions should take and return `BoxedStream<T>`",
+    ))
+}
+
 fn err_type(return_ty: &Type) -> Result<Option<&GenericArgument>> {
     if let syn::Type::Path(pat) = &return_ty {
         if pat.path.segments[0].ident == "Result" {
@@ -769,6 +1022,9 @@ struct ServerFnArgs {
     custom_wrapper: Option<Path>,
     builtin_encoding: bool,
     impl_from: Option<LitBool>,
+    error: Option<Type>,
+    cache: Option<LitStr>,
+    protocol: Option<Type>,
 }
 
 impl Parse for ServerFnArgs {
@@ -788,6 +1044,9 @@ impl Parse for ServerFnArgs {
         let mut client: Option<Type> = None;
         let mut custom_wrapper: Option<Path> = None;
         let mut impl_from: Option<LitBool> = None;
+        let mut error: Option<Type> = None;
+        let mut cache: Option<LitStr> = None;
+        let mut protocol: Option<Type> = None;
 
         let mut use_key_and_value = false;
         let mut arg_pos = 0;
@@ -911,6 +1170,30 @@ impl Parse for ServerFnArgs {
                             ));
                         }
                         impl_from = Some(stream.parse()?);
+                    } else if key == "error" {
+                        if error.is_some() {
+                            return Err(syn::Error::new(
+                                key.span(),
+                                "keyword argument repeated: `error`",
+                            ));
+                        }
+                        error = Some(stream.parse()?);
+                    } else if key == "cache" {
+                        if cache.is_some() {
+                            return Err(syn::Error::new(
+                                key.span(),
+                                "keyword argument repeated: `cache`",
+                            ));
+                        }
+                        cache = Some(stream.parse()?);
+                    } else if key == "protocol" {
+                        if protocol.is_some() {
+                            return Err(syn::Error::new(
+                                key.span(),
+                                "keyword argument repeated: `protocol`",
+                            ));
+                        }
+                        protocol = Some(stream.parse()?);
                     } else {
                         return Err(lookahead.error());
                     }
@@ -962,6 +1245,20 @@ impl Parse for ServerFnArgs {
             }
         }
 
+        if let Some(protocol) = &protocol {
+            if encoding.is_some()
+                || input.is_some()
+                || output.is_some()
+                || cache.is_some()
+            {
+                return Err(syn::Error::new(
+                    protocol.span(),
+                    "`protocol` should not be specified together with \
+                     `encoding`, `input`, `output`, or `cache`",
+                ));
+            }
+        }
+
         // parse legacy encoding into input/output
         let mut builtin_encoding = false;
         if let Some(encoding) = encoding {
@@ -1008,6 +1305,9 @@ impl Parse for ServerFnArgs {
             client,
             custom_wrapper,
             impl_from,
+            error,
+            cache,
+            protocol,
         })
     }
 }
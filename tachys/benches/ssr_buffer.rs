@@ -0,0 +1,106 @@
+//! Counts how often memory is reallocated while rendering a page of a few thousand dynamic text
+//! nodes to HTML, which mostly comes from growing the output buffer.
+//!
+//! Run with `cargo bench -p tachys --features reactive_graph --bench ssr_buffer`.
+
+use reactive_graph::{owner::Owner, signal::RwSignal, traits::Get};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    hint::black_box,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+use tachys::{
+    html::element::{li, ul, ElementChild},
+    view::RenderHtml,
+};
+
+const ROWS: usize = 5_000;
+const RUNS: u32 = 200;
+
+struct CountingAllocator;
+
+static REALLOCS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(
+        &self,
+        ptr: *mut u8,
+        layout: Layout,
+        new_size: usize,
+    ) -> *mut u8 {
+        REALLOCS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+// renders the page `RUNS` times, and returns the reallocations and time for each render
+fn measure(mut render: impl FnMut() -> String) -> (usize, Duration) {
+    let reallocs = REALLOCS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    for _ in 0..RUNS {
+        black_box(render());
+    }
+    let elapsed = start.elapsed();
+    let reallocs = REALLOCS.load(Ordering::Relaxed) - reallocs;
+    (reallocs / RUNS as usize, elapsed / RUNS)
+}
+
+fn main() {
+    let owner = Owner::new();
+    owner.set();
+
+    let labels = (0..ROWS)
+        .map(|n| RwSignal::new(format!("Row number {n}")))
+        .collect::<Vec<_>>();
+    let signals = || {
+        ul().child(
+            labels
+                .iter()
+                .map(|label| li().child(*label))
+                .collect::<Vec<_>>(),
+        )
+    };
+    let closures = || {
+        ul().child(
+            labels
+                .iter()
+                .map(|label| {
+                    let label = *label;
+                    li().child(move || label.get())
+                })
+                .collect::<Vec<_>>(),
+        )
+    };
+
+    // the size of the page, as a server could remember it from rendering the page before
+    let page_len = closures().to_html().len();
+    let cases = [
+        (
+            "signals, without an estimate",
+            measure(|| signals().to_html_with_capacity_hint(0)),
+        ),
+        ("signals, estimated", measure(|| signals().to_html())),
+        ("closures, estimated", measure(|| closures().to_html())),
+        (
+            "closures, with a capacity hint",
+            measure(|| closures().to_html_with_capacity_hint(page_len)),
+        ),
+    ];
+
+    println!("{ROWS} dynamic text nodes, averaged over {RUNS} renders:");
+    for (name, (reallocs, time)) in cases {
+        println!("{name:>32}: {reallocs:>4} reallocations, {time:?}");
+    }
+}
@@ -2,6 +2,7 @@ use super::{Attribute, NextAttribute};
 use std::{
     any::{Any, TypeId},
     fmt::Debug,
+    mem,
 };
 #[cfg(feature = "ssr")]
 use std::{future::Future, pin::Pin};
@@ -31,6 +32,7 @@ pub struct AnyAttribute {
         fn(Box<dyn Any>) -> Pin<Box<dyn Future<Output = AnyAttribute> + Send>>,
     #[cfg(feature = "ssr")]
     dry_resolve: fn(&mut Box<dyn Any + Send>),
+    into_cloneable: fn(Box<dyn Any + Send>) -> CloneableAnyAttribute,
 }
 
 impl Debug for AnyAttribute {
@@ -44,6 +46,7 @@ pub struct AnyAttributeState {
     type_id: TypeId,
     state: Box<dyn Any>,
     el: crate::renderer::types::Element,
+    remove: fn(Box<dyn Any>, &crate::renderer::types::Element),
 }
 
 /// Converts an [`Attribute`] into [`AnyAttribute`].
@@ -95,6 +98,7 @@ where
                             type_id: TypeId::of::<T>(),
                             state,
                             el: el.clone(),
+                            remove: remove::<T>,
                         }
                     };
                 #[cfg(feature = "hydrate")]
@@ -111,6 +115,7 @@ where
                             type_id: TypeId::of::<T>(),
                             state,
                             el: el.clone(),
+                            remove: remove::<T>,
                         }
                     };
                 #[cfg(feature = "hydrate")]
@@ -118,15 +123,16 @@ where
                     |value: Box<dyn Any>,
                      el: &crate::renderer::types::Element| {
                         let value = value.downcast::<T>().expect(
-                            "AnyAttribute::hydrate_from_server couldn't \
+                            "AnyAttribute::hydrate_from_template couldn't \
                              downcast",
                         );
-                        let state = Box::new(value.hydrate::<true>(el));
+                        let state = Box::new(value.hydrate::<false>(el));
 
                         AnyAttributeState {
                             type_id: TypeId::of::<T>(),
                             state,
                             el: el.clone(),
+                            remove: remove::<T>,
                         }
                     };
                 let rebuild =
@@ -142,8 +148,13 @@ where
                             );
                             value.rebuild(state);
                         } else {
-                            let new = value.into_any_attr().build(&state.el);
-                            *state = new;
+                            // the old attribute is removed first, in case the new one sets
+                            // the same attribute
+                            let el = state.el.clone();
+                            let old =
+                                mem::replace(&mut state.state, Box::new(()));
+                            (state.remove)(old, &el);
+                            *state = value.into_any_attr().build(&el);
                         }
                     };
                 #[cfg(feature = "ssr")]
@@ -164,6 +175,12 @@ where
                     )
                         as Pin<Box<dyn Future<Output = AnyAttribute> + Send>>
                 };
+                let into_cloneable = |value: Box<dyn Any + Send>| {
+                    let value = value.downcast::<T>().expect(
+                        "AnyAttribute::into_cloneable couldn't downcast",
+                    );
+                    CloneableAnyAttribute::new(value.into_cloneable_owned())
+                };
                 AnyAttribute {
                     type_id: TypeId::of::<T>(),
                     html_len,
@@ -180,12 +197,23 @@ where
                     resolve,
                     #[cfg(feature = "ssr")]
                     dry_resolve,
+                    into_cloneable,
                 }
             }
         }
     }
 }
 
+fn remove<T: Attribute + 'static>(
+    state: Box<dyn Any>,
+    el: &crate::renderer::types::Element,
+) {
+    let state = state
+        .downcast::<T::State>()
+        .expect("AnyAttribute::remove couldn't downcast state");
+    T::remove(el, *state);
+}
+
 impl NextAttribute for AnyAttribute {
     type Output<NewAttr: Attribute> = (Self, NewAttr);
 
@@ -202,8 +230,8 @@ impl Attribute for AnyAttribute {
 
     type AsyncOutput = AnyAttribute;
     type State = AnyAttributeState;
-    type Cloneable = ();
-    type CloneableOwned = ();
+    type Cloneable = CloneableAnyAttribute;
+    type CloneableOwned = CloneableAnyAttribute;
 
     fn html_len(&self) -> usize {
         self.html_len
@@ -256,12 +284,16 @@ impl Attribute for AnyAttribute {
         (self.rebuild)(self.type_id, self.value, state)
     }
 
+    fn remove(_el: &crate::renderer::types::Element, state: Self::State) {
+        (state.remove)(state.state, &state.el);
+    }
+
     fn into_cloneable(self) -> Self::Cloneable {
-        todo!()
+        (self.into_cloneable)(self.value)
     }
 
     fn into_cloneable_owned(self) -> Self::CloneableOwned {
-        todo!()
+        (self.into_cloneable)(self.value)
     }
 
     fn dry_resolve(&mut self) {
@@ -288,3 +320,115 @@ impl Attribute for AnyAttribute {
         );
     }
 }
+
+/// A type-erased [`Attribute`] that can be cloned, created by
+/// [`AnyAttribute::into_cloneable`](Attribute::into_cloneable).
+pub struct CloneableAnyAttribute {
+    inner: AnyAttribute,
+    clone_value: fn(&(dyn Any + Send)) -> Box<dyn Any + Send>,
+}
+
+impl CloneableAnyAttribute {
+    fn new<T>(value: T) -> Self
+    where
+        T: Attribute + Clone + 'static,
+    {
+        Self {
+            inner: value.into_any_attr(),
+            clone_value: |value| {
+                let value = value
+                    .downcast_ref::<T>()
+                    .expect("CloneableAnyAttribute::clone couldn't downcast");
+                Box::new(value.clone())
+            },
+        }
+    }
+}
+
+impl Clone for CloneableAnyAttribute {
+    fn clone(&self) -> Self {
+        Self {
+            inner: AnyAttribute {
+                value: (self.clone_value)(&*self.inner.value),
+                ..self.inner
+            },
+            clone_value: self.clone_value,
+        }
+    }
+}
+
+impl Debug for CloneableAnyAttribute {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CloneableAnyAttribute")
+            .finish_non_exhaustive()
+    }
+}
+
+impl NextAttribute for CloneableAnyAttribute {
+    type Output<NewAttr: Attribute> = (Self, NewAttr);
+
+    fn add_any_attr<NewAttr: Attribute>(
+        self,
+        new_attr: NewAttr,
+    ) -> Self::Output<NewAttr> {
+        (self, new_attr)
+    }
+}
+
+impl Attribute for CloneableAnyAttribute {
+    const MIN_LENGTH: usize = 0;
+
+    type AsyncOutput = AnyAttribute;
+    type State = AnyAttributeState;
+    type Cloneable = Self;
+    type CloneableOwned = Self;
+
+    fn html_len(&self) -> usize {
+        self.inner.html_len()
+    }
+
+    fn to_html(
+        self,
+        buf: &mut String,
+        class: &mut String,
+        style: &mut String,
+        inner_html: &mut String,
+    ) {
+        self.inner.to_html(buf, class, style, inner_html);
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        el: &crate::renderer::types::Element,
+    ) -> Self::State {
+        self.inner.hydrate::<FROM_SERVER>(el)
+    }
+
+    fn build(self, el: &crate::renderer::types::Element) -> Self::State {
+        self.inner.build(el)
+    }
+
+    fn rebuild(self, state: &mut Self::State) {
+        self.inner.rebuild(state);
+    }
+
+    fn remove(el: &crate::renderer::types::Element, state: Self::State) {
+        AnyAttribute::remove(el, state);
+    }
+
+    fn into_cloneable(self) -> Self::Cloneable {
+        self
+    }
+
+    fn into_cloneable_owned(self) -> Self::CloneableOwned {
+        self
+    }
+
+    fn dry_resolve(&mut self) {
+        self.inner.dry_resolve();
+    }
+
+    async fn resolve(self) -> Self::AsyncOutput {
+        self.inner.resolve().await
+    }
+}
@@ -1,6 +1,7 @@
 use super::NextAttribute;
 use crate::{
     html::attribute::{Attribute, AttributeValue},
+    renderer::Rndr,
     view::{add_attr::AddAnyAttr, Position, ToTemplate},
 };
 use std::{borrow::Cow, sync::Arc};
@@ -46,7 +47,7 @@ where
 {
     const MIN_LENGTH: usize = 0;
     type AsyncOutput = CustomAttr<K, V::AsyncOutput>;
-    type State = V::State;
+    type State = (crate::renderer::types::Element, K, V::State);
     type Cloneable = CustomAttr<K, V::Cloneable>;
     type CloneableOwned = CustomAttr<K, V::CloneableOwned>;
 
@@ -68,19 +69,45 @@ where
         self,
         el: &crate::renderer::types::Element,
     ) -> Self::State {
-        if !K::KEY.is_empty() {
-            self.value.hydrate::<FROM_SERVER>(self.key.as_ref(), el)
-        } else {
-            self.value.build(el, self.key.as_ref())
-        }
+        let state = match (K::KEY.is_empty(), self.key.static_key()) {
+            (false, Some(key)) => {
+                self.value.hydrate_static::<FROM_SERVER>(key, el)
+            }
+            (false, None) => {
+                self.value.hydrate::<FROM_SERVER>(self.key.as_ref(), el)
+            }
+            (true, Some(key)) => self.value.build_static(el, key),
+            (true, None) => self.value.build(el, self.key.as_ref()),
+        };
+        (el.clone(), self.key, state)
     }
 
     fn build(self, el: &crate::renderer::types::Element) -> Self::State {
-        self.value.build(el, self.key.as_ref())
+        let state = match self.key.static_key() {
+            Some(key) => self.value.build_static(el, key),
+            None => self.value.build(el, self.key.as_ref()),
+        };
+        (el.clone(), self.key, state)
     }
 
     fn rebuild(self, state: &mut Self::State) {
-        self.value.rebuild(self.key.as_ref(), state);
+        let (el, key, value_state) = state;
+        if key.as_ref() != self.key.as_ref() {
+            // a different attribute with the same type of key: the old one is removed, and the
+            // new one is built from scratch on the same element
+            Rndr::remove_attribute(el, key.as_ref());
+            let el = el.clone();
+            *state = self.build(&el);
+            return;
+        }
+        match self.key.static_key() {
+            Some(key) => self.value.rebuild_static(key, value_state),
+            None => self.value.rebuild(self.key.as_ref(), value_state),
+        }
+    }
+
+    fn remove(el: &crate::renderer::types::Element, state: Self::State) {
+        Rndr::remove_attribute(el, state.1.as_ref());
     }
 
     fn into_cloneable(self) -> Self::Cloneable {
@@ -147,14 +174,30 @@ where
 pub trait CustomAttributeKey: Clone + AsRef<str> + Send + 'static {
     /// The attribute name.
     const KEY: &'static str;
+
+    /// The attribute name, if it is a `&'static str` that can be kept without copying it.
+    fn static_key(&self) -> Option<&'static str> {
+        (!Self::KEY.is_empty()).then_some(Self::KEY)
+    }
 }
 
 impl CustomAttributeKey for &'static str {
     const KEY: &'static str = "";
+
+    fn static_key(&self) -> Option<&'static str> {
+        Some(self)
+    }
 }
 
 impl CustomAttributeKey for Cow<'static, str> {
     const KEY: &'static str = "";
+
+    fn static_key(&self) -> Option<&'static str> {
+        match self {
+            Cow::Borrowed(key) => Some(key),
+            Cow::Owned(_) => None,
+        }
+    }
 }
 
 impl CustomAttributeKey for String {
@@ -9,7 +9,10 @@ pub mod global;
 mod key;
 mod value;
 
-use crate::view::{Position, ToTemplate};
+use crate::{
+    renderer::Rndr,
+    view::{Position, ToTemplate},
+};
 pub use key::*;
 use std::{fmt::Debug, future::Future};
 pub use value::*;
@@ -58,6 +61,13 @@ pub trait Attribute: NextAttribute + Send {
     /// Applies a new value for the attribute.
     fn rebuild(self, state: &mut Self::State);
 
+    /// Removes the attribute from the element it was built on, given its state.
+    ///
+    /// This is used when an attribute is dropped from a list of attributes, or replaced by an
+    /// attribute of a different type. By default, the element is left as it is.
+    #[allow(unused_variables)]
+    fn remove(el: &crate::renderer::types::Element, state: Self::State) {}
+
     /// Converts this attribute into an equivalent that can be cloned.
     fn into_cloneable(self) -> Self::Cloneable;
 
@@ -205,15 +215,19 @@ where
         self,
         el: &crate::renderer::types::Element,
     ) -> Self::State {
-        self.1.hydrate::<FROM_SERVER>(K::KEY, el)
+        self.1.hydrate_static::<FROM_SERVER>(K::KEY, el)
     }
 
     fn build(self, el: &crate::renderer::types::Element) -> Self::State {
-        V::build(self.1, el, K::KEY)
+        V::build_static(self.1, el, K::KEY)
     }
 
     fn rebuild(self, state: &mut Self::State) {
-        V::rebuild(self.1, K::KEY, state);
+        V::rebuild_static(self.1, K::KEY, state);
+    }
+
+    fn remove(el: &crate::renderer::types::Element, _state: Self::State) {
+        Rndr::remove_attribute(el, K::KEY);
     }
 
     fn into_cloneable(self) -> Self::Cloneable {
@@ -303,6 +317,13 @@ macro_rules! impl_attr_for_tuples {
 				}
 			}
 
+            fn remove(el: &crate::renderer::types::Element, state: Self::State) {
+                #[allow(non_snake_case)]
+                let ($first, $($ty,)*) = state;
+                $first::remove(el, $first);
+                $($ty::remove(el, $ty));*
+            }
+
             fn into_cloneable(self) -> Self::Cloneable {
                 #[allow(non_snake_case)]
                 let ($first, $($ty,)*) = self;
@@ -413,6 +434,13 @@ macro_rules! impl_attr_for_tuples_truncate_additional {
 				}
 			}
 
+            fn remove(el: &crate::renderer::types::Element, state: Self::State) {
+                #[allow(non_snake_case)]
+                let ($first, $($ty,)*) = state;
+                $first::remove(el, $first);
+                $($ty::remove(el, $ty));*
+            }
+
             fn into_cloneable(self) -> Self::Cloneable {
                 #[allow(non_snake_case)]
                 let ($first, $($ty,)*) = self;
@@ -507,6 +535,10 @@ where
         self.0.rebuild(state);
     }
 
+    fn remove(el: &crate::renderer::types::Element, state: Self::State) {
+        A::remove(el, state);
+    }
+
     fn into_cloneable(self) -> Self::Cloneable {
         (self.0.into_cloneable(),)
     }
@@ -578,3 +610,123 @@ impl_attr_for_tuples_truncate_additional!(
     A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y,
     Z
 );
+
+/// A list of attributes whose length is only known at runtime.
+///
+/// Together with [`AnyAttribute`](any_attribute::AnyAttribute), this allows spreading a set of
+/// attributes that is chosen at runtime onto an element:
+/// ```rust,ignore
+/// let attrs: Vec<AnyAttribute> = vec![
+///     title("Save").into_any_attr(),
+///     class(move || if dirty.get() { "dirty" } else { "" }).into_any_attr(),
+///     on(ev::click, save).into_any_attr(),
+/// ];
+/// button().add_any_attr(attrs)
+/// ```
+/// The attributes are rendered and hydrated in order. When the list is rebuilt, each attribute
+/// is rebuilt against the one in the same position, and any additional attributes are built. If
+/// it has become shorter, the extra attributes are removed from the element.
+impl<A> Attribute for Vec<A>
+where
+    A: Attribute,
+{
+    const MIN_LENGTH: usize = 0;
+
+    type State = (crate::renderer::types::Element, Vec<A::State>);
+    type AsyncOutput = Vec<A::AsyncOutput>;
+    type Cloneable = Vec<A::Cloneable>;
+    type CloneableOwned = Vec<A::CloneableOwned>;
+
+    fn html_len(&self) -> usize {
+        self.iter().map(Attribute::html_len).sum()
+    }
+
+    fn to_html(
+        self,
+        buf: &mut String,
+        class: &mut String,
+        style: &mut String,
+        inner_html: &mut String,
+    ) {
+        for attr in self {
+            attr.to_html(buf, class, style, inner_html);
+        }
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        el: &crate::renderer::types::Element,
+    ) -> Self::State {
+        let states = self
+            .into_iter()
+            .map(|attr| attr.hydrate::<FROM_SERVER>(el))
+            .collect();
+        (el.clone(), states)
+    }
+
+    fn build(self, el: &crate::renderer::types::Element) -> Self::State {
+        let states = self.into_iter().map(|attr| attr.build(el)).collect();
+        (el.clone(), states)
+    }
+
+    fn rebuild(self, state: &mut Self::State) {
+        let (el, states) = state;
+        // the extra attributes are removed before the others are rebuilt, in case one of those
+        // now sets the same attribute
+        if states.len() > self.len() {
+            for state in states.drain(self.len()..) {
+                A::remove(el, state);
+            }
+        }
+        for (idx, attr) in self.into_iter().enumerate() {
+            match states.get_mut(idx) {
+                Some(state) => attr.rebuild(state),
+                None => states.push(attr.build(el)),
+            }
+        }
+    }
+
+    fn remove(el: &crate::renderer::types::Element, state: Self::State) {
+        for state in state.1 {
+            A::remove(el, state);
+        }
+    }
+
+    fn into_cloneable(self) -> Self::Cloneable {
+        self.into_iter().map(Attribute::into_cloneable).collect()
+    }
+
+    fn into_cloneable_owned(self) -> Self::CloneableOwned {
+        self.into_iter()
+            .map(Attribute::into_cloneable_owned)
+            .collect()
+    }
+
+    fn dry_resolve(&mut self) {
+        for attr in self.iter_mut() {
+            attr.dry_resolve();
+        }
+    }
+
+    async fn resolve(self) -> Self::AsyncOutput {
+        let mut resolved = Vec::with_capacity(self.len());
+        for attr in self {
+            resolved.push(attr.resolve().await);
+        }
+        resolved
+    }
+}
+
+impl<A> NextAttribute for Vec<A>
+where
+    A: Attribute,
+{
+    type Output<NewAttr: Attribute> = (Self, NewAttr);
+
+    fn add_any_attr<NewAttr: Attribute>(
+        self,
+        new_attr: NewAttr,
+    ) -> Self::Output<NewAttr> {
+        (self, new_attr)
+    }
+}
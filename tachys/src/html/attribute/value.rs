@@ -79,6 +79,41 @@ pub trait AttributeValue: Send {
     /// Applies a new value for the attribute.
     fn rebuild(self, key: &str, state: &mut Self::State);
 
+    /// Like [`AttributeValue::hydrate`], for a key that is known at compile time.
+    ///
+    /// Values that keep the key in their state (like reactive functions) can store a
+    /// `&'static str` rather than allocating a copy of it.
+    fn hydrate_static<const FROM_SERVER: bool>(
+        self,
+        key: &'static str,
+        el: &crate::renderer::types::Element,
+    ) -> Self::State
+    where
+        Self: Sized,
+    {
+        self.hydrate::<FROM_SERVER>(key, el)
+    }
+
+    /// Like [`AttributeValue::build`], for a key that is known at compile time.
+    fn build_static(
+        self,
+        el: &crate::renderer::types::Element,
+        key: &'static str,
+    ) -> Self::State
+    where
+        Self: Sized,
+    {
+        self.build(el, key)
+    }
+
+    /// Like [`AttributeValue::rebuild`], for a key that is known at compile time.
+    fn rebuild_static(self, key: &'static str, state: &mut Self::State)
+    where
+        Self: Sized,
+    {
+        self.rebuild(key, state)
+    }
+
     /// Converts this attribute into an equivalent that can be cloned.
     fn into_cloneable(self) -> Self::Cloneable;
 
@@ -441,8 +476,173 @@ impl AttributeValue for Arc<str> {
         self
     }
 }
-// TODO impl AttributeValue for Rc<str> and Arc<str> too
 
+// `Rc<str>` can't be an attribute value, because attributes need to be `Send` so that views can
+// be rendered on the server; use `Arc<str>` instead
+
+impl AttributeValue for Cow<'static, str> {
+    type AsyncOutput = Self;
+    type State = (crate::renderer::types::Element, Self);
+    type Cloneable = Arc<str>;
+    type CloneableOwned = Arc<str>;
+
+    fn html_len(&self) -> usize {
+        self.len()
+    }
+
+    fn to_html(self, key: &str, buf: &mut String) {
+        <&str as AttributeValue>::to_html(&self, key, buf);
+    }
+
+    fn to_template(_key: &str, _buf: &mut String) {}
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        key: &str,
+        el: &crate::renderer::types::Element,
+    ) -> Self::State {
+        let (el, _) =
+            <&str as AttributeValue>::hydrate::<FROM_SERVER>(&self, key, el);
+        (el, self)
+    }
+
+    fn build(
+        self,
+        el: &crate::renderer::types::Element,
+        key: &str,
+    ) -> Self::State {
+        Rndr::set_attribute(el, key, &self);
+        (el.clone(), self)
+    }
+
+    fn rebuild(self, key: &str, state: &mut Self::State) {
+        let (el, prev_value) = state;
+        if self != *prev_value {
+            Rndr::set_attribute(el, key, &self);
+        }
+        *prev_value = self;
+    }
+
+    fn into_cloneable(self) -> Self::Cloneable {
+        self.into()
+    }
+
+    fn into_cloneable_owned(self) -> Self::CloneableOwned {
+        self.into()
+    }
+
+    fn dry_resolve(&mut self) {}
+
+    async fn resolve(self) -> Self::AsyncOutput {
+        self
+    }
+}
+
+/// An attribute value that is written into HTML without being escaped.
+///
+/// Attribute values are normally escaped during server rendering, so that a value like
+/// `{"a":1}` is written as `{&quot;a&quot;:1}`. If a value has already been made safe to include
+/// in a double-quoted attribute (for example, by a serializer that never emits `"` or `&`), this
+/// can be used to skip that step:
+/// ```rust,ignore
+/// div().attr("data-config", UnescapedAttr::dangerously_unescaped(config_json))
+/// ```
+/// On the client, attributes are set through the DOM and never escaped, so this behaves exactly
+/// like the inner value.
+///
+/// **Note**: The value is not checked in any way. Including untrusted input in it allows that
+/// input to close the attribute and inject arbitrary HTML.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UnescapedAttr<T>(T);
+
+impl<T> UnescapedAttr<T>
+where
+    T: AsRef<str>,
+{
+    /// Wraps a value that will be written into HTML as-is, without escaping.
+    ///
+    /// The caller is responsible for making sure the value cannot break out of a double-quoted
+    /// attribute.
+    pub fn dangerously_unescaped(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Returns the inner value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> AttributeValue for UnescapedAttr<T>
+where
+    T: AsRef<str> + Send,
+{
+    type AsyncOutput = Self;
+    type State = (crate::renderer::types::Element, T);
+    type Cloneable = UnescapedAttr<Arc<str>>;
+    type CloneableOwned = UnescapedAttr<Arc<str>>;
+
+    fn html_len(&self) -> usize {
+        self.0.as_ref().len()
+    }
+
+    fn to_html(self, key: &str, buf: &mut String) {
+        buf.push(' ');
+        buf.push_str(key);
+        buf.push_str("=\"");
+        buf.push_str(self.0.as_ref());
+        buf.push('"');
+    }
+
+    fn to_template(_key: &str, _buf: &mut String) {}
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        key: &str,
+        el: &crate::renderer::types::Element,
+    ) -> Self::State {
+        let (el, _) = <&str as AttributeValue>::hydrate::<FROM_SERVER>(
+            self.0.as_ref(),
+            key,
+            el,
+        );
+        (el, self.0)
+    }
+
+    fn build(
+        self,
+        el: &crate::renderer::types::Element,
+        key: &str,
+    ) -> Self::State {
+        Rndr::set_attribute(el, key, self.0.as_ref());
+        (el.clone(), self.0)
+    }
+
+    fn rebuild(self, key: &str, state: &mut Self::State) {
+        let (el, prev_value) = state;
+        if self.0.as_ref() != prev_value.as_ref() {
+            Rndr::set_attribute(el, key, self.0.as_ref());
+        }
+        *prev_value = self.0;
+    }
+
+    fn into_cloneable(self) -> Self::Cloneable {
+        UnescapedAttr(self.0.as_ref().into())
+    }
+
+    fn into_cloneable_owned(self) -> Self::CloneableOwned {
+        UnescapedAttr(self.0.as_ref().into())
+    }
+
+    fn dry_resolve(&mut self) {}
+
+    async fn resolve(self) -> Self::AsyncOutput {
+        self
+    }
+}
+
+/// A boolean attribute, like `disabled` or `checked`. The attribute is present (with an empty
+/// value) when `true` and absent when `false`, rather than being set to `"true"` or `"false"`.
 impl AttributeValue for bool {
     type AsyncOutput = Self;
     type State = (crate::renderer::types::Element, bool);
@@ -469,7 +669,8 @@ impl AttributeValue for bool {
     ) -> Self::State {
         // if we're actually hydrating from SSRed HTML, we don't need to set the attribute
         // if we're hydrating from a CSR-cloned <template>, we do need to set non-StaticAttr attributes
-        if !FROM_SERVER {
+        // a `false` value was never rendered, so there's nothing to add in either case
+        if !FROM_SERVER && self {
             Rndr::set_attribute(el, key, "");
         }
         (el.clone(), self)
@@ -513,6 +714,12 @@ impl AttributeValue for bool {
     }
 }
 
+/// An optional attribute value. The attribute is only set when the value is `Some(_)`, and is
+/// removed from the element if it later changes to `None`.
+///
+/// Because signals of any attribute value are attribute values themselves, this also means
+/// that a `Signal<Option<V>>` or `Memo<Option<V>>` will add and remove the attribute as the
+/// signal changes.
 impl<V> AttributeValue for Option<V>
 where
     V: AttributeValue,
@@ -599,6 +806,83 @@ pub(crate) fn escape_attr(value: &str) -> Cow<'_, str> {
     html_escape::encode_double_quoted_attribute(value)
 }
 
+// integers are formatted on the stack with `itoa`, rather than allocating a new `String` each
+// time the value changes
+macro_rules! render_integer {
+  ($($child_type:ty),* $(,)?) => {
+      $(
+        impl AttributeValue for $child_type
+        where
+
+        {
+            type AsyncOutput = $child_type;
+            type State = (crate::renderer::types::Element, $child_type);
+            type Cloneable = Self;
+            type CloneableOwned = Self;
+
+            fn html_len(&self) -> usize {
+                0
+            }
+
+            fn to_html(self, key: &str, buf: &mut String) {
+                buf.push(' ');
+                buf.push_str(key);
+                buf.push_str("=\"");
+                buf.push_str(itoa::Buffer::new().format(self));
+                buf.push('"');
+            }
+
+            fn to_template(_key: &str, _buf: &mut String) {}
+
+            fn hydrate<const FROM_SERVER: bool>(
+                self,
+                key: &str,
+                el: &crate::renderer::types::Element,
+            ) -> Self::State {
+                // if we're actually hydrating from SSRed HTML, we don't need to set the attribute
+                // if we're hydrating from a CSR-cloned <template>, we do need to set non-StaticAttr attributes
+                if !FROM_SERVER {
+                    Rndr::set_attribute(el, key, itoa::Buffer::new().format(self));
+                }
+                (el.clone(), self)
+            }
+
+            fn build(self, el: &crate::renderer::types::Element, key: &str) -> Self::State {
+                Rndr::set_attribute(el, key, itoa::Buffer::new().format(self));
+                (el.to_owned(), self)
+            }
+
+            fn rebuild(self, key: &str, state: &mut Self::State) {
+                let (el, prev_value) = state;
+                if self != *prev_value {
+                    Rndr::set_attribute(el, key, itoa::Buffer::new().format(self));
+                }
+                *prev_value = self;
+            }
+
+            fn into_cloneable(self) -> Self::Cloneable {
+                self
+            }
+
+            fn into_cloneable_owned(self) -> Self::CloneableOwned {
+                self
+            }
+
+            fn dry_resolve(&mut self) {
+            }
+
+            async fn resolve(self) -> Self::AsyncOutput {
+                self
+            }
+        }
+      )*
+  }
+}
+
+render_integer![
+    usize, u8, u16, u32, u64, u128, isize, i8, i16, i32, i64, i128,
+];
+
 macro_rules! render_primitive {
   ($($child_type:ty),* $(,)?) => {
       $(
@@ -667,18 +951,6 @@ macro_rules! render_primitive {
 }
 
 render_primitive![
-    usize,
-    u8,
-    u16,
-    u32,
-    u64,
-    u128,
-    isize,
-    i8,
-    i16,
-    i32,
-    i64,
-    i128,
     f32,
     f64,
     char,
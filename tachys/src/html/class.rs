@@ -72,6 +72,10 @@ where
         self.class.rebuild(state)
     }
 
+    fn remove(_el: &crate::renderer::types::Element, state: Self::State) {
+        C::remove(state);
+    }
+
     fn into_cloneable(self) -> Self::Cloneable {
         Class {
             class: self.class.into_cloneable(),
@@ -129,6 +133,9 @@ where
 /// A possible value for a CSS class.
 pub trait IntoClass: Send {
     /// The HTML that should be included in a `<template>`.
+    ///
+    /// Only classes that are known at compile time have a template. Dynamic classes are added to
+    /// the class list when the template is hydrated, so the static classes always come first.
     const TEMPLATE: &'static str = "";
     /// The minimum length of the HTML.
     const MIN_LENGTH: usize = Self::TEMPLATE.len();
@@ -165,6 +172,12 @@ pub trait IntoClass: Send {
     /// Updates the value.
     fn rebuild(self, state: &mut Self::State);
 
+    /// Removes the classes this value added, given its state.
+    ///
+    /// By default, the element is left as it is.
+    #[allow(unused_variables)]
+    fn remove(state: Self::State) {}
+
     /// Converts this to a cloneable type.
     fn into_cloneable(self) -> Self::Cloneable;
 
@@ -180,6 +193,71 @@ pub trait IntoClass: Send {
     fn resolve(self) -> impl Future<Output = Self::AsyncOutput> + Send;
 }
 
+/// The distinct whitespace-separated class names in `value`.
+fn class_tokens(value: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    for name in value.split_ascii_whitespace() {
+        if !tokens.contains(&name) {
+            tokens.push(name);
+        }
+    }
+    tokens
+}
+
+/// Adds each whitespace-separated class name in `value` to the element's class list.
+///
+/// Class names are counted for each element, so that a name added by more than one value (like a
+/// static class and a reactive one) stays until every value has removed it.
+pub(crate) fn add_class_tokens(
+    el: &crate::renderer::types::Element,
+    value: &str,
+) {
+    let add = Rndr::hold_class_tokens(el, &class_tokens(value));
+    Rndr::set_class_tokens(el, &add, &[]);
+}
+
+/// Counts the class names in `value` as held by a value that is being hydrated.
+///
+/// HTML rendered on the server already has the class names, so they are only added if the
+/// element was cloned from a `<template>`.
+pub(crate) fn hydrate_class_tokens<const FROM_SERVER: bool>(
+    el: &crate::renderer::types::Element,
+    value: &str,
+) {
+    if FROM_SERVER {
+        Rndr::hold_class_tokens(el, &class_tokens(value));
+    } else {
+        add_class_tokens(el, value);
+    }
+}
+
+/// Removes the class names in `prev` that are not in `next`, and adds those in `next` that were
+/// not in `prev`.
+///
+/// Only the classes this value added are touched, and a class name that a static class or another
+/// class binding on the same element still holds is kept.
+pub(crate) fn replace_class_tokens(
+    el: &crate::renderer::types::Element,
+    prev: &str,
+    next: &str,
+) {
+    let prev = class_tokens(prev);
+    let next = class_tokens(next);
+    let remove = prev
+        .iter()
+        .copied()
+        .filter(|name| !next.contains(name))
+        .collect::<Vec<_>>();
+    let add = next
+        .iter()
+        .copied()
+        .filter(|name| !prev.contains(name))
+        .collect::<Vec<_>>();
+    let remove = Rndr::release_class_tokens(el, &remove);
+    let add = Rndr::hold_class_tokens(el, &add);
+    Rndr::set_class_tokens(el, &add, &remove);
+}
+
 impl<'a> IntoClass for &'a str {
     type AsyncOutput = Self;
     type State = (crate::renderer::types::Element, Self);
@@ -198,25 +276,28 @@ impl<'a> IntoClass for &'a str {
         self,
         el: &crate::renderer::types::Element,
     ) -> Self::State {
-        if !FROM_SERVER {
-            Rndr::set_attribute(el, "class", self);
-        }
+        hydrate_class_tokens::<FROM_SERVER>(el, self);
         (el.clone(), self)
     }
 
     fn build(self, el: &crate::renderer::types::Element) -> Self::State {
-        Rndr::set_attribute(el, "class", self);
+        add_class_tokens(el, self);
         (el.clone(), self)
     }
 
     fn rebuild(self, state: &mut Self::State) {
         let (el, prev) = state;
         if self != *prev {
-            Rndr::set_attribute(el, "class", self);
+            replace_class_tokens(el, prev, self);
         }
         *prev = self;
     }
 
+    fn remove(state: Self::State) {
+        let (el, prev) = state;
+        replace_class_tokens(&el, prev, "");
+    }
+
     fn into_cloneable(self) -> Self::Cloneable {
         self
     }
@@ -250,25 +331,28 @@ impl IntoClass for String {
         self,
         el: &crate::renderer::types::Element,
     ) -> Self::State {
-        if !FROM_SERVER {
-            Rndr::set_attribute(el, "class", &self);
-        }
+        hydrate_class_tokens::<FROM_SERVER>(el, &self);
         (el.clone(), self)
     }
 
     fn build(self, el: &crate::renderer::types::Element) -> Self::State {
-        Rndr::set_attribute(el, "class", &self);
+        add_class_tokens(el, &self);
         (el.clone(), self)
     }
 
     fn rebuild(self, state: &mut Self::State) {
         let (el, prev) = state;
         if self != *prev {
-            Rndr::set_attribute(el, "class", &self);
+            replace_class_tokens(el, prev, &self);
         }
         *prev = self;
     }
 
+    fn remove(state: Self::State) {
+        let (el, prev) = state;
+        replace_class_tokens(&el, &prev, "");
+    }
+
     fn into_cloneable(self) -> Self::Cloneable {
         self.into()
     }
@@ -302,25 +386,28 @@ impl IntoClass for Arc<str> {
         self,
         el: &crate::renderer::types::Element,
     ) -> Self::State {
-        if !FROM_SERVER {
-            Rndr::set_attribute(el, "class", &self);
-        }
+        hydrate_class_tokens::<FROM_SERVER>(el, &self);
         (el.clone(), self)
     }
 
     fn build(self, el: &crate::renderer::types::Element) -> Self::State {
-        Rndr::set_attribute(el, "class", &self);
+        add_class_tokens(el, &self);
         (el.clone(), self)
     }
 
     fn rebuild(self, state: &mut Self::State) {
         let (el, prev) = state;
         if !Arc::ptr_eq(&self, prev) {
-            Rndr::set_attribute(el, "class", &self);
+            replace_class_tokens(el, prev, &self);
         }
         *prev = self;
     }
 
+    fn remove(state: Self::State) {
+        let (el, prev) = state;
+        replace_class_tokens(&el, &prev, "");
+    }
+
     fn into_cloneable(self) -> Self::Cloneable {
         self
     }
@@ -336,6 +423,65 @@ impl IntoClass for Arc<str> {
     }
 }
 
+/// A class value that may be absent. `None` is treated like an empty string, so rebuilding from
+/// `Some(_)` to `None` removes the classes that were previously added.
+impl<C> IntoClass for Option<C>
+where
+    C: IntoClass + Default,
+{
+    type AsyncOutput = C::AsyncOutput;
+    type State = C::State;
+    type Cloneable = C::Cloneable;
+    type CloneableOwned = C::CloneableOwned;
+
+    fn html_len(&self) -> usize {
+        self.as_ref().map(IntoClass::html_len).unwrap_or(0)
+    }
+
+    fn to_html(self, class: &mut String) {
+        if let Some(inner) = self {
+            inner.to_html(class);
+        }
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        el: &crate::renderer::types::Element,
+    ) -> Self::State {
+        self.unwrap_or_default().hydrate::<FROM_SERVER>(el)
+    }
+
+    fn build(self, el: &crate::renderer::types::Element) -> Self::State {
+        self.unwrap_or_default().build(el)
+    }
+
+    fn rebuild(self, state: &mut Self::State) {
+        self.unwrap_or_default().rebuild(state)
+    }
+
+    fn remove(state: Self::State) {
+        C::remove(state);
+    }
+
+    fn into_cloneable(self) -> Self::Cloneable {
+        self.unwrap_or_default().into_cloneable()
+    }
+
+    fn into_cloneable_owned(self) -> Self::CloneableOwned {
+        self.unwrap_or_default().into_cloneable_owned()
+    }
+
+    fn dry_resolve(&mut self) {
+        if let Some(inner) = self.as_mut() {
+            inner.dry_resolve();
+        }
+    }
+
+    async fn resolve(self) -> Self::AsyncOutput {
+        self.unwrap_or_default().resolve().await
+    }
+}
+
 impl IntoClass for (&'static str, bool) {
     type AsyncOutput = Self;
     type State = (crate::renderer::types::ClassList, bool);
@@ -430,7 +576,7 @@ impl<const V: &'static str> IntoClass for crate::view::static_types::Static<V> {
     }
 
     fn build(self, el: &crate::renderer::types::Element) -> Self::State {
-        Rndr::set_attribute(el, "class", V);
+        add_class_tokens(el, V);
     }
 
     fn rebuild(self, _state: &mut Self::State) {}
@@ -34,6 +34,11 @@ where
     fn tag(&self) -> &str {
         self.0.as_ref()
     }
+
+    // valid custom element names always contain a hyphen
+    fn is_custom_element(&self) -> bool {
+        self.0.as_ref().contains('-')
+    }
 }
 
 impl<E> ElementWithChildren for Custom<E> {}
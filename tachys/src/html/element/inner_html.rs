@@ -79,6 +79,10 @@ where
         self.value.rebuild(state);
     }
 
+    fn remove(el: &crate::renderer::types::Element, _state: Self::State) {
+        Rndr::set_inner_html(el, "");
+    }
+
     fn into_cloneable(self) -> Self::Cloneable {
         InnerHtml {
             value: self.value.into_cloneable(),
@@ -1,8 +1,8 @@
 use crate::{
     html::attribute::Attribute,
-    hydration::Cursor,
+    hydration::{Cursor, ExpectedNode},
     renderer::{CastFrom, Rndr},
-    ssr::StreamBuilder,
+    ssr::{BranchMarks, StreamBuilder},
     view::{
         add_attr::AddAnyAttr, IntoRender, Mountable, Position, PositionState,
         Render, RenderHtml, ToTemplate,
@@ -145,6 +145,13 @@ pub trait ElementType: Send {
 
     /// The element's tag.
     fn tag(&self) -> &str;
+
+    /// Whether this is a custom element. Attributes set on a custom element go through the
+    /// renderer's `set_attribute_or_property`, so that they are set as properties once the
+    /// element has been registered.
+    fn is_custom_element(&self) -> bool {
+        false
+    }
 }
 
 /// Denotes that the type that implements this has a particular HTML element type.
@@ -182,6 +189,9 @@ where
 
     fn build(self) -> Self::State {
         let el = Rndr::create_element(self.tag.tag(), E::NAMESPACE);
+        if self.tag.is_custom_element() {
+            Rndr::mark_custom_element(&el);
+        }
 
         let attrs = self.attributes.build(&el);
         let children = if E::SELF_CLOSING {
@@ -256,7 +266,7 @@ where
         buf: &mut String,
         position: &mut Position,
         _escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) {
         // opening tag
         buf.push('<');
@@ -293,7 +303,7 @@ where
         buffer: &mut StreamBuilder,
         position: &mut Position,
         _escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) where
         Self: Sized,
     {
@@ -348,8 +358,11 @@ where
         } else if curr_position != Position::Current {
             cursor.sibling();
         }
-        let el = crate::renderer::types::Element::cast_from(cursor.current())
-            .unwrap();
+        let el: crate::renderer::types::Element =
+            cursor.current_as(ExpectedNode::Element(E::TAG));
+        if self.tag.is_custom_element() {
+            Rndr::mark_custom_element(&el);
+        }
 
         let attrs = self.attributes.hydrate::<FROM_SERVER>(&el);
 
@@ -378,6 +391,52 @@ where
     }
 }
 
+/// Removes all but the last instance of any attribute that appears more than once in
+/// `buf[start..]`.
+///
+/// The same attribute can be added more than once, for example with a static value on an element
+/// and a reactive one forwarded from a component. On the client, the value that is applied last
+/// wins, so the server keeps the last one as well. (`class` and `style` are not affected, as they
+/// are merged and written separately.)
+fn dedup_attributes(buf: &mut String, start: usize) {
+    // each attribute is written as ` key` or ` key="escaped value"`
+    let mut attrs = Vec::new();
+    let rest = &buf[start..];
+    let mut idx = 0;
+    while idx < rest.len() {
+        let attr_start = idx;
+        let key_start = idx + 1;
+        let key_end = rest[key_start..]
+            .find([' ', '='])
+            .map(|n| key_start + n)
+            .unwrap_or(rest.len());
+        idx = key_end;
+        if rest[idx..].starts_with("=\"") {
+            idx = rest[idx + 2..]
+                .find('"')
+                .map(|n| idx + 2 + n + 1)
+                .unwrap_or(rest.len());
+        }
+        attrs.push((&rest[key_start..key_end], attr_start..idx));
+    }
+
+    let is_shadowed = |(n, (key, _)): &(usize, &(&str, _))| {
+        attrs[n + 1..].iter().any(|(later, _)| later == key)
+    };
+    if !attrs.iter().enumerate().any(|attr| is_shadowed(&attr)) {
+        return;
+    }
+
+    let deduped = attrs
+        .iter()
+        .enumerate()
+        .filter(|attr| !is_shadowed(attr))
+        .map(|(_, (_, range))| &rest[range.clone()])
+        .collect::<String>();
+    buf.truncate(start);
+    buf.push_str(&deduped);
+}
+
 /// Renders an [`Attribute`] (which can be one or more HTML attributes) into an HTML buffer.
 pub fn attributes_to_html<At>(attr: At, buf: &mut String) -> String
 where
@@ -397,7 +456,9 @@ where
     let mut inner_html = String::new();
 
     // inject regular attributes, and fill class and style
+    let start = buf.len();
     attr.to_html(buf, &mut class, &mut style, &mut inner_html);
+    dedup_attributes(buf, start);
 
     if !class.is_empty() {
         buf.push(' ');
@@ -543,63 +604,38 @@ where
         }
     }
 }
-/*
 #[cfg(all(test, feature = "testing"))]
 mod tests {
-    #[cfg(feature = "nightly")]
-    use super::RenderHtml;
-    use super::{main, p, HtmlElement};
+    use super::{main, p};
     use crate::{
         html::{
             attribute::global::GlobalAttributes,
-            element::{em, ElementChild, Main},
+            element::{em, ElementChild},
         },
-        renderer::mock_dom::MockDom,
         view::Render,
     };
 
     #[test]
     fn mock_dom_creates_element() {
-        let el: HtmlElement<Main, _, _, MockDom> =
-            main().child(p().id("test").lang("en").child("Hello, world!"));
+        let el = main().child(p().id("test").lang("en").child("Hello, world!"));
         let el = el.build();
         assert_eq!(
-            el.el.to_debug_html(),
+            el.to_debug_html(),
             "<main><p id=\"test\" lang=\"en\">Hello, world!</p></main>"
         );
     }
 
     #[test]
     fn mock_dom_creates_element_with_several_children() {
-        let el: HtmlElement<Main, _, _, MockDom> = main().child(p().child((
+        let el = main().child(p().child((
             "Hello, ",
             em().child("beautiful"),
             " world!",
         )));
         let el = el.build();
         assert_eq!(
-            el.el.to_debug_html(),
-            "<main><p>Hello, <em>beautiful</em> world!</p></main>"
-        );
-    }
-
-    #[cfg(feature = "nightly")]
-    #[test]
-    fn html_render_allocates_appropriate_buffer() {
-        use crate::view::static_types::Static;
-
-        let el: HtmlElement<Main, _, _, MockDom> = main().child(p().child((
-            Static::<"Hello, ">,
-            em().child(Static::<"beautiful">),
-            Static::<" world!">,
-        )));
-        let allocated_len = el.html_len();
-        let html = el.to_html();
-        assert_eq!(
-            html,
+            el.to_debug_html(),
             "<main><p>Hello, <em>beautiful</em> world!</p></main>"
         );
-        assert_eq!(html.len(), allocated_len);
     }
 }
- */
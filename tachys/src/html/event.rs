@@ -201,6 +201,19 @@ where
     }
 }
 
+impl<E, F> On<E, F> {
+    /// Attaches this event listener directly to the element, rather than using event delegation.
+    ///
+    /// The choice is part of the event type, so it is kept when the listener is converted with
+    /// [`Attribute::into_cloneable_owned`], for example when it is spread onto another view.
+    pub fn undelegated(self) -> On<Undelegated<E>, F> {
+        On {
+            event: Undelegated(self.event),
+            cb: self.cb,
+        }
+    }
+}
+
 impl<E, F> Debug for On<E, F>
 where
     E: Debug,
@@ -267,6 +280,13 @@ where
         *prev_cleanup = Some(self.attach(el));
     }
 
+    fn remove(_el: &crate::renderer::types::Element, state: Self::State) {
+        let (el, cleanup) = state;
+        if let Some(cleanup) = cleanup {
+            (cleanup.into_inner())(&el);
+        }
+    }
+
     fn into_cloneable(self) -> Self::Cloneable {
         On {
             cb: self.cb.map(|cb| SendWrapper::new(cb.take().into_shared())),
@@ -350,6 +370,40 @@ pub trait EventDescriptor: Clone {
     }
 }
 
+/// Creates an event type that is always listened for directly on the element, rather than
+/// being delegated to the document, even if it bubbles.
+///
+/// This can be useful when an event is stopped (or needs to be handled) before it would reach the
+/// delegated listener, like an event inside a shadow root.
+pub fn undelegated<E: EventDescriptor>(event: E) -> Undelegated<E> {
+    Undelegated(event)
+}
+
+/// An event type that is listened for directly on the element rather than delegated.
+///
+/// This is created with [`undelegated`] or [`On::undelegated`].
+#[derive(Debug, Clone, Copy)]
+pub struct Undelegated<E>(E);
+
+impl<E: EventDescriptor> EventDescriptor for Undelegated<E> {
+    type EventType = E::EventType;
+
+    const BUBBLES: bool = false;
+
+    fn name(&self) -> Cow<'static, str> {
+        self.0.name()
+    }
+
+    fn event_delegation_key(&self) -> Cow<'static, str> {
+        self.0.event_delegation_key()
+    }
+
+    #[inline(always)]
+    fn options(&self) -> Option<&web_sys::AddEventListenerOptions> {
+        self.0.options()
+    }
+}
+
 /// A custom event.
 #[derive(Debug)]
 pub struct Custom<E: FromWasmAbi = web_sys::Event> {
@@ -2,7 +2,7 @@ use super::attribute::Attribute;
 use crate::{
     hydration::Cursor,
     prelude::{Render, RenderHtml},
-    ssr::StreamBuilder,
+    ssr::{BranchMarks, StreamBuilder},
     view::{add_attr::AddAnyAttr, Position, PositionState},
 };
 
@@ -129,7 +129,7 @@ where
         buf: &mut String,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) {
         Self::open_tag(self.component, &self.props_json, buf);
         self.view
@@ -142,7 +142,7 @@ where
         buf: &mut StreamBuilder,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) where
         Self: Sized,
     {
@@ -263,7 +263,7 @@ where
         buf: &mut String,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) {
         Self::open_tag(buf);
         self.view
@@ -276,7 +276,7 @@ where
         buf: &mut StreamBuilder,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) where
         Self: Sized,
     {
@@ -1,9 +1,10 @@
 use self::attribute::Attribute;
 use crate::{
-    hydration::Cursor,
+    hydration::{Cursor, ExpectedNode},
     no_attrs,
     prelude::AddAnyAttr,
-    renderer::{CastFrom, Rndr},
+    renderer::Rndr,
+    ssr::BranchMarks,
     view::{Position, PositionState, Render, RenderHtml},
 };
 use std::borrow::Cow;
@@ -63,7 +64,7 @@ impl RenderHtml for Doctype {
         buf: &mut String,
         _position: &mut Position,
         _escape: bool,
-        _mark_branches: bool,
+        _mark_branches: &BranchMarks,
     ) {
         buf.push_str("<!DOCTYPE ");
         buf.push_str(self.value);
@@ -137,7 +138,7 @@ impl RenderHtml for InertElement {
         buf: &mut String,
         position: &mut Position,
         _escape: bool,
-        _mark_branches: bool,
+        _mark_branches: &BranchMarks,
     ) {
         buf.push_str(&self.html);
         *position = Position::NextChild;
@@ -154,8 +155,7 @@ impl RenderHtml for InertElement {
         } else if curr_position != Position::Current {
             cursor.sibling();
         }
-        let el = crate::renderer::types::Element::cast_from(cursor.current())
-            .unwrap();
+        let el = cursor.current_as(ExpectedNode::Element(""));
         position.set(Position::NextChild);
         el
     }
@@ -49,7 +49,7 @@ where
     const MIN_LENGTH: usize = 0;
 
     type AsyncOutput = Self;
-    type State = P::State;
+    type State = (K, P::State);
     type Cloneable = Property<Arc<str>, P::Cloneable>;
     type CloneableOwned = Property<Arc<str>, P::CloneableOwned>;
 
@@ -71,24 +71,32 @@ where
         self,
         el: &crate::renderer::types::Element,
     ) -> Self::State {
-        self.value
+        let state = self
+            .value
             .expect("property removed early")
             .take()
-            .hydrate::<FROM_SERVER>(el, self.key.as_ref())
+            .hydrate::<FROM_SERVER>(el, self.key.as_ref());
+        (self.key, state)
     }
 
     fn build(self, el: &crate::renderer::types::Element) -> Self::State {
-        self.value
+        let state = self
+            .value
             .expect("property removed early")
             .take()
-            .build(el, self.key.as_ref())
+            .build(el, self.key.as_ref());
+        (self.key, state)
     }
 
     fn rebuild(self, state: &mut Self::State) {
         self.value
             .expect("property removed early")
             .take()
-            .rebuild(state, self.key.as_ref())
+            .rebuild(&mut state.1, self.key.as_ref())
+    }
+
+    fn remove(el: &crate::renderer::types::Element, state: Self::State) {
+        Rndr::set_property(el, state.0.as_ref(), &JsValue::UNDEFINED);
     }
 
     fn into_cloneable(self) -> Self::Cloneable {
@@ -486,3 +494,144 @@ prop_type_str!(String);
 prop_type_str!(&String);
 prop_type_str!(&str);
 prop_type_str!(Cow<'_, str>);
+
+/// A DOM property value that can be converted into any [`JsValue`].
+///
+/// This allows setting properties like `valueAsNumber`, `files`, or the object-valued properties
+/// of a custom element, which don't have their own [`IntoProperty`] implementation:
+/// ```rust,ignore
+/// my_element().add_any_attr(prop("files", move || JsProperty(files.get())))
+/// ```
+/// Wrapping an `Option` sets the property to `undefined` when it is `None`.
+#[derive(Debug, Clone)]
+pub struct JsProperty<T>(pub T);
+
+impl<T> IntoProperty for JsProperty<T>
+where
+    T: Into<JsValue> + Clone + 'static,
+{
+    type State = (crate::renderer::types::Element, JsValue);
+    type Cloneable = Self;
+    type CloneableOwned = Self;
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        el: &crate::renderer::types::Element,
+        key: &str,
+    ) -> Self::State {
+        self.build(el, key)
+    }
+
+    fn build(
+        self,
+        el: &crate::renderer::types::Element,
+        key: &str,
+    ) -> Self::State {
+        let value = self.0.into();
+        if !value.is_undefined() {
+            Rndr::set_property(el, key, &value);
+        }
+        (el.clone(), value)
+    }
+
+    fn rebuild(self, state: &mut Self::State, key: &str) {
+        let (el, prev) = state;
+        let value = self.0.into();
+        Rndr::set_property(el, key, &value);
+        *prev = value;
+    }
+
+    fn into_cloneable(self) -> Self::Cloneable {
+        self
+    }
+
+    fn into_cloneable_owned(self) -> Self::CloneableOwned {
+        self
+    }
+}
+
+/// Creates a DOM property value by serializing `value` with `serde_wasm_bindgen`.
+///
+/// ```rust,ignore
+/// my_chart().add_any_attr(prop("config", move || prop_serde(config.get())))
+/// ```
+/// `None` serializes to `undefined`, so an `Option` can be used to clear the property.
+#[cfg(feature = "serde")]
+pub fn prop_serde<T>(value: T) -> SerdeProperty<T>
+where
+    T: serde::Serialize,
+{
+    SerdeProperty(value)
+}
+
+/// A DOM property value that is serialized into a [`JsValue`] with `serde_wasm_bindgen`.
+///
+/// This is created with [`prop_serde`].
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone)]
+pub struct SerdeProperty<T>(T);
+
+#[cfg(feature = "serde")]
+impl<T> SerdeProperty<T>
+where
+    T: serde::Serialize,
+{
+    fn to_js(&self) -> JsValue {
+        match serde_wasm_bindgen::to_value(&self.0) {
+            Ok(value) => value,
+            Err(err) => {
+                if cfg!(debug_assertions) {
+                    web_sys::console::warn_1(&JsValue::from_str(&format!(
+                        "[WARNING] Could not serialize property value: {err}"
+                    )));
+                }
+                JsValue::UNDEFINED
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> IntoProperty for SerdeProperty<T>
+where
+    T: serde::Serialize + Clone + 'static,
+{
+    type State = (crate::renderer::types::Element, JsValue);
+    type Cloneable = Self;
+    type CloneableOwned = Self;
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        el: &crate::renderer::types::Element,
+        key: &str,
+    ) -> Self::State {
+        self.build(el, key)
+    }
+
+    fn build(
+        self,
+        el: &crate::renderer::types::Element,
+        key: &str,
+    ) -> Self::State {
+        let value = self.to_js();
+        if !value.is_undefined() {
+            Rndr::set_property(el, key, &value);
+        }
+        (el.clone(), value)
+    }
+
+    fn rebuild(self, state: &mut Self::State, key: &str) {
+        let (el, prev) = state;
+        let value = self.to_js();
+        Rndr::set_property(el, key, &value);
+        *prev = value;
+    }
+
+    fn into_cloneable(self) -> Self::Cloneable {
+        self
+    }
+
+    fn into_cloneable_owned(self) -> Self::CloneableOwned {
+        self
+    }
+}
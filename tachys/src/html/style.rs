@@ -5,7 +5,12 @@ use crate::{
     renderer::Rndr,
     view::{Position, ToTemplate},
 };
-use std::{future::Future, sync::Arc};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+    future::Future,
+    sync::Arc,
+};
 
 /// Returns an [`Attribute`] that will add to an element's CSS styles.
 #[inline(always)]
@@ -75,6 +80,10 @@ where
         self.style.rebuild(state)
     }
 
+    fn remove(_el: &crate::renderer::types::Element, state: Self::State) {
+        S::remove(state);
+    }
+
     fn into_cloneable(self) -> Self::Cloneable {
         Style {
             style: self.style.into_cloneable(),
@@ -116,20 +125,28 @@ impl<S> ToTemplate for Style<S>
 where
     S: IntoStyle,
 {
+    const STYLE: &'static str = S::TEMPLATE;
+
     fn to_template(
         _buf: &mut String,
-        _style: &mut String,
         _class: &mut String,
+        style: &mut String,
         _inner_html: &mut String,
         _position: &mut Position,
     ) {
-        // TODO: should there be some templating for static styles?
+        S::to_template(style);
     }
 }
 
 /// Any type that can be added to the `style` attribute or set as a style in
 /// the [`CssStyleDeclaration`]. This could be a plain string, or a property name-value pair.
 pub trait IntoStyle: Send {
+    /// The declarations that should be included in a `<template>`.
+    ///
+    /// Only styles that are known at compile time have a template. Dynamic styles are applied on
+    /// top of these when the template is hydrated, so the static declarations always come first.
+    const TEMPLATE: &'static str = "";
+
     /// The type after all async data have resolved.
     type AsyncOutput: IntoStyle;
     /// The view state retained between building and rebuilding.
@@ -142,6 +159,10 @@ pub trait IntoStyle: Send {
     /// Renders the style to HTML.
     fn to_html(self, style: &mut String);
 
+    /// Renders the style to HTML for a `<template>`.
+    #[allow(unused)] // it's used with `nightly` feature
+    fn to_template(style: &mut String) {}
+
     /// Adds interactivity as necessary, given DOM nodes that were created from HTML that has
     /// either been rendered on the server, or cloned for a `<template>`.
     fn hydrate<const FROM_SERVER: bool>(
@@ -155,6 +176,12 @@ pub trait IntoStyle: Send {
     /// Updates the value.
     fn rebuild(self, state: &mut Self::State);
 
+    /// Removes the declarations this value added, given its state.
+    ///
+    /// By default, the element is left as it is.
+    #[allow(unused_variables)]
+    fn remove(state: Self::State) {}
+
     /// Converts this to a cloneable type.
     fn into_cloneable(self) -> Self::Cloneable;
 
@@ -170,6 +197,110 @@ pub trait IntoStyle: Send {
     fn resolve(self) -> impl Future<Output = Self::AsyncOutput> + Send;
 }
 
+/// A value for a single CSS property, as used in `style:name=value` bindings.
+///
+/// Numbers are used as-is, which suits properties like `opacity` or `z-index`. Use [`Px`] for
+/// lengths in pixels.
+pub trait IntoStyleValue {
+    /// Converts this into the value of the property, or `None` if the property should be removed.
+    fn into_style_value(self) -> Option<Cow<'static, str>>;
+}
+
+impl IntoStyleValue for &'static str {
+    fn into_style_value(self) -> Option<Cow<'static, str>> {
+        Some(self.into())
+    }
+}
+
+impl IntoStyleValue for String {
+    fn into_style_value(self) -> Option<Cow<'static, str>> {
+        Some(self.into())
+    }
+}
+
+impl IntoStyleValue for Cow<'static, str> {
+    fn into_style_value(self) -> Option<Cow<'static, str>> {
+        Some(self)
+    }
+}
+
+impl IntoStyleValue for Arc<str> {
+    fn into_style_value(self) -> Option<Cow<'static, str>> {
+        Some(self.to_string().into())
+    }
+}
+
+impl<T> IntoStyleValue for Option<T>
+where
+    T: IntoStyleValue,
+{
+    fn into_style_value(self) -> Option<Cow<'static, str>> {
+        self.and_then(IntoStyleValue::into_style_value)
+    }
+}
+
+/// A length in pixels, which is used as a CSS value with a `px` unit.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct Px<T>(pub T);
+
+macro_rules! style_value_number {
+    ($($ty:ty),*) => {
+        $(
+            impl IntoStyleValue for $ty {
+                fn into_style_value(self) -> Option<Cow<'static, str>> {
+                    Some(self.to_string().into())
+                }
+            }
+
+            impl IntoStyleValue for Px<$ty> {
+                fn into_style_value(self) -> Option<Cow<'static, str>> {
+                    Some(format!("{}px", self.0).into())
+                }
+            }
+        )*
+    };
+}
+
+style_value_number!(
+    i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64
+);
+
+/// Splits a `style` attribute string into its `name: value` declarations.
+fn style_declarations(value: &str) -> impl Iterator<Item = (&str, &str)> {
+    value.split(';').filter_map(|decl| {
+        let (name, value) = decl.split_once(':')?;
+        let name = name.trim();
+        (!name.is_empty()).then(|| (name, value.trim()))
+    })
+}
+
+/// Sets each declaration in a `style` attribute string as an individual CSS property.
+///
+/// Unlike setting the `style` attribute itself, this keeps the properties set by other style
+/// bindings on the same element.
+pub(crate) fn add_style_declarations(
+    el: &crate::renderer::types::Element,
+    value: &str,
+) {
+    let set = style_declarations(value).collect::<Vec<_>>();
+    Rndr::set_style_properties(el, &set, &[]);
+}
+
+/// Removes the properties declared in `prev` that are not declared in `next`, and sets those in
+/// `next`.
+pub(crate) fn replace_style_declarations(
+    el: &crate::renderer::types::Element,
+    prev: &str,
+    next: &str,
+) {
+    let remove = style_declarations(prev)
+        .map(|(name, _)| name)
+        .filter(|name| !style_declarations(next).any(|(next, _)| next == *name))
+        .collect::<Vec<_>>();
+    let set = style_declarations(next).collect::<Vec<_>>();
+    Rndr::set_style_properties(el, &set, &remove);
+}
+
 impl<'a> IntoStyle for &'a str {
     type AsyncOutput = Self;
     type State = (crate::renderer::types::Element, &'a str);
@@ -189,18 +320,23 @@ impl<'a> IntoStyle for &'a str {
     }
 
     fn build(self, el: &crate::renderer::types::Element) -> Self::State {
-        Rndr::set_attribute(el, "style", self);
+        add_style_declarations(el, self);
         (el.clone(), self)
     }
 
     fn rebuild(self, state: &mut Self::State) {
         let (el, prev) = state;
         if self != *prev {
-            Rndr::set_attribute(el, "style", self);
+            replace_style_declarations(el, prev, self);
         }
         *prev = self;
     }
 
+    fn remove(state: Self::State) {
+        let (el, prev) = state;
+        replace_style_declarations(&el, prev, "");
+    }
+
     fn into_cloneable(self) -> Self::Cloneable {
         self
     }
@@ -235,18 +371,23 @@ impl IntoStyle for Arc<str> {
     }
 
     fn build(self, el: &crate::renderer::types::Element) -> Self::State {
-        Rndr::set_attribute(el, "style", &self);
+        add_style_declarations(el, &self);
         (el.clone(), self)
     }
 
     fn rebuild(self, state: &mut Self::State) {
         let (el, prev) = state;
         if self != *prev {
-            Rndr::set_attribute(el, "style", &self);
+            replace_style_declarations(el, prev, &self);
         }
         *prev = self;
     }
 
+    fn remove(state: Self::State) {
+        let (el, prev) = state;
+        replace_style_declarations(&el, &prev, "");
+    }
+
     fn into_cloneable(self) -> Self::Cloneable {
         self
     }
@@ -281,18 +422,23 @@ impl IntoStyle for String {
     }
 
     fn build(self, el: &crate::renderer::types::Element) -> Self::State {
-        Rndr::set_attribute(el, "style", &self);
+        add_style_declarations(el, &self);
         (el.clone(), self)
     }
 
     fn rebuild(self, state: &mut Self::State) {
         let (el, prev) = state;
         if self != *prev {
-            Rndr::set_attribute(el, "style", &self);
+            replace_style_declarations(el, prev, &self);
         }
         *prev = self;
     }
 
+    fn remove(state: Self::State) {
+        let (el, prev) = state;
+        replace_style_declarations(&el, &prev, "");
+    }
+
     fn into_cloneable(self) -> Self::Cloneable {
         self.into()
     }
@@ -555,8 +701,198 @@ impl<const V: &'static str> IntoStyle for (Arc<str>, Static<V>) {
     }
 }
 
+/// A list of styles, each of which is bound separately.
+///
+/// This allows a set of reactive properties to be passed as a single `style` value, while each
+/// one still updates only its own property:
+/// ```rust,ignore
+/// div().style(vec![("left", left), ("top", top)])
+/// ```
+/// When the list is rebuilt, each style is rebuilt against the one in the same position.
+impl<S> IntoStyle for Vec<S>
+where
+    S: IntoStyle,
+{
+    type AsyncOutput = Vec<S::AsyncOutput>;
+    type State = (crate::renderer::types::Element, Vec<S::State>);
+    type Cloneable = Vec<S::Cloneable>;
+    type CloneableOwned = Vec<S::CloneableOwned>;
+
+    fn to_html(self, style: &mut String) {
+        for item in self {
+            item.to_html(style);
+        }
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        el: &crate::renderer::types::Element,
+    ) -> Self::State {
+        let states = self
+            .into_iter()
+            .map(|item| item.hydrate::<FROM_SERVER>(el))
+            .collect();
+        (el.clone(), states)
+    }
+
+    fn build(self, el: &crate::renderer::types::Element) -> Self::State {
+        let states = self.into_iter().map(|item| item.build(el)).collect();
+        (el.clone(), states)
+    }
+
+    fn rebuild(self, state: &mut Self::State) {
+        let (el, states) = state;
+        let len = self.len();
+        for (idx, item) in self.into_iter().enumerate() {
+            match states.get_mut(idx) {
+                Some(state) => item.rebuild(state),
+                None => states.push(item.build(el)),
+            }
+        }
+        states.truncate(len);
+    }
+
+    fn into_cloneable(self) -> Self::Cloneable {
+        self.into_iter().map(IntoStyle::into_cloneable).collect()
+    }
+
+    fn into_cloneable_owned(self) -> Self::CloneableOwned {
+        self.into_iter()
+            .map(IntoStyle::into_cloneable_owned)
+            .collect()
+    }
+
+    fn dry_resolve(&mut self) {
+        for item in self.iter_mut() {
+            item.dry_resolve();
+        }
+    }
+
+    async fn resolve(self) -> Self::AsyncOutput {
+        let mut resolved = Vec::with_capacity(self.len());
+        for item in self {
+            resolved.push(item.resolve().await);
+        }
+        resolved
+    }
+}
+
+/// A map of CSS property names to values, in the owned form used by map styles.
+type StyleMap = BTreeMap<Arc<str>, Option<Cow<'static, str>>>;
+
+/// Converts a map of properties into its owned, sorted form.
+fn style_map<K, V>(entries: impl IntoIterator<Item = (K, V)>) -> StyleMap
+where
+    K: AsRef<str>,
+    V: IntoStyleValue,
+{
+    entries
+        .into_iter()
+        .map(|(name, value)| (name.as_ref().into(), value.into_style_value()))
+        .collect()
+}
+
+/// Sets each property in `map`, and removes those in `prev` that it no longer contains.
+fn update_style_map(
+    el: &crate::renderer::types::Element,
+    prev: &StyleMap,
+    map: &StyleMap,
+) {
+    let remove = prev
+        .iter()
+        .filter(|(name, value)| {
+            value.is_some() && map.get(*name).map_or(true, Option::is_none)
+        })
+        .map(|(name, _)| &**name)
+        .collect::<Vec<_>>();
+    let set = map
+        .iter()
+        .filter_map(|(name, value)| {
+            let value = value.as_ref()?;
+            (prev.get(name) != Some(&Some(value.clone())))
+                .then(|| (&**name, &**value))
+        })
+        .collect::<Vec<_>>();
+    Rndr::set_style_properties(el, &set, &remove);
+}
+
+macro_rules! style_map_impl {
+    ($ty:ident, [$($gen:ident),*], [$($bounds:tt)*]) => {
+        /// A map of CSS property names to values.
+        ///
+        /// Properties are rendered in order of their names, so that the same map always produces
+        /// the same HTML. On the client, when the map is rebuilt, only the properties that have
+        /// changed are set, and those that are no longer present (or are `None`) are removed.
+        impl<K, V, $($gen),*> IntoStyle for $ty<K, V, $($gen),*>
+        where
+            K: AsRef<str> + Send,
+            V: IntoStyleValue + Send,
+            $($bounds)*
+        {
+            type AsyncOutput = Self;
+            type State = (crate::renderer::types::Element, StyleMap);
+            type Cloneable = StyleMap;
+            type CloneableOwned = StyleMap;
+
+            fn to_html(self, style: &mut String) {
+                for (name, value) in style_map(self) {
+                    if let Some(value) = value {
+                        style.push_str(&name);
+                        style.push(':');
+                        style.push_str(&value);
+                        style.push(';');
+                    }
+                }
+            }
+
+            fn hydrate<const FROM_SERVER: bool>(
+                self,
+                el: &crate::renderer::types::Element,
+            ) -> Self::State {
+                let map = style_map(self);
+                if !FROM_SERVER {
+                    update_style_map(el, &StyleMap::new(), &map);
+                }
+                (el.clone(), map)
+            }
+
+            fn build(self, el: &crate::renderer::types::Element) -> Self::State {
+                let map = style_map(self);
+                update_style_map(el, &StyleMap::new(), &map);
+                (el.clone(), map)
+            }
+
+            fn rebuild(self, state: &mut Self::State) {
+                let (el, prev) = state;
+                let map = style_map(self);
+                update_style_map(el, prev, &map);
+                *prev = map;
+            }
+
+            fn into_cloneable(self) -> Self::Cloneable {
+                style_map(self)
+            }
+
+            fn into_cloneable_owned(self) -> Self::CloneableOwned {
+                style_map(self)
+            }
+
+            fn dry_resolve(&mut self) {}
+
+            async fn resolve(self) -> Self::AsyncOutput {
+                self
+            }
+        }
+    };
+}
+
+style_map_impl!(BTreeMap, [], []);
+style_map_impl!(HashMap, [H], [H: Send]);
+
 #[cfg(feature = "nightly")]
 impl<const V: &'static str> IntoStyle for crate::view::static_types::Static<V> {
+    const TEMPLATE: &'static str = V;
+
     type AsyncOutput = Self;
     type State = ();
     type Cloneable = Self;
@@ -567,6 +903,11 @@ impl<const V: &'static str> IntoStyle for crate::view::static_types::Static<V> {
         style.push(';');
     }
 
+    fn to_template(style: &mut String) {
+        style.push_str(V);
+        style.push(';');
+    }
+
     fn hydrate<const FROM_SERVER: bool>(
         self,
         _el: &crate::renderer::types::Element,
@@ -574,7 +915,7 @@ impl<const V: &'static str> IntoStyle for crate::view::static_types::Static<V> {
     }
 
     fn build(self, el: &crate::renderer::types::Element) -> Self::State {
-        Rndr::set_attribute(el, "style", V);
+        add_style_declarations(el, V);
     }
 
     fn rebuild(self, _state: &mut Self::State) {}
@@ -1,20 +1,65 @@
 use crate::{
-    renderer::{CastFrom, Rndr},
+    renderer::{types::Node, CastFrom, Rndr},
+    ssr::BranchMarks,
     view::{Position, PositionState},
 };
-use std::{cell::RefCell, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    error::Error,
+    fmt,
+    panic::Location,
+    rc::Rc,
+};
 
 /// Hydration works by walking over the DOM, adding interactivity as needed.
 ///
 /// This cursor tracks the location in the DOM that is currently being hydrated. Each that type
 /// implements [`RenderHtml`](crate::view::RenderHtml) knows how to advance the cursor to access
 /// the nodes it needs.
+///
+/// In debug builds, or with the `hydration-diagnostics` feature, a view that finds a different
+/// node from the one it expects reports a [`HydrationError`] describing where hydration went off
+/// course, rather than a generic panic.
 #[derive(Debug)]
-pub struct Cursor(Rc<RefCell<crate::renderer::types::Node>>);
+pub struct Cursor {
+    node: Rc<RefCell<Node>>,
+    // whether the last move failed because there was no child or sibling to move to
+    exhausted: Rc<Cell<bool>>,
+    lenient: Option<LenientHydration>,
+    // the branches the cursor is in, innermost last, if the HTML has branch markers
+    branches: Option<Rc<RefCell<Vec<BranchMarks>>>>,
+}
 
 impl Clone for Cursor {
     fn clone(&self) -> Self {
-        Self(Rc::clone(&self.0))
+        Self {
+            node: Rc::clone(&self.node),
+            exhausted: Rc::clone(&self.exhausted),
+            lenient: self.lenient.clone(),
+            branches: self.branches.clone(),
+        }
+    }
+}
+
+/// A location saved by [`Cursor::checkpoint`], which the cursor can be moved back to with
+/// [`Cursor::restore`].
+#[derive(Debug, Clone)]
+pub struct CursorCheckpoint {
+    node: Node,
+    exhausted: bool,
+    position: Position,
+    branches: Option<Vec<BranchMarks>>,
+}
+
+impl CursorCheckpoint {
+    /// The node at which the cursor was located.
+    pub fn node(&self) -> &Node {
+        &self.node
+    }
+
+    /// The position of the view at the checkpoint.
+    pub fn position(&self) -> Position {
+        self.position
     }
 }
 
@@ -28,12 +73,177 @@ where
             crate::renderer::types::Node,
         >>::as_ref(&root)
         .clone();
-        Self(Rc::new(RefCell::new(root)))
+        Self {
+            node: Rc::new(RefCell::new(root)),
+            exhausted: Default::default(),
+            lenient: None,
+            branches: None,
+        }
+    }
+
+    /// Lets hydration skip over nodes that were added to the page after it was rendered on the
+    /// server. This is off by default.
+    pub fn with_lenient(mut self, lenient: LenientHydration) -> Self {
+        self.lenient = Some(lenient);
+        self
+    }
+
+    /// Hydrates HTML that was rendered with the given branch markers, for example by
+    /// [`RenderHtml::to_html_with_branch_marks`](crate::view::RenderHtml::to_html_with_branch_marks).
+    ///
+    /// Each branch then moves past the comments that mark its start and end, and a branch whose
+    /// HTML is not where it is expected is looked up in the document by its ID.
+    pub fn with_branch_marks(mut self, marks: BranchMarks) -> Self {
+        self.branches = marks
+            .is_enabled()
+            .then(|| Rc::new(RefCell::new(vec![marks])));
+        self
+    }
+
+    /// Whether the HTML being hydrated has branch markers.
+    pub fn marks_branches(&self) -> bool {
+        self.branches.is_some()
+    }
+
+    /// Enters a branch with the given local ID, moving to the comment that marks its start.
+    ///
+    /// Does nothing unless the cursor was created [`with_branch_marks`](Cursor::with_branch_marks).
+    pub fn open_branch(&self, local_id: &str, position: &PositionState) {
+        let Some(branches) = &self.branches else {
+            return;
+        };
+        let branch = branches
+            .borrow()
+            .last()
+            .map(|marks| marks.enter(local_id))
+            .unwrap_or_default();
+        let id = branch.id();
+        if position.get() == Position::FirstChild {
+            self.child();
+        } else {
+            self.sibling();
+        }
+        let opening = format!("bo-{id}");
+        if !self.is_comment(&opening) {
+            // the branch was moved, for example because it arrived out of order
+            if let Some(node) = self.find_branch(&id) {
+                self.set(node);
+            }
+        }
+        position.set(Position::NextChild);
+        branches.borrow_mut().push(branch);
+    }
+
+    /// Leaves the branch that was entered last, moving to the comment that marks its end.
+    ///
+    /// Does nothing unless the cursor was created [`with_branch_marks`](Cursor::with_branch_marks).
+    pub fn close_branch(&self, position: &PositionState) {
+        let Some(branches) = &self.branches else {
+            return;
+        };
+        let Some(branch) = branches.borrow_mut().pop() else {
+            return;
+        };
+        if position.get() == Position::FirstChild {
+            self.child();
+        } else {
+            self.sibling();
+        }
+        let closing = format!("bc-{}", branch.id());
+        if !self.is_comment(&closing) {
+            if let Some(node) = self.find_comment(&closing) {
+                self.set(node);
+            }
+        }
+        position.set(Position::NextChild);
+    }
+
+    /// If the branch with the given local ID was rendered where the cursor will move next, moves
+    /// past the whole branch without hydrating it, and returns a checkpoint from which it can be
+    /// hydrated later.
+    ///
+    /// Returns `None`, without moving, if a different branch was rendered there, or unless the
+    /// cursor was created [`with_branch_marks`](Cursor::with_branch_marks).
+    pub fn skip_branch(
+        &self,
+        local_id: &str,
+        position: &PositionState,
+    ) -> Option<CursorCheckpoint> {
+        let branches = self.branches.as_ref()?;
+        let id = branches.borrow().last()?.enter(local_id).id().into_owned();
+        let next = if position.get() == Position::FirstChild {
+            Rndr::first_child(&self.current())
+        } else {
+            Rndr::next_sibling(&self.current())
+        }?;
+        // the markers around a branch are siblings, and the same branch can be nested in itself
+        let opening = format!("bo-{id}");
+        let closing = format!("bc-{id}");
+        if Rndr::comment_text(&next).as_deref() != Some(opening.as_str()) {
+            return None;
+        }
+        let mut depth = 0;
+        let mut node = next;
+        loop {
+            match Rndr::comment_text(&node) {
+                Some(text) if text == opening => depth += 1,
+                Some(text) if text == closing => depth -= 1,
+                _ => {}
+            }
+            if depth == 0 {
+                break;
+            }
+            node = Rndr::next_sibling(&node)?;
+        }
+        let checkpoint = self.checkpoint(position);
+        self.set(node);
+        position.set(Position::NextChild);
+        Some(checkpoint)
+    }
+
+    /// Finds the comment that marks the start of the branch with the given ID, anywhere in the
+    /// document the cursor is in.
+    pub fn find_branch(&self, id: &str) -> Option<Node> {
+        self.find_comment(&format!("bo-{id}"))
+    }
+
+    fn is_comment(&self, text: &str) -> bool {
+        Rndr::comment_text(&self.current()).as_deref() == Some(text)
+    }
+
+    // searches the whole document, in document order, for a comment with this text
+    fn find_comment(&self, text: &str) -> Option<Node> {
+        let mut root = self.current();
+        while let Some(parent) = Rndr::get_parent(&root) {
+            root = parent;
+        }
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            if Rndr::comment_text(&node).as_deref() == Some(text) {
+                return Some(node);
+            }
+            let mut children = Vec::new();
+            let mut child = Rndr::first_child(&node);
+            while let Some(next) = child {
+                child = Rndr::next_sibling(&next);
+                children.push(next);
+            }
+            stack.extend(children.into_iter().rev());
+        }
+        None
+    }
+
+    fn set_exhausted(&self, exhausted: bool) {
+        self.exhausted.set(exhausted);
+    }
+
+    fn is_exhausted(&self) -> bool {
+        self.exhausted.get()
     }
 
     /// Returns the node at which the cursor is currently located.
     pub fn current(&self) -> crate::renderer::types::Node {
-        self.0.borrow().clone()
+        self.node.borrow().clone()
     }
 
     /// Advances to the next child of the node at which the cursor is located.
@@ -42,9 +252,12 @@ where
     pub fn child(&self) {
         //crate::log("advancing to next child of ");
         //Rndr::log_node(&self.current());
-        let mut inner = self.0.borrow_mut();
+        let mut inner = self.node.borrow_mut();
         if let Some(node) = Rndr::first_child(&inner) {
             *inner = node;
+            self.set_exhausted(false);
+        } else {
+            self.set_exhausted(true);
         }
         //drop(inner);
         //crate::log(">> which is ");
@@ -57,9 +270,12 @@ where
     pub fn sibling(&self) {
         //crate::log("advancing to next sibling of ");
         //Rndr::log_node(&self.current());
-        let mut inner = self.0.borrow_mut();
+        let mut inner = self.node.borrow_mut();
         if let Some(node) = Rndr::next_sibling(&inner) {
             *inner = node;
+            self.set_exhausted(false);
+        } else {
+            self.set_exhausted(true);
         }
         //drop(inner);
         //crate::log(">> which is ");
@@ -70,15 +286,241 @@ where
     ///
     /// Does nothing if there is no parent.
     pub fn parent(&self) {
-        let mut inner = self.0.borrow_mut();
+        let mut inner = self.node.borrow_mut();
         if let Some(node) = Rndr::get_parent(&inner) {
             *inner = node;
         }
+        self.set_exhausted(false);
     }
 
     /// Sets the cursor to some node.
     pub fn set(&self, node: crate::renderer::types::Node) {
-        *self.0.borrow_mut() = node;
+        *self.node.borrow_mut() = node;
+        self.set_exhausted(false);
+    }
+
+    /// Saves the current location of the cursor, along with the position of the view there and
+    /// the branches it is in.
+    ///
+    /// A view that will need to hydrate more content later, like a suspense boundary whose
+    /// children arrive after its fallback, can keep the checkpoint and [`restore`](Cursor::restore)
+    /// it, rather than searching the document for its place again.
+    pub fn checkpoint(&self, position: &PositionState) -> CursorCheckpoint {
+        CursorCheckpoint {
+            node: self.current(),
+            exhausted: self.is_exhausted(),
+            position: position.get(),
+            branches: self
+                .branches
+                .as_ref()
+                .map(|branches| branches.borrow().clone()),
+        }
+    }
+
+    /// Moves the cursor back to a [`checkpoint`](Cursor::checkpoint), and sets `position` to the
+    /// position that was saved with it.
+    pub fn restore(
+        &self,
+        checkpoint: &CursorCheckpoint,
+        position: &PositionState,
+    ) {
+        *self.node.borrow_mut() = checkpoint.node.clone();
+        self.set_exhausted(checkpoint.exhausted);
+        position.set(checkpoint.position);
+        if let (Some(branches), Some(saved)) =
+            (&self.branches, &checkpoint.branches)
+        {
+            branches.borrow_mut().clone_from(saved);
+        }
+    }
+
+    /// Creates a new cursor at the same node, which no longer shares its location with this one.
+    ///
+    /// This is useful along with [`restore`](Cursor::restore), to hydrate content from a
+    /// checkpoint without moving the cursor that is hydrating the rest of the page.
+    pub fn deep_clone(&self) -> Self {
+        Self {
+            node: Rc::new(RefCell::new(self.current())),
+            exhausted: Rc::new(Cell::new(self.is_exhausted())),
+            lenient: self.lenient.clone(),
+            branches: self.branches.as_ref().map(|branches| {
+                Rc::new(RefCell::new(branches.borrow().clone()))
+            }),
+        }
+    }
+
+    /// Returns the node at which the cursor is currently located, as the type of node the view
+    /// expects to find there.
+    ///
+    /// If the cursor is [lenient](Cursor::with_lenient), nodes that do not match and that were
+    /// added to the page after it was rendered are skipped first.
+    ///
+    /// # Panics
+    /// Panics if the current node is not of the expected type. In debug builds, or with the
+    /// `hydration-diagnostics` feature, a [`HydrationError`] is reported first.
+    #[track_caller]
+    pub fn current_as<T>(&self, expected: ExpectedNode) -> T
+    where
+        T: CastFrom<Node>,
+    {
+        let check =
+            cfg!(any(debug_assertions, feature = "hydration-diagnostics"))
+                || self.lenient.is_some();
+        let mut skipped = 0;
+        loop {
+            let node = self.current();
+            if !check {
+                return T::cast_from(node).unwrap_or_else(|| {
+                    panic!("hydration mismatch: expected {expected}")
+                });
+            }
+
+            let exhausted = self.is_exhausted();
+            if !exhausted && expected.matches(&node) {
+                if let Some(node) = T::cast_from(node.clone()) {
+                    return node;
+                }
+            }
+            if let Some(lenient) = &self.lenient {
+                if !exhausted
+                    && skipped < lenient.max_skipped
+                    && (lenient.is_foreign)(&node)
+                {
+                    if let Some(next) = Rndr::next_sibling(&node) {
+                        lenient.warn_skipped(&node, expected);
+                        self.set(next);
+                        skipped += 1;
+                        continue;
+                    }
+                }
+            }
+            self.mismatch(expected, &node, exhausted);
+        }
+    }
+
+    #[track_caller]
+    fn mismatch(
+        &self,
+        expected: ExpectedNode,
+        _node: &Node,
+        _exhausted: bool,
+    ) -> ! {
+        #[cfg(any(debug_assertions, feature = "hydration-diagnostics"))]
+        {
+            HydrationError::new(expected, _node, _exhausted, Location::caller())
+                .report()
+        }
+
+        #[cfg(not(any(debug_assertions, feature = "hydration-diagnostics")))]
+        {
+            panic!("hydration mismatch: expected {expected}")
+        }
+    }
+
+    /// Advances to the text node for a piece of text, and returns it.
+    ///
+    /// Adjacent pieces of text are rendered with `<!>` comments between them, so that the
+    /// browser keeps them as separate text nodes, and empty text is rendered as a single space.
+    /// If something between the server and the browser has stripped those comments, the text is
+    /// merged into a single node; given the text that was rendered on the server, this splits off
+    /// the part that belongs to it. If there is no text node for empty text at all, an empty one
+    /// is created.
+    pub fn next_text_node(
+        &self,
+        position: &PositionState,
+        server_text: Option<&str>,
+    ) -> crate::renderer::types::Text {
+        let first_child = position.get() == Position::FirstChild;
+        if first_child {
+            self.child();
+        } else {
+            self.sibling();
+        }
+
+        // separating placeholder marker comes before text node, unless it has been stripped
+        if matches!(position.get(), Position::NextChildAfterText)
+            && (server_text.is_none()
+                || crate::renderer::types::Placeholder::cast_from(
+                    self.current(),
+                )
+                .is_some())
+        {
+            self.sibling();
+        }
+
+        let Some(server_text) = server_text else {
+            return self.current_as(ExpectedNode::Text);
+        };
+        let rendered = if server_text.is_empty() {
+            " "
+        } else {
+            server_text
+        };
+
+        let current = self.current();
+        let found = (!self.is_exhausted())
+            .then(|| crate::renderer::types::Text::cast_from(current.clone()))
+            .flatten();
+        if let Some(node) = found {
+            let content = Rndr::text_content(node.as_ref()).unwrap_or_default();
+            if content.starts_with(rendered) {
+                if content.len() > rendered.len() {
+                    Rndr::split_text(&node, rendered.encode_utf16().count());
+                }
+                return node;
+            }
+        }
+
+        if server_text.is_empty() {
+            // the text node was lost entirely, so create one in its place
+            let node = Rndr::create_text_node("");
+            let (parent, before) = if !self.is_exhausted() {
+                (Rndr::get_parent(&current), Some(current))
+            } else if first_child {
+                (Some(current), None)
+            } else {
+                (Rndr::get_parent(&current), None)
+            };
+            if let Some(parent) =
+                parent.and_then(crate::renderer::types::Element::cast_from)
+            {
+                Rndr::insert_node(&parent, node.as_ref(), before.as_ref());
+                self.set(AsRef::<Node>::as_ref(&node).clone());
+                return node;
+            }
+        }
+
+        self.current_as(ExpectedNode::Text)
+    }
+
+    /// Advances over a run of sibling nodes that the view does not know about, and returns them.
+    ///
+    /// The cursor should be at a comment marking the start of the run. It is left at the comment
+    /// whose text is `close`, which marks the end. Pairs of `open` and `close` comments inside the
+    /// run are skipped along with it.
+    pub fn skip_to_closing_comment(
+        &self,
+        open: &str,
+        close: &str,
+    ) -> Vec<crate::renderer::types::Node> {
+        let mut nodes = Vec::new();
+        let mut depth = 0usize;
+        while let Some(next) = Rndr::next_sibling(&self.current()) {
+            match Rndr::comment_text(&next).as_deref() {
+                Some(text) if text == close => {
+                    if depth == 0 {
+                        self.set(next);
+                        break;
+                    }
+                    depth -= 1;
+                }
+                Some(text) if text == open => depth += 1,
+                _ => {}
+            }
+            nodes.push(next.clone());
+            self.set(next);
+        }
+        nodes
     }
 
     /// Advances to the next placeholder node.
@@ -93,10 +535,8 @@ where
         } else {
             self.sibling();
         }
-        let marker = self.current();
         position.set(Position::NextChild);
-        crate::renderer::types::Placeholder::cast_from(marker)
-            .expect("could not convert current node into marker node")
+        self.current_as(ExpectedNode::Placeholder)
         /*let marker2 = marker.clone();
         Rndr::Placeholder::cast_from(marker).unwrap_or_else(|| {
             crate::dom::log("expecting to find a marker. instead, found");
@@ -105,3 +545,228 @@ where
         })*/
     }
 }
+
+/// The kind of node a view expected to find while hydrating.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExpectedNode {
+    /// An element with the given tag, or any element if the tag is empty.
+    Element(&'static str),
+    /// A text node.
+    Text,
+    /// A placeholder, which is a comment node in the DOM.
+    Placeholder,
+}
+
+impl ExpectedNode {
+    // whether the node looks like the one expected; the type of node is checked when casting it
+    fn matches(&self, node: &Node) -> bool {
+        match self {
+            ExpectedNode::Element(tag) => {
+                tag.is_empty()
+                    || Rndr::describe_node(node).eq_ignore_ascii_case(tag)
+            }
+            _ => true,
+        }
+    }
+}
+
+impl fmt::Display for ExpectedNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExpectedNode::Element("") => f.write_str("an element"),
+            ExpectedNode::Element(tag) => write!(f, "a <{tag}> element"),
+            ExpectedNode::Text => f.write_str("a text node"),
+            ExpectedNode::Placeholder => f.write_str("a marker comment"),
+        }
+    }
+}
+
+/// Lets hydration skip over nodes that were added to the page after it was rendered on the
+/// server, like the elements some browser extensions insert into `<body>`.
+///
+/// When the cursor finds a node that does not match what the view expects, and the node is
+/// foreign, it skips to the next sibling and tries again, logging a warning for each node it
+/// skips. By default, custom elements (other than the ones Leptos renders itself) are foreign,
+/// which covers most extensions; a different predicate can be set with
+/// [`is_foreign`](LenientHydration::is_foreign). At most
+/// [`max_skipped`](LenientHydration::max_skipped) nodes are skipped in a row before giving up
+/// and reporting a mismatch.
+#[derive(Clone)]
+pub struct LenientHydration {
+    max_skipped: usize,
+    is_foreign: Rc<dyn Fn(&Node) -> bool>,
+}
+
+impl Default for LenientHydration {
+    fn default() -> Self {
+        Self {
+            max_skipped: 8,
+            is_foreign: Rc::new(|node| {
+                crate::renderer::types::Element::cast_from(node.clone())
+                    .is_some()
+                    && {
+                        let tag = Rndr::describe_node(node);
+                        tag.contains('-') && !tag.starts_with("leptos-")
+                    }
+            }),
+        }
+    }
+}
+
+impl fmt::Debug for LenientHydration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LenientHydration")
+            .field("max_skipped", &self.max_skipped)
+            .finish_non_exhaustive()
+    }
+}
+
+impl LenientHydration {
+    /// Sets the most nodes that will be skipped in a row.
+    pub fn max_skipped(mut self, max_skipped: usize) -> Self {
+        self.max_skipped = max_skipped;
+        self
+    }
+
+    /// Sets the predicate that decides whether a node was added to the page after it was rendered.
+    pub fn is_foreign(
+        mut self,
+        is_foreign: impl Fn(&Node) -> bool + 'static,
+    ) -> Self {
+        self.is_foreign = Rc::new(is_foreign);
+        self
+    }
+
+    fn warn_skipped(&self, node: &Node, expected: ExpectedNode) {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(
+            expected = %expected,
+            skipped = %Rndr::describe_node(node),
+            "skipping a node that was not rendered on the server"
+        );
+        #[cfg(not(any(feature = "testing", feature = "sledgehammer")))]
+        web_sys::console::warn_2(
+            &wasm_bindgen::JsValue::from_str(&format!(
+                "[WARNING] While hydrating, expected {expected} and skipped a \
+                 node that was not rendered on the server: "
+            )),
+            node,
+        );
+        #[cfg(all(feature = "sledgehammer", not(feature = "testing")))]
+        web_sys::console::warn_2(
+            &wasm_bindgen::JsValue::from_str(&format!(
+                "[WARNING] While hydrating, expected {expected} and skipped a \
+                 node that was not rendered on the server: "
+            )),
+            &node.to_node(),
+        );
+        #[cfg(feature = "testing")]
+        eprintln!(
+            "[WARNING] While hydrating, expected {expected} and skipped a \
+             node that was not rendered on the server: {}",
+            Rndr::describe_node(node)
+        );
+    }
+}
+
+/// Describes where hydration found a different node from the one the view expected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HydrationError {
+    /// The kind of node the view expected.
+    pub expected: ExpectedNode,
+    /// The node that was found instead, or `None` if there were no more nodes to move to.
+    pub found: Option<String>,
+    /// The path to the node that was found, from the document down, with each node's index among
+    /// its siblings.
+    pub path: Vec<String>,
+    /// The location of the view implementation that was hydrating.
+    pub location: &'static Location<'static>,
+}
+
+thread_local! {
+    static HYDRATION_ERRORS: RefCell<Vec<HydrationError>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Takes every [`HydrationError`] reported on this thread so far.
+pub fn take_hydration_errors() -> Vec<HydrationError> {
+    HYDRATION_ERRORS.with(|errors| std::mem::take(&mut *errors.borrow_mut()))
+}
+
+impl HydrationError {
+    #[cfg(any(debug_assertions, feature = "hydration-diagnostics"))]
+    fn new(
+        expected: ExpectedNode,
+        node: &Node,
+        exhausted: bool,
+        location: &'static Location<'static>,
+    ) -> Self {
+        let mut path = Vec::new();
+        let mut current = Some(node.clone());
+        while let Some(node) = current {
+            let parent = Rndr::get_parent(&node);
+            let description = Rndr::describe_node(&node);
+            match &parent {
+                Some(parent) => {
+                    let mut index = 0;
+                    let mut sibling = Rndr::first_child(parent);
+                    while let Some(prev) = sibling {
+                        if prev == node {
+                            break;
+                        }
+                        index += 1;
+                        sibling = Rndr::next_sibling(&prev);
+                    }
+                    path.push(format!("{description}[{index}]"));
+                }
+                None => path.push(description),
+            }
+            current = parent;
+        }
+        path.reverse();
+
+        Self {
+            expected,
+            found: (!exhausted).then(|| Rndr::describe_node(node)),
+            path,
+            location,
+        }
+    }
+
+    /// Records this error so that it can be collected with [`take_hydration_errors`], logs it,
+    /// and panics with it.
+    pub fn report(self) -> ! {
+        #[cfg(feature = "tracing")]
+        tracing::error!(
+            expected = %self.expected,
+            found = ?self.found,
+            path = %self.path.join(" > "),
+            location = %self.location,
+            "hydration mismatch"
+        );
+        let message = self.to_string();
+        HYDRATION_ERRORS.with(|errors| errors.borrow_mut().push(self));
+        panic!("{message}");
+    }
+}
+
+impl fmt::Display for HydrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "hydration mismatch: expected {}, but found ",
+            self.expected
+        )?;
+        match &self.found {
+            Some(found) => f.write_str(found)?,
+            None => f.write_str("no more nodes")?,
+        }
+        write!(
+            f,
+            "\n  at {}\n  while hydrating the view at {}",
+            self.path.join(" > "),
+            self.location
+        )
+    }
+}
+
+impl Error for HydrationError {}
@@ -9,9 +9,16 @@
 
 /// Commonly-used traits.
 pub mod prelude {
+    #[cfg(not(any(feature = "testing", feature = "sledgehammer")))]
+    pub use crate::renderer::dom::Dom;
+    #[cfg(feature = "testing")]
+    pub use crate::renderer::mock_dom::MockDom;
+    #[cfg(all(feature = "sledgehammer", not(feature = "testing")))]
+    pub use crate::renderer::sledgehammer::Sledgehammer;
     pub use crate::{
         html::{
             attribute::{
+                any_attribute::IntoAnyAttribute,
                 aria::AriaAttributes,
                 custom::CustomAttribute,
                 global::{
@@ -25,15 +32,18 @@ pub mod prelude {
             element::{ElementChild, ElementExt, InnerHtmlAttribute},
             node_ref::NodeRefAttribute,
         },
-        renderer::{dom::Dom, Renderer},
+        renderer::Renderer,
         view::{
-            add_attr::AddAnyAttr, any_view::IntoAny, IntoRender, Mountable,
-            Render, RenderHtml,
+            add_attr::AddAnyAttr, any_view::IntoAny,
+            static_view::IntoStaticView, IntoRender, Mountable, Render,
+            RenderHtml,
         },
     };
 }
 
+#[cfg(not(any(feature = "testing", feature = "sledgehammer")))]
 use wasm_bindgen::JsValue;
+#[cfg(not(any(feature = "testing", feature = "sledgehammer")))]
 use web_sys::Node;
 
 /// Helpers for interacting with the DOM.
@@ -68,6 +78,8 @@ pub mod oco;
 #[cfg(feature = "reactive_graph")]
 pub mod reactive_graph;
 
+// only the `web_sys` DOM renderer reports errors from JavaScript
+#[cfg(not(any(feature = "testing", feature = "sledgehammer")))]
 pub(crate) trait UnwrapOrDebug {
     type Output;
 
@@ -80,6 +92,7 @@ pub(crate) trait UnwrapOrDebug {
     ) -> Option<Self::Output>;
 }
 
+#[cfg(not(any(feature = "testing", feature = "sledgehammer")))]
 impl<T> UnwrapOrDebug for Result<T, JsValue> {
     type Output = T;
 
@@ -1,9 +1,16 @@
 use crate::{
-    html::{attribute::AttributeValue, class::IntoClass},
+    html::{
+        attribute::AttributeValue,
+        class::{
+            add_class_tokens, hydrate_class_tokens, replace_class_tokens,
+            IntoClass,
+        },
+    },
     hydration::Cursor,
     no_attrs,
     prelude::{Mountable, Render, RenderHtml},
     renderer::Rndr,
+    ssr::BranchMarks,
     view::{strings::StrState, Position, PositionState, ToTemplate},
 };
 use oco_ref::Oco;
@@ -49,7 +56,7 @@ impl RenderHtml for Oco<'static, str> {
         buf: &mut String,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) {
         <&str as RenderHtml>::to_html_with_buf(
             &self,
@@ -190,25 +197,28 @@ impl IntoClass for Oco<'static, str> {
         self,
         el: &crate::renderer::types::Element,
     ) -> Self::State {
-        if !FROM_SERVER {
-            Rndr::set_attribute(el, "class", &self);
-        }
+        hydrate_class_tokens::<FROM_SERVER>(el, &self);
         (el.clone(), self)
     }
 
     fn build(self, el: &crate::renderer::types::Element) -> Self::State {
-        Rndr::set_attribute(el, "class", &self);
+        add_class_tokens(el, &self);
         (el.clone(), self)
     }
 
     fn rebuild(self, state: &mut Self::State) {
         let (el, prev) = state;
         if self != *prev {
-            Rndr::set_attribute(el, "class", &self);
+            replace_class_tokens(el, prev, &self);
         }
         *prev = self;
     }
 
+    fn remove(state: Self::State) {
+        let (el, prev) = state;
+        replace_class_tokens(&el, &prev, "");
+    }
+
     fn into_cloneable(mut self) -> Self::Cloneable {
         // ensure it's reference-counted
         self.upgrade_inplace();
@@ -0,0 +1,148 @@
+use super::{RenderEffectState, Suspend, SuspendState};
+use crate::{
+    html::attribute::Attribute,
+    hydration::Cursor,
+    ssr::{BranchMarks, StreamBuilder},
+    view::{add_attr::AddAnyAttr, Position, PositionState, Render, RenderHtml},
+};
+use reactive_graph::{
+    computed::{ArcAsyncDerived, AsyncDerived},
+    owner::Storage,
+    traits::Read,
+};
+use std::{future::Future, pin::Pin};
+
+macro_rules! async_derived_impl {
+    ($sig:ty, [$($gen:tt)*], [$($bounds:tt)*]) => {
+        impl<$($gen)*> Render for $sig
+        where
+            T: Render + Send + Sync + Clone + 'static,
+            $($bounds)*
+        {
+            type State = RenderEffectState<SuspendState<T>>;
+
+            fn build(self) -> Self::State {
+                (move || {
+                    let this = self.clone();
+                    Suspend::new(async move { this.await })
+                })
+                .build()
+            }
+
+            fn rebuild(self, state: &mut Self::State) {
+                (move || {
+                    let this = self.clone();
+                    Suspend::new(async move { this.await })
+                })
+                .rebuild(state)
+            }
+        }
+
+        impl<$($gen)*> AddAnyAttr for $sig
+        where
+            T: RenderHtml + Send + Sync + Clone + 'static,
+            $($bounds)*
+        {
+            type Output<SomeNewAttr: Attribute> = Box<
+                dyn FnMut() -> Suspend<
+                        Pin<
+                            Box<
+                                dyn Future<
+                                        Output = <T as AddAnyAttr>::Output<
+                                            <SomeNewAttr::CloneableOwned as Attribute>::CloneableOwned,
+                                        >,
+                                    > + Send,
+                            >,
+                        >,
+                    > + Send,
+            >;
+
+            fn add_any_attr<NewAttr: Attribute>(
+                self,
+                attr: NewAttr,
+            ) -> Self::Output<NewAttr>
+            where
+                Self::Output<NewAttr>: RenderHtml,
+            {
+                let attr = attr.into_cloneable_owned();
+                Box::new(move || {
+                    let this = self.clone();
+                    Suspend::new(async move { this.await })
+                        .add_any_attr(attr.clone())
+                })
+            }
+        }
+
+        impl<$($gen)*> RenderHtml for $sig
+        where
+            T: RenderHtml + Send + Sync + Clone + 'static,
+            $($bounds)*
+        {
+            type AsyncOutput = T;
+
+            const MIN_LENGTH: usize = 0;
+
+            fn dry_resolve(&mut self) {
+                self.read();
+            }
+
+            async fn resolve(self) -> Self::AsyncOutput {
+                self.await
+            }
+
+            fn to_html_with_buf(
+                self,
+                buf: &mut String,
+                position: &mut Position,
+                escape: bool,
+                mark_branches: &BranchMarks,
+            ) {
+                (move || {
+                    let this = self.clone();
+                    Suspend::new(async move { this.await })
+                })
+                .to_html_with_buf(buf, position, escape, mark_branches);
+            }
+
+            fn to_html_async_with_buf<const OUT_OF_ORDER: bool>(
+                self,
+                buf: &mut StreamBuilder,
+                position: &mut Position,
+                escape: bool,
+                mark_branches: &BranchMarks,
+            ) where
+                Self: Sized,
+            {
+                (move || {
+                    let this = self.clone();
+                    Suspend::new(async move { this.await })
+                })
+                .to_html_async_with_buf::<OUT_OF_ORDER>(
+                    buf,
+                    position,
+                    escape,
+                    mark_branches,
+                );
+            }
+
+            fn hydrate<const FROM_SERVER: bool>(
+                self,
+                cursor: &Cursor,
+                position: &PositionState,
+            ) -> Self::State {
+                (move || {
+                    let this = self.clone();
+                    Suspend::new(async move { this.await })
+                })
+                .hydrate::<FROM_SERVER>(cursor, position)
+            }
+        }
+    };
+}
+
+async_derived_impl!(
+    AsyncDerived<T, S>,
+    [T, S],
+    [S: Storage<ArcAsyncDerived<T>> + Send + Sync + 'static,]
+);
+async_derived_impl!(ArcAsyncDerived<T>, [T], []);
@@ -0,0 +1,154 @@
+use super::{ReactiveFunction, SharedReactiveFunction};
+use crate::{
+    html::attribute::{Attribute, NextAttribute},
+    renderer::types::Element,
+};
+use reactive_graph::effect::RenderEffect;
+
+/// Creates a set of attributes that are all updated by a single [`RenderEffect`].
+///
+/// Normally, each reactive attribute on an element creates its own effect. When an element has
+/// many reactive attributes, it can be cheaper to compute all of them in one closure:
+/// ```rust,ignore
+/// div().add_any_attr(batch_attributes(move || {
+///     (id(row.get().id), title(row.get().title), class(row.get().class))
+/// }))
+/// ```
+/// Each time the closure runs, every attribute is rebuilt against its previous value, so only
+/// the attributes whose values have actually changed are written to the DOM.
+pub fn batch_attributes<F, A>(fun: F) -> BatchedAttributes<F>
+where
+    F: ReactiveFunction<Output = A>,
+    A: Attribute,
+{
+    BatchedAttributes(fun)
+}
+
+/// A set of attributes that are updated together by a single [`RenderEffect`].
+///
+/// This is created with [`batch_attributes`].
+#[derive(Debug, Clone)]
+pub struct BatchedAttributes<F>(F);
+
+impl<F, A> Attribute for BatchedAttributes<F>
+where
+    F: ReactiveFunction<Output = A>,
+    A: Attribute + 'static,
+    A::State: 'static,
+{
+    const MIN_LENGTH: usize = A::MIN_LENGTH;
+
+    type State = BatchedAttributesState<A::State>;
+    type AsyncOutput = A::AsyncOutput;
+    type Cloneable = BatchedAttributes<SharedReactiveFunction<A>>;
+    type CloneableOwned = BatchedAttributes<SharedReactiveFunction<A>>;
+
+    fn html_len(&self) -> usize {
+        A::MIN_LENGTH
+    }
+
+    fn to_html(
+        mut self,
+        buf: &mut String,
+        class: &mut String,
+        style: &mut String,
+        inner_html: &mut String,
+    ) {
+        self.0.invoke().to_html(buf, class, style, inner_html);
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(mut self, el: &Element) -> Self::State {
+        let el = el.to_owned();
+        let effect = RenderEffect::new({
+            let el = el.clone();
+            move |prev| {
+                let attrs = self.0.invoke();
+                if let Some(mut state) = prev {
+                    attrs.rebuild(&mut state);
+                    state
+                } else {
+                    attrs.hydrate::<FROM_SERVER>(&el)
+                }
+            }
+        });
+        BatchedAttributesState { el, effect }
+    }
+
+    fn build(self, el: &Element) -> Self::State {
+        let el = el.to_owned();
+        let effect = batched_effect(self.0, el.clone(), None);
+        BatchedAttributesState { el, effect }
+    }
+
+    fn rebuild(self, state: &mut Self::State) {
+        let prev_value = state.effect.take_value();
+        state.effect = batched_effect(self.0, state.el.clone(), prev_value);
+    }
+
+    fn into_cloneable(self) -> Self::Cloneable {
+        BatchedAttributes(self.0.into_shared())
+    }
+
+    fn into_cloneable_owned(self) -> Self::CloneableOwned {
+        BatchedAttributes(self.0.into_shared())
+    }
+
+    fn dry_resolve(&mut self) {
+        self.0.invoke().dry_resolve();
+    }
+
+    async fn resolve(mut self) -> Self::AsyncOutput {
+        self.0.invoke().resolve().await
+    }
+}
+
+// if there is no previous state to update (for example, because the previous effect has been
+// disposed), the attributes are built on the element again
+fn batched_effect<F, A>(
+    mut fun: F,
+    el: Element,
+    prev: Option<A::State>,
+) -> RenderEffect<A::State>
+where
+    F: ReactiveFunction<Output = A>,
+    A: Attribute + 'static,
+    A::State: 'static,
+{
+    RenderEffect::new_with_value(
+        move |prev| {
+            let attrs = fun.invoke();
+            if let Some(mut state) = prev {
+                attrs.rebuild(&mut state);
+                state
+            } else {
+                attrs.build(&el)
+            }
+        },
+        prev,
+    )
+}
+
+/// The state for [`BatchedAttributes`].
+pub struct BatchedAttributesState<S>
+where
+    S: 'static,
+{
+    el: Element,
+    effect: RenderEffect<S>,
+}
+
+impl<F, A> NextAttribute for BatchedAttributes<F>
+where
+    F: ReactiveFunction<Output = A>,
+    A: Attribute + 'static,
+    A::State: 'static,
+{
+    type Output<NewAttr: Attribute> = (Self, NewAttr);
+
+    fn add_any_attr<NewAttr: Attribute>(
+        self,
+        new_attr: NewAttr,
+    ) -> Self::Output<NewAttr> {
+        (self, new_attr)
+    }
+}
@@ -1,9 +1,10 @@
+#[cfg(not(feature = "testing"))]
+use crate::html::event::{change, input, on};
 use crate::{
     dom::{event_target_checked, event_target_value},
     html::{
         attribute::{Attribute, AttributeKey, AttributeValue, NextAttribute},
-        event::{change, input, on},
-        property::{prop, IntoProperty},
+        property::IntoProperty,
     },
     prelude::AddAnyAttr,
     renderer::{types::Element, RemoveEventHandler},
@@ -11,7 +12,7 @@ use crate::{
 };
 use reactive_graph::{
     signal::{ReadSignal, RwSignal, WriteSignal},
-    traits::{Get, Update},
+    traits::{Get, Set},
     wrappers::read::Signal,
 };
 use send_wrapper::SendWrapper;
@@ -56,6 +57,10 @@ where
     ///
     /// // Use `Value` and `String` for everything else
     /// input_element.bind(Value, (text, set_text));
+    ///
+    /// // A `Model` works too, for example a slice of a larger signal
+    /// let name: Model<String> = create_slice(form, |f| f.name.clone(), |f, n| f.name = n);
+    /// input_element.bind(Value, name);
     /// ```
     ///
     /// Depending on the input different events are listened to.
@@ -120,7 +125,7 @@ where
     Key: AttributeKey,
     T: FromEventTarget + AttributeValue + 'static,
     R: Get<Value = T> + Clone + 'static,
-    W: Update<Value = T>,
+    W: Set<Value = T>,
 {
     key: Key,
     read_signal: R,
@@ -132,7 +137,7 @@ where
     Key: AttributeKey,
     T: FromEventTarget + AttributeValue + 'static,
     R: Get<Value = T> + Clone + 'static,
-    W: Update<Value = T> + Clone,
+    W: Set<Value = T> + Clone,
 {
     fn clone(&self) -> Self {
         Self {
@@ -148,7 +153,7 @@ where
     Key: AttributeKey,
     T: FromEventTarget + AttributeValue + PartialEq + Sync + 'static,
     R: Get<Value = T> + Clone + Send + Sync + 'static,
-    W: Update<Value = T> + Clone + 'static,
+    W: Set<Value = T> + Clone + 'static,
     Element: ChangeEvent + GetValue<T>,
 {
     /// Attaches the event listener that updates the signal value to the element.
@@ -181,6 +186,31 @@ where
             Key::KEY
         }
     }
+
+    // the mock DOM can't create `JsValue`s for properties, so the value is mirrored into the
+    // attribute instead, which is also where `GetValue` reads it from
+    #[cfg(feature = "testing")]
+    fn reflect_attribute(
+        &self,
+        el: &Element,
+    ) -> reactive_graph::effect::RenderEffect<()> {
+        use crate::renderer::Rndr;
+        use reactive_graph::effect::RenderEffect;
+
+        let key = self.key();
+        let read_signal = self.read_signal.clone();
+        let el = SendWrapper::new(el.clone());
+        RenderEffect::new(move |_| {
+            Rndr::remove_attribute(&el, key);
+            if Key::KEY == "group" {
+                if el.get_value() == read_signal.get() {
+                    Rndr::set_attribute(&el, key, "");
+                }
+            } else {
+                read_signal.get().build(&el, key);
+            }
+        })
+    }
 }
 
 impl<Key, T, R, W> Attribute for Bind<Key, T, R, W>
@@ -189,15 +219,21 @@ where
     T: FromEventTarget + AttributeValue + PartialEq + Sync + 'static,
     R: Get<Value = T> + Clone + Send + Sync + 'static,
     Signal<BoolOrT<T>>: IntoProperty,
-    W: Update<Value = T> + Clone + Send + 'static,
+    W: Set<Value = T> + Clone + Send + 'static,
     Element: ChangeEvent + GetValue<T>,
 {
     const MIN_LENGTH: usize = 0;
 
+    #[cfg(not(feature = "testing"))]
     type State = (
         <Signal<BoolOrT<T>> as IntoProperty>::State,
         (Element, Option<RemoveEventHandler<Element>>),
     );
+    #[cfg(feature = "testing")]
+    type State = (
+        reactive_graph::effect::RenderEffect<()>,
+        (Element, Option<RemoveEventHandler<Element>>),
+    );
     type AsyncOutput = Self;
     type Cloneable = Bind<Key, T, R, W>;
     type CloneableOwned = Bind<Key, T, R, W>;
@@ -217,8 +253,13 @@ where
 
     #[inline(always)]
     fn hydrate<const FROM_SERVER: bool>(self, el: &Element) -> Self::State {
-        let signal = self.read_signal(el);
-        let attr_state = prop(self.key(), signal).hydrate::<FROM_SERVER>(el);
+        #[cfg(not(feature = "testing"))]
+        let attr_state = {
+            let signal = self.read_signal(el);
+            IntoProperty::hydrate::<FROM_SERVER>(signal, el, self.key())
+        };
+        #[cfg(feature = "testing")]
+        let attr_state = self.reflect_attribute(el);
 
         let cleanup = self.attach(el);
 
@@ -227,8 +268,13 @@ where
 
     #[inline(always)]
     fn build(self, el: &Element) -> Self::State {
-        let signal = self.read_signal(el);
-        let attr_state = prop(self.key(), signal).build(el);
+        #[cfg(not(feature = "testing"))]
+        let attr_state = {
+            let signal = self.read_signal(el);
+            IntoProperty::build(signal, el, self.key())
+        };
+        #[cfg(feature = "testing")]
+        let attr_state = self.reflect_attribute(el);
 
         let cleanup = self.attach(el);
 
@@ -239,8 +285,15 @@ where
     fn rebuild(self, state: &mut Self::State) {
         let (attr_state, (el, prev_cleanup)) = state;
 
-        let signal = self.read_signal(el);
-        prop(self.key(), signal).rebuild(attr_state);
+        #[cfg(not(feature = "testing"))]
+        {
+            let signal = self.read_signal(el);
+            IntoProperty::rebuild(signal, attr_state, self.key());
+        }
+        #[cfg(feature = "testing")]
+        {
+            *attr_state = self.reflect_attribute(el);
+        }
 
         if let Some(prev) = prev_cleanup.take() {
             (prev.into_inner())(el);
@@ -269,7 +322,7 @@ where
     T: FromEventTarget + AttributeValue + PartialEq + Sync + 'static,
     R: Get<Value = T> + Clone + Send + Sync + 'static,
     Signal<BoolOrT<T>>: IntoProperty,
-    W: Update<Value = T> + Clone + Send + 'static,
+    W: Set<Value = T> + Clone + Send + 'static,
     Element: ChangeEvent + GetValue<T>,
 {
     type Output<NewAttr: Attribute> = (Self, NewAttr);
@@ -287,7 +340,7 @@ where
     Key: AttributeKey,
     T: FromEventTarget + AttributeValue + 'static,
     R: Get<Value = T> + Clone + 'static,
-    W: Update<Value = T> + Clone,
+    W: Set<Value = T> + Clone,
 {
     #[inline(always)]
     fn to_template(
@@ -302,14 +355,16 @@ where
 
 /// Splits a combined signal into its read and write parts.
 ///
-/// This allows you to either provide a `RwSignal` or a tuple `(ReadSignal, WriteSignal)`.
+/// This allows you to either provide a `RwSignal` or a tuple `(ReadSignal, WriteSignal)`,
+/// including a [`Model`](reactive_graph::wrappers::write::Model) like
+/// `(Signal<T>, SignalSetter<T>)`.
 pub trait IntoSplitSignal {
     /// The actual contained value of the signal
     type Value;
     /// The read part of the signal
     type Read: Get<Value = Self::Value>;
     /// The write part of the signal
-    type Write: Update<Value = Self::Value>;
+    type Write: Set<Value = Self::Value>;
     /// Splits a combined signal into its read and write parts.
     fn into_split_signal(self) -> (Self::Read, Self::Write);
 }
@@ -331,7 +386,7 @@ where
 impl<T, R, W> IntoSplitSignal for (R, W)
 where
     R: Get<Value = T>,
-    W: Update<Value = T>,
+    W: Set<Value = T>,
 {
     type Value = T;
     type Read = R;
@@ -372,11 +427,12 @@ pub trait ChangeEvent {
     ) -> RemoveEventHandler<Self>
     where
         T: FromEventTarget + AttributeValue + 'static,
-        W: Update<Value = T> + 'static,
+        W: Set<Value = T> + 'static,
         Self: Sized;
 }
 
-impl ChangeEvent for web_sys::Element {
+#[cfg(not(feature = "testing"))]
+impl ChangeEvent for Element {
     fn attach_change_event<T, W>(
         &self,
         key: &str,
@@ -384,21 +440,20 @@ impl ChangeEvent for web_sys::Element {
     ) -> RemoveEventHandler<Self>
     where
         T: FromEventTarget + AttributeValue + 'static,
-        W: Update<Value = T> + 'static,
+        W: Set<Value = T> + 'static,
     {
         if key == "group" {
             let handler = move |evt| {
                 let checked = event_target_checked(&evt);
                 if checked {
-                    write_signal
-                        .try_update(|v| *v = T::from_event_target(&evt));
+                    _ = write_signal.try_set(T::from_event_target(&evt));
                 }
             };
 
             on::<_, _>(change, handler).attach(self)
         } else {
             let handler = move |evt| {
-                write_signal.try_update(|v| *v = T::from_event_target(&evt));
+                _ = write_signal.try_set(T::from_event_target(&evt));
             };
 
             if key == "checked" || self.tag_name() == "SELECT" {
@@ -410,6 +465,23 @@ impl ChangeEvent for web_sys::Element {
     }
 }
 
+// the mock DOM has no JavaScript events to read a new value from, so a binding only sets the
+// attribute, and tests update the signal directly
+#[cfg(feature = "testing")]
+impl ChangeEvent for Element {
+    fn attach_change_event<T, W>(
+        &self,
+        _key: &str,
+        _write_signal: W,
+    ) -> RemoveEventHandler<Self>
+    where
+        T: FromEventTarget + AttributeValue + 'static,
+        W: Set<Value = T> + 'static,
+    {
+        RemoveEventHandler::new(|_| {})
+    }
+}
+
 /// Get the value attribute of an element (input).
 /// Reads `value` if `T` is `String` and `checked` if `T` is `bool`.
 pub trait GetValue<T> {
@@ -429,6 +501,20 @@ impl GetValue<bool> for web_sys::Element {
     }
 }
 
+#[cfg(any(feature = "testing", feature = "sledgehammer"))]
+impl GetValue<String> for Element {
+    fn get_value(&self) -> String {
+        self.get_attribute("value").unwrap_or_default()
+    }
+}
+
+#[cfg(any(feature = "testing", feature = "sledgehammer"))]
+impl GetValue<bool> for Element {
+    fn get_value(&self) -> bool {
+        self.get_attribute("checked").unwrap_or_default() == "true"
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 /// Bool or a type. Needed to make the `group` attribute work. It is decided at runtime
 /// if the derived signal value is a bool or a type `T`.
@@ -73,6 +73,12 @@ where
         );
     }
 
+    fn remove(state: Self::State) {
+        if let Some(state) = state.take_value() {
+            C::remove(state);
+        }
+    }
+
     fn into_cloneable(self) -> Self::Cloneable {
         self.into_shared()
     }
@@ -116,21 +122,31 @@ where
         self,
         el: &crate::renderer::types::Element,
     ) -> Self::State {
-        // TODO FROM_SERVER vs template
         let (name, mut f) = self;
         let class_list = Rndr::class_list(el);
         let name = Rndr::intern(name);
 
+        // the last applied value is kept in the effect's state, so the class list is only
+        // touched when the boolean actually changes
         RenderEffect::new(
             move |prev: Option<(crate::renderer::types::ClassList, bool)>| {
                 let include = *f.invoke().borrow();
-                if let Some((class_list, prev)) = prev {
-                    if include {
-                        if !prev {
+                match prev {
+                    Some((class_list, prev)) => {
+                        if include {
+                            if !prev {
+                                Rndr::add_class(&class_list, name);
+                            }
+                        } else if prev {
+                            Rndr::remove_class(&class_list, name);
+                        }
+                    }
+                    // HTML from the server already includes the class, but a cloned
+                    // <template> doesn't
+                    None => {
+                        if !FROM_SERVER && include {
                             Rndr::add_class(&class_list, name);
                         }
-                    } else if prev {
-                        Rndr::remove_class(&class_list, name);
                     }
                 }
                 (class_list.clone(), include)
@@ -378,6 +394,10 @@ where
         <String as IntoClass>::rebuild(self.deref().to_owned(), state)
     }
 
+    fn remove(state: Self::State) {
+        <String as IntoClass>::remove(state);
+    }
+
     fn into_cloneable(self) -> Self::Cloneable {
         self.as_str().into()
     }
@@ -496,6 +516,12 @@ mod stable {
                     (move || self.get()).rebuild(state)
                 }
 
+                fn remove(state: Self::State) {
+                    if let Some(state) = state.take_value() {
+                        C::remove(state);
+                    }
+                }
+
                 fn into_cloneable(self) -> Self::Cloneable {
                     self
                 }
@@ -613,6 +639,12 @@ mod stable {
                     (move || self.get()).rebuild(state)
                 }
 
+                fn remove(state: Self::State) {
+                    if let Some(state) = state.take_value() {
+                        C::remove(state);
+                    }
+                }
+
                 fn into_cloneable(self) -> Self::Cloneable {
                     self
                 }
@@ -1,5 +1,5 @@
 use super::{ReactiveFunction, SharedReactiveFunction};
-use crate::html::element::InnerHtmlValue;
+use crate::html::element::{inner_html, InnerHtml, InnerHtmlValue};
 use reactive_graph::effect::RenderEffect;
 
 impl<F, V> InnerHtmlValue for F
@@ -86,6 +86,121 @@ where
     }
 }
 
+/// Returns an [`Attribute`](crate::html::attribute::Attribute) that reactively sets the inner
+/// HTML of an element, passing each value through `sanitize` first.
+///
+/// The sanitizer runs every time the value is rendered: during server rendering, during
+/// hydration, and on every update in the browser.
+pub fn inner_html_sanitized<F, V, S>(
+    fun: F,
+    sanitize: S,
+) -> InnerHtml<Sanitized<F, S>>
+where
+    F: ReactiveFunction<Output = V>,
+    V: AsRef<str>,
+    S: Fn(&str) -> String + Send + 'static,
+{
+    inner_html(Sanitized::new(fun, sanitize))
+}
+
+/// Returns an [`Attribute`](crate::html::attribute::Attribute) that reactively sets the inner
+/// HTML of an element, escaping each value so that it is displayed as text rather than parsed
+/// as HTML.
+pub fn inner_html_escaped<F, V>(fun: F) -> InnerHtml<Escaped<F>>
+where
+    F: ReactiveFunction<Output = V>,
+    V: AsRef<str>,
+{
+    inner_html_sanitized(fun, |value| {
+        html_escape::encode_text(value).into_owned()
+    })
+}
+
+/// A reactive [`InnerHtmlValue`] that is HTML-escaped before it is used.
+pub type Escaped<F> = Sanitized<F, fn(&str) -> String>;
+
+/// A reactive [`InnerHtmlValue`] that is passed through a sanitizer before it is used.
+///
+/// This is usually created with [`inner_html_sanitized`] or [`inner_html_escaped`].
+pub struct Sanitized<F, S> {
+    fun: F,
+    sanitize: S,
+}
+
+impl<F, V, S> Sanitized<F, S>
+where
+    F: ReactiveFunction<Output = V>,
+    V: AsRef<str>,
+    S: Fn(&str) -> String + Send + 'static,
+{
+    /// Wraps a reactive function so that each of its values is passed through `sanitize`.
+    pub fn new(fun: F, sanitize: S) -> Self {
+        Self { fun, sanitize }
+    }
+
+    fn invoke(&mut self) -> String {
+        let value = self.fun.invoke();
+        (self.sanitize)(value.as_ref())
+    }
+
+    fn into_fn(mut self) -> impl FnMut() -> String + Send + 'static {
+        move || self.invoke()
+    }
+}
+
+impl<F, V, S> InnerHtmlValue for Sanitized<F, S>
+where
+    F: ReactiveFunction<Output = V>,
+    V: AsRef<str>,
+    S: Fn(&str) -> String + Send + 'static,
+{
+    type AsyncOutput = String;
+    type State = RenderEffect<<String as InnerHtmlValue>::State>;
+    type Cloneable = SharedReactiveFunction<String>;
+    type CloneableOwned = SharedReactiveFunction<String>;
+
+    fn html_len(&self) -> usize {
+        0
+    }
+
+    fn to_html(mut self, buf: &mut String) {
+        buf.push_str(&self.invoke());
+    }
+
+    fn to_template(_buf: &mut String) {}
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        el: &crate::renderer::types::Element,
+    ) -> Self::State {
+        self.into_fn().hydrate::<FROM_SERVER>(el)
+    }
+
+    fn build(self, el: &crate::renderer::types::Element) -> Self::State {
+        self.into_fn().build(el)
+    }
+
+    fn rebuild(self, state: &mut Self::State) {
+        self.into_fn().rebuild(state)
+    }
+
+    fn into_cloneable(self) -> Self::Cloneable {
+        self.into_fn().into_shared()
+    }
+
+    fn into_cloneable_owned(self) -> Self::CloneableOwned {
+        self.into_fn().into_shared()
+    }
+
+    fn dry_resolve(&mut self) {
+        self.fun.invoke();
+    }
+
+    async fn resolve(mut self) -> Self::AsyncOutput {
+        self.invoke()
+    }
+}
+
 #[cfg(not(feature = "nightly"))]
 mod stable {
     use crate::html::element::InnerHtmlValue;
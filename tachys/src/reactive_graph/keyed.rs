@@ -0,0 +1,115 @@
+use crate::view::{keyed::keyed as keyed_view, RenderHtml};
+use std::hash::Hash;
+
+/// Creates a reactive keyed list of views.
+///
+/// Rendering a closure that returns a `Vec` of views rebuilds the whole list each time the
+/// closure runs. This instead diffs the new items against the previous ones by key, and only
+/// creates, moves, or removes the views whose keys have changed:
+/// ```rust,ignore
+/// ul().child(keyed(
+///     move || rows.get(),
+///     |row| row.id,
+///     |row| li().child(row.label),
+/// ))
+/// ```
+/// Views for existing keys are kept as they are, so `view_fn` is only called for new items.
+pub fn keyed<IF, I, T, K, KF, VF, V>(
+    mut items: IF,
+    key_fn: KF,
+    view_fn: VF,
+) -> impl RenderHtml
+where
+    IF: FnMut() -> I + Send + 'static,
+    I: IntoIterator<Item = T> + Send + 'static,
+    T: Send + 'static,
+    K: Eq + Hash + 'static,
+    KF: Fn(&T) -> K + Send + Clone + 'static,
+    VF: Fn(T) -> V + Send + Clone + 'static,
+    V: RenderHtml + 'static,
+{
+    let view_fn = move |_, item| (|_: usize| {}, view_fn(item));
+    move || keyed_view(items(), key_fn.clone(), view_fn.clone())
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::keyed;
+    use crate::{
+        html::element::{li, ul, ElementChild},
+        renderer::{mock_dom::node_eq, recording::RecordingRenderer},
+        view::Render,
+    };
+    use any_spawner::Executor;
+    use reactive_graph::{
+        owner::Owner,
+        signal::RwSignal,
+        traits::{Get, Set},
+    };
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    #[test]
+    fn keyed_list_reorders_inserts_and_removes_without_rebuilding() {
+        _ = Executor::init_manual();
+        let owner = Owner::new();
+        owner.set();
+
+        let rows = RwSignal::new(vec![1, 2, 3]);
+        let views = Arc::new(AtomicUsize::new(0));
+        let el = ul()
+            .child(keyed(move || rows.get(), |n| *n, {
+                let views = Arc::clone(&views);
+                move |n| {
+                    views.fetch_add(1, Ordering::Relaxed);
+                    li().child(n)
+                }
+            }))
+            .build();
+        assert_eq!(
+            el.to_debug_html(),
+            "<ul><li>1</li><li>2</li><li>3</li><!></ul>"
+        );
+        let children = el.children();
+        let (one, two, three) = (&children[0], &children[1], &children[2]);
+
+        // moving items keeps their nodes
+        let recording = RecordingRenderer::start();
+        rows.set(vec![3, 1, 2]);
+        Executor::flush();
+        assert_eq!(
+            el.to_debug_html(),
+            "<ul><li>3</li><li>1</li><li>2</li><!></ul>"
+        );
+        let moved = el.children();
+        assert!(node_eq(&moved[0], three));
+        assert!(node_eq(&moved[1], one));
+        assert!(node_eq(&moved[2], two));
+        assert_eq!(recording.stats().creates, 0);
+        assert_eq!(views.load(Ordering::Relaxed), 3);
+
+        // inserting only creates the new item
+        let recording = RecordingRenderer::start();
+        rows.set(vec![3, 4, 1, 2]);
+        Executor::flush();
+        assert_eq!(
+            el.to_debug_html(),
+            "<ul><li>3</li><li>4</li><li>1</li><li>2</li><!></ul>"
+        );
+        assert!(node_eq(&el.children()[2], one));
+        assert_eq!(views.load(Ordering::Relaxed), 4);
+        assert_eq!(recording.stats().removals, 0);
+
+        // removing only removes the items that are gone
+        let recording = RecordingRenderer::start();
+        rows.set(vec![4, 2]);
+        Executor::flush();
+        assert_eq!(el.to_debug_html(), "<ul><li>4</li><li>2</li><!></ul>");
+        assert!(node_eq(&el.children()[1], two));
+        assert_eq!(recording.stats().creates, 0);
+        assert_eq!(recording.stats().removals, 2);
+        assert_eq!(views.load(Ordering::Relaxed), 4);
+    }
+}
@@ -0,0 +1,271 @@
+use super::{ReactiveFunction, RenderEffectState, WithAttr};
+use crate::{
+    html::attribute::Attribute,
+    hydration::Cursor,
+    ssr::{BranchMarks, StreamBuilder},
+    view::{
+        add_attr::AddAnyAttr, Mountable, Position, PositionState, Render,
+        RenderHtml,
+    },
+};
+use reactive_graph::effect::RenderEffect;
+
+/// Creates a reactive view that only updates the DOM when the value it renders has changed.
+///
+/// A reactive function rebuilds its view every time one of its dependencies changes, even if it
+/// produces the same value as before. This keeps the previously-rendered value, and skips
+/// rebuilding whenever the new value is equal to it:
+/// ```rust,ignore
+/// // only touches the text node when the parity actually changes
+/// p().child(memoized(move || {
+///     if count.get() % 2 == 0 { "even" } else { "odd" }
+/// }))
+/// ```
+/// Unlike wrapping the source in a [`Memo`](reactive_graph::computed::Memo), this compares at the
+/// render boundary, so it also works for closures that combine several signals.
+pub fn memoized<F, V>(fun: F) -> Memoized<F>
+where
+    F: ReactiveFunction<Output = V>,
+    V: PartialEq + Clone,
+{
+    Memoized(fun)
+}
+
+/// A reactive view that skips rebuilding when its value is unchanged.
+///
+/// This is created with [`memoized`].
+#[derive(Debug, Clone)]
+pub struct Memoized<F>(F);
+
+/// Retained view state for a [`Memoized`] view.
+pub struct MemoizedState<V, S> {
+    value: V,
+    state: S,
+}
+
+impl<V, S> MemoizedState<V, S> {
+    fn update(mut self, value: V) -> Self
+    where
+        V: Render<State = S> + PartialEq + Clone,
+    {
+        if value != self.value {
+            value.clone().rebuild(&mut self.state);
+            self.value = value;
+        }
+        self
+    }
+}
+
+impl<V, S> Mountable for MemoizedState<V, S>
+where
+    S: Mountable,
+{
+    fn unmount(&mut self) {
+        self.state.unmount();
+    }
+
+    fn mount(
+        &mut self,
+        parent: &crate::renderer::types::Element,
+        marker: Option<&crate::renderer::types::Node>,
+    ) {
+        self.state.mount(parent, marker);
+    }
+
+    fn insert_before_this(&self, child: &mut dyn Mountable) -> bool {
+        self.state.insert_before_this(child)
+    }
+}
+
+impl<F, V> Render for Memoized<F>
+where
+    F: ReactiveFunction<Output = V>,
+    V: Render + PartialEq + Clone + 'static,
+    V::State: 'static,
+{
+    type State = RenderEffectState<MemoizedState<V, V::State>>;
+
+    #[track_caller]
+    fn build(mut self) -> Self::State {
+        RenderEffect::new(move |prev: Option<MemoizedState<V, V::State>>| {
+            let value = self.0.invoke();
+            match prev {
+                Some(prev) => prev.update(value),
+                None => MemoizedState {
+                    state: value.clone().build(),
+                    value,
+                },
+            }
+        })
+        .into()
+    }
+
+    #[track_caller]
+    fn rebuild(mut self, state: &mut Self::State) {
+        match state.0.as_ref().and_then(|effect| effect.take_value()) {
+            Some(prev) => {
+                *state = RenderEffect::new_with_value(
+                    move |prev: Option<MemoizedState<V, V::State>>| {
+                        let value = self.0.invoke();
+                        match prev {
+                            Some(prev) => prev.update(value),
+                            None => unreachable!(),
+                        }
+                    },
+                    Some(prev),
+                )
+                .into();
+            }
+            None => {
+                let new = self.build();
+                let mut old = std::mem::replace(state, new);
+                old.insert_before_this(state);
+                old.unmount();
+            }
+        }
+    }
+}
+
+impl<F, V> AddAnyAttr for Memoized<F>
+where
+    F: ReactiveFunction<Output = V>,
+    V: RenderHtml + PartialEq + Clone + 'static,
+{
+    type Output<SomeNewAttr: Attribute> =
+        WithAttr<F, SomeNewAttr::CloneableOwned>;
+
+    fn add_any_attr<NewAttr: Attribute>(
+        self,
+        attr: NewAttr,
+    ) -> Self::Output<NewAttr>
+    where
+        Self::Output<NewAttr>: RenderHtml,
+    {
+        WithAttr::new(self.0, attr.into_cloneable_owned())
+    }
+}
+
+impl<F, V> RenderHtml for Memoized<F>
+where
+    F: ReactiveFunction<Output = V>,
+    V: RenderHtml + PartialEq + Clone + 'static,
+    V::State: 'static,
+{
+    type AsyncOutput = V::AsyncOutput;
+
+    const MIN_LENGTH: usize = 0;
+
+    fn dry_resolve(&mut self) {
+        self.0.invoke().dry_resolve();
+    }
+
+    async fn resolve(mut self) -> Self::AsyncOutput {
+        self.0.invoke().resolve().await
+    }
+
+    fn html_len(&self) -> usize {
+        V::MIN_LENGTH
+    }
+
+    fn to_html_with_buf(
+        mut self,
+        buf: &mut String,
+        position: &mut Position,
+        escape: bool,
+        mark_branches: &BranchMarks,
+    ) {
+        self.0
+            .invoke()
+            .to_html_with_buf(buf, position, escape, mark_branches);
+    }
+
+    fn to_html_async_with_buf<const OUT_OF_ORDER: bool>(
+        mut self,
+        buf: &mut StreamBuilder,
+        position: &mut Position,
+        escape: bool,
+        mark_branches: &BranchMarks,
+    ) where
+        Self: Sized,
+    {
+        self.0.invoke().to_html_async_with_buf::<OUT_OF_ORDER>(
+            buf,
+            position,
+            escape,
+            mark_branches,
+        );
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(
+        mut self,
+        cursor: &Cursor,
+        position: &PositionState,
+    ) -> Self::State {
+        let cursor = cursor.clone();
+        let position = position.clone();
+        RenderEffect::new(move |prev: Option<MemoizedState<V, V::State>>| {
+            let value = self.0.invoke();
+            match prev {
+                Some(prev) => prev.update(value),
+                None => MemoizedState {
+                    state: value
+                        .clone()
+                        .hydrate::<FROM_SERVER>(&cursor, &position),
+                    value,
+                },
+            }
+        })
+        .into()
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::memoized;
+    use crate::{
+        html::element::{p, ElementChild},
+        renderer::{mock_dom::node_eq, recording::RecordingRenderer},
+        view::Render,
+    };
+    use any_spawner::Executor;
+    use reactive_graph::{
+        owner::Owner,
+        signal::RwSignal,
+        traits::{Get, Set},
+    };
+    use std::sync::Arc;
+
+    #[test]
+    fn memoized_skips_text_writes_for_equal_values() {
+        _ = Executor::init_manual();
+        let owner = Owner::new();
+        owner.set();
+
+        // an `Arc<str>` is only compared by pointer when it is rebuilt, so a fresh one with the
+        // same text would be written again without `memoized`
+        let count = RwSignal::new(0);
+        let parity = move || -> Arc<str> {
+            if count.get() % 2 == 0 { "even" } else { "odd" }.into()
+        };
+        let plain = p().child(parity).build();
+        let el = p().child(memoized(parity)).build();
+        let text = el.children()[0].clone();
+
+        let recording = RecordingRenderer::start();
+        count.set(2);
+        Executor::flush();
+        // only the text of the plain closure is written
+        assert_eq!(recording.stats().text_writes, 1);
+        assert_eq!(recording.stats().creates, 0);
+        assert!(node_eq(&el.children()[0], &text));
+        assert_eq!(el.to_debug_html(), "<p>even</p>");
+        assert_eq!(plain.to_debug_html(), "<p>even</p>");
+
+        let recording = RecordingRenderer::start();
+        count.set(3);
+        Executor::flush();
+        assert_eq!(recording.stats().text_writes, 2);
+        assert!(node_eq(&el.children()[0], &text));
+        assert_eq!(el.to_debug_html(), "<p>odd</p>");
+    }
+}
@@ -2,33 +2,55 @@ use crate::{
     html::attribute::{Attribute, AttributeValue},
     hydration::Cursor,
     renderer::Rndr,
-    ssr::StreamBuilder,
+    ssr::{BranchMarks, StreamBuilder},
     view::{
         add_attr::AddAnyAttr, Mountable, Position, PositionState, Render,
         RenderHtml, ToTemplate,
     },
 };
-use reactive_graph::effect::RenderEffect;
+use futures::future::{AbortHandle, Abortable};
+use reactive_graph::{effect::RenderEffect, signal::guards::ReadGuard};
+use send_wrapper::SendWrapper;
 use std::{
+    borrow::Cow,
     cell::RefCell,
     future::Future,
+    ops::Deref,
     rc::Rc,
     sync::{Arc, Mutex},
 };
 
+mod async_derived;
+mod batch;
 /// Types for two way data binding.
 pub mod bind;
 mod class;
 mod inner_html;
+mod keyed;
+mod memoized;
 /// Provides a reactive [`NodeRef`](node_ref::NodeRef) type.
 pub mod node_ref;
 mod owned;
 mod property;
+mod result;
+#[cfg(feature = "reactive_stores")]
+mod stores;
 mod style;
+mod suspend_stream;
 mod suspense;
+mod untrack;
+mod visible;
 
+pub use batch::*;
+pub use inner_html::*;
+pub use keyed::*;
+pub use memoized::*;
 pub use owned::*;
+pub use result::*;
+pub use suspend_stream::*;
 pub use suspense::*;
+pub use untrack::*;
+pub use visible::*;
 
 impl<F, V> ToTemplate for F
 where
@@ -72,20 +94,71 @@ where
     }
 
     #[track_caller]
-    fn rebuild(self, state: &mut Self::State) {
-        let new = self.build();
-        let mut old = std::mem::replace(state, new);
-        old.insert_before_this(state);
-        old.unmount();
+    fn rebuild(mut self, state: &mut Self::State) {
+        match state.0.as_ref().and_then(|effect| effect.take_value()) {
+            // if the old effect has already produced a value, the new effect takes it over and
+            // rebuilds it in place, so that it stays in the same position in the DOM
+            Some(prev) => {
+                *state = RenderEffect::new_with_value(
+                    move |prev| {
+                        let value = self.invoke();
+                        if let Some(mut state) = prev {
+                            value.rebuild(&mut state);
+                            state
+                        } else {
+                            value.build()
+                        }
+                    },
+                    Some(prev),
+                )
+                .into();
+            }
+            // otherwise, there's nothing to rebuild: build a new view and mount it in the old
+            // one's place, which is held by a placeholder if the old one was mounted empty
+            None => {
+                let new = self.build();
+                let mut old = std::mem::replace(state, new);
+                old.insert_before_this(state);
+                old.unmount();
+            }
+        }
     }
 }
 
 /// Retained view state for a [`RenderEffect`].
-pub struct RenderEffectState<T: 'static>(Option<RenderEffect<T>>);
+///
+/// If the effect has no value when it is mounted, a placeholder is mounted in its place, so that a
+/// view rebuilt over this state can be mounted in the same position.
+pub struct RenderEffectState<T: 'static>(
+    Option<RenderEffect<T>>,
+    Option<crate::renderer::types::Placeholder>,
+);
+
+impl<T> RenderEffectState<T> {
+    /// Creates a state with no effect, which renders nothing.
+    pub(crate) fn empty() -> Self {
+        Self(None, None)
+    }
+
+    /// Mutably accesses the current value of the effect, if it has produced one.
+    pub fn with_value_mut<U>(
+        &self,
+        fun: impl FnOnce(&mut T) -> U,
+    ) -> Option<U> {
+        self.0
+            .as_ref()
+            .and_then(|effect| effect.with_value_mut(fun))
+    }
+
+    /// Takes the inner [`RenderEffect`], consuming the state.
+    pub fn take(self) -> Option<RenderEffect<T>> {
+        self.0
+    }
+}
 
 impl<T> From<RenderEffect<T>> for RenderEffectState<T> {
     fn from(value: RenderEffect<T>) -> Self {
-        Self(Some(value))
+        Self(Some(value), None)
     }
 }
 
@@ -97,6 +170,9 @@ where
         if let Some(ref mut inner) = self.0 {
             inner.unmount();
         }
+        if let Some(ref mut placeholder) = self.1 {
+            placeholder.unmount();
+        }
     }
 
     fn mount(
@@ -104,16 +180,24 @@ where
         parent: &crate::renderer::types::Element,
         marker: Option<&crate::renderer::types::Node>,
     ) {
-        if let Some(ref mut inner) = self.0 {
-            inner.mount(parent, marker);
+        let mounted = self.0.as_ref().and_then(|inner| {
+            inner.with_value_mut(|state| state.mount(parent, marker))
+        });
+        if mounted.is_none() {
+            self.1
+                .get_or_insert_with(Rndr::create_placeholder)
+                .mount(parent, marker);
         }
     }
 
     fn insert_before_this(&self, child: &mut dyn Mountable) -> bool {
-        if let Some(inner) = &self.0 {
-            inner.insert_before_this(child)
-        } else {
-            false
+        let inserted = self.0.as_ref().and_then(|inner| {
+            inner.with_value_mut(|state| state.insert_before_this(child))
+        });
+        match (inserted, &self.1) {
+            (Some(inserted), _) => inserted,
+            (None, Some(placeholder)) => placeholder.insert_before_this(child),
+            (None, None) => false,
         }
     }
 }
@@ -136,6 +220,9 @@ where
         self.invoke().resolve().await
     }
 
+    // the function's value can't be known without calling it, which needs `&mut self` and would
+    // run it a second time; pages that are mostly closures can pass the size of an earlier render
+    // to `to_html_with_capacity_hint` instead
     fn html_len(&self) -> usize {
         V::MIN_LENGTH
     }
@@ -145,7 +232,7 @@ where
         buf: &mut String,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) {
         let value = self.invoke();
         value.to_html_with_buf(buf, position, escape, mark_branches)
@@ -156,7 +243,7 @@ where
         buf: &mut StreamBuilder,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) where
         Self: Sized,
     {
@@ -195,17 +282,51 @@ where
     V: RenderHtml + 'static,
 {
     type Output<SomeNewAttr: Attribute> =
-        Box<dyn FnMut() -> V::Output<SomeNewAttr::CloneableOwned> + Send>;
+        WithAttr<F, SomeNewAttr::CloneableOwned>;
 
     fn add_any_attr<NewAttr: Attribute>(
-        mut self,
+        self,
         attr: NewAttr,
     ) -> Self::Output<NewAttr>
     where
         Self::Output<NewAttr>: RenderHtml,
     {
-        let attr = attr.into_cloneable_owned();
-        Box::new(move || self.invoke().add_any_attr(attr.clone()))
+        WithAttr::new(self, attr.into_cloneable_owned())
+    }
+}
+
+/// A reactive function that spreads an attribute onto each view it returns.
+///
+/// This is the output of spreading attributes onto a reactive function. It is itself a
+/// [`ReactiveFunction`], so it renders like any other closure child, but keeps both the function
+/// and the attribute as concrete types rather than boxing a new closure.
+#[derive(Debug, Clone)]
+pub struct WithAttr<F, A> {
+    fun: F,
+    attr: A,
+}
+
+impl<F, A> WithAttr<F, A> {
+    /// Wraps the reactive function, adding a clone of `attr` to every view it creates.
+    pub fn new(fun: F, attr: A) -> Self {
+        Self { fun, attr }
+    }
+}
+
+impl<F, V, A> ReactiveFunction for WithAttr<F, A>
+where
+    F: ReactiveFunction<Output = V>,
+    V: RenderHtml + 'static,
+    A: Attribute + Clone + Send + 'static,
+{
+    type Output = V::Output<A>;
+
+    fn invoke(&mut self) -> Self::Output {
+        self.fun.invoke().add_any_attr(self.attr.clone())
+    }
+
+    fn into_shared(mut self) -> Arc<Mutex<dyn FnMut() -> Self::Output + Send>> {
+        Arc::new(Mutex::new(move || self.invoke()))
     }
 }
 
@@ -233,13 +354,20 @@ where
     }
 }
 
+/// Mounts whichever side of the result is present.
+///
+/// The error side is mounted as well, so that it can hold a placeholder (such as a
+/// [`Placeholder`](crate::renderer::types::Placeholder)) that keeps this position in the DOM.
+/// Without one, siblings that insert themselves before an errored child would lose their anchor.
 impl<M, E> Mountable for Result<M, E>
 where
     M: Mountable,
+    E: Mountable,
 {
     fn unmount(&mut self) {
-        if let Ok(ref mut inner) = self {
-            inner.unmount();
+        match self {
+            Ok(inner) => inner.unmount(),
+            Err(placeholder) => placeholder.unmount(),
         }
     }
 
@@ -248,16 +376,16 @@ where
         parent: &crate::renderer::types::Element,
         marker: Option<&crate::renderer::types::Node>,
     ) {
-        if let Ok(ref mut inner) = self {
-            inner.mount(parent, marker);
+        match self {
+            Ok(inner) => inner.mount(parent, marker),
+            Err(placeholder) => placeholder.mount(parent, marker),
         }
     }
 
     fn insert_before_this(&self, child: &mut dyn Mountable) -> bool {
-        if let Ok(inner) = &self {
-            inner.insert_before_this(child)
-        } else {
-            false
+        match self {
+            Ok(inner) => inner.insert_before_this(child),
+            Err(placeholder) => placeholder.insert_before_this(child),
         }
     }
 }
@@ -274,6 +402,7 @@ where
     type Cloneable = SharedReactiveFunction<V>;
     type CloneableOwned = SharedReactiveFunction<V>;
 
+    // like a reactive view, the value isn't known until the function is called
     fn html_len(&self) -> usize {
         0
     }
@@ -286,78 +415,315 @@ where
     fn to_template(_key: &str, _buf: &mut String) {}
 
     fn hydrate<const FROM_SERVER: bool>(
-        mut self,
+        self,
         key: &str,
         el: &crate::renderer::types::Element,
     ) -> Self::State {
-        let key = Rndr::intern(key);
-        let key = key.to_owned();
-        let el = el.to_owned();
+        hydrate_reactive_attribute::<FROM_SERVER, _, _>(
+            self,
+            Cow::Owned(Rndr::intern(key).to_owned()),
+            el,
+        )
+    }
 
-        RenderEffect::new(move |prev| {
-            let value = self.invoke();
+    fn build(
+        self,
+        el: &crate::renderer::types::Element,
+        key: &str,
+    ) -> Self::State {
+        build_reactive_attribute(
+            self,
+            el,
+            Cow::Owned(Rndr::intern(key).to_owned()),
+        )
+    }
+
+    fn rebuild(self, key: &str, state: &mut Self::State) {
+        rebuild_reactive_attribute(
+            self,
+            Cow::Owned(Rndr::intern(key).to_owned()),
+            state,
+        )
+    }
+
+    fn hydrate_static<const FROM_SERVER: bool>(
+        self,
+        key: &'static str,
+        el: &crate::renderer::types::Element,
+    ) -> Self::State {
+        hydrate_reactive_attribute::<FROM_SERVER, _, _>(
+            self,
+            Cow::Borrowed(Rndr::intern(key)),
+            el,
+        )
+    }
+
+    fn build_static(
+        self,
+        el: &crate::renderer::types::Element,
+        key: &'static str,
+    ) -> Self::State {
+        build_reactive_attribute(self, el, Cow::Borrowed(Rndr::intern(key)))
+    }
+
+    fn rebuild_static(self, key: &'static str, state: &mut Self::State) {
+        rebuild_reactive_attribute(
+            self,
+            Cow::Borrowed(Rndr::intern(key)),
+            state,
+        )
+    }
+
+    fn into_cloneable(self) -> Self::Cloneable {
+        self.into_shared()
+    }
+
+    fn into_cloneable_owned(self) -> Self::CloneableOwned {
+        self.into_shared()
+    }
+
+    fn dry_resolve(&mut self) {
+        self.invoke();
+    }
+
+    async fn resolve(mut self) -> Self::AsyncOutput {
+        self.invoke().resolve().await
+    }
+}
+
+// the effect keeps the key for as long as the attribute exists, so keys known at compile time are
+// borrowed, and only dynamic keys are copied
+fn hydrate_reactive_attribute<const FROM_SERVER: bool, F, V>(
+    mut f: F,
+    key: Cow<'static, str>,
+    el: &crate::renderer::types::Element,
+) -> RenderEffect<V::State>
+where
+    F: ReactiveFunction<Output = V>,
+    V: AttributeValue + 'static,
+    V::State: 'static,
+{
+    let el = el.to_owned();
+    RenderEffect::new(move |prev| {
+        let value = f.invoke();
+        if let Some(mut state) = prev {
+            value.rebuild(&key, &mut state);
+            state
+        } else {
+            value.hydrate::<FROM_SERVER>(&key, &el)
+        }
+    })
+}
+
+fn build_reactive_attribute<F, V>(
+    mut f: F,
+    el: &crate::renderer::types::Element,
+    key: Cow<'static, str>,
+) -> RenderEffect<V::State>
+where
+    F: ReactiveFunction<Output = V>,
+    V: AttributeValue + 'static,
+    V::State: 'static,
+{
+    let el = el.to_owned();
+    RenderEffect::new(move |prev| {
+        let value = f.invoke();
+        if let Some(mut state) = prev {
+            value.rebuild(&key, &mut state);
+            state
+        } else {
+            value.build(&el, &key)
+        }
+    })
+}
+
+fn rebuild_reactive_attribute<F, V>(
+    mut f: F,
+    key: Cow<'static, str>,
+    state: &mut RenderEffect<V::State>,
+) where
+    F: ReactiveFunction<Output = V>,
+    V: AttributeValue + 'static,
+    V::State: 'static,
+{
+    let prev_value = state.take_value();
+    *state = RenderEffect::new_with_value(
+        move |prev| {
+            let value = f.invoke();
             if let Some(mut state) = prev {
                 value.rebuild(&key, &mut state);
                 state
             } else {
-                value.hydrate::<FROM_SERVER>(&key, &el)
+                unreachable!()
             }
-        })
+        },
+        prev_value,
+    );
+}
+
+/// Renders a borrowed string directly from a read guard, without cloning the value.
+///
+/// The attribute is set from the borrow while the guard is still held, so no owned copy is
+/// kept as state; as a result, rebuilding always sets the attribute.
+impl<G> AttributeValue for ReadGuard<String, G>
+where
+    G: Deref<Target = String> + Send,
+{
+    type AsyncOutput = Self;
+    type State = crate::renderer::types::Element;
+    type Cloneable = Arc<str>;
+    type CloneableOwned = Arc<str>;
+
+    fn html_len(&self) -> usize {
+        self.len()
+    }
+
+    fn to_html(self, key: &str, buf: &mut String) {
+        <&str as AttributeValue>::to_html(self.as_str(), key, buf);
+    }
+
+    fn to_template(_key: &str, _buf: &mut String) {}
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        key: &str,
+        el: &crate::renderer::types::Element,
+    ) -> Self::State {
+        let (el, _) = <&str as AttributeValue>::hydrate::<FROM_SERVER>(
+            self.as_str(),
+            key,
+            el,
+        );
+        el
     }
 
     fn build(
-        mut self,
+        self,
         el: &crate::renderer::types::Element,
         key: &str,
     ) -> Self::State {
-        let key = Rndr::intern(key);
-        let key = key.to_owned();
-        let el = el.to_owned();
+        Rndr::set_attribute(el, key, self.as_str());
+        el.clone()
+    }
 
-        RenderEffect::new(move |prev| {
-            let value = self.invoke();
-            if let Some(mut state) = prev {
-                value.rebuild(&key, &mut state);
-                state
-            } else {
-                value.build(&el, &key)
-            }
-        })
+    fn rebuild(self, key: &str, state: &mut Self::State) {
+        Rndr::set_attribute(state, key, self.as_str());
     }
 
-    fn rebuild(mut self, key: &str, state: &mut Self::State) {
-        let key = Rndr::intern(key);
-        let key = key.to_owned();
-        let prev_value = state.take_value();
+    fn into_cloneable(self) -> Self::Cloneable {
+        self.as_str().into()
+    }
 
-        *state = RenderEffect::new_with_value(
-            move |prev| {
-                let value = self.invoke();
-                if let Some(mut state) = prev {
-                    value.rebuild(&key, &mut state);
-                    state
-                } else {
-                    unreachable!()
-                }
-            },
-            prev_value,
+    fn into_cloneable_owned(self) -> Self::CloneableOwned {
+        self.as_str().into()
+    }
+
+    fn dry_resolve(&mut self) {}
+
+    async fn resolve(self) -> Self::AsyncOutput {
+        self
+    }
+}
+
+/// Renders a borrowed string directly from a signal's read guard, like the one returned by
+/// `.read()`, without cloning the value.
+///
+/// These guards are not `Send`, so they need to be wrapped in a [`SendWrapper`] to be used as an
+/// attribute value, and have to be rendered on the thread that took them:
+/// ```rust,ignore
+/// div().title(move || SendWrapper::new(text.read()))
+/// ```
+impl<T, G> AttributeValue for SendWrapper<ReadGuard<T, G>>
+where
+    T: AsRef<str>,
+    G: Deref<Target = T>,
+{
+    type AsyncOutput = Arc<str>;
+    type State = crate::renderer::types::Element;
+    type Cloneable = Arc<str>;
+    type CloneableOwned = Arc<str>;
+
+    fn html_len(&self) -> usize {
+        (**self).as_ref().len()
+    }
+
+    fn to_html(self, key: &str, buf: &mut String) {
+        <&str as AttributeValue>::to_html((*self).as_ref(), key, buf);
+    }
+
+    fn to_template(_key: &str, _buf: &mut String) {}
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        key: &str,
+        el: &crate::renderer::types::Element,
+    ) -> Self::State {
+        let (el, _) = <&str as AttributeValue>::hydrate::<FROM_SERVER>(
+            (*self).as_ref(),
+            key,
+            el,
         );
+        el
+    }
+
+    fn build(
+        self,
+        el: &crate::renderer::types::Element,
+        key: &str,
+    ) -> Self::State {
+        Rndr::set_attribute(el, key, (*self).as_ref());
+        el.clone()
+    }
+
+    fn rebuild(self, key: &str, state: &mut Self::State) {
+        Rndr::set_attribute(state, key, (*self).as_ref());
     }
 
     fn into_cloneable(self) -> Self::Cloneable {
-        self.into_shared()
+        (*self).as_ref().into()
     }
 
     fn into_cloneable_owned(self) -> Self::CloneableOwned {
-        self.into_shared()
+        (*self).as_ref().into()
     }
 
-    fn dry_resolve(&mut self) {
-        self.invoke();
+    fn dry_resolve(&mut self) {}
+
+    // the guard is released before the future is sent anywhere
+    fn resolve(self) -> impl Future<Output = Self::AsyncOutput> + Send {
+        let value = self.into_cloneable_owned();
+        async move { value }
     }
+}
 
-    async fn resolve(mut self) -> Self::AsyncOutput {
-        self.invoke().resolve().await
+/// Retained attribute state for a [`Suspend`].
+///
+/// Holds a handle to the most recently spawned future, so that rebuilding the attribute can
+/// cancel a pending future that would otherwise resolve later and overwrite a newer value.
+pub struct SuspendAttributeState<T> {
+    el: crate::renderer::types::Element,
+    value: Rc<RefCell<Option<T>>>,
+    pending: Option<AbortHandle>,
+}
+
+impl<T> SuspendAttributeState<T> {
+    fn new(el: &crate::renderer::types::Element) -> Self {
+        Self {
+            el: el.to_owned(),
+            value: Rc::new(RefCell::new(None)),
+            pending: None,
+        }
+    }
+
+    fn spawn(&mut self, fut: impl Future<Output = ()> + 'static) {
+        if let Some(prev) = self.pending.take() {
+            prev.abort();
+        }
+        let (abort_handle, abort_registration) = AbortHandle::new_pair();
+        self.pending = Some(abort_handle);
+        reactive_graph::spawn_local_scoped(async move {
+            _ = Abortable::new(fut, abort_registration).await;
+        });
     }
 }
 
@@ -367,7 +733,7 @@ where
     V: AttributeValue + 'static,
     V::State: 'static,
 {
-    type State = Rc<RefCell<Option<V::State>>>;
+    type State = SuspendAttributeState<V::State>;
     type AsyncOutput = V;
     type Cloneable = ();
     type CloneableOwned = ();
@@ -392,11 +758,11 @@ where
     ) -> Self::State {
         let key = key.to_owned();
         let el = el.to_owned();
-        let state = Rc::new(RefCell::new(None));
-        reactive_graph::spawn_local_scoped({
-            let state = Rc::clone(&state);
+        let mut state = SuspendAttributeState::new(&el);
+        state.spawn({
+            let value = Rc::clone(&state.value);
             async move {
-                *state.borrow_mut() =
+                *value.borrow_mut() =
                     Some(self.inner.await.hydrate::<FROM_SERVER>(&key, &el));
                 self.subscriber.forward();
             }
@@ -411,11 +777,11 @@ where
     ) -> Self::State {
         let key = key.to_owned();
         let el = el.to_owned();
-        let state = Rc::new(RefCell::new(None));
-        reactive_graph::spawn_local_scoped({
-            let state = Rc::clone(&state);
+        let mut state = SuspendAttributeState::new(&el);
+        state.spawn({
+            let value = Rc::clone(&state.value);
             async move {
-                *state.borrow_mut() = Some(self.inner.await.build(&el, &key));
+                *value.borrow_mut() = Some(self.inner.await.build(&el, &key));
                 self.subscriber.forward();
             }
         });
@@ -424,13 +790,18 @@ where
 
     fn rebuild(self, key: &str, state: &mut Self::State) {
         let key = key.to_owned();
-        reactive_graph::spawn_local_scoped({
-            let state = Rc::clone(state);
+        let el = state.el.clone();
+        // spawning here aborts any future from a previous build or rebuild that is still
+        // pending, so that a stale value can never overwrite this one
+        state.spawn({
+            let state = Rc::clone(&state.value);
             async move {
                 let value = self.inner.await;
                 let mut state = state.borrow_mut();
-                if let Some(state) = state.as_mut() {
-                    value.rebuild(&key, state);
+                match state.as_mut() {
+                    Some(state) => value.rebuild(&key, state),
+                    // the initial future was cancelled before it resolved
+                    None => *state = Some(value.build(&el, &key)),
                 }
                 self.subscriber.forward();
             }
@@ -474,6 +845,8 @@ impl<T: 'static> ReactiveFunction for Arc<Mutex<dyn FnMut() -> T + Send>> {
 
     fn invoke(&mut self) -> Self::Output {
         let mut fun = self.lock().expect("lock poisoned");
+        #[cfg(debug_assertions)]
+        let _guard = reactive_graph::diagnostics::allow_untracked_read();
         fun()
     }
 
@@ -489,6 +862,10 @@ where
     type Output = T;
 
     fn invoke(&mut self) -> Self::Output {
+        // reads inside a reactive function are not reported as untracked, even when it is
+        // rendered without an effect (for example, during server rendering)
+        #[cfg(debug_assertions)]
+        let _guard = reactive_graph::diagnostics::allow_untracked_read();
         self()
     }
 
@@ -500,10 +877,11 @@ where
 #[cfg(not(feature = "nightly"))]
 mod stable {
     use super::RenderEffectState;
+    use crate::renderer::Rndr;
     use crate::{
         html::attribute::{Attribute, AttributeValue},
         hydration::Cursor,
-        ssr::StreamBuilder,
+        ssr::{BranchMarks, StreamBuilder},
         view::{
             add_attr::AddAnyAttr, Mountable, Position, PositionState, Render,
             RenderHtml,
@@ -514,15 +892,118 @@ mod stable {
         effect::RenderEffect,
         owner::Storage,
         signal::{ArcReadSignal, ArcRwSignal, ReadSignal, RwSignal},
-        traits::Get,
-        wrappers::read::{ArcSignal, MaybeSignal, Signal},
+        traits::{DefinedAt, Get, IsDisposed, WithUntracked},
+        wrappers::read::{
+            ArcMappedSignal, ArcSignal, MappedSignal, MaybeSignal, Signal,
+        },
     };
+    use std::{borrow::Cow, mem, panic::Location};
+
+    /// Logs that a view skipped reading a signal because it has been disposed.
+    ///
+    /// This can happen if a view outlives the owner of a signal it renders, for example while a
+    /// component is being torn down; the view then keeps its current contents.
+    fn warn_disposed(defined_at: Option<&'static Location<'static>>) {
+        match defined_at {
+            Some(defined_at) => reactive_graph::log_warning(format_args!(
+                "A view tried to render the signal defined at {defined_at} \
+                 after it was disposed, and was not updated."
+            )),
+            None => reactive_graph::log_warning(format_args!(
+                "A view tried to render a signal after it was disposed, and \
+                 was not updated."
+            )),
+        }
+    }
+
+    // the first run creates the view from the signal's value (callers check that it has not been
+    // disposed), and later runs update it, unless the signal has been disposed in the meantime
+    #[track_caller]
+    fn signal_effect<S, V>(
+        signal: S,
+        mut create: impl FnMut(V) -> V::State + 'static,
+        prev: Option<V::State>,
+    ) -> RenderEffect<V::State>
+    where
+        S: Get<Value = V> + 'static,
+        V: Render,
+        V::State: 'static,
+    {
+        RenderEffect::new_with_value(
+            move |prev| match prev {
+                Some(mut state) => {
+                    match signal.try_get() {
+                        Some(value) => value.rebuild(&mut state),
+                        None => warn_disposed(signal.defined_at()),
+                    }
+                    state
+                }
+                None => create(signal.get()),
+            },
+            prev,
+        )
+    }
+
+    #[track_caller]
+    fn signal_attribute_effect<S, V>(
+        signal: S,
+        key: Cow<'static, str>,
+        mut create: impl FnMut(V, &str) -> V::State + 'static,
+        prev: Option<V::State>,
+    ) -> RenderEffect<V::State>
+    where
+        S: Get<Value = V> + 'static,
+        V: AttributeValue,
+        V::State: 'static,
+    {
+        RenderEffect::new_with_value(
+            move |prev| match prev {
+                Some(mut state) => {
+                    match signal.try_get() {
+                        Some(value) => value.rebuild(&key, &mut state),
+                        None => warn_disposed(signal.defined_at()),
+                    }
+                    state
+                }
+                None => create(signal.get(), &key),
+            },
+            prev,
+        )
+    }
+
+    // takes over the previous effect's attribute state, so the attribute is now driven by this
+    // signal rather than the one it was built with
+    #[track_caller]
+    fn rebuild_signal_attribute<S, V>(
+        signal: S,
+        key: Cow<'static, str>,
+        state: &mut RenderEffect<V::State>,
+    ) where
+        S: Get<Value = V> + IsDisposed + 'static,
+        V: AttributeValue,
+        V::State: 'static,
+    {
+        if signal.is_disposed() {
+            warn_disposed(signal.defined_at());
+            return;
+        }
+        let prev = state.take_value();
+        *state = signal_attribute_effect(
+            signal,
+            key,
+            |_, _| unreachable!("the attribute has already been built"),
+            prev,
+        );
+    }
 
     macro_rules! signal_impl {
         ($sig:ident $dry_resolve:literal) => {
-            impl<V> Render for $sig<V>
+            signal_impl!($sig<> $dry_resolve);
+        };
+        ($sig:ident<$($param:ident),*> $dry_resolve:literal) => {
+            impl<V, $($param: 'static),*> Render for $sig<V, $($param),*>
             where
-                $sig<V>: Get<Value = V>,
+                $sig<V, $($param),*>: Get<Value = V>,
                 V: Render + Clone + Send + Sync + 'static,
                 V::State: 'static,
             {
@@ -535,16 +1016,13 @@ mod stable {
 
                 #[track_caller]
                 fn rebuild(self, state: &mut Self::State) {
-                    let new = self.build();
-                    let mut old = std::mem::replace(state, new);
-                    old.insert_before_this(state);
-                    old.unmount();
+                    (move || self.get()).rebuild(state)
                 }
             }
 
-            impl<V> AddAnyAttr for $sig<V>
+            impl<V, $($param: 'static),*> AddAnyAttr for $sig<V, $($param),*>
             where
-                $sig<V>: Get<Value = V>,
+                $sig<V, $($param),*>: Get<Value = V> + WithUntracked<Value = V>,
                 V: RenderHtml + Clone + Send + Sync + 'static,
                 V::State: 'static,
             {
@@ -561,9 +1039,9 @@ mod stable {
                 }
             }
 
-            impl<V> RenderHtml for $sig<V>
+            impl<V, $($param: 'static),*> RenderHtml for $sig<V, $($param),*>
             where
-                $sig<V>: Get<Value = V>,
+                $sig<V, $($param),*>: Get<Value = V> + WithUntracked<Value = V>,
                 V: RenderHtml + Clone + Send + Sync + 'static,
                 V::State: 'static,
             {
@@ -582,7 +1060,8 @@ mod stable {
                 }
 
                 fn html_len(&self) -> usize {
-                    V::MIN_LENGTH
+                    self.try_with_untracked(|value| value.html_len())
+                        .unwrap_or(V::MIN_LENGTH)
                 }
 
                 fn to_html_with_buf(
@@ -590,7 +1069,7 @@ mod stable {
                     buf: &mut String,
                     position: &mut Position,
                     escape: bool,
-                    mark_branches: bool,
+                    mark_branches: &BranchMarks,
                 ) {
                     let value = self.get();
                     value.to_html_with_buf(buf, position, escape, mark_branches)
@@ -601,7 +1080,7 @@ mod stable {
                     buf: &mut StreamBuilder,
                     position: &mut Position,
                     escape: bool,
-                    mark_branches: bool,
+                    mark_branches: &BranchMarks,
                 ) where
                     Self: Sized,
                 {
@@ -624,9 +1103,9 @@ mod stable {
                 }
             }
 
-            impl<V> AttributeValue for $sig<V>
+            impl<V, $($param: 'static),*> AttributeValue for $sig<V, $($param),*>
             where
-                $sig<V>: Get<Value = V>,
+                $sig<V, $($param),*>: Get<Value = V> + WithUntracked<Value = V>,
                 V: AttributeValue + Clone + Send + Sync + 'static,
                 V::State: 'static,
             {
@@ -636,7 +1115,8 @@ mod stable {
                 type CloneableOwned = Self;
 
                 fn html_len(&self) -> usize {
-                    0
+                    self.try_with_untracked(|value| value.html_len())
+                        .unwrap_or_default()
                 }
 
                 fn to_html(self, key: &str, buf: &mut String) {
@@ -662,10 +1142,36 @@ mod stable {
                     (move || self.get()).build(el, key)
                 }
 
+                // takes over the previous effect's attribute state, so the attribute is now driven
+                // by this signal rather than the one it was built with
                 fn rebuild(self, key: &str, state: &mut Self::State) {
                     (move || self.get()).rebuild(key, state)
                 }
 
+                fn hydrate_static<const FROM_SERVER: bool>(
+                    self,
+                    key: &'static str,
+                    el: &crate::renderer::types::Element,
+                ) -> Self::State {
+                    (move || self.get()).hydrate_static::<FROM_SERVER>(key, el)
+                }
+
+                fn build_static(
+                    self,
+                    el: &crate::renderer::types::Element,
+                    key: &'static str,
+                ) -> Self::State {
+                    (move || self.get()).build_static(el, key)
+                }
+
+                fn rebuild_static(
+                    self,
+                    key: &'static str,
+                    state: &mut Self::State,
+                ) {
+                    (move || self.get()).rebuild_static(key, state)
+                }
+
                 fn into_cloneable(self) -> Self::Cloneable {
                     self
                 }
@@ -685,9 +1191,12 @@ mod stable {
 
     macro_rules! signal_impl_arena {
         ($sig:ident $dry_resolve:literal) => {
-            impl<V, S> Render for $sig<V, S>
+            signal_impl_arena!($sig<> $dry_resolve);
+        };
+        ($sig:ident<$($param:ident),*> $dry_resolve:literal) => {
+            impl<V, $($param: 'static,)* S> Render for $sig<V, $($param,)* S>
             where
-                $sig<V, S>: Get<Value = V>,
+                $sig<V, $($param,)* S>: Get<Value = V> + IsDisposed,
                 S: Send + Sync + 'static,
                 S: Storage<V> + Storage<Option<V>>,
                 V: Render + Send + Sync + Clone + 'static,
@@ -697,27 +1206,53 @@ mod stable {
 
                 #[track_caller]
                 fn build(self) -> Self::State {
-                    (move || self.get()).build()
+                    if self.is_disposed() {
+                        warn_disposed(self.defined_at());
+                        return RenderEffectState::empty();
+                    }
+                    signal_effect(self, V::build, None).into()
                 }
 
                 #[track_caller]
                 fn rebuild(self, state: &mut Self::State) {
-                    let new = self.build();
-                    let mut old = std::mem::replace(state, new);
-                    old.insert_before_this(state);
-                    old.unmount();
+                    if self.is_disposed() {
+                        warn_disposed(self.defined_at());
+                        // like a new view of the disposed signal, this is left empty
+                        let mut old =
+                            mem::replace(state, RenderEffectState::empty());
+                        old.insert_before_this(state);
+                        old.unmount();
+                        return;
+                    }
+                    match state
+                        .0
+                        .as_ref()
+                        .and_then(|effect| effect.take_value())
+                    {
+                        Some(prev) => {
+                            *state = signal_effect(self, V::build, Some(prev))
+                                .into();
+                        }
+                        None => {
+                            let new = self.build();
+                            let mut old = mem::replace(state, new);
+                            old.insert_before_this(state);
+                            old.unmount();
+                        }
+                    }
                 }
             }
 
-            impl<V, S> AddAnyAttr for $sig<V, S>
+            impl<V, $($param: 'static,)* S> AddAnyAttr for $sig<V, $($param,)* S>
             where
-                $sig<V, S>: Get<Value = V>,
+                $sig<V, $($param,)* S>:
+                    Get<Value = V> + IsDisposed + WithUntracked<Value = V>,
                 S: Send + Sync + 'static,
                 S: Storage<V> + Storage<Option<V>>,
                 V: RenderHtml + Clone + Send + Sync + 'static,
                 V::State: 'static,
             {
-                type Output<SomeNewAttr: Attribute> = $sig<V, S>;
+                type Output<SomeNewAttr: Attribute> = $sig<V, $($param,)* S>;
 
                 fn add_any_attr<NewAttr: Attribute>(
                     self,
@@ -730,9 +1265,10 @@ mod stable {
                 }
             }
 
-            impl<V, S> RenderHtml for $sig<V, S>
+            impl<V, $($param: 'static,)* S> RenderHtml for $sig<V, $($param,)* S>
             where
-                $sig<V, S>: Get<Value = V>,
+                $sig<V, $($param,)* S>:
+                    Get<Value = V> + IsDisposed + WithUntracked<Value = V>,
                 S: Send + Sync + 'static,
                 S: Storage<V> + Storage<Option<V>>,
                 V: RenderHtml + Clone + Send + Sync + 'static,
@@ -744,7 +1280,7 @@ mod stable {
 
                 fn dry_resolve(&mut self) {
                     if $dry_resolve {
-                        _ = self.get();
+                        _ = self.try_get();
                     }
                 }
 
@@ -753,7 +1289,8 @@ mod stable {
                 }
 
                 fn html_len(&self) -> usize {
-                    V::MIN_LENGTH
+                    self.try_with_untracked(|value| value.html_len())
+                        .unwrap_or(V::MIN_LENGTH)
                 }
 
                 fn to_html_with_buf(
@@ -761,10 +1298,17 @@ mod stable {
                     buf: &mut String,
                     position: &mut Position,
                     escape: bool,
-                    mark_branches: bool,
+                    mark_branches: &BranchMarks,
                 ) {
-                    let value = self.get();
-                    value.to_html_with_buf(buf, position, escape, mark_branches)
+                    match self.try_get() {
+                        Some(value) => value.to_html_with_buf(
+                            buf,
+                            position,
+                            escape,
+                            mark_branches,
+                        ),
+                        None => warn_disposed(self.defined_at()),
+                    }
                 }
 
                 fn to_html_async_with_buf<const OUT_OF_ORDER: bool>(
@@ -772,17 +1316,20 @@ mod stable {
                     buf: &mut StreamBuilder,
                     position: &mut Position,
                     escape: bool,
-                    mark_branches: bool,
+                    mark_branches: &BranchMarks,
                 ) where
                     Self: Sized,
                 {
-                    let value = self.get();
-                    value.to_html_async_with_buf::<OUT_OF_ORDER>(
-                        buf,
-                        position,
-                        escape,
-                        mark_branches,
-                    );
+                    match self.try_get() {
+                        Some(value) => value
+                            .to_html_async_with_buf::<OUT_OF_ORDER>(
+                                buf,
+                                position,
+                                escape,
+                                mark_branches,
+                            ),
+                        None => warn_disposed(self.defined_at()),
+                    }
                 }
 
                 fn hydrate<const FROM_SERVER: bool>(
@@ -790,14 +1337,23 @@ mod stable {
                     cursor: &Cursor,
                     position: &PositionState,
                 ) -> Self::State {
-                    (move || self.get())
-                        .hydrate::<FROM_SERVER>(cursor, position)
+                    let cursor = cursor.clone();
+                    let position = position.clone();
+                    signal_effect(
+                        self,
+                        move |value: V| {
+                            value.hydrate::<FROM_SERVER>(&cursor, &position)
+                        },
+                        None,
+                    )
+                    .into()
                 }
             }
 
-            impl<V, S> AttributeValue for $sig<V, S>
+            impl<V, $($param: 'static,)* S> AttributeValue for $sig<V, $($param,)* S>
             where
-                $sig<V, S>: Get<Value = V>,
+                $sig<V, $($param,)* S>:
+                    Get<Value = V> + IsDisposed + WithUntracked<Value = V>,
                 S: Storage<V> + Storage<Option<V>>,
                 S: Send + Sync + 'static,
                 V: AttributeValue + Send + Sync + Clone + 'static,
@@ -809,12 +1365,15 @@ mod stable {
                 type CloneableOwned = Self;
 
                 fn html_len(&self) -> usize {
-                    0
+                    self.try_with_untracked(|value| value.html_len())
+                        .unwrap_or_default()
                 }
 
                 fn to_html(self, key: &str, buf: &mut String) {
-                    let value = self.get();
-                    value.to_html(key, buf);
+                    match self.try_get() {
+                        Some(value) => value.to_html(key, buf),
+                        None => warn_disposed(self.defined_at()),
+                    }
                 }
 
                 fn to_template(_key: &str, _buf: &mut String) {}
@@ -824,7 +1383,16 @@ mod stable {
                     key: &str,
                     el: &crate::renderer::types::Element,
                 ) -> Self::State {
-                    (move || self.get()).hydrate::<FROM_SERVER>(key, el)
+                    let key = Cow::Owned(Rndr::intern(key).to_owned());
+                    let el = el.to_owned();
+                    signal_attribute_effect(
+                        self,
+                        key,
+                        move |value: V, key: &str| {
+                            value.hydrate::<FROM_SERVER>(key, &el)
+                        },
+                        None,
+                    )
                 }
 
                 fn build(
@@ -832,11 +1400,63 @@ mod stable {
                     el: &crate::renderer::types::Element,
                     key: &str,
                 ) -> Self::State {
-                    (move || self.get()).build(el, key)
+                    let key = Cow::Owned(Rndr::intern(key).to_owned());
+                    let el = el.to_owned();
+                    signal_attribute_effect(
+                        self,
+                        key,
+                        move |value: V, key: &str| value.build(&el, key),
+                        None,
+                    )
                 }
 
+                // takes over the previous effect's attribute state, so the attribute is now driven
+                // by this signal rather than the one it was built with
                 fn rebuild(self, key: &str, state: &mut Self::State) {
-                    (move || self.get()).rebuild(key, state)
+                    let key = Cow::Owned(Rndr::intern(key).to_owned());
+                    rebuild_signal_attribute(self, key, state)
+                }
+
+                fn hydrate_static<const FROM_SERVER: bool>(
+                    self,
+                    key: &'static str,
+                    el: &crate::renderer::types::Element,
+                ) -> Self::State {
+                    let el = el.to_owned();
+                    signal_attribute_effect(
+                        self,
+                        Cow::Borrowed(Rndr::intern(key)),
+                        move |value: V, key: &str| {
+                            value.hydrate::<FROM_SERVER>(key, &el)
+                        },
+                        None,
+                    )
+                }
+
+                fn build_static(
+                    self,
+                    el: &crate::renderer::types::Element,
+                    key: &'static str,
+                ) -> Self::State {
+                    let el = el.to_owned();
+                    signal_attribute_effect(
+                        self,
+                        Cow::Borrowed(Rndr::intern(key)),
+                        move |value: V, key: &str| value.build(&el, key),
+                        None,
+                    )
+                }
+
+                fn rebuild_static(
+                    self,
+                    key: &'static str,
+                    state: &mut Self::State,
+                ) {
+                    rebuild_signal_attribute(
+                        self,
+                        Cow::Borrowed(Rndr::intern(key)),
+                        state,
+                    )
                 }
 
                 fn into_cloneable(self) -> Self::Cloneable {
@@ -856,73 +1476,1948 @@ mod stable {
         };
     }
 
+    // the second argument determines whether `dry_resolve` reads the signal
+    //
+    // plain signals just hold a value, so reading them has no side effects. memos and derived
+    // signals run user code when read, which may read from resources, so they need to be read
+    // during the dry-resolve pass for those resources to register with any `Suspense`
+    //
+    // mapped signals also take the type of their source's read guard, which is listed after the name
     signal_impl_arena!(RwSignal false);
     signal_impl_arena!(ReadSignal false);
     signal_impl_arena!(Memo true);
     signal_impl_arena!(Signal true);
     signal_impl_arena!(MaybeSignal true);
+    signal_impl_arena!(MappedSignal<G> true);
     signal_impl!(ArcRwSignal false);
     signal_impl!(ArcReadSignal false);
-    signal_impl!(ArcMemo false);
+    signal_impl!(ArcMemo true);
     signal_impl!(ArcSignal true);
+    signal_impl!(ArcMappedSignal<G> true);
 }
 
-/*
-#[cfg(test)]
+#[cfg(all(test, feature = "testing"))]
 mod tests {
     use crate::{
-        html::element::{button, main, HtmlElement},
-        renderer::mock_dom::MockDom,
-        view::Render,
+        html::{
+            attribute::{
+                any_attribute::{AnyAttribute, IntoAnyAttribute},
+                custom::{custom_attribute, CustomAttribute},
+                global::{ClassAttribute, GlobalAttributes, OnAttribute},
+                id, title, Attribute, Value,
+            },
+            class::class,
+            element::{
+                a, button, custom, div, input, li, main, p, section, span,
+                table, td, tr, ul, ElementChild,
+            },
+            event::{self, on},
+        },
+        hydration::Cursor,
+        reactive_graph::{
+            batch_attributes, bind::BindAttribute, on_trigger,
+            StreamResolution, Suspend, SuspendStream,
+        },
+        renderer::{
+            mock_dom::{define_custom_element, dispatch_event, node_eq},
+            recording::{DomOp, RecordingRenderer},
+            types::Element,
+            CastFrom, Rndr,
+        },
+        ssr::{BranchMarks, HashedBranchPaths},
+        view::{
+            add_attr::AddAnyAttr, any_view::IntoAny, keyed::keyed, Mountable,
+            Position, PositionState, Render, RenderHtml,
+        },
+    };
+    use any_spawner::{CustomSpawner, Executor};
+    use either_of::Either;
+    use futures::{
+        channel::{mpsc, oneshot},
+        task::noop_waker_ref,
+        FutureExt, StreamExt,
     };
-    use leptos_reactive::{create_runtime, RwSignal, SignalGet, SignalSet};
+    use reactive_graph::{
+        computed::{
+            create_slice, suspense::SuspenseContext, ArcAsyncDerived,
+            AsyncDerived, Memo, Selector,
+        },
+        owner::{provide_context, Owner},
+        signal::{ArcRwSignal, ArcTrigger, RwSignal},
+        traits::{
+            Dispose, Get, MapRef, Notify, Set, Track, Update, WithUntracked,
+        },
+        wrappers::{read::Signal, write::Model},
+    };
+    use std::{
+        cell::RefCell,
+        future::Future,
+        pin::Pin,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc, Mutex,
+        },
+        task::{Context, Poll},
+    };
+    use throw_error::{Error as AnyError, ErrorHook, ErrorId};
+
+    // effects and other tasks only run when the test calls `Executor::flush()`, so each test can
+    // update a signal, flush, and then check the DOM
+    fn run(test: impl FnOnce()) {
+        _ = Executor::init_manual();
+        let owner = Owner::new();
+        owner.set();
+        test();
+    }
 
     #[test]
     fn create_dynamic_element() {
-        let rt = create_runtime();
-        let count = RwSignal::new(0);
-        let app: HtmlElement<_, _, _, MockDom> =
-            button((), move || count.get().to_string());
-        let el = app.build();
-        assert_eq!(el.el.to_debug_html(), "<button>0</button>");
-        rt.dispose();
+        run(|| {
+            let count = RwSignal::new(0);
+            let app = button().child(move || count.get().to_string());
+            let el = app.build();
+            assert_eq!(el.to_debug_html(), "<button>0</button>");
+        });
     }
 
     #[test]
     fn update_dynamic_element() {
-        let rt = create_runtime();
-        let count = RwSignal::new(0);
-        let app: HtmlElement<_, _, _, MockDom> =
-            button((), move || count.get().to_string());
-        let el = app.build();
-        assert_eq!(el.el.to_debug_html(), "<button>0</button>");
-        count.set(1);
-        assert_eq!(el.el.to_debug_html(), "<button>1</button>");
-        rt.dispose();
+        run(|| {
+            let count = RwSignal::new(0);
+            let app = button().child(move || count.get().to_string());
+            let el = app.build();
+            assert_eq!(el.to_debug_html(), "<button>0</button>");
+            count.set(1);
+            Executor::flush();
+            assert_eq!(el.to_debug_html(), "<button>1</button>");
+        });
     }
 
     #[test]
     fn update_dynamic_element_among_siblings() {
-        let rt = create_runtime();
-        let count = RwSignal::new(0);
-        let app: HtmlElement<_, _, _, MockDom> = main(
-            (),
-            button(
-                (),
-                ("Hello, my ", move || count.get().to_string(), " friends."),
-            ),
-        );
-        let el = app.build();
-        assert_eq!(
-            el.el.to_debug_html(),
-            "<main><button>Hello, my 0 friends.</button></main>"
-        );
-        count.set(42);
-        assert_eq!(
-            el.el.to_debug_html(),
-            "<main><button>Hello, my 42 friends.</button></main>"
-        );
-        rt.dispose();
+        run(|| {
+            let count = RwSignal::new(0);
+            let app = main().child(button().child((
+                "Hello, my ",
+                move || count.get().to_string(),
+                " friends.",
+            )));
+            let el = app.build();
+            assert_eq!(
+                el.to_debug_html(),
+                "<main><button>Hello, my 0 friends.</button></main>"
+            );
+            count.set(42);
+            Executor::flush();
+            assert_eq!(
+                el.to_debug_html(),
+                "<main><button>Hello, my 42 friends.</button></main>"
+            );
+        });
+    }
+
+    #[test]
+    fn optional_signal_attribute_is_added_removed_and_readded() {
+        run(|| {
+            let value = RwSignal::new(Some("x".to_string()));
+            let memo = Memo::new(move |_| value.get());
+            let el = div()
+                .title(Signal::<Option<String>>::from(value))
+                .attr("data-id", memo)
+                .build();
+            Executor::flush();
+            assert_eq!(el.get_attribute("title").as_deref(), Some("x"));
+            assert_eq!(el.get_attribute("data-id").as_deref(), Some("x"));
+
+            value.set(None);
+            Executor::flush();
+            assert_eq!(el.get_attribute("title"), None);
+            assert_eq!(el.get_attribute("data-id"), None);
+
+            value.set(Some("y".to_string()));
+            Executor::flush();
+            assert_eq!(el.get_attribute("title").as_deref(), Some("y"));
+            assert_eq!(el.get_attribute("data-id").as_deref(), Some("y"));
+        });
+    }
+
+    #[test]
+    fn rebuilt_signal_attribute_follows_the_new_signal() {
+        run(|| {
+            let first = RwSignal::new(String::from("/a"));
+            let second = RwSignal::new(String::from("/b"));
+            let mut state = a().href(first).build();
+            assert_eq!(state.get_attribute("href").as_deref(), Some("/a"));
+
+            a().href(second).rebuild(&mut state);
+            assert_eq!(state.get_attribute("href").as_deref(), Some("/b"));
+
+            // the old signal no longer drives the attribute
+            first.set(String::from("/a2"));
+            Executor::flush();
+            assert_eq!(state.get_attribute("href").as_deref(), Some("/b"));
+
+            second.set(String::from("/b2"));
+            Executor::flush();
+            assert_eq!(state.get_attribute("href").as_deref(), Some("/b2"));
+        });
+    }
+
+    #[test]
+    fn spread_attribute_lists_remove_dropped_attributes() {
+        run(|| {
+            let phase = RwSignal::new(0);
+            let clicks = RwSignal::new(0);
+            let attrs = move || -> Vec<AnyAttribute> {
+                match phase.get() {
+                    0 => vec![
+                        custom_attribute("data-a", "1").into_any_attr(),
+                        class("x").into_any_attr(),
+                        on(event::click, move |_| clicks.update(|n| *n += 1))
+                            .into_any_attr(),
+                    ],
+                    1 => vec![class("y").into_any_attr()],
+                    _ => vec![custom_attribute("data-b", "2").into_any_attr()],
+                }
+            };
+            let app = main().child(move || button().add_any_attr(attrs()));
+            let el = app.build();
+            let button =
+                Element::cast_from(Rndr::first_child(&el).unwrap()).unwrap();
+            dispatch_event(&button, "click");
+            assert_eq!(clicks.get(), 1);
+            assert_eq!(
+                el.to_debug_html(),
+                r#"<main><button data-a="1" class="x"><!></button></main>"#
+            );
+
+            // the custom attribute is replaced by a class, and the extra class and listener
+            // are dropped
+            phase.set(1);
+            Executor::flush();
+            assert_eq!(
+                el.to_debug_html(),
+                r#"<main><button class="y"><!></button></main>"#
+            );
+            dispatch_event(&button, "click");
+            assert_eq!(clicks.get(), 1);
+
+            phase.set(2);
+            Executor::flush();
+            assert_eq!(
+                el.to_debug_html(),
+                r#"<main><button class="" data-b="2"><!></button></main>"#
+            );
+        });
+    }
+
+    #[test]
+    fn cloneable_any_attribute_can_be_spread_on_several_elements() {
+        run(|| {
+            let attrs = vec![
+                custom_attribute("data-a", "1").into_any_attr(),
+                class("x").into_any_attr(),
+            ]
+            .into_cloneable();
+            let app = main().child((
+                span().add_any_attr(attrs.clone()),
+                span().add_any_attr(attrs),
+            ));
+            let el = app.build();
+            assert_eq!(
+                el.to_debug_html(),
+                r#"<main><span data-a="1" class="x"><!></span><span data-a="1" class="x"><!></span></main>"#
+            );
+        });
+    }
+
+    #[test]
+    fn removed_vec_items_stop_updating() {
+        run(|| {
+            let len = RwSignal::new(3);
+            let count = RwSignal::new(0);
+            let runs = Arc::new(AtomicUsize::new(0));
+            let app = ul().child({
+                let runs = Arc::clone(&runs);
+                move || {
+                    (0..len.get())
+                        .map(|_| {
+                            let runs = Arc::clone(&runs);
+                            li().child(move || {
+                                runs.fetch_add(1, Ordering::Relaxed);
+                                count.get().to_string()
+                            })
+                        })
+                        .collect::<Vec<_>>()
+                }
+            });
+            let el = app.build();
+            assert_eq!(runs.load(Ordering::Relaxed), 3);
+
+            len.set(1);
+            Executor::flush();
+            assert_eq!(el.to_debug_html(), "<ul><li>0</li><!></ul>");
+            runs.store(0, Ordering::Relaxed);
+
+            // only the remaining item's effect runs
+            count.set(1);
+            Executor::flush();
+            assert_eq!(el.to_debug_html(), "<ul><li>1</li><!></ul>");
+            assert_eq!(runs.load(Ordering::Relaxed), 1);
+
+            // reused items show the current value
+            len.set(3);
+            Executor::flush();
+            assert_eq!(
+                el.to_debug_html(),
+                "<ul><li>1</li><li>1</li><li>1</li><!></ul>"
+            );
+            count.set(2);
+            Executor::flush();
+            assert_eq!(
+                el.to_debug_html(),
+                "<ul><li>2</li><li>2</li><li>2</li><!></ul>"
+            );
+        });
+    }
+
+    #[test]
+    fn vec_reuses_removed_item_nodes_when_it_grows_back() {
+        run(|| {
+            let len = RwSignal::new(10);
+            let prefix = RwSignal::new("a");
+            let el = ul()
+                .child(move || {
+                    let prefix = prefix.get();
+                    (0..len.get())
+                        .map(|i| li().child(format!("{prefix}{i}")))
+                        .collect::<Vec<_>>()
+                })
+                .build();
+            let before = el.children();
+            assert_eq!(before.len(), 11);
+
+            len.set(8);
+            Executor::flush();
+            assert_eq!(el.children().len(), 9);
+
+            // the last two items are rebuilt into the nodes that were removed
+            let recording = RecordingRenderer::start();
+            len.set(10);
+            prefix.set("b");
+            Executor::flush();
+            assert_eq!(recording.stats().creates, 0);
+            let after = el.children();
+            assert_eq!(after.len(), 11);
+            for (after, before) in after.iter().zip(&before) {
+                assert!(node_eq(after, before));
+            }
+            assert_eq!(
+                el.to_debug_html(),
+                format!(
+                    "<ul>{}<!></ul>",
+                    (0..10)
+                        .map(|i| format!("<li>b{i}</li>"))
+                        .collect::<String>()
+                )
+            );
+        });
+    }
+
+    #[test]
+    fn dispatch_event_calls_listener() {
+        run(|| {
+            let count = RwSignal::new(0);
+            let app = main().child(
+                button()
+                    .on(event::click, move |_| count.update(|n| *n += 1))
+                    .child(move || count.get().to_string()),
+            );
+            let el = app.build();
+            let button =
+                Element::cast_from(Rndr::first_child(&el).unwrap()).unwrap();
+            dispatch_event(&button, "click");
+            dispatch_event(&button, "click");
+            Executor::flush();
+            assert_eq!(el.to_debug_html(), "<main><button>2</button></main>");
+        });
+    }
+
+    #[test]
+    fn updating_one_row_writes_one_text_node() {
+        run(|| {
+            let rows = (0..1000)
+                .map(|n| (n, RwSignal::new(format!("Row {n}"))))
+                .collect::<Vec<_>>();
+            let app = table().child(keyed(
+                rows.clone(),
+                |(n, _)| *n,
+                |_, (_, label)| {
+                    ((|_| {}), tr().child(td().child(move || label.get())))
+                },
+            ));
+            let el = app.build();
+            Executor::flush();
+
+            let recording = RecordingRenderer::start();
+            rows[500].1.set("Updated".to_string());
+            Executor::flush();
+            let stats = recording.stats();
+            assert_eq!(stats.text_writes, 1);
+            assert_eq!(stats.creates, 0);
+            assert_eq!(stats.inserts, 0);
+            assert_eq!(stats.removals, 0);
+            assert!(el.to_debug_html().contains("<tr><td>Updated</td></tr>"));
+        });
+    }
+
+    #[test]
+    fn mapped_field_in_memo_ignores_other_fields() {
+        struct User {
+            name: String,
+            age: u32,
+        }
+
+        run(|| {
+            let user = RwSignal::new(User {
+                name: "Alice".to_string(),
+                age: 30,
+            });
+            let mapped = user.map_ref(|user| &user.name);
+            let name = Memo::new(move |_| mapped.get());
+            let renders = Arc::new(AtomicUsize::new(0));
+            let el = div()
+                .child((p().child(mapped), span().child(name), {
+                    let renders = Arc::clone(&renders);
+                    move || {
+                        renders.fetch_add(1, Ordering::Relaxed);
+                        name.get()
+                    }
+                }))
+                .build();
+            Executor::flush();
+            assert_eq!(renders.load(Ordering::Relaxed), 1);
+
+            let recording = RecordingRenderer::start();
+            user.update(|user| user.age += 1);
+            Executor::flush();
+            assert_eq!(renders.load(Ordering::Relaxed), 1);
+            assert_eq!(recording.stats().text_writes, 0);
+
+            user.update(|user| user.name = "Bob".to_string());
+            Executor::flush();
+            assert_eq!(renders.load(Ordering::Relaxed), 2);
+            assert_eq!(
+                el.to_debug_html(),
+                "<div><p>Bob</p><span>Bob</span>Bob</div>"
+            );
+        });
+    }
+
+    #[test]
+    fn batched_updates_write_each_binding_once() {
+        run(|| {
+            let (a, b, c) =
+                (RwSignal::new(0), RwSignal::new(0), RwSignal::new(0));
+            let computations = Arc::new(AtomicUsize::new(0));
+            let sum = Memo::new({
+                let computations = Arc::clone(&computations);
+                move |_| {
+                    computations.fetch_add(1, Ordering::Relaxed);
+                    a.get() + b.get() + c.get()
+                }
+            });
+            let el = span().child(sum).build();
+            Executor::flush();
+            assert_eq!(computations.load(Ordering::Relaxed), 1);
+
+            let recording = RecordingRenderer::start();
+            reactive_graph::batch(|| {
+                a.set(1);
+                b.set(2);
+                c.set(3);
+            });
+            Executor::flush();
+            assert_eq!(computations.load(Ordering::Relaxed), 2);
+            assert_eq!(recording.stats().text_writes, 1);
+            assert_eq!(el.to_debug_html(), "<span>6</span>");
+        });
+    }
+
+    #[test]
+    fn batched_attributes_update_together() {
+        run(|| {
+            let count = RwSignal::new(1);
+            let row = move || {
+                div().add_any_attr(batch_attributes(move || {
+                    (
+                        id(format!("row-{}", count.get())),
+                        title(count.get().to_string()),
+                    )
+                }))
+            };
+            let phase = RwSignal::new(0);
+            let el = main()
+                .child(move || {
+                    phase.track();
+                    row()
+                })
+                .build();
+            assert_eq!(
+                el.to_debug_html(),
+                r#"<main><div id="row-1" title="1"><!></div></main>"#
+            );
+
+            count.set(2);
+            Executor::flush();
+            assert_eq!(
+                el.to_debug_html(),
+                r#"<main><div id="row-2" title="2"><!></div></main>"#
+            );
+
+            // rebuilding the element takes over the attributes, and keeps updating them
+            phase.set(1);
+            Executor::flush();
+            count.set(3);
+            Executor::flush();
+            assert_eq!(
+                el.to_debug_html(),
+                r#"<main><div id="row-3" title="3"><!></div></main>"#
+            );
+        });
+    }
+
+    #[cfg(feature = "graph-introspection")]
+    #[test]
+    fn batched_attributes_create_one_effect() {
+        use crate::html::attribute::lang;
+        use reactive_graph::introspect::{snapshot, Named};
+
+        run(|| {
+            let count = RwSignal::new(0).named("batched_attributes_count");
+            let runs = Arc::new(AtomicUsize::new(0));
+            let separate = div()
+                .id(move || count.get().to_string())
+                .title(move || count.get().to_string())
+                .lang(move || count.get().to_string())
+                .build();
+            let batched = div()
+                .add_any_attr(batch_attributes({
+                    let runs = Arc::clone(&runs);
+                    move || {
+                        runs.fetch_add(1, Ordering::Relaxed);
+                        (
+                            id(count.get().to_string()),
+                            title(count.get().to_string()),
+                            lang(count.get().to_string()),
+                        )
+                    }
+                }))
+                .build();
+
+            let graph = snapshot();
+            let count_node = graph.find("batched_attributes_count").unwrap();
+            // three effects for the separate attributes, and one for the batch
+            assert_eq!(graph.subscribers(count_node.id).count(), 4);
+
+            count.set(1);
+            Executor::flush();
+            assert_eq!(runs.load(Ordering::Relaxed), 2);
+            assert_eq!(
+                separate.to_debug_html(),
+                r#"<div id="1" title="1" lang="1"><!></div>"#
+            );
+            assert_eq!(
+                batched.to_debug_html(),
+                r#"<div id="1" title="1" lang="1"><!></div>"#
+            );
+        });
+    }
+
+    fn counter(count: RwSignal<i32>) -> impl RenderHtml {
+        main().child((
+            button()
+                .class(move || if count.get() > 2 { "big" } else { "small" })
+                .child(("Count: ", move || count.get())),
+            move || (count.get() % 2 == 0).then(|| span().child("even")),
+            ul().child(move || {
+                (0..count.get()).map(|n| li().child(n)).collect::<Vec<_>>()
+            }),
+        ))
+    }
+
+    #[test]
+    fn async_derived_renders_once_resolved_and_updates() {
+        run(|| {
+            // the first value is held back until the test sends it
+            let (tx, rx) = oneshot::channel::<()>();
+            let gate = Arc::new(Mutex::new(Some(rx)));
+            let count = RwSignal::new(1);
+            let doubled = AsyncDerived::new(move || {
+                let count = count.get();
+                let gate = gate.lock().unwrap().take();
+                async move {
+                    if let Some(gate) = gate {
+                        _ = gate.await;
+                    }
+                    count * 2
+                }
+            });
+            let tripled = ArcAsyncDerived::new(move || {
+                let count = count.get();
+                async move { count * 3 }
+            });
+            let el = p().child((doubled, " ", tripled)).build();
+            assert_eq!(el.to_debug_html(), "<p><!> 3</p>");
+
+            tx.send(()).unwrap();
+            Executor::flush();
+            assert_eq!(el.to_debug_html(), "<p>2 3</p>");
+
+            count.set(2);
+            Executor::flush();
+            assert_eq!(el.to_debug_html(), "<p>4 6</p>");
+        });
+    }
+
+    #[test]
+    fn serialized_updates_match_ssr() {
+        run(|| {
+            let count = RwSignal::new(0);
+            let root = Rndr::create_fragment();
+            let mut view = counter(count).build();
+            view.mount(&root, None);
+            assert_eq!(root.serialize(), counter(count).to_html());
+
+            for n in [1, 4, 3, 0] {
+                count.set(n);
+                Executor::flush();
+                assert_eq!(root.serialize(), counter(count).to_html());
+            }
+        });
+    }
+
+    #[test]
+    fn custom_element_bindings_become_properties_once_registered() {
+        run(|| {
+            let value = RwSignal::new("a");
+            let el = custom("rich-input")
+                .attr("value", move || value.get())
+                .build();
+            assert_eq!(el.get_attribute("value").as_deref(), Some("a"));
+            assert_eq!(el.get_property("value"), None);
+
+            define_custom_element("rich-input", &["value"]);
+            value.set("b");
+            Executor::flush();
+            assert_eq!(el.get_property("value").as_deref(), Some("b"));
+            // the attribute keeps the value it had before the element was registered
+            assert_eq!(el.get_attribute("value").as_deref(), Some("a"));
+        });
+    }
+
+    #[test]
+    fn custom_elements_only_set_their_own_properties() {
+        run(|| {
+            define_custom_element("rich-label", &["text"]);
+            let el = custom("rich-label")
+                .attr("text", || "hi")
+                .attr("title", || "label")
+                .build();
+            assert_eq!(el.get_property("text").as_deref(), Some("hi"));
+            assert_eq!(el.get_attribute("title").as_deref(), Some("label"));
+
+            // elements without a hyphen are never custom elements
+            define_custom_element("plain", &["title"]);
+            let el = custom("plain").attr("title", || "label").build();
+            assert_eq!(el.get_attribute("title").as_deref(), Some("label"));
+        });
+    }
+
+    #[test]
+    fn custom_element_bindings_render_as_attributes_in_html() {
+        run(|| {
+            define_custom_element("rich-input", &["value"]);
+            let html = custom("rich-input").attr("value", || "a").to_html();
+            assert_eq!(html, "<rich-input value=\"a\"></rich-input>");
+        });
+    }
+
+    type LocalTask = Pin<Box<dyn Future<Output = ()>>>;
+
+    thread_local! {
+        static MANUAL_QUEUE: RefCell<Vec<LocalTask>> = const { RefCell::new(Vec::new()) };
+    }
+
+    // queues tasks until the test polls them with `pump`
+    struct ManualSpawner;
+
+    impl CustomSpawner for ManualSpawner {
+        fn spawn_local(&self, fut: LocalTask) {
+            MANUAL_QUEUE.with_borrow_mut(|queue| queue.push(fut));
+        }
+    }
+
+    // polls every queued task once, and keeps the ones that are still pending
+    fn pump() {
+        let tasks = MANUAL_QUEUE.take();
+        let mut cx = Context::from_waker(noop_waker_ref());
+        let pending = tasks
+            .into_iter()
+            .filter_map(|mut task| match task.as_mut().poll(&mut cx) {
+                Poll::Ready(()) => None,
+                Poll::Pending => Some(task),
+            })
+            .collect::<Vec<_>>();
+        MANUAL_QUEUE.with_borrow_mut(|queue| queue.extend(pending));
+    }
+
+    #[test]
+    fn scoped_spawner_runs_suspended_attributes() {
+        run(|| {
+            let owner = Owner::new();
+            owner.set_spawner(Arc::new(ManualSpawner));
+            let el = owner
+                .with(|| div().title(Suspend::new(async { "loaded" })).build());
+
+            // the global executor never sees the task
+            Executor::flush();
+            assert_eq!(el.get_attribute("title"), None);
+
+            pump();
+            assert_eq!(el.get_attribute("title").as_deref(), Some("loaded"));
+        });
+    }
+
+    #[test]
+    fn rebuilt_suspended_attribute_ignores_stale_future() {
+        run(|| {
+            let (first_tx, first_rx) = oneshot::channel::<&'static str>();
+            let (second_tx, second_rx) = oneshot::channel::<&'static str>();
+            let receivers = Arc::new(Mutex::new(vec![second_rx, first_rx]));
+            let version = RwSignal::new(0);
+            let el = div()
+                .title(move || {
+                    version.track();
+                    let rx = receivers.lock().unwrap().pop().unwrap();
+                    Suspend::new(async move { rx.await.unwrap() })
+                })
+                .build();
+
+            version.set(1);
+            Executor::flush();
+            assert_eq!(el.get_attribute("title"), None);
+
+            // the newer future resolves first, and the older one can no longer overwrite it
+            second_tx.send("second").unwrap();
+            Executor::flush();
+            assert_eq!(el.get_attribute("title").as_deref(), Some("second"));
+
+            _ = first_tx.send("first");
+            Executor::flush();
+            assert_eq!(el.get_attribute("title").as_deref(), Some("second"));
+        });
+    }
+
+    #[test]
+    fn suspend_stream_appends_items_as_they_arrive() {
+        run(|| {
+            let (tx, rx) = mpsc::unbounded::<u32>();
+            tx.unbounded_send(1).unwrap();
+            let items = SuspendStream::new(rx.map(|n| li().child(n)));
+            let el = ul().child(items).build();
+            assert_eq!(el.to_debug_html(), "<ul><li>1</li><!></ul>");
+
+            tx.unbounded_send(2).unwrap();
+            tx.unbounded_send(3).unwrap();
+            Executor::flush();
+            assert_eq!(
+                el.to_debug_html(),
+                "<ul><li>1</li><li>2</li><li>3</li><!></ul>"
+            );
+        });
+    }
+
+    #[test]
+    fn suspend_stream_holds_suspense_until_resolved() {
+        for resolve in [StreamResolution::FirstItem, StreamResolution::End] {
+            run(|| {
+                let tasks = ArcRwSignal::new(Default::default());
+                provide_context(SuspenseContext {
+                    tasks: tasks.clone(),
+                });
+                let pending = move || !tasks.with_untracked(|t| t.is_empty());
+
+                let (tx, rx) = mpsc::unbounded::<u32>();
+                let items = SuspendStream::new(rx.map(|n| li().child(n)))
+                    .resolve_on(resolve);
+                let el = ul().child(items).build();
+                Executor::flush();
+                assert!(pending());
+
+                tx.unbounded_send(1).unwrap();
+                Executor::flush();
+                assert_eq!(el.to_debug_html(), "<ul><li>1</li><!></ul>");
+                assert_eq!(pending(), resolve == StreamResolution::End);
+
+                drop(tx);
+                Executor::flush();
+                assert!(!pending());
+            });
+        }
+    }
+
+    // collects thrown errors like an error boundary would
+    #[derive(Default)]
+    struct CollectErrors(Mutex<Vec<ErrorId>>);
+
+    impl ErrorHook for CollectErrors {
+        fn throw(&self, _error: AnyError) -> ErrorId {
+            let mut errors = self.0.lock().unwrap();
+            let id = ErrorId::from(errors.len() + 100);
+            errors.push(id.clone());
+            id
+        }
+
+        fn clear(&self, id: &ErrorId) {
+            self.0.lock().unwrap().retain(|error| error != id);
+        }
+    }
+
+    // returns `Pending` once, so that the future can't be resolved immediately
+    async fn yield_once() {
+        let mut yielded = false;
+        futures::future::poll_fn(|cx| {
+            if yielded {
+                Poll::Ready(())
+            } else {
+                yielded = true;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        })
+        .await
+    }
+
+    #[test]
+    fn suspended_errors_are_thrown_and_cleared() {
+        run(|| {
+            let hook = Arc::new(CollectErrors::default());
+            let _guard = throw_error::set_error_hook(
+                Arc::clone(&hook) as Arc<dyn ErrorHook>
+            );
+            provide_context(Arc::clone(&hook) as Arc<dyn ErrorHook>);
+            let errors = move || hook.0.lock().unwrap().len();
+
+            let attempt = RwSignal::new(0);
+            let el = div()
+                .child(move || {
+                    let attempt = attempt.get();
+                    Suspend::new(async move {
+                        yield_once().await;
+                        if attempt == 0 {
+                            Err(std::io::Error::other("failed"))
+                        } else {
+                            Ok("loaded")
+                        }
+                    })
+                })
+                .build();
+            Executor::flush();
+            assert_eq!(errors(), 1);
+            assert_eq!(el.to_debug_html(), "<div><!></div>");
+
+            attempt.set(1);
+            Executor::flush();
+            assert_eq!(errors(), 0);
+            assert_eq!(el.to_debug_html(), "<div>loaded</div>");
+
+            attempt.set(0);
+            Executor::flush();
+            assert_eq!(errors(), 1);
+        });
+    }
+
+    #[test]
+    fn views_of_disposed_signals_stop_updating() {
+        run(|| {
+            let count = RwSignal::new(0);
+            let owner = Owner::new();
+            let label =
+                owner.with(|| Signal::derive(move || count.get().to_string()));
+            let el = div().title(label).child(label).build();
+            assert_eq!(el.to_debug_html(), "<div title=\"0\">0</div>");
+
+            // the effects still depend on `count`, so they run again, but keep their contents
+            owner.cleanup();
+            count.set(1);
+            Executor::flush();
+            assert_eq!(el.to_debug_html(), "<div title=\"0\">0</div>");
+
+            // and new views of the signal are left empty
+            assert_eq!(
+                div().title(label).child(label).to_html(),
+                "<div></div>"
+            );
+            // (in the DOM, a placeholder holds their position, in case they are rebuilt)
+            let el = button().child(label).build();
+            assert_eq!(el.to_debug_html(), "<button><!></button>");
+        });
+    }
+
+    #[test]
+    fn render_effect_state_can_be_unmounted_through_its_value() {
+        run(|| {
+            let text = RwSignal::new(String::from("a"));
+            let root = Rndr::create_element("div", None);
+            let mut state = (move || text.get()).build();
+            state.mount(&root, None);
+            assert_eq!(root.to_debug_html(), "<div>a</div>");
+
+            // a custom view can reach into the state to move its nodes
+            assert_eq!(state.with_value_mut(|inner| inner.unmount()), Some(()));
+            assert_eq!(root.to_debug_html(), "<div></div>");
+
+            // the effect keeps updating the detached nodes
+            text.set(String::from("b"));
+            Executor::flush();
+            assert_eq!(root.to_debug_html(), "<div></div>");
+            state.with_value_mut(|inner| inner.mount(&root, None));
+            assert_eq!(root.to_debug_html(), "<div>b</div>");
+
+            let effect = state.take().unwrap();
+            assert!(effect.with_value_mut(|_| ()).is_some());
+        });
+    }
+
+    #[test]
+    fn rebuilding_empty_reactive_views_mounts_them_in_place() {
+        run(|| {
+            let dead = RwSignal::new(String::from("dead"));
+            dead.dispose();
+            let text = RwSignal::new(String::from("a"));
+            let root = Rndr::create_element("div", None);
+            let mut end = Rndr::create_text_node("end");
+            let mut state = dead.build();
+            end.mount(&root, None);
+            state.mount(&root, Some(&end));
+            assert_eq!(root.to_debug_html(), "<div><!>end</div>");
+
+            // a function rebuilt over an empty state takes its place
+            (move || text.get()).rebuild(&mut state);
+            assert_eq!(root.to_debug_html(), "<div>aend</div>");
+            text.set(String::from("b"));
+            Executor::flush();
+            assert_eq!(root.to_debug_html(), "<div>bend</div>");
+
+            dead.rebuild(&mut state);
+            assert_eq!(root.to_debug_html(), "<div><!>end</div>");
+
+            (move || text.get()).rebuild(&mut state);
+            assert_eq!(root.to_debug_html(), "<div>bend</div>");
+            text.set(String::from("c"));
+            Executor::flush();
+            assert_eq!(root.to_debug_html(), "<div>cend</div>");
+        });
+    }
+
+    #[test]
+    fn redundant_class_updates_touch_the_class_list_once() {
+        run(|| {
+            let count = RwSignal::new(0);
+            let el = div().class(("active", move || count.get() > 0)).build();
+            let recording = RecordingRenderer::start();
+            for n in 1..=5 {
+                count.set(n);
+                Executor::flush();
+            }
+            assert_eq!(recording.stats().class_writes, 1);
+            assert_eq!(el.get_attribute("class").as_deref(), Some("active"));
+
+            // string-valued classes skip unchanged values in the same way
+            let count = RwSignal::new(0);
+            let el = div()
+                .class(move || if count.get() > 0 { "on" } else { "off" })
+                .build();
+            let recording = RecordingRenderer::start();
+            for n in 1..=5 {
+                count.set(n);
+                Executor::flush();
+            }
+            assert_eq!(recording.stats().class_writes, 1);
+            assert_eq!(el.get_attribute("class").as_deref(), Some("on"));
+        });
+    }
+
+    #[test]
+    fn class_values_keep_names_other_classes_hold() {
+        run(|| {
+            let status = RwSignal::new(Some("ok card"));
+            let other = RwSignal::new("card");
+            let el = div()
+                .class("base ok")
+                .class(move || status.get())
+                .class(move || other.get())
+                .build();
+            assert_eq!(
+                el.get_attribute("class").as_deref(),
+                Some("base ok card")
+            );
+
+            // "ok" is still held by the static class, and "card" by the other binding
+            status.set(Some("warn"));
+            Executor::flush();
+            assert_eq!(
+                el.get_attribute("class").as_deref(),
+                Some("base ok card warn")
+            );
+
+            other.set("");
+            Executor::flush();
+            assert_eq!(
+                el.get_attribute("class").as_deref(),
+                Some("base ok warn")
+            );
+
+            status.set(None);
+            Executor::flush();
+            assert_eq!(el.get_attribute("class").as_deref(), Some("base ok"));
+
+            status.set(Some("ok card"));
+            other.set("card");
+            Executor::flush();
+            status.set(Some(""));
+            Executor::flush();
+            assert_eq!(
+                el.get_attribute("class").as_deref(),
+                Some("base ok card")
+            );
+        });
+    }
+
+    #[test]
+    fn any_view_of_the_same_type_keeps_its_nodes() {
+        run(|| {
+            let label = RwSignal::new("a");
+            let wide = RwSignal::new(false);
+            let el = div()
+                .child(move || {
+                    if wide.get() {
+                        span().child(label.get()).into_any()
+                    } else {
+                        p().child(label.get()).into_any()
+                    }
+                })
+                .build();
+            let before = el.children()[0].clone();
+            assert_eq!(el.to_debug_html(), "<div><p>a</p></div>");
+
+            // the same concrete type is rebuilt in place
+            let recording = RecordingRenderer::start();
+            label.set("b");
+            Executor::flush();
+            assert_eq!(el.to_debug_html(), "<div><p>b</p></div>");
+            assert!(node_eq(&el.children()[0], &before));
+            assert_eq!(recording.stats().creates, 0);
+            drop(recording);
+
+            // a different type replaces the node
+            wide.set(true);
+            Executor::flush();
+            assert_eq!(el.to_debug_html(), "<div><span>b</span></div>");
+            assert!(!node_eq(&el.children()[0], &before));
+        });
+    }
+
+    #[test]
+    fn changing_selection_rerenders_two_rows() {
+        run(|| {
+            let rows = RwSignal::new((0..100).collect::<Vec<usize>>());
+            let selected = RwSignal::new(1);
+            let selector = Selector::new(move || selected.get());
+            let updates = Arc::new(AtomicUsize::new(0));
+            let app = ul().child({
+                let selector = selector.clone();
+                let updates = Arc::clone(&updates);
+                move || {
+                    let selector = selector.clone();
+                    let updates = Arc::clone(&updates);
+                    keyed(
+                        rows.get(),
+                        |n| *n,
+                        move |_, n| {
+                            let selector = selector.clone();
+                            let updates = Arc::clone(&updates);
+                            let is_selected = move || {
+                                updates.fetch_add(1, Ordering::Relaxed);
+                                selector.selected(n)
+                            };
+                            ((|_| {}), li().class(("selected", is_selected)))
+                        },
+                    )
+                }
+            });
+            let el = app.build();
+            Executor::flush();
+            assert_eq!(updates.load(Ordering::Relaxed), 100);
+            assert_eq!(selector.subscribed_keys(), 100);
+
+            updates.store(0, Ordering::Relaxed);
+            selected.set(50);
+            Executor::flush();
+            assert_eq!(updates.load(Ordering::Relaxed), 2);
+            let html = el.to_debug_html();
+            assert_eq!(html.matches("selected").count(), 1);
+
+            // rows that are removed stop being tracked
+            rows.set((0..10).collect());
+            Executor::flush();
+            updates.store(0, Ordering::Relaxed);
+            selected.set(5);
+            Executor::flush();
+            assert_eq!(updates.load(Ordering::Relaxed), 1);
+            assert_eq!(selector.subscribed_keys(), 10);
+        });
+    }
+
+    #[test]
+    fn on_trigger_only_rebuilds_when_notified() {
+        run(|| {
+            let label = RwSignal::new("a");
+            let redraw = ArcTrigger::new();
+            let draws = Arc::new(AtomicUsize::new(0));
+            let el = div()
+                .child(on_trigger(redraw.clone(), {
+                    let draws = Arc::clone(&draws);
+                    move || {
+                        draws.fetch_add(1, Ordering::Relaxed);
+                        label.get()
+                    }
+                }))
+                .build();
+            assert_eq!(el.to_debug_html(), "<div>a</div>");
+
+            // the signal read inside the view isn't tracked
+            label.set("b");
+            Executor::flush();
+            assert_eq!(draws.load(Ordering::Relaxed), 1);
+            assert_eq!(el.to_debug_html(), "<div>a</div>");
+
+            redraw.notify();
+            Executor::flush();
+            assert_eq!(draws.load(Ordering::Relaxed), 2);
+            assert_eq!(el.to_debug_html(), "<div>b</div>");
+        });
+    }
+
+    #[test]
+    fn model_binds_input_value_both_ways() {
+        run(|| {
+            #[derive(Clone, Default)]
+            struct Form {
+                name: String,
+            }
+
+            let form = RwSignal::new(Form {
+                name: "Alice".into(),
+            });
+            let name: Model<String> =
+                create_slice(form, |f| f.name.clone(), |f, n| f.name = n);
+            let el = input().bind(Value, name).build();
+            assert_eq!(el.get_attribute("value").as_deref(), Some("Alice"));
+
+            // the setter is what the `input` event handler calls
+            name.1.set("Bob".into());
+            Executor::flush();
+            assert_eq!(form.with_untracked(|f| f.name.clone()), "Bob");
+            assert_eq!(el.get_attribute("value").as_deref(), Some("Bob"));
+
+            form.update(|f| f.name = "Carol".into());
+            Executor::flush();
+            assert_eq!(el.get_attribute("value").as_deref(), Some("Carol"));
+        });
+    }
+
+    #[test]
+    fn hydration_moves_past_branch_markers() {
+        run(|| {
+            let count = RwSignal::new(0);
+            let marks = BranchMarks::with_marker(Arc::new(HashedBranchPaths));
+            let view = move || {
+                div().child(Either::<_, ()>::Left(Either::<(), _>::Right(
+                    span().child(move || count.get().to_string()),
+                )))
+            };
+            let html = view().to_html_with_branch_marks(&marks);
+            assert_eq!(html.matches("<!--b").count(), 4);
+
+            let root = Rndr::create_element("main", None);
+            Rndr::set_inner_html(&root, &html);
+            let cursor = Cursor::new(root.clone()).with_branch_marks(marks);
+            let _state = view().hydrate::<true>(
+                &cursor,
+                &PositionState::new(Position::FirstChild),
+            );
+
+            count.set(1);
+            Executor::flush();
+            assert_eq!(
+                root.to_debug_html(),
+                format!("<main>{}</main>", html.replace(">0<", ">1<"))
+            );
+        });
+    }
+
+    #[test]
+    fn hydration_finds_branches_out_of_document_order() {
+        run(|| {
+            let count = RwSignal::new(0);
+            let marks = BranchMarks::with_marker(Arc::new(HashedBranchPaths));
+            let view = move || {
+                Either::<_, ()>::Left(
+                    span().child(move || count.get().to_string()),
+                )
+            };
+            let html = view().to_html_with_branch_marks(&marks);
+
+            // the branch is somewhere other than where the view begins
+            let root = Rndr::create_element("main", None);
+            Rndr::set_inner_html(
+                &root,
+                &format!("<p></p><section>{html}</section>"),
+            );
+            let id = marks.enter("0").id().into_owned();
+            let opening = Cursor::new(root.clone()).find_branch(&id).unwrap();
+            assert_eq!(Rndr::comment_text(&opening), Some(format!("bo-{id}")));
+
+            let cursor = Cursor::new(root.clone()).with_branch_marks(marks);
+            let _state = view().hydrate::<true>(
+                &cursor,
+                &PositionState::new(Position::FirstChild),
+            );
+
+            count.set(1);
+            Executor::flush();
+            assert_eq!(
+                root.to_debug_html(),
+                format!(
+                    "<main><p></p><section>{}</section></main>",
+                    html.replace(">0<", ">1<")
+                )
+            );
+        });
+    }
+
+    #[test]
+    fn nested_suspends_hydrate_from_checkpoints_in_any_order() {
+        type Gate = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+        fn gate() -> (oneshot::Sender<()>, Gate) {
+            let (tx, rx) = oneshot::channel();
+            (tx, Box::pin(rx.map(|_| ())))
+        }
+
+        run(|| {
+            let count = RwSignal::new(0);
+            let marks = BranchMarks::with_marker(Arc::new(HashedBranchPaths));
+            let view = move |outer: Gate, inner: Gate, sibling: Gate| {
+                div().child((
+                    Suspend::new(async move {
+                        outer.await;
+                        section().child((
+                            "outer",
+                            Suspend::new(async move {
+                                inner.await;
+                                span().child(move || count.get().to_string())
+                            }),
+                        ))
+                    }),
+                    Suspend::new(async move {
+                        sibling.await;
+                        p().child("sibling")
+                    }),
+                ))
+            };
+            let ready = || -> Gate { Box::pin(async {}) };
+            let html = view(ready(), ready(), ready())
+                .to_html_with_branch_marks(&marks);
+
+            // the inner suspend is ready either before the outer one, or only after it
+            for inner_first in [true, false] {
+                count.set(0);
+                Executor::flush();
+                let root = Rndr::create_element("main", None);
+                Rndr::set_inner_html(&root, &html);
+                let (outer_tx, outer) = gate();
+                let (inner_tx, inner) = gate();
+                let (sibling_tx, sibling) = gate();
+                let cursor =
+                    Cursor::new(root.clone()).with_branch_marks(marks.clone());
+                let _state = view(outer, inner, sibling).hydrate::<true>(
+                    &cursor,
+                    &PositionState::new(Position::FirstChild),
+                );
+
+                // nothing is rendered, apart from placeholders kept by suspends that are still
+                // pending: each suspend hydrates the server's HTML where it left off
+                let recording = RecordingRenderer::start_with_log();
+                _ = sibling_tx.send(());
+                Executor::flush();
+                if inner_first {
+                    _ = inner_tx.send(());
+                    _ = outer_tx.send(());
+                } else {
+                    _ = outer_tx.send(());
+                    Executor::flush();
+                    _ = inner_tx.send(());
+                }
+                Executor::flush();
+                let log = recording.log();
+                assert!(
+                    log.iter().all(|op| *op == DomOp::CreatePlaceholder),
+                    "{log:?}"
+                );
+                assert_eq!(
+                    root.to_debug_html(),
+                    format!("<main>{html}</main>")
+                );
+
+                count.set(1);
+                Executor::flush();
+                assert_eq!(
+                    root.to_debug_html(),
+                    format!("<main>{}</main>", html.replace(">0<", ">1<"))
+                );
+            }
+        });
+    }
+
+    #[cfg(feature = "graph-introspection")]
+    #[test]
+    fn dom_bindings_show_up_in_graph_snapshot() {
+        use reactive_graph::introspect::{snapshot, Named, NodeKind};
+
+        run(|| {
+            let active = RwSignal::new(false).named("dom_bindings_active");
+            let el = div()
+                .class(("active", move || active.get()))
+                .child(move || active.get().to_string())
+                .build();
+
+            let graph = snapshot();
+            let active = graph.find("dom_bindings_active").unwrap();
+            let bindings = graph
+                .subscribers(active.id)
+                .map(|node| {
+                    assert_eq!(node.kind, NodeKind::RenderEffect);
+                    node.defined_at.file().replace('\\', "/")
+                })
+                .collect::<Vec<_>>();
+            // the class binding, and the text node
+            assert_eq!(bindings.len(), 2);
+            assert!(bindings
+                .iter()
+                .any(|file| file.ends_with("reactive_graph/class.rs")));
+            drop(el);
+        });
+    }
+
+    #[test]
+    fn either_keep_alive_builds_b_lazily_and_reuses_a() {
+        use crate::view::either::EitherKeepAlive;
+
+        run(|| {
+            let count = RwSignal::new(0);
+            let b_runs = Arc::new(AtomicUsize::new(0));
+            let root = Rndr::create_element("div", None);
+            let mut state = EitherKeepAlive {
+                a: Some(p().child("a")),
+                b: Some(span().child({
+                    let b_runs = Arc::clone(&b_runs);
+                    move || {
+                        b_runs.fetch_add(1, Ordering::Relaxed);
+                        count.get().to_string()
+                    }
+                })),
+                show_b: false,
+            }
+            .build();
+            state.mount(&root, None);
+            let a = root.children()[0].clone();
+            assert_eq!(root.to_debug_html(), "<div><p>a</p></div>");
+            // B is held until it is first shown
+            assert_eq!(b_runs.load(Ordering::Relaxed), 0);
+
+            EitherKeepAlive {
+                a: None,
+                b: None,
+                show_b: true,
+            }
+            .rebuild(&mut state);
+            assert_eq!(root.to_debug_html(), "<div><span>0</span></div>");
+            assert_eq!(b_runs.load(Ordering::Relaxed), 1);
+
+            let recording = RecordingRenderer::start();
+            EitherKeepAlive {
+                a: None,
+                b: None,
+                show_b: false,
+            }
+            .rebuild(&mut state);
+            assert_eq!(root.to_debug_html(), "<div><p>a</p></div>");
+            assert_eq!(recording.stats().creates, 0);
+            assert!(node_eq(&root.children()[0], &a));
+
+            // the hidden branch is kept, rather than rebuilt, when it is shown again
+            count.set(1);
+            Executor::flush();
+            EitherKeepAlive {
+                a: None,
+                b: None,
+                show_b: true,
+            }
+            .rebuild(&mut state);
+            assert_eq!(root.to_debug_html(), "<div><span>1</span></div>");
+            assert_eq!(recording.stats().creates, 0);
+        });
+    }
+
+    #[test]
+    fn or_else_render_switches_arms_in_place() {
+        use crate::{
+            html::element::{em, strong},
+            reactive_graph::or_else_render,
+        };
+
+        run(|| {
+            let input = RwSignal::new("1");
+            let el = div()
+                .child((
+                    strong().child("before"),
+                    or_else_render(
+                        move || {
+                            input.get().parse::<i32>().map_err(|_| input.get())
+                        },
+                        |err| span().child(format!("invalid: {err}")),
+                    ),
+                    em().child("after"),
+                ))
+                .build();
+            let children = el.children();
+            let (before, after) = (&children[0], children.last().unwrap());
+            assert_eq!(
+                el.to_debug_html(),
+                "<div><strong>before</strong>1<em>after</em></div>"
+            );
+
+            input.set("x");
+            Executor::flush();
+            assert_eq!(
+                el.to_debug_html(),
+                "<div><strong>before</strong><span>invalid: \
+                 x</span><em>after</em></div>"
+            );
+
+            input.set("2");
+            Executor::flush();
+            assert_eq!(
+                el.to_debug_html(),
+                "<div><strong>before</strong>2<em>after</em></div>"
+            );
+            // the siblings are left where they are
+            let children = el.children();
+            assert!(node_eq(&children[0], before));
+            assert!(node_eq(children.last().unwrap(), after));
+        });
+    }
+
+    #[test]
+    fn visible_when_toggles_without_rebuilding() {
+        use crate::reactive_graph::{
+            visible_when, visible_when_with, HideWith,
+        };
+
+        run(|| {
+            let shown = RwSignal::new(true);
+            let count = RwSignal::new(0);
+            let el = main()
+                .child((
+                    visible_when(
+                        move || shown.get(),
+                        p().child(move || count.get().to_string()),
+                    ),
+                    visible_when_with(
+                        move || shown.get(),
+                        span().child("styled"),
+                        HideWith::DisplayNone,
+                    ),
+                ))
+                .build();
+            let children = el.children();
+            let (hidden, styled) = (
+                Element::cast_from(children[0].clone()).unwrap(),
+                Element::cast_from(children[1].clone()).unwrap(),
+            );
+            assert_eq!(hidden.get_attribute("hidden"), None);
+            assert_eq!(styled.get_attribute("style"), None);
+
+            let recording = RecordingRenderer::start();
+            for _ in 0..2 {
+                shown.set(false);
+                Executor::flush();
+                assert_eq!(hidden.get_attribute("hidden").as_deref(), Some(""));
+                assert_eq!(
+                    styled.get_attribute("style").as_deref(),
+                    Some("display: none;")
+                );
+
+                // the hidden view keeps updating
+                count.update(|n| *n += 1);
+                Executor::flush();
+
+                shown.set(true);
+                Executor::flush();
+                assert_eq!(hidden.get_attribute("hidden"), None);
+                // as in a browser, removing the last property leaves an empty `style`
+                assert_eq!(styled.get_attribute("style").as_deref(), Some(""));
+            }
+            assert_eq!(recording.stats().creates, 0);
+            assert_eq!(recording.stats().inserts, 0);
+            assert_eq!(recording.stats().removals, 0);
+            let children = el.children();
+            assert!(node_eq(&children[0], &hidden));
+            assert!(node_eq(&children[1], &styled));
+            assert_eq!(
+                el.to_debug_html(),
+                "<main><p>2</p><span style=\"\">styled</span></main>"
+            );
+        });
+    }
+
+    #[test]
+    fn first_node_anchors_moving_item_3_before_item_1() {
+        run(|| {
+            let label = RwSignal::new("a");
+            let root = Rndr::create_element("ul", None);
+            let mut items = (1..=3)
+                .map(|n| {
+                    (move || li().child(format!("{}{n}", label.get()))).build()
+                })
+                .collect::<Vec<_>>();
+            for item in &mut items {
+                item.mount(&root, None);
+            }
+            let nodes = root.children();
+            assert_eq!(
+                root.to_debug_html(),
+                "<ul><li>a1</li><li>a2</li><li>a3</li></ul>"
+            );
+
+            let recording = RecordingRenderer::start();
+            let anchor = items[0].first_node();
+            assert!(node_eq(anchor.as_ref().unwrap(), &nodes[0]));
+            items[2].unmount();
+            items[2].mount(&root, anchor.as_ref());
+            assert_eq!(
+                root.to_debug_html(),
+                "<ul><li>a3</li><li>a1</li><li>a2</li></ul>"
+            );
+            assert_eq!(recording.stats().creates, 0);
+            let moved = root.children();
+            for (moved, before) in moved.iter().zip([2, 0, 1]) {
+                assert!(node_eq(moved, &nodes[before]));
+            }
+
+            // the moved item keeps updating in its new position
+            label.set("b");
+            Executor::flush();
+            assert_eq!(
+                root.to_debug_html(),
+                "<ul><li>b3</li><li>b1</li><li>b2</li></ul>"
+            );
+        });
+    }
+
+    #[test]
+    fn errored_result_keeps_its_place_among_siblings() {
+        run(|| {
+            let value = RwSignal::new(Err::<&str, _>("not yet"));
+            let el = div()
+                .child((
+                    p().child("first"),
+                    move || value.get().map_err(std::io::Error::other),
+                    p().child("last"),
+                ))
+                .build();
+            let children = el.children();
+            let (first, last) = (&children[0], children.last().unwrap());
+            assert_eq!(
+                el.to_debug_html(),
+                "<div><p>first</p><!><p>last</p></div>"
+            );
+
+            for _ in 0..2 {
+                value.set(Ok("middle"));
+                Executor::flush();
+                assert_eq!(
+                    el.to_debug_html(),
+                    "<div><p>first</p>middle<p>last</p></div>"
+                );
+
+                value.set(Err("failed"));
+                Executor::flush();
+                assert_eq!(
+                    el.to_debug_html(),
+                    "<div><p>first</p><!><p>last</p></div>"
+                );
+            }
+            let children = el.children();
+            assert!(node_eq(&children[0], first));
+            assert!(node_eq(children.last().unwrap(), last));
+
+            // a sibling can anchor on the placeholder held by the error side
+            let root = Rndr::create_element("div", None);
+            let mut errored = Err::<Element, _>(Rndr::create_placeholder());
+            let mut last = p().child("last").build();
+            errored.mount(&root, None);
+            last.mount(&root, None);
+            let mut moved = p().child("moved").build();
+            errored.insert_before_this_or_marker(&root, &mut moved, None);
+            assert_eq!(
+                root.to_debug_html(),
+                "<div><p>moved</p><!><p>last</p></div>"
+            );
+        });
+    }
+
+    #[test]
+    fn toggling_disabled_removes_and_readds_the_attribute() {
+        use crate::html::attribute::disabled;
+
+        run(|| {
+            let is_disabled = RwSignal::new(true);
+            let tabindex = RwSignal::new(-1);
+            let el = button()
+                .add_any_attr(disabled(move || is_disabled.get()))
+                .tabindex(move || tabindex.get())
+                .build();
+            assert_eq!(el.get_attribute("disabled").as_deref(), Some(""));
+            assert_eq!(el.get_attribute("tabindex").as_deref(), Some("-1"));
+
+            let recording = RecordingRenderer::start_with_log();
+            is_disabled.set(false);
+            Executor::flush();
+            assert_eq!(el.get_attribute("disabled"), None);
+            is_disabled.set(true);
+            Executor::flush();
+            assert_eq!(el.get_attribute("disabled").as_deref(), Some(""));
+            tabindex.set(0);
+            Executor::flush();
+            assert_eq!(
+                recording.log(),
+                [
+                    DomOp::RemoveAttribute("disabled".into()),
+                    DomOp::SetAttribute("disabled".into(), "".into()),
+                    DomOp::SetAttribute("tabindex".into(), "0".into()),
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn prefixed_attributes_are_set_in_their_namespace() {
+        use crate::{
+            html::attribute::custom::custom_attribute,
+            svg::{r#use, svg},
+        };
+
+        const XLINK: &str = "http://www.w3.org/1999/xlink";
+        const XML: &str = "http://www.w3.org/XML/1998/namespace";
+
+        run(|| {
+            let icon = RwSignal::new(Some("#star"));
+            let el =
+                svg()
+                    .add_any_attr(custom_attribute("xml:lang", "en"))
+                    .child(r#use().add_any_attr(custom_attribute(
+                        "xlink:href",
+                        move || icon.get(),
+                    )))
+                    .build();
+            let used = Element::cast_from(el.children()[0].clone()).unwrap();
+            assert_eq!(el.get_attribute_ns(XML, "lang").as_deref(), Some("en"));
+            assert_eq!(
+                used.get_attribute_ns(XLINK, "href").as_deref(),
+                Some("#star")
+            );
+            // the attribute isn't in the null namespace
+            assert_eq!(used.get_attribute_ns("", "href"), None);
+
+            icon.set(Some("#moon"));
+            Executor::flush();
+            assert_eq!(
+                used.get_attribute_ns(XLINK, "href").as_deref(),
+                Some("#moon")
+            );
+
+            icon.set(None);
+            Executor::flush();
+            assert_eq!(used.get_attribute_ns(XLINK, "href"), None);
+            assert_eq!(used.get_attribute("xlink:href"), None);
+        });
+    }
+
+    #[test]
+    fn moving_left_never_writes_top() {
+        use crate::html::style::{style, Px};
+
+        run(|| {
+            let left = RwSignal::new(Px(10));
+            let top = RwSignal::new(Px(20));
+            let el = div()
+                .add_any_attr(style(vec![("left", left), ("top", top)]))
+                .build();
+            assert_eq!(
+                el.get_attribute("style").as_deref(),
+                Some("left: 10px; top: 20px;")
+            );
+
+            let recording = RecordingRenderer::start_with_log();
+            for x in [15, 20, 25] {
+                left.set(Px(x));
+                Executor::flush();
+            }
+            assert_eq!(
+                recording.log(),
+                [15, 20, 25].map(|x| DomOp::SetStyles {
+                    set: vec![("left".into(), format!("{x}px"))],
+                    remove: vec![],
+                })
+            );
+            assert_eq!(
+                el.get_attribute("style").as_deref(),
+                Some("left: 25px; top: 20px;")
+            );
+        });
+    }
+
+    #[test]
+    fn spread_click_handlers_all_fire_in_order() {
+        run(|| {
+            let fired = Arc::new(Mutex::new(Vec::new()));
+            let handler = |n: usize| {
+                let fired = Arc::clone(&fired);
+                move |_| fired.lock().unwrap().push(n)
+            };
+            let el = button()
+                .on(event::click, handler(1))
+                .add_any_attr(on(event::click, handler(2)))
+                .add_any_attr(on(event::click, handler(3)).undelegated())
+                .build();
+
+            dispatch_event(&el, "click");
+            assert_eq!(*fired.lock().unwrap(), [1, 2, 3]);
+            dispatch_event(&el, "click");
+            assert_eq!(*fired.lock().unwrap(), [1, 2, 3, 1, 2, 3]);
+        });
+    }
+
+    // `AnyView` only renders to HTML with `ssr`, and only hydrates with `hydrate`
+    #[cfg(all(feature = "ssr", feature = "hydrate"))]
+    #[test]
+    fn fragment_of_forty_siblings_hydrates_in_place() {
+        use crate::view::fragment::Fragment;
+
+        run(|| {
+            let count = RwSignal::new(0);
+            let view = move || {
+                let children = (0..40)
+                    .map(|n| {
+                        if n % 2 == 0 {
+                            li().child(n).into_any()
+                        } else {
+                            li().child(move || count.get() + n).into_any()
+                        }
+                    })
+                    .collect::<Fragment>();
+                ul().child(children)
+            };
+            let html = view().to_html();
+
+            let root = Rndr::create_element("main", None);
+            Rndr::set_inner_html(&root, &html);
+            let server_ul =
+                Element::cast_from(root.children().remove(0)).unwrap();
+            let server_items = server_ul.children();
+            assert_eq!(server_items.len(), 41);
+
+            let recording = RecordingRenderer::start();
+            let state = view().hydrate::<true>(
+                &Cursor::new(root.clone()),
+                &PositionState::new(Position::FirstChild),
+            );
+            assert_eq!(recording.stats().creates, 0);
+            assert_eq!(recording.stats().inserts, 0);
+
+            // the cursor walked every sibling, so the reactive ones update the server's nodes
+            count.set(100);
+            Executor::flush();
+            let items = server_ul.children();
+            assert_eq!(items.len(), 41);
+            for (n, (item, server_item)) in
+                items.iter().zip(&server_items).take(40).enumerate()
+            {
+                assert!(node_eq(item, server_item));
+                let expected = if n % 2 == 0 { n } else { n + 100 };
+                assert_eq!(
+                    item.to_debug_html(),
+                    format!("<li>{expected}</li>")
+                );
+            }
+            assert_eq!(recording.stats().text_writes, 20);
+            drop(state);
+        });
+    }
+
+    #[test]
+    fn raw_html_island_hydrates_between_reactive_siblings() {
+        use crate::{html::element::article, view::raw_html::RawHtmlIsland};
+
+        run(|| {
+            let count = RwSignal::new(0);
+            // nested elements, bare text, and a nested island of its own
+            let markdown = "<h1>Title</h1><ul><li><em>a</em> \
+                            b</li></ul>text<!--raw--><p>inner</p><!--/\
+                            raw--><p>last</p>";
+            let view = move || {
+                article().child((
+                    move || count.get(),
+                    RawHtmlIsland::new(markdown),
+                    move || count.get() + 1,
+                    p().child("after"),
+                ))
+            };
+            let html = view().to_html();
+
+            let root = Rndr::create_element("main", None);
+            Rndr::set_inner_html(&root, &html);
+            let island = Element::cast_from(root.children().remove(0))
+                .unwrap()
+                .children()[2]
+                .clone();
+
+            let recording = RecordingRenderer::start();
+            let _state = view().hydrate::<true>(
+                &Cursor::new(root.clone()),
+                &PositionState::new(Position::FirstChild),
+            );
+            assert_eq!(recording.stats().creates, 0);
+            assert_eq!(recording.stats().inserts, 0);
+
+            // the sibling after the island was found past its closing marker
+            count.set(5);
+            Executor::flush();
+            assert_eq!(recording.stats().text_writes, 2);
+            assert_eq!(
+                root.to_debug_html(),
+                format!(
+                    "<main>{}</main>",
+                    html.replace(">0<", ">5<").replace("->1<", "->6<")
+                )
+            );
+            let article =
+                Element::cast_from(root.children().remove(0)).unwrap();
+            assert!(node_eq(&article.children()[2], &island));
+        });
+    }
+
+    #[test]
+    fn lists_mounted_through_a_fragment_match_mounting_each_child() {
+        use crate::view::FRAGMENT_THRESHOLD;
+
+        run(|| {
+            let count = RwSignal::new(0);
+            let len = FRAGMENT_THRESHOLD + 4;
+            let item = move |n: usize| li().child(move || count.get() + n);
+            let parent_with_anchor = || {
+                let parent = Rndr::create_element("ul", None);
+                let anchor = Rndr::create_element("p", None);
+                Rndr::insert_node(&parent, &anchor, None);
+                (parent, anchor)
+            };
+
+            // each item inserted into the parent on its own
+            let (expected, anchor) = parent_with_anchor();
+            let mut expected_states =
+                (0..len).map(|n| item(n).build()).collect::<Vec<_>>();
+            for state in &mut expected_states {
+                state.mount(&expected, Some(&anchor));
+            }
+            Rndr::create_placeholder().mount(&expected, Some(&anchor));
+
+            let (list, anchor) = parent_with_anchor();
+            let mut list_state = (0..len).map(item).collect::<Vec<_>>().build();
+            list_state.mount(&list, Some(&anchor));
+
+            let (keyed_list, anchor) = parent_with_anchor();
+            let order = RwSignal::new((0..len).collect::<Vec<_>>());
+            let mut keyed_state = (move || {
+                keyed(order.get(), |n| *n, move |_, n| ((|_| {}), item(n)))
+            })
+            .build();
+            keyed_state.mount(&keyed_list, Some(&anchor));
+
+            assert_eq!(list.to_debug_html(), expected.to_debug_html());
+            assert_eq!(keyed_list.to_debug_html(), expected.to_debug_html());
+            let first_item = list.children().remove(0);
+
+            // the items keep their own nodes, so they update in place
+            count.set(100);
+            Executor::flush();
+            assert_eq!(list.to_debug_html(), expected.to_debug_html());
+            assert_eq!(keyed_list.to_debug_html(), expected.to_debug_html());
+            assert!(node_eq(&list.children()[0], &first_item));
+
+            // and the keyed list moves its items within the real parent, not the fragment
+            order.update(|order| order.reverse());
+            Executor::flush();
+            let reversed = (0..len)
+                .rev()
+                .map(|n| format!("<li>{}</li>", n + 100))
+                .collect::<String>();
+            assert_eq!(
+                keyed_list.to_debug_html(),
+                format!("<ul>{reversed}<!><p></p></ul>")
+            );
+
+            // a keyed list nested in a list that went through a fragment was mounted into the
+            // fragment, so it finds its parent again when it is rebuilt
+            let (nested, anchor) = parent_with_anchor();
+            let pair = RwSignal::new(vec![0, 1]);
+            let mut nested_state = (0..len)
+                .map(|row| {
+                    move || {
+                        keyed(
+                            pair.get(),
+                            |n| *n,
+                            move |_, n| ((|_| {}), li().child(row * 10 + n)),
+                        )
+                    }
+                })
+                .collect::<Vec<_>>()
+                .build();
+            nested_state.mount(&nested, Some(&anchor));
+            pair.set(vec![1, 0]);
+            Executor::flush();
+            let swapped = (0..len)
+                .map(|row| {
+                    format!("<li>{}</li><li>{}</li><!>", row * 10 + 1, row * 10)
+                })
+                .collect::<String>();
+            assert_eq!(
+                nested.to_debug_html(),
+                format!("<ul>{swapped}<!><p></p></ul>")
+            );
+        });
     }
 }
- */
@@ -1,9 +1,14 @@
 use crate::html::{element::ElementType, node_ref::NodeRefContainer};
+use futures::channel::oneshot;
+#[cfg(not(feature = "testing"))]
+use reactive_graph::traits::Set;
 use reactive_graph::{
+    effect::Effect,
     signal::RwSignal,
-    traits::{DefinedAt, Set, Track, WithUntracked},
+    traits::{DefinedAt, Get, GetUntracked, Track, WithUntracked},
 };
 use send_wrapper::SendWrapper;
+use std::future::{self, Future};
 use wasm_bindgen::JsCast;
 
 /// A reactive reference to a DOM node that can be used with the `node_ref` attribute.
@@ -25,6 +30,52 @@ where
     }
 }
 
+impl<E> NodeRef<E>
+where
+    E: ElementType + 'static,
+    E::Output: JsCast + Clone + 'static,
+{
+    /// Runs the callback exactly once, as soon as the element has been mounted.
+    ///
+    /// If the element is already available, the callback runs immediately. On the server, where
+    /// elements are never mounted, this does nothing.
+    pub fn on_load(self, fun: impl FnOnce(E::Output) + 'static) {
+        if let Some(el) = self.try_get_untracked().flatten() {
+            fun(el);
+            return;
+        }
+
+        let mut fun = Some(fun);
+        Effect::new(move |_| {
+            // once the callback has run, the node ref is no longer read, so the effect has no
+            // dependencies and will not run again
+            if fun.is_some() {
+                if let Some(el) = self.try_get().flatten() {
+                    if let Some(fun) = fun.take() {
+                        fun(el);
+                    }
+                }
+            }
+        });
+    }
+
+    /// Returns a `Future` that resolves to the element once it has been mounted.
+    ///
+    /// On the server, where elements are never mounted, this `Future` never resolves.
+    pub fn loaded(self) -> impl Future<Output = E::Output> {
+        let (tx, rx) = oneshot::channel();
+        self.on_load(move |el| {
+            _ = tx.send(el);
+        });
+        async move {
+            match rx.await {
+                Ok(el) => el,
+                Err(_) => future::pending().await,
+            }
+        }
+    }
+}
+
 impl<E> Default for NodeRef<E>
 where
     E: ElementType,
@@ -57,12 +108,28 @@ where
     E: ElementType,
     E::Output: JsCast + 'static,
 {
+    #[cfg(not(any(feature = "testing", feature = "sledgehammer")))]
     fn load(self, el: &crate::renderer::types::Element) {
         // safe to construct SendWrapper here, because it will only run in the browser
         // so it will always be accessed or dropped from the main thread
         self.0
             .set(Some(SendWrapper::new(el.clone().unchecked_into())));
     }
+
+    // loading the reference reads the element from the DOM, so any queued operations are
+    // flushed first
+    #[cfg(all(feature = "sledgehammer", not(feature = "testing")))]
+    fn load(self, el: &crate::renderer::types::Element) {
+        // safe to construct SendWrapper here, because it will only run in the browser
+        // so it will always be accessed or dropped from the main thread
+        self.0
+            .set(Some(SendWrapper::new(el.to_node().unchecked_into())));
+    }
+
+    // a mock DOM element can't be used as the `web_sys` element type, so the reference is
+    // never loaded
+    #[cfg(feature = "testing")]
+    fn load(self, _el: &crate::renderer::types::Element) {}
 }
 
 impl<E> DefinedAt for NodeRef<E>
@@ -113,3 +180,136 @@ where
 {
     NodeRef::new()
 }
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::NodeRef;
+    use crate::html::element::ElementType;
+    use any_spawner::Executor;
+    use reactive_graph::{owner::Owner, traits::Set};
+    use send_wrapper::SendWrapper;
+    use std::{cell::Cell, rc::Rc};
+    use wasm_bindgen::{JsCast, JsValue};
+
+    // a stand-in for a `web_sys` element, which can't be created or cloned outside the browser
+    #[derive(Debug)]
+    struct TestNode(usize, JsValue);
+
+    impl Clone for TestNode {
+        fn clone(&self) -> Self {
+            Self(self.0, JsValue::NULL)
+        }
+    }
+
+    impl AsRef<JsValue> for TestNode {
+        fn as_ref(&self) -> &JsValue {
+            &self.1
+        }
+    }
+
+    impl From<TestNode> for JsValue {
+        fn from(node: TestNode) -> Self {
+            node.1
+        }
+    }
+
+    impl JsCast for TestNode {
+        fn instanceof(_val: &JsValue) -> bool {
+            true
+        }
+
+        fn unchecked_from_js(val: JsValue) -> Self {
+            Self(0, val)
+        }
+
+        fn unchecked_from_js_ref(_val: &JsValue) -> &Self {
+            unimplemented!()
+        }
+    }
+
+    struct TestElement;
+
+    impl ElementType for TestElement {
+        type Output = TestNode;
+
+        const TAG: &'static str = "div";
+        const SELF_CLOSING: bool = false;
+        const ESCAPE_CHILDREN: bool = true;
+        const NAMESPACE: Option<&'static str> = None;
+
+        fn tag(&self) -> &str {
+            Self::TAG
+        }
+    }
+
+    fn run(test: impl FnOnce()) {
+        _ = Executor::init_manual();
+        let owner = Owner::new();
+        owner.set();
+        test();
+    }
+
+    // the mock DOM can't load a reference, so the tests fill in the element themselves
+    fn load(node_ref: NodeRef<TestElement>, id: usize) {
+        node_ref
+            .0
+            .set(Some(SendWrapper::new(TestNode(id, JsValue::NULL))));
+    }
+
+    #[test]
+    fn on_load_runs_once_after_mount() {
+        run(|| {
+            let node_ref = NodeRef::<TestElement>::new();
+            let loaded = Rc::new(Cell::new(None));
+            node_ref.on_load({
+                let loaded = Rc::clone(&loaded);
+                move |el| {
+                    assert!(loaded.replace(Some(el.0)).is_none());
+                }
+            });
+            Executor::flush();
+            assert_eq!(loaded.get(), None);
+
+            load(node_ref, 1);
+            Executor::flush();
+            assert_eq!(loaded.get(), Some(1));
+
+            load(node_ref, 2);
+            Executor::flush();
+            assert_eq!(loaded.get(), Some(1));
+        });
+    }
+
+    #[test]
+    fn on_load_runs_immediately_if_already_mounted() {
+        run(|| {
+            let node_ref = NodeRef::<TestElement>::new();
+            load(node_ref, 1);
+            let loaded = Rc::new(Cell::new(None));
+            node_ref.on_load({
+                let loaded = Rc::clone(&loaded);
+                move |el| loaded.set(Some(el.0))
+            });
+            assert_eq!(loaded.get(), Some(1));
+        });
+    }
+
+    #[test]
+    fn loaded_resolves_in_a_local_task() {
+        run(|| {
+            let node_ref = NodeRef::<TestElement>::new();
+            let loaded = Rc::new(Cell::new(None));
+            Executor::spawn_local({
+                let loaded = Rc::clone(&loaded);
+                let el = node_ref.loaded();
+                async move { loaded.set(Some(el.await.0)) }
+            });
+            Executor::flush();
+            assert_eq!(loaded.get(), None);
+
+            load(node_ref, 1);
+            Executor::flush();
+            assert_eq!(loaded.get(), Some(1));
+        });
+    }
+}
@@ -2,10 +2,13 @@ use crate::{
     html::attribute::Attribute,
     hydration::Cursor,
     prelude::Mountable,
-    ssr::StreamBuilder,
+    ssr::{BranchMarks, StreamBuilder},
     view::{add_attr::AddAnyAttr, Position, PositionState, Render, RenderHtml},
 };
-use reactive_graph::{computed::ScopedFuture, owner::Owner};
+use reactive_graph::{
+    computed::ScopedFuture,
+    owner::{provide_context, Owner},
+};
 
 /// A view wrapper that sets the reactive [`Owner`] to a particular owner whenever it is rendered.
 #[derive(Debug, Clone)]
@@ -22,9 +25,46 @@ impl<T> OwnedView<T> {
     }
 
     /// Wraps a view with the given owner.
+    ///
+    /// The owner is used as-is: it is not made a child of the current owner, so it will not be
+    /// disposed when the current owner is cleaned up.
     pub fn new_with_owner(view: T, owner: Owner) -> Self {
         Self { owner, view }
     }
+
+    /// Unwraps the view, returning the owner it was wrapped with and the inner view.
+    ///
+    /// This can be used to detach a subtree and wrap it again later, possibly under a different
+    /// parent, using [`OwnedView::new_with_owner`].
+    pub fn into_inner(self) -> (Owner, T) {
+        (self.owner, self.view)
+    }
+}
+
+/// Provides a context value to `children` and their descendants only.
+///
+/// This creates a child of the current [`Owner`], provides `value` to it, and renders `children`
+/// with it as the current owner. Siblings of the returned view, and the current owner, do not
+/// see the value, so this can be used to give each item of a list its own context. The child
+/// owner is cleaned up when the view is unmounted.
+///
+/// ## Panics
+/// Panics if there is no current [`Owner`].
+#[track_caller]
+pub fn provide_context_view<T, V>(
+    value: T,
+    children: impl FnOnce() -> V,
+) -> OwnedView<V>
+where
+    T: Send + Sync + 'static,
+{
+    let (owner, view) = Owner::current()
+        .expect("no current reactive Owner found")
+        .with_child(|| {
+            provide_context(value);
+            children()
+        });
+    OwnedView::new_with_owner(view, owner)
 }
 
 /// Retained view state for an [`OwnedView`].
@@ -45,6 +85,19 @@ where
     fn new(state: T, owner: Owner) -> Self {
         Self { owner, state }
     }
+
+    /// The owner the view was rendered with.
+    pub fn owner(&self) -> &Owner {
+        &self.owner
+    }
+
+    /// Unwraps the state, returning the owner it was rendered with and the inner state.
+    ///
+    /// The owner is cleaned up once its last handle is dropped, so holding on to it allows cleanup
+    /// to be deferred until the caller decides the view is no longer needed.
+    pub fn into_inner(self) -> (Owner, T) {
+        (self.owner, self.state)
+    }
 }
 
 impl<T> Render for OwnedView<T>
@@ -100,7 +153,7 @@ where
         buf: &mut String,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) {
         self.owner.with(|| {
             self.view
@@ -113,7 +166,7 @@ where
         buf: &mut StreamBuilder,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) where
         Self: Sized,
     {
@@ -0,0 +1,33 @@
+use crate::view::RenderHtml;
+use either_of::Either;
+
+/// Creates a reactive view that renders both arms of a `Result`.
+///
+/// Rendering a `Result` directly only renders the `Ok` value, and throws the error to the nearest
+/// error boundary. This instead renders the error with `fallback`, so both arms are handled in
+/// place without needing an error boundary:
+/// ```rust,ignore
+/// p().child(or_else_render(
+///     move || input.get().parse::<i32>(),
+///     |err| span().child(format!("invalid number: {err}")),
+/// ))
+/// ```
+/// Each time the result switches between `Ok` and `Err`, the previous view is replaced in the
+/// same position among its siblings. While it stays on the same arm, the view is rebuilt in place.
+pub fn or_else_render<F, T, E, FV, V>(
+    mut result_fn: F,
+    fallback: FV,
+) -> impl RenderHtml
+where
+    F: FnMut() -> Result<T, E> + Send + 'static,
+    T: RenderHtml + 'static,
+    T::State: 'static,
+    FV: Fn(E) -> V + Send + 'static,
+    V: RenderHtml + 'static,
+    V::State: 'static,
+{
+    move || match result_fn() {
+        Ok(view) => Either::Left(view),
+        Err(err) => Either::Right(fallback(err)),
+    }
+}
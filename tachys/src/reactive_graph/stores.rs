@@ -0,0 +1,291 @@
+use super::RenderEffectState;
+use crate::{
+    html::attribute::{Attribute, AttributeValue},
+    hydration::Cursor,
+    ssr::{BranchMarks, StreamBuilder},
+    view::{add_attr::AddAnyAttr, Position, PositionState, Render, RenderHtml},
+};
+use reactive_graph::{
+    effect::RenderEffect,
+    owner::Storage,
+    traits::{Get, GetUntracked},
+};
+use reactive_stores::{ArcField, Field, StoreField, Subfield};
+
+// store fields render like signals: each one creates a render effect that only subscribes to the
+// field's own trigger, rather than to the whole store
+macro_rules! store_field_impl {
+    ($ty:ty, [$($gen:tt)*], [$($bounds:tt)*]) => {
+        impl<$($gen)*> Render for $ty
+        where
+            $ty: Get<Value = T> + Send + 'static,
+            T: Render + Clone + Send + Sync + 'static,
+            T::State: 'static,
+            $($bounds)*
+        {
+            type State = RenderEffectState<T::State>;
+
+            #[track_caller]
+            fn build(self) -> Self::State {
+                (move || self.get()).build()
+            }
+
+            #[track_caller]
+            fn rebuild(self, state: &mut Self::State) {
+                (move || self.get()).rebuild(state)
+            }
+        }
+
+        impl<$($gen)*> AddAnyAttr for $ty
+        where
+            $ty: Get<Value = T> + Send + 'static,
+            T: RenderHtml + Clone + Send + Sync + 'static,
+            T::State: 'static,
+            $($bounds)*
+        {
+            type Output<SomeNewAttr: Attribute> = Box<
+                dyn FnMut() -> T::Output<SomeNewAttr::CloneableOwned> + Send,
+            >;
+
+            fn add_any_attr<NewAttr: Attribute>(
+                self,
+                attr: NewAttr,
+            ) -> Self::Output<NewAttr>
+            where
+                Self::Output<NewAttr>: RenderHtml,
+            {
+                let attr = attr.into_cloneable_owned();
+                Box::new(move || self.get().add_any_attr(attr.clone()))
+            }
+        }
+
+        impl<$($gen)*> RenderHtml for $ty
+        where
+            $ty: Get<Value = T> + Send + 'static,
+            T: RenderHtml + Clone + Send + Sync + 'static,
+            T::State: 'static,
+            $($bounds)*
+        {
+            type AsyncOutput = Self;
+
+            const MIN_LENGTH: usize = 0;
+
+            fn dry_resolve(&mut self) {}
+
+            async fn resolve(self) -> Self::AsyncOutput {
+                self
+            }
+
+            fn html_len(&self) -> usize {
+                self.try_get_untracked()
+                    .map(|value| value.html_len())
+                    .unwrap_or(T::MIN_LENGTH)
+            }
+
+            fn to_html_with_buf(
+                self,
+                buf: &mut String,
+                position: &mut Position,
+                escape: bool,
+                mark_branches: &BranchMarks,
+            ) {
+                let value = self.get();
+                value.to_html_with_buf(buf, position, escape, mark_branches)
+            }
+
+            fn to_html_async_with_buf<const OUT_OF_ORDER: bool>(
+                self,
+                buf: &mut StreamBuilder,
+                position: &mut Position,
+                escape: bool,
+                mark_branches: &BranchMarks,
+            ) where
+                Self: Sized,
+            {
+                let value = self.get();
+                value.to_html_async_with_buf::<OUT_OF_ORDER>(
+                    buf,
+                    position,
+                    escape,
+                    mark_branches,
+                );
+            }
+
+            fn hydrate<const FROM_SERVER: bool>(
+                self,
+                cursor: &Cursor,
+                position: &PositionState,
+            ) -> Self::State {
+                (move || self.get()).hydrate::<FROM_SERVER>(cursor, position)
+            }
+        }
+
+        impl<$($gen)*> AttributeValue for $ty
+        where
+            $ty: Get<Value = T> + Clone + Send + 'static,
+            T: AttributeValue + Clone + Send + Sync + 'static,
+            T::State: 'static,
+            $($bounds)*
+        {
+            type AsyncOutput = Self;
+            type State = RenderEffect<T::State>;
+            type Cloneable = Self;
+            type CloneableOwned = Self;
+
+            fn html_len(&self) -> usize {
+                self.try_get_untracked()
+                    .map(|value| value.html_len())
+                    .unwrap_or_default()
+            }
+
+            fn to_html(self, key: &str, buf: &mut String) {
+                let value = self.get();
+                value.to_html(key, buf);
+            }
+
+            fn to_template(_key: &str, _buf: &mut String) {}
+
+            fn hydrate<const FROM_SERVER: bool>(
+                self,
+                key: &str,
+                el: &crate::renderer::types::Element,
+            ) -> Self::State {
+                (move || self.get()).hydrate::<FROM_SERVER>(key, el)
+            }
+
+            fn build(
+                self,
+                el: &crate::renderer::types::Element,
+                key: &str,
+            ) -> Self::State {
+                (move || self.get()).build(el, key)
+            }
+
+            fn rebuild(self, key: &str, state: &mut Self::State) {
+                (move || self.get()).rebuild(key, state)
+            }
+
+            fn hydrate_static<const FROM_SERVER: bool>(
+                self,
+                key: &'static str,
+                el: &crate::renderer::types::Element,
+            ) -> Self::State {
+                (move || self.get()).hydrate_static::<FROM_SERVER>(key, el)
+            }
+
+            fn build_static(
+                self,
+                el: &crate::renderer::types::Element,
+                key: &'static str,
+            ) -> Self::State {
+                (move || self.get()).build_static(el, key)
+            }
+
+            fn rebuild_static(self, key: &'static str, state: &mut Self::State) {
+                (move || self.get()).rebuild_static(key, state)
+            }
+
+            fn into_cloneable(self) -> Self::Cloneable {
+                self
+            }
+
+            fn into_cloneable_owned(self) -> Self::CloneableOwned {
+                self
+            }
+
+            fn dry_resolve(&mut self) {}
+
+            async fn resolve(self) -> Self::AsyncOutput {
+                self
+            }
+        }
+    };
+}
+
+store_field_impl!(Field<T, S>, [T, S], [S: Storage<ArcField<T>>,]);
+store_field_impl!(ArcField<T>, [T], []);
+store_field_impl!(
+    Subfield<Inner, Prev, T>,
+    [Inner, Prev, T],
+    [Inner: StoreField<Value = Prev>, Prev: 'static,]
+);
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use crate::{
+        html::element::{div, p, ElementChild},
+        view::Render,
+    };
+    use any_spawner::Executor;
+    use reactive_graph::{owner::Owner, traits::Update};
+    use reactive_stores::Store;
+    use reactive_stores_macro::Store;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    // text that counts how often it has been rendered
+    #[derive(Clone)]
+    struct Label {
+        text: &'static str,
+        renders: Arc<AtomicUsize>,
+    }
+
+    impl Label {
+        fn new(text: &'static str) -> Self {
+            Self {
+                text,
+                renders: Arc::default(),
+            }
+        }
+    }
+
+    impl Render for Label {
+        type State = <&'static str as Render>::State;
+
+        fn build(self) -> Self::State {
+            self.renders.fetch_add(1, Ordering::Relaxed);
+            self.text.build()
+        }
+
+        fn rebuild(self, state: &mut Self::State) {
+            self.renders.fetch_add(1, Ordering::Relaxed);
+            self.text.rebuild(state);
+        }
+    }
+
+    #[derive(Store)]
+    struct Profile {
+        name: Label,
+        bio: Label,
+    }
+
+    #[test]
+    fn updating_a_field_does_not_rerender_its_sibling() {
+        _ = Executor::init_manual();
+        let owner = Owner::new();
+        owner.set();
+
+        let (name, bio) = (Label::new("Alice"), Label::new("Likes Rust"));
+        let (name_renders, bio_renders) =
+            (Arc::clone(&name.renders), Arc::clone(&bio.renders));
+        let store = Store::new(Profile { name, bio });
+        let el = div()
+            .child((p().child(store.name()), p().child(store.bio())))
+            .build();
+        assert_eq!(
+            el.to_debug_html(),
+            "<div><p>Alice</p><p>Likes Rust</p></div>"
+        );
+
+        store.name().update(|name| name.text = "Bob");
+        Executor::flush();
+        assert_eq!(
+            el.to_debug_html(),
+            "<div><p>Bob</p><p>Likes Rust</p></div>"
+        );
+        assert_eq!(name_renders.load(Ordering::Relaxed), 2);
+        assert_eq!(bio_renders.load(Ordering::Relaxed), 1);
+    }
+}
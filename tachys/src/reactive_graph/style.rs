@@ -1,29 +1,67 @@
 use super::{ReactiveFunction, SharedReactiveFunction, Suspend};
-use crate::{html::style::IntoStyle, renderer::Rndr};
+use crate::{
+    html::style::{
+        add_style_declarations, replace_style_declarations, IntoStyle,
+        IntoStyleValue,
+    },
+    renderer::Rndr,
+};
 use futures::FutureExt;
-use reactive_graph::effect::RenderEffect;
-use std::{borrow::Cow, cell::RefCell, future::Future, rc::Rc};
+use reactive_graph::{effect::RenderEffect, signal::guards::ReadGuard};
+use std::{
+    borrow::Cow, cell::RefCell, future::Future, ops::Deref, rc::Rc, sync::Arc,
+};
+
+/// Retained state for a reactive CSS property: the style declaration, the property name, and the
+/// value that was last set, if any.
+type StylePropertyState = (
+    crate::renderer::types::CssStyleDeclaration,
+    &'static str,
+    Option<Cow<'static, str>>,
+);
+
+/// Sets the property to the new value if it has changed, or removes it if the value is `None`.
+fn update_style_property(
+    state: &mut StylePropertyState,
+    name: &'static str,
+    value: Option<Cow<'static, str>>,
+) {
+    let (style, prev_name, prev) = state;
+    if name != *prev_name {
+        // a different binding took over this state: clear the property it had set
+        if prev.is_some() {
+            Rndr::remove_css_property(style, prev_name);
+        }
+        *prev = None;
+        *prev_name = name;
+    }
+    if value != *prev {
+        match &value {
+            Some(value) => Rndr::set_css_property(style, name, value),
+            None => Rndr::remove_css_property(style, name),
+        }
+    }
+    *prev = value;
+}
 
 impl<F, S> IntoStyle for (&'static str, F)
 where
     F: ReactiveFunction<Output = S>,
-    S: Into<Cow<'static, str>> + 'static,
+    S: IntoStyleValue + 'static,
 {
     type AsyncOutput = Self;
-    type State = RenderEffect<(
-        crate::renderer::types::CssStyleDeclaration,
-        Cow<'static, str>,
-    )>;
+    type State = RenderEffect<StylePropertyState>;
     type Cloneable = (&'static str, SharedReactiveFunction<S>);
     type CloneableOwned = (&'static str, SharedReactiveFunction<S>);
 
     fn to_html(self, style: &mut String) {
         let (name, mut f) = self;
-        let value = f.invoke();
-        style.push_str(name);
-        style.push(':');
-        style.push_str(&value.into());
-        style.push(';');
+        if let Some(value) = f.invoke().into_style_value() {
+            style.push_str(name);
+            style.push(':');
+            style.push_str(&value);
+            style.push(';');
+        }
     }
 
     fn hydrate<const FROM_SERVER: bool>(
@@ -35,24 +73,19 @@ where
         // TODO FROM_SERVER vs template
         let style = Rndr::style(el);
         RenderEffect::new(move |prev| {
-            let value = f.invoke().into();
+            let value = f.invoke().into_style_value();
             if let Some(mut state) = prev {
-                let (style, prev): &mut (
-                    crate::renderer::types::CssStyleDeclaration,
-                    Cow<'static, str>,
-                ) = &mut state;
-                if &value != prev {
-                    Rndr::set_css_property(style, name, &value);
-                }
-                *prev = value;
+                update_style_property(&mut state, name, value);
                 state
             } else {
                 // only set the style in template mode
                 // in server mode, it's already been set
                 if !FROM_SERVER {
-                    Rndr::set_css_property(&style, name, &value);
+                    if let Some(value) = &value {
+                        Rndr::set_css_property(&style, name, value);
+                    }
                 }
-                (style.clone(), value)
+                (style.clone(), name, value)
             }
         })
     }
@@ -62,37 +95,29 @@ where
         let name = Rndr::intern(name);
         let style = Rndr::style(el);
         RenderEffect::new(move |prev| {
-            let value = f.invoke().into();
+            let value = f.invoke().into_style_value();
             if let Some(mut state) = prev {
-                let (style, prev): &mut (
-                    crate::renderer::types::CssStyleDeclaration,
-                    Cow<'static, str>,
-                ) = &mut state;
-                if &value != prev {
-                    Rndr::set_css_property(style, name, &value);
-                }
-                *prev = value;
+                update_style_property(&mut state, name, value);
                 state
             } else {
                 // always set the style initially without checking
-                Rndr::set_css_property(&style, name, &value);
-                (style.clone(), value)
+                if let Some(value) = &value {
+                    Rndr::set_css_property(&style, name, value);
+                }
+                (style.clone(), name, value)
             }
         })
     }
 
     fn rebuild(self, state: &mut Self::State) {
         let (name, mut f) = self;
+        let name = Rndr::intern(name);
         let prev_value = state.take_value();
         *state = RenderEffect::new_with_value(
             move |prev| {
-                let value = f.invoke().into();
+                let value = f.invoke().into_style_value();
                 if let Some(mut state) = prev {
-                    let (style, prev) = &mut state;
-                    if &value != prev {
-                        Rndr::set_css_property(style, name, &value);
-                    }
-                    *prev = value;
+                    update_style_property(&mut state, name, value);
                     state
                 } else {
                     unreachable!()
@@ -181,6 +206,12 @@ where
         );
     }
 
+    fn remove(state: Self::State) {
+        if let Some(state) = state.take_value() {
+            C::remove(state);
+        }
+    }
+
     fn into_cloneable(self) -> Self::Cloneable {
         self.into_shared()
     }
@@ -198,6 +229,61 @@ where
     }
 }
 
+impl<G> IntoStyle for ReadGuard<String, G>
+where
+    G: Deref<Target = String> + Send,
+{
+    type AsyncOutput = Self;
+    // the previous value is kept so its declarations can be removed, but its buffer is reused
+    type State = (crate::renderer::types::Element, String);
+    type Cloneable = Arc<str>;
+    type CloneableOwned = Arc<str>;
+
+    fn to_html(self, style: &mut String) {
+        <&str as IntoStyle>::to_html(self.as_str(), style);
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        el: &crate::renderer::types::Element,
+    ) -> Self::State {
+        (el.clone(), self.as_str().to_owned())
+    }
+
+    fn build(self, el: &crate::renderer::types::Element) -> Self::State {
+        add_style_declarations(el, self.as_str());
+        (el.clone(), self.as_str().to_owned())
+    }
+
+    fn rebuild(self, state: &mut Self::State) {
+        let (el, prev) = state;
+        if self.as_str() != prev.as_str() {
+            replace_style_declarations(el, prev, self.as_str());
+            prev.clear();
+            prev.push_str(self.as_str());
+        }
+    }
+
+    fn remove(state: Self::State) {
+        let (el, prev) = state;
+        replace_style_declarations(&el, &prev, "");
+    }
+
+    fn into_cloneable(self) -> Self::Cloneable {
+        self.as_str().into()
+    }
+
+    fn into_cloneable_owned(self) -> Self::CloneableOwned {
+        self.as_str().into()
+    }
+
+    fn dry_resolve(&mut self) {}
+
+    async fn resolve(self) -> Self::AsyncOutput {
+        self
+    }
+}
+
 #[cfg(not(feature = "nightly"))]
 mod stable {
     macro_rules! style_signal {
@@ -236,6 +322,12 @@ mod stable {
                     (move || self.get()).rebuild(state)
                 }
 
+                fn remove(state: Self::State) {
+                    if let Some(state) = state.take_value() {
+                        C::remove(state);
+                    }
+                }
+
                 fn into_cloneable(self) -> Self::Cloneable {
                     self
                 }
@@ -254,13 +346,10 @@ mod stable {
             impl<S> IntoStyle for (&'static str, $sig<S>)
             where
                 $sig<S>: Get<Value = S>,
-                S: Into<Cow<'static, str>> + Send + Sync + Clone + 'static,
+                S: IntoStyleValue + Send + Sync + Clone + 'static,
             {
                 type AsyncOutput = Self;
-                type State = RenderEffect<(
-                    crate::renderer::types::CssStyleDeclaration,
-                    Cow<'static, str>,
-                )>;
+                type State = RenderEffect<StylePropertyState>;
                 type Cloneable = Self;
                 type CloneableOwned = Self;
 
@@ -344,6 +433,12 @@ mod stable {
                     (move || self.get()).rebuild(state)
                 }
 
+                fn remove(state: Self::State) {
+                    if let Some(state) = state.take_value() {
+                        C::remove(state);
+                    }
+                }
+
                 fn into_cloneable(self) -> Self::Cloneable {
                     self
                 }
@@ -364,13 +459,10 @@ mod stable {
                 $sig<S, St>: Get<Value = S>,
                 St: Send + Sync + 'static,
                 St: Storage<S> + Storage<Option<S>>,
-                S: Into<Cow<'static, str>> + Send + Sync + Clone + 'static,
+                S: IntoStyleValue + Send + Sync + Clone + 'static,
             {
                 type AsyncOutput = Self;
-                type State = RenderEffect<(
-                    crate::renderer::types::CssStyleDeclaration,
-                    Cow<'static, str>,
-                )>;
+                type State = RenderEffect<StylePropertyState>;
                 type Cloneable = Self;
                 type CloneableOwned = Self;
 
@@ -416,8 +508,8 @@ mod stable {
         };
     }
 
-    use super::RenderEffect;
-    use crate::html::style::IntoStyle;
+    use super::{RenderEffect, StylePropertyState};
+    use crate::html::style::{IntoStyle, IntoStyleValue};
     use reactive_graph::{
         computed::{ArcMemo, Memo},
         owner::Storage,
@@ -425,7 +517,6 @@ mod stable {
         traits::Get,
         wrappers::read::{ArcSignal, MaybeSignal, Signal},
     };
-    use std::borrow::Cow;
 
     style_signal_arena!(RwSignal);
     style_signal_arena!(ReadSignal);
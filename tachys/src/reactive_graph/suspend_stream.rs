@@ -0,0 +1,576 @@
+use crate::{
+    html::attribute::Attribute,
+    hydration::Cursor,
+    renderer::Rndr,
+    ssr::{BranchMarks, StreamBuilder},
+    view::{
+        add_attr::AddAnyAttr, Mountable, Position, PositionState, Render,
+        RenderHtml,
+    },
+};
+use any_spawner::Executor;
+use futures::{
+    channel::mpsc,
+    future::{self, poll_fn, AbortHandle, Abortable},
+    FutureExt, Sink, SinkExt, Stream, StreamExt,
+};
+use reactive_graph::{
+    computed::suspense::SuspenseContext,
+    owner::{on_cleanup, use_context, Owner},
+};
+use std::{cell::RefCell, fmt::Debug, future::Future, mem, pin::Pin, rc::Rc};
+
+/// Decides when a [`SuspendStream`] stops holding its `Suspense` boundary in the pending state.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum StreamResolution {
+    /// The boundary resolves as soon as the first item has been rendered. Later items are
+    /// appended to the content as they arrive.
+    #[default]
+    FirstItem,
+    /// The boundary stays pending until the stream has ended.
+    End,
+}
+
+/// A suspended `Stream`, which can be used in the view.
+///
+/// Each item is rendered as soon as the stream yields it, and appended after the items that came
+/// before it. This lets a view show partial results progressively, rather than waiting for the
+/// whole result the way a [`Suspend`](super::Suspend) does.
+///
+/// The stream is only polled for its next item once the previous one has been rendered, so a
+/// producer that is faster than the view is held back by the stream itself. Inside a `Suspense`
+/// on the server, items are pulled while the boundary is still pending: at most one item ahead
+/// with [`StreamResolution::FirstItem`], or every item with [`StreamResolution::End`].
+///
+/// When server rendering with in-order or out-of-order streaming, each item is flushed to the
+/// response as it resolves, and the enclosing `Suspense` is resolved according to the
+/// [`StreamResolution`] (after the first item, by default).
+pub struct SuspendStream<S>
+where
+    S: Stream,
+{
+    stream: ScopedStream<S>,
+    resolve: StreamResolution,
+}
+
+impl<S> SuspendStream<S>
+where
+    S: Stream,
+{
+    /// Creates a new suspended stream.
+    pub fn new(stream: S) -> Self {
+        Self {
+            stream: ScopedStream {
+                owner: Owner::current().unwrap_or_default(),
+                source: StreamSource::Direct(Box::pin(stream)),
+            },
+            resolve: StreamResolution::default(),
+        }
+    }
+
+    /// Sets when the enclosing `Suspense` boundary should be resolved.
+    pub fn resolve_on(mut self, resolve: StreamResolution) -> Self {
+        self.resolve = resolve;
+        self
+    }
+}
+
+impl<S> Debug for SuspendStream<S>
+where
+    S: Stream,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SuspendStream")
+            .field("resolve", &self.resolve)
+            .finish()
+    }
+}
+
+// polls the stream with the owner it was created under, like a `ScopedFuture`
+struct ScopedStream<S>
+where
+    S: Stream,
+{
+    owner: Owner,
+    source: StreamSource<S>,
+}
+
+enum StreamSource<S>
+where
+    S: Stream,
+{
+    Direct(Pin<Box<S>>),
+    // items are being pulled ahead of time by another task (see `dry_resolve`)
+    Buffered(BoxedStream<S::Item>),
+}
+
+type BoxedStream<T> = Pin<Box<dyn Stream<Item = T> + Send>>;
+
+impl<S> ScopedStream<S>
+where
+    S: Stream,
+{
+    async fn next(&mut self) -> Option<S::Item> {
+        poll_fn(|cx| match &mut self.source {
+            StreamSource::Direct(stream) => {
+                self.owner.with(|| stream.as_mut().poll_next(cx))
+            }
+            StreamSource::Buffered(items) => items.as_mut().poll_next(cx),
+        })
+        .await
+    }
+
+    /// Takes every item that is available without waiting, and returns whether the stream has
+    /// ended.
+    fn take_ready(&mut self, mut item: impl FnMut(S::Item)) -> bool {
+        loop {
+            match self.next().now_or_never() {
+                Some(Some(next)) => item(next),
+                Some(None) => return true,
+                None => return false,
+            }
+        }
+    }
+}
+
+impl<S> ScopedStream<S>
+where
+    S: Stream + Send + 'static,
+{
+    /// Maps each item with an async function, keeping the original owner.
+    fn then<Fut>(
+        self,
+        fun: impl FnMut(S::Item) -> Fut + Send + 'static,
+    ) -> ScopedStream<BoxedStream<Fut::Output>>
+    where
+        Fut: Future + Send + 'static,
+    {
+        let stream: BoxedStream<S::Item> = match self.source {
+            StreamSource::Direct(stream) => stream,
+            StreamSource::Buffered(items) => items,
+        };
+        ScopedStream {
+            owner: self.owner,
+            source: StreamSource::Direct(Box::pin(Box::pin(stream.then(fun)))),
+        }
+    }
+
+    /// Pulls every item from the stream, sending them on to `tx`.
+    async fn forward_to<Tx>(mut self, mut tx: Tx, mut on_item: impl FnMut())
+    where
+        Tx: Sink<S::Item> + Unpin,
+    {
+        while let Some(item) = self.next().await {
+            // `feed` doesn't wait for the item to be read, unlike `send`
+            if tx.feed(item).await.is_err() {
+                break;
+            }
+            on_item();
+        }
+    }
+}
+
+/// Retained view state for [`SuspendStream`].
+pub struct SuspendStreamState<T>
+where
+    T: Mountable,
+{
+    inner: Rc<RefCell<StreamedItems<T>>>,
+}
+
+struct StreamedItems<T> {
+    states: Vec<T>,
+    // like a `Vec<_>`, new items are added before a placeholder at the end
+    marker: crate::renderer::types::Placeholder,
+}
+
+impl<T> StreamedItems<T>
+where
+    T: Mountable,
+{
+    fn push(&mut self, mut state: T) {
+        // if the items aren't mounted right now, they'll all be mounted together later
+        self.marker.insert_before_this(&mut state);
+        self.states.push(state);
+    }
+}
+
+impl<T> Mountable for SuspendStreamState<T>
+where
+    T: Mountable,
+{
+    fn unmount(&mut self) {
+        let mut inner = self.inner.borrow_mut();
+        for state in inner.states.iter_mut() {
+            state.unmount();
+        }
+        inner.marker.unmount();
+    }
+
+    fn mount(
+        &mut self,
+        parent: &crate::renderer::types::Element,
+        marker: Option<&crate::renderer::types::Node>,
+    ) {
+        let mut inner = self.inner.borrow_mut();
+        for state in inner.states.iter_mut() {
+            state.mount(parent, marker);
+        }
+        inner.marker.mount(parent, marker);
+    }
+
+    fn insert_before_this(&self, child: &mut dyn Mountable) -> bool {
+        let inner = self.inner.borrow();
+        inner
+            .states
+            .iter()
+            .any(|state| state.insert_before_this(child))
+            || inner.marker.insert_before_this(child)
+    }
+}
+
+impl<S> SuspendStream<S>
+where
+    S: Stream + 'static,
+    S::Item: Render,
+{
+    /// Renders the items that are ready now, then spawns a task to append the rest.
+    fn stream_into(
+        self,
+        items: &Rc<RefCell<StreamedItems<<S::Item as Render>::State>>>,
+        mut first: impl FnMut(S::Item) -> <S::Item as Render>::State,
+    ) {
+        let Self {
+            mut stream,
+            resolve,
+        } = self;
+
+        let mut rendered_any = false;
+        let ended = stream.take_ready(|item| {
+            let state = first(item);
+            items.borrow_mut().states.push(state);
+            rendered_any = true;
+        });
+        if ended {
+            return;
+        }
+
+        // hold the enclosing Suspense until the first item, or the end of the stream
+        let mut task = use_context::<SuspenseContext>()
+            .filter(|_| {
+                !(rendered_any && resolve == StreamResolution::FirstItem)
+            })
+            .map(|sc| sc.task_id());
+
+        // stop appending items once this view has been cleaned up
+        let (abort_handle, abort_registration) = AbortHandle::new_pair();
+        on_cleanup(move || abort_handle.abort());
+
+        let items = Rc::clone(items);
+        let fut = async move {
+            while let Some(item) = stream.next().await {
+                let state = item.build();
+                items.borrow_mut().push(state);
+                if resolve == StreamResolution::FirstItem {
+                    task.take();
+                }
+            }
+            drop(task);
+        };
+        reactive_graph::spawn_local_scoped(async move {
+            _ = Abortable::new(fut, abort_registration).await;
+        });
+    }
+}
+
+impl<S> Render for SuspendStream<S>
+where
+    S: Stream + 'static,
+    S::Item: Render,
+{
+    type State = SuspendStreamState<<S::Item as Render>::State>;
+
+    fn build(self) -> Self::State {
+        let inner = Rc::new(RefCell::new(StreamedItems {
+            states: Vec::new(),
+            marker: Rndr::create_placeholder(),
+        }));
+        self.stream_into(&inner, Render::build);
+        SuspendStreamState { inner }
+    }
+
+    fn rebuild(self, state: &mut Self::State) {
+        {
+            let mut inner = state.inner.borrow_mut();
+            for mut old in inner.states.drain(..) {
+                old.unmount();
+            }
+        }
+        let marker = state.inner.borrow().marker.clone();
+        self.stream_into(&state.inner, move |item| {
+            let mut state = item.build();
+            marker.insert_before_this(&mut state);
+            state
+        });
+    }
+}
+
+impl<S> AddAnyAttr for SuspendStream<S>
+where
+    S: Stream + Send + 'static,
+    S::Item: AddAnyAttr,
+{
+    type Output<SomeNewAttr: Attribute> = SuspendStream<
+        Pin<
+            Box<
+                dyn Stream<
+                        Item = <S::Item as AddAnyAttr>::Output<
+                            SomeNewAttr::CloneableOwned,
+                        >,
+                    > + Send,
+            >,
+        >,
+    >;
+
+    fn add_any_attr<NewAttr: Attribute>(
+        self,
+        attr: NewAttr,
+    ) -> Self::Output<NewAttr>
+    where
+        Self::Output<NewAttr>: RenderHtml,
+    {
+        let attr = attr.into_cloneable_owned();
+        SuspendStream {
+            stream: self.stream.then(move |item| {
+                future::ready(item.add_any_attr(attr.clone()))
+            }),
+            resolve: self.resolve,
+        }
+    }
+}
+
+impl<S> RenderHtml for SuspendStream<S>
+where
+    S: Stream + Send + 'static,
+    S::Item: RenderHtml,
+{
+    type AsyncOutput =
+        SuspendStream<BoxedStream<<S::Item as RenderHtml>::AsyncOutput>>;
+
+    const MIN_LENGTH: usize = 3;
+
+    fn html_len(&self) -> usize {
+        3
+    }
+
+    fn to_html_with_buf(
+        mut self,
+        buf: &mut String,
+        position: &mut Position,
+        escape: bool,
+        mark_branches: &BranchMarks,
+    ) {
+        // synchronous rendering can only include the items that are already available
+        self.stream.take_ready(|item| {
+            item.to_html_with_buf(buf, position, escape, mark_branches)
+        });
+        buf.push_str("<!>");
+    }
+
+    fn to_html_async_with_buf<const OUT_OF_ORDER: bool>(
+        mut self,
+        buf: &mut StreamBuilder,
+        position: &mut Position,
+        escape: bool,
+        mark_branches: &BranchMarks,
+    ) where
+        Self: Sized,
+    {
+        let ended = self.stream.take_ready(|item| {
+            item.to_html_async_with_buf::<OUT_OF_ORDER>(
+                buf,
+                position,
+                escape,
+                mark_branches,
+            )
+        });
+        if ended {
+            buf.push_sync("<!>");
+        } else if OUT_OF_ORDER {
+            push_rest_out_of_order(
+                self.stream,
+                buf,
+                position,
+                escape,
+                mark_branches,
+            );
+        } else {
+            push_rest_in_order(
+                self.stream,
+                buf,
+                position,
+                escape,
+                mark_branches,
+            );
+        }
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        cursor: &Cursor,
+        position: &PositionState,
+    ) -> Self::State {
+        // the items that are ready now are expected to match the ones rendered on the server, just
+        // like the items of a `Vec<_>`; later items are appended after them
+        let mut hydrated = Vec::new();
+        let mut stream = self.stream;
+        let ended = stream.take_ready(|item| hydrated.push(item));
+        let states = hydrated
+            .into_iter()
+            .map(|item| item.hydrate::<FROM_SERVER>(cursor, position))
+            .collect();
+        let marker = cursor.next_placeholder(position);
+        let inner = Rc::new(RefCell::new(StreamedItems { states, marker }));
+        if !ended {
+            let this = SuspendStream {
+                stream,
+                resolve: self.resolve,
+            };
+            this.stream_into(&inner, Render::build);
+        }
+        SuspendStreamState { inner }
+    }
+
+    // the items are resolved lazily, so that they can still be streamed one by one
+    async fn resolve(self) -> Self::AsyncOutput {
+        SuspendStream {
+            stream: self.stream.then(RenderHtml::resolve),
+            resolve: self.resolve,
+        }
+    }
+
+    fn dry_resolve(&mut self) {
+        // a Suspense waits for its tasks to finish before rendering its children, so the stream
+        // has to be pulled by a separate task, which holds the boundary until the first item or
+        // the end of the stream
+        let Some(suspense) = use_context::<SuspenseContext>() else {
+            return;
+        };
+        if let StreamSource::Buffered(_) = self.stream.source {
+            return;
+        }
+
+        let task = suspense.task_id();
+        let owner = self.stream.owner.clone();
+        match self.resolve {
+            // with a rendezvous channel, an item is only pulled once the one before it is read
+            StreamResolution::FirstItem => {
+                let (tx, rx) = mpsc::channel(0);
+                let source = mem::replace(
+                    &mut self.stream.source,
+                    StreamSource::Buffered(Box::pin(rx)),
+                );
+                let mut task = Some(task);
+                Executor::spawn(ScopedStream { owner, source }.forward_to(
+                    tx,
+                    move || {
+                        task.take();
+                    },
+                ));
+            }
+            // nothing is read before the stream has ended, so every item is buffered
+            StreamResolution::End => {
+                let (tx, rx) = mpsc::unbounded();
+                let source = mem::replace(
+                    &mut self.stream.source,
+                    StreamSource::Buffered(Box::pin(rx)),
+                );
+                Executor::spawn(async move {
+                    ScopedStream { owner, source }.forward_to(tx, || {}).await;
+                    drop(task);
+                });
+            }
+        }
+    }
+}
+
+// each item is a new async chunk, so it is flushed as soon as it resolves
+fn push_rest_in_order<S>(
+    mut stream: ScopedStream<S>,
+    buf: &mut StreamBuilder,
+    position: &mut Position,
+    escape: bool,
+    mark_branches: &BranchMarks,
+) where
+    S: Stream + Send + 'static,
+    S::Item: RenderHtml,
+{
+    let id = buf.clone_id();
+    let mut item_position = *position;
+    let mark_branches = mark_branches.clone();
+    buf.push_async(async move {
+        let mut builder = StreamBuilder::new(id);
+        match stream.next().await {
+            Some(item) => {
+                item.to_html_async_with_buf::<false>(
+                    &mut builder,
+                    &mut item_position,
+                    escape,
+                    &mark_branches,
+                );
+                push_rest_in_order(
+                    stream,
+                    &mut builder,
+                    &mut item_position,
+                    escape,
+                    &mark_branches,
+                );
+            }
+            None => builder.push_sync("<!>"),
+        }
+        builder.finish().take_chunks()
+    });
+    *position = Position::NextChild;
+}
+
+// each item replaces an empty out-of-order chunk, and leaves a new one behind it for the next item
+fn push_rest_out_of_order<S>(
+    mut stream: ScopedStream<S>,
+    buf: &mut StreamBuilder,
+    position: &mut Position,
+    escape: bool,
+    mark_branches: &BranchMarks,
+) where
+    S: Stream + Send + 'static,
+    S::Item: RenderHtml,
+{
+    buf.next_id();
+    let mut fallback_position = *position;
+    buf.push_fallback::<()>((), &mut fallback_position, mark_branches);
+    let mark_branches = mark_branches.clone();
+    buf.push_async_out_of_order_with(
+        async move {
+            let item = stream.next().await;
+            Some((item, stream))
+        },
+        position,
+        move |(item, stream), builder, position| match item {
+            Some(item) => {
+                item.to_html_async_with_buf::<true>(
+                    builder,
+                    position,
+                    escape,
+                    &mark_branches,
+                );
+                push_rest_out_of_order(
+                    stream,
+                    builder,
+                    position,
+                    escape,
+                    &mark_branches,
+                );
+            }
+            None => builder.push_sync("<!>"),
+        },
+    );
+}
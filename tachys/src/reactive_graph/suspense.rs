@@ -1,13 +1,15 @@
 use crate::{
     html::attribute::Attribute,
     hydration::Cursor,
-    ssr::StreamBuilder,
+    renderer::Rndr,
+    ssr::{BranchMarks, StreamBuilder},
     view::{
         add_attr::AddAnyAttr, iterators::OptionState, Mountable, Position,
         PositionState, Render, RenderHtml,
     },
 };
 use any_spawner::Executor;
+use either_of::Either;
 use futures::{
     future::{AbortHandle, Abortable},
     select, FutureExt,
@@ -301,13 +303,14 @@ where
         buf: &mut String,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) {
         // TODO wrap this with a Suspense as needed
         // currently this is just used for Routes, which creates a Suspend but never actually needs
         // it (because we don't lazy-load routes on the server)
+        // it is rendered as `Some(_)`, so that its branch markers match those hydration expects
         if let Some(inner) = self.inner.now_or_never() {
-            inner.to_html_with_buf(buf, position, escape, mark_branches);
+            Some(inner).to_html_with_buf(buf, position, escape, mark_branches);
         }
     }
 
@@ -316,13 +319,13 @@ where
         buf: &mut StreamBuilder,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) where
         Self: Sized,
     {
         let mut fut = Box::pin(self.inner);
         match fut.as_mut().now_or_never() {
-            Some(inner) => inner.to_html_async_with_buf::<OUT_OF_ORDER>(
+            Some(inner) => Some(inner).to_html_async_with_buf::<OUT_OF_ORDER>(
                 buf,
                 position,
                 escape,
@@ -358,16 +361,20 @@ where
                             mark_branches,
                         );
                     } else {
+                        let mark_branches = mark_branches.clone();
                         buf.push_async({
                             let mut position = *position;
                             async move {
                                 let value = fut.await;
-                                let mut builder = StreamBuilder::new(id);
+                                let mut builder = StreamBuilder::with_capacity(
+                                    value.html_len(),
+                                    id,
+                                );
                                 value.to_html_async_with_buf::<OUT_OF_ORDER>(
                                     &mut builder,
                                     &mut position,
                                     escape,
-                                    mark_branches,
+                                    &mark_branches,
                                 );
                                 builder.finish().take_chunks()
                             }
@@ -399,9 +406,23 @@ where
         // otherwise, start with the fallback
         let initial = fut.as_mut().now_or_never().and_then(Result::ok);
         let initially_pending = initial.is_none();
-        let inner = Rc::new(RefCell::new(
-            initial.hydrate::<FROM_SERVER>(cursor, position),
-        ));
+
+        // if the server rendered the resolved view here, but it isn't ready yet in the browser,
+        // skip over it for now, and hydrate it from this checkpoint once it's ready
+        let checkpoint = (FROM_SERVER && initially_pending)
+            .then(|| cursor.skip_branch("0", position))
+            .flatten();
+        let inner = Rc::new(RefCell::new(if checkpoint.is_some() {
+            Either::Right(Rndr::create_placeholder())
+        } else {
+            initial.hydrate::<FROM_SERVER>(cursor, position)
+        }));
+        let late_cursor = checkpoint.map(|checkpoint| {
+            let cursor = cursor.deep_clone();
+            let position = PositionState::new(Position::FirstChild);
+            cursor.restore(&checkpoint, &position);
+            (cursor, position)
+        });
 
         // get a unique ID if there's a SuspenseContext
         let id = use_context::<SuspenseContext>().map(|sc| sc.task_id());
@@ -422,7 +443,24 @@ where
                     drop(id);
 
                     if let Ok(value) = value {
-                        Some(value).rebuild(&mut *state.borrow_mut());
+                        match late_cursor {
+                            Some((cursor, position)) => {
+                                let mut state = state.borrow_mut();
+                                let placeholder = mem::replace(
+                                    &mut *state,
+                                    Some(value)
+                                        .hydrate::<true>(&cursor, &position),
+                                );
+                                if let Either::Right(mut placeholder) =
+                                    placeholder
+                                {
+                                    placeholder.unmount();
+                                }
+                            }
+                            None => {
+                                Some(value).rebuild(&mut *state.borrow_mut())
+                            }
+                        }
                     }
 
                     subscriber.forward();
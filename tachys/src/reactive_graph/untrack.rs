@@ -0,0 +1,245 @@
+use crate::{
+    html::attribute::Attribute,
+    hydration::Cursor,
+    ssr::{BranchMarks, StreamBuilder},
+    view::{add_attr::AddAnyAttr, Position, PositionState, Render, RenderHtml},
+};
+use reactive_graph::{graph::untrack, traits::Track};
+
+/// Creates a reactive view that is only rebuilt when `trigger` is notified.
+///
+/// Like any reactive function, this is rendered in a
+/// [`RenderEffect`](reactive_graph::effect::RenderEffect), but that effect only tracks the
+/// trigger: signals read inside `fun` are read without tracking, so changing them does not
+/// rebuild the view until the trigger fires again.
+/// ```rust,ignore
+/// let redraw = ArcTrigger::new();
+/// div().child(on_trigger(redraw.clone(), move || draw_chart(data.get())))
+/// // ...later, after drawing-related state has changed
+/// redraw.notify();
+/// ```
+pub fn on_trigger<T, F, V>(
+    trigger: T,
+    mut fun: F,
+) -> impl FnMut() -> V + Send + 'static
+where
+    T: Track + Send + 'static,
+    F: FnMut() -> V + Send + 'static,
+{
+    move || {
+        trigger.track();
+        untrack(&mut fun)
+    }
+}
+
+/// A view wrapper that renders a closure a single time, without creating a
+/// [`RenderEffect`](reactive_graph::effect::RenderEffect).
+///
+/// Any signals read inside the closure are read without tracking, so the view will not update
+/// when they change. Rebuilding the view (for example, because a parent re-rendered) will call
+/// the closure again.
+#[derive(Debug, Clone)]
+pub struct Untrack<F, V> {
+    fun: F,
+    // the value created by `dry_resolve`, which is rendered instead of calling `fun` again
+    value: Option<V>,
+}
+
+impl<F, V> Untrack<F, V>
+where
+    F: FnMut() -> V,
+{
+    /// Wraps a closure so that it is only rendered once.
+    pub fn new(fun: F) -> Self {
+        Self { fun, value: None }
+    }
+
+    fn invoke(&mut self) -> V {
+        match self.value.take() {
+            Some(value) => value,
+            None => untrack(&mut self.fun),
+        }
+    }
+}
+
+impl<F, V> Render for Untrack<F, V>
+where
+    F: FnMut() -> V,
+    V: Render,
+{
+    type State = V::State;
+
+    fn build(mut self) -> Self::State {
+        self.invoke().build()
+    }
+
+    fn rebuild(mut self, state: &mut Self::State) {
+        self.invoke().rebuild(state);
+    }
+}
+
+impl<F, V> AddAnyAttr for Untrack<F, V>
+where
+    F: FnMut() -> V + Send + 'static,
+    V: RenderHtml + 'static,
+{
+    type Output<SomeNewAttr: Attribute> = Untrack<
+        Box<dyn FnMut() -> V::Output<SomeNewAttr::CloneableOwned> + Send>,
+        V::Output<SomeNewAttr::CloneableOwned>,
+    >;
+
+    fn add_any_attr<NewAttr: Attribute>(
+        mut self,
+        attr: NewAttr,
+    ) -> Self::Output<NewAttr>
+    where
+        Self::Output<NewAttr>: RenderHtml,
+    {
+        let attr = attr.into_cloneable_owned();
+        let value = self
+            .value
+            .take()
+            .map(|value| value.add_any_attr(attr.clone()));
+        let mut fun = self.fun;
+        Untrack {
+            fun: Box::new(move || fun().add_any_attr(attr.clone())),
+            value,
+        }
+    }
+}
+
+impl<F, V> RenderHtml for Untrack<F, V>
+where
+    F: FnMut() -> V + Send + 'static,
+    V: RenderHtml + 'static,
+{
+    type AsyncOutput = V::AsyncOutput;
+
+    const MIN_LENGTH: usize = V::MIN_LENGTH;
+
+    fn dry_resolve(&mut self) {
+        let mut value = self.invoke();
+        value.dry_resolve();
+        self.value = Some(value);
+    }
+
+    async fn resolve(mut self) -> Self::AsyncOutput {
+        self.invoke().resolve().await
+    }
+
+    fn to_html_with_buf(
+        mut self,
+        buf: &mut String,
+        position: &mut Position,
+        escape: bool,
+        mark_branches: &BranchMarks,
+    ) {
+        self.invoke()
+            .to_html_with_buf(buf, position, escape, mark_branches);
+    }
+
+    fn to_html_async_with_buf<const OUT_OF_ORDER: bool>(
+        mut self,
+        buf: &mut StreamBuilder,
+        position: &mut Position,
+        escape: bool,
+        mark_branches: &BranchMarks,
+    ) where
+        Self: Sized,
+    {
+        self.invoke().to_html_async_with_buf::<OUT_OF_ORDER>(
+            buf,
+            position,
+            escape,
+            mark_branches,
+        );
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(
+        mut self,
+        cursor: &Cursor,
+        position: &PositionState,
+    ) -> Self::State {
+        self.invoke().hydrate::<FROM_SERVER>(cursor, position)
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::Untrack;
+    use crate::{
+        html::element::{div, ElementChild},
+        view::{Render, RenderHtml},
+    };
+    use any_spawner::Executor;
+    use reactive_graph::{
+        owner::Owner,
+        signal::RwSignal,
+        traits::{Get, Set},
+    };
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    fn counted(
+        label: RwSignal<&'static str>,
+    ) -> (
+        Arc<AtomicUsize>,
+        impl FnMut() -> &'static str + Send + 'static,
+    ) {
+        let draws = Arc::new(AtomicUsize::new(0));
+        let fun = {
+            let draws = Arc::clone(&draws);
+            move || {
+                draws.fetch_add(1, Ordering::Relaxed);
+                label.get()
+            }
+        };
+        (draws, fun)
+    }
+
+    #[test]
+    fn untrack_renders_once() {
+        _ = Executor::init_manual();
+        let owner = Owner::new();
+        owner.set();
+
+        let label = RwSignal::new("a");
+        let (draws, fun) = counted(label);
+        let el = div().child(Untrack::new(fun)).build();
+        assert_eq!(el.to_debug_html(), "<div>a</div>");
+
+        label.set("b");
+        Executor::flush();
+        assert_eq!(draws.load(Ordering::Relaxed), 1);
+        assert_eq!(el.to_debug_html(), "<div>a</div>");
+    }
+
+    #[test]
+    fn untrack_rerenders_when_rebuilt() {
+        _ = Executor::init_manual();
+        let owner = Owner::new();
+        owner.set();
+
+        let label = RwSignal::new("a");
+        let (draws, fun) = counted(label);
+        let mut state = div().child(Untrack::new(fun)).build();
+
+        label.set("b");
+        Executor::flush();
+        assert_eq!(draws.load(Ordering::Relaxed), 1);
+        div()
+            .child(Untrack::new(move || label.get()))
+            .rebuild(&mut state);
+        assert_eq!(state.to_debug_html(), "<div>b</div>");
+    }
+
+    #[test]
+    fn untrack_renders_html_once() {
+        let label = RwSignal::new("a");
+        let (draws, fun) = counted(label);
+        assert_eq!(div().child(Untrack::new(fun)).to_html(), "<div>a</div>");
+        assert_eq!(draws.load(Ordering::Relaxed), 1);
+    }
+}
@@ -0,0 +1,59 @@
+use crate::{
+    html::{attribute::hidden, style::style},
+    view::RenderHtml,
+};
+use either_of::Either;
+
+/// How a view is hidden by [`visible_when_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HideWith {
+    /// Sets the `hidden` attribute.
+    #[default]
+    HiddenAttribute,
+    /// Sets an inline `display: none` style.
+    DisplayNone,
+}
+
+/// Creates a view that is hidden, rather than unmounted, whenever `when` returns `false`.
+///
+/// Rendering a view conditionally (with an `Option` or `Either`) removes the hidden branch, and
+/// rebuilds it from scratch when it is shown again. This builds the view once, and only toggles
+/// its `hidden` attribute, so that its DOM and reactive state are kept while it is hidden:
+/// ```rust,ignore
+/// visible_when(move || tab.get() == Tab::Editor, div().child(editor()))
+/// ```
+/// The attribute is spread onto the view, so it should be an element (or a component whose root
+/// is an element). When rendered on the server, the attribute is included if the view starts out
+/// hidden.
+pub fn visible_when<C, V>(when: C, view: V) -> impl RenderHtml
+where
+    C: Fn() -> bool + Send + Sync + 'static,
+    V: RenderHtml,
+{
+    visible_when_with(when, view, HideWith::HiddenAttribute)
+}
+
+/// Creates a view that is hidden, rather than unmounted, whenever `when` returns `false`, using
+/// the given way of hiding it.
+///
+/// See [`visible_when`] for details.
+pub fn visible_when_with<C, V>(
+    when: C,
+    view: V,
+    hide_with: HideWith,
+) -> impl RenderHtml
+where
+    C: Fn() -> bool + Send + Sync + 'static,
+    V: RenderHtml,
+{
+    match hide_with {
+        HideWith::HiddenAttribute => {
+            Either::Left(view.add_any_attr(hidden(move || !when())))
+        }
+        HideWith::DisplayNone => {
+            Either::Right(view.add_any_attr(style(("display", move || {
+                (!when()).then_some("none")
+            }))))
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use super::{CastFrom, RemoveEventHandler};
+use super::{attribute_namespace, CastFrom, RemoveEventHandler};
 use crate::{
     dom::{document, window},
     ok_or_debug, or_debug,
@@ -7,16 +7,77 @@ use crate::{
 use linear_map::LinearMap;
 use once_cell::unsync::Lazy;
 use rustc_hash::FxHashSet;
-use std::{any::TypeId, borrow::Cow, cell::RefCell};
-use wasm_bindgen::{intern, prelude::Closure, JsCast, JsValue};
+use std::{
+    any::TypeId,
+    borrow::Cow,
+    cell::{Cell, RefCell},
+};
+use wasm_bindgen::{
+    intern,
+    prelude::{wasm_bindgen, Closure},
+    JsCast, JsValue,
+};
 use web_sys::{Comment, HtmlTemplateElement};
 
+/// The number of class names or CSS properties above which [`Dom::set_class_tokens`] and
+/// [`Dom::set_style_properties`] replace the whole attribute, rather than making one call for each.
+pub const BATCH_THRESHOLD: usize = 4;
+
+// each element keeps a JavaScript `Map` from class names to the number of class values holding
+// them, so the counts are dropped along with the element
+const CLASS_COUNTS: &str = "$$classCounts";
+
+fn class_counts(el: &Element) -> js_sys::Map {
+    let key = JsValue::from_str(CLASS_COUNTS);
+    js_sys::Reflect::get(el, &key)
+        .ok()
+        .and_then(|counts| counts.dyn_into::<js_sys::Map>().ok())
+        .unwrap_or_else(|| {
+            let counts = js_sys::Map::new();
+            or_debug!(
+                js_sys::Reflect::set(el, &key, &counts),
+                el,
+                "set property"
+            );
+            counts
+        })
+}
+
+#[wasm_bindgen(inline_js = "
+    export function apply_style_properties(style, remove, set) {
+        if (remove) {
+            for (const name of remove.split('\\0')) {
+                style.removeProperty(name);
+            }
+        }
+        if (set) {
+            const parts = set.split('\\0');
+            for (let i = 0; i + 1 < parts.length; i += 2) {
+                style.setProperty(parts[i], parts[i + 1]);
+            }
+        }
+    }
+")]
+extern "C" {
+    // `remove` holds property names, and `set` alternating names and values, each separated by NUL
+    fn apply_style_properties(
+        style: &CssStyleDeclaration,
+        remove: &str,
+        set: &str,
+    );
+}
+
 /// A [`Renderer`] that uses `web-sys` to manipulate DOM elements in the browser.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Dom;
 
 thread_local! {
     pub(crate) static GLOBAL_EVENTS: RefCell<FxHashSet<Cow<'static, str>>> = Default::default();
+    static INTERN_VALUES_UP_TO: Cell<usize> = const { Cell::new(0) };
+    static CUSTOM_ELEMENTS: Lazy<js_sys::WeakSet> = Lazy::new(js_sys::WeakSet::new);
+    static HAS_CUSTOM_ELEMENTS: Cell<bool> = const { Cell::new(false) };
+    // (tag, key) pairs that are known to be properties of a registered custom element
+    static CUSTOM_ELEMENT_PROPERTIES: RefCell<FxHashSet<(String, String)>> = Default::default();
 }
 
 pub type Node = web_sys::Node;
@@ -33,6 +94,23 @@ impl Dom {
         intern(text)
     }
 
+    /// Interns attribute values with at most `len` bytes, so that values which are set over and
+    /// over (like `"true"` or a few class names) are only copied into JavaScript once. This is
+    /// `0` (off) by default.
+    ///
+    /// Interning only has an effect with the `interning` feature.
+    pub fn intern_values_up_to(len: usize) {
+        INTERN_VALUES_UP_TO.set(len);
+    }
+
+    fn intern_value(value: &str) -> &str {
+        if value.len() <= INTERN_VALUES_UP_TO.get() {
+            intern(value)
+        } else {
+            value
+        }
+    }
+
     pub fn create_element(tag: &str, namespace: Option<&str>) -> Element {
         if let Some(namespace) = namespace {
             document()
@@ -67,12 +145,59 @@ impl Dom {
 
     #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace"))]
     pub fn set_attribute(node: &Element, name: &str, value: &str) {
-        or_debug!(node.set_attribute(name, value), node, "setAttribute");
+        let value = Self::intern_value(value);
+        // only look up custom elements once there are any, so other elements pay nothing for them
+        if HAS_CUSTOM_ELEMENTS.get()
+            && CUSTOM_ELEMENTS.with(|elements| elements.has(node))
+        {
+            return Self::set_attribute_or_property(node, name, value);
+        }
+        match attribute_namespace(name) {
+            Some(namespace) => {
+                Self::set_attribute_ns(node, namespace, name, value)
+            }
+            None => {
+                or_debug!(node.set_attribute(name, value), node, "setAttribute")
+            }
+        }
     }
 
     #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace"))]
     pub fn remove_attribute(node: &Element, name: &str) {
-        or_debug!(node.remove_attribute(name), node, "removeAttribute");
+        match attribute_namespace(name) {
+            Some(namespace) => Self::remove_attribute_ns(node, namespace, name),
+            None => {
+                or_debug!(node.remove_attribute(name), node, "removeAttribute")
+            }
+        }
+    }
+
+    /// Sets an attribute in the given namespace. `name` is the qualified name, including any
+    /// prefix (like `xlink:href`).
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace"))]
+    pub fn set_attribute_ns(
+        node: &Element,
+        namespace: &str,
+        name: &str,
+        value: &str,
+    ) {
+        or_debug!(
+            node.set_attribute_ns(Some(namespace), name, value),
+            node,
+            "setAttributeNS"
+        );
+    }
+
+    /// Removes an attribute from the given namespace. `name` is the qualified name, including
+    /// any prefix; only its local part is used to find the attribute.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace"))]
+    pub fn remove_attribute_ns(node: &Element, namespace: &str, name: &str) {
+        let local_name = name.split_once(':').map_or(name, |(_, local)| local);
+        or_debug!(
+            node.remove_attribute_ns(Some(namespace), local_name),
+            node,
+            "removeAttributeNS"
+        );
     }
 
     #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace"))]
@@ -88,6 +213,26 @@ impl Dom {
         );
     }
 
+    /// Creates a detached `DocumentFragment` that children can be mounted into, before they are
+    /// all inserted into the document at once with [`Dom::append_fragment`].
+    ///
+    /// The fragment is typed as an [`Element`] so that it can be passed to
+    /// [`Mountable::mount`], which only ever calls node methods on its parent.
+    pub fn create_fragment() -> Element {
+        document().create_document_fragment().unchecked_into()
+    }
+
+    /// Moves all the children of a fragment created by [`Dom::create_fragment`] into `parent`,
+    /// before `anchor`.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace"))]
+    pub fn append_fragment(
+        parent: &Element,
+        fragment: &Element,
+        anchor: Option<&Node>,
+    ) {
+        Self::insert_node(parent, fragment, anchor);
+    }
+
     #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace"))]
     pub fn remove_node(parent: &Element, child: &Node) -> Option<Node> {
         ok_or_debug!(parent.remove_child(child), parent, "removeNode")
@@ -152,6 +297,34 @@ impl Dom {
         }
     }
 
+    pub fn text_content(node: &Node) -> Option<String> {
+        node.text_content()
+    }
+
+    pub fn split_text(node: &Text, offset: usize) -> Option<Text> {
+        node.split_text(offset as u32).ok()
+    }
+
+    pub fn comment_text(node: &Node) -> Option<String> {
+        (node.node_type() == Node::COMMENT_NODE)
+            .then(|| node.text_content().unwrap_or_default())
+    }
+
+    pub fn describe_node(node: &Node) -> String {
+        match node.node_type() {
+            Node::ELEMENT_NODE => {
+                node.unchecked_ref::<Element>().tag_name().to_lowercase()
+            }
+            Node::TEXT_NODE => {
+                format!("#text {:?}", node.text_content().unwrap_or_default())
+            }
+            Node::COMMENT_NODE => {
+                format!("<!--{}-->", node.text_content().unwrap_or_default())
+            }
+            _ => node.node_name().to_lowercase(),
+        }
+    }
+
     pub fn log_node(node: &Node) {
         web_sys::console::log_1(node);
     }
@@ -194,6 +367,52 @@ impl Dom {
         }
     }
 
+    /// Marks an element as a custom element, so that attributes set on it with
+    /// [`Dom::set_attribute`] go through [`Dom::set_attribute_or_property`].
+    pub fn mark_custom_element(el: &Element) {
+        HAS_CUSTOM_ELEMENTS.set(true);
+        CUSTOM_ELEMENTS.with(|elements| elements.add(el));
+    }
+
+    /// Sets `key` as a property if the element is a registered custom element with a property of
+    /// that name, and as an attribute otherwise.
+    ///
+    /// Before the element's tag is registered, this always sets the attribute, which the custom
+    /// element can read when it is upgraded.
+    pub fn set_attribute_or_property(el: &Element, key: &str, value: &str) {
+        let tag = el.local_name();
+        let cache_key = (tag, key.to_owned());
+        let is_property = CUSTOM_ELEMENT_PROPERTIES
+            .with_borrow(|properties| properties.contains(&cache_key))
+            || {
+                // this is only cached once it is true, because the element may not have been
+                // registered or upgraded yet
+                let (tag, key) = &cache_key;
+                let is_property =
+                    !window().custom_elements().get(tag).is_undefined()
+                        && js_sys::Reflect::has(el, &JsValue::from_str(key))
+                            .unwrap_or(false);
+                if is_property {
+                    CUSTOM_ELEMENT_PROPERTIES.with_borrow_mut(|properties| {
+                        properties.insert(cache_key)
+                    });
+                }
+                is_property
+            };
+        if is_property {
+            Self::set_property(el, key, &JsValue::from_str(value));
+        } else {
+            match attribute_namespace(key) {
+                Some(namespace) => {
+                    Self::set_attribute_ns(el, namespace, key, value)
+                }
+                None => {
+                    or_debug!(el.set_attribute(key, value), el, "setAttribute")
+                }
+            }
+        }
+    }
+
     pub fn set_property(el: &Element, key: &str, value: &JsValue) {
         or_debug!(
             js_sys::Reflect::set(
@@ -261,11 +480,25 @@ impl Dom {
     ) -> RemoveEventHandler<Element> {
         let cb = Closure::wrap(cb);
         let key = intern(&delegation_key);
-        or_debug!(
-            js_sys::Reflect::set(el, &JsValue::from_str(key), cb.as_ref()),
-            el,
-            "set property"
-        );
+        // each element holds a list of handlers for the event, so that adding a second handler
+        // for the same event (for example, by spreading attributes) doesn't replace the first
+        let handlers = js_sys::Reflect::get(el, &JsValue::from_str(key))
+            .ok()
+            .and_then(|handlers| handlers.dyn_into::<js_sys::Array>().ok())
+            .unwrap_or_else(|| {
+                let handlers = js_sys::Array::new();
+                or_debug!(
+                    js_sys::Reflect::set(
+                        el,
+                        &JsValue::from_str(key),
+                        &handlers
+                    ),
+                    el,
+                    "set property"
+                );
+                handlers
+            });
+        handlers.push(cb.as_ref());
 
         GLOBAL_EVENTS.with(|global_events| {
             let mut events = global_events.borrow_mut();
@@ -292,12 +525,20 @@ impl Dom {
                         .unwrap()
                         .is_truthy();
                         if !node_is_disabled {
-                            let maybe_handler =
+                            let maybe_handlers =
                                 js_sys::Reflect::get(&node, &key).unwrap();
-                            if !maybe_handler.is_undefined() {
-                                let f = maybe_handler
-                                    .unchecked_ref::<js_sys::Function>();
-                                let _ = f.call1(&node, &ev);
+                            if let Some(handlers) =
+                                maybe_handlers.dyn_ref::<js_sys::Array>()
+                            {
+                                // handlers run in the order they were added; iterate over a
+                                // copy, in case one of them removes itself
+                                for handler in
+                                    handlers.slice(0, handlers.length())
+                                {
+                                    let f = handler
+                                        .unchecked_ref::<js_sys::Function>();
+                                    let _ = f.call1(&node, &ev);
+                                }
 
                                 if ev.cancel_bubble() {
                                     return;
@@ -342,15 +583,26 @@ impl Dom {
             // so it will always be accessed or dropped from the main thread
             let cb = send_wrapper::SendWrapper::new(cb);
             move |el: &Element| {
-                drop(cb.take());
-                or_debug!(
-                    js_sys::Reflect::delete_property(
-                        el,
-                        &JsValue::from_str(&key)
-                    ),
-                    el,
-                    "delete property"
-                );
+                let cb = cb.take();
+                let key = JsValue::from_str(&key);
+                let handlers =
+                    js_sys::Reflect::get(el, &key).ok().and_then(|handlers| {
+                        handlers.dyn_into::<js_sys::Array>().ok()
+                    });
+                if let Some(handlers) = handlers {
+                    let idx = handlers.index_of(cb.as_ref(), 0);
+                    if idx >= 0 {
+                        handlers.splice_many(idx as u32, 1, &[]);
+                    }
+                    if handlers.length() == 0 {
+                        or_debug!(
+                            js_sys::Reflect::delete_property(el, &key),
+                            el,
+                            "delete property"
+                        );
+                    }
+                }
+                drop(cb);
             }
         })
     }
@@ -367,6 +619,121 @@ impl Dom {
         or_debug!(list.remove_1(name), list.unchecked_ref(), "remove()");
     }
 
+    /// Removes the class names in `remove` from an element, and then adds those in `add`.
+    ///
+    /// Above [`BATCH_THRESHOLD`] class names, the `class` attribute is read and written once,
+    /// rather than calling `classList` for each one.
+    pub fn set_class_tokens(el: &Element, add: &[&str], remove: &[&str]) {
+        if add.len() + remove.len() <= BATCH_THRESHOLD {
+            let class_list = Self::class_list(el);
+            for name in remove {
+                Self::remove_class(&class_list, name);
+            }
+            for name in add {
+                Self::add_class(&class_list, name);
+            }
+            return;
+        }
+
+        let prev = el.get_attribute("class").unwrap_or_default();
+        let mut tokens = prev
+            .split_ascii_whitespace()
+            .filter(|name| !remove.contains(name))
+            .collect::<Vec<_>>();
+        for name in add {
+            if !tokens.contains(name) {
+                tokens.push(name);
+            }
+        }
+        let next = tokens.join(" ");
+        if next != prev {
+            Self::set_attribute(el, "class", &next);
+        }
+    }
+
+    /// Counts each class name in `names` as held by one more class value on the element, and
+    /// returns those that were not held before, which should be added to its class list.
+    pub fn hold_class_tokens<'a>(
+        el: &Element,
+        names: &[&'a str],
+    ) -> Vec<&'a str> {
+        if names.is_empty() {
+            return Vec::new();
+        }
+        let counts = class_counts(el);
+        names
+            .iter()
+            .copied()
+            .filter(|name| {
+                let name = JsValue::from_str(name);
+                let count = counts.get(&name).as_f64().unwrap_or_default();
+                counts.set(&name, &JsValue::from_f64(count + 1.0));
+                count == 0.0
+            })
+            .collect()
+    }
+
+    /// Releases each class name in `names` once, and returns those that no class value on the
+    /// element holds any more, which should be removed from its class list.
+    pub fn release_class_tokens<'a>(
+        el: &Element,
+        names: &[&'a str],
+    ) -> Vec<&'a str> {
+        if names.is_empty() {
+            return Vec::new();
+        }
+        let counts = class_counts(el);
+        names
+            .iter()
+            .copied()
+            .filter(|name| {
+                let name = JsValue::from_str(name);
+                let count = counts.get(&name).as_f64().unwrap_or_default();
+                if count > 1.0 {
+                    counts.set(&name, &JsValue::from_f64(count - 1.0));
+                    false
+                } else {
+                    counts.delete(&name);
+                    true
+                }
+            })
+            .collect()
+    }
+
+    /// Removes the CSS properties in `remove` from an element, and then sets those in `set`.
+    ///
+    /// Above [`BATCH_THRESHOLD`] properties, these are all passed to JavaScript in a single call,
+    /// which then calls `removeProperty` or `setProperty` for each one, rather than crossing into
+    /// JavaScript for every property.
+    pub fn set_style_properties(
+        el: &Element,
+        set: &[(&str, &str)],
+        remove: &[&str],
+    ) {
+        let style = Self::style(el);
+        // names and values are joined with NUL, which can't appear in a property that the
+        // browser would keep, so anything containing it is left to the unbatched path
+        let has_separator = remove.iter().any(|name| name.contains('\0'))
+            || set.iter().any(|(name, value)| {
+                name.contains('\0') || value.contains('\0')
+            });
+        if set.len() + remove.len() <= BATCH_THRESHOLD || has_separator {
+            for name in remove {
+                Self::remove_css_property(&style, name);
+            }
+            for (name, value) in set {
+                Self::set_css_property(&style, name, value);
+            }
+            return;
+        }
+
+        let set = set
+            .iter()
+            .flat_map(|(name, value)| [*name, *value])
+            .collect::<Vec<_>>();
+        apply_style_properties(&style, &remove.join("\0"), &set.join("\0"));
+    }
+
     pub fn style(el: &Element) -> CssStyleDeclaration {
         el.unchecked_ref::<web_sys::HtmlElement>().style()
     }
@@ -383,6 +750,14 @@ impl Dom {
         );
     }
 
+    pub fn remove_css_property(style: &CssStyleDeclaration, name: &str) {
+        or_debug!(
+            style.remove_property(name),
+            style.unchecked_ref(),
+            "removeProperty"
+        );
+    }
+
     pub fn set_inner_html(el: &Element, html: &str) {
         el.set_inner_html(html);
     }
@@ -1,24 +1,51 @@
-#![allow(unused)]
-
-//! A stupidly-simple mock DOM implementation that can be used for testing.
+//! A simple mock DOM, implemented in Rust, that views can be rendered into in ordinary tests.
 //!
-//! Do not use this for anything real.
-
-use super::{CastFrom, DomRenderer, RemoveEventHandler, Renderer};
-use crate::{
-    html::element::{CreateElement, ElementType},
-    view::Mountable,
+//! With the `testing` feature, this replaces the browser DOM as the renderer, so that views can
+//! be built, updated, hydrated and inspected in `cargo test` without a browser or WASM. It keeps
+//! track of elements, text and comment nodes, attributes (including classes and styles) and event
+//! listeners, and can print any node as HTML with [`Node::to_debug_html`].
+//!
+//! This also works on the server. The node types are plain IDs into a thread-local document, so
+//! they are `Send`, and a view can be built, updated by setting signals, and then written out with
+//! [`Node::serialize`], which produces the same HTML as server rendering would for the view's
+//! current state.
+//!
+//! Updates are applied by effects, which run as spawned tasks. Tests should use
+//! [`Executor::init_manual`](any_spawner::Executor::init_manual), so that those tasks only run
+//! when the test calls [`Executor::flush`](any_spawner::Executor::flush), and the DOM can be
+//! checked right afterwards:
+//!
+//! ```rust,ignore
+//! _ = Executor::init_manual();
+//! let count = RwSignal::new(0);
+//! let el = button().child(move || count.get()).build();
+//!
+//! count.set(1);
+//! Executor::flush();
+//! assert_eq!(el.to_debug_html(), "<button>1</button>");
+//! ```
+//!
+//! It does not run any JavaScript: properties set with `prop:` are ignored, and the event passed
+//! to an event listener by [`dispatch_event`] is `undefined`, so a listener can only use the event
+//! to find its [target](MockDom::event_target).
+
+use super::{
+    attribute_namespace,
+    recording::{record, DomOp},
+    CastFrom, ClassCounts, RemoveEventHandler,
 };
+use crate::view::{Mountable, ToTemplate};
 use indexmap::IndexMap;
+use linear_map::LinearMap;
 use slotmap::{new_key_type, SlotMap};
-use std::{borrow::Cow, cell::RefCell, rc::Rc};
+use std::{any::TypeId, borrow::Cow, cell::RefCell, fmt::Write, rc::Rc};
 use wasm_bindgen::JsValue;
 
-/// A [`Renderer`] that uses a mock DOM structure running in Rust code.
+/// A renderer that uses a mock DOM structure running in Rust code.
 ///
-/// This is intended as a rendering background that can be used to test component logic, without
+/// This is intended as a rendering backend that can be used to test component logic, without
 /// running a browser.
-#[derive(Debug)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct MockDom;
 
 new_key_type! {
@@ -42,599 +69,1211 @@ pub struct Text(Node);
 #[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
 pub struct Placeholder(Node);
 
+/// The class list of a mock element.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ClassList(Element);
+
+/// The inline styles of a mock element.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CssStyleDeclaration(Element);
+
+/// A mock `<template>` element.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TemplateElement(Element);
+
+/// The event passed to event listeners. The mock DOM has no JavaScript values to pass, so this is
+/// always `undefined`.
+pub type Event = JsValue;
+
+macro_rules! node_type {
+    ($($ty:ident),*) => {
+        $(
+            impl AsRef<Node> for $ty {
+                fn as_ref(&self) -> &Node {
+                    &self.0
+                }
+            }
+
+            impl From<$ty> for Node {
+                fn from(value: $ty) -> Self {
+                    value.0
+                }
+            }
+
+            impl std::ops::Deref for $ty {
+                type Target = Node;
+
+                fn deref(&self) -> &Node {
+                    &self.0
+                }
+            }
+        )*
+    };
+}
+
+node_type!(Element, Text, Placeholder);
+
 impl AsRef<Node> for Node {
     fn as_ref(&self) -> &Node {
         self
     }
 }
 
-impl AsRef<Node> for Element {
-    fn as_ref(&self) -> &Node {
-        &self.0
-    }
+/// Tests whether two nodes are references to the same underlying node.
+pub fn node_eq(a: impl AsRef<Node>, b: impl AsRef<Node>) -> bool {
+    a.as_ref() == b.as_ref()
 }
 
-impl AsRef<Node> for Text {
-    fn as_ref(&self) -> &Node {
-        &self.0
-    }
+/// The type of mock DOM node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NodeType {
+    /// A text node.
+    Text(String),
+    /// A comment, which is used for placeholders and hydration markers.
+    Comment(String),
+    /// An element.
+    Element {
+        /// The HTML tag name.
+        tag: Cow<'static, str>,
+        /// The namespace, if the element was created in one.
+        namespace: Option<String>,
+        /// The attributes, in the order they were first set.
+        attrs: IndexMap<String, String>,
+        /// The element's children.
+        children: Vec<Node>,
+    },
+    /// A document fragment, whose children are moved out of it when it is inserted.
+    Fragment {
+        /// The fragment's children.
+        children: Vec<Node>,
+    },
 }
 
-impl AsRef<Node> for Placeholder {
-    fn as_ref(&self) -> &Node {
-        &self.0
+/// The DOM data associated with a particular node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeData {
+    /// The node's parent.
+    pub parent: Option<NodeId>,
+    /// The node itself.
+    pub ty: NodeType,
+}
+
+impl NodeType {
+    fn children(&self) -> Option<&Vec<Node>> {
+        match self {
+            NodeType::Element { children, .. }
+            | NodeType::Fragment { children } => Some(children),
+            _ => None,
+        }
+    }
+
+    fn children_mut(&mut self) -> Option<&mut Vec<Node>> {
+        match self {
+            NodeType::Element { children, .. }
+            | NodeType::Fragment { children } => Some(children),
+            _ => None,
+        }
     }
 }
 
-/// Tests whether two nodes are references to the same underlying node.
-pub fn node_eq(a: impl AsRef<Node>, b: impl AsRef<Node>) -> bool {
-    a.as_ref() == b.as_ref()
+type Listener = Rc<RefCell<Box<dyn FnMut(Event)>>>;
+
+struct Document {
+    nodes: SlotMap<NodeId, NodeData>,
+    listeners: IndexMap<NodeId, Vec<(Cow<'static, str>, usize, Listener)>>,
+    next_listener: usize,
+    templates: LinearMap<TypeId, TemplateElement>,
+    // the mocked custom element registry, from each tag to its property names
+    custom_elements: IndexMap<String, Vec<String>>,
+    // the elements marked as custom elements, and the properties set on them
+    custom_element_properties: IndexMap<NodeId, IndexMap<String, String>>,
+    // the class names held by the class values on each element
+    class_counts: IndexMap<NodeId, ClassCounts>,
+    // the namespace of each attribute set in one, by element and qualified name
+    attribute_namespaces: IndexMap<NodeId, IndexMap<String, String>>,
 }
 
-impl From<Text> for Node {
-    fn from(value: Text) -> Self {
-        Node(value.0 .0)
-    }
+thread_local! {
+    static DOCUMENT: RefCell<Document> = RefCell::new(Document {
+        nodes: SlotMap::with_key(),
+        listeners: IndexMap::new(),
+        next_listener: 0,
+        templates: LinearMap::new(),
+        custom_elements: IndexMap::new(),
+        custom_element_properties: IndexMap::new(),
+        class_counts: IndexMap::new(),
+        attribute_namespaces: IndexMap::new(),
+    });
+    static CURRENT_TARGET: RefCell<Option<Element>> = const { RefCell::new(None) };
 }
 
-impl From<Element> for Node {
-    fn from(value: Element) -> Self {
-        Node(value.0 .0)
-    }
+fn with_node<U>(node: &Node, f: impl FnOnce(&NodeData) -> U) -> U {
+    DOCUMENT.with(|d| {
+        f(d.borrow()
+            .nodes
+            .get(node.0)
+            .expect("mock DOM node has been reset"))
+    })
 }
 
-impl From<Placeholder> for Node {
-    fn from(value: Placeholder) -> Self {
-        Node(value.0 .0)
-    }
+fn with_node_mut<U>(node: &Node, f: impl FnOnce(&mut NodeData) -> U) -> U {
+    DOCUMENT.with(|d| {
+        f(d.borrow_mut()
+            .nodes
+            .get_mut(node.0)
+            .expect("mock DOM node has been reset"))
+    })
 }
 
-impl Element {
-    /// Outputs an HTML form of the element, for testing and debugging purposes.
-    pub fn to_debug_html(&self) -> String {
-        let mut buf = String::new();
-        self.debug_html(&mut buf);
-        buf
-    }
+fn create_node(ty: NodeType) -> Node {
+    DOCUMENT.with(|d| {
+        Node(d.borrow_mut().nodes.insert(NodeData { parent: None, ty }))
+    })
 }
 
-/// The DOM data associated with a particular node.
-#[derive(Debug, PartialEq, Eq)]
-pub struct NodeData {
-    /// The node's parent.
-    pub parent: Option<NodeId>,
-    /// The node itself.
-    pub ty: NodeType,
+fn new_element(tag: &str, namespace: Option<&str>) -> Element {
+    Element(create_node(NodeType::Element {
+        tag: tag.to_string().into(),
+        namespace: namespace.map(str::to_string),
+        attrs: IndexMap::new(),
+        children: Vec::new(),
+    }))
+}
+
+fn write_attribute(el: &Element, name: &str, value: Option<&str>) {
+    with_node_mut(el, |node| {
+        if let NodeType::Element { attrs, .. } = &mut node.ty {
+            match value {
+                Some(value) => {
+                    attrs.insert(name.to_string(), value.to_string());
+                }
+                None => {
+                    attrs.shift_remove(name);
+                }
+            }
+        }
+    });
 }
 
-trait DebugHtml {
-    fn debug_html(&self, buf: &mut String);
+// finds the qualified name of an attribute that was set in `namespace` with this local name
+fn qualified_attribute_name(
+    el: &Element,
+    namespace: &str,
+    local_name: &str,
+) -> Option<String> {
+    DOCUMENT.with(|d| {
+        d.borrow()
+            .attribute_namespaces
+            .get(&el.0 .0)?
+            .iter()
+            .find(|(name, ns)| {
+                ns.as_str() == namespace
+                    && name
+                        .split_once(':')
+                        .map_or(name.as_str(), |(_, local)| local)
+                        == local_name
+            })
+            .map(|(name, _)| name.clone())
+    })
 }
 
-impl DebugHtml for Element {
-    fn debug_html(&self, buf: &mut String) {
-        Document::with_node(self.0 .0, |node| {
-            node.debug_html(buf);
-        });
+// the operations below are used to implement the public ones, and are not recorded themselves
+
+fn insert(parent: &Element, new_child: &Node, anchor: Option<&Node>) {
+    debug_assert!(parent.0 != *new_child);
+    // a fragment's children are moved into the parent, rather than the fragment itself
+    if let NodeType::Fragment { children } = new_child.data().ty {
+        for child in children {
+            insert(parent, &child, anchor);
+        }
+        return;
+    }
+
+    // remove if already mounted
+    if let Some(prev_parent) = MockDom::get_parent(new_child) {
+        detach(&Element(prev_parent), new_child);
     }
+    with_node_mut(parent, |parent| {
+        let children =
+            parent.ty.children_mut().expect("parent is not an element");
+        match anchor {
+            None => children.push(new_child.clone()),
+            Some(anchor) => {
+                let anchor = children
+                    .iter()
+                    .position(|item| item == anchor)
+                    .expect("anchor is not a child of the parent");
+                children.insert(anchor, new_child.clone());
+            }
+        }
+    });
+    with_node_mut(new_child, |node| node.parent = Some(parent.0 .0));
 }
 
-impl DebugHtml for Text {
-    fn debug_html(&self, buf: &mut String) {
-        Document::with_node(self.0 .0, |node| {
-            node.debug_html(buf);
-        });
+fn detach(parent: &Element, child: &Node) -> Option<Node> {
+    let removed = with_node_mut(parent, |parent| {
+        let children = parent.ty.children_mut()?;
+        let index = children.iter().position(|item| item == child)?;
+        Some(children.remove(index))
+    })?;
+    with_node_mut(&removed, |node| node.parent = None);
+    Some(removed)
+}
+
+fn clear(parent: &Element) {
+    let children = with_node_mut(parent, |node| {
+        node.ty
+            .children_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    });
+    for child in children {
+        with_node_mut(&child, |node| node.parent = None);
     }
 }
 
-impl DebugHtml for Node {
-    fn debug_html(&self, buf: &mut String) {
-        Document::with_node(self.0, |node| {
-            node.debug_html(buf);
-        });
+fn replace_children(el: &Element, html: &str) {
+    clear(el);
+    for node in parse_html(html) {
+        insert(el, &node, None);
     }
 }
 
-impl DebugHtml for NodeData {
-    fn debug_html(&self, buf: &mut String) {
-        match &self.ty {
-            NodeType::Text(text) => buf.push_str(text),
+/// Removes every node and event listener from the mock DOM.
+///
+/// Any node that was created before this is no longer valid.
+pub fn reset() {
+    DOCUMENT.with(|d| {
+        let mut d = d.borrow_mut();
+        d.nodes.clear();
+        d.listeners.clear();
+        d.templates.clear();
+        d.custom_elements.clear();
+        d.custom_element_properties.clear();
+        d.class_counts.clear();
+        d.attribute_namespaces.clear();
+    });
+}
+
+/// Registers a custom element with the given tag and property names, like
+/// `customElements.define()`.
+///
+/// Once it is registered, attributes set on elements with this tag that were marked with
+/// [`MockDom::mark_custom_element`] are set as properties instead, if the name is one of
+/// `properties`. This stays registered until [`reset`].
+pub fn define_custom_element(tag: &str, properties: &[&str]) {
+    DOCUMENT.with(|d| {
+        d.borrow_mut().custom_elements.insert(
+            tag.to_string(),
+            properties.iter().map(|name| name.to_string()).collect(),
+        );
+    });
+}
+
+impl Node {
+    /// Returns a copy of the data for this node.
+    pub fn data(&self) -> NodeData {
+        with_node(self, Clone::clone)
+    }
+
+    /// Outputs an HTML form of the node and its children, for testing and debugging purposes.
+    pub fn to_debug_html(&self) -> String {
+        let mut buf = String::new();
+        self.write_html(&mut buf, false);
+        buf
+    }
+
+    /// Outputs the node and its children as HTML in the same form as server rendering.
+    ///
+    /// A view that has been built and then updated serializes to the same HTML as
+    /// [`RenderHtml::to_html`](crate::view::RenderHtml::to_html) produces for its current state.
+    /// Unlike [`Node::to_debug_html`], this separates adjacent text nodes with a `<!>` marker, and
+    /// writes attributes with an empty value, like boolean attributes, without one.
+    pub fn serialize(&self) -> String {
+        let mut buf = String::new();
+        self.write_html(&mut buf, true);
+        buf
+    }
+
+    fn write_html(&self, buf: &mut String, ssr: bool) {
+        let write_children = |children: Vec<Node>, buf: &mut String| {
+            let mut prev_text = false;
+            for child in children {
+                let text = matches!(child.data().ty, NodeType::Text(_));
+                if ssr && text && prev_text {
+                    buf.push_str("<!>");
+                }
+                prev_text = text;
+                child.write_html(buf, ssr);
+            }
+        };
+
+        match self.data().ty {
+            NodeType::Text(text) => {
+                html_escape::encode_text_to_string(text, buf);
+            }
+            NodeType::Comment(text) if text.is_empty() => buf.push_str("<!>"),
+            NodeType::Comment(text) => {
+                _ = write!(buf, "<!--{text}-->");
+            }
             NodeType::Element {
                 tag,
                 attrs,
                 children,
+                ..
             } => {
                 buf.push('<');
-                buf.push_str(tag);
-                for (k, v) in attrs {
+                buf.push_str(&tag);
+                for (name, value) in attrs {
                     buf.push(' ');
-                    buf.push_str(k);
-                    buf.push_str("=\"");
-                    buf.push_str(v);
-                    buf.push('"');
+                    buf.push_str(&name);
+                    if !ssr || !value.is_empty() {
+                        buf.push_str("=\"");
+                        html_escape::encode_double_quoted_attribute_to_string(
+                            value, buf,
+                        );
+                        buf.push('"');
+                    }
                 }
                 buf.push('>');
-
-                for child in children {
-                    child.debug_html(buf);
+                if !VOID_ELEMENTS.contains(&&*tag) {
+                    write_children(children, buf);
+                    _ = write!(buf, "</{tag}>");
                 }
-
-                buf.push_str("</");
-                buf.push_str(tag);
-                buf.push('>');
             }
-            NodeType::Placeholder => buf.push_str("<!>"),
+            NodeType::Fragment { children } => write_children(children, buf),
         }
     }
 }
 
-/// The mock DOM document.
-#[derive(Clone)]
-pub struct Document(Rc<RefCell<SlotMap<NodeId, NodeData>>>);
-
-impl Document {
-    /// Creates a new document.
-    pub fn new() -> Self {
-        Document(Default::default())
-    }
-
-    fn with_node<U>(id: NodeId, f: impl FnOnce(&NodeData) -> U) -> Option<U> {
-        DOCUMENT.with(|d| {
-            let data = d.0.borrow();
-            let data = data.get(id);
-            data.map(f)
-        })
+impl Element {
+    /// The element's tag name.
+    pub fn tag_name(&self) -> String {
+        match self.data().ty {
+            NodeType::Element { tag, .. } => tag.into_owned(),
+            _ => String::new(),
+        }
     }
 
-    fn with_node_mut<U>(
-        id: NodeId,
-        f: impl FnOnce(&mut NodeData) -> U,
-    ) -> Option<U> {
-        DOCUMENT.with(|d| {
-            let mut data = d.0.borrow_mut();
-            let data = data.get_mut(id);
-            data.map(f)
+    /// Returns the value of an attribute, if it is set.
+    pub fn get_attribute(&self, name: &str) -> Option<String> {
+        with_node(self, |node| match &node.ty {
+            NodeType::Element { attrs, .. } => attrs.get(name).cloned(),
+            _ => None,
         })
     }
 
-    /// Resets the document's contents.
-    pub fn reset(&self) {
-        self.0.borrow_mut().clear();
+    /// Returns the value of an attribute in the given namespace, by its local name, if it is set.
+    pub fn get_attribute_ns(
+        &self,
+        namespace: &str,
+        local_name: &str,
+    ) -> Option<String> {
+        let name = qualified_attribute_name(self, namespace, local_name)?;
+        self.get_attribute(&name)
     }
 
-    fn create_element(&self, tag: &str) -> Element {
-        Element(Node(self.0.borrow_mut().insert(NodeData {
-            parent: None,
-            ty: NodeType::Element {
-                tag: tag.to_string().into(),
-                attrs: IndexMap::new(),
-                children: Vec::new(),
-            },
-        })))
+    /// Returns the value of a property set by [`MockDom::set_attribute_or_property`], if any.
+    pub fn get_property(&self, name: &str) -> Option<String> {
+        DOCUMENT.with(|d| {
+            d.borrow()
+                .custom_element_properties
+                .get(&self.0 .0)
+                .and_then(|properties| properties.get(name).cloned())
+        })
     }
 
-    fn create_text_node(&self, data: &str) -> Text {
-        Text(Node(self.0.borrow_mut().insert(NodeData {
-            parent: None,
-            ty: NodeType::Text(data.to_string()),
-        })))
+    /// Returns the element's child nodes.
+    pub fn children(&self) -> Vec<Node> {
+        with_node(self, |node| node.ty.children().cloned().unwrap_or_default())
     }
+}
 
-    fn create_placeholder(&self) -> Placeholder {
-        Placeholder(Node(self.0.borrow_mut().insert(NodeData {
-            parent: None,
-            ty: NodeType::Placeholder,
-        })))
+/// Dispatches an event of type `name` (like `click`) at `target`.
+///
+/// Like a real event, it bubbles up from the target through each of its ancestors, calling the
+/// listeners for that event on each, whether they were added directly or through delegation.
+pub fn dispatch_event(target: &Element, name: &str) {
+    let prev = CURRENT_TARGET.with(|t| t.replace(Some(target.clone())));
+    let mut node = Some(target.0.clone());
+    while let Some(current) = node {
+        let listeners = DOCUMENT.with(|d| {
+            d.borrow()
+                .listeners
+                .get(&current.0)
+                .map(|listeners| {
+                    listeners
+                        .iter()
+                        .filter(|(event, ..)| event == name)
+                        .map(|(.., listener)| Rc::clone(listener))
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default()
+        });
+        for listener in listeners {
+            (listener.borrow_mut())(JsValue::UNDEFINED);
+        }
+        node = MockDom::get_parent(&current);
     }
+    CURRENT_TARGET.with(|t| t.replace(prev));
 }
 
-// TODO!
-impl DomRenderer for MockDom {
-    type Event = ();
-    type ClassList = ();
-    type CssStyleDeclaration = ();
-    type TemplateElement = ();
+// elements that never have children or a closing tag
+const VOID_ELEMENTS: [&str; 13] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta",
+    "source", "track", "wbr",
+];
 
-    fn set_property(el: &Self::Element, key: &str, value: &JsValue) {
-        todo!()
+impl MockDom {
+    pub fn intern(text: &str) -> &str {
+        text
     }
 
-    fn add_event_listener(
-        el: &Self::Element,
-        name: &str,
-        cb: Box<dyn FnMut(Self::Event)>,
-    ) -> RemoveEventHandler<Self::Element> {
-        todo!()
+    pub fn create_element(tag: &str, namespace: Option<&str>) -> Element {
+        record(|| DomOp::CreateElement(tag.to_string()));
+        new_element(tag, namespace)
     }
 
-    fn add_event_listener_delegated(
-        el: &Self::Element,
-        name: Cow<'static, str>,
-        delegation_key: Cow<'static, str>,
-        cb: Box<dyn FnMut(Self::Event)>,
-    ) -> RemoveEventHandler<Self::Element> {
-        todo!()
+    pub fn create_text_node(text: &str) -> Text {
+        record(|| DomOp::CreateText(text.to_string()));
+        Text(create_node(NodeType::Text(text.to_string())))
     }
 
-    fn class_list(el: &Self::Element) -> Self::ClassList {
-        todo!()
+    pub fn create_placeholder() -> Placeholder {
+        record(|| DomOp::CreatePlaceholder);
+        Placeholder(create_node(NodeType::Comment(String::new())))
     }
 
-    fn add_class(class_list: &Self::ClassList, name: &str) {
-        todo!()
+    pub fn set_text(node: &Text, text: &str) {
+        record(|| DomOp::SetText(text.to_string()));
+        with_node_mut(node, |node| {
+            if let NodeType::Text(node) = &mut node.ty {
+                *node = text.to_string();
+            }
+        });
     }
 
-    fn remove_class(class_list: &Self::ClassList, name: &str) {
-        todo!()
+    pub fn set_attribute(node: &Element, name: &str, value: &str) {
+        let is_custom_element = DOCUMENT.with(|d| {
+            d.borrow()
+                .custom_element_properties
+                .contains_key(&node.0 .0)
+        });
+        if is_custom_element {
+            return Self::set_attribute_or_property(node, name, value);
+        }
+        // like the DOM renderer, prefixes with a fixed namespace are set in that namespace
+        if let Some(namespace) = attribute_namespace(name) {
+            return Self::set_attribute_ns(node, namespace, name, value);
+        }
+        record(|| DomOp::SetAttribute(name.to_string(), value.to_string()));
+        write_attribute(node, name, Some(value));
     }
 
-    fn style(el: &Self::Element) -> Self::CssStyleDeclaration {
-        todo!()
+    pub fn remove_attribute(node: &Element, name: &str) {
+        if let Some(namespace) = attribute_namespace(name) {
+            return Self::remove_attribute_ns(node, namespace, name);
+        }
+        record(|| DomOp::RemoveAttribute(name.to_string()));
+        write_attribute(node, name, None);
     }
 
-    fn set_css_property(
-        style: &Self::CssStyleDeclaration,
+    /// Sets an attribute in the given namespace. The mock DOM stores it under its qualified name,
+    /// and keeps its namespace for [`Element::get_attribute_ns`].
+    pub fn set_attribute_ns(
+        node: &Element,
+        namespace: &str,
         name: &str,
         value: &str,
     ) {
-        todo!()
+        record(|| DomOp::SetAttribute(name.to_string(), value.to_string()));
+        write_attribute(node, name, Some(value));
+        DOCUMENT.with(|d| {
+            d.borrow_mut()
+                .attribute_namespaces
+                .entry(node.0 .0)
+                .or_default()
+                .insert(name.to_string(), namespace.to_string());
+        });
     }
 
-    fn set_inner_html(el: &Self::Element, html: &str) {
-        todo!()
+    /// Removes an attribute from the given namespace. As in the DOM, only the local part of
+    /// `name` is used to find it.
+    pub fn remove_attribute_ns(node: &Element, namespace: &str, name: &str) {
+        let local_name = name.split_once(':').map_or(name, |(_, local)| local);
+        let Some(name) = qualified_attribute_name(node, namespace, local_name)
+        else {
+            return;
+        };
+        record(|| DomOp::RemoveAttribute(name.clone()));
+        write_attribute(node, &name, None);
+        DOCUMENT.with(|d| {
+            if let Some(namespaces) =
+                d.borrow_mut().attribute_namespaces.get_mut(&node.0 .0)
+            {
+                namespaces.shift_remove(&name);
+            }
+        });
     }
 
-    fn event_target<T>(ev: &Self::Event) -> T
-    where
-        T: CastFrom<Self::Element>,
-    {
-        todo!()
+    pub fn insert_node(
+        parent: &Element,
+        new_child: &Node,
+        anchor: Option<&Node>,
+    ) {
+        record(|| DomOp::Insert(Self::describe_node(new_child)));
+        insert(parent, new_child, anchor);
     }
 
-    fn get_template<V>() -> Self::TemplateElement
-    where
-        V: crate::view::ToTemplate + 'static,
-    {
-        todo!()
+    /// Creates a detached fragment that children can be mounted into, before they are all
+    /// inserted at once with [`MockDom::append_fragment`].
+    pub fn create_fragment() -> Element {
+        Element(create_node(NodeType::Fragment {
+            children: Vec::new(),
+        }))
     }
 
-    fn clone_template(tpl: &Self::TemplateElement) -> Self::Element {
-        todo!()
+    /// Moves all the children of a fragment into `parent`, before `anchor`.
+    pub fn append_fragment(
+        parent: &Element,
+        fragment: &Element,
+        anchor: Option<&Node>,
+    ) {
+        Self::insert_node(parent, fragment, anchor);
     }
 
-    fn create_element_from_html(html: &str) -> Self::Element {
-        todo!()
+    pub fn remove_node(parent: &Element, child: &Node) -> Option<Node> {
+        record(|| DomOp::Remove(Self::describe_node(child)));
+        detach(parent, child)
     }
-}
 
-impl Default for Document {
-    fn default() -> Self {
-        Self::new()
+    pub fn remove(node: &Node) {
+        if let Some(parent) = Self::get_parent(node) {
+            Self::remove_node(&Element(parent), node);
+        }
     }
-}
-
-thread_local! {
-    static DOCUMENT: Document = Document::new();
-}
 
-/// Returns the global document.
-pub fn document() -> Document {
-    DOCUMENT.with(Clone::clone)
-}
+    pub fn get_parent(node: &Node) -> Option<Node> {
+        with_node(node, |node| node.parent).map(Node)
+    }
 
-/// The type of mock DOM node.
-#[derive(Debug, PartialEq, Eq)]
-pub enum NodeType {
-    /// A text node.
-    Text(String),
-    /// An element.
-    Element {
-        /// The HTML tag name.
-        tag: Cow<'static, str>,
-        /// The attributes.
-        attrs: IndexMap<String, String>,
-        /// The element's children.
-        children: Vec<Node>,
-    },
-    /// A placeholder.
-    Placeholder,
-}
+    pub fn first_child(node: &Node) -> Option<Node> {
+        with_node(node, |node| node.ty.children()?.first().cloned())
+    }
 
-impl Mountable<MockDom> for Node {
-    fn unmount(&mut self) {
-        todo!()
+    pub fn next_sibling(node: &Node) -> Option<Node> {
+        let parent = Self::get_parent(node)?;
+        with_node(&parent, |parent| {
+            let children = parent.ty.children()?;
+            let index = children.iter().position(|item| item == node)?;
+            children.get(index + 1).cloned()
+        })
     }
 
-    fn mount(&mut self, parent: &Element, marker: Option<&Node>) {
-        MockDom::insert_node(parent, self, marker);
+    pub fn text_content(node: &Node) -> Option<String> {
+        Some(match node.data().ty {
+            NodeType::Text(text) | NodeType::Comment(text) => text,
+            NodeType::Element { children, .. }
+            | NodeType::Fragment { children } => children
+                .iter()
+                .filter(|child| {
+                    !matches!(child.data().ty, NodeType::Comment(_))
+                })
+                .filter_map(Self::text_content)
+                .collect(),
+        })
     }
 
-    fn insert_before_this(&self, child: &mut dyn Mountable<MockDom>) -> bool {
-        let parent = MockDom::get_parent(self).and_then(Element::cast_from);
-        if let Some(parent) = parent {
-            child.mount(&parent, Some(self));
-            return true;
+    pub fn split_text(node: &Text, offset: usize) -> Option<Text> {
+        let NodeType::Text(text) = node.data().ty else {
+            return None;
+        };
+        // the offset is in UTF-16 code units, as in the browser
+        let mut units = 0;
+        let index = text
+            .char_indices()
+            .find(|(_, c)| {
+                let found = units >= offset;
+                units += c.len_utf16();
+                found
+            })
+            .map_or(text.len(), |(index, _)| index);
+        let (before, after) = text.split_at(index);
+        let next = Self::create_text_node(after);
+        with_node_mut(node, |node| {
+            node.ty = NodeType::Text(before.to_string())
+        });
+        if let Some(parent) = Self::get_parent(node) {
+            let sibling = Self::next_sibling(node);
+            insert(&Element(parent), &next, sibling.as_ref());
         }
-        false
+        Some(next)
     }
-}
 
-impl Mountable<MockDom> for Text {
-    fn unmount(&mut self) {
-        todo!()
+    pub fn comment_text(node: &Node) -> Option<String> {
+        match node.data().ty {
+            NodeType::Comment(text) => Some(text),
+            _ => None,
+        }
     }
 
-    fn mount(&mut self, parent: &Element, marker: Option<&Node>) {
-        MockDom::insert_node(parent, self.as_ref(), marker);
+    pub fn describe_node(node: &Node) -> String {
+        match node.data().ty {
+            NodeType::Element { tag, .. } => tag.to_lowercase(),
+            NodeType::Text(text) => format!("#text {text:?}"),
+            NodeType::Comment(text) => format!("<!--{text}-->"),
+            NodeType::Fragment { .. } => "#document-fragment".to_string(),
+        }
     }
 
-    fn insert_before_this(&self, child: &mut dyn Mountable<MockDom>) -> bool {
-        let parent =
-            MockDom::get_parent(self.as_ref()).and_then(Element::cast_from);
-        if let Some(parent) = parent {
-            child.mount(&parent, Some(self.as_ref()));
-            return true;
-        }
-        false
+    pub fn log_node(node: &Node) {
+        eprintln!("{}", node.to_debug_html());
     }
-}
 
-impl Mountable<MockDom> for Element {
-    fn unmount(&mut self) {
-        todo!()
+    pub fn clear_children(parent: &Element) {
+        record(|| DomOp::ClearChildren(Self::describe_node(parent)));
+        clear(parent);
     }
 
-    fn mount(&mut self, parent: &Element, marker: Option<&Node>) {
-        MockDom::insert_node(parent, self.as_ref(), marker);
+    /// Mounts the new child before the marker as its sibling.
+    ///
+    /// ## Panics
+    /// Panics if `before` does not have a parent [`Element`].
+    pub fn mount_before<M>(new_child: &mut M, before: &Node)
+    where
+        M: Mountable,
+    {
+        let parent = Element::cast_from(
+            Self::get_parent(before).expect("could not find parent element"),
+        )
+        .expect("placeholder parent should be Element");
+        new_child.mount(&parent, Some(before));
     }
 
-    fn insert_before_this(&self, child: &mut dyn Mountable<MockDom>) -> bool {
-        let parent =
-            MockDom::get_parent(self.as_ref()).and_then(Element::cast_from);
-        if let Some(parent) = parent {
-            child.mount(&parent, Some(self.as_ref()));
-            return true;
+    /// Tries to mount the new child before the marker as its sibling.
+    ///
+    /// Returns `false` if the child did not have a valid parent.
+    pub fn try_mount_before<M>(new_child: &mut M, before: &Node) -> bool
+    where
+        M: Mountable + ?Sized,
+    {
+        if let Some(parent) =
+            Self::get_parent(before).and_then(Element::cast_from)
+        {
+            new_child.mount(&parent, Some(before));
+            true
+        } else {
+            false
         }
-        false
     }
-}
 
-impl Mountable<MockDom> for Placeholder {
-    fn unmount(&mut self) {
-        todo!()
-    }
+    /// Properties are JavaScript values, which the mock DOM can't store, so this does nothing.
+    pub fn set_property(_el: &Element, _key: &str, _value: &JsValue) {}
 
-    fn mount(&mut self, parent: &Element, marker: Option<&Node>) {
-        MockDom::insert_node(parent, self.as_ref(), marker);
+    /// Marks an element as a custom element, so that attributes set on it with
+    /// [`MockDom::set_attribute`] go through [`MockDom::set_attribute_or_property`].
+    pub fn mark_custom_element(el: &Element) {
+        DOCUMENT.with(|d| {
+            d.borrow_mut()
+                .custom_element_properties
+                .entry(el.0 .0)
+                .or_default();
+        });
     }
 
-    fn insert_before_this(&self, child: &mut dyn Mountable<MockDom>) -> bool {
-        let parent =
-            MockDom::get_parent(self.as_ref()).and_then(Element::cast_from);
-        if let Some(parent) = parent {
-            child.mount(&parent, Some(self.as_ref()));
-            return true;
+    /// Sets `key` as a property if the element's tag was registered with
+    /// [`define_custom_element`] with a property of that name, and as an attribute otherwise.
+    pub fn set_attribute_or_property(el: &Element, key: &str, value: &str) {
+        let tag = el.tag_name();
+        let is_property = DOCUMENT.with(|d| {
+            d.borrow()
+                .custom_elements
+                .get(&tag)
+                .is_some_and(|properties| properties.iter().any(|p| p == key))
+        });
+        if is_property {
+            record(|| DomOp::SetProperty(key.to_string(), value.to_string()));
+            DOCUMENT.with(|d| {
+                d.borrow_mut()
+                    .custom_element_properties
+                    .entry(el.0 .0)
+                    .or_default()
+                    .insert(key.to_string(), value.to_string());
+            });
+        } else {
+            record(|| DomOp::SetAttribute(key.to_string(), value.to_string()));
+            write_attribute(el, key, Some(value));
         }
-        false
     }
-}
 
-impl<E: ElementType> CreateElement<MockDom> for E {
-    fn create_element(&self) -> crate::renderer::types::Element {
-        document().create_element(E::TAG)
+    pub fn add_event_listener(
+        el: &Element,
+        name: &str,
+        cb: Box<dyn FnMut(Event)>,
+    ) -> RemoveEventHandler<Element> {
+        record(|| DomOp::AddListener(name.to_string()));
+        let id = DOCUMENT.with(|d| {
+            let mut d = d.borrow_mut();
+            let id = d.next_listener;
+            d.next_listener += 1;
+            d.listeners.entry(el.0 .0).or_default().push((
+                name.to_string().into(),
+                id,
+                Rc::new(RefCell::new(cb)),
+            ));
+            id
+        });
+        RemoveEventHandler::new(move |el: &Element| {
+            DOCUMENT.with(|d| {
+                if let Some(listeners) =
+                    d.borrow_mut().listeners.get_mut(&el.0 .0)
+                {
+                    listeners.retain(|(_, listener, _)| *listener != id);
+                }
+            })
+        })
     }
-}
 
-impl Renderer for MockDom {
-    type Node = Node;
-    type Text = Text;
-    type Element = Element;
-    type Placeholder = Placeholder;
+    /// Returns the element an event was dispatched to with [`dispatch_event`].
+    ///
+    /// ## Panics
+    /// Panics if no event is being dispatched, or the target is not of the expected type.
+    pub fn event_target<T>(_ev: &Event) -> T
+    where
+        T: CastFrom<Element>,
+    {
+        let target = CURRENT_TARGET
+            .with(|t| t.borrow().clone())
+            .expect("event.target not found");
+        T::cast_from(target).expect("incorrect element type")
+    }
 
-    fn intern(text: &str) -> &str {
-        text
+    /// Events bubble up to every listener in the mock DOM, so delegated listeners are simply
+    /// added to the element.
+    pub fn add_event_listener_delegated(
+        el: &Element,
+        name: Cow<'static, str>,
+        _delegation_key: Cow<'static, str>,
+        cb: Box<dyn FnMut(Event)>,
+    ) -> RemoveEventHandler<Element> {
+        Self::add_event_listener(el, &name, cb)
     }
 
-    fn create_text_node(data: &str) -> Self::Text {
-        document().create_text_node(data)
+    pub fn class_list(el: &Element) -> ClassList {
+        ClassList(el.clone())
     }
 
-    fn create_placeholder() -> Self::Placeholder {
-        document().create_placeholder()
+    pub fn add_class(list: &ClassList, name: &str) {
+        Self::set_class_tokens(&list.0, &[name], &[]);
     }
 
-    fn set_text(node: &Self::Text, text: &str) {
-        Document::with_node_mut(node.0 .0, |node| {
-            if let NodeType::Text(ref mut node) = node.ty {
-                *node = text.to_string();
-            }
-        });
+    pub fn remove_class(list: &ClassList, name: &str) {
+        Self::set_class_tokens(&list.0, &[], &[name]);
     }
 
-    fn set_attribute(node: &Self::Element, name: &str, value: &str) {
-        Document::with_node_mut(node.0 .0, |node| {
-            if let NodeType::Element { ref mut attrs, .. } = node.ty {
-                attrs.insert(name.to_string(), value.to_string());
+    /// Removes the class names in `remove` from an element, and then adds those in `add`.
+    pub fn set_class_tokens(el: &Element, add: &[&str], remove: &[&str]) {
+        let prev = el.get_attribute("class");
+        let mut tokens = prev
+            .as_deref()
+            .unwrap_or_default()
+            .split_ascii_whitespace()
+            .filter(|name| !remove.contains(name))
+            .collect::<Vec<_>>();
+        for name in add {
+            if !tokens.contains(name) {
+                tokens.push(name);
             }
-        });
+        }
+        // like `classList`, this leaves the attribute alone if nothing has changed
+        let next = tokens.join(" ");
+        if prev.as_deref().unwrap_or_default() != next {
+            record(|| DomOp::SetClasses {
+                add: add.iter().map(|name| name.to_string()).collect(),
+                remove: remove.iter().map(|name| name.to_string()).collect(),
+            });
+            write_attribute(el, "class", Some(&next));
+        }
+    }
+
+    /// Counts each class name in `names` as held by one more class value on the element, and
+    /// returns those that were not held before, which should be added to its class list.
+    pub fn hold_class_tokens<'a>(
+        el: &Element,
+        names: &[&'a str],
+    ) -> Vec<&'a str> {
+        DOCUMENT.with(|d| {
+            d.borrow_mut()
+                .class_counts
+                .entry(el.0 .0)
+                .or_default()
+                .hold(names)
+        })
     }
 
-    fn remove_attribute(node: &Self::Element, name: &str) {
-        Document::with_node_mut(node.0 .0, |node| {
-            if let NodeType::Element { ref mut attrs, .. } = node.ty {
-                attrs.shift_remove(name);
+    /// Releases each class name in `names` once, and returns those that no class value on the
+    /// element holds any more, which should be removed from its class list.
+    pub fn release_class_tokens<'a>(
+        el: &Element,
+        names: &[&'a str],
+    ) -> Vec<&'a str> {
+        DOCUMENT.with(|d| {
+            d.borrow_mut()
+                .class_counts
+                .entry(el.0 .0)
+                .or_default()
+                .release(names)
+        })
+    }
+
+    /// Removes the CSS properties in `remove` from an element, and then sets those in `set`.
+    pub fn set_style_properties(
+        el: &Element,
+        set: &[(&str, &str)],
+        remove: &[&str],
+    ) {
+        let prev = el.get_attribute("style").unwrap_or_default();
+        let mut declarations = split_declarations(&prev)
+            .filter_map(|declaration| {
+                let (name, value) = declaration.split_once(':')?;
+                Some((name.trim().to_string(), value.trim().to_string()))
+            })
+            .filter(|(name, _)| !remove.contains(&name.as_str()))
+            .collect::<Vec<_>>();
+        for (name, value) in set {
+            match declarations.iter_mut().find(|(prev, _)| prev == name) {
+                Some((_, prev)) => *prev = value.to_string(),
+                None => {
+                    declarations.push((name.to_string(), value.to_string()))
+                }
             }
+        }
+        let mut style = String::new();
+        for (name, value) in declarations {
+            if !style.is_empty() {
+                style.push(' ');
+            }
+            _ = write!(style, "{name}: {value};");
+        }
+        record(|| DomOp::SetStyles {
+            set: set
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+            remove: remove.iter().map(|name| name.to_string()).collect(),
         });
+        write_attribute(el, "style", Some(&style));
     }
 
-    fn insert_node(
-        parent: &Self::Element,
-        new_child: &Self::Node,
-        anchor: Option<&Self::Node>,
+    pub fn style(el: &Element) -> CssStyleDeclaration {
+        CssStyleDeclaration(el.clone())
+    }
+
+    pub fn set_css_property(
+        style: &CssStyleDeclaration,
+        name: &str,
+        value: &str,
     ) {
-        debug_assert!(&parent.0 != new_child);
-        // remove if already mounted
-        if let Some(parent) = MockDom::get_parent(new_child) {
-            let parent = Element(parent);
-            MockDom::remove_node(&parent, new_child);
+        Self::set_style_properties(&style.0, &[(name, value)], &[]);
+    }
+
+    pub fn remove_css_property(style: &CssStyleDeclaration, name: &str) {
+        Self::set_style_properties(&style.0, &[], &[name]);
+    }
+
+    pub fn set_inner_html(el: &Element, html: &str) {
+        record(|| DomOp::SetInnerHtml(html.to_string()));
+        replace_children(el, html);
+    }
+
+    pub fn get_template<V>() -> TemplateElement
+    where
+        V: ToTemplate + 'static,
+    {
+        let id = TypeId::of::<V>();
+        if let Some(tpl) =
+            DOCUMENT.with(|d| d.borrow().templates.get(&id).cloned())
+        {
+            return tpl;
         }
-        // mount on new parent
-        Document::with_node_mut(parent.0 .0, |parent| {
-            if let NodeType::Element {
-                ref mut children, ..
-            } = parent.ty
-            {
-                match anchor {
-                    None => children.push(new_child.clone()),
-                    Some(anchor) => {
-                        let anchor_pos = children
-                            .iter()
-                            .position(|item| item.0 == anchor.0)
-                            .expect("anchor is not a child of the parent");
-                        children.insert(anchor_pos, new_child.clone());
-                    }
+
+        let mut buf = String::new();
+        V::to_template(
+            &mut buf,
+            &mut String::new(),
+            &mut String::new(),
+            &mut String::new(),
+            &mut Default::default(),
+        );
+        let tpl = TemplateElement(new_element("template", None));
+        replace_children(&tpl.0, &buf);
+        DOCUMENT.with(|d| d.borrow_mut().templates.insert(id, tpl.clone()));
+        tpl
+    }
+
+    pub fn clone_template(tpl: &TemplateElement) -> Element {
+        record(|| DomOp::CloneTemplate);
+        let fragment = Self::create_fragment();
+        for child in tpl.0.children() {
+            insert(&fragment, &deep_clone(&child), None);
+        }
+        fragment
+    }
+
+    pub fn create_element_from_html(html: &str) -> Element {
+        record(|| DomOp::CreateFromHtml(html.to_string()));
+        let fragment = Self::create_fragment();
+        for node in parse_html(html) {
+            insert(&fragment, &node, None);
+        }
+        fragment
+    }
+}
+
+// splits a `style` attribute into its declarations, at each `;` that is not inside quotes or
+// parentheses, like the one in `url("data:image/png;base64,...")`
+fn split_declarations(css: &str) -> impl Iterator<Item = &str> {
+    let mut rest = css;
+    std::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+        let mut depth = 0usize;
+        let mut quote = None;
+        let mut escaped = false;
+        let mut end = rest.len();
+        for (index, c) in rest.char_indices() {
+            match (quote, c) {
+                _ if escaped => escaped = false,
+                (_, '\\') => escaped = true,
+                (Some(q), c) if c == q => quote = None,
+                (Some(_), _) => {}
+                (None, '"' | '\'') => quote = Some(c),
+                (None, '(') => depth += 1,
+                (None, ')') => depth = depth.saturating_sub(1),
+                (None, ';') if depth == 0 => {
+                    end = index;
+                    break;
                 }
-            } else {
-                panic!("parent is not an element");
+                _ => {}
             }
-        });
-        // set parent on child node
-        Document::with_node_mut(new_child.0, |node| {
-            node.parent = Some(parent.0 .0)
-        });
+        }
+        let declaration = &rest[..end];
+        rest = rest.get(end + 1..).unwrap_or_default();
+        Some(declaration)
+    })
+}
+
+fn deep_clone(node: &Node) -> Node {
+    let mut data = node.data();
+    let children = data.ty.children_mut().map(std::mem::take);
+    let clone = Element(create_node(data.ty));
+    for child in children.into_iter().flatten() {
+        insert(&clone, &deep_clone(&child), None);
     }
+    clone.0
+}
 
-    fn remove_node(
-        parent: &Self::Element,
-        child: &Self::Node,
-    ) -> Option<Self::Node> {
-        let child = Document::with_node_mut(parent.0 .0, |parent| {
-            if let NodeType::Element {
-                ref mut children, ..
-            } = parent.ty
+// parses a fragment of HTML into its top-level nodes
+//
+// this only needs to handle the well-formed HTML that views render, so it does not try to recover
+// from errors the way a browser's parser does
+fn parse_html(html: &str) -> Vec<Node> {
+    let mut top = Vec::new();
+    let mut stack: Vec<Element> = Vec::new();
+    let mut rest = html;
+
+    let mut push = |node: Node, stack: &[Element]| match stack.last() {
+        Some(parent) => insert(parent, &node, None),
+        None => top.push(node),
+    };
+
+    while !rest.is_empty() {
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            let end = comment.find("-->").unwrap_or(comment.len());
+            push(
+                create_node(NodeType::Comment(comment[..end].to_string())),
+                &stack,
+            );
+            rest = comment.get(end + 3..).unwrap_or_default();
+        } else if let Some(after) = rest.strip_prefix("<!") {
+            // `<!>` and declarations like `<!DOCTYPE html>` are both bogus comments
+            let end = after.find('>').unwrap_or(after.len());
+            push(
+                create_node(NodeType::Comment(after[..end].to_string())),
+                &stack,
+            );
+            rest = after.get(end + 1..).unwrap_or_default();
+        } else if let Some(closing) = rest.strip_prefix("</") {
+            let end = closing.find('>').unwrap_or(closing.len());
+            let tag = closing[..end].trim().to_ascii_lowercase();
+            if let Some(index) =
+                stack.iter().rposition(|el| el.tag_name() == tag)
             {
-                let current_pos = children
-                    .iter()
-                    .position(|item| item.0 == child.0)
-                    .expect("anchor is not a child of the parent");
-                Some(children.remove(current_pos))
-            } else {
-                None
+                stack.truncate(index);
             }
-        })
-        .flatten()?;
-        Document::with_node_mut(child.0, |node| {
-            node.parent = None;
-        });
-        Some(child)
+            rest = closing.get(end + 1..).unwrap_or_default();
+        } else if rest.starts_with('<')
+            && rest[1..].starts_with(|c: char| c.is_ascii_alphabetic())
+        {
+            let (el, self_closing, after) = parse_tag(&rest[1..]);
+            push(el.0.clone(), &stack);
+            rest = after;
+            let tag = el.tag_name();
+            if matches!(tag.as_str(), "script" | "style" | "textarea") {
+                // raw text runs until the closing tag
+                let close = format!("</{tag}");
+                let end = rest.find(&close).unwrap_or(rest.len());
+                if end > 0 {
+                    insert(
+                        &el,
+                        &create_node(NodeType::Text(rest[..end].to_string())),
+                        None,
+                    );
+                }
+                rest = &rest[end..];
+                rest = rest.find('>').map_or("", |end| &rest[end + 1..]);
+            } else if !self_closing && !VOID_ELEMENTS.contains(&tag.as_str()) {
+                stack.push(el);
+            }
+        } else {
+            let end = rest[1..].find('<').map_or(rest.len(), |end| end + 1);
+            let text = html_escape::decode_html_entities(&rest[..end]);
+            push(create_node(NodeType::Text(text.into_owned())), &stack);
+            rest = &rest[end..];
+        }
     }
+    top
+}
 
-    fn remove(node: &Self::Node) {
-        let parent = Element(Node(
-            Self::get_parent(node)
-                .expect("tried to remove a parentless node")
-                .0,
-        ));
-        Self::remove_node(&parent, node);
+// parses a start tag, after the `<`, returning the element, whether it was self-closing, and the
+// rest of the input
+fn parse_tag(input: &str) -> (Element, bool, &str) {
+    let name_end = input
+        .find(|c: char| c.is_ascii_whitespace() || c == '>' || c == '/')
+        .unwrap_or(input.len());
+    let el = new_element(&input[..name_end].to_ascii_lowercase(), None);
+    let mut rest = &input[name_end..];
+    loop {
+        rest = rest.trim_start();
+        if let Some(after) = rest.strip_prefix("/>") {
+            return (el, true, after);
+        } else if let Some(after) = rest.strip_prefix('>') {
+            return (el, false, after);
+        } else if rest.is_empty() {
+            return (el, false, rest);
+        } else if let Some(after) = rest.strip_prefix('/') {
+            rest = after;
+            continue;
+        }
+
+        let name_end = rest
+            .find(|c: char| {
+                c.is_ascii_whitespace() || c == '=' || c == '>' || c == '/'
+            })
+            .unwrap_or(rest.len());
+        let name = rest[..name_end].to_string();
+        rest = rest[name_end..].trim_start();
+        let value = if let Some(after) = rest.strip_prefix('=') {
+            let after = after.trim_start();
+            let (value, after) = match after.chars().next() {
+                Some(quote @ ('"' | '\'')) => {
+                    let after = &after[1..];
+                    let end = after.find(quote).unwrap_or(after.len());
+                    (&after[..end], after.get(end + 1..).unwrap_or_default())
+                }
+                _ => {
+                    let end = after
+                        .find(|c: char| c.is_ascii_whitespace() || c == '>')
+                        .unwrap_or(after.len());
+                    (&after[..end], &after[end..])
+                }
+            };
+            rest = after;
+            html_escape::decode_html_entities(value).into_owned()
+        } else {
+            String::new()
+        };
+        write_attribute(&el, &name, Some(&value));
     }
+}
 
-    fn get_parent(node: &Self::Node) -> Option<Self::Node> {
-        Document::with_node(node.0, |node| node.parent)
-            .flatten()
-            .map(Node)
+impl Mountable for Node {
+    fn unmount(&mut self) {
+        MockDom::remove(self);
     }
 
-    fn first_child(node: &Self::Node) -> Option<Self::Node> {
-        Document::with_node(node.0, |node| match &node.ty {
-            NodeType::Text(_) => None,
-            NodeType::Element { children, .. } => children.first().cloned(),
-            NodeType::Placeholder => None,
-        })
-        .flatten()
-    }
-
-    fn next_sibling(node: &Self::Node) -> Option<Self::Node> {
-        let node_id = node.0;
-        Document::with_node(node_id, |node| {
-            node.parent.and_then(|parent| {
-                Document::with_node(parent, |parent| match &parent.ty {
-                    NodeType::Element { children, .. } => {
-                        let this = children
-                            .iter()
-                            .position(|check| check == &Node(node_id))?;
-                        children.get(this + 1).cloned()
-                    }
-                    _ => panic!(
-                        "Called next_sibling with parent as a node that's not \
-                         an Element."
-                    ),
-                })
-            })
-        })
-        .flatten()
-        .flatten()
+    fn mount(&mut self, parent: &Element, marker: Option<&Node>) {
+        MockDom::insert_node(parent, self, marker);
     }
 
-    fn log_node(node: &Self::Node) {
-        eprintln!("{node:?}");
+    fn insert_before_this(&self, child: &mut dyn Mountable) -> bool {
+        MockDom::try_mount_before(child, self)
     }
+}
 
-    fn clear_children(parent: &Self::Element) {
-        let prev_children =
-            Document::with_node_mut(parent.0 .0, |node| match node.ty {
-                NodeType::Element {
-                    ref mut children, ..
-                } => std::mem::take(children),
-                _ => panic!("Called clear_children on a non-Element node."),
-            })
-            .unwrap_or_default();
-        for child in prev_children {
-            Document::with_node_mut(child.0, |node| {
-                node.parent = None;
-            });
-        }
+macro_rules! mountable {
+    ($($ty:ident),*) => {
+        $(
+            impl Mountable for $ty {
+                fn unmount(&mut self) {
+                    MockDom::remove(self);
+                }
+
+                fn mount(&mut self, parent: &Element, marker: Option<&Node>) {
+                    MockDom::insert_node(parent, self, marker);
+                }
+
+                fn insert_before_this(&self, child: &mut dyn Mountable) -> bool {
+                    MockDom::try_mount_before(child, self)
+                }
+            }
+        )*
+    };
+}
+
+mountable!(Element, Text, Placeholder);
+
+impl CastFrom<Node> for Node {
+    fn cast_from(source: Node) -> Option<Self> {
+        Some(source)
     }
 }
 
 impl CastFrom<Node> for Text {
     fn cast_from(source: Node) -> Option<Self> {
-        Document::with_node(source.0, |node| {
-            matches!(node.ty, NodeType::Text(_))
-        })
-        .and_then(|matches| matches.then_some(Text(Node(source.0))))
+        matches!(source.data().ty, NodeType::Text(_)).then_some(Text(source))
     }
 }
 
 impl CastFrom<Node> for Element {
     fn cast_from(source: Node) -> Option<Self> {
-        Document::with_node(source.0, |node| {
-            matches!(node.ty, NodeType::Element { .. })
-        })
-        .and_then(|matches| matches.then_some(Element(Node(source.0))))
+        matches!(source.data().ty, NodeType::Element { .. })
+            .then_some(Element(source))
     }
 }
 
 impl CastFrom<Node> for Placeholder {
     fn cast_from(source: Node) -> Option<Self> {
-        Document::with_node(source.0, |node| {
-            matches!(node.ty, NodeType::Placeholder)
-        })
-        .and_then(|matches| matches.then_some(Placeholder(Node(source.0))))
+        matches!(source.data().ty, NodeType::Comment(_))
+            .then_some(Placeholder(source))
+    }
+}
+
+impl CastFrom<Element> for Element {
+    fn cast_from(source: Element) -> Option<Self> {
+        Some(source)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::MockDom;
-    use crate::{
-        html::element,
-        renderer::{mock_dom::node_eq, Renderer},
-    };
+    use super::{node_eq, MockDom};
 
     #[test]
     fn html_debugging_works() {
-        let main = MockDom::create_element(element::Main);
-        let p = MockDom::create_element(element::P);
+        let main = MockDom::create_element("main", None);
+        let p = MockDom::create_element("p", None);
         MockDom::set_attribute(&p, "id", "foo");
         let text = MockDom::create_text_node("Hello, world!");
         MockDom::insert_node(&main, p.as_ref(), None);
@@ -647,8 +1286,8 @@ mod tests {
 
     #[test]
     fn remove_attribute_works() {
-        let main = MockDom::create_element(element::Main);
-        let p = MockDom::create_element(element::P);
+        let main = MockDom::create_element("main", None);
+        let p = MockDom::create_element("p", None);
         MockDom::set_attribute(&p, "id", "foo");
         let text = MockDom::create_text_node("Hello, world!");
         MockDom::insert_node(&main, p.as_ref(), None);
@@ -659,8 +1298,8 @@ mod tests {
 
     #[test]
     fn remove_node_works() {
-        let main = MockDom::create_element(element::Main);
-        let p = MockDom::create_element(element::P);
+        let main = MockDom::create_element("main", None);
+        let p = MockDom::create_element("p", None);
         MockDom::set_attribute(&p, "id", "foo");
         let text = MockDom::create_text_node("Hello, world!");
         MockDom::insert_node(&main, p.as_ref(), None);
@@ -671,9 +1310,9 @@ mod tests {
 
     #[test]
     fn insert_before_works() {
-        let main = MockDom::create_element(element::Main);
-        let p = MockDom::create_element(element::P);
-        let span = MockDom::create_element(element::Span);
+        let main = MockDom::create_element("main", None);
+        let p = MockDom::create_element("p", None);
+        let span = MockDom::create_element("span", None);
         let text = MockDom::create_text_node("Hello, world!");
         MockDom::insert_node(&main, p.as_ref(), None);
         MockDom::insert_node(&span, text.as_ref(), None);
@@ -686,8 +1325,8 @@ mod tests {
 
     #[test]
     fn insert_before_sets_parent() {
-        let main = MockDom::create_element(element::Main);
-        let p = MockDom::create_element(element::P);
+        let main = MockDom::create_element("main", None);
+        let p = MockDom::create_element("p", None);
         MockDom::insert_node(&main, p.as_ref(), None);
         let parent =
             MockDom::get_parent(p.as_ref()).expect("p should have parent set");
@@ -696,9 +1335,9 @@ mod tests {
 
     #[test]
     fn insert_before_moves_node() {
-        let main = MockDom::create_element(element::Main);
-        let p = MockDom::create_element(element::P);
-        let span = MockDom::create_element(element::Span);
+        let main = MockDom::create_element("main", None);
+        let p = MockDom::create_element("p", None);
+        let span = MockDom::create_element("span", None);
         let text = MockDom::create_text_node("Hello, world!");
         MockDom::insert_node(&main, p.as_ref(), None);
         MockDom::insert_node(&span, text.as_ref(), None);
@@ -712,9 +1351,9 @@ mod tests {
 
     #[test]
     fn first_child_gets_first_child() {
-        let main = MockDom::create_element(element::Main);
-        let p = MockDom::create_element(element::P);
-        let span = MockDom::create_element(element::Span);
+        let main = MockDom::create_element("main", None);
+        let p = MockDom::create_element("p", None);
+        let span = MockDom::create_element("span", None);
         MockDom::insert_node(&main, p.as_ref(), None);
         MockDom::insert_node(&p, span.as_ref(), None);
         assert_eq!(
@@ -730,9 +1369,9 @@ mod tests {
 
     #[test]
     fn next_sibling_gets_next_sibling() {
-        let main = MockDom::create_element(element::Main);
-        let p = MockDom::create_element(element::P);
-        let span = MockDom::create_element(element::Span);
+        let main = MockDom::create_element("main", None);
+        let p = MockDom::create_element("p", None);
+        let span = MockDom::create_element("span", None);
         let text = MockDom::create_text_node("foo");
         MockDom::insert_node(&main, p.as_ref(), None);
         MockDom::insert_node(&main, span.as_ref(), None);
@@ -746,4 +1385,39 @@ mod tests {
             Some(text.as_ref())
         );
     }
+
+    #[test]
+    fn style_values_can_contain_semicolons() {
+        let el = MockDom::create_element("p", None);
+        let image = "url(\"data:image/svg+xml;base64,PHN2Zz4=\")";
+        MockDom::set_style_properties(
+            &el,
+            &[("background-image", image), ("color", "red")],
+            &[],
+        );
+        MockDom::set_style_properties(&el, &[("color", "blue")], &[]);
+        assert_eq!(
+            el.get_attribute("style").as_deref(),
+            Some(
+                "background-image: url(\"data:image/svg+xml;base64,PHN2Zz4=\"); \
+                 color: blue;"
+            )
+        );
+        MockDom::set_style_properties(&el, &[], &["color"]);
+        assert_eq!(
+            el.get_attribute("style").as_deref(),
+            Some("background-image: url(\"data:image/svg+xml;base64,PHN2Zz4=\");")
+        );
+    }
+
+    #[test]
+    fn parses_templates() {
+        let el = MockDom::create_element_from_html(
+            "<p class=\"a\">Fish &amp; <b>chips</b><!><br></p><!--x-->",
+        );
+        assert_eq!(
+            el.to_debug_html(),
+            "<p class=\"a\">Fish &amp; <b>chips</b><!><br></p><!--x-->"
+        );
+    }
 }
@@ -3,23 +3,44 @@ use std::{borrow::Cow, fmt::Debug};
 use wasm_bindgen::JsValue;
 
 /// A DOM renderer.
+#[cfg(not(any(feature = "testing", feature = "sledgehammer")))]
 pub mod dom;
-
+/// A renderer based on a mock DOM, which is used instead of the browser DOM with the `testing`
+/// feature.
+#[cfg(feature = "testing")]
+pub mod mock_dom;
+/// Counts the operations performed by the mock DOM.
+#[cfg(feature = "testing")]
+pub mod recording;
+/// A DOM renderer that batches DOM operations, which is used instead of the default DOM
+/// renderer with the `sledgehammer` feature. The `testing` feature takes precedence over it.
+#[cfg(all(feature = "sledgehammer", not(feature = "testing")))]
+pub mod sledgehammer;
+
+#[cfg(not(any(feature = "testing", feature = "sledgehammer")))]
 pub type Rndr = dom::Dom;
+#[cfg(feature = "testing")]
+pub type Rndr = mock_dom::MockDom;
+#[cfg(all(feature = "sledgehammer", not(feature = "testing")))]
+pub type Rndr = sledgehammer::Sledgehammer;
 pub mod types {
+    #[cfg(not(any(feature = "testing", feature = "sledgehammer")))]
     pub use super::dom::{
         ClassList, CssStyleDeclaration, Element, Event, Node, Placeholder,
         TemplateElement, Text,
     };
+    #[cfg(feature = "testing")]
+    pub use super::mock_dom::{
+        ClassList, CssStyleDeclaration, Element, Event, Node, Placeholder,
+        TemplateElement, Text,
+    };
+    #[cfg(all(feature = "sledgehammer", not(feature = "testing")))]
+    pub use super::sledgehammer::{
+        ClassList, CssStyleDeclaration, Element, Event, Node, Placeholder,
+        TemplateElement, Text,
+    };
 }
 
-/* #[cfg(feature = "testing")]
-/// A renderer based on a mock DOM.
-pub mod mock_dom;
-/// A DOM renderer optimized for element creation.
-#[cfg(feature = "sledgehammer")]
-pub mod sledgehammer; */
-
 /// Implements the instructions necessary to render an interface on some platform.
 ///
 /// By default, this is implemented for the Document Object Model (DOM) in a Web
@@ -174,6 +195,38 @@ pub trait DomRenderer: Renderer {
         value: &str,
     );
 
+    /// Removes the class names in `remove` from an element, and then adds those in `add`.
+    ///
+    /// By default, this adds or removes each class name individually. Renderers for which each
+    /// call is expensive can override it to apply all the changes at once.
+    fn set_class_tokens(el: &Self::Element, add: &[&str], remove: &[&str]) {
+        let class_list = Self::class_list(el);
+        for name in remove {
+            Self::remove_class(&class_list, name);
+        }
+        for name in add {
+            Self::add_class(&class_list, name);
+        }
+    }
+
+    /// Removes the CSS properties in `remove` from an element, and then sets those in `set`.
+    ///
+    /// By default, this sets each property individually. Renderers for which each call is
+    /// expensive can override it to apply all the changes at once.
+    fn set_style_properties(
+        el: &Self::Element,
+        set: &[(&str, &str)],
+        remove: &[&str],
+    ) {
+        let style = Self::style(el);
+        for name in remove {
+            Self::set_css_property(&style, name, "");
+        }
+        for (name, value) in set {
+            Self::set_css_property(&style, name, value);
+        }
+    }
+
     /// Sets the `innerHTML` of a DOM element, without escaping any values.
     fn set_inner_html(el: &Self::Element, html: &str);
 
@@ -202,3 +255,62 @@ where
     /// Casts a node from one type to another.
     fn cast_from(source: T) -> Option<Self>;
 }
+
+/// The namespace URI for attributes whose prefix has a fixed namespace, like `xlink:href` or
+/// `xml:lang`. These need to be set with `setAttributeNS` to have any effect.
+#[cfg(any(feature = "testing", not(feature = "sledgehammer")))]
+pub(crate) fn attribute_namespace(name: &str) -> Option<&'static str> {
+    match name.split_once(':')?.0 {
+        "xlink" => Some("http://www.w3.org/1999/xlink"),
+        "xml" => Some("http://www.w3.org/XML/1998/namespace"),
+        "xmlns" => Some("http://www.w3.org/2000/xmlns/"),
+        _ => None,
+    }
+}
+
+/// The number of class values holding each class name on an element, so that a name is only
+/// removed from the class list once no value holds it.
+#[cfg(any(feature = "testing", feature = "sledgehammer"))]
+#[derive(Debug, Default)]
+pub(crate) struct ClassCounts(rustc_hash::FxHashMap<String, usize>);
+
+#[cfg(any(feature = "testing", feature = "sledgehammer"))]
+impl ClassCounts {
+    /// Holds each name once more, and returns those that were not held before.
+    pub(crate) fn hold<'a>(&mut self, names: &[&'a str]) -> Vec<&'a str> {
+        names
+            .iter()
+            .copied()
+            .filter(|name| match self.0.get_mut(*name) {
+                Some(count) => {
+                    *count += 1;
+                    false
+                }
+                None => {
+                    self.0.insert((*name).to_string(), 1);
+                    true
+                }
+            })
+            .collect()
+    }
+
+    /// Releases each name once, and returns those that are no longer held.
+    ///
+    /// A name that was never held is returned as well, so that it is removed as before.
+    pub(crate) fn release<'a>(&mut self, names: &[&'a str]) -> Vec<&'a str> {
+        names
+            .iter()
+            .copied()
+            .filter(|name| match self.0.get_mut(*name) {
+                Some(count) if *count > 1 => {
+                    *count -= 1;
+                    false
+                }
+                _ => {
+                    self.0.remove(*name);
+                    true
+                }
+            })
+            .collect()
+    }
+}
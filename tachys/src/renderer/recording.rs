@@ -0,0 +1,195 @@
+//! Counts the operations the mock DOM performs, to test how much work an update does.
+//!
+//! ```rust,ignore
+//! let recording = RecordingRenderer::start();
+//! count.set(1);
+//! Executor::flush();
+//! assert_eq!(recording.stats().text_writes, 1);
+//! assert_eq!(recording.stats().creates, 0);
+//! ```
+
+use std::cell::RefCell;
+
+/// A single operation performed by the mock DOM.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DomOp {
+    /// Created an element with this tag.
+    CreateElement(String),
+    /// Created a text node with this text.
+    CreateText(String),
+    /// Created a placeholder comment.
+    CreatePlaceholder,
+    /// Cloned the nodes of a template.
+    CloneTemplate,
+    /// Parsed HTML into new nodes.
+    CreateFromHtml(String),
+    /// Replaced the text of a text node.
+    SetText(String),
+    /// Set an attribute to a value.
+    SetAttribute(String, String),
+    /// Removed an attribute.
+    RemoveAttribute(String),
+    /// Set a custom element's property to a value, instead of an attribute.
+    SetProperty(String, String),
+    /// Added and removed class names.
+    SetClasses {
+        /// The classes that were added.
+        add: Vec<String>,
+        /// The classes that were removed.
+        remove: Vec<String>,
+    },
+    /// Set and removed inline styles.
+    SetStyles {
+        /// The properties that were set, and their values.
+        set: Vec<(String, String)>,
+        /// The properties that were removed.
+        remove: Vec<String>,
+    },
+    /// Replaced the children of an element with this HTML.
+    SetInnerHtml(String),
+    /// Inserted or moved a node, described as by
+    /// [`MockDom::describe_node`](super::mock_dom::MockDom::describe_node).
+    Insert(String),
+    /// Removed a node from its parent.
+    Remove(String),
+    /// Removed all the children of an element.
+    ClearChildren(String),
+    /// Added an event listener for this event.
+    AddListener(String),
+}
+
+/// The number of operations of each kind performed while recording.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RenderStats {
+    /// Nodes created, including by cloning templates or parsing HTML.
+    pub creates: usize,
+    /// Text nodes whose text was replaced.
+    pub text_writes: usize,
+    /// Attributes set or removed, including custom element properties set in their place.
+    pub attribute_writes: usize,
+    /// Changes to an element's classes.
+    pub class_writes: usize,
+    /// Changes to an element's inline styles.
+    pub style_writes: usize,
+    /// Nodes inserted or moved.
+    pub inserts: usize,
+    /// Nodes removed.
+    pub removals: usize,
+    /// Event listeners added.
+    pub listeners: usize,
+    /// Elements whose children were replaced with `inner_html`.
+    pub inner_html_writes: usize,
+}
+
+impl RenderStats {
+    fn count(&mut self, op: &DomOp) {
+        let counter = match op {
+            DomOp::CreateElement(_)
+            | DomOp::CreateText(_)
+            | DomOp::CreatePlaceholder
+            | DomOp::CloneTemplate
+            | DomOp::CreateFromHtml(_) => &mut self.creates,
+            DomOp::SetText(_) => &mut self.text_writes,
+            DomOp::SetAttribute(..)
+            | DomOp::RemoveAttribute(_)
+            | DomOp::SetProperty(..) => &mut self.attribute_writes,
+            DomOp::SetClasses { .. } => &mut self.class_writes,
+            DomOp::SetStyles { .. } => &mut self.style_writes,
+            DomOp::SetInnerHtml(_) => &mut self.inner_html_writes,
+            DomOp::Insert(_) => &mut self.inserts,
+            DomOp::Remove(_) | DomOp::ClearChildren(_) => &mut self.removals,
+            DomOp::AddListener(_) => &mut self.listeners,
+        };
+        *counter += 1;
+    }
+}
+
+#[derive(Default)]
+struct Recording {
+    stats: RenderStats,
+    log: Option<Vec<DomOp>>,
+}
+
+thread_local! {
+    static RECORDING: RefCell<Option<Recording>> = const { RefCell::new(None) };
+}
+
+/// Records the operations performed by the mock DOM on this thread, from when it is started until
+/// it is dropped.
+///
+/// Only one recording can be active at a time: starting another replaces it.
+#[derive(Debug)]
+#[must_use = "recording stops when the recorder is dropped"]
+pub struct RecordingRenderer(());
+
+impl RecordingRenderer {
+    /// Starts counting operations.
+    pub fn start() -> Self {
+        RECORDING.with(|r| *r.borrow_mut() = Some(Recording::default()));
+        Self(())
+    }
+
+    /// Starts counting operations, and also keeps an ordered log of every operation.
+    pub fn start_with_log() -> Self {
+        RECORDING.with(|r| {
+            *r.borrow_mut() = Some(Recording {
+                stats: RenderStats::default(),
+                log: Some(Vec::new()),
+            })
+        });
+        Self(())
+    }
+
+    /// The number of operations of each kind so far.
+    pub fn stats(&self) -> RenderStats {
+        RECORDING.with(|r| {
+            r.borrow()
+                .as_ref()
+                .map(|recording| recording.stats)
+                .unwrap_or_default()
+        })
+    }
+
+    /// Every operation so far, in order. This is empty unless the recording was started with
+    /// [`RecordingRenderer::start_with_log`].
+    pub fn log(&self) -> Vec<DomOp> {
+        RECORDING.with(|r| {
+            r.borrow()
+                .as_ref()
+                .and_then(|recording| recording.log.clone())
+                .unwrap_or_default()
+        })
+    }
+
+    /// Clears the counts and the log, so that only operations after this are recorded.
+    pub fn clear(&self) {
+        RECORDING.with(|r| {
+            if let Some(recording) = &mut *r.borrow_mut() {
+                recording.stats = RenderStats::default();
+                if let Some(log) = &mut recording.log {
+                    log.clear();
+                }
+            }
+        });
+    }
+}
+
+impl Drop for RecordingRenderer {
+    fn drop(&mut self) {
+        RECORDING.with(|r| *r.borrow_mut() = None);
+    }
+}
+
+// called by the mock DOM for each operation; the operation is only built while recording
+pub(crate) fn record(op: impl FnOnce() -> DomOp) {
+    RECORDING.with(|r| {
+        if let Some(recording) = &mut *r.borrow_mut() {
+            let op = op();
+            recording.stats.count(&op);
+            if let Some(log) = &mut recording.log {
+                log.push(op);
+            }
+        }
+    });
+}
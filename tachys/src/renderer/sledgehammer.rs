@@ -1,6 +1,16 @@
-#![allow(missing_docs)] // Allow missing docs for experimental backend
-
-use super::{CastFrom, DomRenderer, RemoveEventHandler, Renderer};
+//! A DOM renderer that batches DOM operations, rather than calling into JavaScript for each one.
+//!
+//! With the `sledgehammer` feature, this replaces [`Dom`](super::dom::Dom) as the renderer. Each
+//! operation is encoded into a byte buffer using [`sledgehammer_bindgen`], and the whole buffer is
+//! run by a small interpreter in JavaScript once per microtask, which is much cheaper than one
+//! `web_sys` call per operation when many nodes are created or updated at once.
+//!
+//! Nodes are identified by integer IDs, which the interpreter maps to the real DOM nodes. Anything
+//! that has to read from the DOM (like walking the tree during hydration, checking a node's type,
+//! or loading a `NodeRef`) flushes the queued operations first, so it always sees the current
+//! state of the DOM.
+
+use super::{CastFrom, ClassCounts, RemoveEventHandler};
 use crate::{
     dom::window,
     view::{Mountable, ToTemplate},
@@ -12,32 +22,29 @@ use std::{
     any::TypeId,
     borrow::Cow,
     cell::{Cell, RefCell},
+    hash::{Hash, Hasher},
     rc::Rc,
 };
 use wasm_bindgen::{
     prelude::{wasm_bindgen, Closure},
     JsCast, JsValue,
 };
-use web_sys::Node;
 
 #[wasm_bindgen]
 extern "C" {
     #[wasm_bindgen]
-    fn queueMicrotask(closure: &Closure<dyn Fn() -> ()>);
-
-    type Global;
+    fn queueMicrotask(closure: &Closure<dyn Fn()>);
 }
 
 #[bindgen]
 mod js {
-    //#[extends(NodeInterpreter)]
     struct Channel;
 
     const JS: &str = r#"
         function Queue() {
             var head, tail;
-            return Object.freeze({     
-                enqueue(value) { 
+            return Object.freeze({
+                enqueue(value) {
                     const link = {value, next: undefined};
                     tail = head ? tail.next = link : head = link;
                 },
@@ -48,7 +55,6 @@ mod js {
                         return value;
                     }
                 },
-                peek() { return head?.value }
             });
         }
         this.nodes = [null];
@@ -59,22 +65,34 @@ mod js {
         "this.nodes[$id$]=null;"
     }
 
-    fn store_body(id: u32) {
-        "this.nodes[$id$]=document.body;"
+    fn store_jsvalue_node(id: u32) {
+        "this.nodes[$id$]=this.jsvalues.dequeue();"
     }
 
-    fn create_text_node(id: u32, data: &str) {
+    fn create_text_node(id: u32, data: impl Writable<u8>) {
         "this.nodes[$id$]=document.createTextNode($data$);"
     }
 
     fn create_comment(id: u32) {
-        "this.nodes[$id$]=document.createComment();"
+        "this.nodes[$id$]=document.createComment('');"
     }
 
-    fn create_element(id: u32, name: &'static str<u8, name_cache>) {
+    fn create_element(id: u32, name: &str<u8, name_cache>) {
         "this.nodes[$id$]=document.createElement($name$);"
     }
 
+    fn create_element_ns(
+        id: u32,
+        name: &str<u8, name_cache>,
+        ns: &str<u8, ns_cache>,
+    ) {
+        "this.nodes[$id$]=document.createElementNS($ns$,$name$);"
+    }
+
+    fn create_fragment(id: u32) {
+        "this.nodes[$id$]=document.createDocumentFragment();"
+    }
+
     fn set_attribute(
         id: u32,
         name: &str<u8, name_cache>,
@@ -83,16 +101,34 @@ mod js {
         "this.nodes[$id$].setAttribute($name$,$val$);"
     }
 
-    fn remove_child(parent: u32, child: u32) {
-        "this.nodes[$parent$].removeChild(this.nodes[$child$]);"
+    fn set_attribute_or_property(
+        id: u32,
+        name: &str<u8, name_cache>,
+        val: impl Writable<u8>,
+    ) {
+        "{const n=this.nodes[$id$],k=$name$,v=$val$;if(customElements.get(n.\
+         localName)&&k in n)n[k]=v;else n.setAttribute(k,v);}"
     }
 
     fn remove_attribute(id: u32, name: &str<u8, name_cache>) {
         "this.nodes[$id$].removeAttribute($name$);"
     }
 
-    fn append_child(id: u32, id2: u32) {
-        "this.nodes[$id$].appendChild(nodes[$id2$]);"
+    fn set_attribute_ns(
+        id: u32,
+        ns: &str<u8, ns_cache>,
+        name: &str<u8, name_cache>,
+        val: impl Writable<u8>,
+    ) {
+        "this.nodes[$id$].setAttributeNS($ns$,$name$,$val$);"
+    }
+
+    fn remove_attribute_ns(
+        id: u32,
+        ns: &str<u8, ns_cache>,
+        name: &str<u8, name_cache>,
+    ) {
+        "this.nodes[$id$].removeAttributeNS($ns$,$name$);"
     }
 
     fn insert_before(parent: u32, child: u32, marker: u32) {
@@ -100,318 +136,599 @@ mod js {
          nodes[$marker$]);"
     }
 
-    fn set_text(id: u32, text: impl Writable<u8>) {
-        "this.nodes[$id$].textContent=$text$;"
+    fn remove_child(parent: u32, child: u32) {
+        "this.nodes[$parent$].removeChild(this.nodes[$child$]);"
     }
 
     fn remove(id: u32) {
         "this.nodes[$id$].remove();"
     }
 
-    fn replace(id: u32, id2: u32) {
-        "this.nodes[$id$].replaceWith(this.nodes[$id2$]);"
+    fn set_text(id: u32, text: impl Writable<u8>) {
+        "this.nodes[$id$].nodeValue=$text$;"
     }
 
-    fn first_child(parent: u32, child: u32) {
-        "this.nodes[$child$]=this.nodes[$parent$].firstChild;"
+    fn clear_children(id: u32) {
+        "this.nodes[$id$].textContent='';"
     }
 
-    fn next_sibling(anchor: u32, sibling: u32) {
-        "this.nodes[$sibling$]=this.nodes[$anchor$].nextSibling;"
+    fn split_text(id: u32, offset: u32, new_id: u32) {
+        "this.nodes[$new_id$]=this.nodes[$id$].splitText($offset$);"
     }
 
-    fn class_list(el: u32, class_list: u32) {
-        "this.nodes[$class_list$]=this.nodes[$el$].classList;"
+    fn add_class(id: u32, name: &str<u8, class_cache>) {
+        "this.nodes[$id$].classList.add($name$);"
     }
 
-    fn add_class(class_list: u32, name: &str<u8, class_cache>) {
-        "this.nodes[$class_list$].add($name$);"
+    fn remove_class(id: u32, name: &str<u8, class_cache>) {
+        "this.nodes[$id$].classList.remove($name$);"
     }
 
-    fn remove_class(class_list: u32, name: &str<u8, class_cache>) {
-        "this.nodes[$class_list$].remove($name$);"
+    fn set_css_property(
+        id: u32,
+        name: &str<u8, style_cache>,
+        val: impl Writable<u8>,
+    ) {
+        "this.nodes[$id$].style.setProperty($name$,$val$);"
+    }
+
+    fn remove_css_property(id: u32, name: &str<u8, style_cache>) {
+        "this.nodes[$id$].style.removeProperty($name$);"
+    }
+
+    fn set_inner_html(id: u32, html: impl Writable<u8>) {
+        "this.nodes[$id$].innerHTML=$html$;"
     }
 
-    fn set_inner_html(node: u32, html: &str) {
-        "this.nodes[$node$].innerHTML = $html$;"
+    fn clone_template(tpl: u32, id: u32) {
+        "this.nodes[$id$]=this.nodes[$tpl$].content.cloneNode(true);"
     }
 
-    fn clone_template(tpl_node: u32, into_node: u32) {
-        "this.nodes[$into_node$]=this.nodes[$tpl_node$].content.\
-         cloneNode(true);"
+    fn set_property(id: u32, name: &str<u8, name_cache>) {
+        "this.nodes[$id$][$name$]=this.jsvalues.dequeue();"
     }
 
-    fn set_property(node: u32, name: &str<u8, name_cache>) {
-        "{let jsv=this.jsvalues.dequeue();this.nodes[$node$][$name$]=jsv;}"
+    fn add_listener(id: u32, name: &str<u8, name_cache>) {
+        "this.nodes[$id$].addEventListener($name$,this.jsvalues.dequeue());"
     }
 
-    fn add_listener(node: u32, name: &str<u8, name_cache>) {
-        "{let jsv=this.jsvalues.dequeue();this.nodes[$node$].\
-         addEventListener($name$, jsv);}"
+    fn remove_listener(id: u32, name: &str<u8, name_cache>) {
+        "this.nodes[$id$].removeEventListener($name$,this.jsvalues.\
+         dequeue());"
+    }
+
+    fn add_delegated(id: u32, key: &str<u8, name_cache>) {
+        "{const n=this.nodes[$id$];(n[$key$]||(n[$key$]=[])).push(this.\
+         jsvalues.dequeue());}"
+    }
+
+    fn remove_delegated(id: u32, key: &str<u8, name_cache>) {
+        "{const n=this.nodes[$id$],f=this.jsvalues.dequeue(),h=n[$key$];if(h)\
+         {const i=h.indexOf(f);if(i>=0)h.splice(i,1);if(!h.length)delete \
+         n[$key$];}}"
     }
 }
 
 #[wasm_bindgen(inline_js = "
-    export function get_node(channel, id){
+    export function get_node(channel, id) {
         return channel.nodes[id];
     }
 
-    export function store_node(channel, id, node){
-        channel.nodes[id] = node;
-    }
-
     export function store_jsvalue(channel, value) {
         channel.jsvalues.enqueue(value);
     }
 ")]
 extern "C" {
-    fn get_node(channel: &JSChannel, id: u32) -> Node;
-
-    fn store_node(channel: &JSChannel, id: u32, node: Node);
+    fn get_node(channel: &JSChannel, id: u32) -> JsValue;
 
-    fn store_jsvalue(channel: &JSChannel, value: JsValue);
+    fn store_jsvalue(channel: &JSChannel, value: &JsValue);
 }
 
-#[derive(Debug)]
+/// A renderer that batches DOM operations with [`sledgehammer_bindgen`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Sledgehammer;
 
-impl Sledgehammer {
-    pub fn body() -> SNode {
-        let node = SNode::new();
-        with(|channel| channel.store_body(node.0 .0));
-        node
-    }
+pub type Event = wasm_bindgen::JsValue;
 
-    pub fn store(node: Node) -> SNode {
-        let snode = SNode::new();
-        with(|channel| store_node(channel.js_channel(), snode.0 .0, node));
-        snode
-    }
+thread_local! {
+    static CHANNEL: RefCell<Channel> = RefCell::new(Channel::default());
+    static FLUSH_PENDING: Cell<bool> = const { Cell::new(false) };
+    static FLUSH_CLOSURE: Closure<dyn Fn()> = Closure::new(flush_sync);
+    static NEXT_ID: Cell<u32> = const { Cell::new(1) };
+    static RECYCLE_IDS: RefCell<Vec<u32>> = const { RefCell::new(Vec::new()) };
+    static TEMPLATES: RefCell<LinearMap<TypeId, TemplateElement>> = Default::default();
 
-    pub fn element(tag_name: &'static str) -> SNode {
-        let node = SNode::new();
-        with(|channel| channel.create_element(node.0 .0, tag_name));
-        node
+    pub(crate) static GLOBAL_EVENTS: RefCell<FxHashSet<Cow<'static, str>>> = Default::default();
+}
+
+// queues an operation, and makes sure the queue will be flushed
+fn with(fun: impl FnOnce(&mut Channel)) {
+    CHANNEL.with_borrow_mut(fun);
+    if !FLUSH_PENDING.replace(true) {
+        FLUSH_CLOSURE.with(queueMicrotask);
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct SNode(Rc<SNodeInner>);
+// queues a JavaScript value to be used by the next operation that dequeues one
+fn with_jsvalue(value: &JsValue, fun: impl FnOnce(&mut Channel)) {
+    with(|channel| {
+        store_jsvalue(channel.js_channel(), value);
+        fun(channel);
+    });
+}
+
+/// Runs every queued DOM operation now.
+///
+/// Operations are flushed automatically at the end of the current task, and before anything is
+/// read back from the DOM, so this only needs to be called before the DOM is accessed some other
+/// way, like through `web_sys`.
+pub fn flush_sync() {
+    FLUSH_PENDING.set(false);
+    CHANNEL.with_borrow_mut(|channel| channel.flush());
+}
 
-#[derive(Debug, PartialEq, Eq, Hash)]
-struct SNodeInner(u32);
+const ELEMENT_NODE: u16 = web_sys::Node::ELEMENT_NODE;
+const TEXT_NODE: u16 = web_sys::Node::TEXT_NODE;
+const COMMENT_NODE: u16 = web_sys::Node::COMMENT_NODE;
+const DOCUMENT_FRAGMENT_NODE: u16 = web_sys::Node::DOCUMENT_FRAGMENT_NODE;
 
-impl SNode {
-    fn new() -> Self {
-        let id = if let Some(id) = RECYCLE_IDS.with_borrow_mut(Vec::pop) {
+/// A handle to a DOM node, which is identified by an integer ID.
+///
+/// Two handles are equal if they share the same ID. Reading a node back from the DOM (for
+/// example, with [`Sledgehammer::get_parent`]) creates a new ID, so a handle returned that way
+/// is not equal to one created earlier for the same node.
+#[derive(Debug, Clone)]
+pub struct Node(Rc<NodeInner>);
+
+#[derive(Debug)]
+struct NodeInner {
+    id: u32,
+    // the node type, like `Node.ELEMENT_NODE`, which is known when the node is created, so that
+    // casting between node types doesn't need to read it from the DOM
+    kind: u16,
+    custom_element: Cell<bool>,
+    // the class names held by the class values on this element, which are kept with the handle,
+    // because they are only updated through the handle the element was created with
+    class_counts: RefCell<ClassCounts>,
+}
+
+impl Node {
+    fn new(kind: u16) -> Self {
+        let id = RECYCLE_IDS.with_borrow_mut(Vec::pop).unwrap_or_else(|| {
+            let id = NEXT_ID.get();
+            NEXT_ID.set(id + 1);
             id
-        } else {
-            let new_id = NEXT_ID.get();
-            NEXT_ID.set(new_id + 1);
-            new_id
-        };
-        Self(Rc::new(SNodeInner(id)))
+        });
+        Self(Rc::new(NodeInner {
+            id,
+            kind,
+            custom_element: Cell::new(false),
+            class_counts: RefCell::default(),
+        }))
     }
 
-    pub fn to_node(&self) -> Node {
-        CHANNEL.with_borrow(|channel| get_node(channel.js_channel(), self.0 .0))
+    fn id(&self) -> u32 {
+        self.0.id
+    }
+
+    /// Flushes any queued operations, and returns the real DOM node.
+    pub fn to_node(&self) -> web_sys::Node {
+        flush_sync();
+        CHANNEL
+            .with_borrow(|channel| get_node(channel.js_channel(), self.id()))
+            .unchecked_into()
     }
 }
 
-impl Drop for SNodeInner {
+impl Drop for NodeInner {
     fn drop(&mut self) {
-        RECYCLE_IDS.with_borrow_mut(|ids| ids.push(self.0));
-        with(|channel| channel.drop_node(self.0));
+        // the ID can be reused immediately, because the operation that clears it is queued before
+        // any operation that could use it again
+        let id = self.id;
+        _ = RECYCLE_IDS.try_with(|ids| ids.borrow_mut().push(id));
+        _ = CHANNEL.try_with(|channel| {
+            if let Ok(mut channel) = channel.try_borrow_mut() {
+                channel.drop_node(id);
+            }
+        });
     }
 }
 
-impl AsRef<SNode> for SNode {
-    fn as_ref(&self) -> &SNode {
-        self
+impl PartialEq for Node {
+    fn eq(&self, other: &Self) -> bool {
+        self.id() == other.id()
     }
 }
 
-impl CastFrom<SNode> for SNode {
-    fn cast_from(source: SNode) -> Option<Self> {
-        Some(source)
+impl Eq for Node {}
+
+impl Hash for Node {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id().hash(state);
     }
 }
 
-thread_local! {
-    static CHANNEL: RefCell<Channel> = RefCell::new(Channel::default());
-    static FLUSH_PENDING: Cell<bool> = const { Cell::new(false) };
-    static FLUSH_CLOSURE: Closure<dyn Fn()> = Closure::new(|| {
-        FLUSH_PENDING.set(false);
-        CHANNEL.with_borrow_mut(|channel| {
-            channel.flush();
-        });
-    });
-    static NEXT_ID: Cell<u32> = const { Cell::new(1) };
-    static RECYCLE_IDS: RefCell<Vec<u32>> = const { RefCell::new(Vec::new()) };
+/// An element.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Element(Node);
 
-    pub(crate) static GLOBAL_EVENTS: RefCell<FxHashSet<Cow<'static, str>>> = Default::default();
+/// A text node.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Text(Node);
+
+/// A comment node.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Placeholder(Node);
+
+/// The class list of an element.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ClassList(Element);
+
+/// The inline styles of an element.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CssStyleDeclaration(Element);
+
+/// A `<template>` element.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TemplateElement(Element);
+
+macro_rules! node_type {
+    ($($ty:ident),*) => {
+        $(
+            impl AsRef<Node> for $ty {
+                fn as_ref(&self) -> &Node {
+                    &self.0
+                }
+            }
+
+            impl From<$ty> for Node {
+                fn from(value: $ty) -> Self {
+                    value.0
+                }
+            }
+
+            impl std::ops::Deref for $ty {
+                type Target = Node;
+
+                fn deref(&self) -> &Node {
+                    &self.0
+                }
+            }
+        )*
+    };
 }
 
-fn with(fun: impl FnOnce(&mut Channel)) {
-    CHANNEL.with_borrow_mut(fun);
-    flush();
+node_type!(Element, Text, Placeholder);
+
+impl AsRef<Node> for Node {
+    fn as_ref(&self) -> &Node {
+        self
+    }
 }
 
-#[allow(unused)] // might be handy at some point!
-fn flush_sync() {
-    FLUSH_PENDING.set(false);
-    CHANNEL.with_borrow_mut(|channel| channel.flush());
+impl Element {
+    /// The element's tag name, read from the DOM.
+    pub fn tag_name(&self) -> String {
+        self.to_node()
+            .unchecked_into::<web_sys::Element>()
+            .tag_name()
+    }
+
+    /// Returns the value of an attribute, read from the DOM.
+    pub fn get_attribute(&self, name: &str) -> Option<String> {
+        self.to_node()
+            .unchecked_into::<web_sys::Element>()
+            .get_attribute(name)
+    }
 }
 
-fn flush() {
-    let was_pending = FLUSH_PENDING.replace(true);
-    if !was_pending {
-        FLUSH_CLOSURE.with(queueMicrotask);
+/// The namespace URI for attributes whose prefix has a fixed namespace, like `xlink:href` or
+/// `xml:lang`. These need to be set with `setAttributeNS` to have any effect.
+fn attribute_namespace(name: &str) -> Option<&'static str> {
+    match name.split_once(':')?.0 {
+        "xlink" => Some("http://www.w3.org/1999/xlink"),
+        "xml" => Some("http://www.w3.org/XML/1998/namespace"),
+        "xmlns" => Some("http://www.w3.org/2000/xmlns/"),
+        _ => None,
     }
 }
 
-impl Renderer for Sledgehammer {
-    type Node = SNode;
-    type Text = SNode;
-    type Element = SNode;
-    type Placeholder = SNode;
+impl Sledgehammer {
+    /// Creates a handle for a node that already exists in the DOM, like the `<body>`.
+    pub fn store(node: &web_sys::Node) -> Node {
+        let handle = Node::new(node.node_type());
+        with_jsvalue(node, |channel| channel.store_jsvalue_node(handle.id()));
+        handle
+    }
+
+    /// Creates a handle for an element that already exists in the DOM, like the `<body>`, to
+    /// mount a view into.
+    pub fn store_element(el: &web_sys::Element) -> Element {
+        Element(Self::store(el))
+    }
 
-    fn intern(text: &str) -> &str {
+    // reads a node back from the DOM, if there is one
+    fn store_opt(node: Option<web_sys::Node>) -> Option<Node> {
+        node.map(|node| Self::store(&node))
+    }
+
+    pub fn intern(text: &str) -> &str {
         text
     }
 
-    fn create_text_node(text: &str) -> Self::Text {
-        let node = SNode::new();
-        with(|channel| channel.create_text_node(node.0 .0, text));
-        node
+    pub fn create_element(tag: &str, namespace: Option<&str>) -> Element {
+        let node = Node::new(ELEMENT_NODE);
+        match namespace {
+            Some(namespace) => with(|channel| {
+                channel.create_element_ns(node.id(), tag, namespace)
+            }),
+            None => with(|channel| channel.create_element(node.id(), tag)),
+        }
+        Element(node)
     }
 
-    fn create_placeholder() -> Self::Placeholder {
-        let node = SNode::new();
-        with(|channel| channel.create_comment(node.0 .0));
-        node
+    pub fn create_text_node(text: &str) -> Text {
+        let node = Node::new(TEXT_NODE);
+        with(|channel| channel.create_text_node(node.id(), text));
+        Text(node)
     }
 
-    fn set_text(node: &Self::Text, text: &str) {
-        with(|channel| channel.set_text(node.0 .0, text));
+    pub fn create_placeholder() -> Placeholder {
+        let node = Node::new(COMMENT_NODE);
+        with(|channel| channel.create_comment(node.id()));
+        Placeholder(node)
     }
 
-    fn set_attribute(node: &Self::Element, name: &str, value: &str) {
-        with(|channel| channel.set_attribute(node.0 .0, name, value));
+    pub fn set_text(node: &Text, text: &str) {
+        with(|channel| channel.set_text(node.id(), text));
     }
 
-    fn remove_attribute(node: &Self::Element, name: &str) {
-        with(|channel| channel.remove_attribute(node.0 .0, name));
+    pub fn set_attribute(node: &Element, name: &str, value: &str) {
+        if node.0 .0.custom_element.get() {
+            return Self::set_attribute_or_property(node, name, value);
+        }
+        match attribute_namespace(name) {
+            Some(namespace) => {
+                Self::set_attribute_ns(node, namespace, name, value)
+            }
+            None => {
+                with(|channel| channel.set_attribute(node.id(), name, value))
+            }
+        }
     }
 
-    fn insert_node(
-        parent: &Self::Element,
-        new_child: &Self::Node,
-        anchor: Option<&Self::Node>,
+    pub fn remove_attribute(node: &Element, name: &str) {
+        match attribute_namespace(name) {
+            Some(namespace) => Self::remove_attribute_ns(node, namespace, name),
+            None => with(|channel| channel.remove_attribute(node.id(), name)),
+        }
+    }
+
+    /// Sets an attribute in the given namespace. `name` is the qualified name, including any
+    /// prefix (like `xlink:href`).
+    pub fn set_attribute_ns(
+        node: &Element,
+        namespace: &str,
+        name: &str,
+        value: &str,
+    ) {
+        with(|channel| {
+            channel.set_attribute_ns(node.id(), namespace, name, value)
+        });
+    }
+
+    /// Removes an attribute from the given namespace. `name` is the qualified name, including
+    /// any prefix; only its local part is used to find the attribute.
+    pub fn remove_attribute_ns(node: &Element, namespace: &str, name: &str) {
+        let local_name = name.split_once(':').map_or(name, |(_, local)| local);
+        with(|channel| {
+            channel.remove_attribute_ns(node.id(), namespace, local_name)
+        });
+    }
+
+    pub fn insert_node(
+        parent: &Element,
+        new_child: &Node,
+        anchor: Option<&Node>,
     ) {
         with(|channel| {
             channel.insert_before(
-                parent.0 .0,
-                new_child.0 .0,
-                anchor.map(|n| n.0 .0).unwrap_or(0),
+                parent.id(),
+                new_child.id(),
+                // ID 0 is always `null`, which inserts at the end
+                anchor.map_or(0, Node::id),
             )
         });
     }
 
-    fn remove_node(
-        parent: &Self::Element,
-        child: &Self::Node,
-    ) -> Option<Self::Node> {
-        with(|channel| channel.remove_child(parent.0 .0, child.0 .0));
+    /// Creates a detached `DocumentFragment` that children can be mounted into, before they are
+    /// all inserted into the document at once with [`Sledgehammer::append_fragment`].
+    pub fn create_fragment() -> Element {
+        let node = Node::new(DOCUMENT_FRAGMENT_NODE);
+        with(|channel| channel.create_fragment(node.id()));
+        Element(node)
+    }
+
+    /// Moves all the children of a fragment created by [`Sledgehammer::create_fragment`] into
+    /// `parent`, before `anchor`.
+    pub fn append_fragment(
+        parent: &Element,
+        fragment: &Element,
+        anchor: Option<&Node>,
+    ) {
+        Self::insert_node(parent, fragment, anchor);
+    }
+
+    pub fn remove_node(parent: &Element, child: &Node) -> Option<Node> {
+        with(|channel| channel.remove_child(parent.id(), child.id()));
         Some(child.clone())
     }
 
-    fn remove(node: &Self::Node) {
-        with(|channel| channel.remove(node.0 .0));
+    pub fn remove(node: &Node) {
+        with(|channel| channel.remove(node.id()));
     }
 
-    fn get_parent(_node: &Self::Node) -> Option<Self::Node> {
-        todo!() // node.parent_node()
+    pub fn get_parent(node: &Node) -> Option<Node> {
+        Self::store_opt(node.to_node().parent_node())
     }
 
-    fn first_child(node: &Self::Node) -> Option<Self::Node> {
-        let child = SNode::new();
-        with(|channel| channel.first_child(node.0 .0, child.0 .0));
-        Some(child)
+    pub fn first_child(node: &Node) -> Option<Node> {
+        let child = node.to_node().first_child()?;
+        // if it's a comment node that starts with hot-reload, it's a marker that should be
+        // ignored
+        if cfg!(debug_assertions) && is_hot_reload_marker(&child) {
+            return Self::next_real_sibling(&child);
+        }
+        Some(Self::store(&child))
     }
 
-    fn next_sibling(node: &Self::Node) -> Option<Self::Node> {
-        let sibling = SNode::new();
-        with(|channel| channel.next_sibling(node.0 .0, sibling.0 .0));
-        Some(sibling)
+    pub fn next_sibling(node: &Node) -> Option<Node> {
+        Self::next_real_sibling(&node.to_node())
     }
 
-    fn log_node(_node: &Self::Node) {
-        todo!()
+    fn next_real_sibling(node: &web_sys::Node) -> Option<Node> {
+        let mut next = node.next_sibling();
+        if cfg!(debug_assertions) {
+            while next.as_ref().is_some_and(is_hot_reload_marker) {
+                next = next.and_then(|node| node.next_sibling());
+            }
+        }
+        Self::store_opt(next)
     }
 
-    fn clear_children(parent: &Self::Element) {
-        with(|channel| channel.set_text(parent.0 .0, ""));
+    pub fn text_content(node: &Node) -> Option<String> {
+        node.to_node().text_content()
     }
-}
 
-#[derive(Debug, Clone)]
-pub struct ClassList(SNode);
+    pub fn split_text(node: &Text, offset: usize) -> Option<Text> {
+        let next = Node::new(TEXT_NODE);
+        with(|channel| channel.split_text(node.id(), offset as u32, next.id()));
+        Some(Text(next))
+    }
 
-#[derive(Debug, Clone)]
-#[allow(dead_code)] // this will be used, it's just all unimplemented
-pub struct CssStyle(SNode);
+    pub fn comment_text(node: &Node) -> Option<String> {
+        (node.0.kind == COMMENT_NODE)
+            .then(|| node.to_node().text_content().unwrap_or_default())
+    }
+
+    pub fn describe_node(node: &Node) -> String {
+        let real = node.to_node();
+        match node.0.kind {
+            ELEMENT_NODE => real
+                .unchecked_ref::<web_sys::Element>()
+                .tag_name()
+                .to_lowercase(),
+            TEXT_NODE => {
+                format!("#text {:?}", real.text_content().unwrap_or_default())
+            }
+            COMMENT_NODE => {
+                format!("<!--{}-->", real.text_content().unwrap_or_default())
+            }
+            _ => real.node_name().to_lowercase(),
+        }
+    }
+
+    pub fn log_node(node: &Node) {
+        web_sys::console::log_1(&node.to_node());
+    }
+
+    pub fn clear_children(parent: &Element) {
+        with(|channel| channel.clear_children(parent.id()));
+    }
+
+    /// Mounts the new child before the marker as its sibling.
+    ///
+    /// ## Panics
+    /// Panics if `before` does not have a parent [`Element`].
+    pub fn mount_before<M>(new_child: &mut M, before: &Node)
+    where
+        M: Mountable,
+    {
+        let parent = Element::cast_from(
+            Self::get_parent(before).expect("could not find parent element"),
+        )
+        .expect("placeholder parent should be Element");
+        new_child.mount(&parent, Some(before));
+    }
+
+    /// Tries to mount the new child before the marker as its sibling.
+    ///
+    /// Returns `false` if the child did not have a valid parent.
+    pub fn try_mount_before<M>(new_child: &mut M, before: &Node) -> bool
+    where
+        M: Mountable + ?Sized,
+    {
+        if let Some(parent) =
+            Self::get_parent(before).and_then(Element::cast_from)
+        {
+            new_child.mount(&parent, Some(before));
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Marks an element as a custom element, so that attributes set on it with
+    /// [`Sledgehammer::set_attribute`] go through [`Sledgehammer::set_attribute_or_property`].
+    pub fn mark_custom_element(el: &Element) {
+        el.0 .0.custom_element.set(true);
+    }
 
-impl DomRenderer for Sledgehammer {
-    type Event = JsValue;
-    type ClassList = ClassList;
-    type CssStyleDeclaration = CssStyle;
-    type TemplateElement = SNode;
+    /// Sets `key` as a property if the element is a registered custom element with a property of
+    /// that name, and as an attribute otherwise. This is decided when the queued operation runs.
+    pub fn set_attribute_or_property(el: &Element, key: &str, value: &str) {
+        with(|channel| channel.set_attribute_or_property(el.id(), key, value));
+    }
 
-    fn set_property(_el: &Self::Element, _key: &str, _value: &JsValue) {
-        todo!()
+    pub fn set_property(el: &Element, key: &str, value: &JsValue) {
+        with_jsvalue(value, |channel| channel.set_property(el.id(), key));
     }
 
-    fn add_event_listener(
-        el: &Self::Element,
+    pub fn add_event_listener(
+        el: &Element,
         name: &str,
-        cb: Box<dyn FnMut(Self::Event)>,
-    ) -> RemoveEventHandler<Self::Element> {
-        let cb = wasm_bindgen::closure::Closure::wrap(cb).into_js_value();
-        CHANNEL.with_borrow_mut(|channel| {
-            channel.add_listener(el.0 .0, name);
-            let channel = channel.js_channel();
-            store_jsvalue(channel, cb);
-        });
+        cb: Box<dyn FnMut(Event)>,
+    ) -> RemoveEventHandler<Element> {
+        let cb = Closure::wrap(cb).into_js_value();
+        with_jsvalue(&cb, |channel| channel.add_listener(el.id(), name));
 
         // return the remover
-        RemoveEventHandler(Box::new(move |_el| todo!()))
+        RemoveEventHandler::new({
+            let name = name.to_owned();
+            // safe to construct this here, because it will only run in the browser
+            // so it will always be accessed or dropped from the main thread
+            let cb = send_wrapper::SendWrapper::new(cb);
+            move |el: &Element| {
+                with_jsvalue(&cb, |channel| {
+                    channel.remove_listener(el.id(), &name)
+                });
+            }
+        })
     }
 
-    fn event_target<T>(_ev: &Self::Event) -> T
+    pub fn event_target<T>(ev: &Event) -> T
     where
-        T: CastFrom<Self::Element>,
+        T: CastFrom<Element>,
     {
-        todo!()
-        /*let el = ev
-            .unchecked_ref::<Event>()
+        let el = ev
+            .unchecked_ref::<web_sys::Event>()
             .target()
             .expect("event.target not found")
-            .unchecked_into::<Element>();
-        T::cast_from(el).expect("incorrect element type")*/
+            .unchecked_into::<web_sys::Element>();
+        T::cast_from(Self::store_element(&el)).expect("incorrect element type")
     }
 
-    fn add_event_listener_delegated(
-        el: &Self::Element,
+    pub fn add_event_listener_delegated(
+        el: &Element,
         name: Cow<'static, str>,
         delegation_key: Cow<'static, str>,
-        cb: Box<dyn FnMut(Self::Event)>,
-    ) -> RemoveEventHandler<Self::Element> {
+        cb: Box<dyn FnMut(Event)>,
+    ) -> RemoveEventHandler<Element> {
         let cb = Closure::wrap(cb).into_js_value();
-        CHANNEL.with_borrow_mut(|channel| {
-            channel.set_property(el.0 .0, &delegation_key);
-            let channel = channel.js_channel();
-            store_jsvalue(channel, cb);
+        // each element holds a list of handlers for the event, so that adding a second handler
+        // for the same event (for example, by spreading attributes) doesn't replace the first
+        with_jsvalue(&cb, |channel| {
+            channel.add_delegated(el.id(), &delegation_key)
         });
 
         GLOBAL_EVENTS.with(|global_events| {
@@ -428,9 +745,6 @@ impl DomRenderer for Sledgehammer {
                         node
                     };
 
-                    // TODO reverse Shadow DOM retargetting
-                    // TODO simulate currentTarget
-
                     while !node.is_null() {
                         let node_is_disabled = js_sys::Reflect::get(
                             &node,
@@ -439,12 +753,18 @@ impl DomRenderer for Sledgehammer {
                         .unwrap()
                         .is_truthy();
                         if !node_is_disabled {
-                            let maybe_handler =
+                            let maybe_handlers =
                                 js_sys::Reflect::get(&node, &key).unwrap();
-                            if !maybe_handler.is_undefined() {
-                                let f = maybe_handler
-                                    .unchecked_ref::<js_sys::Function>();
-                                let _ = f.call1(&node, &ev);
+                            if let Some(handlers) =
+                                maybe_handlers.dyn_ref::<js_sys::Array>()
+                            {
+                                for handler in
+                                    handlers.slice(0, handlers.length())
+                                {
+                                    let f = handler
+                                        .unchecked_ref::<js_sys::Function>();
+                                    let _ = f.call1(&node, &ev);
+                                }
 
                                 if ev.cancel_bubble() {
                                     return;
@@ -483,53 +803,107 @@ impl DomRenderer for Sledgehammer {
         });
 
         // return the remover
-        RemoveEventHandler(Box::new(move |_el| todo!()))
+        RemoveEventHandler::new({
+            let key = delegation_key.into_owned();
+            // safe to construct this here, because it will only run in the browser
+            // so it will always be accessed or dropped from the main thread
+            let cb = send_wrapper::SendWrapper::new(cb);
+            move |el: &Element| {
+                with_jsvalue(&cb, |channel| {
+                    channel.remove_delegated(el.id(), &key)
+                });
+            }
+        })
     }
 
-    fn class_list(el: &Self::Element) -> Self::ClassList {
-        let class_list = SNode::new();
-        with(|channel| channel.class_list(el.0 .0, class_list.0 .0));
-        ClassList(class_list)
+    pub fn class_list(el: &Element) -> ClassList {
+        ClassList(el.clone())
     }
 
-    fn add_class(list: &Self::ClassList, name: &str) {
-        with(|channel| channel.add_class(list.0 .0 .0, name));
+    pub fn add_class(list: &ClassList, name: &str) {
+        with(|channel| channel.add_class(list.0.id(), name));
     }
 
-    fn remove_class(list: &Self::ClassList, name: &str) {
-        with(|channel| channel.remove_class(list.0 .0 .0, name));
+    pub fn remove_class(list: &ClassList, name: &str) {
+        with(|channel| channel.remove_class(list.0.id(), name));
     }
 
-    fn style(_el: &Self::Element) -> Self::CssStyleDeclaration {
-        todo!()
-        //el.unchecked_ref::<HtmlElement>().style()
+    /// Removes the class names in `remove` from an element, and then adds those in `add`.
+    ///
+    /// Every operation is already batched, so this always updates the class list one name at a
+    /// time.
+    pub fn set_class_tokens(el: &Element, add: &[&str], remove: &[&str]) {
+        with(|channel| {
+            for name in remove {
+                channel.remove_class(el.id(), name);
+            }
+            for name in add {
+                channel.add_class(el.id(), name);
+            }
+        });
     }
 
-    fn set_css_property(
-        _style: &Self::CssStyleDeclaration,
-        _name: &str,
-        _value: &str,
+    /// Counts each class name in `names` as held by one more class value on the element, and
+    /// returns those that were not held before, which should be added to its class list.
+    pub fn hold_class_tokens<'a>(
+        el: &Element,
+        names: &[&'a str],
+    ) -> Vec<&'a str> {
+        el.0 .0.class_counts.borrow_mut().hold(names)
+    }
+
+    /// Releases each class name in `names` once, and returns those that no class value on the
+    /// element holds any more, which should be removed from its class list.
+    pub fn release_class_tokens<'a>(
+        el: &Element,
+        names: &[&'a str],
+    ) -> Vec<&'a str> {
+        el.0 .0.class_counts.borrow_mut().release(names)
+    }
+
+    /// Removes the CSS properties in `remove` from an element, and then sets those in `set`.
+    ///
+    /// Every operation is already batched, so this always updates the styles one property at a
+    /// time.
+    pub fn set_style_properties(
+        el: &Element,
+        set: &[(&str, &str)],
+        remove: &[&str],
     ) {
-        todo!()
-        /*or_debug!(
-            style.set_property(name, value),
-            style.unchecked_ref(),
-            "setProperty"
-        );*/
+        with(|channel| {
+            for name in remove {
+                channel.remove_css_property(el.id(), name);
+            }
+            for (name, value) in set {
+                channel.set_css_property(el.id(), name, *value);
+            }
+        });
+    }
+
+    pub fn style(el: &Element) -> CssStyleDeclaration {
+        CssStyleDeclaration(el.clone())
+    }
+
+    pub fn set_css_property(
+        style: &CssStyleDeclaration,
+        name: &str,
+        value: &str,
+    ) {
+        with(|channel| channel.set_css_property(style.0.id(), name, value));
     }
 
-    fn set_inner_html(el: &Self::Element, html: &str) {
-        with(|channel| channel.set_inner_html(el.0 .0, html))
+    pub fn remove_css_property(style: &CssStyleDeclaration, name: &str) {
+        with(|channel| channel.remove_css_property(style.0.id(), name));
     }
 
-    fn get_template<V>() -> Self::TemplateElement
+    pub fn set_inner_html(el: &Element, html: &str) {
+        with(|channel| channel.set_inner_html(el.id(), html));
+    }
+
+    pub fn get_template<V>() -> TemplateElement
     where
         V: ToTemplate + 'static,
     {
-        thread_local! {
-            static TEMPLATES: RefCell<LinearMap<TypeId, SNode>> = Default::default();
-        }
-
         TEMPLATES.with(|t| {
             t.borrow_mut()
                 .entry(TypeId::of::<V>())
@@ -542,43 +916,107 @@ impl DomRenderer for Sledgehammer {
                         &mut String::new(),
                         &mut Default::default(),
                     );
-                    let node = SNode::new();
-                    with(|channel| {
-                        channel.create_element(node.0 .0, "template");
-                        channel.set_inner_html(node.0 .0, &buf)
-                    });
-                    node
+                    let tpl = Self::create_element("template", None);
+                    Self::set_inner_html(&tpl, &buf);
+                    TemplateElement(tpl)
                 })
                 .clone()
         })
     }
 
-    fn clone_template(tpl: &Self::TemplateElement) -> Self::Element {
-        let node = SNode::new();
-        with(|channel| {
-            channel.clone_template(tpl.0 .0, node.0 .0);
-        });
-        node
+    pub fn clone_template(tpl: &TemplateElement) -> Element {
+        let node = Node::new(DOCUMENT_FRAGMENT_NODE);
+        with(|channel| channel.clone_template(tpl.0.id(), node.id()));
+        Element(node)
     }
 
-    fn create_element_from_html(_html: &str) -> Self::Element {
-        todo!()
+    pub fn create_element_from_html(html: &str) -> Element {
+        let tpl = Self::create_element("template", None);
+        Self::set_inner_html(&tpl, html);
+        Self::clone_template(&TemplateElement(tpl))
     }
 }
 
-impl Mountable<Sledgehammer> for SNode {
+fn is_hot_reload_marker(node: &web_sys::Node) -> bool {
+    node.node_type() == COMMENT_NODE
+        && node
+            .text_content()
+            .unwrap_or_default()
+            .starts_with("hot-reload")
+}
+
+impl Mountable for Node {
     fn unmount(&mut self) {
-        with(|channel| channel.remove(self.0 .0));
+        Sledgehammer::remove(self);
     }
 
-    fn mount(&mut self, parent: &SNode, marker: Option<&SNode>) {
+    fn mount(&mut self, parent: &Element, marker: Option<&Node>) {
         Sledgehammer::insert_node(parent, self, marker);
     }
 
-    fn insert_before_this(
-        &self,
-        _child: &mut dyn Mountable<Sledgehammer>,
-    ) -> bool {
-        todo!()
+    fn insert_before_this(&self, child: &mut dyn Mountable) -> bool {
+        Sledgehammer::try_mount_before(child, self)
+    }
+}
+
+macro_rules! mountable {
+    ($($ty:ident),*) => {
+        $(
+            impl Mountable for $ty {
+                fn unmount(&mut self) {
+                    Sledgehammer::remove(self);
+                }
+
+                fn mount(&mut self, parent: &Element, marker: Option<&Node>) {
+                    Sledgehammer::insert_node(parent, self, marker);
+                }
+
+                fn insert_before_this(&self, child: &mut dyn Mountable) -> bool {
+                    Sledgehammer::try_mount_before(child, self)
+                }
+            }
+        )*
+    };
+}
+
+mountable!(Element, Text, Placeholder);
+
+impl CastFrom<Node> for Node {
+    fn cast_from(source: Node) -> Option<Self> {
+        Some(source)
+    }
+}
+
+impl CastFrom<Node> for Text {
+    fn cast_from(source: Node) -> Option<Self> {
+        (source.0.kind == TEXT_NODE).then_some(Text(source))
+    }
+}
+
+impl CastFrom<Node> for Element {
+    fn cast_from(source: Node) -> Option<Self> {
+        (source.0.kind == ELEMENT_NODE).then_some(Element(source))
+    }
+}
+
+impl CastFrom<Node> for Placeholder {
+    fn cast_from(source: Node) -> Option<Self> {
+        (source.0.kind == COMMENT_NODE).then_some(Placeholder(source))
+    }
+}
+
+impl CastFrom<Element> for Element {
+    fn cast_from(source: Element) -> Option<Self> {
+        Some(source)
+    }
+}
+
+// event targets are read as `web_sys` elements
+impl<T> CastFrom<Element> for T
+where
+    T: JsCast,
+{
+    fn cast_from(source: Element) -> Option<Self> {
+        source.to_node().dyn_into::<T>().ok()
     }
 }
@@ -0,0 +1,144 @@
+use std::{borrow::Cow, fmt::Debug, sync::Arc};
+
+/// Chooses the IDs written into the marker comments around conditional branches, when a view is
+/// rendered with branch markers (see
+/// [`RenderHtml::to_html_branching`](crate::view::RenderHtml::to_html_branching)).
+///
+/// By default, each branch is marked with its local ID: the index of the arm for `Either` and the
+/// `EitherOf` enums, or a hash of the name of the inner view's type for `AnyView`. A marker can be
+/// passed with [`BranchMarks::with_marker`] to derive IDs from more context instead.
+pub trait BranchMarker: Send + Sync {
+    /// Returns the ID for a branch.
+    ///
+    /// `path` holds the local IDs of every branch enclosing this one, from the outermost in,
+    /// followed by the local ID of this branch itself. It does not depend on the siblings of any
+    /// of these branches, so the ID of a branch does not change when an unrelated part of the
+    /// view is added or removed.
+    fn branch_id(&self, path: &[String]) -> String;
+}
+
+/// Marks each branch with its local ID only. This is the default behavior.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LocalBranchIds;
+
+impl BranchMarker for LocalBranchIds {
+    fn branch_id(&self, path: &[String]) -> String {
+        path.last().cloned().unwrap_or_default()
+    }
+}
+
+/// Marks each branch with a hash of its path of enclosing branches.
+///
+/// The hash is computed with FNV-1a, so it is the same across builds and platforms as long as the
+/// local IDs in the path are.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HashedBranchPaths;
+
+impl BranchMarker for HashedBranchPaths {
+    fn branch_id(&self, path: &[String]) -> String {
+        const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+        const PRIME: u64 = 0x100000001b3;
+
+        let mut hash = OFFSET_BASIS;
+        for segment in path {
+            // separate segments, so that ["ab", "c"] and ["a", "bc"] hash differently
+            for byte in segment.bytes().chain([0]) {
+                hash ^= u64::from(byte);
+                hash = hash.wrapping_mul(PRIME);
+            }
+        }
+        format!("{hash:016x}")
+    }
+}
+
+/// Whether to mark the conditional branches of a view while rendering it to HTML, and where in
+/// the view the rendering is.
+///
+/// This is passed to [`RenderHtml::to_html_with_buf`](crate::view::RenderHtml::to_html_with_buf)
+/// and [`RenderHtml::to_html_async_with_buf`](crate::view::RenderHtml::to_html_async_with_buf).
+/// Each branch renders its contents with the marks returned by [`enter`](BranchMarks::enter),
+/// so the path of enclosing branches travels with the view, even when part of it is rendered
+/// later in an async chunk.
+#[derive(Clone, Default)]
+pub struct BranchMarks {
+    enabled: bool,
+    marker: Option<Arc<dyn BranchMarker>>,
+    path: Vec<String>,
+}
+
+impl Debug for BranchMarks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BranchMarks")
+            .field("enabled", &self.enabled)
+            .field("marker", &self.marker.is_some())
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+impl BranchMarks {
+    /// Does not mark branches.
+    pub const NONE: BranchMarks = BranchMarks {
+        enabled: false,
+        marker: None,
+        path: Vec::new(),
+    };
+
+    /// Marks each branch with its local ID.
+    pub fn local() -> Self {
+        Self {
+            enabled: true,
+            ..Default::default()
+        }
+    }
+
+    /// Marks each branch with the ID chosen by `marker`.
+    pub fn with_marker(marker: Arc<dyn BranchMarker>) -> Self {
+        Self {
+            enabled: true,
+            marker: Some(marker),
+            path: Vec::new(),
+        }
+    }
+
+    /// Whether branches are marked.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Returns the marks for the contents of a branch with the given local ID, inside the current
+    /// one.
+    pub fn enter(&self, local_id: &str) -> BranchMarks {
+        if !self.enabled {
+            return BranchMarks::NONE;
+        }
+        let mut path = if self.marker.is_some() {
+            self.path.clone()
+        } else {
+            Vec::with_capacity(1)
+        };
+        path.push(local_id.to_string());
+        BranchMarks {
+            enabled: true,
+            marker: self.marker.clone(),
+            path,
+        }
+    }
+
+    /// The ID of the innermost branch that has been [entered](BranchMarks::enter).
+    pub fn id(&self) -> Cow<'_, str> {
+        match &self.marker {
+            None => Cow::Borrowed(
+                self.path.last().map(String::as_str).unwrap_or_default(),
+            ),
+            Some(marker) => Cow::Owned(marker.branch_id(&self.path)),
+        }
+    }
+}
+
+/// Returns a local branch ID for a type, which is the same across builds and targets as long as
+/// the name of the type is.
+#[cfg(any(feature = "ssr", feature = "hydrate"))]
+pub(crate) fn type_branch_id<T: ?Sized>() -> String {
+    HashedBranchPaths.branch_id(&[std::any::type_name::<T>().to_string()])
+}
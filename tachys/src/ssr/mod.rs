@@ -1,13 +1,25 @@
 use crate::view::{Position, RenderHtml};
-use futures::Stream;
+use futures::{
+    channel::mpsc::{self, UnboundedSender},
+    Stream, StreamExt,
+};
 use std::{
     collections::VecDeque,
     fmt::{Debug, Write},
     future::Future,
     mem,
     pin::Pin,
+    sync::Arc,
     task::{Context, Poll},
 };
+use throw_error::ErrorHookFuture;
+
+mod branch;
+#[cfg(any(feature = "ssr", feature = "hydrate"))]
+pub(crate) use branch::type_branch_id;
+pub use branch::*;
+mod transform;
+pub use transform::*;
 
 /// Manages streaming HTML rendering for the response to a single request.
 #[derive(Default)]
@@ -17,8 +29,97 @@ pub struct StreamBuilder {
     pending: Option<ChunkFuture>,
     pending_ooo: VecDeque<PinnedFuture<OooChunk>>,
     id: Option<Vec<u16>>,
+    max_buffered_bytes: Option<usize>,
+    // the rest of a chunk that was larger than `max_buffered_bytes`, not yet yielded
+    overflow: String,
+    reorder: ReorderScript,
+    nonce: Option<Arc<str>>,
+    reorder_script_written: bool,
+    transformer: Option<Box<dyn HtmlTransformer + Send>>,
+    chunks_yielded: usize,
+}
+
+/// The order in which a stream sends the HTML for the async parts of a view.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum StreamingStrategy {
+    /// Sends HTML up to the next async boundary that has not yet resolved, waits for it, and
+    /// renders its HTML in place before continuing. Nested boundaries are resolved before their
+    /// parent continues.
+    ///
+    /// The stream contains no fallbacks and no scripts, so the document is valid HTML in the
+    /// order it is sent.
+    InOrder,
+    /// Sends a fallback for each async boundary immediately, and sends its HTML once it has
+    /// resolved, along with a script that moves it into place.
+    #[default]
+    OutOfOrder,
 }
 
+/// How chunks that arrive out of order are moved into place in the browser.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ReorderScript {
+    /// An inline `<script>` follows each chunk and moves it into place.
+    #[default]
+    Inline,
+    /// A single external `<script>` is loaded from this URL, the first time a chunk arrives out
+    /// of order, and moves every chunk into place as it arrives. The URL should serve
+    /// [`REORDER_SCRIPT`].
+    ///
+    /// This avoids inline scripts, so it can be used with a Content Security Policy that does
+    /// not allow them.
+    External(Arc<str>),
+}
+
+/// The script that moves out-of-order chunks into place, when using [`ReorderScript::External`].
+pub const REORDER_SCRIPT: &str = r#"(function () {
+  function reorder(id) {
+    let tpl = document.getElementById(`${id}f`);
+    let open = undefined;
+    let close = undefined;
+    let done = undefined;
+    let walker = document.createTreeWalker(document, NodeFilter.SHOW_COMMENT);
+    while (walker.nextNode()) {
+      let text = walker.currentNode.textContent;
+      if (text == `s-${id}o`) { open = walker.currentNode; }
+      else if (text == `s-${id}c`) { close = walker.currentNode; }
+      else if (text == `s-${id}r` || text == `s-${id}k`) { done = walker.currentNode; }
+    }
+    if (!tpl || !open || !close || !done) { return; }
+    if (done.textContent.endsWith("r")) {
+      let range = new Range();
+      range.setStartBefore(open);
+      range.setEndBefore(close);
+      range.deleteContents();
+      close.parentNode.insertBefore(tpl.content.cloneNode(true), close);
+      close.remove();
+    } else {
+      close.remove();
+      open.remove();
+    }
+    done.remove();
+  }
+  function scan(root) {
+    let walker = document.createTreeWalker(root, NodeFilter.SHOW_COMMENT);
+    let ids = [];
+    while (walker.nextNode()) {
+      let text = walker.currentNode.textContent;
+      if (text.startsWith("s-") && (text.endsWith("r") || text.endsWith("k"))) {
+        ids.push(text.slice(2, -1));
+      }
+    }
+    ids.forEach(reorder);
+  }
+  scan(document);
+  new MutationObserver(function (records) {
+    for (let record of records) {
+      for (let node of record.addedNodes) {
+        if (node.nodeType == Node.COMMENT_NODE) { scan(node.parentNode); }
+      }
+    }
+  }).observe(document, { childList: true, subtree: true });
+})();
+"#;
+
 type PinnedFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
 type ChunkFuture = PinnedFuture<VecDeque<StreamChunk>>;
 
@@ -37,8 +138,76 @@ impl StreamBuilder {
         }
     }
 
-    /// Reserves additional space in the synchronous buffer.
+    /// Creates a new stream for the given strategy, with a given capacity in the synchronous
+    /// buffer.
+    pub fn for_strategy(strategy: StreamingStrategy, capacity: usize) -> Self {
+        let id = match strategy {
+            StreamingStrategy::InOrder => None,
+            StreamingStrategy::OutOfOrder => Some(vec![0]),
+        };
+        Self::with_capacity(capacity, id)
+    }
+
+    /// The strategy this stream was created for.
+    ///
+    /// Only out-of-order streams need identifiers for their chunks, so a stream without one is
+    /// in order.
+    pub fn strategy(&self) -> StreamingStrategy {
+        if self.id.is_some() {
+            StreamingStrategy::OutOfOrder
+        } else {
+            StreamingStrategy::InOrder
+        }
+    }
+
+    /// Limits how much HTML the stream buffers before it is yielded.
+    ///
+    /// Synchronous HTML is normally rendered and yielded all at once, up to the next point at
+    /// which the stream has to wait. With a limit, views that can be rendered in pieces (such as
+    /// keyed lists) stop rendering once the buffer reaches the limit, and only continue when the
+    /// stream is polled again, so a consumer that waits before polling (for example, an HTTP
+    /// response body waiting for the connection to drain) can apply backpressure. Any piece that
+    /// is still larger than the limit is split up before it is yielded. The output, taken as a
+    /// whole, is the same either way. (A `Vec` of views is always rendered all at once, because
+    /// its items may borrow from the rest of the view.)
+    ///
+    /// For rendering to stop at the limit, it has to be set before the view is rendered, using
+    /// [`RenderHtml::to_html_stream_with_builder`].
+    pub fn with_max_buffered_bytes(
+        mut self,
+        max_buffered_bytes: usize,
+    ) -> Self {
+        self.max_buffered_bytes = Some(max_buffered_bytes.max(1));
+        self
+    }
+
+    /// Sets how chunks that arrive out of order are moved into place.
+    pub fn with_reorder_script(mut self, reorder: ReorderScript) -> Self {
+        self.reorder = reorder;
+        self
+    }
+
+    /// Sets a transformer, which rewrites each chunk of HTML before it is yielded.
+    pub fn with_transformer(
+        mut self,
+        transformer: impl HtmlTransformer + Send + 'static,
+    ) -> Self {
+        self.transformer = Some(Box::new(transformer));
+        self
+    }
+
+    /// Sets a nonce, which is added to every `<script>` tag written by the stream.
+    pub fn with_nonce(mut self, nonce: impl Into<Arc<str>>) -> Self {
+        self.nonce = Some(nonce.into());
+        self
+    }
+
+    /// Reserves additional space in the synchronous buffer, up to the limit set by
+    /// [`with_max_buffered_bytes`](Self::with_max_buffered_bytes).
     pub fn reserve(&mut self, additional: usize) {
+        let additional = self
+            .max_buffered_bytes
+            .map_or(additional, |max| additional.min(max));
         self.sync_buf.reserve(additional);
     }
 
@@ -62,6 +231,58 @@ impl StreamBuilder {
         });
     }
 
+    /// Whether the synchronous buffer has reached the limit set by
+    /// [`with_max_buffered_bytes`](Self::with_max_buffered_bytes), so that the rest of the view
+    /// should be rendered with [`defer`](Self::defer).
+    pub fn should_defer(&self) -> bool {
+        self.max_buffered_bytes
+            .is_some_and(|max| self.sync_buf.len() >= max)
+    }
+
+    /// Renders the rest of the view only once the stream has yielded everything before it.
+    ///
+    /// `render` is called with a new stream builder, in place of this one, when the stream
+    /// reaches this point.
+    pub fn defer(
+        &mut self,
+        render: impl FnOnce(&mut StreamBuilder) + Send + 'static,
+    ) {
+        self.next_id();
+        let id = self.child_id();
+        let max_buffered_bytes = self.max_buffered_bytes;
+        #[cfg(feature = "reactive_graph")]
+        let owner = reactive_graph::owner::Owner::current();
+        self.push_async(async move {
+            let mut builder = StreamBuilder {
+                max_buffered_bytes,
+                ..StreamBuilder::new(id)
+            };
+            #[cfg(feature = "reactive_graph")]
+            match owner {
+                Some(owner) => owner.with(|| render(&mut builder)),
+                None => render(&mut builder),
+            }
+            #[cfg(not(feature = "reactive_graph"))]
+            render(&mut builder);
+            builder.finish().take_chunks()
+        });
+    }
+
+    /// The number of bytes of HTML the stream is holding, which have been rendered but not yet
+    /// yielded.
+    pub fn buffered_bytes(&self) -> usize {
+        self.sync_buf.len()
+            + self.overflow.len()
+            + self
+                .chunks
+                .iter()
+                .map(|chunk| match chunk {
+                    StreamChunk::Sync(html) => html.len(),
+                    _ => 0,
+                })
+                .sum::<usize>()
+    }
+
     /// Mutates the synchronous buffer.
     pub fn with_buf(&mut self, fun: impl FnOnce(&mut String)) {
         fun(&mut self.sync_buf)
@@ -82,6 +303,15 @@ impl StreamBuilder {
         self.sync_buf.push_str(&other.sync_buf);
     }
 
+    /// Pushes a set of chunks into the stream, after everything that has been pushed so far.
+    pub fn push_chunks(&mut self, chunks: VecDeque<StreamChunk>) {
+        let sync = mem::take(&mut self.sync_buf);
+        if !sync.is_empty() {
+            self.chunks.push_back(StreamChunk::Sync(sync));
+        }
+        self.chunks.extend(chunks);
+    }
+
     /// Completes the stream.
     pub fn finish(mut self) -> Self {
         let sync_buf_remaining = mem::take(&mut self.sync_buf);
@@ -101,7 +331,7 @@ impl StreamBuilder {
         &mut self,
         fallback: View,
         position: &mut Position,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) where
         View: RenderHtml,
     {
@@ -158,9 +388,35 @@ impl StreamBuilder {
         &mut self,
         view: impl Future<Output = Option<View>> + Send + 'static,
         position: &mut Position,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) where
         View: RenderHtml,
+    {
+        let mark_branches = mark_branches.clone();
+        self.push_async_out_of_order_with(
+            view,
+            position,
+            move |view, subbuilder, position| {
+                subbuilder.reserve(view.html_len());
+                view.to_html_async_with_buf::<true>(
+                    subbuilder,
+                    position,
+                    true,
+                    &mark_branches,
+                );
+            },
+        );
+    }
+
+    /// Injects an out-of-order chunk into the stream, which is rendered by `render` once `value`
+    /// resolves. If it resolves to `None`, the chunk's fallback is left in place.
+    pub fn push_async_out_of_order_with<T>(
+        &mut self,
+        value: impl Future<Output = Option<T>> + Send + 'static,
+        position: &mut Position,
+        render: impl FnOnce(T, &mut StreamBuilder, &mut Position) + Send + 'static,
+    ) where
+        T: Send,
     {
         let id = self.clone_id();
         // copy so it's not updated by additional iterations
@@ -169,7 +425,7 @@ impl StreamBuilder {
 
         self.chunks.push_back(StreamChunk::OutOfOrder {
             chunks: Box::pin(async move {
-                let view = view.await;
+                let value = value.await;
 
                 let mut subbuilder = StreamBuilder::new(id);
                 let mut id = String::new();
@@ -181,14 +437,9 @@ impl StreamBuilder {
                 if let Some(id) = subbuilder.id.as_mut() {
                     id.push(0);
                 }
-                let replace = view.is_some();
-                if let Some(view) = view {
-                    view.to_html_async_with_buf::<true>(
-                        &mut subbuilder,
-                        &mut position,
-                        true,
-                        mark_branches,
-                    );
+                let replace = value.is_some();
+                if let Some(value) = value {
+                    render(value, &mut subbuilder, &mut position);
                 }
                 let chunks = subbuilder.finish().take_chunks();
 
@@ -228,6 +479,114 @@ pub enum StreamChunk {
     },
 }
 
+impl StreamChunk {
+    /// Waits for the given chunks to be ready, including any chunks that they resolve to.
+    ///
+    /// In-order chunks are replaced by their contents, and out-of-order chunks by ones that are
+    /// already resolved, so that the result can be pushed in place of the original chunks.
+    pub fn resolve_all(
+        chunks: VecDeque<StreamChunk>,
+    ) -> PinnedFuture<VecDeque<StreamChunk>> {
+        Box::pin(async move {
+            let mut resolved = VecDeque::with_capacity(chunks.len());
+            for chunk in chunks {
+                match chunk {
+                    StreamChunk::Sync(html) => {
+                        resolved.push_back(StreamChunk::Sync(html))
+                    }
+                    StreamChunk::Async { chunks } => resolved
+                        .extend(StreamChunk::resolve_all(chunks.await).await),
+                    StreamChunk::OutOfOrder { chunks } => {
+                        let OooChunk {
+                            id,
+                            chunks,
+                            replace,
+                        } = chunks.await;
+                        let chunks = StreamChunk::resolve_all(chunks).await;
+                        resolved.push_back(StreamChunk::OutOfOrder {
+                            chunks: Box::pin(async move {
+                                OooChunk {
+                                    id,
+                                    chunks,
+                                    replace,
+                                }
+                            }),
+                        });
+                    }
+                }
+            }
+            resolved
+        })
+    }
+
+    /// Makes sure that errors thrown while rendering the given chunks are caught by the error
+    /// hook that is set now.
+    ///
+    /// Returns the wrapped chunks, and a `Future` that resolves once all of them, including the
+    /// chunks they resolve to, are ready. This lets an error boundary find out whether its
+    /// children threw any errors after they have been rendered asynchronously.
+    pub fn with_error_hook(
+        chunks: VecDeque<StreamChunk>,
+    ) -> (VecDeque<StreamChunk>, impl Future<Output = ()> + Send) {
+        let (tx, mut rx) = mpsc::unbounded();
+        let chunks = StreamChunk::track_errors(chunks, &tx);
+        drop(tx);
+        // the receiver only ends once every chunk has dropped its sender
+        (chunks, async move { while rx.next().await.is_some() {} })
+    }
+
+    // wraps each pending chunk so that it's polled with the current error hook, and holds on to
+    // `pending` until it and the chunks it resolves to are ready
+    fn track_errors(
+        chunks: VecDeque<StreamChunk>,
+        pending: &UnboundedSender<()>,
+    ) -> VecDeque<StreamChunk> {
+        chunks
+            .into_iter()
+            .map(|chunk| match chunk {
+                StreamChunk::Sync(html) => StreamChunk::Sync(html),
+                StreamChunk::Async { chunks } => {
+                    let pending = pending.clone();
+                    StreamChunk::Async {
+                        chunks: Box::pin(ErrorHookFuture::new(async move {
+                            StreamChunk::track_errors(chunks.await, &pending)
+                        })),
+                    }
+                }
+                StreamChunk::OutOfOrder { chunks } => {
+                    let pending = pending.clone();
+                    StreamChunk::OutOfOrder {
+                        chunks: Box::pin(ErrorHookFuture::new(async move {
+                            let mut chunk = chunks.await;
+                            chunk.chunks = StreamChunk::track_errors(
+                                chunk.chunks,
+                                &pending,
+                            );
+                            chunk
+                        })),
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+// pushes an opening `<script>` tag, with an optional source and nonce
+fn push_script_tag(src: Option<&str>, nonce: Option<&str>, buf: &mut String) {
+    buf.push_str("<script");
+    if let Some(src) = src {
+        buf.push_str(" src=\"");
+        buf.push_str(&html_escape::encode_double_quoted_attribute(src));
+        buf.push('"');
+    }
+    if let Some(nonce) = nonce {
+        buf.push_str(" nonce=\"");
+        buf.push_str(&html_escape::encode_double_quoted_attribute(nonce));
+        buf.push('"');
+    }
+    buf.push('>');
+}
+
 /// A chunk of the out-of-order stream.
 #[derive(Debug)]
 pub struct OooChunk {
@@ -247,11 +606,28 @@ impl OooChunk {
 
     /// Pushes a closing `</template>` and update script into the buffer.
     pub fn push_end(replace: bool, id: &str, buf: &mut String) {
+        Self::push_end_with_nonce(replace, id, None, buf)
+    }
+
+    /// Pushes a closing `</template>` and a marker comment into the buffer, to be handled by the
+    /// external [`REORDER_SCRIPT`].
+    pub fn push_end_external(replace: bool, id: &str, buf: &mut String) {
+        buf.push_str("</template><!--s-");
+        buf.push_str(id);
+        buf.push_str(if replace { "r-->" } else { "k-->" });
+    }
+
+    /// Pushes a closing `</template>` and update script, with the given nonce, into the buffer.
+    pub fn push_end_with_nonce(
+        replace: bool,
+        id: &str,
+        nonce: Option<&str>,
+        buf: &mut String,
+    ) {
         buf.push_str("</template>");
 
-        // TODO nonce
-        buf.push_str("<script");
-        buf.push_str(r#">(function() { let id = ""#);
+        push_script_tag(None, nonce, buf);
+        buf.push_str(r#"(function() { let id = ""#);
         buf.push_str(id);
         buf.push_str(
             "\";let open = undefined;let close = undefined;let walker = \
@@ -291,6 +667,31 @@ impl Debug for StreamChunk {
     }
 }
 
+impl StreamBuilder {
+    // splits off and returns at most `max_buffered_bytes` from the front of `chunk`, keeping the
+    // rest to be yielded next
+    fn limit_chunk(&mut self, mut chunk: String) -> String {
+        match self.max_buffered_bytes {
+            Some(max) if chunk.len() > max => {
+                let mut at = max;
+                while !chunk.is_char_boundary(at) {
+                    at -= 1;
+                }
+                // a single character wider than the limit still has to be yielded whole
+                if at == 0 {
+                    at = chunk
+                        .char_indices()
+                        .nth(1)
+                        .map_or(chunk.len(), |(idx, _)| idx);
+                }
+                self.overflow = chunk.split_off(at);
+                chunk
+            }
+            _ => chunk,
+        }
+    }
+}
+
 impl Stream for StreamBuilder {
     type Item = String;
 
@@ -298,6 +699,31 @@ impl Stream for StreamBuilder {
         mut self: Pin<&mut Self>,
         cx: &mut Context<'_>,
     ) -> Poll<Option<Self::Item>> {
+        if !self.overflow.is_empty() {
+            let overflow = mem::take(&mut self.overflow);
+            let chunk = self.limit_chunk(overflow);
+            return Poll::Ready(Some(chunk));
+        }
+        match self.as_mut().poll_next_chunk(cx) {
+            Poll::Ready(Some(mut chunk)) => {
+                let this = self.as_mut().get_mut();
+                if let Some(transformer) = &mut this.transformer {
+                    let context = ChunkContext::new(this.chunks_yielded, true);
+                    transformer.transform_chunk(&mut chunk, context);
+                }
+                this.chunks_yielded += 1;
+                Poll::Ready(Some(self.limit_chunk(chunk)))
+            }
+            other => other,
+        }
+    }
+}
+
+impl StreamBuilder {
+    fn poll_next_chunk(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<String>> {
         let mut this = self.as_mut();
         let pending = this.pending.take();
         if let Some(mut pending) = pending {
@@ -310,88 +736,120 @@ impl Stream for StreamBuilder {
                     for chunk in chunks.into_iter().rev() {
                         this.chunks.push_front(chunk);
                     }
-                    self.poll_next(cx)
+                    self.poll_next_chunk(cx)
                 }
             }
         } else {
             let next_chunk = this.chunks.pop_front();
             match next_chunk {
                 None => {
-                    // now, handle out-of-order chunks
-                    if let Some(mut pending) = this.pending_ooo.pop_front() {
+                    // now, handle out-of-order chunks, any of which may be ready first
+                    let mut ready = None;
+                    for _ in 0..this.pending_ooo.len() {
+                        let Some(mut pending) = this.pending_ooo.pop_front()
+                        else {
+                            break;
+                        };
                         match pending.as_mut().poll(cx) {
-                            Poll::Ready(OooChunk {
-                                id,
-                                chunks,
-                                replace,
-                            }) => {
-                                let opening = format!("<!--s-{id}o-->");
-                                let placeholder_at =
-                                    this.sync_buf.find(&opening);
-                                if let Some(start) = placeholder_at {
-                                    let closing = format!("<!--s-{id}c-->");
-                                    let end =
-                                        this.sync_buf.find(&closing).unwrap();
-                                    let chunks_iter = chunks.into_iter().rev();
-
-                                    // TODO can probably make this more efficient
-                                    let (before, replaced) =
-                                        this.sync_buf.split_at(start);
-                                    let (_, after) = replaced
-                                        .split_at(end - start + closing.len());
-                                    let mut buf = String::new();
-                                    buf.push_str(before);
-
-                                    let mut held_chunks = VecDeque::new();
-                                    for chunk in chunks_iter {
-                                        if let StreamChunk::Sync(ready) = chunk
-                                        {
-                                            buf.push_str(&ready);
-                                        } else {
-                                            held_chunks.push_front(chunk);
-                                        }
+                            Poll::Ready(chunk) => {
+                                ready = Some(chunk);
+                                break;
+                            }
+                            Poll::Pending => {
+                                this.pending_ooo.push_back(pending)
+                            }
+                        }
+                    }
+                    match ready {
+                        Some(OooChunk {
+                            id,
+                            chunks,
+                            replace,
+                        }) => {
+                            let opening = format!("<!--s-{id}o-->");
+                            let placeholder_at = this.sync_buf.find(&opening);
+                            if let Some(start) = placeholder_at {
+                                let closing = format!("<!--s-{id}c-->");
+                                let end = this.sync_buf.find(&closing).unwrap();
+                                let chunks_iter = chunks.into_iter().rev();
+
+                                // TODO can probably make this more efficient
+                                let (before, replaced) =
+                                    this.sync_buf.split_at(start);
+                                let (_, after) = replaced
+                                    .split_at(end - start + closing.len());
+                                let mut buf = String::new();
+                                buf.push_str(before);
+                                // a chunk that doesn't replace its placeholder just removes the
+                                // markers around it, like the script below
+                                if !replace {
+                                    buf.push_str(
+                                        &replaced[opening.len()..end - start],
+                                    );
+                                }
+
+                                let mut held_chunks = VecDeque::new();
+                                for chunk in chunks_iter {
+                                    if let StreamChunk::Sync(ready) = chunk {
+                                        buf.push_str(&ready);
+                                    } else {
+                                        held_chunks.push_front(chunk);
                                     }
-                                    buf.push_str(after);
-                                    this.sync_buf = buf;
-                                    for chunk in held_chunks {
+                                }
+                                buf.push_str(after);
+                                this.sync_buf = buf;
+                                for chunk in held_chunks {
+                                    this.chunks.push_front(chunk);
+                                }
+                            } else {
+                                OooChunk::push_start(&id, &mut this.sync_buf);
+                                for chunk in chunks.into_iter().rev() {
+                                    if let StreamChunk::Sync(ready) = chunk {
+                                        this.sync_buf.push_str(&ready);
+                                    } else {
                                         this.chunks.push_front(chunk);
                                     }
-                                } else {
-                                    OooChunk::push_start(
-                                        &id,
-                                        &mut this.sync_buf,
-                                    );
-                                    for chunk in chunks.into_iter().rev() {
-                                        if let StreamChunk::Sync(ready) = chunk
-                                        {
-                                            this.sync_buf.push_str(&ready);
-                                        } else {
-                                            this.chunks.push_front(chunk);
+                                }
+                                let nonce = this.nonce.clone();
+                                match this.reorder.clone() {
+                                    ReorderScript::Inline => {
+                                        OooChunk::push_end_with_nonce(
+                                            replace,
+                                            &id,
+                                            nonce.as_deref(),
+                                            &mut this.sync_buf,
+                                        )
+                                    }
+                                    ReorderScript::External(src) => {
+                                        OooChunk::push_end_external(
+                                            replace,
+                                            &id,
+                                            &mut this.sync_buf,
+                                        );
+                                        if !this.reorder_script_written {
+                                            this.reorder_script_written = true;
+                                            push_script_tag(
+                                                Some(&src),
+                                                nonce.as_deref(),
+                                                &mut this.sync_buf,
+                                            );
+                                            this.sync_buf.push_str("</script>");
                                         }
                                     }
-                                    OooChunk::push_end(
-                                        replace,
-                                        &id,
-                                        &mut this.sync_buf,
-                                    );
                                 }
-                                self.poll_next(cx)
                             }
-                            Poll::Pending => {
-                                this.pending_ooo.push_back(pending);
-                                if this.sync_buf.is_empty() {
-                                    Poll::Pending
-                                } else {
-                                    Poll::Ready(Some(mem::take(
-                                        &mut this.sync_buf,
-                                    )))
-                                }
+                            self.poll_next_chunk(cx)
+                        }
+                        None if this.sync_buf.is_empty() => {
+                            if this.pending_ooo.is_empty() {
+                                Poll::Ready(None)
+                            } else {
+                                Poll::Pending
                             }
                         }
-                    } else if this.sync_buf.is_empty() {
-                        Poll::Ready(None)
-                    } else {
-                        Poll::Ready(Some(mem::take(&mut this.sync_buf)))
+                        None => {
+                            Poll::Ready(Some(mem::take(&mut this.sync_buf)))
+                        }
                     }
                 }
                 Some(StreamChunk::Sync(value)) => {
@@ -416,12 +874,12 @@ impl Stream for StreamBuilder {
                         }
                     }
 
-                    this.poll_next(cx)
+                    this.poll_next_chunk(cx)
                 }
                 Some(StreamChunk::Async { chunks, .. }) => {
                     this.pending = Some(chunks);
                     if this.sync_buf.is_empty() {
-                        self.poll_next(cx)
+                        self.poll_next_chunk(cx)
                     } else {
                         Poll::Ready(Some(mem::take(&mut this.sync_buf)))
                     }
@@ -429,7 +887,7 @@ impl Stream for StreamBuilder {
                 Some(StreamChunk::OutOfOrder { chunks, .. }) => {
                     this.pending_ooo.push_back(chunks);
                     if this.sync_buf.is_empty() {
-                        self.poll_next(cx)
+                        self.poll_next_chunk(cx)
                     } else {
                         Poll::Ready(Some(mem::take(&mut this.sync_buf)))
                     }
@@ -0,0 +1,405 @@
+use std::mem;
+
+/// Rewrites HTML as it is rendered, one chunk at a time.
+///
+/// A transformer can be set on a [`StreamBuilder`](super::StreamBuilder) with
+/// [`with_transformer`](super::StreamBuilder::with_transformer), in which case it is called with
+/// every chunk the stream yields, in order, or used with
+/// [`RenderHtml::to_html_with_transformer`](crate::view::RenderHtml::to_html_with_transformer),
+/// in which case it is called once with the whole document.
+///
+/// Chunks can end anywhere, including in the middle of a tag, so a transformer that needs to see
+/// a whole tag at once should hold on to the start of it and write it out with the next chunk.
+pub trait HtmlTransformer {
+    /// Rewrites a chunk of HTML in place.
+    fn transform_chunk(&mut self, chunk: &mut String, context: ChunkContext);
+}
+
+/// Describes the chunk passed to [`HtmlTransformer::transform_chunk`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ChunkContext {
+    /// The index of this chunk among the chunks yielded so far.
+    pub index: usize,
+    /// Whether this chunk is part of a stream, rather than a whole document rendered at once.
+    pub streaming: bool,
+}
+
+impl ChunkContext {
+    /// Creates the context for a chunk.
+    pub fn new(index: usize, streaming: bool) -> Self {
+        Self { index, streaming }
+    }
+}
+
+/// Leaves HTML as it is.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Identity;
+
+impl HtmlTransformer for Identity {
+    fn transform_chunk(&mut self, _chunk: &mut String, _context: ChunkContext) {
+    }
+}
+
+/// Collapses each run of whitespace in text to a single space.
+///
+/// Whitespace is never removed entirely, so every text node that was rendered is still there,
+/// and the output hydrates just like the original. Tags, comments (including hydration and branch
+/// markers), and the contents of `<pre>`, `<textarea>`, `<script>` and `<style>` are left as they
+/// are, as is the content of any element with a `data-preserve-whitespace` attribute.
+#[derive(Debug, Default)]
+pub struct Minify {
+    scanner: Scanner,
+    space: bool,
+}
+
+impl HtmlTransformer for Minify {
+    fn transform_chunk(&mut self, chunk: &mut String, _context: ChunkContext) {
+        let Self { scanner, space } = self;
+        let mut out = String::with_capacity(chunk.len());
+        scanner.feed(chunk, |token| match token {
+            Token::Text(c, false) if c.is_ascii_whitespace() => {
+                if !*space {
+                    out.push(' ');
+                    *space = true;
+                }
+            }
+            Token::Text(c, _) => {
+                out.push(c);
+                *space = false;
+            }
+            Token::Markup(markup) => {
+                out.push_str(markup);
+                *space = false;
+            }
+            Token::Tag(tag) => {
+                out.push_str(tag.text);
+                *space = false;
+            }
+        });
+        *chunk = out;
+    }
+}
+
+/// Puts each tag on its own line, indented by how deeply it is nested.
+///
+/// This is meant for reading the HTML while debugging. The added whitespace creates text nodes
+/// that the view does not expect, so indented HTML should not be hydrated. The contents of the
+/// same elements as for [`Minify`] are left as they are.
+#[derive(Debug)]
+pub struct Indent {
+    scanner: Scanner,
+    width: usize,
+    depth: usize,
+    started: bool,
+}
+
+impl Default for Indent {
+    fn default() -> Self {
+        Self::new(2)
+    }
+}
+
+impl Indent {
+    /// Creates a transformer that indents each level of nesting by `width` spaces.
+    pub fn new(width: usize) -> Self {
+        Self {
+            scanner: Scanner::default(),
+            width,
+            depth: 0,
+            started: false,
+        }
+    }
+}
+
+impl HtmlTransformer for Indent {
+    fn transform_chunk(&mut self, chunk: &mut String, _context: ChunkContext) {
+        let Self {
+            scanner,
+            width,
+            depth,
+            started,
+        } = self;
+        let mut out = String::with_capacity(chunk.len() * 2);
+        scanner.feed(chunk, |token| {
+            match token {
+                Token::Text(c, _) => out.push(c),
+                Token::Markup(markup) => out.push_str(markup),
+                Token::Tag(tag) => {
+                    if tag.closing && !tag.preserved_after {
+                        *depth = depth.saturating_sub(1);
+                    }
+                    if !tag.preserved_before && *started {
+                        out.push('\n');
+                        out.extend(
+                            std::iter::repeat(' ').take(*depth * *width),
+                        );
+                    }
+                    out.push_str(tag.text);
+                    if !tag.closing && !tag.void && !tag.preserved_before {
+                        *depth += 1;
+                    }
+                }
+            }
+            *started = true;
+        });
+        *chunk = out;
+    }
+}
+
+// elements that never have children or a closing tag
+const VOID_ELEMENTS: [&str; 13] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta",
+    "source", "track", "wbr",
+];
+
+// elements whose whitespace is significant
+const PRESERVED_ELEMENTS: [&str; 4] = ["pre", "textarea", "script", "style"];
+
+const PRESERVE_ATTRIBUTE: &str = "data-preserve-whitespace";
+
+// a piece of HTML, as seen by the scanner
+enum Token<'a> {
+    // a character of text, and whether its whitespace is significant
+    Text(char, bool),
+    // part of a comment or a declaration like `<!DOCTYPE html>`
+    Markup(&'a str),
+    Tag(ScannedTag<'a>),
+}
+
+struct ScannedTag<'a> {
+    text: &'a str,
+    closing: bool,
+    void: bool,
+    // whether the tag is inside an element whose whitespace is significant, before and after it
+    preserved_before: bool,
+    preserved_after: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum ScanState {
+    #[default]
+    Text,
+    // just after `<`
+    Open,
+    // just after `<!`
+    Bang,
+    Comment,
+    Declaration,
+    TagName,
+    Tag,
+}
+
+// splits HTML into text, markup and tags, keeping its place across chunks
+#[derive(Debug, Default)]
+struct Scanner {
+    state: ScanState,
+    // the tag read so far, which is only emitted once it is complete
+    buf: String,
+    name: String,
+    closing: bool,
+    self_closing: bool,
+    quote: Option<char>,
+    dashes: usize,
+    // the `script` or `style` element we are inside, whose text can contain `<`
+    raw_text: Option<&'static str>,
+    // the element whose whitespace is significant, and how many of it we are nested inside
+    preserved: Option<(String, usize)>,
+}
+
+impl Scanner {
+    fn feed(&mut self, chunk: &str, mut emit: impl FnMut(Token<'_>)) {
+        for c in chunk.chars() {
+            self.push(c, &mut emit);
+        }
+    }
+
+    fn push(&mut self, c: char, emit: &mut impl FnMut(Token<'_>)) {
+        match self.state {
+            ScanState::Text => match self.raw_text {
+                Some(raw) => self.push_raw_text(raw, c, emit),
+                None if c == '<' => {
+                    self.buf.push(c);
+                    self.state = ScanState::Open;
+                }
+                None => emit(Token::Text(c, self.preserved.is_some())),
+            },
+            ScanState::Open => {
+                self.buf.push(c);
+                match c {
+                    '!' => self.state = ScanState::Bang,
+                    '/' => {
+                        self.closing = true;
+                        self.state = ScanState::TagName;
+                    }
+                    c if c.is_ascii_alphabetic() => {
+                        self.name.push(c.to_ascii_lowercase());
+                        self.state = ScanState::TagName;
+                    }
+                    // not a tag after all
+                    _ => {
+                        let preserved = self.preserved.is_some();
+                        for c in mem::take(&mut self.buf).chars() {
+                            emit(Token::Text(c, preserved));
+                        }
+                        self.state = ScanState::Text;
+                    }
+                }
+            }
+            ScanState::Bang => {
+                self.buf.push(c);
+                if c == '-' {
+                    self.dashes += 1;
+                    if self.dashes == 2 {
+                        self.dashes = 0;
+                        self.state = ScanState::Comment;
+                        emit(Token::Markup(&self.buf));
+                        self.buf.clear();
+                    }
+                } else {
+                    self.dashes = 0;
+                    self.state = if c == '>' {
+                        ScanState::Text
+                    } else {
+                        ScanState::Declaration
+                    };
+                    emit(Token::Markup(&self.buf));
+                    self.buf.clear();
+                }
+            }
+            ScanState::Comment => {
+                let mut tmp = [0; 4];
+                emit(Token::Markup(c.encode_utf8(&mut tmp)));
+                if c == '-' {
+                    self.dashes += 1;
+                } else {
+                    if c == '>' && self.dashes >= 2 {
+                        self.state = ScanState::Text;
+                    }
+                    self.dashes = 0;
+                }
+            }
+            ScanState::Declaration => {
+                let mut tmp = [0; 4];
+                emit(Token::Markup(c.encode_utf8(&mut tmp)));
+                if c == '>' {
+                    self.state = ScanState::Text;
+                }
+            }
+            ScanState::TagName => {
+                self.buf.push(c);
+                match c {
+                    '>' => self.finish_tag(emit),
+                    '/' => {
+                        self.self_closing = true;
+                        self.state = ScanState::Tag;
+                    }
+                    c if c.is_ascii_whitespace() => self.state = ScanState::Tag,
+                    c => self.name.push(c.to_ascii_lowercase()),
+                }
+            }
+            ScanState::Tag => {
+                self.buf.push(c);
+                match self.quote {
+                    Some(quote) => {
+                        if c == quote {
+                            self.quote = None;
+                        }
+                    }
+                    None => match c {
+                        '>' => self.finish_tag(emit),
+                        '"' | '\'' => {
+                            self.quote = Some(c);
+                            self.self_closing = false;
+                        }
+                        '/' => self.self_closing = true,
+                        c if c.is_ascii_whitespace() => {}
+                        _ => self.self_closing = false,
+                    },
+                }
+            }
+        }
+    }
+
+    // inside `<script>` or `<style>`, only the matching closing tag ends the text
+    fn push_raw_text(
+        &mut self,
+        raw: &'static str,
+        c: char,
+        emit: &mut impl FnMut(Token<'_>),
+    ) {
+        let expected = match self.buf.len() {
+            0 => Some('<'),
+            1 => Some('/'),
+            n => raw[n - 2..].chars().next(),
+        };
+        if expected.is_some_and(|e| c.eq_ignore_ascii_case(&e)) {
+            self.buf.push(c);
+            if self.buf.len() == raw.len() + 2 {
+                self.raw_text = None;
+                self.closing = true;
+                self.name.push_str(raw);
+                self.state = ScanState::TagName;
+            }
+        } else {
+            for c in mem::take(&mut self.buf).chars() {
+                emit(Token::Text(c, true));
+            }
+            if c == '<' {
+                self.buf.push(c);
+            } else {
+                emit(Token::Text(c, true));
+            }
+        }
+    }
+
+    fn finish_tag(&mut self, emit: &mut impl FnMut(Token<'_>)) {
+        let name = mem::take(&mut self.name);
+        let closing = mem::take(&mut self.closing);
+        let void = mem::take(&mut self.self_closing)
+            || VOID_ELEMENTS.contains(&name.as_str());
+        let preserved_before = self.preserved.is_some();
+
+        if closing {
+            if let Some((preserved, depth)) = &mut self.preserved {
+                if *preserved == name {
+                    *depth -= 1;
+                    if *depth == 0 {
+                        self.preserved = None;
+                    }
+                }
+            }
+        } else if !void {
+            match &mut self.preserved {
+                Some((preserved, depth)) => {
+                    if *preserved == name {
+                        *depth += 1;
+                    }
+                }
+                None => {
+                    if PRESERVED_ELEMENTS.contains(&name.as_str())
+                        || self.buf.contains(PRESERVE_ATTRIBUTE)
+                    {
+                        self.preserved = Some((name.clone(), 1));
+                    }
+                }
+            }
+            self.raw_text = match name.as_str() {
+                "script" => Some("script"),
+                "style" => Some("style"),
+                _ => None,
+            };
+        }
+
+        emit(Token::Tag(ScannedTag {
+            text: &self.buf,
+            closing,
+            void,
+            preserved_before,
+            preserved_after: self.preserved.is_some(),
+        }));
+        self.buf.clear();
+        self.quote = None;
+        self.state = ScanState::Text;
+    }
+}
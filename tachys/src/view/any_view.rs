@@ -5,7 +5,9 @@ use super::{
     RenderHtml,
 };
 use crate::{
-    html::attribute::Attribute, hydration::Cursor, ssr::StreamBuilder,
+    html::attribute::Attribute,
+    hydration::Cursor,
+    ssr::{BranchMarks, StreamBuilder},
 };
 use std::{
     any::{Any, TypeId},
@@ -21,6 +23,12 @@ use std::{future::Future, pin::Pin};
 /// It can also be used to create recursive components, which otherwise cannot return themselves
 /// due to the static typing of the view tree.
 ///
+/// The retained state remembers the concrete type it was built from. When an `AnyView` is rebuilt
+/// with a view of the same underlying type (for example, when `move || view.into_any()` reruns
+/// and takes the same branch as before), the state is downcast and the concrete view's `rebuild`
+/// is used, so the existing DOM is updated in place. Only a change of type unmounts the old view
+/// and builds the new one.
+///
 /// Generally speaking, using `AnyView` restricts the amount of information available to the
 /// compiler and should be limited to situations in which it is necessary to preserve the maximum
 /// amount of type information possible.
@@ -36,13 +44,13 @@ pub struct AnyView {
     #[cfg(feature = "ssr")]
     html_len: usize,
     #[cfg(feature = "ssr")]
-    to_html: fn(Box<dyn Any>, &mut String, &mut Position, bool, bool),
+    to_html: fn(Box<dyn Any>, &mut String, &mut Position, bool, &BranchMarks),
     #[cfg(feature = "ssr")]
     to_html_async:
-        fn(Box<dyn Any>, &mut StreamBuilder, &mut Position, bool, bool),
+        fn(Box<dyn Any>, &mut StreamBuilder, &mut Position, bool, &BranchMarks),
     #[cfg(feature = "ssr")]
     to_html_async_ooo:
-        fn(Box<dyn Any>, &mut StreamBuilder, &mut Position, bool, bool),
+        fn(Box<dyn Any>, &mut StreamBuilder, &mut Position, bool, &BranchMarks),
     #[cfg(feature = "ssr")]
     #[allow(clippy::type_complexity)]
     resolve: fn(Box<dyn Any>) -> Pin<Box<dyn Future<Output = AnyView> + Send>>,
@@ -163,25 +171,20 @@ where
                      buf: &mut String,
                      position: &mut Position,
                      escape: bool,
-                     mark_branches: bool| {
-                        let type_id = mark_branches
-                            .then(|| format!("{:?}", TypeId::of::<T>()))
-                            .unwrap_or_default();
+                     mark_branches: &BranchMarks| {
+                        let mark_branches = &mark_branches
+                            .enter(&crate::ssr::type_branch_id::<T>());
                         let value = value
                             .downcast::<T>()
                             .expect("AnyView::to_html could not be downcast");
-                        if mark_branches {
-                            buf.open_branch(&type_id);
-                        }
+                        buf.open_branch(mark_branches);
                         value.to_html_with_buf(
                             buf,
                             position,
                             escape,
                             mark_branches,
                         );
-                        if mark_branches {
-                            buf.close_branch(&type_id);
-                        }
+                        buf.close_branch(mark_branches);
                     };
                 #[cfg(feature = "ssr")]
                 let to_html_async =
@@ -189,25 +192,20 @@ where
                      buf: &mut StreamBuilder,
                      position: &mut Position,
                      escape: bool,
-                     mark_branches: bool| {
-                        let type_id = mark_branches
-                            .then(|| format!("{:?}", TypeId::of::<T>()))
-                            .unwrap_or_default();
+                     mark_branches: &BranchMarks| {
+                        let mark_branches = &mark_branches
+                            .enter(&crate::ssr::type_branch_id::<T>());
                         let value = value
                             .downcast::<T>()
                             .expect("AnyView::to_html could not be downcast");
-                        if mark_branches {
-                            buf.open_branch(&type_id);
-                        }
+                        buf.open_branch(mark_branches);
                         value.to_html_async_with_buf::<false>(
                             buf,
                             position,
                             escape,
                             mark_branches,
                         );
-                        if mark_branches {
-                            buf.close_branch(&type_id);
-                        }
+                        buf.close_branch(mark_branches);
                     };
                 #[cfg(feature = "ssr")]
                 let to_html_async_ooo =
@@ -215,7 +213,7 @@ where
                      buf: &mut StreamBuilder,
                      position: &mut Position,
                      escape: bool,
-                     mark_branches: bool| {
+                     mark_branches: &BranchMarks| {
                         let value = value
                             .downcast::<T>()
                             .expect("AnyView::to_html could not be downcast");
@@ -249,8 +247,18 @@ where
                         let value = value.downcast::<T>().expect(
                             "AnyView::hydrate_from_server couldn't downcast",
                         );
+                        let marks_branches = cursor.marks_branches();
+                        if marks_branches {
+                            cursor.open_branch(
+                                &crate::ssr::type_branch_id::<T>(),
+                                position,
+                            );
+                        }
                         let state =
                             Box::new(value.hydrate::<true>(cursor, position));
+                        if marks_branches {
+                            cursor.close_branch(position);
+                        }
 
                         AnyViewState {
                             type_id: TypeId::of::<T>(),
@@ -367,7 +375,7 @@ impl RenderHtml for AnyView {
         buf: &mut String,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) {
         #[cfg(feature = "ssr")]
         (self.to_html)(self.value, buf, position, escape, mark_branches);
@@ -389,7 +397,7 @@ impl RenderHtml for AnyView {
         buf: &mut StreamBuilder,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) where
         Self: Sized,
     {
@@ -3,7 +3,9 @@ use super::{
     Render, RenderHtml,
 };
 use crate::{
-    html::attribute::Attribute, hydration::Cursor, ssr::StreamBuilder,
+    html::attribute::Attribute,
+    hydration::Cursor,
+    ssr::{BranchMarks, StreamBuilder},
 };
 use either_of::*;
 use futures::future::join;
@@ -150,26 +152,20 @@ where
         buf: &mut String,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) {
         match self {
             Either::Left(left) => {
-                if mark_branches {
-                    buf.open_branch("0");
-                }
+                let mark_branches = &mark_branches.enter("0");
+                buf.open_branch(mark_branches);
                 left.to_html_with_buf(buf, position, escape, mark_branches);
-                if mark_branches {
-                    buf.close_branch("0");
-                }
+                buf.close_branch(mark_branches);
             }
             Either::Right(right) => {
-                if mark_branches {
-                    buf.open_branch("1");
-                }
+                let mark_branches = &mark_branches.enter("1");
+                buf.open_branch(mark_branches);
                 right.to_html_with_buf(buf, position, escape, mark_branches);
-                if mark_branches {
-                    buf.close_branch("1");
-                }
+                buf.close_branch(mark_branches);
             }
         }
     }
@@ -179,38 +175,32 @@ where
         buf: &mut StreamBuilder,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) where
         Self: Sized,
     {
         match self {
             Either::Left(left) => {
-                if mark_branches {
-                    buf.open_branch("0");
-                }
+                let mark_branches = &mark_branches.enter("0");
+                buf.open_branch(mark_branches);
                 left.to_html_async_with_buf::<OUT_OF_ORDER>(
                     buf,
                     position,
                     escape,
                     mark_branches,
                 );
-                if mark_branches {
-                    buf.close_branch("0");
-                }
+                buf.close_branch(mark_branches);
             }
             Either::Right(right) => {
-                if mark_branches {
-                    buf.open_branch("1");
-                }
+                let mark_branches = &mark_branches.enter("1");
+                buf.open_branch(mark_branches);
                 right.to_html_async_with_buf::<OUT_OF_ORDER>(
                     buf,
                     position,
                     escape,
                     mark_branches,
                 );
-                if mark_branches {
-                    buf.close_branch("1");
-                }
+                buf.close_branch(mark_branches);
             }
         }
     }
@@ -222,16 +212,26 @@ where
     ) -> Self::State {
         match self {
             Either::Left(left) => {
-                Either::Left(left.hydrate::<FROM_SERVER>(cursor, position))
+                cursor.open_branch("0", position);
+                let state = left.hydrate::<FROM_SERVER>(cursor, position);
+                cursor.close_branch(position);
+                Either::Left(state)
             }
             Either::Right(right) => {
-                Either::Right(right.hydrate::<FROM_SERVER>(cursor, position))
+                cursor.open_branch("1", position);
+                let state = right.hydrate::<FROM_SERVER>(cursor, position);
+                cursor.close_branch(position);
+                Either::Right(state)
             }
         }
     }
 }
 
-/// Stores each value in the view state, overwriting it only if `Some(_)` is provided.
+/// Switches between two views, keeping the hidden one alive.
+///
+/// Unlike [`Either`], switching branches unmounts the hidden branch rather than dropping it, so
+/// its DOM and reactive state are preserved and simply remounted when it is shown again. Each
+/// value is stored in the view state, overwriting it only if `Some(_)` is provided.
 pub struct EitherKeepAlive<A, B> {
     /// The first possibility.
     pub a: Option<A>,
@@ -242,58 +242,106 @@ pub struct EitherKeepAlive<A, B> {
 }
 
 /// Retained view state for [`EitherKeepAlive`].
-pub struct EitherKeepAliveState<A, B> {
-    a: Option<A>,
-    b: Option<B>,
+///
+/// Each branch is only built the first time it is shown. Until then, the view provided for it is
+/// held as-is, and once built its state is kept while the other branch is shown.
+pub struct EitherKeepAliveState<A, B>
+where
+    A: Render,
+    B: Render,
+{
+    a: KeepAlive<A>,
+    b: KeepAlive<B>,
     showing_b: bool,
 }
 
+enum KeepAlive<V: Render> {
+    Empty,
+    Pending(V),
+    Built(V::State),
+}
+
+impl<V: Render> KeepAlive<V> {
+    fn new(view: Option<V>, active: bool) -> Self {
+        match view {
+            Some(view) if active => KeepAlive::Built(view.build()),
+            Some(view) => KeepAlive::Pending(view),
+            None => KeepAlive::Empty,
+        }
+    }
+
+    fn update(&mut self, view: Option<V>) {
+        // `None` just means "no change"
+        match (view, self) {
+            (Some(view), KeepAlive::Built(state)) => view.rebuild(state),
+            (Some(view), this) => *this = KeepAlive::Pending(view),
+            (None, _) => {}
+        }
+    }
+
+    fn activate(&mut self) -> Option<&mut V::State> {
+        if matches!(self, KeepAlive::Pending(_)) {
+            if let KeepAlive::Pending(view) =
+                std::mem::replace(self, KeepAlive::Empty)
+            {
+                *self = KeepAlive::Built(view.build());
+            }
+        }
+        self.state_mut()
+    }
+
+    fn state(&self) -> Option<&V::State> {
+        match self {
+            KeepAlive::Built(state) => Some(state),
+            _ => None,
+        }
+    }
+
+    fn state_mut(&mut self) -> Option<&mut V::State> {
+        match self {
+            KeepAlive::Built(state) => Some(state),
+            _ => None,
+        }
+    }
+}
+
 impl<A, B> Render for EitherKeepAlive<A, B>
 where
     A: Render,
     B: Render,
 {
-    type State = EitherKeepAliveState<A::State, B::State>;
+    type State = EitherKeepAliveState<A, B>;
 
     fn build(self) -> Self::State {
         let showing_b = self.show_b;
-        let a = self.a.map(Render::build);
-        let b = self.b.map(Render::build);
-        EitherKeepAliveState { a, b, showing_b }
+        EitherKeepAliveState {
+            a: KeepAlive::new(self.a, !showing_b),
+            b: KeepAlive::new(self.b, showing_b),
+            showing_b,
+        }
     }
 
     fn rebuild(self, state: &mut Self::State) {
-        // set or update A -- `None` just means "no change"
-        match (self.a, &mut state.a) {
-            (Some(new), Some(old)) => new.rebuild(old),
-            (Some(new), None) => state.a = Some(new.build()),
-            _ => {}
-        }
-
-        // set or update B
-        match (self.b, &mut state.b) {
-            (Some(new), Some(old)) => new.rebuild(old),
-            (Some(new), None) => state.b = Some(new.build()),
-            _ => {}
-        }
+        state.a.update(self.a);
+        state.b.update(self.b);
 
         match (self.show_b, state.showing_b) {
             // transition from A to B
-            (true, false) => match (&mut state.a, &mut state.b) {
-                (Some(a), Some(b)) => {
+            (true, false) => {
+                let b = state.b.activate().expect("B was not present");
+                if let Some(a) = state.a.state_mut() {
                     a.insert_before_this(b);
                     a.unmount();
                 }
-                _ => unreachable!(),
-            },
+            }
             // transition from B to A
-            (false, true) => match (&mut state.a, &mut state.b) {
-                (Some(a), Some(b)) => {
+            (false, true) => {
+                let a = state.a.activate().expect("A was not present");
+                if let Some(b) = state.b.state_mut() {
                     b.insert_before_this(a);
                     b.unmount();
                 }
-                _ => unreachable!(),
-            },
+            }
             _ => {}
         }
         state.showing_b = self.show_b;
@@ -370,7 +418,7 @@ where
         buf: &mut String,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) {
         if self.show_b {
             self.b
@@ -388,7 +436,7 @@ where
         buf: &mut StreamBuilder,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) where
         Self: Sized,
     {
@@ -418,36 +466,39 @@ where
         cursor: &Cursor,
         position: &PositionState,
     ) -> Self::State {
+        // only the active branch was rendered on the server; the other one is built if and when
+        // it is first shown
         let showing_b = self.show_b;
-        let a = self.a.map(|a| {
-            if showing_b {
-                a.build()
-            } else {
-                a.hydrate::<FROM_SERVER>(cursor, position)
-            }
-        });
-        let b = self.b.map(|b| {
-            if showing_b {
-                b.hydrate::<FROM_SERVER>(cursor, position)
-            } else {
-                b.build()
-            }
-        });
+        let (a, b) = if showing_b {
+            (
+                KeepAlive::new(self.a, false),
+                self.b.map_or(KeepAlive::Empty, |b| {
+                    KeepAlive::Built(b.hydrate::<FROM_SERVER>(cursor, position))
+                }),
+            )
+        } else {
+            (
+                self.a.map_or(KeepAlive::Empty, |a| {
+                    KeepAlive::Built(a.hydrate::<FROM_SERVER>(cursor, position))
+                }),
+                KeepAlive::new(self.b, false),
+            )
+        };
 
-        EitherKeepAliveState { showing_b, a, b }
+        EitherKeepAliveState { a, b, showing_b }
     }
 }
 
 impl<A, B> Mountable for EitherKeepAliveState<A, B>
 where
-    A: Mountable,
-    B: Mountable,
+    A: Render,
+    B: Render,
 {
     fn unmount(&mut self) {
         if self.showing_b {
-            self.b.as_mut().expect("B was not present").unmount();
+            self.b.state_mut().expect("B was not present").unmount();
         } else {
-            self.a.as_mut().expect("A was not present").unmount();
+            self.a.state_mut().expect("A was not present").unmount();
         }
     }
 
@@ -458,12 +509,12 @@ where
     ) {
         if self.showing_b {
             self.b
-                .as_mut()
+                .activate()
                 .expect("B was not present")
                 .mount(parent, marker);
         } else {
             self.a
-                .as_mut()
+                .activate()
                 .expect("A was not present")
                 .mount(parent, marker);
         }
@@ -472,12 +523,12 @@ where
     fn insert_before_this(&self, child: &mut dyn Mountable) -> bool {
         if self.showing_b {
             self.b
-                .as_ref()
+                .state()
                 .expect("B was not present")
                 .insert_before_this(child)
         } else {
             self.a
-                .as_ref()
+                .state()
                 .expect("A was not present")
                 .insert_before_this(child)
         }
@@ -617,34 +668,28 @@ macro_rules! tuples {
                     }
                 }
 
-                fn to_html_with_buf(self, buf: &mut String, position: &mut Position, escape: bool, mark_branches: bool) {
+                fn to_html_with_buf(self, buf: &mut String, position: &mut Position, escape: bool, mark_branches: &BranchMarks) {
                     match self {
                         $([<EitherOf $num>]::$ty(this) => {
-                            if mark_branches {
-                                buf.open_branch(stringify!($ty));
-                            }
+                            let mark_branches = &mark_branches.enter(stringify!($ty));
+                            buf.open_branch(mark_branches);
                             this.to_html_with_buf(buf, position, escape, mark_branches);
-                            if mark_branches {
-                                buf.close_branch(stringify!($ty));
-                            }
+                            buf.close_branch(mark_branches);
                         })*
                     }
                 }
 
                 fn to_html_async_with_buf<const OUT_OF_ORDER: bool>(
                     self,
-                    buf: &mut StreamBuilder, position: &mut Position, escape: bool, mark_branches: bool) where
+                    buf: &mut StreamBuilder, position: &mut Position, escape: bool, mark_branches: &BranchMarks) where
                     Self: Sized,
                 {
                     match self {
                         $([<EitherOf $num>]::$ty(this) => {
-                            if mark_branches {
-                                buf.open_branch(stringify!($ty));
-                            }
+                            let mark_branches = &mark_branches.enter(stringify!($ty));
+                            buf.open_branch(mark_branches);
                             this.to_html_async_with_buf::<OUT_OF_ORDER>(buf, position, escape, mark_branches);
-                            if mark_branches {
-                                buf.close_branch(stringify!($ty));
-                            }
+                            buf.close_branch(mark_branches);
                         })*
                     }
                 }
@@ -656,7 +701,10 @@ macro_rules! tuples {
                 ) -> Self::State {
                     let state = match self {
                         $([<EitherOf $num>]::$ty(this) => {
-                            [<EitherOf $num>]::$ty(this.hydrate::<FROM_SERVER>(cursor, position))
+                            cursor.open_branch(stringify!($ty), position);
+                            let state = this.hydrate::<FROM_SERVER>(cursor, position);
+                            cursor.close_branch(position);
+                            [<EitherOf $num>]::$ty(state)
                         })*
                     };
 
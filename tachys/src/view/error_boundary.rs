@@ -2,7 +2,7 @@ use super::{add_attr::AddAnyAttr, Position, PositionState, RenderHtml};
 use crate::{
     html::attribute::Attribute,
     hydration::Cursor,
-    ssr::StreamBuilder,
+    ssr::{BranchMarks, StreamBuilder},
     view::{iterators::OptionState, Mountable, Render},
 };
 use either_of::Either;
@@ -31,9 +31,12 @@ where
     fn rebuild(self, state: &mut Self::State) {
         let _guard = state.hook.clone().map(throw_error::set_error_hook);
         match (&mut state.state, self) {
-            // both errors: throw the new error and replace
+            // both errors: replace the old error, keeping its id where possible
             (Either::Right(_), Err(new)) => {
-                state.error = Some(throw_error::throw(new.into()))
+                state.error = Some(match &state.error {
+                    Some(id) => throw_error::rethrow(id, new.into()),
+                    None => throw_error::throw(new.into()),
+                })
             }
             // both Ok: need to rebuild child
             (Either::Left(old), Ok(new)) => {
@@ -160,7 +163,7 @@ where
         buf: &mut String,
         position: &mut super::Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) {
         match self {
             Ok(inner) => {
@@ -178,7 +181,7 @@ where
         buf: &mut StreamBuilder,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) where
         Self: Sized,
     {
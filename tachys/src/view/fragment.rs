@@ -1,6 +1,19 @@
-use super::any_view::{AnyView, IntoAny};
+use super::{
+    add_attr::AddAnyAttr,
+    any_view::{AnyView, AnyViewState, IntoAny},
+    iterators::VecState,
+    Position, PositionState, Render, RenderHtml,
+};
+use crate::{
+    html::attribute::Attribute,
+    hydration::Cursor,
+    ssr::{BranchMarks, StreamBuilder},
+};
 
 /// A typed-erased collection of different views.
+///
+/// Unlike a tuple, a fragment can hold any number of children, collected at runtime. It renders
+/// like a `Vec<AnyView>`: each child keeps its own state, followed by a single placeholder.
 pub struct Fragment {
     /// The nodes contained in the fragment.
     pub nodes: Vec<AnyView>,
@@ -38,6 +51,86 @@ impl Fragment {
     }
 }
 
+impl Render for Fragment {
+    type State = VecState<AnyViewState>;
+
+    fn build(self) -> Self::State {
+        self.nodes.build()
+    }
+
+    fn rebuild(self, state: &mut Self::State) {
+        self.nodes.rebuild(state)
+    }
+}
+
+impl AddAnyAttr for Fragment {
+    type Output<SomeNewAttr: Attribute> = Fragment;
+
+    fn add_any_attr<NewAttr: Attribute>(
+        self,
+        attr: NewAttr,
+    ) -> Self::Output<NewAttr>
+    where
+        Self::Output<NewAttr>: RenderHtml,
+    {
+        Fragment::new(self.nodes.add_any_attr(attr))
+    }
+}
+
+impl RenderHtml for Fragment {
+    type AsyncOutput = Fragment;
+
+    const MIN_LENGTH: usize = 0;
+
+    fn dry_resolve(&mut self) {
+        self.nodes.dry_resolve();
+    }
+
+    async fn resolve(self) -> Self::AsyncOutput {
+        Fragment::new(self.nodes.resolve().await)
+    }
+
+    fn html_len(&self) -> usize {
+        self.nodes.html_len()
+    }
+
+    fn to_html_with_buf(
+        self,
+        buf: &mut String,
+        position: &mut Position,
+        escape: bool,
+        mark_branches: &BranchMarks,
+    ) {
+        self.nodes
+            .to_html_with_buf(buf, position, escape, mark_branches);
+    }
+
+    fn to_html_async_with_buf<const OUT_OF_ORDER: bool>(
+        self,
+        buf: &mut StreamBuilder,
+        position: &mut Position,
+        escape: bool,
+        mark_branches: &BranchMarks,
+    ) where
+        Self: Sized,
+    {
+        self.nodes.to_html_async_with_buf::<OUT_OF_ORDER>(
+            buf,
+            position,
+            escape,
+            mark_branches,
+        );
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        cursor: &Cursor,
+        position: &PositionState,
+    ) -> Self::State {
+        self.nodes.hydrate::<FROM_SERVER>(cursor, position)
+    }
+}
+
 impl<T> IntoFragment for Vec<T>
 where
     T: IntoAny,
@@ -103,3 +196,27 @@ tuples!(
     A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y,
     Z
 );
+tuples!(
+    A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y,
+    Z, AA
+);
+tuples!(
+    A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y,
+    Z, AA, AB
+);
+tuples!(
+    A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y,
+    Z, AA, AB, AC
+);
+tuples!(
+    A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y,
+    Z, AA, AB, AC, AD
+);
+tuples!(
+    A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y,
+    Z, AA, AB, AC, AD, AE
+);
+tuples!(
+    A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y,
+    Z, AA, AB, AC, AD, AE, AF
+);
@@ -1,13 +1,16 @@
 use super::{
-    add_attr::AddAnyAttr, Mountable, Position, PositionState, Render,
-    RenderHtml,
+    add_attr::AddAnyAttr, mount_children, Mountable, Position, PositionState,
+    Render, RenderHtml,
 };
 use crate::{
-    html::attribute::Attribute, hydration::Cursor, renderer::Rndr,
-    ssr::StreamBuilder,
+    html::attribute::Attribute,
+    hydration::Cursor,
+    renderer::Rndr,
+    ssr::{BranchMarks, StreamBuilder},
 };
 use either_of::Either;
-use itertools::Itertools;
+#[cfg(feature = "reactive_graph")]
+use reactive_graph::owner::Owner;
 
 /// Retained view state for an `Option`.
 pub type OptionState<T> = Either<<T as Render>::State, <() as Render>::State>;
@@ -86,7 +89,7 @@ where
         buf: &mut String,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) {
         match self {
             Some(value) => Either::Left(value),
@@ -100,7 +103,7 @@ where
         buf: &mut StreamBuilder,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) where
         Self: Sized,
     {
@@ -139,63 +142,156 @@ where
     fn build(self) -> Self::State {
         let marker = Rndr::create_placeholder();
         VecState {
-            states: self.into_iter().map(T::build).collect(),
+            states: self.into_iter().map(ItemState::build).collect(),
             marker,
+            pool: Vec::new(),
         }
     }
 
     fn rebuild(self, state: &mut Self::State) {
-        let VecState { states, marker } = state;
-        let old = states;
+        let VecState {
+            states,
+            marker,
+            pool,
+        } = state;
         // this is an unkeyed diff
-        if old.is_empty() {
-            let mut new = self.build().states;
-            for item in new.iter_mut() {
-                Rndr::mount_before(item, marker.as_ref());
+        let new_len = self.len();
+        let mut items = self.into_iter();
+        for (old, new) in states.iter_mut().zip(items.by_ref()) {
+            old.rebuild(new);
+        }
+
+        // keep the states of removed items around, so that they can be reused if the list grows
+        // back again; the pool is a stack, so the item nearest the end of the list is reused first
+        if states.len() > new_len {
+            for mut old in states.drain(new_len..).rev() {
+                old.unmount();
+                old.pause();
+                pool.push(old);
             }
-            *old = new;
-        } else if self.is_empty() {
-            // TODO fast path for clearing
-            for item in old.iter_mut() {
-                item.unmount();
+            if pool.len() > VEC_STATE_POOL_SIZE {
+                pool.drain(..pool.len() - VEC_STATE_POOL_SIZE);
             }
-            old.clear();
-        } else {
-            let mut adds = vec![];
-            let mut removes_at_end = 0;
-            for item in self.into_iter().zip_longest(old.iter_mut()) {
-                match item {
-                    itertools::EitherOrBoth::Both(new, old) => {
-                        T::rebuild(new, old)
-                    }
-                    itertools::EitherOrBoth::Left(new) => {
-                        let mut new_state = new.build();
-                        Rndr::mount_before(&mut new_state, marker.as_ref());
-                        adds.push(new_state);
-                    }
-                    itertools::EitherOrBoth::Right(old) => {
-                        removes_at_end += 1;
-                        old.unmount()
-                    }
+        }
+
+        for new in items {
+            let mut new_state = match pool.pop() {
+                Some(mut pooled) => {
+                    pooled.resume();
+                    pooled.rebuild(new);
+                    pooled
                 }
-            }
-            old.truncate(old.len() - removes_at_end);
-            old.append(&mut adds);
+                None => ItemState::build(new),
+            };
+            Rndr::mount_before(&mut new_state, marker.as_ref());
+            states.push(new_state);
         }
     }
 }
 
+/// The maximum number of unmounted item states that a `Vec<_>` keeps around after shrinking.
+const VEC_STATE_POOL_SIZE: usize = 16;
+
 /// Retained view state for a `Vec<_>`.
 pub struct VecState<T>
 where
     T: Mountable,
 {
-    states: Vec<T>,
+    states: Vec<ItemState<T>>,
     // Vecs keep a placeholder because they have the potential to add additional items,
     // after their own items but before the next neighbor. It is much easier to add an
     // item before a known placeholder than to add it after the last known item, so we
     // just leave a placeholder here unlike zero-or-one iterators (Option, Result, etc.)
     marker: crate::renderer::types::Placeholder,
+    // unmounted states of items that were removed from the end of the list
+    pool: Vec<ItemState<T>>,
+}
+
+// the state of a single item of a `Vec<_>`
+//
+// each item is rendered with its own reactive owner, so that the render effects of an item that
+// has been removed can be paused while its state is kept in the pool
+struct ItemState<T> {
+    state: T,
+    #[cfg(feature = "reactive_graph")]
+    owner: Owner,
+}
+
+impl<T> ItemState<T>
+where
+    T: Mountable,
+{
+    fn build<V>(view: V) -> Self
+    where
+        V: Render<State = T>,
+    {
+        Self::new(|| view.build())
+    }
+
+    fn hydrate<const FROM_SERVER: bool, V>(
+        view: V,
+        cursor: &Cursor,
+        position: &PositionState,
+    ) -> Self
+    where
+        V: RenderHtml<State = T>,
+    {
+        Self::new(|| view.hydrate::<FROM_SERVER>(cursor, position))
+    }
+
+    fn new(state: impl FnOnce() -> T) -> Self {
+        #[cfg(feature = "reactive_graph")]
+        {
+            let owner = Owner::new();
+            let state = owner.with(state);
+            Self { state, owner }
+        }
+        #[cfg(not(feature = "reactive_graph"))]
+        {
+            Self { state: state() }
+        }
+    }
+
+    fn rebuild<V>(&mut self, view: V)
+    where
+        V: Render<State = T>,
+    {
+        #[cfg(feature = "reactive_graph")]
+        self.owner.with(|| view.rebuild(&mut self.state));
+        #[cfg(not(feature = "reactive_graph"))]
+        view.rebuild(&mut self.state);
+    }
+
+    fn pause(&self) {
+        #[cfg(feature = "reactive_graph")]
+        self.owner.pause();
+    }
+
+    fn resume(&self) {
+        #[cfg(feature = "reactive_graph")]
+        self.owner.resume();
+    }
+}
+
+impl<T> Mountable for ItemState<T>
+where
+    T: Mountable,
+{
+    fn unmount(&mut self) {
+        self.state.unmount();
+    }
+
+    fn mount(
+        &mut self,
+        parent: &crate::renderer::types::Element,
+        marker: Option<&crate::renderer::types::Node>,
+    ) {
+        self.state.mount(parent, marker);
+    }
+
+    fn insert_before_this(&self, child: &mut dyn Mountable) -> bool {
+        self.state.insert_before_this(child)
+    }
 }
 
 impl<T> Mountable for VecState<T>
@@ -214,18 +310,21 @@ where
         parent: &crate::renderer::types::Element,
         marker: Option<&crate::renderer::types::Node>,
     ) {
-        for state in self.states.iter_mut() {
-            state.mount(parent, marker);
-        }
-        self.marker.mount(parent, marker);
+        mount_children(self.states.len(), parent, marker, |parent, marker| {
+            for state in self.states.iter_mut() {
+                state.mount(parent, marker);
+            }
+            self.marker.mount(parent, marker);
+        });
     }
 
     fn insert_before_this(&self, child: &mut dyn Mountable) -> bool {
-        if let Some(first) = self.states.first() {
-            first.insert_before_this(child)
-        } else {
-            false
-        }
+        // an item may itself be empty (for example, an empty inner Vec), so look for the first
+        // one that can actually place the child, falling back to the placeholder
+        self.states
+            .iter()
+            .any(|state| state.insert_before_this(child))
+            || self.marker.insert_before_this(child)
     }
 }
 
@@ -280,7 +379,7 @@ where
         buf: &mut String,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) {
         let mut children = self.into_iter();
         if let Some(first) = children.next() {
@@ -297,7 +396,7 @@ where
         buf: &mut StreamBuilder,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) where
         Self: Sized,
     {
@@ -328,11 +427,17 @@ where
     ) -> Self::State {
         let states = self
             .into_iter()
-            .map(|child| child.hydrate::<FROM_SERVER>(cursor, position))
+            .map(|child| {
+                ItemState::hydrate::<FROM_SERVER, _>(child, cursor, position)
+            })
             .collect();
 
         let marker = cursor.next_placeholder(position);
 
-        VecState { states, marker }
+        VecState {
+            states,
+            marker,
+            pool: Vec::new(),
+        }
     }
 }
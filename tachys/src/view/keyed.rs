@@ -1,12 +1,12 @@
 use super::{
-    add_attr::AddAnyAttr, Mountable, Position, PositionState, Render,
-    RenderHtml,
+    add_attr::AddAnyAttr, mount_children, Mountable, Position, PositionState,
+    Render, RenderHtml,
 };
 use crate::{
     html::attribute::Attribute,
     hydration::Cursor,
     renderer::{CastFrom, Rndr},
-    ssr::StreamBuilder,
+    ssr::{BranchMarks, StreamBuilder},
 };
 use drain_filter_polyfill::VecExt as VecDrainFilterExt;
 use indexmap::IndexSet;
@@ -114,6 +114,11 @@ where
 
         let cmds = diff(hashed_items, &new_hashed_items);
 
+        // the list may have been mounted into a fragment that has since been inserted into the
+        // document, in which case the marker knows its real parent
+        let parent = Rndr::get_parent(marker.as_ref())
+            .and_then(crate::renderer::types::Element::cast_from)
+            .or_else(|| parent.clone());
         apply_diff(
             parent
                 .as_ref()
@@ -182,6 +187,42 @@ where
     }
 }
 
+// renders the rest of a keyed list once the stream is polled for it, deferring again whenever
+// the stream has buffered enough
+fn render_deferred<V, const OUT_OF_ORDER: bool>(
+    buf: &mut StreamBuilder,
+    views: Vec<V>,
+    escape: bool,
+    mark_branches: &BranchMarks,
+) where
+    V: RenderHtml + 'static,
+{
+    let mark_branches = mark_branches.clone();
+    buf.defer(move |buf| {
+        let mut views = views.into_iter();
+        let mut position = Position::NextChild;
+        for view in views.by_ref() {
+            view.to_html_async_with_buf::<OUT_OF_ORDER>(
+                buf,
+                &mut position,
+                escape,
+                &mark_branches,
+            );
+            position = Position::NextChild;
+            if buf.should_defer() {
+                render_deferred::<V, OUT_OF_ORDER>(
+                    buf,
+                    views.collect(),
+                    escape,
+                    &mark_branches,
+                );
+                return;
+            }
+        }
+        buf.push_sync("<!>");
+    });
+}
+
 impl<T, I, K, KF, VF, VFS, V> RenderHtml for Keyed<T, I, K, KF, VF, VFS, V>
 where
     I: IntoIterator<Item = T> + Send,
@@ -217,7 +258,7 @@ where
         buf: &mut String,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) {
         for (index, item) in self.items.into_iter().enumerate() {
             let (_, item) = (self.view_fn)(index, item);
@@ -232,9 +273,10 @@ where
         buf: &mut StreamBuilder,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) {
-        for (index, item) in self.items.into_iter().enumerate() {
+        let mut items = self.items.into_iter().enumerate();
+        for (index, item) in items.by_ref() {
             let (_, item) = (self.view_fn)(index, item);
             item.to_html_async_with_buf::<OUT_OF_ORDER>(
                 buf,
@@ -243,6 +285,20 @@ where
                 mark_branches,
             );
             *position = Position::NextChild;
+            // once the stream has buffered enough, the rest of the items are only rendered when
+            // it's polled for them
+            if buf.should_defer() {
+                let rest = items
+                    .map(|(index, item)| (self.view_fn)(index, item).1)
+                    .collect::<Vec<_>>();
+                render_deferred::<V, OUT_OF_ORDER>(
+                    buf,
+                    rest,
+                    escape,
+                    mark_branches,
+                );
+                return;
+            }
         }
         buf.push_sync("<!>");
     }
@@ -297,10 +353,13 @@ where
         marker: Option<&crate::renderer::types::Node>,
     ) {
         self.parent = Some(parent.clone());
-        for (_, item) in self.rendered_items.iter_mut().flatten() {
-            item.mount(parent, marker);
-        }
-        self.marker.mount(parent, marker);
+        let count = self.rendered_items.len();
+        mount_children(count, parent, marker, |parent, marker| {
+            for (_, item) in self.rendered_items.iter_mut().flatten() {
+                item.mount(parent, marker);
+            }
+            self.marker.mount(parent, marker);
+        });
     }
 
     fn unmount(&mut self) {
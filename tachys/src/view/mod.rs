@@ -1,5 +1,12 @@
 use self::add_attr::AddAnyAttr;
-use crate::{hydration::Cursor, ssr::StreamBuilder};
+use crate::{
+    hydration::Cursor,
+    renderer::Rndr,
+    ssr::{
+        BranchMarks, ChunkContext, HtmlTransformer, StreamBuilder,
+        StreamingStrategy,
+    },
+};
 use parking_lot::RwLock;
 use std::{cell::RefCell, future::Future, rc::Rc, sync::Arc};
 
@@ -18,13 +25,19 @@ pub mod iterators;
 /// Keyed list iteration.
 pub mod keyed;
 mod primitives;
+/// Pre-rendered HTML that is inserted as it is.
+pub mod raw_html;
 /// Optimized types for static strings known at compile time.
 #[cfg(feature = "nightly")]
 pub mod static_types;
+/// Views that are known not to change once rendered.
+pub mod static_view;
 /// View implementation for string types.
 pub mod strings;
 /// Optimizations for creating views via HTML `<template>` nodes.
 pub mod template;
+/// Helpers for testing custom view types.
+pub mod testing;
 /// View implementations for tuples.
 pub mod tuples;
 
@@ -46,37 +59,60 @@ pub trait Render: Sized {
     fn rebuild(self, state: &mut Self::State);
 }
 
+/// The number of children above which a container mounts them into a detached fragment, and
+/// inserts that into the document once, rather than inserting each child into the document.
+pub(crate) const FRAGMENT_THRESHOLD: usize = 16;
+
+// mounts `count` children with `mount_children`, going through a fragment if there are many
+pub(crate) fn mount_children(
+    count: usize,
+    parent: &crate::renderer::types::Element,
+    marker: Option<&crate::renderer::types::Node>,
+    mount: impl FnOnce(
+        &crate::renderer::types::Element,
+        Option<&crate::renderer::types::Node>,
+    ),
+) {
+    if count > FRAGMENT_THRESHOLD {
+        let fragment = Rndr::create_fragment();
+        mount(&fragment, None);
+        Rndr::append_fragment(parent, &fragment, marker);
+    } else {
+        mount(parent, marker);
+    }
+}
+
 pub(crate) trait MarkBranch {
-    fn open_branch(&mut self, branch_id: &str);
+    fn open_branch(&mut self, branch: &BranchMarks);
 
-    fn close_branch(&mut self, branch_id: &str);
+    fn close_branch(&mut self, branch: &BranchMarks);
 }
 
 impl MarkBranch for String {
-    fn open_branch(&mut self, branch_id: &str) {
-        self.push_str("<!--bo-");
-        self.push_str(branch_id);
-        self.push_str("-->");
+    fn open_branch(&mut self, branch: &BranchMarks) {
+        if branch.is_enabled() {
+            self.push_str("<!--bo-");
+            self.push_str(&branch.id());
+            self.push_str("-->");
+        }
     }
 
-    fn close_branch(&mut self, branch_id: &str) {
-        self.push_str("<!--bc-");
-        self.push_str(branch_id);
-        self.push_str("-->");
+    fn close_branch(&mut self, branch: &BranchMarks) {
+        if branch.is_enabled() {
+            self.push_str("<!--bc-");
+            self.push_str(&branch.id());
+            self.push_str("-->");
+        }
     }
 }
 
 impl MarkBranch for StreamBuilder {
-    fn open_branch(&mut self, branch_id: &str) {
-        self.sync_buf.push_str("<!--bo-");
-        self.sync_buf.push_str(branch_id);
-        self.sync_buf.push_str("-->");
+    fn open_branch(&mut self, branch: &BranchMarks) {
+        self.sync_buf.open_branch(branch);
     }
 
-    fn close_branch(&mut self, branch_id: &str) {
-        self.sync_buf.push_str("<!--bc-");
-        self.sync_buf.push_str(branch_id);
-        self.sync_buf.push_str("-->");
+    fn close_branch(&mut self, branch: &BranchMarks) {
+        self.sync_buf.close_branch(branch);
     }
 }
 
@@ -128,7 +164,44 @@ where
         Self: Sized,
     {
         let mut buf = String::with_capacity(self.html_len());
-        self.to_html_with_buf(&mut buf, &mut Position::FirstChild, true, false);
+        self.to_html_with_buf(
+            &mut buf,
+            &mut Position::FirstChild,
+            true,
+            &BranchMarks::NONE,
+        );
+        buf
+    }
+
+    /// Renders a view to an HTML string, allocating the given capacity up front instead of
+    /// estimating it with [`html_len`](RenderHtml::html_len).
+    ///
+    /// This is useful when the size of the page is known, for example from rendering it before,
+    /// or when the estimate is poor because much of the page is reactive.
+    fn to_html_with_capacity_hint(self, capacity_hint: usize) -> String
+    where
+        Self: Sized,
+    {
+        let mut buf = String::with_capacity(capacity_hint);
+        self.to_html_with_buf(
+            &mut buf,
+            &mut Position::FirstChild,
+            true,
+            &BranchMarks::NONE,
+        );
+        buf
+    }
+
+    /// Renders a view to an HTML string, and rewrites it with the given transformer.
+    fn to_html_with_transformer(
+        self,
+        mut transformer: impl HtmlTransformer,
+    ) -> String
+    where
+        Self: Sized,
+    {
+        let mut buf = self.to_html();
+        transformer.transform_chunk(&mut buf, ChunkContext::new(0, false));
         buf
     }
 
@@ -136,15 +209,92 @@ where
     /// HTML pages against one another, by marking sections of the view that branch to different
     /// types with marker comments.
     fn to_html_branching(self) -> String
+    where
+        Self: Sized,
+    {
+        self.to_html_with_branch_marks(&BranchMarks::local())
+    }
+
+    /// Renders a view to HTML with the given branch markers.
+    ///
+    /// The HTML can be hydrated by a [`Cursor`] created
+    /// [`with_branch_marks`](Cursor::with_branch_marks) using the same marks.
+    fn to_html_with_branch_marks(self, mark_branches: &BranchMarks) -> String
     where
         Self: Sized,
     {
         let mut buf = String::with_capacity(self.html_len());
-        self.to_html_with_buf(&mut buf, &mut Position::FirstChild, true, true);
+        self.to_html_with_buf(
+            &mut buf,
+            &mut Position::FirstChild,
+            true,
+            mark_branches,
+        );
         buf
     }
 
+    /// Renders a view to a stream of HTML, using the given strategy for its async parts.
+    fn to_html_stream(self, strategy: StreamingStrategy) -> StreamBuilder
+    where
+        Self: Sized,
+    {
+        let mut builder =
+            StreamBuilder::for_strategy(strategy, self.html_len());
+        match strategy {
+            StreamingStrategy::InOrder => self.to_html_async_with_buf::<false>(
+                &mut builder,
+                &mut Position::FirstChild,
+                true,
+                &BranchMarks::NONE,
+            ),
+            StreamingStrategy::OutOfOrder => self
+                .to_html_async_with_buf::<true>(
+                    &mut builder,
+                    &mut Position::FirstChild,
+                    true,
+                    &BranchMarks::NONE,
+                ),
+        }
+        builder.finish()
+    }
+
+    /// Renders a view into the given stream builder, which has already been configured.
+    ///
+    /// The builder's [strategy](StreamBuilder::strategy) decides whether the view is streamed in
+    /// order or out of order. Unlike options set on the stream after the view has been rendered,
+    /// a limit set with [`with_max_buffered_bytes`](StreamBuilder::with_max_buffered_bytes)
+    /// applies while rendering, so that large views are not buffered all at once.
+    fn to_html_stream_with_builder(
+        self,
+        mut builder: StreamBuilder,
+        mark_branches: &BranchMarks,
+    ) -> StreamBuilder
+    where
+        Self: Sized,
+    {
+        builder.reserve(self.html_len());
+        match builder.strategy() {
+            StreamingStrategy::InOrder => self.to_html_async_with_buf::<false>(
+                &mut builder,
+                &mut Position::FirstChild,
+                true,
+                mark_branches,
+            ),
+            StreamingStrategy::OutOfOrder => self
+                .to_html_async_with_buf::<true>(
+                    &mut builder,
+                    &mut Position::FirstChild,
+                    true,
+                    mark_branches,
+                ),
+        }
+        builder.finish()
+    }
+
     /// Renders a view to an in-order stream of HTML.
+    ///
+    /// This is the same as [`to_html_stream`](RenderHtml::to_html_stream) with
+    /// [`StreamingStrategy::InOrder`].
     fn to_html_stream_in_order(self) -> StreamBuilder
     where
         Self: Sized,
@@ -154,7 +304,7 @@ where
             &mut builder,
             &mut Position::FirstChild,
             true,
-            false,
+            &BranchMarks::NONE,
         );
         builder.finish()
     }
@@ -171,7 +321,7 @@ where
             &mut builder,
             &mut Position::FirstChild,
             true,
-            true,
+            &BranchMarks::local(),
         );
         builder.finish()
     }
@@ -189,7 +339,7 @@ where
             &mut builder,
             &mut Position::FirstChild,
             true,
-            false,
+            &BranchMarks::NONE,
         );
         builder.finish()
     }
@@ -209,7 +359,7 @@ where
             &mut builder,
             &mut Position::FirstChild,
             true,
-            true,
+            &BranchMarks::local(),
         );
         builder.finish()
     }
@@ -220,7 +370,7 @@ where
         buf: &mut String,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     );
 
     /// Renders a view into a buffer of (synchronous or asynchronous) HTML chunks.
@@ -229,7 +379,7 @@ where
         buf: &mut StreamBuilder,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) where
         Self: Sized,
     {
@@ -305,6 +455,36 @@ pub trait Mountable {
             child.mount(parent, marker);
         }
     }
+
+    /// Returns the first DOM node of this view, if it is currently mounted.
+    ///
+    /// This can be used as an anchor to move other views relative to this one, by mounting them
+    /// before it. Views that do not exist in the UI (for example, `()`) return `None`.
+    fn first_node(&self) -> Option<crate::renderer::types::Node> {
+        // every view knows how to insert something before its first node, so ask it to insert a
+        // probe and record the node it would be inserted before
+        let mut probe = FirstNodeProbe(None);
+        self.insert_before_this(&mut probe);
+        probe.0
+    }
+}
+
+struct FirstNodeProbe(Option<crate::renderer::types::Node>);
+
+impl Mountable for FirstNodeProbe {
+    fn unmount(&mut self) {}
+
+    fn mount(
+        &mut self,
+        _parent: &crate::renderer::types::Element,
+        marker: Option<&crate::renderer::types::Node>,
+    ) {
+        self.0 = marker.cloned();
+    }
+
+    fn insert_before_this(&self, _child: &mut dyn Mountable) -> bool {
+        false
+    }
 }
 
 /// Indicates where a node should be mounted to its parent.
@@ -416,16 +596,32 @@ impl PositionState {
 }
 
 /// The position of this element, relative to others.
+///
+/// While rendering to HTML, each view reads the position to decide whether it needs to separate
+/// itself from the previous sibling, and then sets it to describe what it has just written, for
+/// the next sibling to read. While hydrating, the position tells a view where the [`Cursor`]
+/// needs to move to reach its own node: down into the first child, or across to the next sibling.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
 pub enum Position {
     /// This is the current node.
+    ///
+    /// When hydrating, the cursor is already on this view's node, so it should not move. This is
+    /// used when hydrating from a node that has already been located, like the root element.
     Current,
     /// This is the first child of its parent.
+    ///
+    /// When hydrating, the cursor is on the parent and should move to its first child.
     #[default]
     FirstChild,
     /// This is the next child after another child.
+    ///
+    /// When hydrating, the cursor is on the previous sibling and should move to the next one.
     NextChild,
     /// This is the next child after a text node.
+    ///
+    /// Adjacent text nodes are merged by the browser when parsing HTML, so a text node that
+    /// follows another one is separated from it by an empty comment (`<!>`), which has to be
+    /// written during SSR and skipped when hydrating.
     NextChildAfterText,
     /// This is the only child of its parent.
     OnlyChild,
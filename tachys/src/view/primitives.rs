@@ -1,8 +1,6 @@
 use super::{Mountable, Position, PositionState, Render, RenderHtml};
 use crate::{
-    hydration::Cursor,
-    no_attrs,
-    renderer::{CastFrom, Rndr},
+    hydration::Cursor, no_attrs, renderer::Rndr, ssr::BranchMarks,
     view::ToTemplate,
 };
 use std::{
@@ -74,7 +72,7 @@ macro_rules! render_primitive {
                     self
                 }
 
-				fn to_html_with_buf(self, buf: &mut String, position: &mut Position, _escape: bool, _mark_branches: bool) {
+				fn to_html_with_buf(self, buf: &mut String, position: &mut Position, _escape: bool, _mark_branches: &BranchMarks) {
 					// add a comment node to separate from previous sibling, if any
 					if matches!(position, Position::NextChildAfterText) {
 						buf.push_str("<!>")
@@ -88,20 +86,8 @@ macro_rules! render_primitive {
 					cursor: &Cursor,
 					position: &PositionState,
 				) -> Self::State {
-					if position.get() == Position::FirstChild {
-						cursor.child();
-					} else {
-						cursor.sibling();
-					}
-
-					// separating placeholder marker comes before text node
-					if matches!(position.get(), Position::NextChildAfterText) {
-						cursor.sibling();
-					}
-
-					let node = cursor.current();
-					let node = crate::renderer::types::Text::cast_from(node)
-						.expect("couldn't cast text node from node");
+					let text = FROM_SERVER.then(|| self.to_string());
+					let node = cursor.next_text_node(position, text.as_deref());
 
 					if !FROM_SERVER {
 						Rndr::set_text(&node, &self.to_string());
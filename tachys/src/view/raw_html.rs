@@ -0,0 +1,161 @@
+use super::{Mountable, Position, PositionState, Render, RenderHtml};
+use crate::{
+    hydration::{Cursor, ExpectedNode},
+    no_attrs,
+    renderer::{types, CastFrom, Rndr},
+    ssr::BranchMarks,
+};
+
+const OPEN_MARKER: &str = "raw";
+const CLOSE_MARKER: &str = "/raw";
+
+/// A fragment of HTML that has already been rendered, like the output of a Markdown renderer,
+/// which is inserted into the view as it is.
+///
+/// Unlike setting `inner_html` on an element, the fragment does not need a wrapping element, and
+/// can contain any number of nodes. On the server, it is written out verbatim between two marker
+/// comments, and hydration skips over everything between them, so the nodes it contains never
+/// have to match the view. On the client, it is parsed with a `<template>` element.
+///
+/// The HTML is not escaped or sanitized in any way, so it must come from a trusted source.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RawHtmlIsland(pub String);
+
+impl RawHtmlIsland {
+    /// Wraps a fragment of pre-rendered HTML.
+    pub fn new(html: impl Into<String>) -> Self {
+        Self(html.into())
+    }
+}
+
+no_attrs!(RawHtmlIsland);
+
+/// Retained view state for a [`RawHtmlIsland`].
+pub struct RawHtmlIslandState {
+    html: String,
+    start: types::Placeholder,
+    nodes: Vec<types::Node>,
+    end: types::Placeholder,
+}
+
+// parses the HTML into its top-level nodes
+fn parse_nodes(html: &str) -> Vec<types::Node> {
+    let fragment = Rndr::create_element_from_html(html);
+    let mut nodes = Vec::new();
+    let mut next = Rndr::first_child(fragment.as_ref());
+    while let Some(node) = next {
+        next = Rndr::next_sibling(&node);
+        nodes.push(node);
+    }
+    nodes
+}
+
+impl Mountable for RawHtmlIslandState {
+    fn unmount(&mut self) {
+        self.start.unmount();
+        for node in &self.nodes {
+            Rndr::remove(node);
+        }
+        self.end.unmount();
+    }
+
+    fn mount(&mut self, parent: &types::Element, marker: Option<&types::Node>) {
+        self.start.mount(parent, marker);
+        for node in &self.nodes {
+            Rndr::insert_node(parent, node, marker);
+        }
+        self.end.mount(parent, marker);
+    }
+
+    fn insert_before_this(&self, child: &mut dyn Mountable) -> bool {
+        self.start.insert_before_this(child)
+    }
+}
+
+impl Render for RawHtmlIsland {
+    type State = RawHtmlIslandState;
+
+    fn build(self) -> Self::State {
+        RawHtmlIslandState {
+            nodes: parse_nodes(&self.0),
+            html: self.0,
+            start: Rndr::create_placeholder(),
+            end: Rndr::create_placeholder(),
+        }
+    }
+
+    fn rebuild(self, state: &mut Self::State) {
+        if self.0 == state.html {
+            return;
+        }
+        let nodes = parse_nodes(&self.0);
+        if let Some(parent) = Rndr::get_parent(state.end.as_ref())
+            .and_then(types::Element::cast_from)
+        {
+            for node in &state.nodes {
+                Rndr::remove(node);
+            }
+            for node in &nodes {
+                Rndr::insert_node(&parent, node, Some(state.end.as_ref()));
+            }
+        }
+        state.nodes = nodes;
+        state.html = self.0;
+    }
+}
+
+impl RenderHtml for RawHtmlIsland {
+    type AsyncOutput = Self;
+
+    const MIN_LENGTH: usize = OPEN_MARKER.len() + CLOSE_MARKER.len() + 14;
+
+    fn dry_resolve(&mut self) {}
+
+    async fn resolve(self) -> Self::AsyncOutput {
+        self
+    }
+
+    fn html_len(&self) -> usize {
+        Self::MIN_LENGTH + self.0.len()
+    }
+
+    fn to_html_with_buf(
+        self,
+        buf: &mut String,
+        position: &mut Position,
+        _escape: bool,
+        _mark_branches: &BranchMarks,
+    ) {
+        buf.push_str("<!--");
+        buf.push_str(OPEN_MARKER);
+        buf.push_str("-->");
+        buf.push_str(&self.0);
+        buf.push_str("<!--");
+        buf.push_str(CLOSE_MARKER);
+        buf.push_str("-->");
+        *position = Position::NextChild;
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        cursor: &Cursor,
+        position: &PositionState,
+    ) -> Self::State {
+        if position.get() == Position::FirstChild {
+            cursor.child();
+        } else {
+            cursor.sibling();
+        }
+        let start = cursor.current_as(ExpectedNode::Placeholder);
+        let nodes = cursor.skip_to_closing_comment(OPEN_MARKER, CLOSE_MARKER);
+        let end = cursor.current_as(ExpectedNode::Placeholder);
+        position.set(Position::NextChild);
+
+        RawHtmlIslandState {
+            html: self.0,
+            start,
+            nodes,
+            end,
+        }
+    }
+}
@@ -6,6 +6,7 @@ use crate::{
     html::attribute::{Attribute, AttributeKey, AttributeValue, NextAttribute},
     hydration::Cursor,
     renderer::Rndr,
+    ssr::BranchMarks,
 };
 use std::marker::PhantomData;
 
@@ -92,6 +93,10 @@ where
 
     fn rebuild(self, _state: &mut Self::State) {}
 
+    fn remove(el: &crate::renderer::types::Element, _state: Self::State) {
+        Rndr::remove_attribute(el, K::KEY);
+    }
+
     fn into_cloneable(self) -> Self::Cloneable {
         self
     }
@@ -174,7 +179,7 @@ impl<const V: &'static str> RenderHtml for Static<V> {
         buf: &mut String,
         position: &mut Position,
         escape: bool,
-        _mark_branches: bool,
+        _mark_branches: &BranchMarks,
     ) {
         // add a comment node to separate from previous sibling, if any
         if matches!(position, Position::NextChildAfterText) {
@@ -0,0 +1,207 @@
+use super::{
+    add_attr::AddAnyAttr, Mountable, Position, PositionState, Render,
+    RenderHtml, ToTemplate,
+};
+use crate::{
+    html::attribute::Attribute,
+    hydration::Cursor,
+    ssr::{BranchMarks, StreamBuilder},
+};
+
+/// A view that is known not to change after it is first rendered.
+///
+/// Many children are constant once they are created, like a label computed from props or an
+/// icon, but still go through the usual rebuilding machinery. Wrapping them in a `StaticView`
+/// (usually with [`IntoStaticView::into_static`]) makes rebuilding a no-op. In debug builds, the
+/// previous value is kept so that rebuilding with a different value panics instead of being
+/// silently ignored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StaticView<T>(T);
+
+impl<T> StaticView<T> {
+    /// Wraps a view that will not change.
+    pub fn new(view: T) -> Self {
+        Self(view)
+    }
+
+    /// Returns the wrapped view.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+/// Marks a view as never changing, so that it can skip rebuilding.
+pub trait IntoStaticView: Sized {
+    /// Wraps the view in a [`StaticView`].
+    fn into_static(self) -> StaticView<Self>;
+}
+
+impl<T> IntoStaticView for T
+where
+    T: Render,
+{
+    fn into_static(self) -> StaticView<Self> {
+        StaticView(self)
+    }
+}
+
+/// Retained view state for a [`StaticView`].
+pub struct StaticViewState<T>
+where
+    T: Render,
+{
+    state: T::State,
+    #[cfg(debug_assertions)]
+    value: T,
+}
+
+impl<T> Mountable for StaticViewState<T>
+where
+    T: Render,
+{
+    fn unmount(&mut self) {
+        self.state.unmount();
+    }
+
+    fn mount(
+        &mut self,
+        parent: &crate::renderer::types::Element,
+        marker: Option<&crate::renderer::types::Node>,
+    ) {
+        self.state.mount(parent, marker);
+    }
+
+    fn insert_before_this(&self, child: &mut dyn Mountable) -> bool {
+        self.state.insert_before_this(child)
+    }
+}
+
+impl<T> StaticViewState<T>
+where
+    T: Render + PartialEq + Clone,
+{
+    fn new(value: T, state: impl FnOnce(T) -> T::State) -> Self {
+        Self {
+            #[cfg(debug_assertions)]
+            state: state(value.clone()),
+            #[cfg(not(debug_assertions))]
+            state: state(value),
+            #[cfg(debug_assertions)]
+            value,
+        }
+    }
+}
+
+impl<T> Render for StaticView<T>
+where
+    T: Render + PartialEq + Clone,
+{
+    type State = StaticViewState<T>;
+
+    fn build(self) -> Self::State {
+        StaticViewState::new(self.0, T::build)
+    }
+
+    fn rebuild(self, _state: &mut Self::State) {
+        #[cfg(debug_assertions)]
+        assert!(
+            self.0 == _state.value,
+            "a StaticView was rebuilt with a different value"
+        );
+    }
+}
+
+impl<T> AddAnyAttr for StaticView<T>
+where
+    T: RenderHtml + PartialEq + Clone,
+{
+    type Output<SomeNewAttr: Attribute> = T::Output<SomeNewAttr>;
+
+    fn add_any_attr<NewAttr: Attribute>(
+        self,
+        attr: NewAttr,
+    ) -> Self::Output<NewAttr>
+    where
+        Self::Output<NewAttr>: RenderHtml,
+    {
+        self.0.add_any_attr(attr)
+    }
+}
+
+impl<T> RenderHtml for StaticView<T>
+where
+    T: RenderHtml + PartialEq + Clone,
+{
+    type AsyncOutput = T::AsyncOutput;
+
+    const MIN_LENGTH: usize = T::MIN_LENGTH;
+
+    fn dry_resolve(&mut self) {
+        self.0.dry_resolve();
+    }
+
+    async fn resolve(self) -> Self::AsyncOutput {
+        self.0.resolve().await
+    }
+
+    fn html_len(&self) -> usize {
+        self.0.html_len()
+    }
+
+    fn to_html_with_buf(
+        self,
+        buf: &mut String,
+        position: &mut Position,
+        escape: bool,
+        mark_branches: &BranchMarks,
+    ) {
+        self.0
+            .to_html_with_buf(buf, position, escape, mark_branches);
+    }
+
+    fn to_html_async_with_buf<const OUT_OF_ORDER: bool>(
+        self,
+        buf: &mut StreamBuilder,
+        position: &mut Position,
+        escape: bool,
+        mark_branches: &BranchMarks,
+    ) where
+        Self: Sized,
+    {
+        // a static view never has anything to wait for, so it can always go straight into the
+        // synchronous part of the buffer
+        let mut html = String::with_capacity(self.html_len());
+        self.0
+            .to_html_with_buf(&mut html, position, escape, mark_branches);
+        buf.push_sync(&html);
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        cursor: &Cursor,
+        position: &PositionState,
+    ) -> Self::State {
+        StaticViewState::new(self.0, |value| {
+            value.hydrate::<FROM_SERVER>(cursor, position)
+        })
+    }
+}
+
+impl<T> ToTemplate for StaticView<T>
+where
+    T: ToTemplate,
+{
+    const TEMPLATE: &'static str = T::TEMPLATE;
+    const CLASS: &'static str = T::CLASS;
+    const STYLE: &'static str = T::STYLE;
+
+    fn to_template(
+        buf: &mut String,
+        class: &mut String,
+        style: &mut String,
+        inner_html: &mut String,
+        position: &mut Position,
+    ) {
+        T::to_template(buf, class, style, inner_html, position)
+    }
+}
@@ -1,11 +1,7 @@
 use super::{
     Mountable, Position, PositionState, Render, RenderHtml, ToTemplate,
 };
-use crate::{
-    hydration::Cursor,
-    no_attrs,
-    renderer::{CastFrom, Rndr},
-};
+use crate::{hydration::Cursor, no_attrs, renderer::Rndr, ssr::BranchMarks};
 use std::{borrow::Cow, rc::Rc, sync::Arc};
 
 no_attrs!(&'a str);
@@ -13,6 +9,21 @@ no_attrs!(String);
 no_attrs!(Arc<str>);
 no_attrs!(Cow<'a, str>);
 
+// the length of a text node once it has been escaped, plus room for a separator comment before
+// it, so that text with many characters to escape does not outgrow its estimate
+pub(crate) fn escaped_text_len(text: &str) -> usize {
+    let escaped = text
+        .bytes()
+        .map(|b| match b {
+            b'&' => 4,
+            b'<' | b'>' => 3,
+            _ => 0,
+        })
+        .sum::<usize>();
+    // empty text is rendered as a single space
+    text.len().max(1) + escaped + 3
+}
+
 /// Retained view state for `&str`.
 pub struct StrState<'a> {
     pub(crate) node: crate::renderer::types::Text,
@@ -48,7 +59,7 @@ impl<'a> RenderHtml for &'a str {
     }
 
     fn html_len(&self) -> usize {
-        self.len()
+        escaped_text_len(self)
     }
 
     fn to_html_with_buf(
@@ -56,7 +67,7 @@ impl<'a> RenderHtml for &'a str {
         buf: &mut String,
         position: &mut Position,
         escape: bool,
-        _mark_branches: bool,
+        _mark_branches: &BranchMarks,
     ) {
         // add a comment node to separate from previous sibling, if any
         if matches!(position, Position::NextChildAfterText) {
@@ -65,8 +76,7 @@ impl<'a> RenderHtml for &'a str {
         if self.is_empty() {
             buf.push(' ');
         } else if escape {
-            let escaped = html_escape::encode_text(self);
-            buf.push_str(&escaped);
+            html_escape::encode_text_to_string(self, buf);
         } else {
             buf.push_str(self);
         }
@@ -78,20 +88,7 @@ impl<'a> RenderHtml for &'a str {
         cursor: &Cursor,
         position: &PositionState,
     ) -> Self::State {
-        if position.get() == Position::FirstChild {
-            cursor.child();
-        } else {
-            cursor.sibling();
-        }
-
-        // separating placeholder marker comes before text node
-        if matches!(position.get(), Position::NextChildAfterText) {
-            cursor.sibling();
-        }
-
-        let node = cursor.current();
-        let node = crate::renderer::types::Text::cast_from(node)
-            .expect("couldn't cast text node from node");
+        let node = cursor.next_text_node(position, FROM_SERVER.then_some(self));
 
         if !FROM_SERVER {
             Rndr::set_text(&node, self);
@@ -172,7 +169,7 @@ impl RenderHtml for String {
     }
 
     fn html_len(&self) -> usize {
-        self.len()
+        escaped_text_len(self)
     }
 
     fn to_html_with_buf(
@@ -180,7 +177,7 @@ impl RenderHtml for String {
         buf: &mut String,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) {
         <&str as RenderHtml>::to_html_with_buf(
             self.as_str(),
@@ -275,10 +272,10 @@ where
     }
 
     fn html_len(&self) -> usize {
-        self.len()
+        escaped_text_len(self)
     }
 
-    fn to_html_with_buf(self, buf: &mut String, position: &mut Position, escape: bool, mark_branches: bool) {
+    fn to_html_with_buf(self, buf: &mut String, position: &mut Position, escape: bool, mark_branches: &BranchMarks) {
         <&str as RenderHtml>::to_html_with_buf(&self, buf, position)
     }
 
@@ -363,7 +360,7 @@ impl RenderHtml for Arc<str> {
     }
 
     fn html_len(&self) -> usize {
-        self.len()
+        escaped_text_len(self)
     }
 
     fn to_html_with_buf(
@@ -371,7 +368,7 @@ impl RenderHtml for Arc<str> {
         buf: &mut String,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) {
         <&str as RenderHtml>::to_html_with_buf(
             &self,
@@ -463,7 +460,7 @@ impl<'a> RenderHtml for Cow<'a, str> {
     }
 
     fn html_len(&self) -> usize {
-        self.len()
+        escaped_text_len(self)
     }
 
     fn to_html_with_buf(
@@ -471,7 +468,7 @@ impl<'a> RenderHtml for Cow<'a, str> {
         buf: &mut String,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) {
         <&str as RenderHtml>::to_html_with_buf(
             &self,
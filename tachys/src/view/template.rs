@@ -2,7 +2,10 @@ use super::{
     add_attr::AddAnyAttr, Mountable, Position, PositionState, Render,
     RenderHtml, ToTemplate,
 };
-use crate::{html::attribute::Attribute, hydration::Cursor, renderer::Rndr};
+use crate::{
+    html::attribute::Attribute, hydration::Cursor, renderer::Rndr,
+    ssr::BranchMarks,
+};
 
 /// A view wrapper that uses a `<template>` node to optimize DOM node creation.
 ///
@@ -80,7 +83,7 @@ where
         buf: &mut String,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) {
         self.view
             .to_html_with_buf(buf, position, escape, mark_branches)
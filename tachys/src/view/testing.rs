@@ -0,0 +1,168 @@
+use super::{
+    add_attr::AddAnyAttr, Position, PositionState, Render, RenderHtml,
+};
+use crate::{
+    html::attribute::Attribute,
+    hydration::Cursor,
+    ssr::{BranchMarks, StreamBuilder},
+};
+use std::sync::{Arc, Mutex};
+
+/// Renders a view to HTML, recording how the [`Position`] changes across the views traced with
+/// the provided [`PositionLog`].
+///
+/// This is useful when writing a custom view type that manages its own nodes or markers, to
+/// check that it leaves the position in the state its siblings expect:
+/// ```rust,ignore
+/// let (html, transitions) = position_transitions(|log| {
+///     div().child(("a", log.trace(MyView::new()), "b"))
+/// });
+/// assert_eq!(
+///     transitions,
+///     [(Position::NextChildAfterText, Position::NextChild)]
+/// );
+/// ```
+pub fn position_transitions<V>(
+    view: impl FnOnce(&PositionLog) -> V,
+) -> (String, Vec<(Position, Position)>)
+where
+    V: RenderHtml,
+{
+    let log = PositionLog::default();
+    let html = view(&log).to_html();
+    (html, log.transitions())
+}
+
+/// A shared log of the positions before and after rendering each traced view.
+#[derive(Debug, Clone, Default)]
+pub struct PositionLog(Arc<Mutex<Vec<(Position, Position)>>>);
+
+impl PositionLog {
+    /// Wraps a view so that its position transitions are recorded in this log.
+    pub fn trace<V>(&self, view: V) -> Traced<V> {
+        Traced {
+            view,
+            log: self.clone(),
+        }
+    }
+
+    /// Returns the `(before, after)` positions recorded so far, in rendering order.
+    pub fn transitions(&self) -> Vec<(Position, Position)> {
+        self.0.lock().expect("lock poisoned").clone()
+    }
+
+    fn push(&self, before: Position, after: Position) {
+        self.0.lock().expect("lock poisoned").push((before, after));
+    }
+}
+
+/// A view whose position transitions are recorded in a [`PositionLog`].
+///
+/// This is created with [`PositionLog::trace`].
+#[derive(Debug, Clone)]
+pub struct Traced<V> {
+    view: V,
+    log: PositionLog,
+}
+
+impl<V> Render for Traced<V>
+where
+    V: Render,
+{
+    type State = V::State;
+
+    fn build(self) -> Self::State {
+        self.view.build()
+    }
+
+    fn rebuild(self, state: &mut Self::State) {
+        self.view.rebuild(state)
+    }
+}
+
+impl<V> AddAnyAttr for Traced<V>
+where
+    V: RenderHtml,
+{
+    type Output<SomeNewAttr: Attribute> = Traced<V::Output<SomeNewAttr>>;
+
+    fn add_any_attr<NewAttr: Attribute>(
+        self,
+        attr: NewAttr,
+    ) -> Self::Output<NewAttr>
+    where
+        Self::Output<NewAttr>: RenderHtml,
+    {
+        Traced {
+            view: self.view.add_any_attr(attr),
+            log: self.log,
+        }
+    }
+}
+
+impl<V> RenderHtml for Traced<V>
+where
+    V: RenderHtml,
+{
+    type AsyncOutput = Traced<V::AsyncOutput>;
+
+    const MIN_LENGTH: usize = V::MIN_LENGTH;
+
+    fn dry_resolve(&mut self) {
+        self.view.dry_resolve();
+    }
+
+    async fn resolve(self) -> Self::AsyncOutput {
+        Traced {
+            view: self.view.resolve().await,
+            log: self.log,
+        }
+    }
+
+    fn html_len(&self) -> usize {
+        self.view.html_len()
+    }
+
+    fn to_html_with_buf(
+        self,
+        buf: &mut String,
+        position: &mut Position,
+        escape: bool,
+        mark_branches: &BranchMarks,
+    ) {
+        let before = *position;
+        self.view
+            .to_html_with_buf(buf, position, escape, mark_branches);
+        self.log.push(before, *position);
+    }
+
+    fn to_html_async_with_buf<const OUT_OF_ORDER: bool>(
+        self,
+        buf: &mut StreamBuilder,
+        position: &mut Position,
+        escape: bool,
+        mark_branches: &BranchMarks,
+    ) where
+        Self: Sized,
+    {
+        let before = *position;
+        self.view.to_html_async_with_buf::<OUT_OF_ORDER>(
+            buf,
+            position,
+            escape,
+            mark_branches,
+        );
+        self.log.push(before, *position);
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        cursor: &Cursor,
+        position: &PositionState,
+    ) -> Self::State {
+        let before = position.get();
+        let state = self.view.hydrate::<FROM_SERVER>(cursor, position);
+        self.log.push(before, position.get());
+        state
+    }
+}
@@ -5,6 +5,7 @@ use crate::{
     html::attribute::Attribute,
     hydration::Cursor,
     renderer::Rndr,
+    ssr::BranchMarks,
     view::{add_attr::AddAnyAttr, StreamBuilder},
 };
 use const_str_slice_concat::{
@@ -32,7 +33,7 @@ impl RenderHtml for () {
         buf: &mut String,
         position: &mut Position,
         escape: bool,
-        _mark_branches: bool,
+        _mark_branches: &BranchMarks,
     ) {
         if escape {
             buf.push_str("<!>");
@@ -124,7 +125,7 @@ where
         buf: &mut String,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) {
         self.0
             .to_html_with_buf(buf, position, escape, mark_branches);
@@ -135,7 +136,7 @@ where
         buf: &mut StreamBuilder,
         position: &mut Position,
         escape: bool,
-        mark_branches: bool,
+        mark_branches: &BranchMarks,
     ) where
         Self: Sized,
     {
@@ -244,7 +245,7 @@ macro_rules! impl_view_for_tuples {
                 $($ty.html_len() +)* $first.html_len()
             }
 
-			fn to_html_with_buf(self, buf: &mut String, position: &mut Position, escape: bool, mark_branches: bool) {
+			fn to_html_with_buf(self, buf: &mut String, position: &mut Position, escape: bool, mark_branches: &BranchMarks) {
                 #[allow(non_snake_case)]
                 let ($first, $($ty,)* ) = self;
                 $first.to_html_with_buf(buf, position, escape, mark_branches);
@@ -253,7 +254,7 @@ macro_rules! impl_view_for_tuples {
 
 			fn to_html_async_with_buf<const OUT_OF_ORDER: bool>(
 				self,
-				buf: &mut StreamBuilder, position: &mut Position, escape: bool, mark_branches: bool) where
+				buf: &mut StreamBuilder, position: &mut Position, escape: bool, mark_branches: &BranchMarks) where
 				Self: Sized,
 			{
                 #[allow(non_snake_case)]
@@ -406,3 +407,27 @@ impl_view_for_tuples!(
     A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y,
     Z
 );
+impl_view_for_tuples!(
+    A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y,
+    Z, AA
+);
+impl_view_for_tuples!(
+    A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y,
+    Z, AA, AB
+);
+impl_view_for_tuples!(
+    A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y,
+    Z, AA, AB, AC
+);
+impl_view_for_tuples!(
+    A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y,
+    Z, AA, AB, AC, AD
+);
+impl_view_for_tuples!(
+    A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y,
+    Z, AA, AB, AC, AD, AE
+);
+impl_view_for_tuples!(
+    A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y,
+    Z, AA, AB, AC, AD, AE, AF
+);
@@ -0,0 +1,91 @@
+//! Checks that reactive attributes with keys known at compile time keep the key without
+//! allocating a copy of it.
+#![cfg(all(feature = "testing", feature = "reactive_graph"))]
+
+use any_spawner::Executor;
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+use tachys::{
+    html::{attribute::custom::CustomAttribute, element::div},
+    view::Render,
+};
+
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        _ = ALLOCATIONS.try_with(|n| n.set(n.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations_during<T>(f: impl FnOnce() -> T) -> (usize, T) {
+    let before = ALLOCATIONS.get();
+    let value = f();
+    (ALLOCATIONS.get() - before, value)
+}
+
+const ELEMENTS: usize = 10_000;
+const KEYS: [&str; 5] = ["data-a", "data-b", "data-c", "data-d", "data-e"];
+
+#[test]
+fn static_attribute_keys_are_not_copied() {
+    _ = Executor::init_manual();
+    // builds one element first, so that one-time setup (like the mock DOM) isn't counted
+    let _warm_up = div().attr(KEYS[0], || "value").build();
+
+    let (static_keys, _static_views) = allocations_during(|| {
+        (0..ELEMENTS)
+            .map(|_| {
+                div()
+                    .attr(KEYS[0], || "value")
+                    .attr(KEYS[1], || "value")
+                    .attr(KEYS[2], || "value")
+                    .attr(KEYS[3], || "value")
+                    .attr(KEYS[4], || "value")
+                    .build()
+            })
+            .collect::<Vec<_>>()
+    });
+
+    // the dynamic keys are created before counting, so only the copies made while building are
+    // counted
+    let keys = (0..ELEMENTS)
+        .map(|_| KEYS.map(String::from))
+        .collect::<Vec<_>>();
+    let (dynamic_keys, _dynamic_views) = allocations_during(|| {
+        keys.into_iter()
+            .map(|[a, b, c, d, e]| {
+                div()
+                    .attr(a, || "value")
+                    .attr(b, || "value")
+                    .attr(c, || "value")
+                    .attr(d, || "value")
+                    .attr(e, || "value")
+                    .build()
+            })
+            .collect::<Vec<_>>()
+    });
+
+    // one copy of each dynamic key is kept by the attribute's effect; comparing per element
+    // leaves out the few times the mock DOM's storage grows
+    assert_eq!(
+        dynamic_keys / ELEMENTS - static_keys / ELEMENTS,
+        KEYS.len(),
+        "static keys: {static_keys} allocations, dynamic keys: \
+         {dynamic_keys} allocations"
+    );
+}
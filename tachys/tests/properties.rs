@@ -0,0 +1,85 @@
+//! Binds reactive DOM properties on a custom element in the browser, and reads them back from the
+//! element. Properties are JavaScript values, which the mock DOM can't hold, so these only run
+//! in a browser:
+//!
+//! ```sh
+//! wasm-pack test --headless --chrome tachys --features reactive_graph,serde
+//! ```
+#![cfg(all(
+    target_arch = "wasm32",
+    not(any(feature = "testing", feature = "sledgehammer")),
+    feature = "reactive_graph"
+))]
+
+use any_spawner::Executor;
+use reactive_graph::{
+    owner::Owner,
+    signal::RwSignal,
+    traits::{Get, Set},
+};
+use tachys::{
+    html::{
+        element::custom,
+        property::{prop, JsProperty},
+    },
+    view::{add_attr::AddAnyAttr, Render},
+};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn property(el: &web_sys::Element, key: &str) -> JsValue {
+    js_sys::Reflect::get(el, &JsValue::from_str(key)).unwrap()
+}
+
+fn run(test: impl FnOnce()) {
+    _ = Executor::init_manual();
+    let owner = Owner::new();
+    owner.set();
+    test();
+}
+
+#[wasm_bindgen_test]
+fn js_property_updates_and_clears() {
+    run(|| {
+        let value = RwSignal::new(Some(1.5));
+        let el = custom("my-gauge")
+            .add_any_attr(prop("value", move || JsProperty(value.get())))
+            .build();
+        assert_eq!(property(&el, "value").as_f64(), Some(1.5));
+
+        value.set(Some(2.5));
+        Executor::flush();
+        assert_eq!(property(&el, "value").as_f64(), Some(2.5));
+
+        value.set(None);
+        Executor::flush();
+        assert!(property(&el, "value").is_undefined());
+        // properties aren't reflected as attributes
+        assert_eq!(el.outer_html(), "<my-gauge></my-gauge>");
+    });
+}
+
+#[cfg(feature = "serde")]
+#[wasm_bindgen_test]
+fn serde_property_updates_object_value() {
+    use tachys::html::property::prop_serde;
+
+    run(|| {
+        let points = RwSignal::new(vec![1, 2]);
+        let el = custom("my-chart")
+            .add_any_attr(prop("points", move || prop_serde(points.get())))
+            .build();
+        let json = |el: &web_sys::Element| {
+            js_sys::JSON::stringify(&property(el, "points"))
+                .unwrap()
+                .as_string()
+        };
+        assert_eq!(json(&el).as_deref(), Some("[1,2]"));
+
+        points.set(vec![3, 4, 5]);
+        Executor::flush();
+        assert_eq!(json(&el).as_deref(), Some("[3,4,5]"));
+    });
+}
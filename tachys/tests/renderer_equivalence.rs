@@ -0,0 +1,255 @@
+//! Renders the same views with whichever DOM renderer is enabled, and checks the resulting
+//! `outerHTML`, so that the default renderer and the `sledgehammer` renderer can be compared by
+//! running these tests once with each:
+//!
+//! ```sh
+//! wasm-pack test --headless --chrome tachys
+//! wasm-pack test --headless --chrome tachys --features sledgehammer
+//! ```
+//!
+//! `cargo make test-renderers` in this crate runs both, as CI does.
+#![cfg(all(target_arch = "wasm32", not(feature = "testing")))]
+
+use std::{cell::Cell, rc::Rc};
+use tachys::{
+    dom::{body, document},
+    html::{
+        attribute::global::{ClassAttribute, GlobalAttributes},
+        element::{button, em, li, p, ul, ElementChild},
+    },
+    renderer::{types::Element, CastFrom, Rndr},
+    view::{Mountable, Render},
+};
+#[cfg(not(feature = "sledgehammer"))]
+use wasm_bindgen::prelude::wasm_bindgen;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+// creates an empty `<section>` in the document, as both a `web_sys` element (to read the result)
+// and a renderer element (to mount into)
+fn container() -> (web_sys::Element, Element) {
+    let section = document().create_element("section").unwrap();
+    body().append_child(&section).unwrap();
+    #[cfg(not(feature = "sledgehammer"))]
+    let el = section.clone();
+    #[cfg(feature = "sledgehammer")]
+    let el = Rndr::store_element(&section);
+    (section, el)
+}
+
+fn outer_html(el: &web_sys::Element) -> String {
+    #[cfg(feature = "sledgehammer")]
+    tachys::renderer::sledgehammer::flush_sync();
+    el.outer_html()
+}
+
+#[wasm_bindgen_test]
+fn renders_nested_elements() {
+    let (section, parent) = container();
+    let mut view = ul()
+        .class("list")
+        .child((
+            li().id("first").child("one"),
+            li().child(("two, ", em().child("emphasized"), " three")),
+        ))
+        .build();
+    view.mount(&parent, None);
+    assert_eq!(
+        outer_html(&section),
+        "<section><ul class=\"list\"><li id=\"first\">one</li><li>two, \
+         <em>emphasized</em> three</li></ul></section>"
+    );
+
+    view.unmount();
+    assert_eq!(outer_html(&section), "<section></section>");
+}
+
+#[wasm_bindgen_test]
+fn applies_updates_in_order() {
+    let (section, parent) = container();
+    let el = Rndr::create_element("p", None);
+    let text = Rndr::create_text_node("before");
+    Rndr::insert_node(&el, text.as_ref(), None);
+    Rndr::insert_node(&parent, el.as_ref(), None);
+
+    Rndr::set_attribute(&el, "title", "a");
+    Rndr::set_attribute(&el, "title", "b");
+    Rndr::set_attribute(&el, "lang", "en");
+    Rndr::remove_attribute(&el, "lang");
+    Rndr::set_class_tokens(&el, &["a", "b"], &[]);
+    Rndr::set_class_tokens(&el, &["c"], &["a"]);
+    Rndr::set_style_properties(&el, &[("color", "red")], &[]);
+    Rndr::set_text(&text, "after");
+
+    let placeholder = Rndr::create_placeholder();
+    Rndr::insert_node(&parent, placeholder.as_ref(), Some(el.as_ref()));
+
+    assert_eq!(
+        outer_html(&section),
+        "<section><!----><p title=\"b\" class=\"b c\" \
+         style=\"color: red;\">after</p></section>"
+    );
+}
+
+#[wasm_bindgen_test]
+fn reading_back_sees_queued_updates() {
+    let (_section, parent) = container();
+    let mut view = p().child("hello").build();
+    view.mount(&parent, None);
+
+    let child = Rndr::first_child(parent.as_ref()).unwrap();
+    let child = Element::cast_from(child).unwrap();
+    assert_eq!(Rndr::text_content(child.as_ref()).as_deref(), Some("hello"));
+    assert_eq!(Rndr::describe_node(child.as_ref()), "p");
+}
+
+#[wasm_bindgen_test]
+fn calls_event_listeners() {
+    let (section, parent) = container();
+    let clicks = Rc::new(Cell::new(0));
+    let mut view = button().child("click").build();
+    view.mount(&parent, None);
+    let el = Element::cast_from(Rndr::first_child(parent.as_ref()).unwrap())
+        .unwrap();
+    let _remove = Rndr::add_event_listener(&el, "click", {
+        let clicks = Rc::clone(&clicks);
+        Box::new(move |_| clicks.set(clicks.get() + 1))
+    });
+
+    let button = section.first_element_child().unwrap();
+    let button = button.unchecked_into::<web_sys::HtmlElement>();
+    outer_html(&section);
+    button.click();
+    assert_eq!(clicks.get(), 1);
+}
+
+// applies the same changes to one element in batches and to another one property at a time
+#[wasm_bindgen_test]
+fn batched_styles_match_single_properties() {
+    let (section, parent) = container();
+    let batched = Rndr::create_element("p", None);
+    let single = Rndr::create_element("p", None);
+    Rndr::insert_node(&parent, batched.as_ref(), None);
+    Rndr::insert_node(&parent, single.as_ref(), None);
+
+    let image = "url(\"data:image/svg+xml;base64,PHN2Zz48L3N2Zz4=\")";
+    let steps: [(&[(&str, &str)], &[&str]); 2] = [
+        (
+            &[
+                ("margin-top", "1px"),
+                ("margin-right", "2px"),
+                ("margin-bottom", "3px"),
+                ("margin-left", "4px"),
+                ("background-image", image),
+            ],
+            &[],
+        ),
+        (
+            &[
+                ("color", "red"),
+                ("padding-top", "1px"),
+                ("padding-left", "2px"),
+                ("padding-right", "3px"),
+            ],
+            &["margin-top"],
+        ),
+    ];
+    for (set, remove) in steps {
+        Rndr::set_style_properties(&batched, set, remove);
+        let style = Rndr::style(&single);
+        for name in remove {
+            Rndr::remove_css_property(&style, name);
+        }
+        for (name, value) in set {
+            Rndr::set_css_property(&style, name, value);
+        }
+    }
+
+    outer_html(&section);
+    let batched = section.first_element_child().unwrap();
+    let single = batched.next_element_sibling().unwrap();
+    let style = |el: &web_sys::Element| {
+        el.unchecked_ref::<web_sys::HtmlElement>().style()
+    };
+    assert_eq!(style(&batched).css_text(), style(&single).css_text());
+    assert_eq!(
+        style(&batched).get_property_value("margin-top").unwrap(),
+        ""
+    );
+    assert_eq!(
+        style(&batched).get_property_value("margin-left").unwrap(),
+        "4px"
+    );
+    assert!(style(&batched)
+        .get_property_value("background-image")
+        .unwrap()
+        .contains("svg+xml;base64"));
+}
+
+#[cfg(not(feature = "sledgehammer"))]
+#[wasm_bindgen(inline_js = "
+    const counted = [
+        [DOMTokenList.prototype, 'add'],
+        [DOMTokenList.prototype, 'remove'],
+        [Element.prototype, 'getAttribute'],
+        [Element.prototype, 'setAttribute'],
+    ];
+    let calls = 0;
+
+    export function count_class_calls(f) {
+        const originals = counted.map(([proto, name]) => proto[name]);
+        counted.forEach(([proto, name], i) => {
+            proto[name] = function (...args) {
+                calls += 1;
+                return originals[i].apply(this, args);
+            };
+        });
+        calls = 0;
+        try {
+            f();
+        } finally {
+            counted.forEach(([proto, name], i) => proto[name] = originals[i]);
+        }
+        return calls;
+    }
+")]
+extern "C" {
+    // the number of `classList.add()`, `classList.remove()`, `getAttribute()` and
+    // `setAttribute()` calls made while running `f`
+    fn count_class_calls(f: &dyn Fn()) -> u32;
+}
+
+// measures the calls into JavaScript that swapping a set of class names takes, with and without
+// batching them
+#[cfg(not(feature = "sledgehammer"))]
+#[wasm_bindgen_test]
+fn batched_classes_make_fewer_calls() {
+    let (_section, parent) = container();
+    let el = Rndr::create_element("p", None);
+    Rndr::insert_node(&parent, el.as_ref(), None);
+    let before = ["a", "b", "c", "d", "e", "f"];
+    let after = ["g", "h", "i", "j", "k", "l"];
+    Rndr::set_class_tokens(&el, &before, &[]);
+
+    let batched = count_class_calls(&|| {
+        Rndr::set_class_tokens(&el, &after, &before);
+    });
+    assert_eq!(el.get_attribute("class").as_deref(), Some("g h i j k l"));
+
+    let single = count_class_calls(&|| {
+        let class_list = Rndr::class_list(&el);
+        for name in after {
+            Rndr::remove_class(&class_list, name);
+        }
+        for name in before {
+            Rndr::add_class(&class_list, name);
+        }
+    });
+    assert_eq!(el.get_attribute("class").as_deref(), Some("a b c d e f"));
+
+    // one `getAttribute()` and one `setAttribute()`, instead of one call per class name
+    assert_eq!(batched, 2);
+    assert_eq!(single, 12);
+}